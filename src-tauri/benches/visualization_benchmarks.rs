@@ -0,0 +1,119 @@
+// Criterion benchmarks for chart data downsampling
+//
+// These benchmarks measure:
+// - LTTB downsampling of long series on its own
+// - get_mood_chart_data for all-time ranges with and without max_points
+// - Datasets sized like years of several daily check-ins (up to 20,000 points)
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+use std::sync::Arc;
+use tauri_sveltekit_modern_lib::db::Database;
+use tauri_sveltekit_modern_lib::features::visualization::downsample::downsample_lttb;
+use tauri_sveltekit_modern_lib::features::visualization::models::{
+    ChartDataPoint, ChartQueryOptions,
+};
+use tauri_sveltekit_modern_lib::features::visualization::repository::VisualizationRepository;
+use tempfile::TempDir;
+
+/// Four check-ins a day, oldest first, ending now
+fn checkin_times(num_checkins: usize) -> Vec<String> {
+    let now = chrono::Utc::now().naive_utc();
+    (0..num_checkins)
+        .rev()
+        .map(|i| {
+            (now - chrono::Duration::hours(6 * i as i64))
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string()
+        })
+        .collect()
+}
+
+/// Helper to create a database with `num_checkins` mood check-ins
+/// Returns (repo, temp_dir)
+fn setup_mood_data(num_checkins: usize) -> (VisualizationRepository, TempDir) {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let db =
+        Arc::new(Database::new(temp_dir.path().to_path_buf()).expect("Failed to create database"));
+
+    {
+        let conn = db.get_connection();
+        let mut conn = conn.lock();
+        let tx = conn.transaction().expect("Failed to start transaction");
+        {
+            let mut stmt = tx
+                .prepare("INSERT INTO mood_checkins (mood_rating, created_at) VALUES (?, ?)")
+                .expect("Failed to prepare insert");
+            for (i, created_at) in checkin_times(num_checkins).iter().enumerate() {
+                stmt.execute(rusqlite::params![1 + (i * 7 / 5) % 7, created_at])
+                    .expect("Failed to insert check-in");
+            }
+        }
+        tx.commit().expect("Failed to commit check-ins");
+    }
+
+    (VisualizationRepository::new(db), temp_dir)
+}
+
+fn bench_lttb_scaling(c: &mut Criterion) {
+    let mut group = c.benchmark_group("lttb_scaling");
+
+    for size in [1_000, 10_000, 100_000].iter() {
+        let points: Vec<ChartDataPoint> = checkin_times(*size)
+            .into_iter()
+            .enumerate()
+            .map(|(i, timestamp)| ChartDataPoint {
+                timestamp,
+                value: 4.0 + 3.0 * (i as f64 / 40.0).sin(),
+                label: None,
+            })
+            .collect();
+
+        group.bench_with_input(BenchmarkId::from_parameter(size), size, |b, &_size| {
+            b.iter(|| {
+                black_box(downsample_lttb(&points, 500));
+            });
+        });
+    }
+
+    group.finish();
+}
+
+fn bench_mood_chart_all_time(c: &mut Criterion) {
+    let mut group = c.benchmark_group("mood_chart_all_time");
+    group.sample_size(20);
+
+    for size in [1_000, 5_000, 20_000].iter() {
+        let (repo, _temp_dir) = setup_mood_data(*size);
+
+        group.bench_with_input(BenchmarkId::new("full", size), size, |b, &_size| {
+            b.iter(|| {
+                black_box(
+                    repo.get_mood_chart_data(None, None, false)
+                        .expect("Failed to get mood chart data"),
+                );
+            });
+        });
+
+        group.bench_with_input(BenchmarkId::new("max_500", size), size, |b, &_size| {
+            b.iter(|| {
+                black_box(
+                    repo.get_mood_chart_data_with_options(
+                        None,
+                        None,
+                        false,
+                        &ChartQueryOptions {
+                            max_points: Some(500),
+                            ..Default::default()
+                        },
+                    )
+                    .expect("Failed to get mood chart data"),
+                );
+            });
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_lttb_scaling, bench_mood_chart_all_time);
+criterion_main!(benches);
@@ -11,6 +11,34 @@ pub struct AppConfig {
 
     /// Data retention in days (None = keep forever)
     pub data_retention_days: Option<u32>,
+
+    /// Rest days allowed between active days when evaluating streak milestones
+    #[serde(default = "default_streak_grace_days")]
+    pub streak_grace_days: u32,
+
+    /// Do-not-disturb windows for reminder notifications
+    #[serde(default)]
+    pub quiet_hours: Vec<QuietHoursWindow>,
+
+    /// Whether notifications due in quiet time are deferred or dropped
+    #[serde(default)]
+    pub quiet_time_policy: QuietTimePolicy,
+
+    /// All reminders are paused until this RFC 3339 time
+    #[serde(default)]
+    pub reminders_paused_until: Option<String>,
+
+    /// Announce each week's insight digest with a notification
+    #[serde(default = "default_weekly_digest_notification")]
+    pub weekly_digest_notification: bool,
+}
+
+fn default_streak_grace_days() -> u32 {
+    1
+}
+
+fn default_weekly_digest_notification() -> bool {
+    true
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
@@ -20,12 +48,45 @@ pub enum Theme {
     Dark,
 }
 
+/// A do-not-disturb window starting on one weekday
+#[derive(Serialize, Deserialize, specta::Type, Clone, Debug, PartialEq)]
+pub struct QuietHoursWindow {
+    pub weekday: i32,  // 0-6 (Sunday-Saturday), the day the window starts
+    pub start: String, // HH:MM local time
+    pub end: String,   // HH:MM; at or before `start` means the next day
+}
+
+/// What happens to notifications that fall due during quiet time
+#[derive(Serialize, Deserialize, specta::Type, Clone, Copy, Debug, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum QuietTimePolicy {
+    /// Hold them and send them when quiet time ends
+    #[default]
+    Defer,
+    /// Don't send them; schedules move on to their next occurrence
+    Drop,
+}
+
+/// Notification preferences, as stored in `AppConfig`
+#[derive(Serialize, Deserialize, specta::Type, Clone, Debug, PartialEq)]
+pub struct NotificationSettings {
+    pub notification_enabled: bool,
+    pub quiet_hours: Vec<QuietHoursWindow>,
+    pub quiet_time_policy: QuietTimePolicy,
+    pub paused_until: Option<String>, // RFC 3339; reminders are paused until then
+}
+
 impl Default for AppConfig {
     fn default() -> Self {
         Self {
             theme: Theme::Light,
             notification_enabled: true,
             data_retention_days: None, // Keep all data by default
+            streak_grace_days: default_streak_grace_days(),
+            quiet_hours: Vec::new(),
+            quiet_time_policy: QuietTimePolicy::default(),
+            reminders_paused_until: None,
+            weekly_digest_notification: default_weekly_digest_notification(),
         }
     }
 }
@@ -42,4 +103,22 @@ impl AppConfig {
         confy::store("mental-health-tracker", "config", self)?;
         Ok(())
     }
+
+    /// The notification-related settings
+    pub fn notification_settings(&self) -> NotificationSettings {
+        NotificationSettings {
+            notification_enabled: self.notification_enabled,
+            quiet_hours: self.quiet_hours.clone(),
+            quiet_time_policy: self.quiet_time_policy,
+            paused_until: self.reminders_paused_until.clone(),
+        }
+    }
+
+    /// Replace the notification-related settings
+    pub fn set_notification_settings(&mut self, settings: NotificationSettings) {
+        self.notification_enabled = settings.notification_enabled;
+        self.quiet_hours = settings.quiet_hours;
+        self.quiet_time_policy = settings.quiet_time_policy;
+        self.reminders_paused_until = settings.paused_until;
+    }
 }
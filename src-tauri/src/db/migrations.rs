@@ -57,6 +57,78 @@ pub fn run_migrations(db: &Database) -> Result<()> {
         info!("Applied migration 006: Add partial unique constraint for drafts");
     }
 
+    if current_version < 7 {
+        apply_migration_007(db)?;
+        record_migration(db, 7)?;
+        info!("Applied migration 007: Add assessment history index");
+    }
+
+    if current_version < 8 {
+        apply_migration_008(db)?;
+        record_migration(db, 8)?;
+        info!("Applied migration 008: Streak milestones");
+    }
+
+    if current_version < 9 {
+        apply_migration_009(db)?;
+        record_migration(db, 9)?;
+        info!("Applied migration 009: Planned activities");
+    }
+
+    if current_version < 10 {
+        apply_migration_010(db)?;
+        record_migration(db, 10)?;
+        info!("Applied migration 010: Group ordering, archiving and appearance");
+    }
+
+    if current_version < 11 {
+        apply_migration_011(db)?;
+        record_migration(db, 11)?;
+        info!("Applied migration 011: Goal templates");
+    }
+
+    if current_version < 12 {
+        apply_migration_012(db)?;
+        record_migration(db, 12)?;
+        info!("Applied migration 012: Schedule timezones");
+    }
+
+    if current_version < 13 {
+        apply_migration_013(db)?;
+        record_migration(db, 13)?;
+        info!("Applied migration 013: Schedule targets");
+    }
+
+    if current_version < 14 {
+        apply_migration_014(db)?;
+        record_migration(db, 14)?;
+        info!("Applied migration 014: Schedule recurrence rules");
+    }
+
+    if current_version < 15 {
+        apply_migration_015(db)?;
+        record_migration(db, 15)?;
+        info!("Applied migration 015: Reminder events");
+    }
+
+    if current_version < 16 {
+        apply_migration_016(db)?;
+        record_migration(db, 16)?;
+        info!("Applied migration 016: Missed reminders");
+    }
+
+    if current_version < 17 {
+        apply_migration_017(db)?;
+        record_migration(db, 17)?;
+        info!("Applied migration 017: Adaptive schedule frequency");
+    }
+
+    if current_version < 18 {
+        apply_migration_018(db)?;
+        record_migration(db, 18)?;
+        info!("Applied migration 018: Weekly insight digests");
+    }
+
     info!("All migrations applied successfully");
     Ok(())
 }
@@ -228,3 +300,266 @@ fn apply_migration_006(db: &Database) -> Result<()> {
 
     Ok(())
 }
+
+fn apply_migration_007(db: &Database) -> Result<()> {
+    let schema_sql = include_str!("migrations/007_history_query_index.sql");
+
+    let conn = db.get_connection();
+    let mut conn = conn.lock();
+
+    // Wrap migration in explicit transaction for atomicity
+    let tx = conn
+        .transaction()
+        .context("Failed to begin transaction for migration 007")?;
+
+    tx.execute_batch(schema_sql)
+        .context("Failed to execute migration 007 DDL statements")?;
+
+    tx.commit()
+        .context("Failed to commit migration 007 transaction")?;
+
+    Ok(())
+}
+
+fn apply_migration_008(db: &Database) -> Result<()> {
+    let schema_sql = include_str!("migrations/008_streak_milestones.sql");
+
+    let conn = db.get_connection();
+    let mut conn = conn.lock();
+
+    // Wrap migration in explicit transaction for atomicity
+    let tx = conn
+        .transaction()
+        .context("Failed to begin transaction for migration 008")?;
+
+    tx.execute_batch(schema_sql)
+        .context("Failed to execute migration 008 DDL statements")?;
+
+    tx.commit()
+        .context("Failed to commit migration 008 transaction")?;
+
+    Ok(())
+}
+
+fn apply_migration_009(db: &Database) -> Result<()> {
+    let schema_sql = include_str!("migrations/009_planned_activities.sql");
+
+    let conn = db.get_connection();
+    let mut conn = conn.lock();
+
+    // Wrap migration in explicit transaction for atomicity
+    let tx = conn
+        .transaction()
+        .context("Failed to begin transaction for migration 009")?;
+
+    tx.execute_batch(schema_sql)
+        .context("Failed to execute migration 009 DDL statements")?;
+
+    tx.commit()
+        .context("Failed to commit migration 009 transaction")?;
+
+    Ok(())
+}
+
+fn apply_migration_010(db: &Database) -> Result<()> {
+    let schema_sql = include_str!("migrations/010_group_ordering_archiving.sql");
+
+    let conn = db.get_connection();
+    let mut conn = conn.lock();
+
+    // Wrap migration in explicit transaction for atomicity
+    let tx = conn
+        .transaction()
+        .context("Failed to begin transaction for migration 010")?;
+
+    tx.execute_batch(schema_sql)
+        .context("Failed to execute migration 010 DDL statements")?;
+
+    tx.commit()
+        .context("Failed to commit migration 010 transaction")?;
+
+    Ok(())
+}
+
+fn apply_migration_011(db: &Database) -> Result<()> {
+    let schema_sql = include_str!("migrations/011_goal_templates.sql");
+
+    let conn = db.get_connection();
+    let mut conn = conn.lock();
+
+    // Wrap migration in explicit transaction for atomicity
+    let tx = conn
+        .transaction()
+        .context("Failed to begin transaction for migration 011")?;
+
+    tx.execute_batch(schema_sql)
+        .context("Failed to execute migration 011 DDL statements")?;
+
+    tx.commit()
+        .context("Failed to commit migration 011 transaction")?;
+
+    Ok(())
+}
+
+fn apply_migration_012(db: &Database) -> Result<()> {
+    let schema_sql = include_str!("migrations/012_schedule_timezone.sql");
+
+    let conn = db.get_connection();
+    let mut conn = conn.lock();
+
+    // Wrap migration in explicit transaction for atomicity
+    let tx = conn
+        .transaction()
+        .context("Failed to begin transaction for migration 012")?;
+
+    tx.execute_batch(schema_sql)
+        .context("Failed to execute migration 012 DDL statements")?;
+
+    tx.commit()
+        .context("Failed to commit migration 012 transaction")?;
+
+    Ok(())
+}
+
+/// Migration 013: Schedule targets
+///
+/// Recreates assessment_schedules so assessment_type_id can be NULL for
+/// mood, activity and habit reminders. No table references assessment_schedules,
+/// so unlike 003/004 foreign keys can stay on and the migration runs in a transaction.
+fn apply_migration_013(db: &Database) -> Result<()> {
+    let schema_sql = include_str!("migrations/013_schedule_targets.sql");
+
+    let conn = db.get_connection();
+    let mut conn = conn.lock();
+
+    // Wrap migration in explicit transaction for atomicity
+    let tx = conn
+        .transaction()
+        .context("Failed to begin transaction for migration 013")?;
+
+    tx.execute_batch(schema_sql)
+        .context("Failed to execute migration 013 DDL statements")?;
+
+    tx.commit()
+        .context("Failed to commit migration 013 transaction")?;
+
+    Ok(())
+}
+
+/// Migration 014: Schedule recurrence rules
+///
+/// Recreates assessment_schedules with rrule/dtstart/exdates columns, backfilled
+/// with rules equivalent to each schedule's frequency, and allows 'custom'.
+/// Transactional for the same reason as 013.
+fn apply_migration_014(db: &Database) -> Result<()> {
+    let schema_sql = include_str!("migrations/014_schedule_rrule.sql");
+
+    let conn = db.get_connection();
+    let mut conn = conn.lock();
+
+    // Wrap migration in explicit transaction for atomicity
+    let tx = conn
+        .transaction()
+        .context("Failed to begin transaction for migration 014")?;
+
+    tx.execute_batch(schema_sql)
+        .context("Failed to execute migration 014 DDL statements")?;
+
+    tx.commit()
+        .context("Failed to commit migration 014 transaction")?;
+
+    Ok(())
+}
+
+/// Migration 015: Reminder events
+///
+/// Adds the reminder_events log of fired, snoozed, dismissed and completed reminders.
+fn apply_migration_015(db: &Database) -> Result<()> {
+    let schema_sql = include_str!("migrations/015_reminder_events.sql");
+
+    let conn = db.get_connection();
+    let mut conn = conn.lock();
+
+    // Wrap migration in explicit transaction for atomicity
+    let tx = conn
+        .transaction()
+        .context("Failed to begin transaction for migration 015")?;
+
+    tx.execute_batch(schema_sql)
+        .context("Failed to execute migration 015 DDL statements")?;
+
+    tx.commit()
+        .context("Failed to commit migration 015 transaction")?;
+
+    Ok(())
+}
+
+/// Migration 016: Missed reminders
+///
+/// Adds assessment_schedules.missed_policy and the missed_reminders table.
+fn apply_migration_016(db: &Database) -> Result<()> {
+    let schema_sql = include_str!("migrations/016_missed_reminders.sql");
+
+    let conn = db.get_connection();
+    let mut conn = conn.lock();
+
+    // Wrap migration in explicit transaction for atomicity
+    let tx = conn
+        .transaction()
+        .context("Failed to begin transaction for migration 016")?;
+
+    tx.execute_batch(schema_sql)
+        .context("Failed to execute migration 016 DDL statements")?;
+
+    tx.commit()
+        .context("Failed to commit migration 016 transaction")?;
+
+    Ok(())
+}
+
+/// Migration 017: Adaptive schedule frequency
+///
+/// Adds severity-driven frequency rules per schedule and a log of the changes
+/// they make.
+fn apply_migration_017(db: &Database) -> Result<()> {
+    let schema_sql = include_str!("migrations/017_adaptive_schedules.sql");
+
+    let conn = db.get_connection();
+    let mut conn = conn.lock();
+
+    // Wrap migration in explicit transaction for atomicity
+    let tx = conn
+        .transaction()
+        .context("Failed to begin transaction for migration 017")?;
+
+    tx.execute_batch(schema_sql)
+        .context("Failed to execute migration 017 DDL statements")?;
+
+    tx.commit()
+        .context("Failed to commit migration 017 transaction")?;
+
+    Ok(())
+}
+
+/// Migration 018: Weekly insight digests
+///
+/// Adds the table the scheduler stores each completed week's digest in.
+fn apply_migration_018(db: &Database) -> Result<()> {
+    let schema_sql = include_str!("migrations/018_weekly_insights.sql");
+
+    let conn = db.get_connection();
+    let mut conn = conn.lock();
+
+    // Wrap migration in explicit transaction for atomicity
+    let tx = conn
+        .transaction()
+        .context("Failed to begin transaction for migration 018")?;
+
+    tx.execute_batch(schema_sql)
+        .context("Failed to execute migration 018 DDL statements")?;
+
+    tx.commit()
+        .context("Failed to commit migration 018 transaction")?;
+
+    Ok(())
+}
@@ -0,0 +1,30 @@
+-- Migration 008: Streak milestones
+--
+-- Records every streak milestone (3, 7, 14, ... active days) reached by an
+-- activity, activity group, or mood check-in streak. A milestone is identified
+-- by the run it belongs to (streak_start_date), so a broken-and-restarted
+-- streak can reach the same milestone again, while the scheduler never
+-- announces the same milestone of the same run twice.
+--
+-- notified_at IS NULL marks a milestone still to be announced; it stays NULL
+-- until a notification succeeds, so failed ones are retried. Lower milestones
+-- recorded together with a higher one get notified_at on insert, since only
+-- the highest is announced.
+
+CREATE TABLE streak_milestones (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    streak_kind TEXT NOT NULL CHECK(streak_kind IN ('activity', 'activity_group', 'mood_checkin')),
+    target_id INTEGER,  -- activity or group ID, NULL for mood check-ins
+    milestone INTEGER NOT NULL CHECK(milestone > 0),
+    streak_start_date TEXT NOT NULL,  -- YYYY-MM-DD
+    achieved_at TEXT NOT NULL DEFAULT (datetime('now')),
+    notified_at TEXT
+);
+
+-- COALESCE so mood check-in milestones (NULL target) are unique as well
+CREATE UNIQUE INDEX idx_streak_milestones_unique
+    ON streak_milestones(streak_kind, COALESCE(target_id, 0), milestone, streak_start_date);
+
+CREATE INDEX idx_streak_milestones_pending
+    ON streak_milestones(notified_at)
+    WHERE notified_at IS NULL;
@@ -0,0 +1,32 @@
+-- Migration 009: Planned activities
+--
+-- Lets users schedule intended activities ("Yoga on Tue/Thu at 07:00") so
+-- adherence can be measured by matching planned occurrences against
+-- activity_logs within a tolerance window.
+--
+-- Recurrence: every `interval_weeks` weeks on the listed weekdays, counted
+-- from the week containing start_date. days_of_week, time_of_day, start_date
+-- and end_date are wall-clock values in the plan's IANA timezone; new plans
+-- default to the system timezone. Expansion happens in Rust (chrono-tz).
+
+CREATE TABLE planned_activities (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    activity_id INTEGER NOT NULL,
+    days_of_week TEXT NOT NULL,  -- Comma-separated weekdays, 0 = Sunday (e.g. "2,4")
+    time_of_day TEXT NOT NULL CHECK(length(time_of_day) = 5),  -- HH:MM
+    timezone TEXT NOT NULL DEFAULT 'UTC',  -- IANA name, e.g. "Europe/Berlin"
+    interval_weeks INTEGER NOT NULL DEFAULT 1 CHECK(interval_weeks BETWEEN 1 AND 52),
+    tolerance_minutes INTEGER NOT NULL DEFAULT 120 CHECK(tolerance_minutes BETWEEN 0 AND 720),
+    start_date TEXT NOT NULL,  -- YYYY-MM-DD
+    end_date TEXT,             -- YYYY-MM-DD, NULL = open-ended
+    reminder_enabled INTEGER NOT NULL DEFAULT 0 CHECK(reminder_enabled IN (0, 1)),
+    reminder_minutes_before INTEGER NOT NULL DEFAULT 0 CHECK(reminder_minutes_before BETWEEN 0 AND 1440),
+    last_reminded_at TEXT,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    deleted_at TEXT,  -- Soft delete timestamp
+    FOREIGN KEY (activity_id) REFERENCES activities(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_planned_activities_activity
+    ON planned_activities(activity_id)
+    WHERE deleted_at IS NULL;
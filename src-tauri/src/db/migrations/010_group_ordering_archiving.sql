@@ -0,0 +1,14 @@
+-- Migration 010: Activity group ordering, archiving and default appearance
+--
+-- sort_order: user-defined position (NULL = unpositioned, sorted by name after
+--   positioned rows). Applies to groups and to activities within a group.
+-- archived_at: hides a group from pickers without touching its activities,
+--   logs, goals or reports (unlike deleted_at, which cascades).
+-- color/icon: group defaults inherited by activities created without their own.
+
+ALTER TABLE activity_groups ADD COLUMN sort_order INTEGER;
+ALTER TABLE activity_groups ADD COLUMN archived_at TEXT;
+ALTER TABLE activity_groups ADD COLUMN color TEXT;
+ALTER TABLE activity_groups ADD COLUMN icon TEXT;
+
+ALTER TABLE activities ADD COLUMN sort_order INTEGER;
@@ -0,0 +1,19 @@
+-- Migration 011: User-defined goal templates
+--
+-- Built-in templates live in code (features::goal_templates::models); this
+-- table only stores setups users saved themselves. The group, activities and
+-- goals of a template are stored together as a JSON document in `definition`
+-- (see GoalTemplateDefinition) since they are only ever read as a whole.
+
+CREATE TABLE goal_templates (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL CHECK(length(name) >= 1 AND length(name) <= 100),
+    description TEXT CHECK(description IS NULL OR length(description) <= 500),
+    definition TEXT NOT NULL,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    deleted_at TEXT  -- Soft delete timestamp
+);
+
+CREATE UNIQUE INDEX idx_goal_templates_name_unique
+    ON goal_templates(name)
+    WHERE deleted_at IS NULL;
@@ -0,0 +1,8 @@
+-- Migration 012: Timezone-aware assessment schedules
+--
+-- time_of_day (and day_of_week/day_of_month) are now wall-clock values in the
+-- schedule's IANA timezone. Existing schedules were evaluated in UTC, so they
+-- keep 'UTC' to fire exactly as before; new schedules default to the system
+-- timezone. Due-ness is computed in Rust (chrono-tz), not in SQL.
+
+ALTER TABLE assessment_schedules ADD COLUMN timezone TEXT NOT NULL DEFAULT 'UTC';
@@ -0,0 +1,49 @@
+-- Migration 013: Reminder schedules with non-assessment targets
+--
+-- A schedule can now remind about an assessment, a mood check-in, a specific
+-- activity or activity group, or a free-text habit. target_kind selects which
+-- of the target columns is set; the CHECK keeps exactly the matching one
+-- non-NULL. assessment_type_id becomes nullable, which SQLite can only do by
+-- recreating the table. Existing schedules become 'assessment' targets.
+
+CREATE TABLE assessment_schedules_new (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    target_kind TEXT NOT NULL DEFAULT 'assessment'
+        CHECK (target_kind IN ('assessment', 'mood_checkin', 'activity', 'activity_group', 'habit')),
+    assessment_type_id INTEGER,
+    activity_id INTEGER,
+    activity_group_id INTEGER,
+    habit_text TEXT CHECK (habit_text IS NULL OR length(habit_text) BETWEEN 1 AND 200),
+    frequency TEXT NOT NULL CHECK (frequency IN ('daily', 'weekly', 'biweekly', 'monthly')),
+    time_of_day TEXT NOT NULL,  -- HH:MM format
+    day_of_week INTEGER,
+    day_of_month INTEGER,
+    enabled INTEGER NOT NULL DEFAULT 1,  -- Boolean stored as INTEGER (0=false, 1=true)
+    last_triggered_at TEXT,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+    timezone TEXT NOT NULL DEFAULT 'UTC',
+    FOREIGN KEY (assessment_type_id) REFERENCES assessment_types(id),
+    FOREIGN KEY (activity_id) REFERENCES activities(id),
+    FOREIGN KEY (activity_group_id) REFERENCES activity_groups(id),
+    CHECK ((target_kind = 'assessment') = (assessment_type_id IS NOT NULL)),
+    CHECK ((target_kind = 'activity') = (activity_id IS NOT NULL)),
+    CHECK ((target_kind = 'activity_group') = (activity_group_id IS NOT NULL)),
+    CHECK ((target_kind = 'habit') = (habit_text IS NOT NULL))
+);
+
+INSERT INTO assessment_schedules_new
+    (id, target_kind, assessment_type_id, frequency, time_of_day, day_of_week, day_of_month,
+     enabled, last_triggered_at, created_at, updated_at, timezone)
+SELECT id, 'assessment', assessment_type_id, frequency, time_of_day, day_of_week, day_of_month,
+       enabled, last_triggered_at, created_at, updated_at, timezone
+FROM assessment_schedules;
+
+DROP TABLE assessment_schedules;
+ALTER TABLE assessment_schedules_new RENAME TO assessment_schedules;
+
+CREATE INDEX idx_assessment_schedules_enabled ON assessment_schedules(enabled);
+CREATE INDEX idx_assessment_schedules_next ON assessment_schedules(enabled, last_triggered_at);
+CREATE INDEX idx_schedules_enabled_time
+ON assessment_schedules(enabled, time_of_day)
+WHERE enabled = 1;
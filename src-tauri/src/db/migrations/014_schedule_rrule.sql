@@ -0,0 +1,105 @@
+-- Migration 014: Recurrence rules for reminder schedules
+--
+-- Every schedule now stores an RFC 5545 RRULE anchored at a local DTSTART,
+-- plus comma-separated EXDATE exceptions; occurrences are expanded from the
+-- rule. Schedules that don't fit daily/weekly/biweekly/monthly use the new
+-- 'custom' frequency, which needs recreating the table to widen the CHECK.
+--
+-- Existing rows get rules that reproduce their current behaviour:
+--   daily     FREQ=DAILY;BYHOUR=h;BYMINUTE=m
+--   weekly    FREQ=WEEKLY;BYDAY=XX;BYHOUR=h;BYMINUTE=m
+--   biweekly  FREQ=WEEKLY;INTERVAL=2;BYDAY=XX;... anchored on the last trigger
+--             day (or the next matching weekday if never triggered), so the
+--             14-day rhythm is kept
+--   monthly   FREQ=MONTHLY;BYMONTHDAY=d;... and for days 29-31 the last of
+--             BYMONTHDAY=28..d (BYSETPOS=-1), the old clamp to short months
+-- dtstart is the day before creation for the others; dates are UTC-derived,
+-- so the day of margin covers any timezone.
+
+CREATE TABLE assessment_schedules_new (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    target_kind TEXT NOT NULL DEFAULT 'assessment'
+        CHECK (target_kind IN ('assessment', 'mood_checkin', 'activity', 'activity_group', 'habit')),
+    assessment_type_id INTEGER,
+    activity_id INTEGER,
+    activity_group_id INTEGER,
+    habit_text TEXT CHECK (habit_text IS NULL OR length(habit_text) BETWEEN 1 AND 200),
+    frequency TEXT NOT NULL
+        CHECK (frequency IN ('daily', 'weekly', 'biweekly', 'monthly', 'custom')),
+    time_of_day TEXT NOT NULL,  -- HH:MM format; earliest rule time for custom schedules
+    day_of_week INTEGER,
+    day_of_month INTEGER,
+    rrule TEXT NOT NULL,        -- RRULE value, e.g. FREQ=WEEKLY;BYDAY=MO;BYHOUR=9;BYMINUTE=0
+    dtstart TEXT NOT NULL,      -- Local YYYY-MM-DDTHH:MM:SS in the schedule's timezone
+    exdates TEXT NOT NULL DEFAULT '',  -- Comma-separated local dates/date-times to skip
+    enabled INTEGER NOT NULL DEFAULT 1,  -- Boolean stored as INTEGER (0=false, 1=true)
+    last_triggered_at TEXT,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    updated_at TEXT NOT NULL DEFAULT (datetime('now')),
+    timezone TEXT NOT NULL DEFAULT 'UTC',
+    FOREIGN KEY (assessment_type_id) REFERENCES assessment_types(id),
+    FOREIGN KEY (activity_id) REFERENCES activities(id),
+    FOREIGN KEY (activity_group_id) REFERENCES activity_groups(id),
+    CHECK ((target_kind = 'assessment') = (assessment_type_id IS NOT NULL)),
+    CHECK ((target_kind = 'activity') = (activity_id IS NOT NULL)),
+    CHECK ((target_kind = 'activity_group') = (activity_group_id IS NOT NULL)),
+    CHECK ((target_kind = 'habit') = (habit_text IS NOT NULL))
+);
+
+INSERT INTO assessment_schedules_new
+    (id, target_kind, assessment_type_id, activity_id, activity_group_id, habit_text,
+     frequency, time_of_day, day_of_week, day_of_month, rrule, dtstart, exdates,
+     enabled, last_triggered_at, created_at, updated_at, timezone)
+SELECT id, target_kind, assessment_type_id, activity_id, activity_group_id, habit_text,
+       frequency, time_of_day, day_of_week, day_of_month,
+       CASE frequency
+           WHEN 'daily' THEN 'FREQ=DAILY'
+           WHEN 'weekly' THEN 'FREQ=WEEKLY'
+           WHEN 'biweekly' THEN 'FREQ=WEEKLY;INTERVAL=2'
+           ELSE 'FREQ=MONTHLY'
+       END
+       || CASE
+           WHEN frequency <> 'monthly' OR day_of_month IS NULL THEN ''
+           WHEN day_of_month <= 28 THEN ';BYMONTHDAY=' || day_of_month
+           WHEN day_of_month = 29 THEN ';BYMONTHDAY=28,29'
+           WHEN day_of_month = 30 THEN ';BYMONTHDAY=28,29,30'
+           ELSE ';BYMONTHDAY=28,29,30,31'
+       END
+       || CASE
+           WHEN frequency IN ('weekly', 'biweekly')
+           THEN COALESCE(';BYDAY=' || substr('SUMOTUWETHFRSA', day_of_week * 2 + 1, 2), '')
+           ELSE ''
+       END
+       || ';BYHOUR=' || CAST(substr(time_of_day, 1, 2) AS INTEGER)
+       || ';BYMINUTE=' || CAST(substr(time_of_day, 4, 2) AS INTEGER)
+       || CASE
+           WHEN frequency = 'monthly' AND day_of_month > 28 THEN ';BYSETPOS=-1'
+           ELSE ''
+       END,
+       CASE
+           WHEN frequency = 'biweekly' AND last_triggered_at IS NOT NULL THEN
+               CASE
+                   WHEN CAST(strftime('%w', last_triggered_at) AS INTEGER) = day_of_week
+                       THEN date(last_triggered_at)
+                   WHEN CAST(strftime('%w', last_triggered_at, '-1 day') AS INTEGER) = day_of_week
+                       THEN date(last_triggered_at, '-1 day')
+                   WHEN CAST(strftime('%w', last_triggered_at, '+1 day') AS INTEGER) = day_of_week
+                       THEN date(last_triggered_at, '+1 day')
+                   ELSE date(last_triggered_at)
+               END
+           WHEN frequency = 'biweekly' THEN
+               COALESCE(date('now', 'weekday ' || day_of_week), date('now'))
+           ELSE date(created_at, '-1 day')
+       END || 'T00:00:00',
+       '',
+       enabled, last_triggered_at, created_at, updated_at, timezone
+FROM assessment_schedules;
+
+DROP TABLE assessment_schedules;
+ALTER TABLE assessment_schedules_new RENAME TO assessment_schedules;
+
+CREATE INDEX idx_assessment_schedules_enabled ON assessment_schedules(enabled);
+CREATE INDEX idx_assessment_schedules_next ON assessment_schedules(enabled, last_triggered_at);
+CREATE INDEX idx_schedules_enabled_time
+ON assessment_schedules(enabled, time_of_day)
+WHERE enabled = 1;
@@ -0,0 +1,39 @@
+-- Migration 015: Reminder events
+--
+-- Log of what happened to each reminder notification. Every time a schedule
+-- fires, a 'fired' row is written; the user's responses to it ('snoozed',
+-- 'dismissed', 'completed') point back at that row via related_event_id. When
+-- a snooze runs out the scheduler fires again, recorded as another 'fired'
+-- row pointing at the original one, so related_event_id always names the
+-- reminder an event belongs to and original reminders are the rows where it
+-- is NULL. A completion caused by submitting an assessment links the response.
+
+CREATE TABLE reminder_events (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    schedule_id INTEGER NOT NULL,
+    event_type TEXT NOT NULL CHECK (event_type IN ('fired', 'snoozed', 'dismissed', 'completed')),
+    occurred_at TEXT NOT NULL,  -- UTC "YYYY-MM-DD HH:MM:SS"
+    snooze_until TEXT,          -- UTC, only for 'snoozed'
+    related_event_id INTEGER,
+    assessment_response_id INTEGER,
+    created_at TEXT NOT NULL DEFAULT (datetime('now')),
+    FOREIGN KEY (schedule_id) REFERENCES assessment_schedules(id) ON DELETE CASCADE,
+    FOREIGN KEY (related_event_id) REFERENCES reminder_events(id) ON DELETE CASCADE,
+    FOREIGN KEY (assessment_response_id) REFERENCES assessment_responses(id) ON DELETE SET NULL,
+    CHECK ((event_type = 'snoozed') = (snooze_until IS NOT NULL)),
+    CHECK (event_type = 'fired' OR related_event_id IS NOT NULL)
+);
+
+-- Supports: per-schedule history and adherence over a date range
+CREATE INDEX idx_reminder_events_schedule
+    ON reminder_events(schedule_id, occurred_at);
+
+-- Supports: follow-up events of a reminder
+CREATE INDEX idx_reminder_events_related
+    ON reminder_events(related_event_id)
+    WHERE related_event_id IS NOT NULL;
+
+-- Supports: the scheduler's search for snoozes that have run out
+CREATE INDEX idx_reminder_events_snoozes
+    ON reminder_events(snooze_until)
+    WHERE event_type = 'snoozed';
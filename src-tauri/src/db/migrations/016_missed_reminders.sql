@@ -0,0 +1,32 @@
+-- Migration 016: Missed reminders
+--
+-- The scheduler only runs while the app is open. On startup it now works out
+-- which occurrences fell due while the app was closed and handles them
+-- according to each schedule's missed_policy:
+--   coalesce  one catch-up notification for all missed occurrences (default)
+--   fire_all  one catch-up notification per missed occurrence
+--   skip      no notification, the occurrences are only recorded
+-- Every missed occurrence is recorded in missed_reminders so the UI can show
+-- "you missed your Monday PHQ-9" until the user acknowledges it.
+
+ALTER TABLE assessment_schedules ADD COLUMN missed_policy TEXT NOT NULL DEFAULT 'coalesce'
+    CHECK (missed_policy IN ('coalesce', 'fire_all', 'skip'));
+
+CREATE TABLE missed_reminders (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    schedule_id INTEGER NOT NULL,
+    scheduled_for TEXT NOT NULL,  -- UTC "YYYY-MM-DD HH:MM:SS" of the missed occurrence
+    local_time TEXT NOT NULL,     -- Same occurrence in the schedule's timezone, "YYYY-MM-DDTHH:MM:SS"
+    policy TEXT NOT NULL CHECK (policy IN ('coalesce', 'fire_all', 'skip')),
+    reminder_event_id INTEGER,    -- Catch-up notification, NULL when skipped
+    detected_at TEXT NOT NULL,
+    acknowledged_at TEXT,
+    FOREIGN KEY (schedule_id) REFERENCES assessment_schedules(id) ON DELETE CASCADE,
+    FOREIGN KEY (reminder_event_id) REFERENCES reminder_events(id) ON DELETE SET NULL,
+    UNIQUE (schedule_id, scheduled_for)
+);
+
+-- Supports: unacknowledged missed reminders, newest first
+CREATE INDEX idx_missed_reminders_pending
+    ON missed_reminders(scheduled_for)
+    WHERE acknowledged_at IS NULL;
@@ -0,0 +1,48 @@
+-- Migration 017: Adaptive schedule frequency
+--
+-- Assessment schedules can carry ordered rules that change their frequency
+-- based on recent results, e.g. "moderate or worse -> weekly" and "two
+-- consecutive minimal results -> monthly". Rules are evaluated after each
+-- submission and by the scheduler; the first matching rule wins.
+--
+-- adaptive_response_id remembers the newest response a schedule was evaluated
+-- against, so rules only act on new results and never undo a manual edit.
+-- Every automatic change is logged with its reason in schedule_frequency_changes.
+
+ALTER TABLE assessment_schedules ADD COLUMN adaptive_response_id INTEGER;
+
+CREATE TABLE schedule_adaptive_rules (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    schedule_id INTEGER NOT NULL,
+    position INTEGER NOT NULL,        -- Evaluation order, 0 first
+    comparison TEXT NOT NULL CHECK (comparison IN ('at_least', 'at_most')),
+    severity_level TEXT NOT NULL
+        CHECK (severity_level IN ('minimal', 'mild', 'moderate', 'moderately_severe', 'severe')),
+    consecutive_count INTEGER NOT NULL DEFAULT 1 CHECK (consecutive_count BETWEEN 1 AND 10),
+    frequency TEXT NOT NULL CHECK (frequency IN ('daily', 'weekly', 'biweekly', 'monthly')),
+    day_of_week INTEGER CHECK (day_of_week BETWEEN 0 AND 6),
+    day_of_month INTEGER CHECK (day_of_month BETWEEN 1 AND 31),
+    created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    FOREIGN KEY (schedule_id) REFERENCES assessment_schedules(id) ON DELETE CASCADE,
+    UNIQUE (schedule_id, position)
+);
+
+CREATE TABLE schedule_frequency_changes (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    schedule_id INTEGER NOT NULL,
+    rule_id INTEGER,                  -- NULL once the rule is replaced
+    from_frequency TEXT NOT NULL,
+    to_frequency TEXT NOT NULL,
+    from_rrule TEXT NOT NULL,
+    to_rrule TEXT NOT NULL,
+    reason TEXT NOT NULL,
+    assessment_response_id INTEGER,   -- The result that triggered the change
+    changed_at TEXT NOT NULL,
+    FOREIGN KEY (schedule_id) REFERENCES assessment_schedules(id) ON DELETE CASCADE,
+    FOREIGN KEY (rule_id) REFERENCES schedule_adaptive_rules(id) ON DELETE SET NULL,
+    FOREIGN KEY (assessment_response_id) REFERENCES assessment_responses(id) ON DELETE SET NULL
+);
+
+-- Supports: a schedule's change history, newest first
+CREATE INDEX idx_schedule_frequency_changes_schedule
+    ON schedule_frequency_changes(schedule_id, changed_at);
@@ -0,0 +1,19 @@
+-- Migration 018: Weekly insight digests
+--
+-- The scheduler computes a digest for every completed week (Monday to Sunday
+-- in the local timezone): mood average and change, best and worst day,
+-- activities linked with better mood, goal achievements, missed reminders and
+-- assessment severity changes. The digest is stored as JSON together with its
+-- human-readable sentences, so it reads the same later even if the
+-- underlying data changes. notified_at is set once it has been announced
+-- (or deliberately not announced), so it is never announced twice.
+
+CREATE TABLE weekly_insights (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    week_start TEXT NOT NULL UNIQUE,  -- Local Monday, YYYY-MM-DD
+    week_end TEXT NOT NULL,           -- Local Sunday, YYYY-MM-DD
+    timezone TEXT NOT NULL,           -- IANA name the week was computed in
+    digest TEXT NOT NULL,             -- WeeklyDigest JSON
+    generated_at TEXT NOT NULL DEFAULT (datetime('now')),
+    notified_at TEXT
+);
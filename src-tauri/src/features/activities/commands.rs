@@ -78,6 +78,103 @@ pub async fn delete_activity_group(
     })
 }
 
+#[tauri::command]
+#[specta::specta]
+pub async fn set_activity_group_appearance(
+    id: i32,
+    request: SetActivityGroupAppearanceRequest,
+    state: State<'_, AppState>,
+) -> Result<ActivityGroup, CommandError> {
+    // Validate request
+    request.validate().map_err(|e| {
+        CommandError::permanent(format!("Validation failed: {}", e), ErrorType::Validation)
+    })?;
+
+    let repo = ActivityRepository::new(state.db.clone());
+    repo.set_activity_group_appearance(
+        id,
+        request.color.as_ref().map(|c| c.value()),
+        request.icon.as_deref(),
+    )
+    .map_err(|e| {
+        error!(
+            "set_activity_group_appearance error: {} (id: {}, has_color: {}, has_icon: {})",
+            e,
+            id,
+            request.color.is_some(),
+            request.icon.is_some()
+        );
+        e.to_command_error()
+    })
+}
+
+/// Archive an activity group.
+///
+/// Non-destructive alternative to `delete_activity_group`: the group and its
+/// activities are hidden from pickers, but logs, goals and reports are kept.
+#[tauri::command]
+#[specta::specta]
+pub async fn archive_activity_group(
+    id: i32,
+    state: State<'_, AppState>,
+) -> Result<ActivityGroup, CommandError> {
+    let repo = ActivityRepository::new(state.db.clone());
+    repo.archive_activity_group(id).map_err(|e| {
+        error!("archive_activity_group error: {} (id: {})", e, id);
+        e.to_command_error()
+    })
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn unarchive_activity_group(
+    id: i32,
+    state: State<'_, AppState>,
+) -> Result<ActivityGroup, CommandError> {
+    let repo = ActivityRepository::new(state.db.clone());
+    repo.unarchive_activity_group(id).map_err(|e| {
+        error!("unarchive_activity_group error: {} (id: {})", e, id);
+        e.to_command_error()
+    })
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn reorder_activity_groups(
+    ordered_ids: Vec<i32>,
+    state: State<'_, AppState>,
+) -> Result<Vec<ActivityGroup>, CommandError> {
+    let repo = ActivityRepository::new(state.db.clone());
+    repo.reorder_activity_groups(&ordered_ids).map_err(|e| {
+        error!(
+            "reorder_activity_groups error: {} (count: {})",
+            e,
+            ordered_ids.len()
+        );
+        e.to_command_error()
+    })
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn reorder_activities(
+    group_id: i32,
+    ordered_ids: Vec<i32>,
+    state: State<'_, AppState>,
+) -> Result<Vec<Activity>, CommandError> {
+    let repo = ActivityRepository::new(state.db.clone());
+    repo.reorder_activities(group_id, &ordered_ids)
+        .map_err(|e| {
+            error!(
+                "reorder_activities error: {} (group_id: {}, count: {})",
+                e,
+                group_id,
+                ordered_ids.len()
+            );
+            e.to_command_error()
+        })
+}
+
 // ========================================
 // Activity Logging Commands
 // ========================================
@@ -211,3 +308,60 @@ pub async fn delete_activity_goal(
         e.to_command_error()
     })
 }
+
+// ========================================
+// Planned Activity Commands
+// ========================================
+
+#[tauri::command]
+#[specta::specta]
+pub async fn create_planned_activity(
+    request: CreatePlannedActivityRequest,
+    state: State<'_, AppState>,
+) -> Result<PlannedActivity, CommandError> {
+    // Validate request
+    request.validate().map_err(|e| {
+        CommandError::permanent(format!("Validation failed: {}", e), ErrorType::Validation)
+    })?;
+
+    let repo = ActivityRepository::new(state.db.clone());
+    repo.create_planned_activity(&request).map_err(|e| {
+        error!(
+            "create_planned_activity error: {} (activity_id: {}, days_of_week: {:?}, time_of_day: {})",
+            e, request.activity_id, request.days_of_week, request.time_of_day
+        );
+        e.to_command_error()
+    })
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn update_planned_activity(
+    id: i32,
+    request: UpdatePlannedActivityRequest,
+    state: State<'_, AppState>,
+) -> Result<PlannedActivity, CommandError> {
+    // Validate request
+    request.validate().map_err(|e| {
+        CommandError::permanent(format!("Validation failed: {}", e), ErrorType::Validation)
+    })?;
+
+    let repo = ActivityRepository::new(state.db.clone());
+    repo.update_planned_activity(id, &request).map_err(|e| {
+        error!("update_planned_activity error: {} (id: {})", e, id);
+        e.to_command_error()
+    })
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn delete_planned_activity(
+    id: i32,
+    state: State<'_, AppState>,
+) -> Result<(), CommandError> {
+    let repo = ActivityRepository::new(state.db.clone());
+    repo.delete_planned_activity(id).map_err(|e| {
+        error!("delete_planned_activity error: {} (id: {})", e, id);
+        e.to_command_error()
+    })
+}
@@ -1,4 +1,5 @@
 use crate::errors::{CommandError, ErrorType, ToCommandError};
+use crate::features::scheduling::models::{parse_timezone, resolve_local_time};
 use crate::types::activity::{GoalType, HexColor};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
@@ -62,6 +63,15 @@ pub enum ActivityError {
     #[error("Period days must be positive: {0}")]
     InvalidPeriodDays(i32),
 
+    #[error("Planned activity not found: {0}")]
+    PlannedActivityNotFound(i32),
+
+    #[error("Invalid activity plan: {0}")]
+    InvalidPlan(String),
+
+    #[error("Invalid ordering: {0}")]
+    InvalidOrder(String),
+
     #[error("Database lock poisoned - a panic occurred while holding the database lock. The application should restart.")]
     LockPoisoned,
 
@@ -109,6 +119,12 @@ impl ToCommandError for ActivityError {
             ActivityError::InvalidPeriodDays(_) => {
                 CommandError::permanent(self.to_string(), ErrorType::Validation)
             }
+            ActivityError::InvalidPlan(_) => {
+                CommandError::permanent(self.to_string(), ErrorType::Validation)
+            }
+            ActivityError::InvalidOrder(_) => {
+                CommandError::permanent(self.to_string(), ErrorType::Validation)
+            }
 
             // Not found errors - not retryable
             ActivityError::GroupNotFound(id) => {
@@ -143,6 +159,14 @@ impl ToCommandError for ActivityError {
                     }),
                 )
             }
+            ActivityError::PlannedActivityNotFound(id) => {
+                CommandError::permanent(self.to_string(), ErrorType::NotFound).with_details(
+                    serde_json::json!({
+                        "resource": "planned_activity",
+                        "id": id
+                    }),
+                )
+            }
 
             // Duplicate errors - not retryable
             ActivityError::DuplicateGroupName(name) => {
@@ -180,6 +204,14 @@ pub struct ActivityGroup {
     pub id: i32,
     pub name: String,
     pub description: Option<String>,
+    /// Default color inherited by activities created without one
+    pub color: Option<HexColor>,
+    /// Default icon inherited by activities created without one
+    pub icon: Option<String>,
+    /// User-defined position (None = unpositioned, listed by name after positioned groups)
+    pub sort_order: Option<i32>,
+    /// Archived groups are hidden from pickers but keep their history and reports
+    pub archived_at: Option<String>,
     pub created_at: String,
     pub deleted_at: Option<String>,
 }
@@ -260,6 +292,35 @@ pub enum Trend {
     Stable,
 }
 
+impl Trend {
+    /// Classifies a percentage change (threshold: ±10%)
+    pub fn from_change_percentage(change_percentage: f64) -> Self {
+        if change_percentage > 10.0 {
+            Trend::Improving
+        } else if change_percentage < -10.0 {
+            Trend::Declining
+        } else {
+            Trend::Stable
+        }
+    }
+}
+
+/// Percentage change between two periods of unique-day counts.
+///
+/// A previous period of zero is treated as 100% improvement when the current
+/// period has any activity, and 0% when both are zero.
+pub fn calculate_change_percentage(current_days: i32, previous_days: i32) -> f64 {
+    if previous_days == 0 {
+        if current_days > 0 {
+            100.0 // Improvement from nothing
+        } else {
+            0.0 // No change (both zero)
+        }
+    } else {
+        (((current_days - previous_days) as f64) / (previous_days as f64)) * 100.0
+    }
+}
+
 /// Goal progress report showing current vs target values
 #[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
 pub struct GoalProgress {
@@ -310,6 +371,153 @@ pub struct ActivityTrend {
     pub trend: Trend,
 }
 
+/// Group frequency report: days on which any activity in the group was logged
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct GroupFrequency {
+    pub group_id: i32,
+    /// Number of unique days with a log for any non-deleted activity in the group
+    pub unique_days: i32,
+    /// Total number of activity logs across the group
+    pub total_logs: i32,
+    /// Average days per week: (unique_days / num_weeks)
+    pub days_per_week: f64,
+    /// Start of analysis period (ISO 8601)
+    pub period_start: String,
+    /// End of analysis period (ISO 8601)
+    pub period_end: String,
+}
+
+/// Group trend report comparing current vs previous period
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct GroupTrend {
+    pub group_id: i32,
+    /// Unique days in current period
+    pub current_period_days: i32,
+    /// Unique days in previous period (same duration)
+    pub previous_period_days: i32,
+    /// Difference: current - previous
+    pub change_days: i32,
+    /// Percentage change: ((current - previous) / previous) * 100
+    pub change_percentage: f64,
+    /// Trend classification based on change_percentage
+    pub trend: Trend,
+}
+
+/// One row of the all-groups weekly summary
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct GroupWeeklySummary {
+    pub group_id: i32,
+    pub group_name: String,
+    /// Unique days with any activity in the group during the last 7 days
+    pub days_per_week: i32,
+    /// Unique days during the 7 days before that
+    pub previous_week_days: i32,
+    /// Difference: days_per_week - previous_week_days
+    pub change_days: i32,
+    /// Percentage change against the previous week
+    pub change_percentage: f64,
+    /// Trend classification based on change_percentage
+    pub trend: Trend,
+}
+
+/// Planned (intended) activity with weekly recurrence
+///
+/// Describes when the user intends to do an activity, e.g. "Yoga on Tue/Thu at 07:00".
+/// Occurrences are expanded from `start_date` on the given weekdays every
+/// `interval_weeks` weeks, and matched against `activity_logs` within
+/// `tolerance_minutes` of the planned time.
+///
+/// Days, dates and the time of day are wall-clock values in `timezone`, like
+/// assessment schedules; expanded occurrences are reported in UTC.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct PlannedActivity {
+    pub id: i32,
+    pub activity_id: i32,
+    /// Weekdays the activity is planned on (0 = Sunday, 6 = Saturday)
+    pub days_of_week: Vec<i32>,
+    /// Planned time of day (HH:MM)
+    pub time_of_day: String,
+    /// IANA timezone the days and time of day are local to
+    pub timezone: String,
+    /// Repeat every N weeks (1 = weekly, 2 = every other week)
+    pub interval_weeks: i32,
+    /// How far (in minutes) a log may be from the planned time and still count
+    pub tolerance_minutes: i32,
+    /// First day of the plan (YYYY-MM-DD)
+    pub start_date: String,
+    /// Last day of the plan (YYYY-MM-DD, None = open-ended)
+    pub end_date: Option<String>,
+    /// Send a reminder through the scheduler before each occurrence
+    pub reminder_enabled: bool,
+    /// Minutes before the planned time to send the reminder
+    pub reminder_minutes_before: i32,
+    pub last_reminded_at: Option<String>,
+    pub created_at: String,
+    pub deleted_at: Option<String>,
+}
+
+/// Outcome of a single planned occurrence
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, specta::Type, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum PlannedOccurrenceStatus {
+    /// A matching activity log was found within the tolerance window
+    Done,
+    /// The tolerance window has passed without a matching log
+    Missed,
+    /// The tolerance window has not closed yet
+    Pending,
+}
+
+/// A single expanded occurrence of a plan and how it was matched
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct PlannedOccurrence {
+    pub planned_activity_id: i32,
+    pub activity_id: i32,
+    /// Planned time (ISO 8601, UTC)
+    pub scheduled_at: String,
+    pub status: PlannedOccurrenceStatus,
+    /// Activity log that fulfilled this occurrence
+    pub matched_log_id: Option<i32>,
+    pub matched_logged_at: Option<String>,
+}
+
+/// Plan-versus-actual adherence for an activity or a group
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct AdherenceReport {
+    /// Set for activity reports
+    pub activity_id: Option<i32>,
+    /// Set for group reports
+    pub group_id: Option<i32>,
+    /// Start of analysis period (ISO 8601)
+    pub period_start: String,
+    /// End of analysis period (ISO 8601)
+    pub period_end: String,
+    /// Number of planned occurrences in the period
+    pub planned: i32,
+    /// Occurrences matched by a log
+    pub done: i32,
+    /// Occurrences whose window passed without a log
+    pub missed: i32,
+    /// Occurrences whose window is still open
+    pub pending: i32,
+    /// Logs in the period that did not match any planned occurrence
+    pub unplanned: i32,
+    /// done / (done + missed) * 100 (0 when nothing is due yet)
+    pub adherence_percentage: f64,
+    /// Every occurrence in chronological order
+    pub occurrences: Vec<PlannedOccurrence>,
+}
+
+/// Reminder due for a planned activity occurrence
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct PlannedActivityReminder {
+    pub planned_activity_id: i32,
+    pub activity_id: i32,
+    pub activity_name: String,
+    /// Planned time the reminder is for (ISO 8601, UTC)
+    pub scheduled_at: String,
+}
+
 /// Request to create an activity group
 #[derive(Debug, Serialize, Deserialize, specta::Type, Validate)]
 pub struct CreateActivityGroupRequest {
@@ -328,6 +536,16 @@ pub struct UpdateActivityGroupRequest {
     pub description: Option<String>,
 }
 
+/// Request to set the default color and icon of an activity group.
+/// `None` clears the corresponding default.
+#[derive(Debug, Serialize, Deserialize, specta::Type, Validate)]
+pub struct SetActivityGroupAppearanceRequest {
+    /// Color validated on deserialization via HexColor newtype
+    pub color: Option<HexColor>,
+    #[validate(custom(function = "validate_optional_icon"))]
+    pub icon: Option<String>,
+}
+
 /// Request to create an activity
 #[derive(Debug, Serialize, Deserialize, specta::Type, Validate)]
 pub struct CreateActivityRequest {
@@ -375,6 +593,282 @@ pub struct SetActivityGoalRequest {
     pub period_days: i32,
 }
 
+/// Request to plan an activity
+#[derive(Debug, Serialize, Deserialize, specta::Type, Validate)]
+#[validate(schema(function = "validate_create_plan_request"))]
+pub struct CreatePlannedActivityRequest {
+    pub activity_id: i32,
+    /// Weekdays (0 = Sunday, 6 = Saturday)
+    pub days_of_week: Vec<i32>,
+    /// HH:MM in `timezone`
+    pub time_of_day: String,
+    /// IANA name, defaults to the system timezone
+    pub timezone: Option<String>,
+    /// Defaults to 1 (every week)
+    #[validate(range(min = 1, max = 52))]
+    pub interval_weeks: Option<i32>,
+    /// Defaults to DEFAULT_PLAN_TOLERANCE_MINUTES
+    #[validate(range(min = 0, max = 720))]
+    pub tolerance_minutes: Option<i32>,
+    /// YYYY-MM-DD
+    pub start_date: String,
+    /// YYYY-MM-DD
+    pub end_date: Option<String>,
+    pub reminder_enabled: bool,
+    #[validate(range(min = 0, max = 1440))]
+    pub reminder_minutes_before: Option<i32>,
+}
+
+/// Request to update a planned activity
+#[derive(Debug, Serialize, Deserialize, specta::Type, Validate)]
+#[validate(schema(function = "validate_update_plan_request"))]
+pub struct UpdatePlannedActivityRequest {
+    pub days_of_week: Option<Vec<i32>>,
+    pub time_of_day: Option<String>,
+    pub timezone: Option<String>,
+    #[validate(range(min = 1, max = 52))]
+    pub interval_weeks: Option<i32>,
+    #[validate(range(min = 0, max = 720))]
+    pub tolerance_minutes: Option<i32>,
+    pub start_date: Option<String>,
+    pub end_date: Option<String>,
+    /// Remove the end date, making the plan open-ended (can't be combined with `end_date`)
+    #[serde(default)]
+    pub clear_end_date: bool,
+    pub reminder_enabled: Option<bool>,
+    #[validate(range(min = 0, max = 1440))]
+    pub reminder_minutes_before: Option<i32>,
+}
+
+/// Default tolerance window around a planned time (2 hours either side)
+pub const DEFAULT_PLAN_TOLERANCE_MINUTES: i32 = 120;
+
+/// How far a plan reminder check looks back (two scheduler ticks), so an
+/// occurrence that passed between checks is still reminded
+pub const PLAN_REMINDER_LOOKBACK_SECONDS: i64 = 120;
+
+fn validate_create_plan_request(
+    request: &CreatePlannedActivityRequest,
+) -> Result<(), validator::ValidationError> {
+    validate_plan_fields(
+        Some(&request.days_of_week),
+        Some(&request.time_of_day),
+        request.timezone.as_deref(),
+        Some(&request.start_date),
+        request.end_date.as_deref(),
+    )
+    .map_err(plan_validation_error)
+}
+
+fn validate_update_plan_request(
+    request: &UpdatePlannedActivityRequest,
+) -> Result<(), validator::ValidationError> {
+    if request.clear_end_date && request.end_date.is_some() {
+        return Err(plan_validation_error(ActivityError::InvalidPlan(
+            "end_date and clear_end_date can't be combined".to_string(),
+        )));
+    }
+    validate_plan_fields(
+        request.days_of_week.as_deref(),
+        request.time_of_day.as_deref(),
+        request.timezone.as_deref(),
+        request.start_date.as_deref(),
+        request.end_date.as_deref(),
+    )
+    .map_err(plan_validation_error)
+}
+
+fn plan_validation_error(error: ActivityError) -> validator::ValidationError {
+    let mut validation_error = validator::ValidationError::new("invalid_plan");
+    validation_error.message = Some(std::borrow::Cow::from(error.to_string()));
+    validation_error
+}
+
+/// Validates the schedule fields of a plan (shared by requests and repository)
+pub fn validate_plan_fields(
+    days_of_week: Option<&[i32]>,
+    time_of_day: Option<&str>,
+    timezone: Option<&str>,
+    start_date: Option<&str>,
+    end_date: Option<&str>,
+) -> Result<(), ActivityError> {
+    if let Some(days) = days_of_week {
+        if days.is_empty() {
+            return Err(ActivityError::InvalidPlan(
+                "At least one day of week is required".to_string(),
+            ));
+        }
+        if let Some(day) = days.iter().find(|d| !(0..=6).contains(*d)) {
+            return Err(ActivityError::InvalidPlan(format!(
+                "Day of week must be 0-6 (Sunday-Saturday): {}",
+                day
+            )));
+        }
+    }
+
+    if let Some(time) = time_of_day {
+        if chrono::NaiveTime::parse_from_str(time, "%H:%M").is_err() || time.len() != 5 {
+            return Err(ActivityError::InvalidPlan(format!(
+                "Time must be in HH:MM format: {}",
+                time
+            )));
+        }
+    }
+
+    if let Some(timezone) = timezone {
+        parse_plan_timezone(timezone)?;
+    }
+
+    let start = start_date.map(parse_plan_date).transpose()?;
+    let end = end_date.map(parse_plan_date).transpose()?;
+    if let (Some(start), Some(end)) = (start, end) {
+        if end < start {
+            return Err(ActivityError::InvalidPlan(
+                "End date must not be before start date".to_string(),
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Parses a plan date (YYYY-MM-DD)
+pub fn parse_plan_date(date: &str) -> Result<chrono::NaiveDate, ActivityError> {
+    chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .map_err(|_| ActivityError::InvalidPlan(format!("Date must be YYYY-MM-DD: {}", date)))
+}
+
+/// Parses a plan's IANA timezone
+pub fn parse_plan_timezone(timezone: &str) -> Result<chrono_tz::Tz, ActivityError> {
+    parse_timezone(timezone).map_err(|e| ActivityError::InvalidPlan(e.to_string()))
+}
+
+/// Expands the planned occurrences of a plan that fall on days in `[from, to]`,
+/// as local times of the plan's timezone.
+pub fn expand_plan_occurrences(
+    plan: &PlannedActivity,
+    from: chrono::NaiveDate,
+    to: chrono::NaiveDate,
+) -> Result<Vec<chrono::NaiveDateTime>, ActivityError> {
+    use chrono::{Datelike, Duration};
+
+    let plan_start = parse_plan_date(&plan.start_date)?;
+    let plan_end = plan.end_date.as_deref().map(parse_plan_date).transpose()?;
+    let time = chrono::NaiveTime::parse_from_str(&plan.time_of_day, "%H:%M").map_err(|_| {
+        ActivityError::InvalidPlan(format!(
+            "Time must be in HH:MM format: {}",
+            plan.time_of_day
+        ))
+    })?;
+    let interval_weeks = plan.interval_weeks.max(1) as i64;
+
+    // Week numbering is anchored on the Sunday of the plan's first week
+    let anchor = plan_start - Duration::days(plan_start.weekday().num_days_from_sunday() as i64);
+
+    let first = from.max(plan_start);
+    let last = match plan_end {
+        Some(end) => to.min(end),
+        None => to,
+    };
+
+    let mut occurrences = Vec::new();
+    let mut day = first;
+    while day <= last {
+        let weekday = day.weekday().num_days_from_sunday() as i32;
+        let week_index = (day - anchor).num_days() / 7;
+        if plan.days_of_week.contains(&weekday) && week_index % interval_weeks == 0 {
+            occurrences.push(day.and_time(time));
+        }
+        day += Duration::days(1);
+    }
+
+    Ok(occurrences)
+}
+
+/// Planned occurrences of a plan within `[from, to]`, as UTC times.
+///
+/// Local times that fall into a DST gap or overlap resolve like reminder
+/// schedules do (see `resolve_local_time`).
+pub fn plan_occurrences_between(
+    plan: &PlannedActivity,
+    from: chrono::NaiveDateTime,
+    to: chrono::NaiveDateTime,
+) -> Result<Vec<chrono::NaiveDateTime>, ActivityError> {
+    let tz = parse_plan_timezone(&plan.timezone)?;
+    // A day either side covers every offset
+    let local_day = |utc: chrono::NaiveDateTime| utc.and_utc().with_timezone(&tz).date_naive();
+    let first = local_day(from) - chrono::Duration::days(1);
+    let last = local_day(to) + chrono::Duration::days(1);
+
+    Ok(expand_plan_occurrences(plan, first, last)?
+        .into_iter()
+        .map(|local| resolve_local_time(&tz, local).naive_utc())
+        .filter(|at| *at >= from && *at <= to)
+        .collect())
+}
+
+/// Matches planned occurrences of one activity against its logs.
+///
+/// Occurrences are processed in chronological order; each takes the closest
+/// still-unmatched log within its tolerance window, so one log never fulfils
+/// two occurrences.
+///
+/// # Arguments
+/// * `occurrences` - `(plan, planned time)` pairs for a single activity
+/// * `logs` - `(log id, logged at, raw timestamp)` for the same activity
+/// * `now` - Occurrences whose window is still open become `Pending`
+///
+/// # Returns
+/// Matched occurrences in chronological order and the IDs of matched logs.
+pub fn match_planned_occurrences(
+    occurrences: &[(&PlannedActivity, chrono::NaiveDateTime)],
+    logs: &[(i32, chrono::NaiveDateTime, String)],
+    now: chrono::NaiveDateTime,
+) -> (Vec<PlannedOccurrence>, Vec<i32>) {
+    let mut ordered: Vec<&(&PlannedActivity, chrono::NaiveDateTime)> = occurrences.iter().collect();
+    ordered.sort_by_key(|(_, at)| *at);
+
+    let mut used = vec![false; logs.len()];
+    let mut matched_log_ids = Vec::new();
+    let mut result = Vec::with_capacity(ordered.len());
+
+    for (plan, at) in ordered {
+        let tolerance = chrono::Duration::minutes(plan.tolerance_minutes as i64);
+
+        let best = logs
+            .iter()
+            .enumerate()
+            .filter(|(i, (_, logged_at, _))| !used[*i] && (*logged_at - *at).abs() <= tolerance)
+            .min_by_key(|(_, (_, logged_at, _))| (*logged_at - *at).abs())
+            .map(|(i, _)| i);
+
+        let (status, matched_log_id, matched_logged_at) = match best {
+            Some(i) => {
+                used[i] = true;
+                matched_log_ids.push(logs[i].0);
+                (
+                    PlannedOccurrenceStatus::Done,
+                    Some(logs[i].0),
+                    Some(logs[i].2.clone()),
+                )
+            }
+            None if *at + tolerance > now => (PlannedOccurrenceStatus::Pending, None, None),
+            None => (PlannedOccurrenceStatus::Missed, None, None),
+        };
+
+        result.push(PlannedOccurrence {
+            planned_activity_id: plan.id,
+            activity_id: plan.activity_id,
+            scheduled_at: at.format("%Y-%m-%dT%H:%M:%SZ").to_string(),
+            status,
+            matched_log_id,
+            matched_logged_at,
+        });
+    }
+
+    (result, matched_log_ids)
+}
+
 /// Custom schema validator to ensure activity_id and group_id are mutually exclusive
 fn validate_goal_target_exclusivity(
     request: &SetActivityGoalRequest,
@@ -415,3 +909,199 @@ fn validate_optional_icon(icon: &str) -> Result<(), validator::ValidationError>
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{NaiveDate, NaiveDateTime};
+
+    fn plan(days_of_week: Vec<i32>, interval_weeks: i32) -> PlannedActivity {
+        PlannedActivity {
+            id: 1,
+            activity_id: 10,
+            days_of_week,
+            time_of_day: "07:00".to_string(),
+            timezone: "UTC".to_string(),
+            interval_weeks,
+            tolerance_minutes: 60,
+            start_date: "2025-01-06".to_string(), // Monday
+            end_date: None,
+            reminder_enabled: false,
+            reminder_minutes_before: 0,
+            last_reminded_at: None,
+            created_at: "2025-01-01 00:00:00".to_string(),
+            deleted_at: None,
+        }
+    }
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    fn datetime(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S").unwrap()
+    }
+
+    #[test]
+    fn test_expand_plan_occurrences_weekly() {
+        // Tuesday and Thursday
+        let plan = plan(vec![2, 4], 1);
+        let occurrences = expand_plan_occurrences(&plan, date("2025-01-01"), date("2025-01-19"))
+            .expect("Failed to expand");
+
+        assert_eq!(
+            occurrences,
+            vec![
+                datetime("2025-01-07 07:00:00"),
+                datetime("2025-01-09 07:00:00"),
+                datetime("2025-01-14 07:00:00"),
+                datetime("2025-01-16 07:00:00"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_expand_plan_occurrences_every_other_week() {
+        let plan = plan(vec![2], 2);
+        let occurrences = expand_plan_occurrences(&plan, date("2025-01-06"), date("2025-02-02"))
+            .expect("Failed to expand");
+
+        assert_eq!(
+            occurrences,
+            vec![
+                datetime("2025-01-07 07:00:00"),
+                datetime("2025-01-21 07:00:00"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_expand_plan_occurrences_respects_end_date() {
+        let mut plan = plan(vec![1, 2, 3, 4, 5], 1);
+        plan.end_date = Some("2025-01-08".to_string());
+        let occurrences = expand_plan_occurrences(&plan, date("2025-01-01"), date("2025-01-31"))
+            .expect("Failed to expand");
+
+        assert_eq!(occurrences.len(), 3); // Mon 6, Tue 7, Wed 8
+    }
+
+    #[test]
+    fn test_match_planned_occurrences() {
+        let plan = plan(vec![2, 4], 1);
+        let occurrences = vec![
+            (&plan, datetime("2025-01-07 07:00:00")),
+            (&plan, datetime("2025-01-09 07:00:00")),
+            (&plan, datetime("2025-01-14 07:00:00")),
+        ];
+        let logs = vec![
+            // Within 60 minutes of Jan 7
+            (
+                1,
+                datetime("2025-01-07 07:40:00"),
+                "2025-01-07T07:40:00Z".to_string(),
+            ),
+            // Outside the window of Jan 9 - unplanned
+            (
+                2,
+                datetime("2025-01-09 12:00:00"),
+                "2025-01-09T12:00:00Z".to_string(),
+            ),
+        ];
+
+        let (matched, matched_ids) =
+            match_planned_occurrences(&occurrences, &logs, datetime("2025-01-14 07:30:00"));
+
+        assert_eq!(matched_ids, vec![1]);
+        assert_eq!(matched[0].status, PlannedOccurrenceStatus::Done);
+        assert_eq!(matched[0].matched_log_id, Some(1));
+        assert_eq!(matched[1].status, PlannedOccurrenceStatus::Missed);
+        // Window (07:00 +/- 60 min) still open at 07:30
+        assert_eq!(matched[2].status, PlannedOccurrenceStatus::Pending);
+    }
+
+    #[test]
+    fn test_match_planned_occurrences_log_used_once() {
+        let mut plan = plan(vec![2], 1);
+        plan.tolerance_minutes = 720;
+        let occurrences = vec![
+            (&plan, datetime("2025-01-07 07:00:00")),
+            (&plan, datetime("2025-01-07 18:00:00")),
+        ];
+        let logs = vec![(1, datetime("2025-01-07 12:00:00"), String::new())];
+
+        let (matched, _) =
+            match_planned_occurrences(&occurrences, &logs, datetime("2025-01-10 00:00:00"));
+
+        assert_eq!(matched[0].status, PlannedOccurrenceStatus::Done);
+        assert_eq!(matched[1].status, PlannedOccurrenceStatus::Missed);
+    }
+
+    #[test]
+    fn test_validate_plan_fields() {
+        assert!(validate_plan_fields(
+            Some(&[1, 3]),
+            Some("07:00"),
+            Some("Europe/Berlin"),
+            Some("2025-01-01"),
+            None
+        )
+        .is_ok());
+        assert!(validate_plan_fields(Some(&[]), None, None, None, None).is_err());
+        assert!(validate_plan_fields(Some(&[7]), None, None, None, None).is_err());
+        assert!(validate_plan_fields(None, Some("7:00"), None, None, None).is_err());
+        assert!(validate_plan_fields(None, None, Some("Mars/Base"), None, None).is_err());
+        assert!(
+            validate_plan_fields(None, None, None, Some("2025-01-10"), Some("2025-01-01")).is_err()
+        );
+        assert!(validate_plan_fields(None, None, None, Some("01/10/2025"), None).is_err());
+    }
+
+    #[test]
+    fn test_plan_occurrences_between_uses_local_time() {
+        let mut plan = plan(vec![2], 1); // Tuesdays at 07:00
+        plan.timezone = "America/New_York".to_string();
+
+        // 07:00 EST is 12:00 UTC; after the March change 07:00 EDT is 11:00 UTC
+        let occurrences = plan_occurrences_between(
+            &plan,
+            datetime("2025-03-03 00:00:00"),
+            datetime("2025-03-12 00:00:00"),
+        )
+        .unwrap();
+        assert_eq!(
+            occurrences,
+            vec![
+                datetime("2025-03-04 12:00:00"),
+                datetime("2025-03-11 11:00:00")
+            ]
+        );
+
+        // Tuesday 21:00 in Tokyo is still Tuesday in UTC, at noon
+        plan.timezone = "Asia/Tokyo".to_string();
+        plan.time_of_day = "21:00".to_string();
+        let occurrences = plan_occurrences_between(
+            &plan,
+            datetime("2025-01-07 00:00:00"),
+            datetime("2025-01-07 23:59:59"),
+        )
+        .unwrap();
+        assert_eq!(occurrences, vec![datetime("2025-01-07 12:00:00")]);
+    }
+
+    #[test]
+    fn test_update_plan_request_rejects_end_date_with_clear() {
+        let request = UpdatePlannedActivityRequest {
+            days_of_week: None,
+            time_of_day: None,
+            timezone: None,
+            interval_weeks: None,
+            tolerance_minutes: None,
+            start_date: None,
+            end_date: Some("2025-02-01".to_string()),
+            clear_end_date: true,
+            reminder_enabled: None,
+            reminder_minutes_before: None,
+        };
+        assert!(request.validate().is_err());
+    }
+}
@@ -24,6 +24,19 @@ pub async fn get_activity_groups(
     })
 }
 
+#[tauri::command]
+#[specta::specta]
+pub async fn get_archived_activity_groups(
+    state: State<'_, AppState>,
+) -> Result<Vec<ActivityGroup>, CommandError> {
+    let repo = ActivityRepository::new(state.db.clone());
+
+    repo.get_archived_activity_groups().map_err(|e| {
+        error!("get_archived_activity_groups error: {}", e);
+        e.to_command_error()
+    })
+}
+
 // ========================================
 // Activity Log Queries
 // ========================================
@@ -137,3 +150,125 @@ pub async fn check_goal_progress(
             e.to_command_error()
         })
 }
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_group_frequency(
+    group_id: i32,
+    start_date: String,
+    end_date: String,
+    state: State<'_, AppState>,
+) -> Result<GroupFrequency, CommandError> {
+    let repo = ActivityRepository::new(state.db.clone());
+
+    repo.get_group_frequency(group_id, &start_date, &end_date)
+        .map_err(|e| {
+            error!(
+                "get_group_frequency error: {} (group_id: {}, start: {}, end: {})",
+                e, group_id, start_date, end_date
+            );
+            e.to_command_error()
+        })
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_group_trend(
+    group_id: i32,
+    period_days: i32,
+    current_time: String,
+    state: State<'_, AppState>,
+) -> Result<GroupTrend, CommandError> {
+    let repo = ActivityRepository::new(state.db.clone());
+
+    repo.get_group_trend(group_id, period_days, &current_time)
+        .map_err(|e| {
+            error!(
+                "get_group_trend error: {} (group_id: {}, period_days: {}, current_time: {})",
+                e, group_id, period_days, current_time
+            );
+            e.to_command_error()
+        })
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_weekly_group_summary(
+    current_time: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<GroupWeeklySummary>, CommandError> {
+    let repo = ActivityRepository::new(state.db.clone());
+
+    repo.get_weekly_group_summary(&current_time).map_err(|e| {
+        error!(
+            "get_weekly_group_summary error: {} (current_time: {})",
+            e, current_time
+        );
+        e.to_command_error()
+    })
+}
+
+// ========================================
+// Planned Activity Queries
+// ========================================
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_planned_activities(
+    activity_id: Option<i32>,
+    group_id: Option<i32>,
+    state: State<'_, AppState>,
+) -> Result<Vec<PlannedActivity>, CommandError> {
+    let repo = ActivityRepository::new(state.db.clone());
+
+    repo.get_planned_activities(activity_id, group_id)
+        .map_err(|e| {
+            error!(
+                "get_planned_activities error: {} (activity_id: {:?}, group_id: {:?})",
+                e, activity_id, group_id
+            );
+            e.to_command_error()
+        })
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_activity_adherence(
+    activity_id: i32,
+    start_date: String,
+    end_date: String,
+    current_time: String,
+    state: State<'_, AppState>,
+) -> Result<AdherenceReport, CommandError> {
+    let repo = ActivityRepository::new(state.db.clone());
+
+    repo.get_activity_adherence(activity_id, &start_date, &end_date, &current_time)
+        .map_err(|e| {
+            error!(
+                "get_activity_adherence error: {} (activity_id: {}, start: {}, end: {}, current_time: {})",
+                e, activity_id, start_date, end_date, current_time
+            );
+            e.to_command_error()
+        })
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_group_adherence(
+    group_id: i32,
+    start_date: String,
+    end_date: String,
+    current_time: String,
+    state: State<'_, AppState>,
+) -> Result<AdherenceReport, CommandError> {
+    let repo = ActivityRepository::new(state.db.clone());
+
+    repo.get_group_adherence(group_id, &start_date, &end_date, &current_time)
+        .map_err(|e| {
+            error!(
+                "get_group_adherence error: {} (group_id: {}, start: {}, end: {}, current_time: {})",
+                e, group_id, start_date, end_date, current_time
+            );
+            e.to_command_error()
+        })
+}
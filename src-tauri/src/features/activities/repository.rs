@@ -15,6 +15,7 @@
 
 use super::models::*;
 use crate::db::Database;
+use crate::features::scheduling::models::system_timezone;
 use crate::types::activity::{GoalType, HexColor};
 use rusqlite::OptionalExtension;
 use std::sync::Arc;
@@ -88,6 +89,10 @@ impl ActivityRepository {
             id,
             name: name.to_string(),
             description: description.map(|s| s.to_string()),
+            color: None,
+            icon: None,
+            sort_order: None,
+            archived_at: None,
             created_at,
             deleted_at: None,
         })
@@ -229,7 +234,10 @@ impl ActivityRepository {
         Ok(())
     }
 
-    /// Gets all non-deleted activity groups.
+    /// Gets all non-deleted, non-archived activity groups (the set shown in pickers).
+    ///
+    /// Groups are returned in their user-defined order; groups that were never
+    /// positioned follow alphabetically.
     ///
     /// # Returns
     /// * `Ok(Vec<ActivityGroup>)` - List of all active activity groups
@@ -238,28 +246,198 @@ impl ActivityRepository {
         let conn = self.db.get_connection();
         let conn = conn.lock();
 
-        let mut stmt = conn.prepare(
-            "SELECT id, name, description, CAST(created_at AS VARCHAR), CAST(deleted_at AS VARCHAR)
+        self.get_activity_groups_with_conn(&conn, false)
+    }
+
+    /// Gets archived (but not deleted) activity groups, ordered by name.
+    ///
+    /// # Returns
+    /// * `Ok(Vec<ActivityGroup>)` - List of archived activity groups
+    /// * `Err(ActivityError)` - On database error
+    pub fn get_archived_activity_groups(&self) -> Result<Vec<ActivityGroup>, ActivityError> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock();
+
+        self.get_activity_groups_with_conn(&conn, true)
+    }
+
+    /// Helper listing non-deleted groups that are either archived or not.
+    fn get_activity_groups_with_conn(
+        &self,
+        conn: &rusqlite::Connection,
+        archived: bool,
+    ) -> Result<Vec<ActivityGroup>, ActivityError> {
+        let query = if archived {
+            "SELECT id, name, description, color, icon, sort_order, CAST(archived_at AS VARCHAR),
+                    CAST(created_at AS VARCHAR), CAST(deleted_at AS VARCHAR)
              FROM activity_groups
-             WHERE deleted_at IS NULL
-             ORDER BY name ASC",
-        )?;
+             WHERE deleted_at IS NULL AND archived_at IS NOT NULL
+             ORDER BY name ASC"
+        } else {
+            "SELECT id, name, description, color, icon, sort_order, CAST(archived_at AS VARCHAR),
+                    CAST(created_at AS VARCHAR), CAST(deleted_at AS VARCHAR)
+             FROM activity_groups
+             WHERE deleted_at IS NULL AND archived_at IS NULL
+             ORDER BY sort_order IS NULL, sort_order, name ASC"
+        };
 
+        let mut stmt = conn.prepare(query)?;
         let groups = stmt
-            .query_map([], |row| {
-                Ok(ActivityGroup {
-                    id: row.get(0)?,
-                    name: row.get(1)?,
-                    description: row.get(2)?,
-                    created_at: row.get(3)?,
-                    deleted_at: row.get(4)?,
-                })
-            })?
+            .query_map([], map_activity_group_row)?
             .collect::<Result<Vec<_>, _>>()?;
 
         Ok(groups)
     }
 
+    /// Sets the default color and icon of an activity group.
+    ///
+    /// Activities created in the group without their own color or icon inherit
+    /// these values. Existing activities are not changed. Passing `None` clears
+    /// the corresponding default.
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If activity group doesn't exist or is deleted
+    /// * `ActivityIconTooLong` - If icon exceeds 20 characters
+    /// * `Database` - On database errors
+    pub fn set_activity_group_appearance(
+        &self,
+        id: i32,
+        color: Option<&str>,
+        icon: Option<&str>,
+    ) -> Result<ActivityGroup, ActivityError> {
+        let icon = icon.map(|i| i.trim()).filter(|i| !i.is_empty());
+        if let Some(ic) = icon {
+            let icon_char_count = ic.chars().count();
+            if icon_char_count > 20 {
+                return Err(ActivityError::ActivityIconTooLong(icon_char_count));
+            }
+        }
+
+        let conn = self.db.get_connection();
+        let conn = conn.lock();
+
+        let updated = conn.execute(
+            "UPDATE activity_groups SET color = ?, icon = ? WHERE id = ? AND deleted_at IS NULL",
+            rusqlite::params![color, icon, id],
+        )?;
+        if updated == 0 {
+            return Err(ActivityError::GroupNotFound(id));
+        }
+
+        info!(
+            group_id = id,
+            has_color = color.is_some(),
+            has_icon = icon.is_some(),
+            "Updated activity group appearance"
+        );
+
+        self.get_activity_group_by_id_with_conn(&conn, id)
+    }
+
+    /// Archives an activity group.
+    ///
+    /// Unlike `delete_activity_group`, archiving does not touch the group's
+    /// activities, logs, goals or plans: the group only disappears from
+    /// `get_activity_groups` (and its activities from check-in pickers) while
+    /// history and reports stay intact. Archiving an archived group is a no-op.
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If activity group doesn't exist or is deleted
+    /// * `Database` - On database errors
+    pub fn archive_activity_group(&self, id: i32) -> Result<ActivityGroup, ActivityError> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock();
+
+        let updated = conn.execute(
+            "UPDATE activity_groups SET archived_at = COALESCE(archived_at, datetime('now'))
+             WHERE id = ? AND deleted_at IS NULL",
+            rusqlite::params![id],
+        )?;
+        if updated == 0 {
+            return Err(ActivityError::GroupNotFound(id));
+        }
+
+        info!(group_id = id, "Archived activity group");
+
+        self.get_activity_group_by_id_with_conn(&conn, id)
+    }
+
+    /// Restores an archived activity group to pickers.
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If activity group doesn't exist or is deleted
+    /// * `Database` - On database errors
+    pub fn unarchive_activity_group(&self, id: i32) -> Result<ActivityGroup, ActivityError> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock();
+
+        let updated = conn.execute(
+            "UPDATE activity_groups SET archived_at = NULL WHERE id = ? AND deleted_at IS NULL",
+            rusqlite::params![id],
+        )?;
+        if updated == 0 {
+            return Err(ActivityError::GroupNotFound(id));
+        }
+
+        info!(group_id = id, "Unarchived activity group");
+
+        self.get_activity_group_by_id_with_conn(&conn, id)
+    }
+
+    /// Reorders activity groups.
+    ///
+    /// The listed groups take positions 1..n in the given order. Any other
+    /// non-deleted group (including archived ones) keeps its relative order and
+    /// is placed after them, so positions always form a single sequence.
+    ///
+    /// # Returns
+    /// * `Ok(Vec<ActivityGroup>)` - The visible groups in their new order
+    ///
+    /// # Errors
+    /// * `InvalidOrder` - If an ID is listed more than once
+    /// * `GroupNotFound` - If a listed group doesn't exist or is deleted
+    /// * `Database` - On database errors
+    pub fn reorder_activity_groups(
+        &self,
+        ordered_ids: &[i32],
+    ) -> Result<Vec<ActivityGroup>, ActivityError> {
+        validate_unique_ids(ordered_ids)?;
+
+        let conn = self.db.get_connection();
+        let mut conn = conn.lock();
+
+        let tx = conn.transaction_with_behavior(rusqlite::TransactionBehavior::Immediate)?;
+
+        let current_ids: Vec<i32> = {
+            let mut stmt = tx.prepare(
+                "SELECT id FROM activity_groups
+                 WHERE deleted_at IS NULL
+                 ORDER BY sort_order IS NULL, sort_order, name ASC",
+            )?;
+            let ids = stmt
+                .query_map([], |row| row.get(0))?
+                .collect::<Result<Vec<_>, _>>()?;
+            ids
+        };
+
+        if let Some(missing) = ordered_ids.iter().find(|id| !current_ids.contains(id)) {
+            return Err(ActivityError::GroupNotFound(*missing));
+        }
+
+        for (position, id) in merge_order(ordered_ids, &current_ids).iter().enumerate() {
+            tx.execute(
+                "UPDATE activity_groups SET sort_order = ? WHERE id = ?",
+                rusqlite::params![position as i32 + 1, id],
+            )?;
+        }
+
+        tx.commit()?;
+
+        info!(group_count = ordered_ids.len(), "Reordered activity groups");
+
+        self.get_activity_groups_with_conn(&conn, false)
+    }
+
     /// Helper method to get an activity group by ID using an existing connection.
     /// Follows the `_with_conn` pattern to avoid deadlocks.
     ///
@@ -276,19 +454,12 @@ impl ActivityRepository {
         id: i32,
     ) -> Result<ActivityGroup, ActivityError> {
         conn.query_row(
-            "SELECT id, name, description, CAST(created_at AS VARCHAR), CAST(deleted_at AS VARCHAR)
+            "SELECT id, name, description, color, icon, sort_order, CAST(archived_at AS VARCHAR),
+                    CAST(created_at AS VARCHAR), CAST(deleted_at AS VARCHAR)
              FROM activity_groups
              WHERE id = ? AND deleted_at IS NULL",
             rusqlite::params![id],
-            |row| {
-                Ok(ActivityGroup {
-                    id: row.get(0)?,
-                    name: row.get(1)?,
-                    description: row.get(2)?,
-                    created_at: row.get(3)?,
-                    deleted_at: row.get(4)?,
-                })
-            },
+            map_activity_group_row,
         )
         .map_err(|e| match e {
             rusqlite::Error::QueryReturnedNoRows => ActivityError::GroupNotFound(id),
@@ -574,12 +745,17 @@ impl ActivityRepository {
             return Err(ActivityError::GroupNotFound(group_id));
         }
 
-        // Insert activity using RETURNING
-        let (id, created_at): (i32, String) = conn.query_row(
-            "INSERT INTO activities (group_id, name, color, icon) VALUES (?, ?, ?, ?)
-             RETURNING id, CAST(created_at AS VARCHAR)",
+        // Insert activity using RETURNING. Missing color/icon fall back to the
+        // group's defaults.
+        let (id, created_at, color, icon): (i32, String, Option<HexColor>, Option<String>) = conn
+            .query_row(
+            "INSERT INTO activities (group_id, name, color, icon)
+                 VALUES (?1, ?2,
+                         COALESCE(?3, (SELECT color FROM activity_groups WHERE id = ?1)),
+                         COALESCE(?4, (SELECT icon FROM activity_groups WHERE id = ?1)))
+                 RETURNING id, CAST(created_at AS VARCHAR), color, icon",
             rusqlite::params![group_id, name, color, icon],
-            |row| Ok((row.get(0)?, row.get(1)?)),
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
         )?;
 
         info!(
@@ -590,18 +766,12 @@ impl ActivityRepository {
             "Created activity"
         );
 
-        // Convert color string to HexColor (validation already done at request layer)
-        let color = color
-            .map(HexColor::new)
-            .transpose()
-            .map_err(|_| ActivityError::Database(rusqlite::Error::InvalidQuery))?;
-
         Ok(Activity {
             id,
             group_id,
             name: name.to_string(),
             color,
-            icon: icon.map(|s| s.to_string()),
+            icon,
             created_at,
             deleted_at: None,
         })
@@ -613,17 +783,26 @@ impl ActivityRepository {
     /// * `group_id` - Activity group ID to filter by
     ///
     /// # Returns
-    /// * `Ok(Vec<Activity>)` - List of activities in the group, ordered by name
+    /// * `Ok(Vec<Activity>)` - List of activities in the group, in their
+    ///   user-defined order (unpositioned activities follow by name)
     /// * `Err(ActivityError)` - On database error
     pub fn get_activities_by_group(&self, group_id: i32) -> Result<Vec<Activity>, ActivityError> {
         let conn = self.db.get_connection();
         let conn = conn.lock();
 
+        self.get_activities_by_group_with_conn(&conn, group_id)
+    }
+
+    fn get_activities_by_group_with_conn(
+        &self,
+        conn: &rusqlite::Connection,
+        group_id: i32,
+    ) -> Result<Vec<Activity>, ActivityError> {
         let mut stmt = conn.prepare(
             "SELECT id, group_id, name, color, icon, CAST(created_at AS VARCHAR), CAST(deleted_at AS VARCHAR)
              FROM activities
              WHERE group_id = ? AND deleted_at IS NULL
-             ORDER BY name ASC"
+             ORDER BY sort_order IS NULL, sort_order, name ASC"
         )?;
 
         let activities = stmt
@@ -643,6 +822,79 @@ impl ActivityRepository {
         Ok(activities)
     }
 
+    /// Reorders the activities of a group.
+    ///
+    /// The listed activities take positions 1..n in the given order; other
+    /// activities in the group keep their relative order after them.
+    ///
+    /// # Returns
+    /// * `Ok(Vec<Activity>)` - The group's activities in their new order
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group doesn't exist or is deleted
+    /// * `InvalidOrder` - If an ID is repeated or doesn't belong to the group
+    /// * `Database` - On database errors
+    pub fn reorder_activities(
+        &self,
+        group_id: i32,
+        ordered_ids: &[i32],
+    ) -> Result<Vec<Activity>, ActivityError> {
+        validate_unique_ids(ordered_ids)?;
+
+        let conn = self.db.get_connection();
+        let mut conn = conn.lock();
+
+        let tx = conn.transaction_with_behavior(rusqlite::TransactionBehavior::Immediate)?;
+
+        let group_exists: bool = tx
+            .query_row(
+                "SELECT 1 FROM activity_groups WHERE id = ? AND deleted_at IS NULL",
+                rusqlite::params![group_id],
+                |_| Ok(true),
+            )
+            .optional()?
+            .unwrap_or(false);
+        if !group_exists {
+            return Err(ActivityError::GroupNotFound(group_id));
+        }
+
+        let current_ids: Vec<i32> = {
+            let mut stmt = tx.prepare(
+                "SELECT id FROM activities
+                 WHERE group_id = ? AND deleted_at IS NULL
+                 ORDER BY sort_order IS NULL, sort_order, name ASC",
+            )?;
+            let ids = stmt
+                .query_map([group_id], |row| row.get(0))?
+                .collect::<Result<Vec<_>, _>>()?;
+            ids
+        };
+
+        if let Some(foreign) = ordered_ids.iter().find(|id| !current_ids.contains(id)) {
+            return Err(ActivityError::InvalidOrder(format!(
+                "activity {} does not belong to group {}",
+                foreign, group_id
+            )));
+        }
+
+        for (position, id) in merge_order(ordered_ids, &current_ids).iter().enumerate() {
+            tx.execute(
+                "UPDATE activities SET sort_order = ? WHERE id = ?",
+                rusqlite::params![position as i32 + 1, id],
+            )?;
+        }
+
+        tx.commit()?;
+
+        info!(
+            group_id = group_id,
+            activity_count = ordered_ids.len(),
+            "Reordered activities"
+        );
+
+        self.get_activities_by_group_with_conn(&conn, group_id)
+    }
+
     // ========================================
     // Activity Goals
     // ========================================
@@ -879,91 +1131,602 @@ impl ActivityRepository {
     }
 
     // ========================================
-    // Reporting Queries
+    // Planned Activities
     // ========================================
 
-    /// Calculates activity frequency (days per week).
-    ///
-    /// # Arguments
-    /// * `activity_id` - Activity ID to analyze
-    /// * `start_date` - Period start (ISO 8601)
-    /// * `end_date` - Period end (ISO 8601)
+    /// Plans an activity with weekly recurrence.
     ///
     /// # Returns
-    /// * `Ok(ActivityFrequency)` - Frequency report
-    pub fn get_activity_frequency(
+    /// * `Ok(PlannedActivity)` - The created plan
+    ///
+    /// # Errors
+    /// * `ActivityNotFound` - If the activity doesn't exist or is deleted
+    /// * `InvalidPlan` - If weekdays, time, or dates are invalid
+    pub fn create_planned_activity(
         &self,
-        activity_id: i32,
-        start_date: &str,
-        end_date: &str,
-    ) -> Result<ActivityFrequency, ActivityError> {
+        request: &CreatePlannedActivityRequest,
+    ) -> Result<PlannedActivity, ActivityError> {
         let conn = self.db.get_connection();
         let conn = conn.lock();
 
-        // Count unique days and total logs
-        let (unique_days, total_logs): (i32, i32) = conn.query_row(
-            "SELECT
-                COUNT(DISTINCT DATE(logged_at)) as unique_days,
-                COUNT(*) as total_logs
-             FROM activity_logs
-             WHERE activity_id = ?
-               AND logged_at >= ?
-               AND logged_at <= ?
-               AND deleted_at IS NULL",
-            rusqlite::params![activity_id, start_date, end_date],
-            |row| Ok((row.get(0)?, row.get(1)?)),
+        self.create_planned_activity_with_conn(&conn, request)
+    }
+
+    /// Helper: Plans an activity using an existing connection (or transaction).
+    ///
+    /// # Notes
+    /// This is a `_with_conn` helper - it accepts a connection reference
+    /// and does NOT acquire locks. Only use from methods that already hold the lock.
+    pub(crate) fn create_planned_activity_with_conn(
+        &self,
+        conn: &rusqlite::Connection,
+        request: &CreatePlannedActivityRequest,
+    ) -> Result<PlannedActivity, ActivityError> {
+        // Plans without an explicit timezone follow the system timezone
+        let timezone = request.timezone.clone().unwrap_or_else(system_timezone);
+        validate_plan_fields(
+            Some(&request.days_of_week),
+            Some(&request.time_of_day),
+            Some(&timezone),
+            Some(&request.start_date),
+            request.end_date.as_deref(),
         )?;
 
-        // Calculate period duration in days
-        let period_duration_days: f64 = conn.query_row(
-            "SELECT CAST((julianday(?) - julianday(?)) AS REAL)",
-            rusqlite::params![end_date, start_date],
+        let activity_exists: bool = conn.query_row(
+            "SELECT EXISTS(SELECT 1 FROM activities WHERE id = ? AND deleted_at IS NULL)",
+            [request.activity_id],
             |row| row.get(0),
         )?;
+        if !activity_exists {
+            return Err(ActivityError::ActivityNotFound(request.activity_id));
+        }
 
-        // Calculate days per week
-        let num_weeks = period_duration_days / 7.0;
-        let days_per_week = if num_weeks > 0.0 {
-            (unique_days as f64) / num_weeks
-        } else {
-            0.0
-        };
+        let id: i32 = conn.query_row(
+            "INSERT INTO planned_activities
+                (activity_id, days_of_week, time_of_day, timezone, interval_weeks,
+                 tolerance_minutes, start_date, end_date, reminder_enabled,
+                 reminder_minutes_before)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+             RETURNING id",
+            rusqlite::params![
+                request.activity_id,
+                format_days_of_week(&request.days_of_week),
+                &request.time_of_day,
+                &timezone,
+                request.interval_weeks.unwrap_or(1),
+                request
+                    .tolerance_minutes
+                    .unwrap_or(DEFAULT_PLAN_TOLERANCE_MINUTES),
+                &request.start_date,
+                &request.end_date,
+                request.reminder_enabled,
+                request.reminder_minutes_before.unwrap_or(0),
+            ],
+            |row| row.get(0),
+        )?;
 
-        Ok(ActivityFrequency {
-            activity_id,
-            unique_days,
-            total_logs,
-            days_per_week,
-            period_start: start_date.to_string(),
-            period_end: end_date.to_string(),
-        })
+        info!(
+            planned_activity_id = id,
+            activity_id = request.activity_id,
+            "Created planned activity"
+        );
+
+        self.get_planned_activity_with_conn(conn, id)
     }
 
-    /// Calculates activity trend (comparison with previous period).
-    ///
-    /// # Arguments
-    /// * `activity_id` - Activity ID to analyze
-    /// * `period_days` - Period length in days (e.g., 7 for weekly)
-    /// * `current_time` - End of current period (ISO 8601)
+    /// Updates a planned activity (only provided fields are changed;
+    /// `clear_end_date` removes the end date).
     ///
-    /// # Returns
-    /// * `Ok(ActivityTrend)` - Trend analysis
-    pub fn get_activity_trend(
+    /// # Errors
+    /// * `PlannedActivityNotFound` - If the plan doesn't exist or is deleted
+    /// * `InvalidPlan` - If the resulting plan is invalid
+    pub fn update_planned_activity(
         &self,
-        activity_id: i32,
-        period_days: i32,
-        current_time: &str,
-    ) -> Result<ActivityTrend, ActivityError> {
+        id: i32,
+        request: &UpdatePlannedActivityRequest,
+    ) -> Result<PlannedActivity, ActivityError> {
         let conn = self.db.get_connection();
         let conn = conn.lock();
 
-        // Calculate period boundaries using SQLite date functions
-        let (current_start, previous_start): (String, String) = conn.query_row(
-            "SELECT
-                datetime(?, '-' || ? || ' days') as current_start,
-                datetime(?, '-' || (? * 2) || ' days') as previous_start",
-            rusqlite::params![current_time, period_days, current_time, period_days],
-            |row| Ok((row.get(0)?, row.get(1)?)),
+        let current = self.get_planned_activity_with_conn(&conn, id)?;
+
+        let days_of_week = request.days_of_week.clone().unwrap_or(current.days_of_week);
+        let time_of_day = request.time_of_day.clone().unwrap_or(current.time_of_day);
+        let timezone = request.timezone.clone().unwrap_or(current.timezone);
+        let start_date = request.start_date.clone().unwrap_or(current.start_date);
+        let end_date = if request.clear_end_date {
+            None
+        } else {
+            request.end_date.clone().or(current.end_date)
+        };
+
+        // Validate the merged plan so e.g. a new start date can't pass an existing end date
+        validate_plan_fields(
+            Some(&days_of_week),
+            Some(&time_of_day),
+            Some(&timezone),
+            Some(&start_date),
+            end_date.as_deref(),
+        )?;
+
+        conn.execute(
+            "UPDATE planned_activities
+             SET days_of_week = ?, time_of_day = ?, timezone = ?, interval_weeks = ?,
+                 tolerance_minutes = ?, start_date = ?, end_date = ?, reminder_enabled = ?,
+                 reminder_minutes_before = ?
+             WHERE id = ? AND deleted_at IS NULL",
+            rusqlite::params![
+                format_days_of_week(&days_of_week),
+                &time_of_day,
+                &timezone,
+                request.interval_weeks.unwrap_or(current.interval_weeks),
+                request
+                    .tolerance_minutes
+                    .unwrap_or(current.tolerance_minutes),
+                &start_date,
+                &end_date,
+                request.reminder_enabled.unwrap_or(current.reminder_enabled),
+                request
+                    .reminder_minutes_before
+                    .unwrap_or(current.reminder_minutes_before),
+                id,
+            ],
+        )?;
+
+        info!(planned_activity_id = id, "Updated planned activity");
+
+        self.get_planned_activity_with_conn(&conn, id)
+    }
+
+    /// Soft-deletes a planned activity. Activity logs are not affected.
+    pub fn delete_planned_activity(&self, id: i32) -> Result<(), ActivityError> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock();
+
+        let rows_affected = conn.execute(
+            "UPDATE planned_activities SET deleted_at = datetime('now')
+             WHERE id = ? AND deleted_at IS NULL",
+            [id],
+        )?;
+
+        if rows_affected == 0 {
+            return Err(ActivityError::PlannedActivityNotFound(id));
+        }
+
+        info!(planned_activity_id = id, "Deleted planned activity");
+
+        Ok(())
+    }
+
+    /// Gets non-deleted plans, optionally filtered by activity or group.
+    pub fn get_planned_activities(
+        &self,
+        activity_id: Option<i32>,
+        group_id: Option<i32>,
+    ) -> Result<Vec<PlannedActivity>, ActivityError> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock();
+
+        self.get_planned_activities_with_conn(&conn, activity_id, group_id)
+    }
+
+    /// Gets non-deleted plans of non-deleted activities, each with its activity's name.
+    pub fn get_planned_activities_with_names(
+        &self,
+    ) -> Result<Vec<(PlannedActivity, String)>, ActivityError> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock();
+
+        let names: std::collections::HashMap<i32, String> = {
+            let mut stmt =
+                conn.prepare("SELECT id, name FROM activities WHERE deleted_at IS NULL")?;
+            let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+            rows.collect::<Result<_, _>>()?
+        };
+
+        let plans = self.get_planned_activities_with_conn(&conn, None, None)?;
+        Ok(plans
+            .into_iter()
+            .filter_map(|plan| {
+                let name = names.get(&plan.activity_id)?.clone();
+                Some((plan, name))
+            })
+            .collect())
+    }
+
+    /// Plan-versus-actual adherence for a single activity.
+    ///
+    /// # Arguments
+    /// * `activity_id` - Activity ID to analyze
+    /// * `start_date` - Period start (ISO 8601)
+    /// * `end_date` - Period end (ISO 8601)
+    /// * `current_time` - Occurrences whose window is still open at this time are pending (ISO 8601)
+    pub fn get_activity_adherence(
+        &self,
+        activity_id: i32,
+        start_date: &str,
+        end_date: &str,
+        current_time: &str,
+    ) -> Result<AdherenceReport, ActivityError> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock();
+
+        let activity_exists: bool = conn.query_row(
+            "SELECT EXISTS(SELECT 1 FROM activities WHERE id = ? AND deleted_at IS NULL)",
+            [activity_id],
+            |row| row.get(0),
+        )?;
+        if !activity_exists {
+            return Err(ActivityError::ActivityNotFound(activity_id));
+        }
+
+        let mut report = self.calculate_adherence_with_conn(
+            &conn,
+            &[activity_id],
+            start_date,
+            end_date,
+            current_time,
+        )?;
+        report.activity_id = Some(activity_id);
+
+        Ok(report)
+    }
+
+    /// Plan-versus-actual adherence across all non-deleted activities in a group.
+    ///
+    /// Logs only fulfil plans of the same activity; logs of any group activity
+    /// that match no plan count as unplanned.
+    pub fn get_group_adherence(
+        &self,
+        group_id: i32,
+        start_date: &str,
+        end_date: &str,
+        current_time: &str,
+    ) -> Result<AdherenceReport, ActivityError> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock();
+
+        self.get_activity_group_by_id_with_conn(&conn, group_id)?;
+
+        let activity_ids: Vec<i32> = {
+            let mut stmt = conn
+                .prepare("SELECT id FROM activities WHERE group_id = ? AND deleted_at IS NULL")?;
+            let rows = stmt.query_map([group_id], |row| row.get(0))?;
+            rows.collect::<Result<Vec<_>, _>>()?
+        };
+
+        let mut report = self.calculate_adherence_with_conn(
+            &conn,
+            &activity_ids,
+            start_date,
+            end_date,
+            current_time,
+        )?;
+        report.group_id = Some(group_id);
+
+        Ok(report)
+    }
+
+    /// Finds plan reminders that are due at `now` and haven't been sent yet.
+    ///
+    /// A reminder is due from `reminder_minutes_before` ahead of an occurrence
+    /// until shortly after it (`PLAN_REMINDER_LOOKBACK_SECONDS`), so reminders
+    /// without lead time still fire on the next check; reminders missed
+    /// entirely (e.g. app closed) are not sent late.
+    pub fn get_due_plan_reminders(
+        &self,
+        now: chrono::NaiveDateTime,
+    ) -> Result<Vec<PlannedActivityReminder>, ActivityError> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock();
+
+        let mut stmt = conn.prepare(
+            "SELECT p.id, a.name
+             FROM planned_activities p
+             JOIN activities a ON a.id = p.activity_id
+             WHERE p.reminder_enabled = 1
+               AND p.deleted_at IS NULL
+               AND a.deleted_at IS NULL",
+        )?;
+        let candidates = stmt
+            .query_map([], |row| {
+                Ok((row.get::<_, i32>(0)?, row.get::<_, String>(1)?))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut reminders = Vec::new();
+        for (id, activity_name) in candidates {
+            let plan = self.get_planned_activity_with_conn(&conn, id)?;
+            let lead = chrono::Duration::minutes(plan.reminder_minutes_before as i64);
+            let last_reminded = plan
+                .last_reminded_at
+                .as_deref()
+                .and_then(crate::utils::parse_timestamp);
+
+            // Occurrences within the lead time have their reminder window open
+            let lookback = chrono::Duration::seconds(PLAN_REMINDER_LOOKBACK_SECONDS);
+            let occurrences = plan_occurrences_between(&plan, now - lookback, now + lead)?;
+
+            let due = occurrences.into_iter().find(|at| {
+                let remind_at = *at - lead;
+                last_reminded.is_none_or(|last| last < remind_at)
+            });
+
+            if let Some(at) = due {
+                reminders.push(PlannedActivityReminder {
+                    planned_activity_id: plan.id,
+                    activity_id: plan.activity_id,
+                    activity_name,
+                    scheduled_at: at.format("%Y-%m-%dT%H:%M:%SZ").to_string(),
+                });
+            }
+        }
+
+        Ok(reminders)
+    }
+
+    /// Records that reminders were sent for the given plans
+    pub fn mark_plans_reminded(
+        &self,
+        planned_activity_ids: &[i32],
+        now: chrono::NaiveDateTime,
+    ) -> Result<(), ActivityError> {
+        if planned_activity_ids.is_empty() {
+            return Ok(());
+        }
+
+        let conn = self.db.get_connection();
+        let conn = conn.lock();
+
+        let query = format!(
+            "UPDATE planned_activities SET last_reminded_at = ? WHERE id IN {}",
+            crate::db::query_builder::generate_in_clause(planned_activity_ids.len())
+        );
+        let now_str = now.format("%Y-%m-%d %H:%M:%S").to_string();
+        let mut params: Vec<&dyn rusqlite::ToSql> = vec![&now_str];
+        for id in planned_activity_ids {
+            params.push(id);
+        }
+        conn.execute(&query, params.as_slice())?;
+
+        Ok(())
+    }
+
+    /// Helper: Fetches a non-deleted plan by ID using an existing connection.
+    fn get_planned_activity_with_conn(
+        &self,
+        conn: &rusqlite::Connection,
+        id: i32,
+    ) -> Result<PlannedActivity, ActivityError> {
+        conn.query_row(
+            "SELECT id, activity_id, days_of_week, time_of_day, interval_weeks, tolerance_minutes,
+                    start_date, end_date, reminder_enabled, reminder_minutes_before,
+                    CAST(last_reminded_at AS VARCHAR), CAST(created_at AS VARCHAR),
+                    CAST(deleted_at AS VARCHAR), timezone
+             FROM planned_activities
+             WHERE id = ? AND deleted_at IS NULL",
+            [id],
+            map_planned_activity_row,
+        )
+        .optional()?
+        .ok_or(ActivityError::PlannedActivityNotFound(id))
+    }
+
+    /// Helper: Lists non-deleted plans using an existing connection.
+    fn get_planned_activities_with_conn(
+        &self,
+        conn: &rusqlite::Connection,
+        activity_id: Option<i32>,
+        group_id: Option<i32>,
+    ) -> Result<Vec<PlannedActivity>, ActivityError> {
+        let mut stmt = conn.prepare(
+            "SELECT p.id, p.activity_id, p.days_of_week, p.time_of_day, p.interval_weeks,
+                    p.tolerance_minutes, p.start_date, p.end_date, p.reminder_enabled,
+                    p.reminder_minutes_before, CAST(p.last_reminded_at AS VARCHAR),
+                    CAST(p.created_at AS VARCHAR), CAST(p.deleted_at AS VARCHAR), p.timezone
+             FROM planned_activities p
+             JOIN activities a ON a.id = p.activity_id
+             WHERE p.deleted_at IS NULL
+               AND a.deleted_at IS NULL
+               AND (?1 IS NULL OR p.activity_id = ?1)
+               AND (?2 IS NULL OR a.group_id = ?2)
+             ORDER BY p.start_date ASC, p.id ASC",
+        )?;
+
+        let plans = stmt
+            .query_map(
+                rusqlite::params![activity_id, group_id],
+                map_planned_activity_row,
+            )?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(plans)
+    }
+
+    /// Helper: Builds an adherence report for a set of activities.
+    ///
+    /// # Notes
+    /// This is a `_with_conn` helper - it accepts a connection reference
+    /// and does NOT acquire locks. Only use from public methods that already hold the lock.
+    fn calculate_adherence_with_conn(
+        &self,
+        conn: &rusqlite::Connection,
+        activity_ids: &[i32],
+        start_date: &str,
+        end_date: &str,
+        current_time: &str,
+    ) -> Result<AdherenceReport, ActivityError> {
+        let parse = |value: &str| {
+            crate::utils::parse_timestamp(value)
+                .ok_or_else(|| ActivityError::InvalidPlan(format!("Invalid timestamp: {}", value)))
+        };
+        let period_start = parse(start_date)?;
+        let period_end = parse(end_date)?;
+        let now = parse(current_time)?;
+
+        let mut occurrences = Vec::new();
+        let mut unplanned = 0;
+
+        for &activity_id in activity_ids {
+            let plans = self.get_planned_activities_with_conn(conn, Some(activity_id), None)?;
+
+            let mut planned = Vec::new();
+            for plan in &plans {
+                for at in plan_occurrences_between(plan, period_start, period_end)? {
+                    planned.push((plan, at));
+                }
+            }
+
+            // Tolerance is at most 12 hours, so one day either side covers every match
+            let mut stmt = conn.prepare(
+                "SELECT id, logged_at
+                 FROM activity_logs
+                 WHERE activity_id = ?
+                   AND deleted_at IS NULL
+                   AND DATE(logged_at) >= DATE(?, '-1 day')
+                   AND DATE(logged_at) <= DATE(?, '+1 day')",
+            )?;
+            let logs: Vec<(i32, chrono::NaiveDateTime, String)> = stmt
+                .query_map(
+                    rusqlite::params![activity_id, start_date, end_date],
+                    |row| Ok((row.get::<_, i32>(0)?, row.get::<_, String>(1)?)),
+                )?
+                .collect::<Result<Vec<_>, _>>()?
+                .into_iter()
+                .filter_map(|(id, raw)| {
+                    crate::utils::parse_timestamp(&raw).map(|logged_at| (id, logged_at, raw))
+                })
+                .collect();
+
+            let (matched, matched_log_ids) = match_planned_occurrences(&planned, &logs, now);
+            occurrences.extend(matched);
+
+            unplanned += logs
+                .iter()
+                .filter(|(id, logged_at, _)| {
+                    *logged_at >= period_start
+                        && *logged_at <= period_end
+                        && !matched_log_ids.contains(id)
+                })
+                .count() as i32;
+        }
+
+        occurrences.sort_by(|a, b| a.scheduled_at.cmp(&b.scheduled_at));
+
+        let count = |status: PlannedOccurrenceStatus| {
+            occurrences.iter().filter(|o| o.status == status).count() as i32
+        };
+        let done = count(PlannedOccurrenceStatus::Done);
+        let missed = count(PlannedOccurrenceStatus::Missed);
+        let pending = count(PlannedOccurrenceStatus::Pending);
+
+        let adherence_percentage = if done + missed > 0 {
+            (done as f64 / (done + missed) as f64) * 100.0
+        } else {
+            0.0
+        };
+
+        Ok(AdherenceReport {
+            activity_id: None,
+            group_id: None,
+            period_start: start_date.to_string(),
+            period_end: end_date.to_string(),
+            planned: occurrences.len() as i32,
+            done,
+            missed,
+            pending,
+            unplanned,
+            adherence_percentage,
+            occurrences,
+        })
+    }
+
+    // ========================================
+    // Reporting Queries
+    // ========================================
+
+    /// Calculates activity frequency (days per week).
+    ///
+    /// # Arguments
+    /// * `activity_id` - Activity ID to analyze
+    /// * `start_date` - Period start (ISO 8601)
+    /// * `end_date` - Period end (ISO 8601)
+    ///
+    /// # Returns
+    /// * `Ok(ActivityFrequency)` - Frequency report
+    pub fn get_activity_frequency(
+        &self,
+        activity_id: i32,
+        start_date: &str,
+        end_date: &str,
+    ) -> Result<ActivityFrequency, ActivityError> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock();
+
+        // Count unique days and total logs
+        let (unique_days, total_logs): (i32, i32) = conn.query_row(
+            "SELECT
+                COUNT(DISTINCT DATE(logged_at)) as unique_days,
+                COUNT(*) as total_logs
+             FROM activity_logs
+             WHERE activity_id = ?
+               AND logged_at >= ?
+               AND logged_at <= ?
+               AND deleted_at IS NULL",
+            rusqlite::params![activity_id, start_date, end_date],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?;
+
+        // Calculate period duration in days
+        let period_duration_days: f64 = conn.query_row(
+            "SELECT CAST((julianday(?) - julianday(?)) AS REAL)",
+            rusqlite::params![end_date, start_date],
+            |row| row.get(0),
+        )?;
+
+        // Calculate days per week
+        let num_weeks = period_duration_days / 7.0;
+        let days_per_week = if num_weeks > 0.0 {
+            (unique_days as f64) / num_weeks
+        } else {
+            0.0
+        };
+
+        Ok(ActivityFrequency {
+            activity_id,
+            unique_days,
+            total_logs,
+            days_per_week,
+            period_start: start_date.to_string(),
+            period_end: end_date.to_string(),
+        })
+    }
+
+    /// Calculates activity trend (comparison with previous period).
+    ///
+    /// # Arguments
+    /// * `activity_id` - Activity ID to analyze
+    /// * `period_days` - Period length in days (e.g., 7 for weekly)
+    /// * `current_time` - End of current period (ISO 8601)
+    ///
+    /// # Returns
+    /// * `Ok(ActivityTrend)` - Trend analysis
+    pub fn get_activity_trend(
+        &self,
+        activity_id: i32,
+        period_days: i32,
+        current_time: &str,
+    ) -> Result<ActivityTrend, ActivityError> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock();
+
+        // Calculate period boundaries using SQLite date functions
+        let (current_start, previous_start): (String, String) = conn.query_row(
+            "SELECT
+                datetime(?, '-' || ? || ' days') as current_start,
+                datetime(?, '-' || (? * 2) || ' days') as previous_start",
+            rusqlite::params![current_time, period_days, current_time, period_days],
+            |row| Ok((row.get(0)?, row.get(1)?)),
         )?;
 
         // Count unique days in current period
@@ -993,25 +1756,12 @@ impl ActivityRepository {
         // Calculate change
         let change_days = current_period_days - previous_period_days;
 
-        // Calculate percentage change (handle division by zero)
-        let change_percentage = if previous_period_days == 0 {
-            if current_period_days > 0 {
-                100.0 // Improvement from nothing
-            } else {
-                0.0 // No change (both zero)
-            }
-        } else {
-            ((change_days as f64) / (previous_period_days as f64)) * 100.0
-        };
+        // Calculate percentage change (handles division by zero)
+        let change_percentage =
+            calculate_change_percentage(current_period_days, previous_period_days);
 
         // Determine trend (threshold: ±10%)
-        let trend = if change_percentage > 10.0 {
-            Trend::Improving
-        } else if change_percentage < -10.0 {
-            Trend::Declining
-        } else {
-            Trend::Stable
-        };
+        let trend = Trend::from_change_percentage(change_percentage);
 
         Ok(ActivityTrend {
             activity_id,
@@ -1023,7 +1773,174 @@ impl ActivityRepository {
         })
     }
 
-    /// Checks progress toward a goal.
+    /// Calculates group frequency (days per week).
+    ///
+    /// A day counts once if any non-deleted activity in the group was logged on it.
+    ///
+    /// # Arguments
+    /// * `group_id` - Activity group ID to analyze
+    /// * `start_date` - Period start (ISO 8601)
+    /// * `end_date` - Period end (ISO 8601)
+    ///
+    /// # Returns
+    /// * `Ok(GroupFrequency)` - Frequency report
+    /// * `Err(ActivityError::GroupNotFound)` - If group doesn't exist
+    pub fn get_group_frequency(
+        &self,
+        group_id: i32,
+        start_date: &str,
+        end_date: &str,
+    ) -> Result<GroupFrequency, ActivityError> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock();
+
+        self.get_activity_group_by_id_with_conn(&conn, group_id)?;
+
+        // Count unique days and total logs across the group's activities
+        let (unique_days, total_logs): (i32, i32) = conn.query_row(
+            "SELECT
+                COUNT(DISTINCT DATE(l.logged_at)) as unique_days,
+                COUNT(l.id) as total_logs
+             FROM activity_logs l
+             JOIN activities a ON a.id = l.activity_id
+             WHERE a.group_id = ?
+               AND a.deleted_at IS NULL
+               AND l.logged_at >= ?
+               AND l.logged_at <= ?
+               AND l.deleted_at IS NULL",
+            rusqlite::params![group_id, start_date, end_date],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?;
+
+        // Calculate period duration in days
+        let period_duration_days: f64 = conn.query_row(
+            "SELECT CAST((julianday(?) - julianday(?)) AS REAL)",
+            rusqlite::params![end_date, start_date],
+            |row| row.get(0),
+        )?;
+
+        // Calculate days per week
+        let num_weeks = period_duration_days / 7.0;
+        let days_per_week = if num_weeks > 0.0 {
+            (unique_days as f64) / num_weeks
+        } else {
+            0.0
+        };
+
+        Ok(GroupFrequency {
+            group_id,
+            unique_days,
+            total_logs,
+            days_per_week,
+            period_start: start_date.to_string(),
+            period_end: end_date.to_string(),
+        })
+    }
+
+    /// Calculates group trend (comparison with previous period).
+    ///
+    /// # Arguments
+    /// * `group_id` - Activity group ID to analyze
+    /// * `period_days` - Period length in days (e.g., 7 for weekly)
+    /// * `current_time` - End of current period (ISO 8601)
+    ///
+    /// # Returns
+    /// * `Ok(GroupTrend)` - Trend analysis
+    /// * `Err(ActivityError::GroupNotFound)` - If group doesn't exist
+    pub fn get_group_trend(
+        &self,
+        group_id: i32,
+        period_days: i32,
+        current_time: &str,
+    ) -> Result<GroupTrend, ActivityError> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock();
+
+        self.get_activity_group_by_id_with_conn(&conn, group_id)?;
+
+        let (current_start, previous_start): (String, String) = conn.query_row(
+            "SELECT
+                datetime(?, '-' || ? || ' days') as current_start,
+                datetime(?, '-' || (? * 2) || ' days') as previous_start",
+            rusqlite::params![current_time, period_days, current_time, period_days],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?;
+
+        let (current_period_days, previous_period_days) = self
+            .count_group_period_days_with_conn(
+                &conn,
+                Some(group_id),
+                &previous_start,
+                &current_start,
+                current_time,
+            )?
+            .into_iter()
+            .next()
+            .map(|(_, _, current, previous)| (current, previous))
+            .unwrap_or((0, 0));
+
+        let change_days = current_period_days - previous_period_days;
+        let change_percentage =
+            calculate_change_percentage(current_period_days, previous_period_days);
+
+        Ok(GroupTrend {
+            group_id,
+            current_period_days,
+            previous_period_days,
+            change_days,
+            change_percentage,
+            trend: Trend::from_change_percentage(change_percentage),
+        })
+    }
+
+    /// Weekly summary for every non-deleted group in a single query.
+    ///
+    /// Compares the 7 days ending at `current_time` with the 7 days before.
+    ///
+    /// # Arguments
+    /// * `current_time` - End of current week (ISO 8601)
+    ///
+    /// # Returns
+    /// * `Ok(Vec<GroupWeeklySummary>)` - One entry per group, ordered by name
+    pub fn get_weekly_group_summary(
+        &self,
+        current_time: &str,
+    ) -> Result<Vec<GroupWeeklySummary>, ActivityError> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock();
+
+        let (current_start, previous_start): (String, String) = conn.query_row(
+            "SELECT datetime(?, '-7 days'), datetime(?, '-14 days')",
+            rusqlite::params![current_time, current_time],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?;
+
+        let rows = self.count_group_period_days_with_conn(
+            &conn,
+            None,
+            &previous_start,
+            &current_start,
+            current_time,
+        )?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(group_id, group_name, current, previous)| {
+                let change_percentage = calculate_change_percentage(current, previous);
+                GroupWeeklySummary {
+                    group_id,
+                    group_name,
+                    days_per_week: current,
+                    previous_week_days: previous,
+                    change_days: current - previous,
+                    change_percentage,
+                    trend: Trend::from_change_percentage(change_percentage),
+                }
+            })
+            .collect())
+    }
+
+    /// Checks progress toward a goal.
     ///
     /// # Arguments
     /// * `goal_id` - Goal ID to check
@@ -1129,366 +2046,849 @@ impl ActivityRepository {
             }
         };
 
-        // Calculate progress percentage
-        let percentage = (current_value as f64 / goal.target_value as f64) * 100.0;
-        let is_achieved = percentage >= 100.0;
+        // Calculate progress percentage
+        let percentage = (current_value as f64 / goal.target_value as f64) * 100.0;
+        let is_achieved = percentage >= 100.0;
+
+        Ok(GoalProgress {
+            goal_id,
+            current_value,
+            target_value: goal.target_value,
+            percentage,
+            is_achieved,
+            period_start,
+            period_end: current_time.to_string(),
+        })
+    }
+
+    /// Helper: Counts unique days per group for two adjacent periods.
+    ///
+    /// Uses conditional aggregation so all groups are counted in one pass.
+    /// Groups without logs are included with zero counts.
+    ///
+    /// # Returns
+    /// `(group_id, group_name, current_days, previous_days)` ordered by group name,
+    /// where current is `[current_start, current_end]` and previous is
+    /// `[previous_start, current_start)`.
+    ///
+    /// # Notes
+    /// This is a `_with_conn` helper - it accepts a connection reference
+    /// and does NOT acquire locks. Only use from public methods that already hold the lock.
+    fn count_group_period_days_with_conn(
+        &self,
+        conn: &rusqlite::Connection,
+        group_id: Option<i32>,
+        previous_start: &str,
+        current_start: &str,
+        current_end: &str,
+    ) -> Result<Vec<(i32, String, i32, i32)>, ActivityError> {
+        let mut stmt = conn.prepare(
+            "SELECT g.id, g.name,
+                COUNT(DISTINCT CASE WHEN l.logged_at >= ?2 AND l.logged_at <= ?3
+                                    THEN DATE(l.logged_at) END) as current_days,
+                COUNT(DISTINCT CASE WHEN l.logged_at >= ?1 AND l.logged_at < ?2
+                                    THEN DATE(l.logged_at) END) as previous_days
+             FROM activity_groups g
+             LEFT JOIN activities a
+                ON a.group_id = g.id AND a.deleted_at IS NULL
+             LEFT JOIN activity_logs l
+                ON l.activity_id = a.id
+               AND l.deleted_at IS NULL
+               AND l.logged_at >= ?1
+               AND l.logged_at <= ?3
+             WHERE g.deleted_at IS NULL
+               AND (?4 IS NULL OR g.id = ?4)
+             GROUP BY g.id, g.name
+             ORDER BY g.name ASC",
+        )?;
+
+        let rows = stmt.query_map(
+            rusqlite::params![previous_start, current_start, current_end, group_id],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+        )?;
+
+        Ok(rows.collect::<Result<Vec<_>, _>>()?)
+    }
+
+    /// Helper: Counts unique days with activity logs for given activities.
+    ///
+    /// # Notes
+    /// This is a `_with_conn` helper - it accepts a connection reference
+    /// and does NOT acquire locks. Only use from public methods that already hold the lock.
+    fn calculate_unique_days_for_activities_with_conn(
+        &self,
+        conn: &rusqlite::Connection,
+        activity_ids: &[i32],
+        start_date: &str,
+        end_date: &str,
+    ) -> Result<i32, ActivityError> {
+        if activity_ids.is_empty() {
+            return Ok(0);
+        }
+
+        // Build placeholders for IN clause
+        let placeholders = activity_ids
+            .iter()
+            .map(|_| "?")
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let query = format!(
+            "SELECT COUNT(DISTINCT DATE(logged_at))
+             FROM activity_logs
+             WHERE activity_id IN ({})
+               AND logged_at >= ?
+               AND logged_at <= ?
+               AND deleted_at IS NULL",
+            placeholders
+        );
+
+        let mut stmt = conn.prepare(&query)?;
+
+        // Build params: activity_ids + start_date + end_date
+        let mut params: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
+        for aid in activity_ids {
+            params.push(Box::new(*aid));
+        }
+        params.push(Box::new(start_date.to_string()));
+        params.push(Box::new(end_date.to_string()));
+
+        let params_refs: Vec<&dyn rusqlite::ToSql> = params.iter().map(|p| p.as_ref()).collect();
+
+        let unique_days: i32 = stmt.query_row(params_refs.as_slice(), |row| row.get(0))?;
+
+        Ok(unique_days)
+    }
+}
+
+/// Maps an `activity_groups` row (columns 0-8) to an `ActivityGroup`
+pub(crate) fn map_activity_group_row(row: &rusqlite::Row) -> rusqlite::Result<ActivityGroup> {
+    Ok(ActivityGroup {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        description: row.get(2)?,
+        color: row.get::<_, Option<HexColor>>(3)?,
+        icon: row.get(4)?,
+        sort_order: row.get(5)?,
+        archived_at: row.get(6)?,
+        created_at: row.get(7)?,
+        deleted_at: row.get(8)?,
+    })
+}
+
+fn validate_unique_ids(ids: &[i32]) -> Result<(), ActivityError> {
+    let mut seen = std::collections::HashSet::new();
+    match ids.iter().find(|id| !seen.insert(**id)) {
+        Some(duplicate) => Err(ActivityError::InvalidOrder(format!(
+            "id {} listed more than once",
+            duplicate
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// Places the requested IDs first, followed by the remaining current IDs in
+/// their existing order.
+fn merge_order(requested: &[i32], current: &[i32]) -> Vec<i32> {
+    requested
+        .iter()
+        .copied()
+        .chain(current.iter().copied().filter(|id| !requested.contains(id)))
+        .collect()
+}
+
+/// Maps a `planned_activities` row (columns 0-13) to a `PlannedActivity`
+fn map_planned_activity_row(row: &rusqlite::Row) -> rusqlite::Result<PlannedActivity> {
+    let days: String = row.get(2)?;
+    Ok(PlannedActivity {
+        id: row.get(0)?,
+        activity_id: row.get(1)?,
+        days_of_week: parse_days_of_week(&days),
+        time_of_day: row.get(3)?,
+        timezone: row.get(13)?,
+        interval_weeks: row.get(4)?,
+        tolerance_minutes: row.get(5)?,
+        start_date: row.get(6)?,
+        end_date: row.get(7)?,
+        reminder_enabled: row.get(8)?,
+        reminder_minutes_before: row.get(9)?,
+        last_reminded_at: row.get(10)?,
+        created_at: row.get(11)?,
+        deleted_at: row.get(12)?,
+    })
+}
+
+/// Stores weekdays as a sorted, comma-separated list (e.g. "2,4")
+fn format_days_of_week(days: &[i32]) -> String {
+    let mut days = days.to_vec();
+    days.sort_unstable();
+    days.dedup();
+    days.iter()
+        .map(|d| d.to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn parse_days_of_week(days: &str) -> Vec<i32> {
+    days.split(',')
+        .filter_map(|d| d.trim().parse().ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::Database;
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    fn setup_test_repo() -> (ActivityRepository, TempDir) {
+        let temp_dir = TempDir::new().expect("Failed to create temp directory");
+        let db = Arc::new(
+            Database::new(temp_dir.path().to_path_buf()).expect("Failed to create database"),
+        );
+        (ActivityRepository::new(db), temp_dir)
+    }
+
+    #[test]
+    fn test_create_activity_group() {
+        let (repo, _temp_dir) = setup_test_repo();
+
+        let group = repo
+            .create_activity_group("Exercise", Some("Physical activities"))
+            .expect("Failed to create activity group");
+
+        assert_eq!(group.name, "Exercise");
+        assert_eq!(group.description, Some("Physical activities".to_string()));
+        assert!(group.id > 0);
+        assert!(group.deleted_at.is_none());
+    }
+
+    #[test]
+    fn test_create_activity_group_empty_name() {
+        let (repo, _temp_dir) = setup_test_repo();
+
+        let result = repo.create_activity_group("", None);
+
+        assert!(matches!(result, Err(ActivityError::EmptyGroupName)));
+    }
+
+    #[test]
+    fn test_create_activity_group_name_too_long() {
+        let (repo, _temp_dir) = setup_test_repo();
+
+        let long_name = "a".repeat(101);
+        let result = repo.create_activity_group(&long_name, None);
+
+        assert!(matches!(result, Err(ActivityError::GroupNameTooLong(101))));
+    }
+
+    #[test]
+    fn test_update_activity_group() {
+        let (repo, _temp_dir) = setup_test_repo();
+
+        let group = repo
+            .create_activity_group("Exercise", Some("Physical activities"))
+            .expect("Failed to create activity group");
+
+        let updated = repo
+            .update_activity_group(group.id, Some("Workout"), Some("All workouts"))
+            .expect("Failed to update activity group");
+
+        assert_eq!(updated.id, group.id);
+        assert_eq!(updated.name, "Workout");
+        assert_eq!(updated.description, Some("All workouts".to_string()));
+    }
+
+    #[test]
+    fn test_update_activity_group_not_found() {
+        let (repo, _temp_dir) = setup_test_repo();
+
+        let result = repo.update_activity_group(999, Some("Test"), None);
+
+        assert!(matches!(result, Err(ActivityError::GroupNotFound(999))));
+    }
+
+    #[test]
+    fn test_delete_activity_group() {
+        let (repo, _temp_dir) = setup_test_repo();
+
+        let group = repo
+            .create_activity_group("Exercise", None)
+            .expect("Failed to create activity group");
+
+        repo.delete_activity_group(group.id)
+            .expect("Failed to delete activity group");
+
+        // Verify it's no longer in the list
+        let groups = repo.get_activity_groups().expect("Failed to get groups");
+        assert!(!groups.iter().any(|g| g.id == group.id));
+    }
+
+    #[test]
+    fn test_delete_activity_group_not_found() {
+        let (repo, _temp_dir) = setup_test_repo();
+
+        let result = repo.delete_activity_group(999);
+
+        assert!(matches!(result, Err(ActivityError::GroupNotFound(999))));
+    }
+
+    #[test]
+    fn test_get_activity_groups() {
+        let (repo, _temp_dir) = setup_test_repo();
+
+        repo.create_activity_group("Exercise", None)
+            .expect("Failed to create group");
+        repo.create_activity_group("Social", None)
+            .expect("Failed to create group");
+        repo.create_activity_group("Work", None)
+            .expect("Failed to create group");
+
+        let groups = repo.get_activity_groups().expect("Failed to get groups");
+
+        assert_eq!(groups.len(), 3);
+        // Should be sorted by name
+        assert_eq!(groups[0].name, "Exercise");
+        assert_eq!(groups[1].name, "Social");
+        assert_eq!(groups[2].name, "Work");
+    }
+
+    #[test]
+    fn test_get_activity_groups_excludes_deleted() {
+        let (repo, _temp_dir) = setup_test_repo();
+
+        let group1 = repo
+            .create_activity_group("Exercise", None)
+            .expect("Failed to create group");
+        repo.create_activity_group("Social", None)
+            .expect("Failed to create group");
+
+        // Delete first group
+        repo.delete_activity_group(group1.id)
+            .expect("Failed to delete group");
+
+        let groups = repo.get_activity_groups().expect("Failed to get groups");
+
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].name, "Social");
+    }
+
+    #[test]
+    fn test_cascading_deletes() {
+        // This test verifies CASCADE delete behavior defined in migration 003_activity_groups.sql
+        // Full cascade testing requires Activity creation methods (not yet implemented)
+        // For now, verify database schema has CASCADE constraints
+        let (repo, _temp_dir) = setup_test_repo();
+
+        let group = repo
+            .create_activity_group("Exercise", None)
+            .expect("Failed to create group");
+
+        // Verify the CASCADE constraint exists in the schema
+        let conn = repo.db.get_connection();
+        let conn = conn.lock();
+
+        // Query sqlite_master to verify FOREIGN KEY with CASCADE is defined
+        let has_cascade: bool = conn
+            .query_row(
+                "SELECT sql FROM sqlite_master WHERE type='table' AND name='activities'",
+                [],
+                |row| {
+                    let sql: String = row.get(0)?;
+                    Ok(sql.contains("ON DELETE CASCADE"))
+                },
+            )
+            .unwrap_or(false);
+
+        assert!(
+            has_cascade,
+            "activities table should have ON DELETE CASCADE constraint"
+        );
+
+        // Soft delete the group (actual CASCADE testing requires Activity methods)
+        drop(conn); // Release lock before calling repository method
+        repo.delete_activity_group(group.id)
+            .expect("Failed to delete group");
+
+        // Verify group is soft deleted
+        let groups = repo.get_activity_groups().expect("Failed to get groups");
+        assert!(!groups.iter().any(|g| g.id == group.id));
+
+        // NOTE: Full CASCADE delete testing will be added when Activity CRUD methods
+        // are implemented (tasks 1.21-1.29). This will include:
+        // 1. Creating activities linked to this group
+        // 2. Deleting the group
+        // 3. Verifying all linked activities are CASCADE deleted
+    }
+
+    #[test]
+    fn test_create_activity_group_description_too_long() {
+        let (repo, _temp_dir) = setup_test_repo();
+
+        let long_description = "a".repeat(501);
+        let result = repo.create_activity_group("Exercise", Some(&long_description));
+
+        assert!(matches!(
+            result,
+            Err(ActivityError::DescriptionTooLong(501))
+        ));
+    }
+
+    #[test]
+    fn test_update_activity_group_partial_name_only() {
+        let (repo, _temp_dir) = setup_test_repo();
+
+        let group = repo
+            .create_activity_group("Exercise", Some("Original description"))
+            .expect("Failed to create group");
+
+        let updated = repo
+            .update_activity_group(group.id, Some("Updated Name"), None)
+            .expect("Failed to update");
+
+        assert_eq!(updated.name, "Updated Name");
+        assert_eq!(
+            updated.description,
+            Some("Original description".to_string())
+        );
+    }
+
+    #[test]
+    fn test_update_activity_group_partial_description_only() {
+        let (repo, _temp_dir) = setup_test_repo();
+
+        let group = repo
+            .create_activity_group("Exercise", Some("Original"))
+            .expect("Failed to create group");
 
-        Ok(GoalProgress {
-            goal_id,
-            current_value,
-            target_value: goal.target_value,
-            percentage,
-            is_achieved,
-            period_start,
-            period_end: current_time.to_string(),
-        })
+        let updated = repo
+            .update_activity_group(group.id, None, Some("Updated description"))
+            .expect("Failed to update");
+
+        assert_eq!(updated.name, "Exercise");
+        assert_eq!(updated.description, Some("Updated description".to_string()));
     }
 
-    /// Helper: Counts unique days with activity logs for given activities.
-    ///
-    /// # Notes
-    /// This is a `_with_conn` helper - it accepts a connection reference
-    /// and does NOT acquire locks. Only use from public methods that already hold the lock.
-    fn calculate_unique_days_for_activities_with_conn(
-        &self,
-        conn: &rusqlite::Connection,
-        activity_ids: &[i32],
-        start_date: &str,
-        end_date: &str,
-    ) -> Result<i32, ActivityError> {
-        if activity_ids.is_empty() {
-            return Ok(0);
-        }
+    #[test]
+    fn test_create_activity_group_utf8_characters() {
+        let (repo, _temp_dir) = setup_test_repo();
 
-        // Build placeholders for IN clause
-        let placeholders = activity_ids
-            .iter()
-            .map(|_| "?")
-            .collect::<Vec<_>>()
-            .join(", ");
+        // Test with emojis and multi-byte characters
+        let name_with_emoji = "Exercise 💪";
+        let description_with_unicode = "健康とフィットネス"; // Japanese characters
 
-        let query = format!(
-            "SELECT COUNT(DISTINCT DATE(logged_at))
-             FROM activity_logs
-             WHERE activity_id IN ({})
-               AND logged_at >= ?
-               AND logged_at <= ?
-               AND deleted_at IS NULL",
-            placeholders
+        let group = repo
+            .create_activity_group(name_with_emoji, Some(description_with_unicode))
+            .expect("Failed to create group with UTF-8");
+
+        assert_eq!(group.name, name_with_emoji);
+        assert_eq!(
+            group.description,
+            Some(description_with_unicode.to_string())
         );
+    }
 
-        let mut stmt = conn.prepare(&query)?;
+    #[test]
+    fn test_create_activity_group_utf8_length_validation() {
+        let (repo, _temp_dir) = setup_test_repo();
 
-        // Build params: activity_ids + start_date + end_date
-        let mut params: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
-        for aid in activity_ids {
-            params.push(Box::new(*aid));
-        }
-        params.push(Box::new(start_date.to_string()));
-        params.push(Box::new(end_date.to_string()));
+        // Create a name with exactly 100 emoji characters (which are 4 bytes each)
+        // This tests that we're counting characters, not bytes
+        let emoji_name = "😀".repeat(100);
+        let result = repo.create_activity_group(&emoji_name, None);
+        assert!(result.is_ok(), "Should accept 100 emoji characters");
 
-        let params_refs: Vec<&dyn rusqlite::ToSql> = params.iter().map(|p| p.as_ref()).collect();
+        // 101 emoji characters should fail
+        let emoji_name_too_long = "😀".repeat(101);
+        let result = repo.create_activity_group(&emoji_name_too_long, None);
+        assert!(
+            matches!(result, Err(ActivityError::GroupNameTooLong(101))),
+            "Should reject 101 emoji characters"
+        );
+    }
 
-        let unique_days: i32 = stmt.query_row(params_refs.as_slice(), |row| row.get(0))?;
+    #[test]
+    fn test_update_activity_group_description_too_long() {
+        let (repo, _temp_dir) = setup_test_repo();
 
-        Ok(unique_days)
-    }
-}
+        let group = repo
+            .create_activity_group("Exercise", None)
+            .expect("Failed to create group");
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::db::Database;
-    use std::sync::Arc;
-    use tempfile::TempDir;
+        let long_description = "a".repeat(501);
+        let result = repo.update_activity_group(group.id, None, Some(&long_description));
 
-    fn setup_test_repo() -> (ActivityRepository, TempDir) {
-        let temp_dir = TempDir::new().expect("Failed to create temp directory");
-        let db = Arc::new(
-            Database::new(temp_dir.path().to_path_buf()).expect("Failed to create database"),
-        );
-        (ActivityRepository::new(db), temp_dir)
+        assert!(matches!(
+            result,
+            Err(ActivityError::DescriptionTooLong(501))
+        ));
     }
 
+    // Activity and ActivityLog Tests
+
     #[test]
-    fn test_create_activity_group() {
+    fn test_create_activity_with_group_id() {
         let (repo, _temp_dir) = setup_test_repo();
 
         let group = repo
-            .create_activity_group("Exercise", Some("Physical activities"))
-            .expect("Failed to create activity group");
+            .create_activity_group("Exercise", None)
+            .expect("Failed to create group");
 
-        assert_eq!(group.name, "Exercise");
-        assert_eq!(group.description, Some("Physical activities".to_string()));
-        assert!(group.id > 0);
-        assert!(group.deleted_at.is_none());
+        let activity = repo
+            .create_activity(group.id, "Running", Some("#FF0000"), Some("fire"))
+            .expect("Failed to create activity");
+
+        assert_eq!(activity.name, "Running");
+        assert_eq!(activity.group_id, group.id);
+        assert_eq!(activity.color, Some(HexColor::new("#FF0000").unwrap()));
+        assert_eq!(activity.icon, Some("fire".to_string()));
+        assert!(activity.id > 0);
     }
 
     #[test]
-    fn test_create_activity_group_empty_name() {
+    fn test_create_activity_empty_name() {
         let (repo, _temp_dir) = setup_test_repo();
 
-        let result = repo.create_activity_group("", None);
+        let group = repo
+            .create_activity_group("Exercise", None)
+            .expect("Failed to create group");
 
-        assert!(matches!(result, Err(ActivityError::EmptyGroupName)));
+        let result = repo.create_activity(group.id, "", None, None);
+
+        assert!(matches!(result, Err(ActivityError::EmptyActivityName)));
     }
 
     #[test]
-    fn test_create_activity_group_name_too_long() {
+    fn test_create_activity_name_too_long() {
         let (repo, _temp_dir) = setup_test_repo();
 
-        let long_name = "a".repeat(101);
-        let result = repo.create_activity_group(&long_name, None);
+        let group = repo
+            .create_activity_group("Exercise", None)
+            .expect("Failed to create group");
 
-        assert!(matches!(result, Err(ActivityError::GroupNameTooLong(101))));
+        let long_name = "a".repeat(51);
+        let result = repo.create_activity(group.id, &long_name, None, None);
+
+        assert!(matches!(
+            result,
+            Err(ActivityError::ActivityNameTooLong(51))
+        ));
     }
 
     #[test]
-    fn test_update_activity_group() {
+    fn test_create_activity_icon_too_long() {
         let (repo, _temp_dir) = setup_test_repo();
 
         let group = repo
-            .create_activity_group("Exercise", Some("Physical activities"))
-            .expect("Failed to create activity group");
+            .create_activity_group("Exercise", None)
+            .expect("Failed to create group");
 
-        let updated = repo
-            .update_activity_group(group.id, Some("Workout"), Some("All workouts"))
-            .expect("Failed to update activity group");
+        let long_icon = "a".repeat(21);
+        let result = repo.create_activity(group.id, "Running", None, Some(&long_icon));
 
-        assert_eq!(updated.id, group.id);
-        assert_eq!(updated.name, "Workout");
-        assert_eq!(updated.description, Some("All workouts".to_string()));
+        assert!(matches!(
+            result,
+            Err(ActivityError::ActivityIconTooLong(21))
+        ));
     }
 
     #[test]
-    fn test_update_activity_group_not_found() {
+    fn test_create_activity_group_not_found() {
         let (repo, _temp_dir) = setup_test_repo();
 
-        let result = repo.update_activity_group(999, Some("Test"), None);
+        let result = repo.create_activity(999, "Running", None, None);
 
         assert!(matches!(result, Err(ActivityError::GroupNotFound(999))));
     }
 
     #[test]
-    fn test_delete_activity_group() {
+    fn test_get_activities_by_group() {
         let (repo, _temp_dir) = setup_test_repo();
 
-        let group = repo
+        let group1 = repo
             .create_activity_group("Exercise", None)
-            .expect("Failed to create activity group");
+            .expect("Failed to create group");
+        let group2 = repo
+            .create_activity_group("Social", None)
+            .expect("Failed to create group");
 
-        repo.delete_activity_group(group.id)
-            .expect("Failed to delete activity group");
+        repo.create_activity(group1.id, "Running", None, None)
+            .expect("Failed to create activity");
+        repo.create_activity(group1.id, "Swimming", None, None)
+            .expect("Failed to create activity");
+        repo.create_activity(group2.id, "Party", None, None)
+            .expect("Failed to create activity");
 
-        // Verify it's no longer in the list
-        let groups = repo.get_activity_groups().expect("Failed to get groups");
-        assert!(!groups.iter().any(|g| g.id == group.id));
+        let group1_activities = repo
+            .get_activities_by_group(group1.id)
+            .expect("Failed to get activities");
+
+        assert_eq!(group1_activities.len(), 2);
+        assert_eq!(group1_activities[0].name, "Running");
+        assert_eq!(group1_activities[1].name, "Swimming");
     }
 
     #[test]
-    fn test_delete_activity_group_not_found() {
+    fn test_log_activity() {
         let (repo, _temp_dir) = setup_test_repo();
 
-        let result = repo.delete_activity_group(999);
+        let group = repo
+            .create_activity_group("Exercise", None)
+            .expect("Failed to create group");
+        let activity = repo
+            .create_activity(group.id, "Running", None, None)
+            .expect("Failed to create activity");
 
-        assert!(matches!(result, Err(ActivityError::GroupNotFound(999))));
+        let logged_at = "2025-01-15T10:00:00Z";
+        let log = repo
+            .log_activity(activity.id, logged_at, Some("Felt great!"))
+            .expect("Failed to log activity");
+
+        assert_eq!(log.activity_id, activity.id);
+        assert_eq!(log.logged_at, logged_at);
+        assert_eq!(log.notes, Some("Felt great!".to_string()));
+        assert!(log.id > 0);
     }
 
     #[test]
-    fn test_get_activity_groups() {
+    fn test_log_activity_notes_too_long() {
         let (repo, _temp_dir) = setup_test_repo();
 
-        repo.create_activity_group("Exercise", None)
-            .expect("Failed to create group");
-        repo.create_activity_group("Social", None)
-            .expect("Failed to create group");
-        repo.create_activity_group("Work", None)
+        let group = repo
+            .create_activity_group("Exercise", None)
             .expect("Failed to create group");
+        let activity = repo
+            .create_activity(group.id, "Running", None, None)
+            .expect("Failed to create activity");
+
+        let long_notes = "a".repeat(501);
+        let result = repo.log_activity(activity.id, "2025-01-15T10:00:00Z", Some(&long_notes));
+
+        assert!(matches!(
+            result,
+            Err(ActivityError::NotesLengthExceeded(501))
+        ));
+    }
+
+    #[test]
+    fn test_log_activity_not_found() {
+        let (repo, _temp_dir) = setup_test_repo();
 
-        let groups = repo.get_activity_groups().expect("Failed to get groups");
+        let result = repo.log_activity(999, "2025-01-15T10:00:00Z", None);
 
-        assert_eq!(groups.len(), 3);
-        // Should be sorted by name
-        assert_eq!(groups[0].name, "Exercise");
-        assert_eq!(groups[1].name, "Social");
-        assert_eq!(groups[2].name, "Work");
+        assert!(matches!(result, Err(ActivityError::ActivityNotFound(999))));
     }
 
     #[test]
-    fn test_get_activity_groups_excludes_deleted() {
+    fn test_get_activity_logs_no_filter() {
         let (repo, _temp_dir) = setup_test_repo();
 
-        let group1 = repo
+        let group = repo
             .create_activity_group("Exercise", None)
             .expect("Failed to create group");
-        repo.create_activity_group("Social", None)
-            .expect("Failed to create group");
+        let activity = repo
+            .create_activity(group.id, "Running", None, None)
+            .expect("Failed to create activity");
 
-        // Delete first group
-        repo.delete_activity_group(group1.id)
-            .expect("Failed to delete group");
+        repo.log_activity(activity.id, "2025-01-15T10:00:00Z", Some("Morning run"))
+            .expect("Failed to log");
+        repo.log_activity(activity.id, "2025-01-16T10:00:00Z", Some("Evening run"))
+            .expect("Failed to log");
 
-        let groups = repo.get_activity_groups().expect("Failed to get groups");
+        let logs = repo
+            .get_activity_logs(None, None, None)
+            .expect("Failed to get logs");
 
-        assert_eq!(groups.len(), 1);
-        assert_eq!(groups[0].name, "Social");
+        assert_eq!(logs.len(), 2);
+        // Should be ordered by logged_at DESC
+        assert_eq!(logs[0].logged_at, "2025-01-16T10:00:00Z");
+        assert_eq!(logs[1].logged_at, "2025-01-15T10:00:00Z");
     }
 
     #[test]
-    fn test_cascading_deletes() {
-        // This test verifies CASCADE delete behavior defined in migration 003_activity_groups.sql
-        // Full cascade testing requires Activity creation methods (not yet implemented)
-        // For now, verify database schema has CASCADE constraints
+    fn test_get_activity_logs_with_activity_filter() {
         let (repo, _temp_dir) = setup_test_repo();
 
         let group = repo
             .create_activity_group("Exercise", None)
             .expect("Failed to create group");
+        let activity1 = repo
+            .create_activity(group.id, "Running", None, None)
+            .expect("Failed to create activity");
+        let activity2 = repo
+            .create_activity(group.id, "Swimming", None, None)
+            .expect("Failed to create activity");
 
-        // Verify the CASCADE constraint exists in the schema
-        let conn = repo.db.get_connection();
-        let conn = conn.lock();
+        repo.log_activity(activity1.id, "2025-01-15T10:00:00Z", None)
+            .expect("Failed to log");
+        repo.log_activity(activity2.id, "2025-01-15T11:00:00Z", None)
+            .expect("Failed to log");
 
-        // Query sqlite_master to verify FOREIGN KEY with CASCADE is defined
-        let has_cascade: bool = conn
-            .query_row(
-                "SELECT sql FROM sqlite_master WHERE type='table' AND name='activities'",
-                [],
-                |row| {
-                    let sql: String = row.get(0)?;
-                    Ok(sql.contains("ON DELETE CASCADE"))
-                },
-            )
-            .unwrap_or(false);
+        let logs = repo
+            .get_activity_logs(Some(activity1.id), None, None)
+            .expect("Failed to get logs");
 
-        assert!(
-            has_cascade,
-            "activities table should have ON DELETE CASCADE constraint"
-        );
+        assert_eq!(logs.len(), 1);
+        assert_eq!(logs[0].activity_id, activity1.id);
+    }
 
-        // Soft delete the group (actual CASCADE testing requires Activity methods)
-        drop(conn); // Release lock before calling repository method
-        repo.delete_activity_group(group.id)
-            .expect("Failed to delete group");
+    #[test]
+    fn test_get_activity_logs_with_date_filter() {
+        let (repo, _temp_dir) = setup_test_repo();
 
-        // Verify group is soft deleted
-        let groups = repo.get_activity_groups().expect("Failed to get groups");
-        assert!(!groups.iter().any(|g| g.id == group.id));
+        let group = repo
+            .create_activity_group("Exercise", None)
+            .expect("Failed to create group");
+        let activity = repo
+            .create_activity(group.id, "Running", None, None)
+            .expect("Failed to create activity");
 
-        // NOTE: Full CASCADE delete testing will be added when Activity CRUD methods
-        // are implemented (tasks 1.21-1.29). This will include:
-        // 1. Creating activities linked to this group
-        // 2. Deleting the group
-        // 3. Verifying all linked activities are CASCADE deleted
+        repo.log_activity(activity.id, "2025-01-15T10:00:00Z", None)
+            .expect("Failed to log");
+        repo.log_activity(activity.id, "2025-01-17T10:00:00Z", None)
+            .expect("Failed to log");
+        repo.log_activity(activity.id, "2025-01-20T10:00:00Z", None)
+            .expect("Failed to log");
+
+        let logs = repo
+            .get_activity_logs(None, Some("2025-01-16"), Some("2025-01-19"))
+            .expect("Failed to get logs");
+
+        assert_eq!(logs.len(), 1);
+        assert_eq!(logs[0].logged_at, "2025-01-17T10:00:00Z");
     }
 
     #[test]
-    fn test_create_activity_group_description_too_long() {
+    fn test_activity_utf8_characters() {
         let (repo, _temp_dir) = setup_test_repo();
 
-        let long_description = "a".repeat(501);
-        let result = repo.create_activity_group("Exercise", Some(&long_description));
+        let group = repo
+            .create_activity_group("Exercise", None)
+            .expect("Failed to create group");
 
-        assert!(matches!(
-            result,
-            Err(ActivityError::DescriptionTooLong(501))
-        ));
+        let name_with_emoji = "Running 🏃";
+        let icon_with_emoji = "🏃";
+
+        let activity = repo
+            .create_activity(group.id, name_with_emoji, None, Some(icon_with_emoji))
+            .expect("Failed to create activity with UTF-8");
+
+        assert_eq!(activity.name, name_with_emoji);
+        assert_eq!(activity.icon, Some(icon_with_emoji.to_string()));
     }
 
     #[test]
-    fn test_update_activity_group_partial_name_only() {
+    fn test_activity_name_utf8_length_validation() {
         let (repo, _temp_dir) = setup_test_repo();
 
         let group = repo
-            .create_activity_group("Exercise", Some("Original description"))
+            .create_activity_group("Exercise", None)
             .expect("Failed to create group");
 
-        let updated = repo
-            .update_activity_group(group.id, Some("Updated Name"), None)
-            .expect("Failed to update");
+        // 50 emoji characters (4 bytes each) should be accepted
+        let emoji_name = "😀".repeat(50);
+        let result = repo.create_activity(group.id, &emoji_name, None, None);
+        assert!(result.is_ok(), "Should accept 50 emoji characters");
 
-        assert_eq!(updated.name, "Updated Name");
-        assert_eq!(
-            updated.description,
-            Some("Original description".to_string())
+        // 51 emoji characters should fail
+        let emoji_name_too_long = "😀".repeat(51);
+        let result = repo.create_activity(group.id, &emoji_name_too_long, None, None);
+        assert!(
+            matches!(result, Err(ActivityError::ActivityNameTooLong(51))),
+            "Should reject 51 emoji characters"
         );
     }
 
     #[test]
-    fn test_update_activity_group_partial_description_only() {
+    fn test_create_activity_trims_whitespace() {
         let (repo, _temp_dir) = setup_test_repo();
 
         let group = repo
-            .create_activity_group("Exercise", Some("Original"))
+            .create_activity_group("Exercise", None)
             .expect("Failed to create group");
 
-        let updated = repo
-            .update_activity_group(group.id, None, Some("Updated description"))
-            .expect("Failed to update");
+        // Test name trimming
+        let activity = repo
+            .create_activity(group.id, "  Running  ", None, None)
+            .expect("Failed to create activity");
+        assert_eq!(activity.name, "Running", "Name should be trimmed");
 
-        assert_eq!(updated.name, "Exercise");
-        assert_eq!(updated.description, Some("Updated description".to_string()));
+        // Test icon trimming
+        let activity = repo
+            .create_activity(group.id, "Cycling", None, Some("  🚴  "))
+            .expect("Failed to create activity");
+        assert_eq!(
+            activity.icon,
+            Some("🚴".to_string()),
+            "Icon should be trimmed"
+        );
     }
 
     #[test]
-    fn test_create_activity_group_utf8_characters() {
+    fn test_create_activity_whitespace_only_name_fails() {
         let (repo, _temp_dir) = setup_test_repo();
 
-        // Test with emojis and multi-byte characters
-        let name_with_emoji = "Exercise 💪";
-        let description_with_unicode = "健康とフィットネス"; // Japanese characters
-
         let group = repo
-            .create_activity_group(name_with_emoji, Some(description_with_unicode))
-            .expect("Failed to create group with UTF-8");
+            .create_activity_group("Exercise", None)
+            .expect("Failed to create group");
 
-        assert_eq!(group.name, name_with_emoji);
-        assert_eq!(
-            group.description,
-            Some(description_with_unicode.to_string())
+        // Whitespace-only name should fail after trimming
+        let result = repo.create_activity(group.id, "     ", None, None);
+        assert!(
+            matches!(result, Err(ActivityError::EmptyActivityName)),
+            "Whitespace-only name should be rejected"
         );
     }
 
     #[test]
-    fn test_create_activity_group_utf8_length_validation() {
+    fn test_create_activity_whitespace_only_icon_becomes_none() {
         let (repo, _temp_dir) = setup_test_repo();
 
-        // Create a name with exactly 100 emoji characters (which are 4 bytes each)
-        // This tests that we're counting characters, not bytes
-        let emoji_name = "😀".repeat(100);
-        let result = repo.create_activity_group(&emoji_name, None);
-        assert!(result.is_ok(), "Should accept 100 emoji characters");
+        let group = repo
+            .create_activity_group("Exercise", None)
+            .expect("Failed to create group");
 
-        // 101 emoji characters should fail
-        let emoji_name_too_long = "😀".repeat(101);
-        let result = repo.create_activity_group(&emoji_name_too_long, None);
-        assert!(
-            matches!(result, Err(ActivityError::GroupNameTooLong(101))),
-            "Should reject 101 emoji characters"
+        // Whitespace-only icon should become None after trimming
+        let activity = repo
+            .create_activity(group.id, "Running", None, Some("     "))
+            .expect("Failed to create activity");
+        assert_eq!(
+            activity.icon, None,
+            "Whitespace-only icon should become None"
         );
     }
 
     #[test]
-    fn test_update_activity_group_description_too_long() {
+    fn test_log_activity_trims_notes() {
         let (repo, _temp_dir) = setup_test_repo();
 
         let group = repo
             .create_activity_group("Exercise", None)
             .expect("Failed to create group");
 
-        let long_description = "a".repeat(501);
-        let result = repo.update_activity_group(group.id, None, Some(&long_description));
+        let activity = repo
+            .create_activity(group.id, "Running", None, None)
+            .expect("Failed to create activity");
 
-        assert!(matches!(
-            result,
-            Err(ActivityError::DescriptionTooLong(501))
-        ));
-    }
+        // Test notes trimming
+        let log = repo
+            .log_activity(
+                activity.id,
+                "2025-01-15T10:00:00Z",
+                Some("  Great workout!  "),
+            )
+            .expect("Failed to log activity");
 
-    // Activity and ActivityLog Tests
+        assert_eq!(
+            log.notes,
+            Some("Great workout!".to_string()),
+            "Notes should be trimmed"
+        );
+    }
 
     #[test]
-    fn test_create_activity_with_group_id() {
+    fn test_log_activity_whitespace_only_notes_becomes_none() {
         let (repo, _temp_dir) = setup_test_repo();
 
         let group = repo
@@ -1496,101 +2896,128 @@ mod tests {
             .expect("Failed to create group");
 
         let activity = repo
-            .create_activity(group.id, "Running", Some("#FF0000"), Some("fire"))
+            .create_activity(group.id, "Running", None, None)
             .expect("Failed to create activity");
 
-        assert_eq!(activity.name, "Running");
-        assert_eq!(activity.group_id, group.id);
-        assert_eq!(activity.color, Some(HexColor::new("#FF0000").unwrap()));
-        assert_eq!(activity.icon, Some("fire".to_string()));
-        assert!(activity.id > 0);
+        // Whitespace-only notes should become None after trimming
+        let log = repo
+            .log_activity(activity.id, "2025-01-15T10:00:00Z", Some("     "))
+            .expect("Failed to log activity");
+
+        assert_eq!(log.notes, None, "Whitespace-only notes should become None");
     }
 
+    // ========================================
+    // Activity Goals Tests
+    // ========================================
+
     #[test]
-    fn test_create_activity_empty_name() {
+    fn test_set_activity_goal_for_activity() {
         let (repo, _temp_dir) = setup_test_repo();
 
         let group = repo
             .create_activity_group("Exercise", None)
             .expect("Failed to create group");
+        let activity = repo
+            .create_activity(group.id, "Running", None, None)
+            .expect("Failed to create activity");
 
-        let result = repo.create_activity(group.id, "", None, None);
+        // Set activity-level goal: 3 days per week
+        let goal = repo
+            .set_activity_goal(Some(activity.id), None, GoalType::DaysPerPeriod, 3, 7)
+            .expect("Failed to set activity goal");
 
-        assert!(matches!(result, Err(ActivityError::EmptyActivityName)));
+        assert_eq!(goal.activity_id, Some(activity.id));
+        assert_eq!(goal.group_id, None);
+        assert_eq!(goal.goal_type, GoalType::DaysPerPeriod);
+        assert_eq!(goal.target_value, 3);
+        assert_eq!(goal.period_days, 7);
+        assert!(goal.id > 0);
+        assert!(goal.deleted_at.is_none());
     }
 
     #[test]
-    fn test_create_activity_name_too_long() {
+    fn test_set_activity_goal_for_group() {
         let (repo, _temp_dir) = setup_test_repo();
 
         let group = repo
-            .create_activity_group("Exercise", None)
+            .create_activity_group("Social", None)
             .expect("Failed to create group");
 
-        let long_name = "a".repeat(51);
-        let result = repo.create_activity(group.id, &long_name, None, None);
+        // Set group-level goal: 5 days per 14-day period
+        let goal = repo
+            .set_activity_goal(None, Some(group.id), GoalType::DaysPerPeriod, 5, 14)
+            .expect("Failed to set group goal");
 
-        assert!(matches!(
-            result,
-            Err(ActivityError::ActivityNameTooLong(51))
-        ));
+        assert_eq!(goal.activity_id, None);
+        assert_eq!(goal.group_id, Some(group.id));
+        assert_eq!(goal.goal_type, GoalType::DaysPerPeriod);
+        assert_eq!(goal.target_value, 5);
+        assert_eq!(goal.period_days, 14);
     }
 
     #[test]
-    fn test_create_activity_icon_too_long() {
+    fn test_set_activity_goal_percent_improvement_type() {
         let (repo, _temp_dir) = setup_test_repo();
 
         let group = repo
             .create_activity_group("Exercise", None)
             .expect("Failed to create group");
+        let activity = repo
+            .create_activity(group.id, "Meditation", None, None)
+            .expect("Failed to create activity");
 
-        let long_icon = "a".repeat(21);
-        let result = repo.create_activity(group.id, "Running", None, Some(&long_icon));
+        // Set percent improvement goal: 20% improvement over 30 days
+        let goal = repo
+            .set_activity_goal(
+                Some(activity.id),
+                None,
+                GoalType::PercentImprovement,
+                20,
+                30,
+            )
+            .expect("Failed to set percent improvement goal");
 
-        assert!(matches!(
-            result,
-            Err(ActivityError::ActivityIconTooLong(21))
-        ));
+        assert_eq!(goal.goal_type, GoalType::PercentImprovement);
+        assert_eq!(goal.target_value, 20);
+        assert_eq!(goal.period_days, 30);
     }
 
     #[test]
-    fn test_create_activity_group_not_found() {
+    fn test_set_activity_goal_both_targets_rejected() {
         let (repo, _temp_dir) = setup_test_repo();
 
-        let result = repo.create_activity(999, "Running", None, None);
+        let group = repo
+            .create_activity_group("Exercise", None)
+            .expect("Failed to create group");
+        let activity = repo
+            .create_activity(group.id, "Running", None, None)
+            .expect("Failed to create activity");
 
-        assert!(matches!(result, Err(ActivityError::GroupNotFound(999))));
+        // Cannot set goal for both activity AND group
+        let result = repo.set_activity_goal(
+            Some(activity.id),
+            Some(group.id),
+            GoalType::DaysPerPeriod,
+            3,
+            7,
+        );
+
+        assert!(matches!(result, Err(ActivityError::InvalidGoalTarget)));
     }
 
     #[test]
-    fn test_get_activities_by_group() {
+    fn test_set_activity_goal_no_targets_rejected() {
         let (repo, _temp_dir) = setup_test_repo();
 
-        let group1 = repo
-            .create_activity_group("Exercise", None)
-            .expect("Failed to create group");
-        let group2 = repo
-            .create_activity_group("Social", None)
-            .expect("Failed to create group");
-
-        repo.create_activity(group1.id, "Running", None, None)
-            .expect("Failed to create activity");
-        repo.create_activity(group1.id, "Swimming", None, None)
-            .expect("Failed to create activity");
-        repo.create_activity(group2.id, "Party", None, None)
-            .expect("Failed to create activity");
-
-        let group1_activities = repo
-            .get_activities_by_group(group1.id)
-            .expect("Failed to get activities");
+        // Must set goal for either activity OR group
+        let result = repo.set_activity_goal(None, None, GoalType::DaysPerPeriod, 3, 7);
 
-        assert_eq!(group1_activities.len(), 2);
-        assert_eq!(group1_activities[0].name, "Running");
-        assert_eq!(group1_activities[1].name, "Swimming");
+        assert!(matches!(result, Err(ActivityError::MissingGoalTarget)));
     }
 
     #[test]
-    fn test_log_activity() {
+    fn test_set_activity_goal_negative_target_rejected() {
         let (repo, _temp_dir) = setup_test_repo();
 
         let group = repo
@@ -1600,19 +3027,14 @@ mod tests {
             .create_activity(group.id, "Running", None, None)
             .expect("Failed to create activity");
 
-        let logged_at = "2025-01-15T10:00:00Z";
-        let log = repo
-            .log_activity(activity.id, logged_at, Some("Felt great!"))
-            .expect("Failed to log activity");
+        let result =
+            repo.set_activity_goal(Some(activity.id), None, GoalType::DaysPerPeriod, -5, 7);
 
-        assert_eq!(log.activity_id, activity.id);
-        assert_eq!(log.logged_at, logged_at);
-        assert_eq!(log.notes, Some("Felt great!".to_string()));
-        assert!(log.id > 0);
+        assert!(matches!(result, Err(ActivityError::InvalidTargetValue(-5))));
     }
 
     #[test]
-    fn test_log_activity_notes_too_long() {
+    fn test_set_activity_goal_zero_period_rejected() {
         let (repo, _temp_dir) = setup_test_repo();
 
         let group = repo
@@ -1622,26 +3044,13 @@ mod tests {
             .create_activity(group.id, "Running", None, None)
             .expect("Failed to create activity");
 
-        let long_notes = "a".repeat(501);
-        let result = repo.log_activity(activity.id, "2025-01-15T10:00:00Z", Some(&long_notes));
-
-        assert!(matches!(
-            result,
-            Err(ActivityError::NotesLengthExceeded(501))
-        ));
-    }
-
-    #[test]
-    fn test_log_activity_not_found() {
-        let (repo, _temp_dir) = setup_test_repo();
-
-        let result = repo.log_activity(999, "2025-01-15T10:00:00Z", None);
+        let result = repo.set_activity_goal(Some(activity.id), None, GoalType::DaysPerPeriod, 3, 0);
 
-        assert!(matches!(result, Err(ActivityError::ActivityNotFound(999))));
+        assert!(matches!(result, Err(ActivityError::InvalidPeriodDays(0))));
     }
 
     #[test]
-    fn test_get_activity_logs_no_filter() {
+    fn test_get_activity_goals_by_activity() {
         let (repo, _temp_dir) = setup_test_repo();
 
         let group = repo
@@ -1651,50 +3060,51 @@ mod tests {
             .create_activity(group.id, "Running", None, None)
             .expect("Failed to create activity");
 
-        repo.log_activity(activity.id, "2025-01-15T10:00:00Z", Some("Morning run"))
-            .expect("Failed to log");
-        repo.log_activity(activity.id, "2025-01-16T10:00:00Z", Some("Evening run"))
-            .expect("Failed to log");
+        let goal1 = repo
+            .set_activity_goal(Some(activity.id), None, GoalType::DaysPerPeriod, 3, 7)
+            .expect("Failed to set goal 1");
+        let goal2 = repo
+            .set_activity_goal(
+                Some(activity.id),
+                None,
+                GoalType::PercentImprovement,
+                20,
+                30,
+            )
+            .expect("Failed to set goal 2");
 
-        let logs = repo
-            .get_activity_logs(None, None, None)
-            .expect("Failed to get logs");
+        let goals = repo
+            .get_activity_goals(Some(activity.id), None)
+            .expect("Failed to get activity goals");
 
-        assert_eq!(logs.len(), 2);
-        // Should be ordered by logged_at DESC
-        assert_eq!(logs[0].logged_at, "2025-01-16T10:00:00Z");
-        assert_eq!(logs[1].logged_at, "2025-01-15T10:00:00Z");
+        assert_eq!(goals.len(), 2);
+        assert!(goals.iter().any(|g| g.id == goal1.id));
+        assert!(goals.iter().any(|g| g.id == goal2.id));
     }
 
     #[test]
-    fn test_get_activity_logs_with_activity_filter() {
+    fn test_get_activity_goals_by_group() {
         let (repo, _temp_dir) = setup_test_repo();
 
         let group = repo
-            .create_activity_group("Exercise", None)
+            .create_activity_group("Social", None)
             .expect("Failed to create group");
-        let activity1 = repo
-            .create_activity(group.id, "Running", None, None)
-            .expect("Failed to create activity");
-        let activity2 = repo
-            .create_activity(group.id, "Swimming", None, None)
-            .expect("Failed to create activity");
 
-        repo.log_activity(activity1.id, "2025-01-15T10:00:00Z", None)
-            .expect("Failed to log");
-        repo.log_activity(activity2.id, "2025-01-15T11:00:00Z", None)
-            .expect("Failed to log");
+        let goal = repo
+            .set_activity_goal(None, Some(group.id), GoalType::DaysPerPeriod, 5, 14)
+            .expect("Failed to set group goal");
 
-        let logs = repo
-            .get_activity_logs(Some(activity1.id), None, None)
-            .expect("Failed to get logs");
+        let goals = repo
+            .get_activity_goals(None, Some(group.id))
+            .expect("Failed to get group goals");
 
-        assert_eq!(logs.len(), 1);
-        assert_eq!(logs[0].activity_id, activity1.id);
+        assert_eq!(goals.len(), 1);
+        assert_eq!(goals[0].id, goal.id);
+        assert_eq!(goals[0].group_id, Some(group.id));
     }
 
     #[test]
-    fn test_get_activity_logs_with_date_filter() {
+    fn test_get_activity_goals_excludes_deleted() {
         let (repo, _temp_dir) = setup_test_repo();
 
         let group = repo
@@ -1704,175 +3114,183 @@ mod tests {
             .create_activity(group.id, "Running", None, None)
             .expect("Failed to create activity");
 
-        repo.log_activity(activity.id, "2025-01-15T10:00:00Z", None)
-            .expect("Failed to log");
-        repo.log_activity(activity.id, "2025-01-17T10:00:00Z", None)
-            .expect("Failed to log");
-        repo.log_activity(activity.id, "2025-01-20T10:00:00Z", None)
-            .expect("Failed to log");
+        let goal = repo
+            .set_activity_goal(Some(activity.id), None, GoalType::DaysPerPeriod, 3, 7)
+            .expect("Failed to set goal");
 
-        let logs = repo
-            .get_activity_logs(None, Some("2025-01-16"), Some("2025-01-19"))
-            .expect("Failed to get logs");
+        // Delete the goal
+        repo.delete_activity_goal(goal.id)
+            .expect("Failed to delete goal");
 
-        assert_eq!(logs.len(), 1);
-        assert_eq!(logs[0].logged_at, "2025-01-17T10:00:00Z");
+        // Should not appear in results
+        let goals = repo
+            .get_activity_goals(Some(activity.id), None)
+            .expect("Failed to get goals");
+
+        assert_eq!(goals.len(), 0);
     }
 
     #[test]
-    fn test_activity_utf8_characters() {
+    fn test_update_activity_goal() {
         let (repo, _temp_dir) = setup_test_repo();
 
         let group = repo
             .create_activity_group("Exercise", None)
             .expect("Failed to create group");
+        let activity = repo
+            .create_activity(group.id, "Running", None, None)
+            .expect("Failed to create activity");
 
-        let name_with_emoji = "Running 🏃";
-        let icon_with_emoji = "🏃";
+        let goal = repo
+            .set_activity_goal(Some(activity.id), None, GoalType::DaysPerPeriod, 3, 7)
+            .expect("Failed to set goal");
 
-        let activity = repo
-            .create_activity(group.id, name_with_emoji, None, Some(icon_with_emoji))
-            .expect("Failed to create activity with UTF-8");
+        // Update target to 5 days per 14-day period
+        let updated = repo
+            .update_activity_goal(goal.id, 5, 14)
+            .expect("Failed to update goal");
 
-        assert_eq!(activity.name, name_with_emoji);
-        assert_eq!(activity.icon, Some(icon_with_emoji.to_string()));
+        assert_eq!(updated.id, goal.id);
+        assert_eq!(updated.target_value, 5);
+        assert_eq!(updated.period_days, 14);
+        assert_eq!(updated.goal_type, GoalType::DaysPerPeriod); // Unchanged
     }
 
     #[test]
-    fn test_activity_name_utf8_length_validation() {
-        let (repo, _temp_dir) = setup_test_repo();
-
-        let group = repo
-            .create_activity_group("Exercise", None)
-            .expect("Failed to create group");
+    fn test_update_activity_goal_not_found() {
+        let (repo, _temp_dir) = setup_test_repo();
 
-        // 50 emoji characters (4 bytes each) should be accepted
-        let emoji_name = "😀".repeat(50);
-        let result = repo.create_activity(group.id, &emoji_name, None, None);
-        assert!(result.is_ok(), "Should accept 50 emoji characters");
+        let result = repo.update_activity_goal(999, 5, 14);
 
-        // 51 emoji characters should fail
-        let emoji_name_too_long = "😀".repeat(51);
-        let result = repo.create_activity(group.id, &emoji_name_too_long, None, None);
-        assert!(
-            matches!(result, Err(ActivityError::ActivityNameTooLong(51))),
-            "Should reject 51 emoji characters"
-        );
+        assert!(matches!(result, Err(ActivityError::GoalNotFound(999))));
     }
 
     #[test]
-    fn test_create_activity_trims_whitespace() {
+    fn test_delete_activity_goal() {
         let (repo, _temp_dir) = setup_test_repo();
 
         let group = repo
             .create_activity_group("Exercise", None)
             .expect("Failed to create group");
-
-        // Test name trimming
         let activity = repo
-            .create_activity(group.id, "  Running  ", None, None)
+            .create_activity(group.id, "Running", None, None)
             .expect("Failed to create activity");
-        assert_eq!(activity.name, "Running", "Name should be trimmed");
 
-        // Test icon trimming
-        let activity = repo
-            .create_activity(group.id, "Cycling", None, Some("  🚴  "))
-            .expect("Failed to create activity");
-        assert_eq!(
-            activity.icon,
-            Some("🚴".to_string()),
-            "Icon should be trimmed"
-        );
+        let goal = repo
+            .set_activity_goal(Some(activity.id), None, GoalType::DaysPerPeriod, 3, 7)
+            .expect("Failed to set goal");
+
+        repo.delete_activity_goal(goal.id)
+            .expect("Failed to delete goal");
+
+        // Verify it's soft-deleted (not in active list)
+        let goals = repo
+            .get_activity_goals(Some(activity.id), None)
+            .expect("Failed to get goals");
+        assert!(!goals.iter().any(|g| g.id == goal.id));
     }
 
     #[test]
-    fn test_create_activity_whitespace_only_name_fails() {
+    fn test_delete_activity_goal_not_found() {
         let (repo, _temp_dir) = setup_test_repo();
 
-        let group = repo
-            .create_activity_group("Exercise", None)
-            .expect("Failed to create group");
+        let result = repo.delete_activity_goal(999);
 
-        // Whitespace-only name should fail after trimming
-        let result = repo.create_activity(group.id, "     ", None, None);
-        assert!(
-            matches!(result, Err(ActivityError::EmptyActivityName)),
-            "Whitespace-only name should be rejected"
-        );
+        assert!(matches!(result, Err(ActivityError::GoalNotFound(999))));
     }
 
+    // ========================================
+    // Activity Frequency Tests
+    // ========================================
+
     #[test]
-    fn test_create_activity_whitespace_only_icon_becomes_none() {
+    fn test_get_activity_frequency_basic() {
         let (repo, _temp_dir) = setup_test_repo();
 
         let group = repo
             .create_activity_group("Exercise", None)
             .expect("Failed to create group");
-
-        // Whitespace-only icon should become None after trimming
         let activity = repo
-            .create_activity(group.id, "Running", None, Some("     "))
+            .create_activity(group.id, "Running", None, None)
             .expect("Failed to create activity");
-        assert_eq!(
-            activity.icon, None,
-            "Whitespace-only icon should become None"
-        );
+
+        // Log activity on 4 different days within a 14-day period
+        repo.log_activity(activity.id, "2025-01-01T10:00:00Z", None)
+            .expect("Failed to log");
+        repo.log_activity(activity.id, "2025-01-02T10:00:00Z", None)
+            .expect("Failed to log");
+        repo.log_activity(activity.id, "2025-01-02T15:00:00Z", None)
+            .expect("Failed to log"); // Same day - should count as 1
+        repo.log_activity(activity.id, "2025-01-04T10:00:00Z", None)
+            .expect("Failed to log");
+        repo.log_activity(activity.id, "2025-01-08T10:00:00Z", None)
+            .expect("Failed to log");
+
+        let frequency = repo
+            .get_activity_frequency(activity.id, "2025-01-01T00:00:00Z", "2025-01-14T23:59:59Z")
+            .expect("Failed to get frequency");
+
+        assert_eq!(frequency.activity_id, activity.id);
+        assert_eq!(frequency.unique_days, 4);
+        assert_eq!(frequency.total_logs, 5);
+        // 14 days = 2 weeks, 4 unique days / 2 weeks = 2.0 days/week
+        assert!((frequency.days_per_week - 2.0).abs() < 0.01);
     }
 
     #[test]
-    fn test_log_activity_trims_notes() {
+    fn test_get_activity_frequency_no_logs() {
         let (repo, _temp_dir) = setup_test_repo();
 
         let group = repo
             .create_activity_group("Exercise", None)
             .expect("Failed to create group");
-
         let activity = repo
             .create_activity(group.id, "Running", None, None)
             .expect("Failed to create activity");
 
-        // Test notes trimming
-        let log = repo
-            .log_activity(
-                activity.id,
-                "2025-01-15T10:00:00Z",
-                Some("  Great workout!  "),
-            )
-            .expect("Failed to log activity");
+        let frequency = repo
+            .get_activity_frequency(activity.id, "2025-01-01T00:00:00Z", "2025-01-14T23:59:59Z")
+            .expect("Failed to get frequency");
 
-        assert_eq!(
-            log.notes,
-            Some("Great workout!".to_string()),
-            "Notes should be trimmed"
-        );
+        assert_eq!(frequency.unique_days, 0);
+        assert_eq!(frequency.total_logs, 0);
+        assert_eq!(frequency.days_per_week, 0.0);
     }
 
     #[test]
-    fn test_log_activity_whitespace_only_notes_becomes_none() {
+    fn test_get_activity_frequency_partial_week() {
         let (repo, _temp_dir) = setup_test_repo();
 
         let group = repo
             .create_activity_group("Exercise", None)
             .expect("Failed to create group");
-
         let activity = repo
             .create_activity(group.id, "Running", None, None)
             .expect("Failed to create activity");
 
-        // Whitespace-only notes should become None after trimming
-        let log = repo
-            .log_activity(activity.id, "2025-01-15T10:00:00Z", Some("     "))
-            .expect("Failed to log activity");
+        // 3 days logged within 3.5 day period (0.5 weeks)
+        repo.log_activity(activity.id, "2025-01-01T10:00:00Z", None)
+            .expect("Failed to log");
+        repo.log_activity(activity.id, "2025-01-02T10:00:00Z", None)
+            .expect("Failed to log");
+        repo.log_activity(activity.id, "2025-01-03T10:00:00Z", None)
+            .expect("Failed to log");
 
-        assert_eq!(log.notes, None, "Whitespace-only notes should become None");
+        let frequency = repo
+            .get_activity_frequency(activity.id, "2025-01-01T00:00:00Z", "2025-01-04T12:00:00Z")
+            .expect("Failed to get frequency");
+
+        assert_eq!(frequency.unique_days, 3);
+        // 3.5 days = 0.5 weeks, 3 days / 0.5 weeks = 6 days/week
+        assert!((frequency.days_per_week - 6.0).abs() < 0.1);
     }
 
     // ========================================
-    // Activity Goals Tests
+    // Activity Trend Tests
     // ========================================
 
     #[test]
-    fn test_set_activity_goal_for_activity() {
+    fn test_get_activity_trend_improving() {
         let (repo, _temp_dir) = setup_test_repo();
 
         let group = repo
@@ -1882,69 +3300,82 @@ mod tests {
             .create_activity(group.id, "Running", None, None)
             .expect("Failed to create activity");
 
-        // Set activity-level goal: 3 days per week
-        let goal = repo
-            .set_activity_goal(Some(activity.id), None, GoalType::DaysPerPeriod, 3, 7)
-            .expect("Failed to set activity goal");
-
-        assert_eq!(goal.activity_id, Some(activity.id));
-        assert_eq!(goal.group_id, None);
-        assert_eq!(goal.goal_type, GoalType::DaysPerPeriod);
-        assert_eq!(goal.target_value, 3);
-        assert_eq!(goal.period_days, 7);
-        assert!(goal.id > 0);
-        assert!(goal.deleted_at.is_none());
-    }
-
-    #[test]
-    fn test_set_activity_goal_for_group() {
-        let (repo, _temp_dir) = setup_test_repo();
+        // Previous period (Jan 1-7): 3 unique days
+        repo.log_activity(activity.id, "2025-01-01T10:00:00Z", None)
+            .expect("Failed to log");
+        repo.log_activity(activity.id, "2025-01-03T10:00:00Z", None)
+            .expect("Failed to log");
+        repo.log_activity(activity.id, "2025-01-05T10:00:00Z", None)
+            .expect("Failed to log");
 
-        let group = repo
-            .create_activity_group("Social", None)
-            .expect("Failed to create group");
+        // Current period (Jan 8-14): 5 unique days
+        repo.log_activity(activity.id, "2025-01-08T10:00:00Z", None)
+            .expect("Failed to log");
+        repo.log_activity(activity.id, "2025-01-09T10:00:00Z", None)
+            .expect("Failed to log");
+        repo.log_activity(activity.id, "2025-01-10T10:00:00Z", None)
+            .expect("Failed to log");
+        repo.log_activity(activity.id, "2025-01-12T10:00:00Z", None)
+            .expect("Failed to log");
+        repo.log_activity(activity.id, "2025-01-14T10:00:00Z", None)
+            .expect("Failed to log");
 
-        // Set group-level goal: 5 days per 14-day period
-        let goal = repo
-            .set_activity_goal(None, Some(group.id), GoalType::DaysPerPeriod, 5, 14)
-            .expect("Failed to set group goal");
+        let trend = repo
+            .get_activity_trend(activity.id, 7, "2025-01-15T00:00:00Z")
+            .expect("Failed to get trend");
 
-        assert_eq!(goal.activity_id, None);
-        assert_eq!(goal.group_id, Some(group.id));
-        assert_eq!(goal.goal_type, GoalType::DaysPerPeriod);
-        assert_eq!(goal.target_value, 5);
-        assert_eq!(goal.period_days, 14);
+        assert_eq!(trend.activity_id, activity.id);
+        assert_eq!(trend.current_period_days, 5);
+        assert_eq!(trend.previous_period_days, 3);
+        assert_eq!(trend.change_days, 2);
+        // (5 - 3) / 3 * 100 = 66.67%
+        assert!((trend.change_percentage - 66.67).abs() < 0.1);
+        assert_eq!(trend.trend, Trend::Improving);
     }
 
     #[test]
-    fn test_set_activity_goal_percent_improvement_type() {
+    fn test_get_activity_trend_declining() {
         let (repo, _temp_dir) = setup_test_repo();
 
         let group = repo
             .create_activity_group("Exercise", None)
             .expect("Failed to create group");
         let activity = repo
-            .create_activity(group.id, "Meditation", None, None)
+            .create_activity(group.id, "Running", None, None)
             .expect("Failed to create activity");
 
-        // Set percent improvement goal: 20% improvement over 30 days
-        let goal = repo
-            .set_activity_goal(
-                Some(activity.id),
-                None,
-                GoalType::PercentImprovement,
-                20,
-                30,
-            )
-            .expect("Failed to set percent improvement goal");
+        // Previous period: 5 days
+        repo.log_activity(activity.id, "2025-01-01T10:00:00Z", None)
+            .expect("Failed to log");
+        repo.log_activity(activity.id, "2025-01-02T10:00:00Z", None)
+            .expect("Failed to log");
+        repo.log_activity(activity.id, "2025-01-03T10:00:00Z", None)
+            .expect("Failed to log");
+        repo.log_activity(activity.id, "2025-01-04T10:00:00Z", None)
+            .expect("Failed to log");
+        repo.log_activity(activity.id, "2025-01-05T10:00:00Z", None)
+            .expect("Failed to log");
 
-        assert_eq!(goal.goal_type, GoalType::PercentImprovement);
-        assert_eq!(goal.target_value, 20);
-        assert_eq!(goal.period_days, 30);
+        // Current period: 2 days
+        repo.log_activity(activity.id, "2025-01-08T10:00:00Z", None)
+            .expect("Failed to log");
+        repo.log_activity(activity.id, "2025-01-10T10:00:00Z", None)
+            .expect("Failed to log");
+
+        let trend = repo
+            .get_activity_trend(activity.id, 7, "2025-01-15T00:00:00Z")
+            .expect("Failed to get trend");
+
+        assert_eq!(trend.previous_period_days, 5);
+        assert_eq!(trend.current_period_days, 2);
+        assert_eq!(trend.change_days, -3);
+        // (2 - 5) / 5 * 100 = -60%
+        assert!((trend.change_percentage + 60.0).abs() < 0.1);
+        assert_eq!(trend.trend, Trend::Declining);
     }
 
     #[test]
-    fn test_set_activity_goal_both_targets_rejected() {
+    fn test_get_activity_trend_stable() {
         let (repo, _temp_dir) = setup_test_repo();
 
         let group = repo
@@ -1954,30 +3385,39 @@ mod tests {
             .create_activity(group.id, "Running", None, None)
             .expect("Failed to create activity");
 
-        // Cannot set goal for both activity AND group
-        let result = repo.set_activity_goal(
-            Some(activity.id),
-            Some(group.id),
-            GoalType::DaysPerPeriod,
-            3,
-            7,
-        );
-
-        assert!(matches!(result, Err(ActivityError::InvalidGoalTarget)));
-    }
+        // Previous period: 4 days
+        repo.log_activity(activity.id, "2025-01-01T10:00:00Z", None)
+            .expect("Failed to log");
+        repo.log_activity(activity.id, "2025-01-02T10:00:00Z", None)
+            .expect("Failed to log");
+        repo.log_activity(activity.id, "2025-01-03T10:00:00Z", None)
+            .expect("Failed to log");
+        repo.log_activity(activity.id, "2025-01-04T10:00:00Z", None)
+            .expect("Failed to log");
 
-    #[test]
-    fn test_set_activity_goal_no_targets_rejected() {
-        let (repo, _temp_dir) = setup_test_repo();
+        // Current period: 4 days
+        repo.log_activity(activity.id, "2025-01-08T10:00:00Z", None)
+            .expect("Failed to log");
+        repo.log_activity(activity.id, "2025-01-09T10:00:00Z", None)
+            .expect("Failed to log");
+        repo.log_activity(activity.id, "2025-01-10T10:00:00Z", None)
+            .expect("Failed to log");
+        repo.log_activity(activity.id, "2025-01-11T10:00:00Z", None)
+            .expect("Failed to log");
 
-        // Must set goal for either activity OR group
-        let result = repo.set_activity_goal(None, None, GoalType::DaysPerPeriod, 3, 7);
+        let trend = repo
+            .get_activity_trend(activity.id, 7, "2025-01-15T00:00:00Z")
+            .expect("Failed to get trend");
 
-        assert!(matches!(result, Err(ActivityError::MissingGoalTarget)));
+        assert_eq!(trend.previous_period_days, 4);
+        assert_eq!(trend.current_period_days, 4);
+        assert_eq!(trend.change_days, 0);
+        assert_eq!(trend.change_percentage, 0.0);
+        assert_eq!(trend.trend, Trend::Stable);
     }
 
     #[test]
-    fn test_set_activity_goal_negative_target_rejected() {
+    fn test_get_activity_trend_from_zero() {
         let (repo, _temp_dir) = setup_test_repo();
 
         let group = repo
@@ -1987,424 +3427,591 @@ mod tests {
             .create_activity(group.id, "Running", None, None)
             .expect("Failed to create activity");
 
-        let result =
-            repo.set_activity_goal(Some(activity.id), None, GoalType::DaysPerPeriod, -5, 7);
+        // Previous period: 0 days
+        // Current period: 3 days
+        repo.log_activity(activity.id, "2025-01-08T10:00:00Z", None)
+            .expect("Failed to log");
+        repo.log_activity(activity.id, "2025-01-10T10:00:00Z", None)
+            .expect("Failed to log");
+        repo.log_activity(activity.id, "2025-01-12T10:00:00Z", None)
+            .expect("Failed to log");
 
-        assert!(matches!(result, Err(ActivityError::InvalidTargetValue(-5))));
+        let trend = repo
+            .get_activity_trend(activity.id, 7, "2025-01-15T00:00:00Z")
+            .expect("Failed to get trend");
+
+        assert_eq!(trend.previous_period_days, 0);
+        assert_eq!(trend.current_period_days, 3);
+        // From 0 to 3 = 100% improvement (per algorithm spec)
+        assert_eq!(trend.change_percentage, 100.0);
+        assert_eq!(trend.trend, Trend::Improving);
     }
 
+    // ========================================
+    // Group Frequency and Trend Tests
+    // ========================================
+
     #[test]
-    fn test_set_activity_goal_zero_period_rejected() {
+    fn test_get_group_frequency_unions_activities() {
         let (repo, _temp_dir) = setup_test_repo();
 
         let group = repo
             .create_activity_group("Exercise", None)
             .expect("Failed to create group");
-        let activity = repo
+        let running = repo
             .create_activity(group.id, "Running", None, None)
             .expect("Failed to create activity");
+        let swimming = repo
+            .create_activity(group.id, "Swimming", None, None)
+            .expect("Failed to create activity");
 
-        let result = repo.set_activity_goal(Some(activity.id), None, GoalType::DaysPerPeriod, 3, 0);
+        repo.log_activity(running.id, "2025-01-01T10:00:00Z", None)
+            .expect("Failed to log");
+        repo.log_activity(swimming.id, "2025-01-01T18:00:00Z", None)
+            .expect("Failed to log"); // Same day, different activity - counts as 1
+        repo.log_activity(swimming.id, "2025-01-03T10:00:00Z", None)
+            .expect("Failed to log");
+        repo.log_activity(running.id, "2025-01-08T10:00:00Z", None)
+            .expect("Failed to log");
 
-        assert!(matches!(result, Err(ActivityError::InvalidPeriodDays(0))));
+        let frequency = repo
+            .get_group_frequency(group.id, "2025-01-01T00:00:00Z", "2025-01-14T23:59:59Z")
+            .expect("Failed to get group frequency");
+
+        assert_eq!(frequency.group_id, group.id);
+        assert_eq!(frequency.unique_days, 3);
+        assert_eq!(frequency.total_logs, 4);
+        assert!((frequency.days_per_week - 1.5).abs() < 0.01);
     }
 
     #[test]
-    fn test_get_activity_goals_by_activity() {
+    fn test_get_group_frequency_excludes_deleted_activities() {
         let (repo, _temp_dir) = setup_test_repo();
 
         let group = repo
             .create_activity_group("Exercise", None)
             .expect("Failed to create group");
-        let activity = repo
+        let running = repo
             .create_activity(group.id, "Running", None, None)
             .expect("Failed to create activity");
+        let swimming = repo
+            .create_activity(group.id, "Swimming", None, None)
+            .expect("Failed to create activity");
 
-        let goal1 = repo
-            .set_activity_goal(Some(activity.id), None, GoalType::DaysPerPeriod, 3, 7)
-            .expect("Failed to set goal 1");
-        let goal2 = repo
-            .set_activity_goal(
-                Some(activity.id),
-                None,
-                GoalType::PercentImprovement,
-                20,
-                30,
+        repo.log_activity(running.id, "2025-01-01T10:00:00Z", None)
+            .expect("Failed to log");
+        repo.log_activity(swimming.id, "2025-01-02T10:00:00Z", None)
+            .expect("Failed to log");
+
+        {
+            let conn = repo.db.get_connection();
+            let conn = conn.lock();
+            conn.execute(
+                "UPDATE activities SET deleted_at = datetime('now') WHERE id = ?",
+                [swimming.id],
             )
-            .expect("Failed to set goal 2");
+            .expect("Failed to soft delete activity");
+        }
 
-        let goals = repo
-            .get_activity_goals(Some(activity.id), None)
-            .expect("Failed to get activity goals");
+        let frequency = repo
+            .get_group_frequency(group.id, "2025-01-01T00:00:00Z", "2025-01-07T23:59:59Z")
+            .expect("Failed to get group frequency");
 
-        assert_eq!(goals.len(), 2);
-        assert!(goals.iter().any(|g| g.id == goal1.id));
-        assert!(goals.iter().any(|g| g.id == goal2.id));
+        assert_eq!(frequency.unique_days, 1);
+        assert_eq!(frequency.total_logs, 1);
     }
 
     #[test]
-    fn test_get_activity_goals_by_group() {
+    fn test_get_group_frequency_group_not_found() {
         let (repo, _temp_dir) = setup_test_repo();
 
-        let group = repo
-            .create_activity_group("Social", None)
-            .expect("Failed to create group");
-
-        let goal = repo
-            .set_activity_goal(None, Some(group.id), GoalType::DaysPerPeriod, 5, 14)
-            .expect("Failed to set group goal");
-
-        let goals = repo
-            .get_activity_goals(None, Some(group.id))
-            .expect("Failed to get group goals");
-
-        assert_eq!(goals.len(), 1);
-        assert_eq!(goals[0].id, goal.id);
-        assert_eq!(goals[0].group_id, Some(group.id));
+        let result = repo.get_group_frequency(999, "2025-01-01T00:00:00Z", "2025-01-07T23:59:59Z");
+        assert!(matches!(result, Err(ActivityError::GroupNotFound(999))));
     }
 
     #[test]
-    fn test_get_activity_goals_excludes_deleted() {
+    fn test_get_group_trend_improving() {
         let (repo, _temp_dir) = setup_test_repo();
 
         let group = repo
             .create_activity_group("Exercise", None)
             .expect("Failed to create group");
-        let activity = repo
+        let running = repo
             .create_activity(group.id, "Running", None, None)
             .expect("Failed to create activity");
+        let swimming = repo
+            .create_activity(group.id, "Swimming", None, None)
+            .expect("Failed to create activity");
 
-        let goal = repo
-            .set_activity_goal(Some(activity.id), None, GoalType::DaysPerPeriod, 3, 7)
-            .expect("Failed to set goal");
+        // Previous period (Jan 1-7): 2 unique days
+        repo.log_activity(running.id, "2025-01-02T10:00:00Z", None)
+            .expect("Failed to log");
+        repo.log_activity(swimming.id, "2025-01-04T10:00:00Z", None)
+            .expect("Failed to log");
 
-        // Delete the goal
-        repo.delete_activity_goal(goal.id)
-            .expect("Failed to delete goal");
+        // Current period (Jan 8-14): 4 unique days across both activities
+        repo.log_activity(running.id, "2025-01-09T10:00:00Z", None)
+            .expect("Failed to log");
+        repo.log_activity(swimming.id, "2025-01-10T10:00:00Z", None)
+            .expect("Failed to log");
+        repo.log_activity(running.id, "2025-01-12T10:00:00Z", None)
+            .expect("Failed to log");
+        repo.log_activity(swimming.id, "2025-01-13T10:00:00Z", None)
+            .expect("Failed to log");
 
-        // Should not appear in results
-        let goals = repo
-            .get_activity_goals(Some(activity.id), None)
-            .expect("Failed to get goals");
+        let trend = repo
+            .get_group_trend(group.id, 7, "2025-01-15T00:00:00Z")
+            .expect("Failed to get group trend");
 
-        assert_eq!(goals.len(), 0);
+        assert_eq!(trend.group_id, group.id);
+        assert_eq!(trend.current_period_days, 4);
+        assert_eq!(trend.previous_period_days, 2);
+        assert_eq!(trend.change_days, 2);
+        assert!((trend.change_percentage - 100.0).abs() < 0.1);
+        assert_eq!(trend.trend, Trend::Improving);
     }
 
     #[test]
-    fn test_update_activity_goal() {
+    fn test_get_weekly_group_summary_all_groups() {
         let (repo, _temp_dir) = setup_test_repo();
 
-        let group = repo
+        let exercise = repo
             .create_activity_group("Exercise", None)
             .expect("Failed to create group");
-        let activity = repo
-            .create_activity(group.id, "Running", None, None)
+        let social = repo
+            .create_activity_group("Social", None)
+            .expect("Failed to create group");
+        let empty = repo
+            .create_activity_group("Creative", None)
+            .expect("Failed to create group");
+        let running = repo
+            .create_activity(exercise.id, "Running", None, None)
+            .expect("Failed to create activity");
+        let call = repo
+            .create_activity(social.id, "Call a friend", None, None)
             .expect("Failed to create activity");
 
-        let goal = repo
-            .set_activity_goal(Some(activity.id), None, GoalType::DaysPerPeriod, 3, 7)
-            .expect("Failed to set goal");
+        // Exercise: 1 day last week, 3 days this week
+        repo.log_activity(running.id, "2025-01-03T10:00:00Z", None)
+            .expect("Failed to log");
+        for ts in [
+            "2025-01-09T10:00:00Z",
+            "2025-01-10T10:00:00Z",
+            "2025-01-11T10:00:00Z",
+        ] {
+            repo.log_activity(running.id, ts, None)
+                .expect("Failed to log");
+        }
 
-        // Update target to 5 days per 14-day period
-        let updated = repo
-            .update_activity_goal(goal.id, 5, 14)
-            .expect("Failed to update goal");
+        // Social: 2 days last week, 1 day this week
+        repo.log_activity(call.id, "2025-01-02T10:00:00Z", None)
+            .expect("Failed to log");
+        repo.log_activity(call.id, "2025-01-04T10:00:00Z", None)
+            .expect("Failed to log");
+        repo.log_activity(call.id, "2025-01-12T10:00:00Z", None)
+            .expect("Failed to log");
 
-        assert_eq!(updated.id, goal.id);
-        assert_eq!(updated.target_value, 5);
-        assert_eq!(updated.period_days, 14);
-        assert_eq!(updated.goal_type, GoalType::DaysPerPeriod); // Unchanged
+        let summary = repo
+            .get_weekly_group_summary("2025-01-15T00:00:00Z")
+            .expect("Failed to get weekly summary");
+
+        // Ordered by name: Creative, Exercise, Social
+        assert_eq!(summary.len(), 3);
+        assert_eq!(summary[0].group_id, empty.id);
+        assert_eq!(summary[0].days_per_week, 0);
+        assert_eq!(summary[0].trend, Trend::Stable);
+
+        assert_eq!(summary[1].group_id, exercise.id);
+        assert_eq!(summary[1].days_per_week, 3);
+        assert_eq!(summary[1].previous_week_days, 1);
+        assert_eq!(summary[1].trend, Trend::Improving);
+
+        assert_eq!(summary[2].group_id, social.id);
+        assert_eq!(summary[2].days_per_week, 1);
+        assert_eq!(summary[2].previous_week_days, 2);
+        assert!((summary[2].change_percentage + 50.0).abs() < 0.1);
+        assert_eq!(summary[2].trend, Trend::Declining);
     }
 
-    #[test]
-    fn test_update_activity_goal_not_found() {
-        let (repo, _temp_dir) = setup_test_repo();
-
-        let result = repo.update_activity_goal(999, 5, 14);
+    // ========================================
+    // Planned Activity Tests
+    // ========================================
 
-        assert!(matches!(result, Err(ActivityError::GoalNotFound(999))));
+    fn plan_request(activity_id: i32, days_of_week: Vec<i32>) -> CreatePlannedActivityRequest {
+        CreatePlannedActivityRequest {
+            activity_id,
+            days_of_week,
+            time_of_day: "07:00".to_string(),
+            timezone: Some("UTC".to_string()),
+            interval_weeks: None,
+            tolerance_minutes: Some(60),
+            start_date: "2025-01-06".to_string(),
+            end_date: None,
+            reminder_enabled: false,
+            reminder_minutes_before: None,
+        }
     }
 
     #[test]
-    fn test_delete_activity_goal() {
+    fn test_create_planned_activity() {
         let (repo, _temp_dir) = setup_test_repo();
 
         let group = repo
             .create_activity_group("Exercise", None)
             .expect("Failed to create group");
-        let activity = repo
-            .create_activity(group.id, "Running", None, None)
+        let yoga = repo
+            .create_activity(group.id, "Yoga", None, None)
             .expect("Failed to create activity");
 
-        let goal = repo
-            .set_activity_goal(Some(activity.id), None, GoalType::DaysPerPeriod, 3, 7)
-            .expect("Failed to set goal");
+        let plan = repo
+            .create_planned_activity(&plan_request(yoga.id, vec![4, 2, 2]))
+            .expect("Failed to create plan");
 
-        repo.delete_activity_goal(goal.id)
-            .expect("Failed to delete goal");
+        assert_eq!(plan.activity_id, yoga.id);
+        assert_eq!(plan.days_of_week, vec![2, 4]); // Sorted and de-duplicated
+        assert_eq!(plan.interval_weeks, 1);
+        assert_eq!(plan.tolerance_minutes, 60);
+        assert!(!plan.reminder_enabled);
 
-        // Verify it's soft-deleted (not in active list)
-        let goals = repo
-            .get_activity_goals(Some(activity.id), None)
-            .expect("Failed to get goals");
-        assert!(!goals.iter().any(|g| g.id == goal.id));
+        let plans = repo
+            .get_planned_activities(None, Some(group.id))
+            .expect("Failed to get plans");
+        assert_eq!(plans.len(), 1);
     }
 
     #[test]
-    fn test_delete_activity_goal_not_found() {
+    fn test_create_planned_activity_activity_not_found() {
         let (repo, _temp_dir) = setup_test_repo();
 
-        let result = repo.delete_activity_goal(999);
-
-        assert!(matches!(result, Err(ActivityError::GoalNotFound(999))));
+        let result = repo.create_planned_activity(&plan_request(999, vec![1]));
+        assert!(matches!(result, Err(ActivityError::ActivityNotFound(999))));
     }
 
-    // ========================================
-    // Activity Frequency Tests
-    // ========================================
-
     #[test]
-    fn test_get_activity_frequency_basic() {
+    fn test_create_planned_activity_invalid_weekday() {
         let (repo, _temp_dir) = setup_test_repo();
 
-        let group = repo
-            .create_activity_group("Exercise", None)
-            .expect("Failed to create group");
-        let activity = repo
-            .create_activity(group.id, "Running", None, None)
-            .expect("Failed to create activity");
-
-        // Log activity on 4 different days within a 14-day period
-        repo.log_activity(activity.id, "2025-01-01T10:00:00Z", None)
-            .expect("Failed to log");
-        repo.log_activity(activity.id, "2025-01-02T10:00:00Z", None)
-            .expect("Failed to log");
-        repo.log_activity(activity.id, "2025-01-02T15:00:00Z", None)
-            .expect("Failed to log"); // Same day - should count as 1
-        repo.log_activity(activity.id, "2025-01-04T10:00:00Z", None)
-            .expect("Failed to log");
-        repo.log_activity(activity.id, "2025-01-08T10:00:00Z", None)
-            .expect("Failed to log");
-
-        let frequency = repo
-            .get_activity_frequency(activity.id, "2025-01-01T00:00:00Z", "2025-01-14T23:59:59Z")
-            .expect("Failed to get frequency");
-
-        assert_eq!(frequency.activity_id, activity.id);
-        assert_eq!(frequency.unique_days, 4);
-        assert_eq!(frequency.total_logs, 5);
-        // 14 days = 2 weeks, 4 unique days / 2 weeks = 2.0 days/week
-        assert!((frequency.days_per_week - 2.0).abs() < 0.01);
+        let result = repo.create_planned_activity(&plan_request(1, vec![7]));
+        assert!(matches!(result, Err(ActivityError::InvalidPlan(_))));
     }
 
     #[test]
-    fn test_get_activity_frequency_no_logs() {
+    fn test_update_and_delete_planned_activity() {
         let (repo, _temp_dir) = setup_test_repo();
 
         let group = repo
             .create_activity_group("Exercise", None)
             .expect("Failed to create group");
-        let activity = repo
-            .create_activity(group.id, "Running", None, None)
+        let yoga = repo
+            .create_activity(group.id, "Yoga", None, None)
             .expect("Failed to create activity");
+        let plan = repo
+            .create_planned_activity(&plan_request(yoga.id, vec![2]))
+            .expect("Failed to create plan");
 
-        let frequency = repo
-            .get_activity_frequency(activity.id, "2025-01-01T00:00:00Z", "2025-01-14T23:59:59Z")
-            .expect("Failed to get frequency");
+        let updated = repo
+            .update_planned_activity(
+                plan.id,
+                &UpdatePlannedActivityRequest {
+                    days_of_week: Some(vec![1, 3, 5]),
+                    time_of_day: Some("18:30".to_string()),
+                    timezone: None,
+                    interval_weeks: None,
+                    tolerance_minutes: None,
+                    start_date: None,
+                    end_date: None,
+                    clear_end_date: false,
+                    reminder_enabled: Some(true),
+                    reminder_minutes_before: Some(15),
+                },
+            )
+            .expect("Failed to update plan");
 
-        assert_eq!(frequency.unique_days, 0);
-        assert_eq!(frequency.total_logs, 0);
-        assert_eq!(frequency.days_per_week, 0.0);
+        assert_eq!(updated.days_of_week, vec![1, 3, 5]);
+        assert_eq!(updated.time_of_day, "18:30");
+        assert_eq!(updated.tolerance_minutes, 60); // Unchanged
+        assert!(updated.reminder_enabled);
+        assert_eq!(updated.reminder_minutes_before, 15);
+
+        repo.delete_planned_activity(plan.id)
+            .expect("Failed to delete plan");
+        assert!(matches!(
+            repo.delete_planned_activity(plan.id),
+            Err(ActivityError::PlannedActivityNotFound(_))
+        ));
+        assert!(repo
+            .get_planned_activities(Some(yoga.id), None)
+            .expect("Failed to get plans")
+            .is_empty());
     }
 
     #[test]
-    fn test_get_activity_frequency_partial_week() {
+    fn test_update_planned_activity_clears_end_date() {
         let (repo, _temp_dir) = setup_test_repo();
 
         let group = repo
             .create_activity_group("Exercise", None)
             .expect("Failed to create group");
-        let activity = repo
-            .create_activity(group.id, "Running", None, None)
+        let yoga = repo
+            .create_activity(group.id, "Yoga", None, None)
             .expect("Failed to create activity");
+        let mut request = plan_request(yoga.id, vec![2]);
+        request.end_date = Some("2025-03-31".to_string());
+        let plan = repo
+            .create_planned_activity(&request)
+            .expect("Failed to create plan");
+
+        let update = |end_date: Option<&str>, clear_end_date: bool| UpdatePlannedActivityRequest {
+            days_of_week: None,
+            time_of_day: None,
+            timezone: None,
+            interval_weeks: None,
+            tolerance_minutes: None,
+            start_date: None,
+            end_date: end_date.map(str::to_string),
+            clear_end_date,
+            reminder_enabled: None,
+            reminder_minutes_before: None,
+        };
 
-        // 3 days logged within 3.5 day period (0.5 weeks)
-        repo.log_activity(activity.id, "2025-01-01T10:00:00Z", None)
-            .expect("Failed to log");
-        repo.log_activity(activity.id, "2025-01-02T10:00:00Z", None)
-            .expect("Failed to log");
-        repo.log_activity(activity.id, "2025-01-03T10:00:00Z", None)
-            .expect("Failed to log");
-
-        let frequency = repo
-            .get_activity_frequency(activity.id, "2025-01-01T00:00:00Z", "2025-01-04T12:00:00Z")
-            .expect("Failed to get frequency");
+        // Leaving end_date out keeps it
+        let kept = repo
+            .update_planned_activity(plan.id, &update(None, false))
+            .expect("Failed to update plan");
+        assert_eq!(kept.end_date.as_deref(), Some("2025-03-31"));
 
-        assert_eq!(frequency.unique_days, 3);
-        // 3.5 days = 0.5 weeks, 3 days / 0.5 weeks = 6 days/week
-        assert!((frequency.days_per_week - 6.0).abs() < 0.1);
+        let cleared = repo
+            .update_planned_activity(plan.id, &update(None, true))
+            .expect("Failed to update plan");
+        assert_eq!(cleared.end_date, None);
     }
 
-    // ========================================
-    // Activity Trend Tests
-    // ========================================
-
     #[test]
-    fn test_get_activity_trend_improving() {
+    fn test_get_activity_adherence() {
         let (repo, _temp_dir) = setup_test_repo();
 
         let group = repo
             .create_activity_group("Exercise", None)
             .expect("Failed to create group");
-        let activity = repo
-            .create_activity(group.id, "Running", None, None)
+        let yoga = repo
+            .create_activity(group.id, "Yoga", None, None)
             .expect("Failed to create activity");
 
-        // Previous period (Jan 1-7): 3 unique days
-        repo.log_activity(activity.id, "2025-01-01T10:00:00Z", None)
-            .expect("Failed to log");
-        repo.log_activity(activity.id, "2025-01-03T10:00:00Z", None)
-            .expect("Failed to log");
-        repo.log_activity(activity.id, "2025-01-05T10:00:00Z", None)
-            .expect("Failed to log");
+        // Tue/Thu at 07:00 starting Monday Jan 6
+        repo.create_planned_activity(&plan_request(yoga.id, vec![2, 4]))
+            .expect("Failed to create plan");
 
-        // Current period (Jan 8-14): 5 unique days
-        repo.log_activity(activity.id, "2025-01-08T10:00:00Z", None)
-            .expect("Failed to log");
-        repo.log_activity(activity.id, "2025-01-09T10:00:00Z", None)
-            .expect("Failed to log");
-        repo.log_activity(activity.id, "2025-01-10T10:00:00Z", None)
+        // Jan 7 (Tue): done, 30 minutes late
+        repo.log_activity(yoga.id, "2025-01-07T07:30:00Z", None)
             .expect("Failed to log");
-        repo.log_activity(activity.id, "2025-01-12T10:00:00Z", None)
+        // Jan 9 (Thu): missed - logged in the evening instead (unplanned)
+        repo.log_activity(yoga.id, "2025-01-09T19:00:00Z", None)
             .expect("Failed to log");
-        repo.log_activity(activity.id, "2025-01-14T10:00:00Z", None)
+        // Jan 14 (Tue): done, early
+        repo.log_activity(yoga.id, "2025-01-14T06:15:00Z", None)
             .expect("Failed to log");
 
-        let trend = repo
-            .get_activity_trend(activity.id, 7, "2025-01-15T00:00:00Z")
-            .expect("Failed to get trend");
-
-        assert_eq!(trend.activity_id, activity.id);
-        assert_eq!(trend.current_period_days, 5);
-        assert_eq!(trend.previous_period_days, 3);
-        assert_eq!(trend.change_days, 2);
-        // (5 - 3) / 3 * 100 = 66.67%
-        assert!((trend.change_percentage - 66.67).abs() < 0.1);
-        assert_eq!(trend.trend, Trend::Improving);
+        let report = repo
+            .get_activity_adherence(
+                yoga.id,
+                "2025-01-06T00:00:00Z",
+                "2025-01-19T23:59:59Z",
+                "2025-01-16T07:30:00Z",
+            )
+            .expect("Failed to get adherence");
+
+        assert_eq!(report.activity_id, Some(yoga.id));
+        // Jan 7, 9, 14, 16
+        assert_eq!(report.planned, 4);
+        assert_eq!(report.done, 2);
+        assert_eq!(report.missed, 1);
+        // Jan 16 07:00 window is still open at 07:30
+        assert_eq!(report.pending, 1);
+        assert_eq!(report.unplanned, 1);
+        assert!((report.adherence_percentage - 66.67).abs() < 0.1);
+        assert_eq!(report.occurrences[0].status, PlannedOccurrenceStatus::Done);
+        assert_eq!(
+            report.occurrences[0].matched_logged_at.as_deref(),
+            Some("2025-01-07T07:30:00Z")
+        );
     }
 
     #[test]
-    fn test_get_activity_trend_declining() {
+    fn test_get_group_adherence() {
         let (repo, _temp_dir) = setup_test_repo();
 
         let group = repo
             .create_activity_group("Exercise", None)
             .expect("Failed to create group");
-        let activity = repo
+        let yoga = repo
+            .create_activity(group.id, "Yoga", None, None)
+            .expect("Failed to create activity");
+        let running = repo
             .create_activity(group.id, "Running", None, None)
             .expect("Failed to create activity");
 
-        // Previous period: 5 days
-        repo.log_activity(activity.id, "2025-01-01T10:00:00Z", None)
-            .expect("Failed to log");
-        repo.log_activity(activity.id, "2025-01-02T10:00:00Z", None)
-            .expect("Failed to log");
-        repo.log_activity(activity.id, "2025-01-03T10:00:00Z", None)
-            .expect("Failed to log");
-        repo.log_activity(activity.id, "2025-01-04T10:00:00Z", None)
-            .expect("Failed to log");
-        repo.log_activity(activity.id, "2025-01-05T10:00:00Z", None)
-            .expect("Failed to log");
+        repo.create_planned_activity(&plan_request(yoga.id, vec![2]))
+            .expect("Failed to create plan");
+        repo.create_planned_activity(&plan_request(running.id, vec![2]))
+            .expect("Failed to create plan");
 
-        // Current period: 2 days
-        repo.log_activity(activity.id, "2025-01-08T10:00:00Z", None)
+        // Only yoga was done; running was logged on an unplanned day
+        repo.log_activity(yoga.id, "2025-01-07T07:00:00Z", None)
             .expect("Failed to log");
-        repo.log_activity(activity.id, "2025-01-10T10:00:00Z", None)
+        repo.log_activity(running.id, "2025-01-08T07:00:00Z", None)
             .expect("Failed to log");
 
-        let trend = repo
-            .get_activity_trend(activity.id, 7, "2025-01-15T00:00:00Z")
-            .expect("Failed to get trend");
+        let report = repo
+            .get_group_adherence(
+                group.id,
+                "2025-01-06T00:00:00Z",
+                "2025-01-12T23:59:59Z",
+                "2025-01-13T00:00:00Z",
+            )
+            .expect("Failed to get group adherence");
 
-        assert_eq!(trend.previous_period_days, 5);
-        assert_eq!(trend.current_period_days, 2);
-        assert_eq!(trend.change_days, -3);
-        // (2 - 5) / 5 * 100 = -60%
-        assert!((trend.change_percentage + 60.0).abs() < 0.1);
-        assert_eq!(trend.trend, Trend::Declining);
+        assert_eq!(report.group_id, Some(group.id));
+        assert_eq!(report.planned, 2);
+        assert_eq!(report.done, 1);
+        assert_eq!(report.missed, 1);
+        assert_eq!(report.unplanned, 1);
     }
 
     #[test]
-    fn test_get_activity_trend_stable() {
+    fn test_get_due_plan_reminders() {
         let (repo, _temp_dir) = setup_test_repo();
 
         let group = repo
             .create_activity_group("Exercise", None)
             .expect("Failed to create group");
-        let activity = repo
-            .create_activity(group.id, "Running", None, None)
+        let yoga = repo
+            .create_activity(group.id, "Yoga", None, None)
             .expect("Failed to create activity");
 
-        // Previous period: 4 days
-        repo.log_activity(activity.id, "2025-01-01T10:00:00Z", None)
-            .expect("Failed to log");
-        repo.log_activity(activity.id, "2025-01-02T10:00:00Z", None)
-            .expect("Failed to log");
-        repo.log_activity(activity.id, "2025-01-03T10:00:00Z", None)
-            .expect("Failed to log");
-        repo.log_activity(activity.id, "2025-01-04T10:00:00Z", None)
-            .expect("Failed to log");
-
-        // Current period: 4 days
-        repo.log_activity(activity.id, "2025-01-08T10:00:00Z", None)
-            .expect("Failed to log");
-        repo.log_activity(activity.id, "2025-01-09T10:00:00Z", None)
-            .expect("Failed to log");
-        repo.log_activity(activity.id, "2025-01-10T10:00:00Z", None)
-            .expect("Failed to log");
-        repo.log_activity(activity.id, "2025-01-11T10:00:00Z", None)
-            .expect("Failed to log");
+        let mut request = plan_request(yoga.id, vec![2]);
+        request.reminder_enabled = true;
+        request.reminder_minutes_before = Some(30);
+        let plan = repo
+            .create_planned_activity(&request)
+            .expect("Failed to create plan");
 
-        let trend = repo
-            .get_activity_trend(activity.id, 7, "2025-01-15T00:00:00Z")
-            .expect("Failed to get trend");
+        let at = |s: &str| {
+            chrono::NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S").expect("valid time")
+        };
 
-        assert_eq!(trend.previous_period_days, 4);
-        assert_eq!(trend.current_period_days, 4);
-        assert_eq!(trend.change_days, 0);
-        assert_eq!(trend.change_percentage, 0.0);
-        assert_eq!(trend.trend, Trend::Stable);
+        // Too early (Tue Jan 7, 06:00 - reminder opens at 06:30)
+        assert!(repo
+            .get_due_plan_reminders(at("2025-01-07 06:00:00"))
+            .expect("Failed to get reminders")
+            .is_empty());
+
+        let due = repo
+            .get_due_plan_reminders(at("2025-01-07 06:40:00"))
+            .expect("Failed to get reminders");
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].planned_activity_id, plan.id);
+        assert_eq!(due[0].activity_name, "Yoga");
+        assert_eq!(due[0].scheduled_at, "2025-01-07T07:00:00Z");
+
+        // Marked reminders are not repeated for the same occurrence
+        repo.mark_plans_reminded(&[plan.id], at("2025-01-07 06:40:00"))
+            .expect("Failed to mark reminded");
+        assert!(repo
+            .get_due_plan_reminders(at("2025-01-07 06:50:00"))
+            .expect("Failed to get reminders")
+            .is_empty());
+
+        // Next week's occurrence is reminded again
+        assert_eq!(
+            repo.get_due_plan_reminders(at("2025-01-14 06:45:00"))
+                .expect("Failed to get reminders")
+                .len(),
+            1
+        );
     }
 
     #[test]
-    fn test_get_activity_trend_from_zero() {
+    fn test_get_due_plan_reminders_without_lead_time() {
         let (repo, _temp_dir) = setup_test_repo();
 
         let group = repo
             .create_activity_group("Exercise", None)
             .expect("Failed to create group");
-        let activity = repo
-            .create_activity(group.id, "Running", None, None)
+        let yoga = repo
+            .create_activity(group.id, "Yoga", None, None)
             .expect("Failed to create activity");
 
-        // Previous period: 0 days
-        // Current period: 3 days
-        repo.log_activity(activity.id, "2025-01-08T10:00:00Z", None)
-            .expect("Failed to log");
-        repo.log_activity(activity.id, "2025-01-10T10:00:00Z", None)
-            .expect("Failed to log");
-        repo.log_activity(activity.id, "2025-01-12T10:00:00Z", None)
-            .expect("Failed to log");
+        // Reminder at the planned time itself (default lead of 0 minutes)
+        let mut request = plan_request(yoga.id, vec![2]);
+        request.reminder_enabled = true;
+        let plan = repo
+            .create_planned_activity(&request)
+            .expect("Failed to create plan");
+        assert_eq!(plan.reminder_minutes_before, 0);
 
-        let trend = repo
-            .get_activity_trend(activity.id, 7, "2025-01-15T00:00:00Z")
-            .expect("Failed to get trend");
+        let at = |s: &str| {
+            chrono::NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S").expect("valid time")
+        };
 
-        assert_eq!(trend.previous_period_days, 0);
-        assert_eq!(trend.current_period_days, 3);
-        // From 0 to 3 = 100% improvement (per algorithm spec)
-        assert_eq!(trend.change_percentage, 100.0);
-        assert_eq!(trend.trend, Trend::Improving);
+        assert!(repo
+            .get_due_plan_reminders(at("2025-01-07 06:59:00"))
+            .expect("Failed to get reminders")
+            .is_empty());
+
+        // A check just after 07:00 still catches the occurrence
+        let due = repo
+            .get_due_plan_reminders(at("2025-01-07 07:00:40"))
+            .expect("Failed to get reminders");
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].scheduled_at, "2025-01-07T07:00:00Z");
+
+        repo.mark_plans_reminded(&[plan.id], at("2025-01-07 07:00:40"))
+            .expect("Failed to mark reminded");
+        assert!(repo
+            .get_due_plan_reminders(at("2025-01-07 07:01:40"))
+            .expect("Failed to get reminders")
+            .is_empty());
+
+        // Long past the occurrence, nothing is sent late
+        assert!(repo
+            .get_due_plan_reminders(at("2025-01-14 07:30:00"))
+            .expect("Failed to get reminders")
+            .is_empty());
+    }
+
+    #[test]
+    fn test_plan_reminders_use_plan_timezone() {
+        let (repo, _temp_dir) = setup_test_repo();
+
+        let group = repo
+            .create_activity_group("Exercise", None)
+            .expect("Failed to create group");
+        let yoga = repo
+            .create_activity(group.id, "Yoga", None, None)
+            .expect("Failed to create activity");
+
+        // Tuesdays at 07:00 in Berlin, which is 06:00 UTC in January
+        let mut request = plan_request(yoga.id, vec![2]);
+        request.timezone = Some("Europe/Berlin".to_string());
+        request.reminder_enabled = true;
+        request.reminder_minutes_before = Some(30);
+        let plan = repo
+            .create_planned_activity(&request)
+            .expect("Failed to create plan");
+        assert_eq!(plan.timezone, "Europe/Berlin");
+
+        let at = |s: &str| {
+            chrono::NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S").expect("valid time")
+        };
+        assert!(repo
+            .get_due_plan_reminders(at("2025-01-07 06:40:00"))
+            .expect("Failed to get reminders")
+            .is_empty());
+        let due = repo
+            .get_due_plan_reminders(at("2025-01-07 05:40:00"))
+            .expect("Failed to get reminders");
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].scheduled_at, "2025-01-07T06:00:00Z");
     }
 
     // ========================================
@@ -2575,4 +4182,235 @@ mod tests {
 
         assert!(matches!(result, Err(ActivityError::GoalNotFound(999))));
     }
+
+    // ========================================
+    // Group Ordering, Archiving and Appearance Tests
+    // ========================================
+
+    fn group_names(groups: &[ActivityGroup]) -> Vec<&str> {
+        groups.iter().map(|g| g.name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_reorder_activity_groups() {
+        let (repo, _temp_dir) = setup_test_repo();
+
+        let exercise = repo
+            .create_activity_group("Exercise", None)
+            .expect("Failed to create group");
+        let social = repo
+            .create_activity_group("Social", None)
+            .expect("Failed to create group");
+        let work = repo
+            .create_activity_group("Work", None)
+            .expect("Failed to create group");
+
+        // Only Work and Exercise are listed; Social keeps its place after them
+        let groups = repo
+            .reorder_activity_groups(&[work.id, exercise.id])
+            .expect("Failed to reorder groups");
+
+        assert_eq!(group_names(&groups), vec!["Work", "Exercise", "Social"]);
+        assert_eq!(groups[0].sort_order, Some(1));
+        assert_eq!(groups[2].sort_order, Some(3));
+        assert_eq!(groups[2].id, social.id);
+
+        // Order persists across reads
+        let groups = repo.get_activity_groups().expect("Failed to get groups");
+        assert_eq!(group_names(&groups), vec!["Work", "Exercise", "Social"]);
+    }
+
+    #[test]
+    fn test_unpositioned_group_listed_after_positioned_groups() {
+        let (repo, _temp_dir) = setup_test_repo();
+
+        let social = repo
+            .create_activity_group("Social", None)
+            .expect("Failed to create group");
+        repo.reorder_activity_groups(&[social.id])
+            .expect("Failed to reorder groups");
+
+        repo.create_activity_group("Art", None)
+            .expect("Failed to create group");
+
+        let groups = repo.get_activity_groups().expect("Failed to get groups");
+        assert_eq!(group_names(&groups), vec!["Social", "Art"]);
+        assert_eq!(groups[1].sort_order, None);
+    }
+
+    #[test]
+    fn test_reorder_activity_groups_rejects_invalid_ids() {
+        let (repo, _temp_dir) = setup_test_repo();
+
+        let group = repo
+            .create_activity_group("Exercise", None)
+            .expect("Failed to create group");
+
+        let duplicate = repo.reorder_activity_groups(&[group.id, group.id]);
+        assert!(matches!(duplicate, Err(ActivityError::InvalidOrder(_))));
+
+        let unknown = repo.reorder_activity_groups(&[group.id, 999]);
+        assert!(matches!(unknown, Err(ActivityError::GroupNotFound(999))));
+    }
+
+    #[test]
+    fn test_reorder_activities_within_group() {
+        let (repo, _temp_dir) = setup_test_repo();
+
+        let group = repo
+            .create_activity_group("Exercise", None)
+            .expect("Failed to create group");
+        repo.create_activity(group.id, "Cycling", None, None)
+            .expect("Failed to create activity");
+        repo.create_activity(group.id, "Running", None, None)
+            .expect("Failed to create activity");
+        let yoga = repo
+            .create_activity(group.id, "Yoga", None, None)
+            .expect("Failed to create activity");
+
+        let activities = repo
+            .reorder_activities(group.id, &[yoga.id])
+            .expect("Failed to reorder activities");
+
+        let names: Vec<&str> = activities.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(names, vec!["Yoga", "Cycling", "Running"]);
+
+        let activities = repo
+            .get_activities_by_group(group.id)
+            .expect("Failed to get activities");
+        assert_eq!(activities[0].id, yoga.id);
+    }
+
+    #[test]
+    fn test_reorder_activities_rejects_activity_from_other_group() {
+        let (repo, _temp_dir) = setup_test_repo();
+
+        let exercise = repo
+            .create_activity_group("Exercise", None)
+            .expect("Failed to create group");
+        let social = repo
+            .create_activity_group("Social", None)
+            .expect("Failed to create group");
+        let call = repo
+            .create_activity(social.id, "Phone call", None, None)
+            .expect("Failed to create activity");
+
+        let result = repo.reorder_activities(exercise.id, &[call.id]);
+        assert!(matches!(result, Err(ActivityError::InvalidOrder(_))));
+
+        let result = repo.reorder_activities(999, &[call.id]);
+        assert!(matches!(result, Err(ActivityError::GroupNotFound(999))));
+    }
+
+    #[test]
+    fn test_archive_activity_group_keeps_history() {
+        let (repo, _temp_dir) = setup_test_repo();
+
+        let group = repo
+            .create_activity_group("Exercise", None)
+            .expect("Failed to create group");
+        repo.create_activity_group("Social", None)
+            .expect("Failed to create group");
+        let activity = repo
+            .create_activity(group.id, "Running", None, None)
+            .expect("Failed to create activity");
+        repo.log_activity(activity.id, "2025-01-10T10:00:00Z", None)
+            .expect("Failed to log activity");
+
+        let archived = repo
+            .archive_activity_group(group.id)
+            .expect("Failed to archive group");
+        assert!(archived.archived_at.is_some());
+        assert!(archived.deleted_at.is_none());
+
+        // Hidden from pickers, listed separately
+        let groups = repo.get_activity_groups().expect("Failed to get groups");
+        assert_eq!(group_names(&groups), vec!["Social"]);
+        let archived_groups = repo
+            .get_archived_activity_groups()
+            .expect("Failed to get archived groups");
+        assert_eq!(group_names(&archived_groups), vec!["Exercise"]);
+
+        // Activities and logs are untouched (no cascade)
+        let activities = repo
+            .get_activities_by_group(group.id)
+            .expect("Failed to get activities");
+        assert_eq!(activities.len(), 1);
+        let logs = repo
+            .get_activity_logs(Some(activity.id), None, None)
+            .expect("Failed to get logs");
+        assert_eq!(logs.len(), 1);
+
+        let restored = repo
+            .unarchive_activity_group(group.id)
+            .expect("Failed to unarchive group");
+        assert!(restored.archived_at.is_none());
+        let groups = repo.get_activity_groups().expect("Failed to get groups");
+        assert_eq!(group_names(&groups), vec!["Exercise", "Social"]);
+    }
+
+    #[test]
+    fn test_archive_deleted_group_not_found() {
+        let (repo, _temp_dir) = setup_test_repo();
+
+        let group = repo
+            .create_activity_group("Exercise", None)
+            .expect("Failed to create group");
+        repo.delete_activity_group(group.id)
+            .expect("Failed to delete group");
+
+        let result = repo.archive_activity_group(group.id);
+        assert!(matches!(result, Err(ActivityError::GroupNotFound(_))));
+    }
+
+    #[test]
+    fn test_activity_inherits_group_appearance() {
+        let (repo, _temp_dir) = setup_test_repo();
+
+        let group = repo
+            .create_activity_group("Exercise", None)
+            .expect("Failed to create group");
+        let group = repo
+            .set_activity_group_appearance(group.id, Some("#00FF00"), Some("dumbbell"))
+            .expect("Failed to set appearance");
+        assert_eq!(group.color.as_ref().map(|c| c.value()), Some("#00FF00"));
+        assert_eq!(group.icon.as_deref(), Some("dumbbell"));
+
+        // No color/icon given: group defaults apply
+        let inherited = repo
+            .create_activity(group.id, "Running", None, None)
+            .expect("Failed to create activity");
+        assert_eq!(inherited.color.as_ref().map(|c| c.value()), Some("#00FF00"));
+        assert_eq!(inherited.icon.as_deref(), Some("dumbbell"));
+
+        // Explicit values win
+        let explicit = repo
+            .create_activity(group.id, "Yoga", Some("#FF0000"), None)
+            .expect("Failed to create activity");
+        assert_eq!(explicit.color.as_ref().map(|c| c.value()), Some("#FF0000"));
+        assert_eq!(explicit.icon.as_deref(), Some("dumbbell"));
+
+        let stored = repo
+            .get_activities_by_group(group.id)
+            .expect("Failed to get activities");
+        assert!(stored.iter().all(|a| a.icon.as_deref() == Some("dumbbell")));
+    }
+
+    #[test]
+    fn test_set_activity_group_appearance_validation() {
+        let (repo, _temp_dir) = setup_test_repo();
+
+        let result = repo.set_activity_group_appearance(999, None, None);
+        assert!(matches!(result, Err(ActivityError::GroupNotFound(999))));
+
+        let group = repo
+            .create_activity_group("Exercise", None)
+            .expect("Failed to create group");
+        let long_icon = "x".repeat(21);
+        let result = repo.set_activity_group_appearance(group.id, None, Some(&long_icon));
+        assert!(matches!(
+            result,
+            Err(ActivityError::ActivityIconTooLong(21))
+        ));
+    }
 }
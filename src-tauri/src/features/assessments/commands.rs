@@ -2,12 +2,13 @@
 use super::models::{UNANSWERED, *};
 use super::repository::AssessmentRepository;
 use super::repository_trait::AssessmentRepositoryTrait;
+use crate::features::scheduling::SchedulingRepository;
 use crate::{
     errors::{ErrorType, ToCommandError},
     AppState, CommandError,
 };
 use tauri::State;
-use tracing::error;
+use tracing::{error, info, warn};
 use validator::Validate;
 
 /// Submit a completed assessment
@@ -23,7 +24,7 @@ pub async fn submit_assessment(
     })?;
 
     let repo = AssessmentRepository::new(state.db.clone());
-    submit_assessment_impl(&repo, &request).map_err(|e| {
+    let response = submit_assessment_impl(&repo, &request).map_err(|e| {
         error!(
             "submit_assessment error: {} (type: '{}', responses: {}, has_notes: {})",
             e,
@@ -32,7 +33,55 @@ pub async fn submit_assessment(
             request.notes.is_some()
         );
         e.to_command_error()
-    })
+    })?;
+
+    if response.status == AssessmentStatus::Completed {
+        let scheduling = SchedulingRepository::new(state.db.clone());
+        link_reminder(&scheduling, &response);
+        adapt_schedules(&scheduling, &response);
+    }
+
+    Ok(response)
+}
+
+/// Record a completed assessment against the reminder that asked for it.
+/// The assessment is already saved, so failures are only logged.
+fn link_reminder(repo: &SchedulingRepository, response: &AssessmentResponse) {
+    let completed_at = crate::utils::parse_timestamp(&response.completed_at)
+        .map(|t| t.and_utc())
+        .unwrap_or_else(chrono::Utc::now);
+
+    if let Err(e) =
+        repo.link_assessment_completion(response.assessment_type.id, response.id, completed_at)
+    {
+        warn!(
+            "Failed to link assessment {} to its reminder: {}",
+            response.id, e
+        );
+    }
+}
+
+/// Let adaptive schedules of this assessment react to the new result.
+/// The assessment is already saved, so failures are only logged; the
+/// scheduler retries on its next pass.
+fn adapt_schedules(repo: &SchedulingRepository, response: &AssessmentResponse) {
+    match repo.apply_adaptive_rules(Some(response.assessment_type.id), chrono::Utc::now()) {
+        Ok(changes) => {
+            for change in changes {
+                info!(
+                    "Schedule {} changed from {} to {}: {}",
+                    change.schedule_id,
+                    change.from_frequency.as_str(),
+                    change.to_frequency.as_str(),
+                    change.reason
+                );
+            }
+        }
+        Err(e) => warn!(
+            "Failed to apply adaptive schedules after assessment {}: {}",
+            response.id, e
+        ),
+    }
 }
 
 /// Calculate score based on assessment status (draft vs completed)
@@ -0,0 +1,52 @@
+// Backup commands - Full export and import (Tauri commands)
+
+use chrono::Utc;
+use tauri::State;
+use tracing::error;
+
+use super::models::*;
+use super::repository::BackupRepository;
+use crate::{errors::ToCommandError, AppState, CommandError};
+
+/// Write every table to a versioned JSON document at `path`
+#[tauri::command]
+#[specta::specta]
+pub fn export_data(path: String, state: State<AppState>) -> Result<ExportSummary, CommandError> {
+    let repo = BackupRepository::new(state.db.clone());
+    let exported_at = Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+    repo.export_document(&exported_at)
+        .and_then(|document| {
+            let json = serde_json::to_vec_pretty(&document)?;
+            std::fs::write(&path, json).map_err(|e| BackupError::Io(format!("{}: {}", path, e)))?;
+            Ok(document.summary())
+        })
+        .map_err(|e| {
+            error!("export_data error: {} (path: {})", e, path);
+            e.to_command_error()
+        })
+}
+
+/// Import an export document from `path`. Restore needs a database without
+/// any data; merge skips rows already there. With `dry_run`, reports what
+/// would be imported without writing anything.
+#[tauri::command]
+#[specta::specta]
+pub fn import_data(
+    path: String,
+    mode: ImportMode,
+    dry_run: bool,
+    state: State<AppState>,
+) -> Result<ImportReport, CommandError> {
+    let repo = BackupRepository::new(state.db.clone());
+    std::fs::read(&path)
+        .map_err(|e| BackupError::Io(format!("{}: {}", path, e)))
+        .and_then(|json| Ok(serde_json::from_slice::<ExportDocument>(&json)?))
+        .and_then(|document| repo.import_document(&document, mode, dry_run))
+        .map_err(|e| {
+            error!(
+                "import_data error: {} (path: {}, mode: {:?}, dry_run: {})",
+                e, path, mode, dry_run
+            );
+            e.to_command_error()
+        })
+}
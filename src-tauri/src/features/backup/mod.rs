@@ -0,0 +1,11 @@
+// Backup feature module
+// Full JSON export of every table, and import by restore or merge
+
+pub mod commands;
+pub mod models;
+pub mod repository;
+pub mod tables;
+
+// Re-export commonly used types
+pub use models::{BackupError, ExportDocument, ImportMode, ImportReport};
+pub use repository::BackupRepository;
@@ -0,0 +1,205 @@
+// Backup feature models
+// The versioned export document, import options and what an import did
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::errors::{CommandError, ErrorType, ToCommandError};
+
+/// Identifies an export document
+pub const EXPORT_FORMAT: &str = "mental-health-tracker-export";
+
+/// Version of the document layout; bump when a table or column changes
+/// meaning, and teach the import to read the older versions
+pub const EXPORT_SCHEMA_VERSION: u32 = 1;
+
+/// Version of the app writing the document
+pub const APP_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Backup-specific errors
+#[derive(Error, Debug)]
+pub enum BackupError {
+    #[error("Not an export file of this app")]
+    UnsupportedFormat,
+
+    #[error("Export schema version {found} is newer than this app supports ({supported})")]
+    UnsupportedSchemaVersion { found: u32, supported: u32 },
+
+    #[error("The database already has {0}; use merge mode to import into it")]
+    DatabaseNotEmpty(String),
+
+    #[error("Unknown table in export: {0}")]
+    UnknownTable(String),
+
+    #[error("Unknown column in export: {table}.{column}")]
+    UnknownColumn { table: String, column: String },
+
+    #[error("Invalid value in export: {table}.{column} of row {row}")]
+    InvalidValue {
+        table: String,
+        row: usize,
+        column: String,
+    },
+
+    #[error("{table}.{column} of row {row} refers to {id}, which is not in the export")]
+    MissingReference {
+        table: String,
+        row: usize,
+        column: String,
+        id: i64,
+    },
+
+    #[error("Unknown assessment type: {0}")]
+    UnknownAssessmentType(String),
+
+    #[error("Row {row} of {table} could not be imported: {message}")]
+    InvalidRow {
+        table: String,
+        row: usize,
+        message: String,
+    },
+
+    #[error("Database error: {0}")]
+    Database(#[from] rusqlite::Error),
+
+    #[error("Invalid export file: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("File error: {0}")]
+    Io(String),
+}
+
+impl ToCommandError for BackupError {
+    fn to_command_error(&self) -> CommandError {
+        match self {
+            BackupError::UnsupportedSchemaVersion { found, supported } => {
+                CommandError::permanent(self.to_string(), ErrorType::Validation).with_details(
+                    serde_json::json!({
+                        "field": "schema_version",
+                        "value": found,
+                        "supported": supported
+                    }),
+                )
+            }
+            BackupError::DatabaseNotEmpty(table) => {
+                CommandError::permanent(self.to_string(), ErrorType::Validation).with_details(
+                    serde_json::json!({
+                        "field": "mode",
+                        "table": table
+                    }),
+                )
+            }
+            BackupError::UnknownColumn { table, column }
+            | BackupError::InvalidValue { table, column, .. }
+            | BackupError::MissingReference { table, column, .. } => {
+                CommandError::permanent(self.to_string(), ErrorType::Validation).with_details(
+                    serde_json::json!({
+                        "table": table,
+                        "column": column
+                    }),
+                )
+            }
+            BackupError::UnsupportedFormat
+            | BackupError::UnknownTable(_)
+            | BackupError::UnknownAssessmentType(_)
+            | BackupError::Json(_) => {
+                CommandError::permanent(self.to_string(), ErrorType::Validation)
+            }
+            BackupError::InvalidRow { table, row, .. } => {
+                CommandError::permanent(self.to_string(), ErrorType::ConstraintViolation)
+                    .with_details(serde_json::json!({
+                        "table": table,
+                        "row": row
+                    }))
+            }
+            BackupError::Database(e) => CommandError::from_rusqlite_error(e),
+            BackupError::Io(_) => CommandError::permanent(self.to_string(), ErrorType::IoError),
+        }
+    }
+}
+
+/// One row of a table: its id and columns by name
+pub type ExportRow = serde_json::Map<String, serde_json::Value>;
+
+/// Every table of the database in one JSON document
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ExportDocument {
+    pub format: String, // EXPORT_FORMAT
+    pub schema_version: u32,
+    pub app_version: String,
+    pub database_version: i32, // Last migration applied to the exporting database
+    pub exported_at: String,
+    pub tables: BTreeMap<String, Vec<ExportRow>>, // Rows of each table, by id
+}
+
+impl ExportDocument {
+    /// Row count of each table, by table name
+    pub fn summary(&self) -> ExportSummary {
+        ExportSummary {
+            schema_version: self.schema_version,
+            app_version: self.app_version.clone(),
+            exported_at: self.exported_at.clone(),
+            tables: self
+                .tables
+                .iter()
+                .map(|(table, rows)| TableRowCount {
+                    table: table.clone(),
+                    rows: rows.len() as i32,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// What an export wrote
+#[derive(Serialize, Deserialize, specta::Type, Clone, Debug)]
+pub struct ExportSummary {
+    pub schema_version: u32,
+    pub app_version: String,
+    pub exported_at: String,
+    pub tables: Vec<TableRowCount>,
+}
+
+#[derive(Serialize, Deserialize, specta::Type, Clone, Debug, PartialEq)]
+pub struct TableRowCount {
+    pub table: String,
+    pub rows: i32,
+}
+
+/// How an import treats data already in the database
+#[derive(Serialize, Deserialize, specta::Type, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportMode {
+    Restore, // Only into a database without any data, e.g. on a new machine
+    Merge,   // Add what is not already there
+}
+
+/// What an import did, or would do in a dry run
+#[derive(Serialize, Deserialize, specta::Type, Clone, Debug)]
+pub struct ImportReport {
+    pub mode: ImportMode,
+    pub dry_run: bool, // Nothing was written
+    pub schema_version: u32,
+    pub app_version: String, // Of the app that wrote the export
+    pub tables: Vec<TableImportCount>,
+}
+
+impl ImportReport {
+    pub fn imported(&self) -> i32 {
+        self.tables.iter().map(|t| t.imported).sum()
+    }
+
+    pub fn duplicates(&self) -> i32 {
+        self.tables.iter().map(|t| t.duplicates).sum()
+    }
+}
+
+/// Rows of one table added, and rows skipped as already present
+#[derive(Serialize, Deserialize, specta::Type, Clone, Debug, PartialEq)]
+pub struct TableImportCount {
+    pub table: String,
+    pub imported: i32,
+    pub duplicates: i32, // Same content and timestamp as a row already there
+}
@@ -0,0 +1,385 @@
+// Backup repository - Export every table to one document and import it back
+//
+// An import runs in a single IMMEDIATE transaction: a dry run rolls it back,
+// and any invalid row rolls back everything before it. Rows get new ids, and
+// references are rewritten to the ids their targets got. A row with the same
+// identity (see tables.rs) as one already in the database, or earlier in the
+// document, is skipped and its id mapped to that row.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use rusqlite::types::{Value as SqlValue, ValueRef};
+use serde_json::Value;
+
+use super::models::*;
+use super::tables::{table_spec, Reference, TableSpec, ASSESSMENT_TYPES, TABLES};
+use crate::db::Database;
+
+/// Old id -> new id, per table
+type IdMaps = HashMap<&'static str, HashMap<i64, i64>>;
+
+pub struct BackupRepository {
+    db: Arc<Database>,
+}
+
+impl BackupRepository {
+    pub fn new(db: Arc<Database>) -> Self {
+        Self { db }
+    }
+
+    /// Every exported table, rows ordered by id
+    pub fn export_document(&self, exported_at: &str) -> Result<ExportDocument, BackupError> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock();
+
+        let database_version: i32 = conn.query_row(
+            "SELECT COALESCE(MAX(version), 0) FROM schema_migrations",
+            [],
+            |row| row.get(0),
+        )?;
+
+        let mut tables = std::collections::BTreeMap::new();
+        for spec in TABLES {
+            let mut columns = vec!["id"];
+            columns.extend_from_slice(spec.columns);
+            let mut stmt = conn.prepare(&format!(
+                "SELECT {} FROM {} ORDER BY id",
+                columns.join(", "),
+                spec.name
+            ))?;
+            let mut rows = stmt.query([])?;
+            let mut exported = Vec::new();
+            while let Some(row) = rows.next()? {
+                let mut values = ExportRow::new();
+                for (index, column) in columns.iter().enumerate() {
+                    let value =
+                        to_json(row.get_ref(index)?).ok_or_else(|| BackupError::InvalidValue {
+                            table: spec.name.to_string(),
+                            row: exported.len(),
+                            column: column.to_string(),
+                        })?;
+                    values.insert(column.to_string(), value);
+                }
+                exported.push(values);
+            }
+            tables.insert(spec.name.to_string(), exported);
+        }
+
+        Ok(ExportDocument {
+            format: EXPORT_FORMAT.to_string(),
+            schema_version: EXPORT_SCHEMA_VERSION,
+            app_version: APP_VERSION.to_string(),
+            database_version,
+            exported_at: exported_at.to_string(),
+            tables,
+        })
+    }
+
+    /// Import `document`. Restore needs a database without any data; merge
+    /// adds only rows that are not already there. A dry run checks every
+    /// row and reports what would be imported without writing anything.
+    pub fn import_document(
+        &self,
+        document: &ExportDocument,
+        mode: ImportMode,
+        dry_run: bool,
+    ) -> Result<ImportReport, BackupError> {
+        validate_document(document)?;
+
+        let conn = self.db.get_connection();
+        let mut conn = conn.lock();
+        let tx = conn.transaction_with_behavior(rusqlite::TransactionBehavior::Immediate)?;
+
+        if mode == ImportMode::Restore {
+            for spec in TABLES.iter().filter(|spec| !spec.reference_only) {
+                let has_rows: bool = tx.query_row(
+                    &format!("SELECT EXISTS (SELECT 1 FROM {})", spec.name),
+                    [],
+                    |row| row.get(0),
+                )?;
+                if has_rows {
+                    return Err(BackupError::DatabaseNotEmpty(spec.name.replace('_', " ")));
+                }
+            }
+        }
+
+        let mut ids: IdMaps = HashMap::new();
+        let mut counts = Vec::new();
+        for spec in TABLES {
+            let rows = document
+                .tables
+                .get(spec.name)
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+            let (imported, duplicates) = import_table(&tx, spec, rows, &mut ids)?;
+            if !spec.reference_only {
+                counts.push(TableImportCount {
+                    table: spec.name.to_string(),
+                    imported,
+                    duplicates,
+                });
+            }
+        }
+
+        if dry_run {
+            tx.rollback()?;
+        } else {
+            tx.commit()?;
+        }
+
+        Ok(ImportReport {
+            mode,
+            dry_run,
+            schema_version: document.schema_version,
+            app_version: document.app_version.clone(),
+            tables: counts,
+        })
+    }
+}
+
+/// Check the format, version, tables and columns before touching the database
+fn validate_document(document: &ExportDocument) -> Result<(), BackupError> {
+    if document.format != EXPORT_FORMAT {
+        return Err(BackupError::UnsupportedFormat);
+    }
+    if document.schema_version > EXPORT_SCHEMA_VERSION {
+        return Err(BackupError::UnsupportedSchemaVersion {
+            found: document.schema_version,
+            supported: EXPORT_SCHEMA_VERSION,
+        });
+    }
+
+    for (table, rows) in &document.tables {
+        let spec = table_spec(table).ok_or_else(|| BackupError::UnknownTable(table.clone()))?;
+        for (index, row) in rows.iter().enumerate() {
+            if let Some(column) = row
+                .keys()
+                .find(|column| *column != "id" && !spec.columns.contains(&column.as_str()))
+            {
+                return Err(BackupError::UnknownColumn {
+                    table: table.clone(),
+                    column: column.clone(),
+                });
+            }
+            if row.get("id").and_then(Value::as_i64).is_none() {
+                return Err(BackupError::InvalidValue {
+                    table: table.clone(),
+                    row: index,
+                    column: "id".to_string(),
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Insert the rows of one table, skipping duplicates.
+/// Returns (imported, duplicates).
+fn import_table(
+    tx: &rusqlite::Transaction,
+    spec: &'static TableSpec,
+    rows: &[ExportRow],
+    ids: &mut IdMaps,
+) -> Result<(i32, i32), BackupError> {
+    let mut existing = existing_identities(tx, spec)?;
+    let (mut imported, mut duplicates) = (0, 0);
+
+    for (index, row) in rows.iter().enumerate() {
+        let old_id = row.get("id").and_then(Value::as_i64).unwrap_or_default();
+        let values = remap_row(spec, index, row, ids)?;
+
+        let identity = identity_key(spec, |column| values.get(column).cloned());
+        if let Some(&id) = existing.get(&identity) {
+            ids.entry(spec.name).or_default().insert(old_id, id);
+            duplicates += 1;
+            continue;
+        }
+        if spec.reference_only {
+            // Built-in rows only come from migrations
+            let code = values
+                .get("code")
+                .and_then(Value::as_str)
+                .unwrap_or_default();
+            return Err(match spec.name {
+                ASSESSMENT_TYPES => BackupError::UnknownAssessmentType(code.to_string()),
+                _ => BackupError::UnknownTable(spec.name.to_string()),
+            });
+        }
+
+        // Columns missing from the row take their defaults
+        let columns: Vec<&str> = spec
+            .columns
+            .iter()
+            .copied()
+            .filter(|column| values.contains_key(*column))
+            .collect();
+        let mut params: Vec<SqlValue> = Vec::with_capacity(columns.len());
+        for column in &columns {
+            params.push(
+                to_sql(&values[*column]).ok_or_else(|| BackupError::InvalidValue {
+                    table: spec.name.to_string(),
+                    row: index,
+                    column: column.to_string(),
+                })?,
+            );
+        }
+        let sql = if columns.is_empty() {
+            format!("INSERT INTO {} DEFAULT VALUES", spec.name)
+        } else {
+            format!(
+                "INSERT INTO {} ({}) VALUES ({})",
+                spec.name,
+                columns.join(", "),
+                vec!["?"; columns.len()].join(", ")
+            )
+        };
+        tx.execute(&sql, rusqlite::params_from_iter(params))
+            .map_err(|e| BackupError::InvalidRow {
+                table: spec.name.to_string(),
+                row: index,
+                message: e.to_string(),
+            })?;
+
+        let id = tx.last_insert_rowid();
+        ids.entry(spec.name).or_default().insert(old_id, id);
+        existing.insert(identity, id);
+        imported += 1;
+    }
+
+    Ok((imported, duplicates))
+}
+
+/// The row's columns with references rewritten to their new ids
+fn remap_row(
+    spec: &TableSpec,
+    index: usize,
+    row: &ExportRow,
+    ids: &IdMaps,
+) -> Result<ExportRow, BackupError> {
+    let mut values = row.clone();
+    values.remove("id");
+
+    for (column, reference) in spec.references {
+        let Some(old_id) = values.get(*column).filter(|v| !v.is_null()) else {
+            continue;
+        };
+        let invalid = || BackupError::InvalidValue {
+            table: spec.name.to_string(),
+            row: index,
+            column: column.to_string(),
+        };
+        let old_id = old_id.as_i64().ok_or_else(invalid)?;
+        let target = match reference {
+            Reference::Table(table) => Some(*table),
+            Reference::ByKind {
+                kind_column,
+                tables,
+            } => {
+                let kind = values.get(*kind_column).and_then(Value::as_str);
+                tables
+                    .iter()
+                    .find(|(name, _)| Some(*name) == kind)
+                    .map(|(_, table)| *table)
+            }
+        };
+        let Some(target) = target else {
+            continue;
+        };
+        let new_id = ids
+            .get(target)
+            .and_then(|map| map.get(&old_id))
+            .ok_or_else(|| BackupError::MissingReference {
+                table: spec.name.to_string(),
+                row: index,
+                column: column.to_string(),
+                id: old_id,
+            })?;
+        values.insert(column.to_string(), Value::from(*new_id));
+    }
+
+    Ok(values)
+}
+
+/// Identity of each row already in the table -> its id
+fn existing_identities(
+    tx: &rusqlite::Transaction,
+    spec: &TableSpec,
+) -> Result<HashMap<String, i64>, BackupError> {
+    let mut columns: Vec<&str> = spec.identity.to_vec();
+    if let Some(partial) = &spec.partial_unique {
+        for column in std::iter::once(&partial.column).chain(partial.identity) {
+            if !columns.contains(column) {
+                columns.push(column);
+            }
+        }
+    }
+
+    let mut stmt = tx.prepare(&format!(
+        "SELECT id, {} FROM {}",
+        columns.join(", "),
+        spec.name
+    ))?;
+    let mut rows = stmt.query([])?;
+    let mut existing = HashMap::new();
+    while let Some(row) = rows.next()? {
+        let id: i64 = row.get(0)?;
+        let mut values = Vec::with_capacity(columns.len());
+        for index in 0..columns.len() {
+            values.push(to_json(row.get_ref(index + 1)?));
+        }
+        let identity = identity_key(spec, |column| {
+            let position = columns.iter().position(|c| *c == column)?;
+            values[position].clone()
+        });
+        existing.entry(identity).or_insert(id);
+    }
+    Ok(existing)
+}
+
+/// The identity columns' values as one comparable string. Rows under the
+/// table's partial unique index use that index's columns instead, tagged so
+/// the two kinds of key never match each other.
+fn identity_key(spec: &TableSpec, value_of: impl Fn(&str) -> Option<Value>) -> String {
+    let (tag, identity) = match &spec.partial_unique {
+        Some(partial)
+            if value_of(partial.column).unwrap_or(Value::Null)
+                == partial.value.map_or(Value::Null, Value::from) =>
+        {
+            ("unique", partial.identity)
+        }
+        _ => ("row", spec.identity),
+    };
+    let mut values = vec![Value::from(tag)];
+    values.extend(
+        identity
+            .iter()
+            .map(|column| value_of(column).unwrap_or(Value::Null)),
+    );
+    Value::Array(values).to_string()
+}
+
+/// A column value as JSON; None for blobs, which no table stores
+fn to_json(value: ValueRef) -> Option<Value> {
+    match value {
+        ValueRef::Null => Some(Value::Null),
+        ValueRef::Integer(i) => Some(Value::from(i)),
+        ValueRef::Real(f) => serde_json::Number::from_f64(f).map(Value::Number),
+        ValueRef::Text(text) => Some(Value::from(String::from_utf8_lossy(text).into_owned())),
+        ValueRef::Blob(_) => None,
+    }
+}
+
+/// A JSON value as a column value; None for arrays and objects
+fn to_sql(value: &Value) -> Option<SqlValue> {
+    match value {
+        Value::Null => Some(SqlValue::Null),
+        Value::Bool(b) => Some(SqlValue::Integer(*b as i64)),
+        Value::Number(n) => n
+            .as_i64()
+            .map(SqlValue::Integer)
+            .or_else(|| n.as_f64().map(SqlValue::Real)),
+        Value::String(s) => Some(SqlValue::Text(s.clone())),
+        Value::Array(_) | Value::Object(_) => None,
+    }
+}
@@ -0,0 +1,411 @@
+// Exported tables
+//
+// Each table lists its columns (besides id), the columns holding ids of
+// other tables, and the columns that make two rows the same: their content
+// and timestamp, with references compared after remapping. Where a table
+// has a unique constraint, its identity covers it, and rows under a partial
+// unique index are matched on that index's columns instead, so a merge
+// skips the row instead of failing. Tables come after the tables they
+// refer to.
+//
+// Adding a column to the database means adding it here; a column that
+// changes meaning means bumping EXPORT_SCHEMA_VERSION.
+
+/// How a column refers to rows of another table
+#[derive(Debug)]
+pub enum Reference {
+    /// Id of a row of the table
+    Table(&'static str),
+    /// Id of a row of the table for the row's value of `kind_column`;
+    /// other kinds have no reference
+    ByKind {
+        kind_column: &'static str,
+        tables: &'static [(&'static str, &'static str)],
+    },
+}
+
+/// A partial unique index: rows where `column` is NULL (`value` None) or
+/// equals `value` are the same row when their `identity` columns are equal
+#[derive(Debug)]
+pub struct PartialUnique {
+    pub column: &'static str,
+    pub value: Option<&'static str>,
+    pub identity: &'static [&'static str],
+}
+
+/// Names of live (not deleted) rows are unique
+const LIVE_NAME_UNIQUE: PartialUnique = PartialUnique {
+    column: "deleted_at",
+    value: None,
+    identity: &["name"],
+};
+
+#[derive(Debug)]
+pub struct TableSpec {
+    pub name: &'static str,
+    pub columns: &'static [&'static str],
+    pub references: &'static [(&'static str, Reference)],
+    pub identity: &'static [&'static str],
+    pub partial_unique: Option<PartialUnique>,
+    /// Built-in rows that are matched but never inserted
+    pub reference_only: bool,
+}
+
+pub const ASSESSMENT_TYPES: &str = "assessment_types";
+
+/// Every exported table, each after the tables it refers to
+pub const TABLES: &[TableSpec] = &[
+    TableSpec {
+        name: ASSESSMENT_TYPES,
+        columns: &[
+            "code",
+            "name",
+            "description",
+            "question_count",
+            "min_score",
+            "max_score",
+            "thresholds",
+            "created_at",
+        ],
+        references: &[],
+        identity: &["code"],
+        partial_unique: None,
+        reference_only: true,
+    },
+    TableSpec {
+        name: "activity_groups",
+        columns: &[
+            "name",
+            "description",
+            "created_at",
+            "deleted_at",
+            "sort_order",
+            "archived_at",
+            "color",
+            "icon",
+        ],
+        references: &[],
+        identity: &["name", "created_at"],
+        partial_unique: Some(LIVE_NAME_UNIQUE),
+        reference_only: false,
+    },
+    TableSpec {
+        name: "activities",
+        columns: &[
+            "group_id",
+            "name",
+            "color",
+            "icon",
+            "created_at",
+            "deleted_at",
+            "sort_order",
+        ],
+        references: &[("group_id", Reference::Table("activity_groups"))],
+        identity: &["group_id", "name", "created_at"],
+        partial_unique: Some(LIVE_NAME_UNIQUE),
+        reference_only: false,
+    },
+    TableSpec {
+        name: "mood_checkins",
+        columns: &["mood_rating", "notes", "created_at"],
+        references: &[],
+        identity: &["mood_rating", "notes", "created_at"],
+        partial_unique: None,
+        reference_only: false,
+    },
+    TableSpec {
+        name: "mood_checkin_activities",
+        columns: &["mood_checkin_id", "activity_id"],
+        references: &[
+            ("mood_checkin_id", Reference::Table("mood_checkins")),
+            ("activity_id", Reference::Table("activities")),
+        ],
+        identity: &["mood_checkin_id", "activity_id"],
+        partial_unique: None,
+        reference_only: false,
+    },
+    TableSpec {
+        name: "activity_logs",
+        columns: &[
+            "activity_id",
+            "logged_at",
+            "created_at",
+            "notes",
+            "deleted_at",
+        ],
+        references: &[("activity_id", Reference::Table("activities"))],
+        identity: &["activity_id", "logged_at", "notes"],
+        partial_unique: None,
+        reference_only: false,
+    },
+    TableSpec {
+        name: "activity_goals",
+        columns: &[
+            "activity_id",
+            "group_id",
+            "goal_type",
+            "target_value",
+            "period_days",
+            "created_at",
+            "deleted_at",
+        ],
+        references: &[
+            ("activity_id", Reference::Table("activities")),
+            ("group_id", Reference::Table("activity_groups")),
+        ],
+        identity: &[
+            "activity_id",
+            "group_id",
+            "goal_type",
+            "target_value",
+            "period_days",
+            "created_at",
+        ],
+        partial_unique: None,
+        reference_only: false,
+    },
+    TableSpec {
+        name: "planned_activities",
+        columns: &[
+            "activity_id",
+            "days_of_week",
+            "time_of_day",
+            "timezone",
+            "interval_weeks",
+            "tolerance_minutes",
+            "start_date",
+            "end_date",
+            "reminder_enabled",
+            "reminder_minutes_before",
+            "last_reminded_at",
+            "created_at",
+            "deleted_at",
+        ],
+        references: &[("activity_id", Reference::Table("activities"))],
+        identity: &[
+            "activity_id",
+            "days_of_week",
+            "time_of_day",
+            "start_date",
+            "created_at",
+        ],
+        partial_unique: None,
+        reference_only: false,
+    },
+    TableSpec {
+        name: "goal_templates",
+        columns: &[
+            "name",
+            "description",
+            "definition",
+            "created_at",
+            "deleted_at",
+        ],
+        references: &[],
+        identity: &["name", "created_at"],
+        partial_unique: Some(LIVE_NAME_UNIQUE),
+        reference_only: false,
+    },
+    TableSpec {
+        name: "streak_milestones",
+        columns: &[
+            "streak_kind",
+            "target_id",
+            "milestone",
+            "streak_start_date",
+            "achieved_at",
+            "notified_at",
+        ],
+        references: &[(
+            "target_id",
+            Reference::ByKind {
+                kind_column: "streak_kind",
+                tables: &[
+                    ("activity", "activities"),
+                    ("activity_group", "activity_groups"),
+                ],
+            },
+        )],
+        identity: &["streak_kind", "target_id", "milestone", "streak_start_date"],
+        partial_unique: None,
+        reference_only: false,
+    },
+    TableSpec {
+        name: "assessment_responses",
+        columns: &[
+            "assessment_type_id",
+            "responses",
+            "total_score",
+            "severity_level",
+            "completed_at",
+            "notes",
+            "status",
+        ],
+        references: &[("assessment_type_id", Reference::Table(ASSESSMENT_TYPES))],
+        identity: &["assessment_type_id", "responses", "completed_at", "status"],
+        // One draft per assessment type
+        partial_unique: Some(PartialUnique {
+            column: "status",
+            value: Some("draft"),
+            identity: &["assessment_type_id"],
+        }),
+        reference_only: false,
+    },
+    TableSpec {
+        name: "assessment_schedules",
+        columns: &[
+            "target_kind",
+            "assessment_type_id",
+            "activity_id",
+            "activity_group_id",
+            "habit_text",
+            "frequency",
+            "time_of_day",
+            "day_of_week",
+            "day_of_month",
+            "rrule",
+            "dtstart",
+            "exdates",
+            "enabled",
+            "last_triggered_at",
+            "created_at",
+            "updated_at",
+            "timezone",
+            "missed_policy",
+            "adaptive_response_id",
+        ],
+        references: &[
+            ("assessment_type_id", Reference::Table(ASSESSMENT_TYPES)),
+            ("activity_id", Reference::Table("activities")),
+            ("activity_group_id", Reference::Table("activity_groups")),
+            (
+                "adaptive_response_id",
+                Reference::Table("assessment_responses"),
+            ),
+        ],
+        identity: &[
+            "target_kind",
+            "assessment_type_id",
+            "activity_id",
+            "activity_group_id",
+            "habit_text",
+            "rrule",
+            "dtstart",
+            "created_at",
+        ],
+        partial_unique: None,
+        reference_only: false,
+    },
+    TableSpec {
+        name: "schedule_adaptive_rules",
+        columns: &[
+            "schedule_id",
+            "position",
+            "comparison",
+            "severity_level",
+            "consecutive_count",
+            "frequency",
+            "day_of_week",
+            "day_of_month",
+            "created_at",
+        ],
+        references: &[("schedule_id", Reference::Table("assessment_schedules"))],
+        identity: &["schedule_id", "position"],
+        partial_unique: None,
+        reference_only: false,
+    },
+    TableSpec {
+        name: "schedule_frequency_changes",
+        columns: &[
+            "schedule_id",
+            "rule_id",
+            "from_frequency",
+            "to_frequency",
+            "from_rrule",
+            "to_rrule",
+            "reason",
+            "assessment_response_id",
+            "changed_at",
+        ],
+        references: &[
+            ("schedule_id", Reference::Table("assessment_schedules")),
+            ("rule_id", Reference::Table("schedule_adaptive_rules")),
+            (
+                "assessment_response_id",
+                Reference::Table("assessment_responses"),
+            ),
+        ],
+        identity: &["schedule_id", "to_rrule", "changed_at"],
+        partial_unique: None,
+        reference_only: false,
+    },
+    TableSpec {
+        // Snoozes and dismissals refer to earlier events of the same table
+        name: "reminder_events",
+        columns: &[
+            "schedule_id",
+            "event_type",
+            "occurred_at",
+            "snooze_until",
+            "related_event_id",
+            "assessment_response_id",
+            "created_at",
+        ],
+        references: &[
+            ("schedule_id", Reference::Table("assessment_schedules")),
+            ("related_event_id", Reference::Table("reminder_events")),
+            (
+                "assessment_response_id",
+                Reference::Table("assessment_responses"),
+            ),
+        ],
+        identity: &[
+            "schedule_id",
+            "event_type",
+            "occurred_at",
+            "related_event_id",
+        ],
+        partial_unique: None,
+        reference_only: false,
+    },
+    TableSpec {
+        name: "missed_reminders",
+        columns: &[
+            "schedule_id",
+            "scheduled_for",
+            "local_time",
+            "policy",
+            "reminder_event_id",
+            "detected_at",
+            "acknowledged_at",
+        ],
+        references: &[
+            ("schedule_id", Reference::Table("assessment_schedules")),
+            ("reminder_event_id", Reference::Table("reminder_events")),
+        ],
+        identity: &["schedule_id", "scheduled_for"],
+        partial_unique: None,
+        reference_only: false,
+    },
+    TableSpec {
+        // Digest JSON keeps the exporting database's activity and goal ids
+        name: "weekly_insights",
+        columns: &[
+            "week_start",
+            "week_end",
+            "timezone",
+            "digest",
+            "generated_at",
+            "notified_at",
+        ],
+        references: &[],
+        identity: &["week_start"],
+        partial_unique: None,
+        reference_only: false,
+    },
+];
+
+/// The spec of `name`, if it is exported
+pub fn table_spec(name: &str) -> Option<&'static TableSpec> {
+    TABLES.iter().find(|spec| spec.name == name)
+}
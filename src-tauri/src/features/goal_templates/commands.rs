@@ -0,0 +1,73 @@
+// Goal template commands - Write operations (Tauri commands)
+
+use super::models::*;
+use super::repository::GoalTemplateRepository;
+use crate::{
+    errors::{ErrorType, ToCommandError},
+    AppState, CommandError,
+};
+use tauri::State;
+use tracing::error;
+use validator::Validate;
+
+/// Create the group, activities and goals of a template in one transaction.
+#[tauri::command]
+#[specta::specta]
+pub async fn apply_goal_template(
+    request: ApplyGoalTemplateRequest,
+    state: State<'_, AppState>,
+) -> Result<AppliedGoalTemplate, CommandError> {
+    request.validate().map_err(|e| {
+        CommandError::permanent(format!("Validation failed: {}", e), ErrorType::Validation)
+    })?;
+
+    let repo = GoalTemplateRepository::new(state.db.clone());
+    repo.apply_goal_template(
+        request.template_key.as_deref(),
+        request.template_id,
+        request.group_name.as_deref(),
+    )
+    .map_err(|e| {
+        error!(
+            "apply_goal_template error: {} (template_key: {:?}, template_id: {:?})",
+            e, request.template_key, request.template_id
+        );
+        e.to_command_error()
+    })
+}
+
+/// Save an existing group's activities and goals as a reusable template.
+#[tauri::command]
+#[specta::specta]
+pub async fn save_goal_template(
+    request: SaveGoalTemplateRequest,
+    state: State<'_, AppState>,
+) -> Result<GoalTemplate, CommandError> {
+    request.validate().map_err(|e| {
+        CommandError::permanent(format!("Validation failed: {}", e), ErrorType::Validation)
+    })?;
+
+    let repo = GoalTemplateRepository::new(state.db.clone());
+    repo.save_group_as_template(
+        request.group_id,
+        &request.name,
+        request.description.as_deref(),
+    )
+    .map_err(|e| {
+        error!(
+            "save_goal_template error: {} (group_id: {}, name: '{}')",
+            e, request.group_id, request.name
+        );
+        e.to_command_error()
+    })
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn delete_goal_template(id: i32, state: State<'_, AppState>) -> Result<(), CommandError> {
+    let repo = GoalTemplateRepository::new(state.db.clone());
+    repo.delete_goal_template(id).map_err(|e| {
+        error!("delete_goal_template error: {} (id: {})", e, id);
+        e.to_command_error()
+    })
+}
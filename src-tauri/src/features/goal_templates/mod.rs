@@ -0,0 +1,11 @@
+// Goal template feature module
+// Built-in starter programs and user-saved group/activity/goal setups
+
+pub mod commands;
+pub mod models;
+pub mod queries;
+pub mod repository;
+
+// Re-export commonly used types
+pub use models::{GoalTemplate, GoalTemplateDefinition, GoalTemplateError};
+pub use repository::GoalTemplateRepository;
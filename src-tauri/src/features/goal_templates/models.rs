@@ -0,0 +1,397 @@
+// Goal template models
+// Reusable group + activities + goals setups, built-in or saved by the user
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use validator::Validate;
+
+use crate::errors::{CommandError, ErrorType, ToCommandError};
+use crate::features::activities::models::{Activity, ActivityGoal, ActivityGroup};
+use crate::types::activity::{GoalType, HexColor};
+
+/// Goal template errors
+#[derive(Error, Debug)]
+pub enum GoalTemplateError {
+    #[error("Built-in goal template not found: {0}")]
+    BuiltInTemplateNotFound(String),
+
+    #[error("Goal template not found: {0}")]
+    TemplateNotFound(i32),
+
+    #[error("Activity group not found: {0}")]
+    GroupNotFound(i32),
+
+    #[error("Specify exactly one of a built-in template key or a saved template ID")]
+    InvalidTemplateReference,
+
+    #[error("Invalid goal template: {0}")]
+    InvalidTemplate(String),
+
+    #[error("Goal template name already exists: {0}")]
+    DuplicateTemplateName(String),
+
+    #[error("Activity '{0}' already exists in another group")]
+    ActivityInOtherGroup(String),
+
+    #[error("Database error: {0}")]
+    Database(#[from] rusqlite::Error),
+
+    #[error("Template serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+
+    #[error("Database lock issue. This request will be retried automatically.")]
+    LockPoisoned,
+}
+
+impl ToCommandError for GoalTemplateError {
+    fn to_command_error(&self) -> CommandError {
+        match self {
+            GoalTemplateError::BuiltInTemplateNotFound(key) => {
+                CommandError::permanent(self.to_string(), ErrorType::NotFound)
+                    .with_details(serde_json::json!({ "template_key": key }))
+            }
+            GoalTemplateError::TemplateNotFound(id) => {
+                CommandError::permanent(self.to_string(), ErrorType::NotFound)
+                    .with_details(serde_json::json!({ "template_id": id }))
+            }
+            GoalTemplateError::GroupNotFound(id) => {
+                CommandError::permanent(self.to_string(), ErrorType::NotFound)
+                    .with_details(serde_json::json!({ "group_id": id }))
+            }
+            GoalTemplateError::InvalidTemplateReference | GoalTemplateError::InvalidTemplate(_) => {
+                CommandError::permanent(self.to_string(), ErrorType::Validation)
+            }
+            GoalTemplateError::DuplicateTemplateName(name) => {
+                CommandError::permanent(self.to_string(), ErrorType::Duplicate).with_details(
+                    serde_json::json!({
+                        "field": "name",
+                        "value": name
+                    }),
+                )
+            }
+            GoalTemplateError::ActivityInOtherGroup(name) => {
+                CommandError::permanent(self.to_string(), ErrorType::Duplicate).with_details(
+                    serde_json::json!({
+                        "field": "activity_name",
+                        "value": name
+                    }),
+                )
+            }
+            GoalTemplateError::Database(e) => CommandError::from_rusqlite_error(e),
+            GoalTemplateError::Serialization(_) => {
+                CommandError::permanent(self.to_string(), ErrorType::Serialization)
+            }
+            GoalTemplateError::LockPoisoned => CommandError::retryable(
+                "Database lock issue. This request will be retried automatically.".to_string(),
+                ErrorType::LockPoisoned,
+            ),
+        }
+    }
+}
+
+/// An activity created by a template
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type, PartialEq)]
+pub struct TemplateActivity {
+    pub name: String,
+    pub color: Option<HexColor>,
+    pub icon: Option<String>,
+}
+
+/// A goal created by a template
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type, PartialEq)]
+pub struct TemplateGoal {
+    /// Name of the template activity this goal targets (None = goal on the whole group)
+    pub activity_name: Option<String>,
+    pub goal_type: GoalType,
+    pub target_value: i32,
+    pub period_days: i32,
+}
+
+/// Everything a template creates: one group, its activities and their goals
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type, PartialEq)]
+pub struct GoalTemplateDefinition {
+    pub group_name: String,
+    pub group_description: Option<String>,
+    pub group_color: Option<HexColor>,
+    pub group_icon: Option<String>,
+    pub activities: Vec<TemplateActivity>,
+    pub goals: Vec<TemplateGoal>,
+}
+
+/// A goal template, either from the built-in catalogue or saved by the user
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct GoalTemplate {
+    /// Saved template ID (None for built-in templates)
+    pub id: Option<i32>,
+    /// Stable key of a built-in template (None for saved templates)
+    pub key: Option<String>,
+    pub name: String,
+    pub description: Option<String>,
+    pub built_in: bool,
+    pub definition: GoalTemplateDefinition,
+    pub created_at: Option<String>,
+}
+
+/// Result of applying a template
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct AppliedGoalTemplate {
+    pub group: ActivityGroup,
+    /// True when the group was created, false when an existing group was reused
+    pub group_created: bool,
+    /// Template activities, in template order (existing ones are reused)
+    pub activities: Vec<Activity>,
+    /// Template goals, in template order (identical active goals are reused)
+    pub goals: Vec<ActivityGoal>,
+}
+
+/// Request to apply a goal template
+///
+/// Exactly one of `template_key` (built-in) or `template_id` (saved) must be set.
+#[derive(Debug, Serialize, Deserialize, specta::Type, Validate)]
+pub struct ApplyGoalTemplateRequest {
+    pub template_key: Option<String>,
+    pub template_id: Option<i32>,
+    /// Overrides the template's group name
+    #[validate(length(min = 1, max = 100))]
+    pub group_name: Option<String>,
+}
+
+/// Request to save an existing group's setup as a template
+#[derive(Debug, Serialize, Deserialize, specta::Type, Validate)]
+pub struct SaveGoalTemplateRequest {
+    pub group_id: i32,
+    #[validate(length(min = 1, max = 100))]
+    pub name: String,
+    #[validate(length(max = 500))]
+    pub description: Option<String>,
+}
+
+fn activity(name: &str) -> TemplateActivity {
+    TemplateActivity {
+        name: name.to_string(),
+        color: None,
+        icon: None,
+    }
+}
+
+fn built_in(
+    key: &str,
+    name: &str,
+    description: &str,
+    definition: GoalTemplateDefinition,
+) -> GoalTemplate {
+    GoalTemplate {
+        id: None,
+        key: Some(key.to_string()),
+        name: name.to_string(),
+        description: Some(description.to_string()),
+        built_in: true,
+        definition,
+        created_at: None,
+    }
+}
+
+/// The built-in starter catalogue.
+///
+/// Targets follow common starting doses from behavioural activation and
+/// sleep-hygiene programs; they are meant to be achievable, not ambitious.
+/// Activities inherit the group's color and icon.
+pub fn built_in_templates() -> Vec<GoalTemplate> {
+    vec![
+        built_in(
+            "exercise_3x_week",
+            "Exercise 3x/week",
+            "Moderate physical activity on three days a week.",
+            GoalTemplateDefinition {
+                group_name: "Exercise".to_string(),
+                group_description: Some("Physical activity".to_string()),
+                group_color: Some(HexColor::new("#4CAF50").expect("valid color")),
+                group_icon: Some("🏃".to_string()),
+                activities: vec![activity("Walking"), activity("Workout"), activity("Yoga")],
+                goals: vec![TemplateGoal {
+                    activity_name: None,
+                    goal_type: GoalType::DaysPerPeriod,
+                    target_value: 3,
+                    period_days: 7,
+                }],
+            },
+        ),
+        built_in(
+            "social_contact_2x_week",
+            "Social contact 2x/week",
+            "Reach out to or meet someone on two days a week.",
+            GoalTemplateDefinition {
+                group_name: "Social".to_string(),
+                group_description: Some("Time with other people".to_string()),
+                group_color: Some(HexColor::new("#2196F3").expect("valid color")),
+                group_icon: Some("💬".to_string()),
+                activities: vec![activity("Call a friend"), activity("Meet in person")],
+                goals: vec![TemplateGoal {
+                    activity_name: None,
+                    goal_type: GoalType::DaysPerPeriod,
+                    target_value: 2,
+                    period_days: 7,
+                }],
+            },
+        ),
+        built_in(
+            "sleep_routine_5x_week",
+            "Sleep routine 5 nights/week",
+            "Keep a consistent bedtime on five nights a week.",
+            GoalTemplateDefinition {
+                group_name: "Sleep".to_string(),
+                group_description: Some("Sleep routine".to_string()),
+                group_color: Some(HexColor::new("#673AB7").expect("valid color")),
+                group_icon: Some("🌙".to_string()),
+                activities: vec![activity("Consistent bedtime"), activity("Screen-free hour")],
+                goals: vec![TemplateGoal {
+                    activity_name: Some("Consistent bedtime".to_string()),
+                    goal_type: GoalType::DaysPerPeriod,
+                    target_value: 5,
+                    period_days: 7,
+                }],
+            },
+        ),
+    ]
+}
+
+/// Looks up a built-in template by key
+pub fn find_built_in_template(key: &str) -> Result<GoalTemplate, GoalTemplateError> {
+    built_in_templates()
+        .into_iter()
+        .find(|t| t.key.as_deref() == Some(key))
+        .ok_or_else(|| GoalTemplateError::BuiltInTemplateNotFound(key.to_string()))
+}
+
+/// Validates a template definition with the same limits as the activities feature.
+pub fn validate_definition(definition: &GoalTemplateDefinition) -> Result<(), GoalTemplateError> {
+    let invalid = |msg: String| Err(GoalTemplateError::InvalidTemplate(msg));
+
+    let group_len = definition.group_name.trim().chars().count();
+    if group_len == 0 || group_len > 100 {
+        return invalid("group name must be 1-100 characters".to_string());
+    }
+    if let Some(icon) = &definition.group_icon {
+        if icon.chars().count() > 20 {
+            return invalid("group icon must be 20 characters or less".to_string());
+        }
+    }
+
+    let mut names = std::collections::HashSet::new();
+    for activity in &definition.activities {
+        let name_len = activity.name.trim().chars().count();
+        if name_len == 0 || name_len > 50 {
+            return invalid(format!(
+                "activity name '{}' must be 1-50 characters",
+                activity.name
+            ));
+        }
+        if activity
+            .icon
+            .as_ref()
+            .is_some_and(|i| i.chars().count() > 20)
+        {
+            return invalid(format!(
+                "icon of activity '{}' must be 20 characters or less",
+                activity.name
+            ));
+        }
+        if !names.insert(activity.name.trim()) {
+            return invalid(format!(
+                "activity '{}' listed more than once",
+                activity.name
+            ));
+        }
+    }
+
+    if definition.goals.is_empty() {
+        return invalid("template must contain at least one goal".to_string());
+    }
+    for goal in &definition.goals {
+        if goal.target_value <= 0 || goal.period_days <= 0 {
+            return invalid("goal target and period must be positive".to_string());
+        }
+        if let Some(name) = &goal.activity_name {
+            if !names.contains(name.trim()) {
+                return invalid(format!("goal targets unknown activity '{}'", name));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn definition() -> GoalTemplateDefinition {
+        find_built_in_template("sleep_routine_5x_week")
+            .expect("Built-in template missing")
+            .definition
+    }
+
+    #[test]
+    fn test_built_in_templates_are_valid() {
+        let templates = built_in_templates();
+        assert_eq!(templates.len(), 3);
+        for template in &templates {
+            assert!(template.built_in);
+            validate_definition(&template.definition).expect("Built-in template invalid");
+        }
+    }
+
+    #[test]
+    fn test_built_in_template_keys_are_unique() {
+        let templates = built_in_templates();
+        let keys: std::collections::HashSet<_> =
+            templates.iter().filter_map(|t| t.key.clone()).collect();
+        assert_eq!(keys.len(), templates.len());
+    }
+
+    #[test]
+    fn test_find_built_in_template_unknown_key() {
+        let result = find_built_in_template("meditate_daily");
+        assert!(matches!(
+            result,
+            Err(GoalTemplateError::BuiltInTemplateNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_validate_definition_rejects_unknown_goal_activity() {
+        let mut def = definition();
+        def.goals[0].activity_name = Some("Nap".to_string());
+        assert!(matches!(
+            validate_definition(&def),
+            Err(GoalTemplateError::InvalidTemplate(_))
+        ));
+    }
+
+    #[test]
+    fn test_validate_definition_rejects_duplicate_activities() {
+        let mut def = definition();
+        def.activities.push(activity("Consistent bedtime"));
+        assert!(validate_definition(&def).is_err());
+    }
+
+    #[test]
+    fn test_validate_definition_requires_goals() {
+        let mut def = definition();
+        def.goals.clear();
+        assert!(validate_definition(&def).is_err());
+
+        let mut def = definition();
+        def.goals[0].target_value = 0;
+        assert!(validate_definition(&def).is_err());
+    }
+
+    #[test]
+    fn test_definition_json_round_trip() {
+        let def = definition();
+        let json = serde_json::to_string(&def).expect("Failed to serialize");
+        let parsed: GoalTemplateDefinition =
+            serde_json::from_str(&json).expect("Failed to deserialize");
+        assert_eq!(parsed, def);
+    }
+}
@@ -0,0 +1,21 @@
+// Goal template queries - Read operations (Tauri commands)
+
+use super::models::*;
+use super::repository::GoalTemplateRepository;
+use crate::{errors::ToCommandError, AppState, CommandError};
+use tauri::State;
+use tracing::error;
+
+/// List the built-in template catalogue followed by the user's saved templates.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_goal_templates(
+    state: State<'_, AppState>,
+) -> Result<Vec<GoalTemplate>, CommandError> {
+    let repo = GoalTemplateRepository::new(state.db.clone());
+
+    repo.get_goal_templates().map_err(|e| {
+        error!("get_goal_templates error: {}", e);
+        e.to_command_error()
+    })
+}
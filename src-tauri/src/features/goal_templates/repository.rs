@@ -0,0 +1,716 @@
+// Goal template repository - Saved templates and template application
+//
+// Applying a template creates (or reuses) the group, its activities and goals
+// inside a single IMMEDIATE transaction, so a conflict half-way through leaves
+// no partial setup behind.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use rusqlite::OptionalExtension;
+use tracing::info;
+
+use super::models::*;
+use crate::db::Database;
+use crate::features::activities::models::{Activity, ActivityGoal, ActivityGroup};
+use crate::features::activities::repository::map_activity_group_row;
+use crate::types::activity::{GoalType, HexColor};
+
+pub struct GoalTemplateRepository {
+    db: Arc<Database>,
+}
+
+impl GoalTemplateRepository {
+    pub fn new(db: Arc<Database>) -> Self {
+        Self { db }
+    }
+
+    /// Lists the built-in catalogue followed by saved templates (by name).
+    pub fn get_goal_templates(&self) -> Result<Vec<GoalTemplate>, GoalTemplateError> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock();
+
+        let mut stmt = conn.prepare(
+            "SELECT id, name, description, definition, CAST(created_at AS VARCHAR)
+             FROM goal_templates
+             WHERE deleted_at IS NULL
+             ORDER BY name ASC",
+        )?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, i32>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, Option<String>>(2)?,
+                    row.get::<_, String>(3)?,
+                    row.get::<_, String>(4)?,
+                ))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut templates = built_in_templates();
+        for (id, name, description, definition, created_at) in rows {
+            templates.push(GoalTemplate {
+                id: Some(id),
+                key: None,
+                name,
+                description,
+                built_in: false,
+                definition: serde_json::from_str(&definition)?,
+                created_at: Some(created_at),
+            });
+        }
+
+        Ok(templates)
+    }
+
+    /// Saves an existing group's setup (appearance, activities and active
+    /// goals) as a reusable template.
+    ///
+    /// # Errors
+    /// * `GroupNotFound` - If the group doesn't exist or is deleted
+    /// * `InvalidTemplate` - If the group has no goals to save
+    /// * `DuplicateTemplateName` - If a saved template already has this name
+    pub fn save_group_as_template(
+        &self,
+        group_id: i32,
+        name: &str,
+        description: Option<&str>,
+    ) -> Result<GoalTemplate, GoalTemplateError> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock();
+
+        let group = conn
+            .query_row(
+                "SELECT id, name, description, color, icon, sort_order, CAST(archived_at AS VARCHAR),
+                        CAST(created_at AS VARCHAR), CAST(deleted_at AS VARCHAR)
+                 FROM activity_groups
+                 WHERE id = ? AND deleted_at IS NULL",
+                [group_id],
+                map_activity_group_row,
+            )
+            .optional()?
+            .ok_or(GoalTemplateError::GroupNotFound(group_id))?;
+
+        let activities: Vec<(i32, TemplateActivity)> = {
+            let mut stmt = conn.prepare(
+                "SELECT id, name, color, icon
+                 FROM activities
+                 WHERE group_id = ? AND deleted_at IS NULL
+                 ORDER BY sort_order IS NULL, sort_order, name ASC",
+            )?;
+            let rows = stmt
+                .query_map([group_id], |row| {
+                    Ok((
+                        row.get(0)?,
+                        TemplateActivity {
+                            name: row.get(1)?,
+                            color: row.get::<_, Option<HexColor>>(2)?,
+                            icon: row.get(3)?,
+                        },
+                    ))
+                })?
+                .collect::<Result<Vec<_>, _>>()?;
+            rows
+        };
+        let activity_names: HashMap<i32, String> = activities
+            .iter()
+            .map(|(id, a)| (*id, a.name.clone()))
+            .collect();
+
+        let goals: Vec<TemplateGoal> = {
+            let mut stmt = conn.prepare(
+                "SELECT ag.activity_id, ag.goal_type, ag.target_value, ag.period_days
+                 FROM activity_goals ag
+                 LEFT JOIN activities a ON ag.activity_id = a.id
+                 WHERE ag.deleted_at IS NULL
+                   AND (ag.group_id = ?1 OR (a.group_id = ?1 AND a.deleted_at IS NULL))
+                 ORDER BY ag.created_at ASC, ag.id ASC",
+            )?;
+            let rows = stmt
+                .query_map([group_id], |row| {
+                    Ok((
+                        row.get::<_, Option<i32>>(0)?,
+                        row.get::<_, GoalType>(1)?,
+                        row.get::<_, i32>(2)?,
+                        row.get::<_, i32>(3)?,
+                    ))
+                })?
+                .collect::<Result<Vec<_>, _>>()?;
+            rows.into_iter()
+                .map(
+                    |(activity_id, goal_type, target_value, period_days)| TemplateGoal {
+                        activity_name: activity_id.and_then(|id| activity_names.get(&id).cloned()),
+                        goal_type,
+                        target_value,
+                        period_days,
+                    },
+                )
+                .collect()
+        };
+
+        let definition = GoalTemplateDefinition {
+            group_name: group.name,
+            group_description: group.description,
+            group_color: group.color,
+            group_icon: group.icon,
+            activities: activities.into_iter().map(|(_, a)| a).collect(),
+            goals,
+        };
+        validate_definition(&definition)?;
+
+        let result = conn.query_row(
+            "INSERT INTO goal_templates (name, description, definition) VALUES (?, ?, ?)
+             RETURNING id, CAST(created_at AS VARCHAR)",
+            rusqlite::params![name, description, serde_json::to_string(&definition)?],
+            |row| Ok((row.get::<_, i32>(0)?, row.get::<_, String>(1)?)),
+        );
+        let (id, created_at) = match result {
+            Ok(data) => data,
+            Err(rusqlite::Error::SqliteFailure(err, _))
+                if err.code == rusqlite::ErrorCode::ConstraintViolation =>
+            {
+                return Err(GoalTemplateError::DuplicateTemplateName(name.to_string()));
+            }
+            Err(e) => return Err(GoalTemplateError::Database(e)),
+        };
+
+        info!(
+            template_id = id,
+            group_id = group_id,
+            goal_count = definition.goals.len(),
+            "Saved goal template"
+        );
+
+        Ok(GoalTemplate {
+            id: Some(id),
+            key: None,
+            name: name.to_string(),
+            description: description.map(|d| d.to_string()),
+            built_in: false,
+            definition,
+            created_at: Some(created_at),
+        })
+    }
+
+    /// Soft deletes a saved template. Built-in templates cannot be deleted.
+    pub fn delete_goal_template(&self, id: i32) -> Result<(), GoalTemplateError> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock();
+
+        let updated = conn.execute(
+            "UPDATE goal_templates SET deleted_at = datetime('now') WHERE id = ? AND deleted_at IS NULL",
+            [id],
+        )?;
+        if updated == 0 {
+            return Err(GoalTemplateError::TemplateNotFound(id));
+        }
+
+        info!(template_id = id, "Soft deleted goal template");
+        Ok(())
+    }
+
+    /// Applies a built-in (`template_key`) or saved (`template_id`) template.
+    ///
+    /// The group is looked up by name and reused if it exists (an archived one
+    /// is restored); activities are reused if they already belong to that group. Goals identical to an
+    /// active goal on the same target are not duplicated, so applying a
+    /// template twice is harmless.
+    ///
+    /// # Errors
+    /// * `InvalidTemplateReference` - Unless exactly one of key/ID is given
+    /// * `BuiltInTemplateNotFound` / `TemplateNotFound` - Unknown template
+    /// * `ActivityInOtherGroup` - A template activity name is taken by another
+    ///   group (activity names are unique); nothing is created
+    pub fn apply_goal_template(
+        &self,
+        template_key: Option<&str>,
+        template_id: Option<i32>,
+        group_name: Option<&str>,
+    ) -> Result<AppliedGoalTemplate, GoalTemplateError> {
+        let mut definition = match (template_key, template_id) {
+            (Some(key), None) => find_built_in_template(key)?.definition,
+            (None, Some(id)) => self.get_saved_definition(id)?,
+            _ => return Err(GoalTemplateError::InvalidTemplateReference),
+        };
+        if let Some(name) = group_name {
+            definition.group_name = name.to_string();
+        }
+        validate_definition(&definition)?;
+
+        let conn = self.db.get_connection();
+        let mut conn = conn.lock();
+
+        let tx = conn.transaction_with_behavior(rusqlite::TransactionBehavior::Immediate)?;
+
+        let group_name = definition.group_name.trim();
+        let existing_group_id: Option<i32> = tx
+            .query_row(
+                "SELECT id FROM activity_groups WHERE name = ? AND deleted_at IS NULL",
+                [group_name],
+                |row| row.get(0),
+            )
+            .optional()?;
+        let group_created = existing_group_id.is_none();
+        let group_id = match existing_group_id {
+            Some(id) => {
+                // The name stays taken while archived, so bring the group back
+                tx.execute(
+                    "UPDATE activity_groups SET archived_at = NULL WHERE id = ?",
+                    [id],
+                )?;
+                id
+            }
+            None => tx.query_row(
+                "INSERT INTO activity_groups (name, description, color, icon)
+                 VALUES (?, ?, ?, ?) RETURNING id",
+                rusqlite::params![
+                    group_name,
+                    definition.group_description,
+                    definition.group_color,
+                    definition.group_icon
+                ],
+                |row| row.get(0),
+            )?,
+        };
+
+        let mut activities = Vec::with_capacity(definition.activities.len());
+        for template_activity in &definition.activities {
+            let name = template_activity.name.trim();
+            let existing: Option<i32> = tx
+                .query_row(
+                    "SELECT id FROM activities
+                     WHERE group_id = ? AND name = ? AND deleted_at IS NULL",
+                    rusqlite::params![group_id, name],
+                    |row| row.get(0),
+                )
+                .optional()?;
+            let taken_elsewhere = existing.is_none()
+                && tx.query_row(
+                    "SELECT EXISTS(SELECT 1 FROM activities WHERE name = ? AND deleted_at IS NULL)",
+                    [name],
+                    |row| row.get::<_, bool>(0),
+                )?;
+            if taken_elsewhere {
+                return Err(GoalTemplateError::ActivityInOtherGroup(name.to_string()));
+            }
+
+            let activity_id = match existing {
+                Some(id) => id,
+                // Missing color/icon fall back to the group's defaults
+                None => tx.query_row(
+                    "INSERT INTO activities (group_id, name, color, icon)
+                     VALUES (?1, ?2,
+                             COALESCE(?3, (SELECT color FROM activity_groups WHERE id = ?1)),
+                             COALESCE(?4, (SELECT icon FROM activity_groups WHERE id = ?1)))
+                     RETURNING id",
+                    rusqlite::params![
+                        group_id,
+                        name,
+                        template_activity.color,
+                        template_activity.icon
+                    ],
+                    |row| row.get(0),
+                )?,
+            };
+
+            activities.push(tx.query_row(
+                "SELECT id, group_id, name, color, icon, CAST(created_at AS VARCHAR), CAST(deleted_at AS VARCHAR)
+                 FROM activities WHERE id = ?",
+                [activity_id],
+                |row| {
+                    Ok(Activity {
+                        id: row.get(0)?,
+                        group_id: row.get(1)?,
+                        name: row.get(2)?,
+                        color: row.get::<_, Option<HexColor>>(3)?,
+                        icon: row.get(4)?,
+                        created_at: row.get(5)?,
+                        deleted_at: row.get(6)?,
+                    })
+                },
+            )?);
+        }
+
+        let mut goals = Vec::with_capacity(definition.goals.len());
+        for template_goal in &definition.goals {
+            let (activity_id, target_group_id) = match &template_goal.activity_name {
+                Some(name) => {
+                    let activity = activities
+                        .iter()
+                        .find(|a| a.name == name.trim())
+                        .ok_or_else(|| {
+                            GoalTemplateError::InvalidTemplate(format!(
+                                "goal targets unknown activity '{}'",
+                                name
+                            ))
+                        })?;
+                    (Some(activity.id), None)
+                }
+                None => (None, Some(group_id)),
+            };
+
+            let existing_goal_id: Option<i32> = tx
+                .query_row(
+                    "SELECT id FROM activity_goals
+                     WHERE activity_id IS ?1 AND group_id IS ?2
+                       AND goal_type = ?3 AND target_value = ?4 AND period_days = ?5
+                       AND deleted_at IS NULL
+                     LIMIT 1",
+                    rusqlite::params![
+                        activity_id,
+                        target_group_id,
+                        template_goal.goal_type.as_str(),
+                        template_goal.target_value,
+                        template_goal.period_days
+                    ],
+                    |row| row.get(0),
+                )
+                .optional()?;
+
+            let goal_id: i32 = match existing_goal_id {
+                Some(id) => id,
+                None => tx.query_row(
+                    "INSERT INTO activity_goals (activity_id, group_id, goal_type, target_value, period_days)
+                     VALUES (?, ?, ?, ?, ?)
+                     RETURNING id",
+                    rusqlite::params![
+                        activity_id,
+                        target_group_id,
+                        template_goal.goal_type.as_str(),
+                        template_goal.target_value,
+                        template_goal.period_days
+                    ],
+                    |row| row.get(0),
+                )?,
+            };
+
+            goals.push(tx.query_row(
+                "SELECT id, activity_id, group_id, goal_type, target_value, period_days,
+                        CAST(created_at AS VARCHAR), CAST(deleted_at AS VARCHAR)
+                 FROM activity_goals WHERE id = ?",
+                [goal_id],
+                |row| {
+                    Ok(ActivityGoal {
+                        id: row.get(0)?,
+                        activity_id: row.get(1)?,
+                        group_id: row.get(2)?,
+                        goal_type: row.get(3)?,
+                        target_value: row.get(4)?,
+                        period_days: row.get(5)?,
+                        created_at: row.get(6)?,
+                        deleted_at: row.get(7)?,
+                    })
+                },
+            )?);
+        }
+
+        let group: ActivityGroup = tx.query_row(
+            "SELECT id, name, description, color, icon, sort_order, CAST(archived_at AS VARCHAR),
+                    CAST(created_at AS VARCHAR), CAST(deleted_at AS VARCHAR)
+             FROM activity_groups WHERE id = ?",
+            [group_id],
+            map_activity_group_row,
+        )?;
+
+        tx.commit()?;
+
+        info!(
+            group_id = group_id,
+            group_created = group_created,
+            activity_count = activities.len(),
+            goal_count = goals.len(),
+            "Applied goal template"
+        );
+
+        Ok(AppliedGoalTemplate {
+            group,
+            group_created,
+            activities,
+            goals,
+        })
+    }
+
+    fn get_saved_definition(&self, id: i32) -> Result<GoalTemplateDefinition, GoalTemplateError> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock();
+
+        let definition: String = conn
+            .query_row(
+                "SELECT definition FROM goal_templates WHERE id = ? AND deleted_at IS NULL",
+                [id],
+                |row| row.get(0),
+            )
+            .optional()?
+            .ok_or(GoalTemplateError::TemplateNotFound(id))?;
+
+        Ok(serde_json::from_str(&definition)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::activities::repository::ActivityRepository;
+    use tempfile::TempDir;
+
+    fn setup_test_repo() -> (GoalTemplateRepository, ActivityRepository, TempDir) {
+        let temp_dir = TempDir::new().expect("Failed to create temp directory");
+        let db = Arc::new(
+            Database::new(temp_dir.path().to_path_buf()).expect("Failed to create database"),
+        );
+        (
+            GoalTemplateRepository::new(db.clone()),
+            ActivityRepository::new(db),
+            temp_dir,
+        )
+    }
+
+    #[test]
+    fn test_get_goal_templates_lists_built_ins() {
+        let (repo, _activity_repo, _temp_dir) = setup_test_repo();
+
+        let templates = repo.get_goal_templates().expect("Failed to get templates");
+
+        assert_eq!(templates.len(), built_in_templates().len());
+        assert!(templates.iter().all(|t| t.built_in));
+    }
+
+    #[test]
+    fn test_apply_built_in_template_creates_everything() {
+        let (repo, activity_repo, _temp_dir) = setup_test_repo();
+
+        let applied = repo
+            .apply_goal_template(Some("exercise_3x_week"), None, None)
+            .expect("Failed to apply template");
+
+        assert!(applied.group_created);
+        assert_eq!(applied.group.name, "Exercise");
+        assert_eq!(applied.activities.len(), 3);
+        // Activities inherit the group's appearance
+        assert!(applied
+            .activities
+            .iter()
+            .all(|a| a.icon == applied.group.icon && a.color == applied.group.color));
+        assert_eq!(applied.goals.len(), 1);
+        assert_eq!(applied.goals[0].group_id, Some(applied.group.id));
+        assert_eq!(applied.goals[0].target_value, 3);
+
+        let goals = activity_repo
+            .get_activity_goals(None, Some(applied.group.id))
+            .expect("Failed to get goals");
+        assert_eq!(goals.len(), 1);
+    }
+
+    #[test]
+    fn test_apply_template_with_activity_goal() {
+        let (repo, _activity_repo, _temp_dir) = setup_test_repo();
+
+        let applied = repo
+            .apply_goal_template(Some("sleep_routine_5x_week"), None, Some("Evening"))
+            .expect("Failed to apply template");
+
+        assert_eq!(applied.group.name, "Evening");
+        let bedtime = applied
+            .activities
+            .iter()
+            .find(|a| a.name == "Consistent bedtime")
+            .expect("Activity missing");
+        assert_eq!(applied.goals[0].activity_id, Some(bedtime.id));
+        assert_eq!(applied.goals[0].group_id, None);
+    }
+
+    #[test]
+    fn test_apply_template_twice_reuses_existing_setup() {
+        let (repo, activity_repo, _temp_dir) = setup_test_repo();
+
+        let first = repo
+            .apply_goal_template(Some("social_contact_2x_week"), None, None)
+            .expect("Failed to apply template");
+        let second = repo
+            .apply_goal_template(Some("social_contact_2x_week"), None, None)
+            .expect("Failed to apply template again");
+
+        assert!(!second.group_created);
+        assert_eq!(second.group.id, first.group.id);
+        assert_eq!(second.goals[0].id, first.goals[0].id);
+        let goals = activity_repo
+            .get_activity_goals(None, Some(first.group.id))
+            .expect("Failed to get goals");
+        assert_eq!(goals.len(), 1);
+    }
+
+    #[test]
+    fn test_apply_template_conflict_rolls_back() {
+        let (repo, activity_repo, _temp_dir) = setup_test_repo();
+
+        let other = activity_repo
+            .create_activity_group("Outdoors", None)
+            .expect("Failed to create group");
+        activity_repo
+            .create_activity(other.id, "Yoga", None, None)
+            .expect("Failed to create activity");
+
+        let result = repo.apply_goal_template(Some("exercise_3x_week"), None, None);
+        assert!(matches!(
+            result,
+            Err(GoalTemplateError::ActivityInOtherGroup(ref name)) if name == "Yoga"
+        ));
+
+        // No partial setup left behind
+        let groups = activity_repo
+            .get_activity_groups()
+            .expect("Failed to get groups");
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].name, "Outdoors");
+    }
+
+    #[test]
+    fn test_apply_template_restores_archived_group() {
+        let (repo, activity_repo, _temp_dir) = setup_test_repo();
+
+        let first = repo
+            .apply_goal_template(Some("exercise_3x_week"), None, None)
+            .expect("Failed to apply template");
+        activity_repo
+            .archive_activity_group(first.group.id)
+            .expect("Failed to archive group");
+
+        let second = repo
+            .apply_goal_template(Some("exercise_3x_week"), None, None)
+            .expect("Failed to apply template again");
+
+        assert!(!second.group_created);
+        assert_eq!(second.group.id, first.group.id);
+        assert!(second.group.archived_at.is_none());
+        let activity_ids: Vec<i32> = second.activities.iter().map(|a| a.id).collect();
+        let first_ids: Vec<i32> = first.activities.iter().map(|a| a.id).collect();
+        assert_eq!(activity_ids, first_ids);
+    }
+
+    #[test]
+    fn test_apply_template_reference_validation() {
+        let (repo, _activity_repo, _temp_dir) = setup_test_repo();
+
+        let result = repo.apply_goal_template(Some("exercise_3x_week"), Some(1), None);
+        assert!(matches!(
+            result,
+            Err(GoalTemplateError::InvalidTemplateReference)
+        ));
+
+        let result = repo.apply_goal_template(None, None, None);
+        assert!(matches!(
+            result,
+            Err(GoalTemplateError::InvalidTemplateReference)
+        ));
+
+        let result = repo.apply_goal_template(None, Some(999), None);
+        assert!(matches!(
+            result,
+            Err(GoalTemplateError::TemplateNotFound(999))
+        ));
+    }
+
+    #[test]
+    fn test_save_group_as_template_and_reapply() {
+        let (repo, activity_repo, _temp_dir) = setup_test_repo();
+
+        let group = activity_repo
+            .create_activity_group("Mindfulness", Some("Calm"))
+            .expect("Failed to create group");
+        let meditation = activity_repo
+            .create_activity(group.id, "Meditation", Some("#FF9800"), None)
+            .expect("Failed to create activity");
+        activity_repo
+            .set_activity_goal(Some(meditation.id), None, GoalType::DaysPerPeriod, 4, 7)
+            .expect("Failed to set goal");
+        activity_repo
+            .set_activity_goal(None, Some(group.id), GoalType::PercentImprovement, 20, 30)
+            .expect("Failed to set goal");
+
+        let template = repo
+            .save_group_as_template(group.id, "My calm week", None)
+            .expect("Failed to save template");
+
+        assert!(!template.built_in);
+        assert_eq!(template.definition.group_name, "Mindfulness");
+        assert_eq!(template.definition.activities.len(), 1);
+        assert_eq!(template.definition.goals.len(), 2);
+        assert_eq!(
+            template.definition.goals[0].activity_name.as_deref(),
+            Some("Meditation")
+        );
+        assert_eq!(template.definition.goals[1].activity_name, None);
+
+        let templates = repo.get_goal_templates().expect("Failed to get templates");
+        assert_eq!(templates.last().map(|t| t.id), Some(template.id));
+
+        // Re-applying onto the original group changes nothing
+        let applied = repo
+            .apply_goal_template(None, template.id, None)
+            .expect("Failed to apply saved template");
+        assert!(!applied.group_created);
+        assert_eq!(applied.goals.len(), 2);
+        let goals = activity_repo
+            .get_activity_goals(None, None)
+            .expect("Failed to get goals");
+        assert_eq!(goals.len(), 2);
+    }
+
+    #[test]
+    fn test_save_group_as_template_errors() {
+        let (repo, activity_repo, _temp_dir) = setup_test_repo();
+
+        let result = repo.save_group_as_template(999, "Nothing", None);
+        assert!(matches!(result, Err(GoalTemplateError::GroupNotFound(999))));
+
+        // A group without goals is not a useful template
+        let group = activity_repo
+            .create_activity_group("Hobbies", None)
+            .expect("Failed to create group");
+        let result = repo.save_group_as_template(group.id, "Hobbies", None);
+        assert!(matches!(result, Err(GoalTemplateError::InvalidTemplate(_))));
+
+        activity_repo
+            .set_activity_goal(None, Some(group.id), GoalType::DaysPerPeriod, 1, 7)
+            .expect("Failed to set goal");
+        repo.save_group_as_template(group.id, "Hobbies", None)
+            .expect("Failed to save template");
+        let result = repo.save_group_as_template(group.id, "Hobbies", None);
+        assert!(matches!(
+            result,
+            Err(GoalTemplateError::DuplicateTemplateName(_))
+        ));
+    }
+
+    #[test]
+    fn test_delete_goal_template() {
+        let (repo, activity_repo, _temp_dir) = setup_test_repo();
+
+        let applied = repo
+            .apply_goal_template(Some("exercise_3x_week"), None, None)
+            .expect("Failed to apply template");
+        let template = repo
+            .save_group_as_template(applied.group.id, "Copy", None)
+            .expect("Failed to save template");
+        let id = template.id.expect("Saved template has an ID");
+
+        repo.delete_goal_template(id)
+            .expect("Failed to delete template");
+
+        let templates = repo.get_goal_templates().expect("Failed to get templates");
+        assert!(templates.iter().all(|t| t.id != Some(id)));
+        assert!(matches!(
+            repo.delete_goal_template(id),
+            Err(GoalTemplateError::TemplateNotFound(_))
+        ));
+        // The group created from the template is unaffected
+        assert_eq!(
+            activity_repo
+                .get_activity_groups()
+                .expect("Failed to get groups")
+                .len(),
+            1
+        );
+    }
+}
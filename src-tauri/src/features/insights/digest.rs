@@ -0,0 +1,508 @@
+// Weekly digest building blocks
+//
+// Week boundaries, per-day mood, the activities and severity changes worth
+// mentioning, and the templated sentences. Everything here works on data
+// already fetched, so it is independent of the database.
+
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Utc};
+use chrono_tz::Tz;
+
+use super::models::*;
+use crate::features::assessments::models::AssessmentResponse;
+use crate::features::mood::models::ActivityCorrelation;
+use crate::features::scheduling::models::resolve_local_time;
+use crate::features::visualization::trend::parse_chart_timestamp;
+use crate::types::assessment::SeverityLevel;
+
+const DATE_FORMAT: &str = "%Y-%m-%d";
+const TIMESTAMP_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+/// Change in average mood small enough to call "about the same"
+const SAME_MOOD_CHANGE: f64 = 0.05;
+
+/// A local Monday-to-Sunday week and its UTC bounds
+#[derive(Debug, Clone, PartialEq)]
+pub struct DigestWeek {
+    pub start: NaiveDate, // Monday
+    pub tz: Tz,
+}
+
+impl DigestWeek {
+    /// The last week that ended before `now`
+    pub fn completed_before(now: DateTime<Utc>, tz: Tz) -> Self {
+        let today = now.with_timezone(&tz).date_naive();
+        let this_monday = today - Duration::days(today.weekday().num_days_from_monday() as i64);
+        DigestWeek {
+            start: this_monday - Duration::days(7),
+            tz,
+        }
+    }
+
+    /// Sunday
+    pub fn end(&self) -> NaiveDate {
+        self.start + Duration::days(6)
+    }
+
+    /// The week before this one
+    pub fn previous(&self) -> Self {
+        DigestWeek {
+            start: self.start - Duration::days(7),
+            tz: self.tz,
+        }
+    }
+
+    /// Stored-timestamp bounds, both inclusive: from Monday 00:00 local to
+    /// one second before the next Monday
+    pub fn utc_bounds(&self) -> (String, String) {
+        let at_midnight = |day: NaiveDate| {
+            resolve_local_time(&self.tz, day.and_hms_opt(0, 0, 0).unwrap_or_default())
+        };
+        let from = at_midnight(self.start);
+        let to = at_midnight(self.start + Duration::days(7)) - Duration::seconds(1);
+        (
+            from.format(TIMESTAMP_FORMAT).to_string(),
+            to.format(TIMESTAMP_FORMAT).to_string(),
+        )
+    }
+
+    pub fn start_label(&self) -> String {
+        self.start.format(DATE_FORMAT).to_string()
+    }
+
+    pub fn end_label(&self) -> String {
+        self.end().format(DATE_FORMAT).to_string()
+    }
+}
+
+/// Average mood per local day of `week`, from (created_at, rating) rows
+pub fn day_moods(checkins: &[(String, i32)], week: &DigestWeek) -> Vec<DayMood> {
+    let mut days: BTreeMap<NaiveDate, (i64, i32)> = BTreeMap::new();
+    for (created_at, rating) in checkins {
+        let Some(at) = parse_chart_timestamp(created_at) else {
+            continue;
+        };
+        let day = at.and_utc().with_timezone(&week.tz).date_naive();
+        if day < week.start || day > week.end() {
+            continue;
+        }
+        let entry = days.entry(day).or_insert((0, 0));
+        entry.0 += *rating as i64;
+        entry.1 += 1;
+    }
+    days.into_iter()
+        .map(|(day, (sum, count))| DayMood {
+            date: day.format(DATE_FORMAT).to_string(),
+            average_mood: sum as f64 / count as f64,
+            checkin_count: count,
+        })
+        .collect()
+}
+
+/// Best and worst day; the earlier day wins ties. No worst day unless at
+/// least two days had check-ins.
+pub fn best_and_worst_days(days: &[DayMood]) -> (Option<DayMood>, Option<DayMood>) {
+    let mut best: Option<&DayMood> = None;
+    let mut worst: Option<&DayMood> = None;
+    for day in days {
+        if best.is_none_or(|b| day.average_mood > b.average_mood) {
+            best = Some(day);
+        }
+        if worst.is_none_or(|w| day.average_mood < w.average_mood) {
+            worst = Some(day);
+        }
+    }
+    let worst = worst.filter(|_| days.len() > 1);
+    (best.cloned(), worst.cloned())
+}
+
+/// Activities whose check-ins averaged above the week's mood, best first.
+/// Deleted activities are left out.
+pub fn top_activities(
+    correlations: &[ActivityCorrelation],
+    week_average: f64,
+) -> Vec<ActivityInsight> {
+    let mut insights: Vec<ActivityInsight> = correlations
+        .iter()
+        .filter(|c| c.activity.deleted_at.is_none() && c.average_mood > week_average)
+        .map(|c| ActivityInsight {
+            activity_id: c.activity.id,
+            activity_name: c.activity.name.clone(),
+            average_mood: c.average_mood,
+            checkin_count: c.checkin_count,
+            difference: c.average_mood - week_average,
+        })
+        .collect();
+    insights.sort_by(|a, b| {
+        b.difference
+            .total_cmp(&a.difference)
+            .then(b.checkin_count.cmp(&a.checkin_count))
+    });
+    insights.truncate(MAX_TOP_ACTIVITIES);
+    insights
+}
+
+/// Types whose latest severity in the week differs from their latest before
+/// it. `history` is completed assessments up to the end of the week, in any
+/// order; `week_from` is the week's first stored timestamp.
+pub fn severity_changes(history: &[AssessmentResponse], week_from: &str) -> Vec<SeverityChange> {
+    // Per type: latest before the week and latest within it
+    let mut latest: BTreeMap<String, (Option<&AssessmentResponse>, Option<&AssessmentResponse>)> =
+        BTreeMap::new();
+    for response in history {
+        let entry = latest
+            .entry(response.assessment_type.code.to_string())
+            .or_default();
+        let slot = if response.completed_at.as_str() < week_from {
+            &mut entry.0
+        } else {
+            &mut entry.1
+        };
+        if slot.is_none_or(|r| {
+            (r.completed_at.as_str(), r.id) < (response.completed_at.as_str(), response.id)
+        }) {
+            *slot = Some(response);
+        }
+    }
+
+    latest
+        .into_iter()
+        .filter_map(|(code, pair)| {
+            let (Some(previous), Some(current)) = pair else {
+                return None;
+            };
+            if previous.severity_level == current.severity_level {
+                return None;
+            }
+            Some(SeverityChange {
+                assessment_type_code: code,
+                assessment_type_name: current.assessment_type.name.clone(),
+                previous_severity: previous.severity_level,
+                current_severity: current.severity_level,
+                previous_score: previous.total_score,
+                current_score: current.total_score,
+                improved: current
+                    .severity_level
+                    .compare(&previous.severity_level)
+                    .map(|order| order == Ordering::Less),
+            })
+        })
+        .collect()
+}
+
+/// One sentence per insight, in the order the digest lists them
+pub fn sentences(digest: &WeeklyDigest) -> Vec<String> {
+    let mut sentences = vec![mood_sentence(&digest.mood)];
+
+    match (&digest.best_day, &digest.worst_day) {
+        (Some(best), Some(worst)) => sentences.push(format!(
+            "Your best day was {} ({:.1}) and your hardest was {} ({:.1}).",
+            weekday(&best.date),
+            best.average_mood,
+            weekday(&worst.date),
+            worst.average_mood
+        )),
+        (Some(best), None) => sentences.push(format!(
+            "{} was your only day with check-ins ({:.1}).",
+            weekday(&best.date),
+            best.average_mood
+        )),
+        _ => {}
+    }
+
+    if !digest.top_activities.is_empty() {
+        let activities: Vec<String> = digest
+            .top_activities
+            .iter()
+            .map(|a| format!("{} (+{:.1})", a.activity_name, a.difference))
+            .collect();
+        sentences.push(format!(
+            "Your mood was higher with {}.",
+            join_list(&activities)
+        ));
+    }
+
+    if !digest.goals.is_empty() {
+        let achieved: Vec<String> = digest
+            .goals
+            .iter()
+            .filter(|g| g.is_achieved)
+            .map(|g| g.target_name.clone())
+            .collect();
+        sentences.push(if let [only] = digest.goals.as_slice() {
+            if only.is_achieved {
+                format!("You reached your {} goal.", only.target_name)
+            } else {
+                format!("You did not reach your {} goal.", only.target_name)
+            }
+        } else if achieved.is_empty() {
+            format!("None of your {} goals were reached.", digest.goals.len())
+        } else {
+            format!(
+                "You reached {} of {}: {}.",
+                achieved.len(),
+                count_of(digest.goals.len(), "goal"),
+                join_list(&achieved)
+            )
+        });
+    }
+
+    if !digest.missed_reminders.is_empty() {
+        let total: i32 = digest.missed_reminders.iter().map(|m| m.count).sum();
+        let targets: Vec<String> = digest
+            .missed_reminders
+            .iter()
+            .map(|m| format!("{} ({})", m.target_name, m.count))
+            .collect();
+        sentences.push(format!(
+            "You missed {}: {}.",
+            count_of(total as usize, "reminder"),
+            join_list(&targets)
+        ));
+    }
+
+    for change in &digest.severity_changes {
+        let verb = match change.improved {
+            Some(true) => "improved",
+            Some(false) => "worsened",
+            None => "changed",
+        };
+        sentences.push(format!(
+            "{} {} from {} to {} (score {} to {}).",
+            change.assessment_type_name,
+            verb,
+            severity_label(change.previous_severity),
+            severity_label(change.current_severity),
+            change.previous_score,
+            change.current_score
+        ));
+    }
+
+    sentences
+}
+
+fn mood_sentence(mood: &MoodInsight) -> String {
+    let Some(average) = mood.average_mood else {
+        return "No mood check-ins were recorded this week.".to_string();
+    };
+    let base = format!(
+        "Your average mood was {:.1} from {}",
+        average,
+        count_of(mood.checkin_count as usize, "check-in")
+    );
+    match mood.change {
+        Some(change) if change.abs() < SAME_MOOD_CHANGE => {
+            format!("{}, about the same as last week.", base)
+        }
+        Some(change) => format!(
+            "{}, {} {:.1} from last week.",
+            base,
+            if change > 0.0 { "up" } else { "down" },
+            change.abs()
+        ),
+        None => format!("{}.", base),
+    }
+}
+
+/// "2025-03-03" → "Monday"
+fn weekday(date: &str) -> String {
+    NaiveDate::parse_from_str(date, DATE_FORMAT)
+        .map(|d| d.format("%A").to_string())
+        .unwrap_or_else(|_| date.to_string())
+}
+
+fn severity_label(level: SeverityLevel) -> String {
+    level.as_str().replace('_', " ")
+}
+
+/// "1 goal", "3 goals"
+fn count_of(count: usize, noun: &str) -> String {
+    if count == 1 {
+        format!("1 {}", noun)
+    } else {
+        format!("{} {}s", count, noun)
+    }
+}
+
+/// "A", "A and B", "A, B and C"
+fn join_list(items: &[String]) -> String {
+    match items {
+        [] => String::new(),
+        [only] => only.clone(),
+        [rest @ .., last] => format!("{} and {}", rest.join(", "), last),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digest() -> WeeklyDigest {
+        WeeklyDigest {
+            id: 1,
+            week_start: "2025-03-03".to_string(),
+            week_end: "2025-03-09".to_string(),
+            timezone: "UTC".to_string(),
+            mood: MoodInsight {
+                average_mood: Some(4.5),
+                checkin_count: 6,
+                previous_average_mood: Some(4.0),
+                change: Some(0.5),
+            },
+            best_day: Some(DayMood {
+                date: "2025-03-08".to_string(),
+                average_mood: 6.0,
+                checkin_count: 2,
+            }),
+            worst_day: Some(DayMood {
+                date: "2025-03-04".to_string(),
+                average_mood: 3.0,
+                checkin_count: 1,
+            }),
+            top_activities: vec![],
+            goals: vec![],
+            missed_reminders: vec![],
+            severity_changes: vec![],
+            sentences: vec![],
+            generated_at: "2025-03-10 00:00:00".to_string(),
+            notified_at: None,
+        }
+    }
+
+    #[test]
+    fn test_completed_week_bounds() {
+        let tz: Tz = "America/New_York".parse().unwrap();
+        // Wednesday 12 March 2025, 15:00 UTC
+        let now = "2025-03-12T15:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let week = DigestWeek::completed_before(now, tz);
+
+        assert_eq!(week.start_label(), "2025-03-03");
+        assert_eq!(week.end_label(), "2025-03-09");
+        // Starts in EST (UTC-5), ends after the switch to EDT (UTC-4)
+        assert_eq!(
+            week.utc_bounds(),
+            (
+                "2025-03-03 05:00:00".to_string(),
+                "2025-03-10 03:59:59".to_string()
+            )
+        );
+        assert_eq!(week.previous().start_label(), "2025-02-24");
+
+        // A Monday just after midnight local already completes the week before
+        let monday = "2025-03-10T04:30:00Z".parse::<DateTime<Utc>>().unwrap();
+        assert_eq!(
+            DigestWeek::completed_before(monday, tz).start_label(),
+            "2025-03-03"
+        );
+    }
+
+    #[test]
+    fn test_best_and_worst_days() {
+        let week = DigestWeek {
+            start: NaiveDate::from_ymd_opt(2025, 3, 3).unwrap(),
+            tz: chrono_tz::UTC,
+        };
+        let checkins = vec![
+            ("2025-03-03 09:00:00".to_string(), 3),
+            ("2025-03-03 21:00:00".to_string(), 5),
+            ("2025-03-05 09:00:00".to_string(), 6),
+            ("2025-03-07 09:00:00".to_string(), 2),
+            ("2025-03-10 09:00:00".to_string(), 7), // Next week
+        ];
+        let days = day_moods(&checkins, &week);
+        assert_eq!(days.len(), 3);
+        assert_eq!(days[0].average_mood, 4.0);
+
+        let (best, worst) = best_and_worst_days(&days);
+        assert_eq!(best.unwrap().date, "2025-03-05");
+        assert_eq!(worst.unwrap().date, "2025-03-07");
+
+        let (best, worst) = best_and_worst_days(&days[..1]);
+        assert_eq!(best.unwrap().date, "2025-03-03");
+        assert_eq!(worst, None);
+    }
+
+    #[test]
+    fn test_sentences() {
+        let mut digest = digest();
+        digest.top_activities = vec![
+            ActivityInsight {
+                activity_id: 1,
+                activity_name: "Running".to_string(),
+                average_mood: 5.5,
+                checkin_count: 2,
+                difference: 1.0,
+            },
+            ActivityInsight {
+                activity_id: 2,
+                activity_name: "Reading".to_string(),
+                average_mood: 5.0,
+                checkin_count: 3,
+                difference: 0.5,
+            },
+        ];
+        digest.goals = vec![
+            GoalInsight {
+                goal_id: 1,
+                target_name: "Running".to_string(),
+                current_value: 3,
+                target_value: 3,
+                is_achieved: true,
+            },
+            GoalInsight {
+                goal_id: 2,
+                target_name: "Exercise".to_string(),
+                current_value: 1,
+                target_value: 5,
+                is_achieved: false,
+            },
+        ];
+        digest.missed_reminders = vec![MissedReminderInsight {
+            schedule_id: 4,
+            target_name: "PHQ-9".to_string(),
+            count: 1,
+        }];
+        digest.severity_changes = vec![SeverityChange {
+            assessment_type_code: "PHQ9".to_string(),
+            assessment_type_name: "PHQ-9".to_string(),
+            previous_severity: SeverityLevel::ModeratelySevere,
+            current_severity: SeverityLevel::Moderate,
+            previous_score: 16,
+            current_score: 12,
+            improved: Some(true),
+        }];
+
+        assert_eq!(
+            sentences(&digest),
+            vec![
+                "Your average mood was 4.5 from 6 check-ins, up 0.5 from last week.",
+                "Your best day was Saturday (6.0) and your hardest was Tuesday (3.0).",
+                "Your mood was higher with Running (+1.0) and Reading (+0.5).",
+                "You reached 1 of 2 goals: Running.",
+                "You missed 1 reminder: PHQ-9 (1).",
+                "PHQ-9 improved from moderately severe to moderate (score 16 to 12).",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sentences_quiet_week() {
+        let mut digest = digest();
+        digest.mood = MoodInsight {
+            average_mood: None,
+            checkin_count: 0,
+            previous_average_mood: Some(4.0),
+            change: None,
+        };
+        digest.best_day = None;
+        digest.worst_day = None;
+
+        assert!(digest.is_empty());
+        assert_eq!(
+            sentences(&digest),
+            vec!["No mood check-ins were recorded this week."]
+        );
+    }
+}
@@ -0,0 +1,12 @@
+// Insights feature module
+// Weekly digests generated by the scheduler from mood, goals, reminders and assessments
+
+pub mod digest;
+pub mod models;
+pub mod queries;
+pub mod repository;
+
+// Re-export commonly used types
+pub use digest::DigestWeek;
+pub use models::{InsightsError, WeeklyDigest};
+pub use repository::InsightsRepository;
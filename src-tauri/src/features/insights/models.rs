@@ -0,0 +1,160 @@
+// Insights feature models
+// Weekly digests computed from mood, activities, goals, reminders and assessments
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::errors::{CommandError, ErrorType, ToCommandError};
+use crate::features::activities::models::ActivityError;
+use crate::features::assessments::models::AssessmentError;
+use crate::features::mood::models::MoodError;
+use crate::features::scheduling::models::SchedulingError;
+use crate::types::assessment::SeverityLevel;
+
+/// Most activities listed as linked with better mood
+pub const MAX_TOP_ACTIVITIES: usize = 3;
+
+/// Largest number of digests one query returns
+pub const MAX_DIGEST_LIMIT: i32 = 520;
+
+/// Insights-specific errors
+#[derive(Error, Debug)]
+pub enum InsightsError {
+    #[error("Limit must be between 1 and {max}: {0}", max = MAX_DIGEST_LIMIT)]
+    InvalidLimit(i32),
+
+    #[error(transparent)]
+    Mood(#[from] MoodError),
+
+    #[error(transparent)]
+    Activity(#[from] ActivityError),
+
+    #[error(transparent)]
+    Assessment(#[from] AssessmentError),
+
+    #[error(transparent)]
+    Scheduling(#[from] SchedulingError),
+
+    #[error("Database error: {0}")]
+    Database(#[from] rusqlite::Error),
+
+    #[error("Digest serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+
+    #[error("Database lock issue. This request will be retried automatically.")]
+    LockPoisoned,
+}
+
+impl ToCommandError for InsightsError {
+    fn to_command_error(&self) -> CommandError {
+        match self {
+            InsightsError::InvalidLimit(value) => {
+                CommandError::permanent(self.to_string(), ErrorType::Validation).with_details(
+                    serde_json::json!({
+                        "field": "limit",
+                        "value": value,
+                        "max": MAX_DIGEST_LIMIT
+                    }),
+                )
+            }
+            InsightsError::Mood(e) => e.to_command_error(),
+            InsightsError::Activity(e) => e.to_command_error(),
+            InsightsError::Assessment(e) => e.to_command_error(),
+            InsightsError::Scheduling(e) => e.to_command_error(),
+            InsightsError::Database(e) => CommandError::from_rusqlite_error(e),
+            InsightsError::Serialization(_) => {
+                CommandError::permanent(self.to_string(), ErrorType::Serialization)
+            }
+            InsightsError::LockPoisoned => CommandError::retryable(
+                "Database lock issue. This request will be retried automatically.".to_string(),
+                ErrorType::LockPoisoned,
+            ),
+        }
+    }
+}
+
+/// Insights for one completed week, Monday to Sunday in the local timezone
+#[derive(Serialize, Deserialize, specta::Type, Clone, Debug, PartialEq)]
+pub struct WeeklyDigest {
+    pub id: i32,
+    pub week_start: String, // YYYY-MM-DD, Monday
+    pub week_end: String,   // YYYY-MM-DD, Sunday
+    pub timezone: String,
+    pub mood: MoodInsight,
+    pub best_day: Option<DayMood>,
+    pub worst_day: Option<DayMood>, // None when only one day has check-ins
+    pub top_activities: Vec<ActivityInsight>, // Best first, at most MAX_TOP_ACTIVITIES
+    pub goals: Vec<GoalInsight>,
+    pub missed_reminders: Vec<MissedReminderInsight>,
+    pub severity_changes: Vec<SeverityChange>,
+    pub sentences: Vec<String>, // Human-readable summary, one sentence per insight
+    pub generated_at: String,
+    pub notified_at: Option<String>,
+}
+
+impl WeeklyDigest {
+    /// Whether the week had anything to report
+    pub fn is_empty(&self) -> bool {
+        self.mood.checkin_count == 0
+            && self.goals.iter().all(|g| !g.is_achieved)
+            && self.missed_reminders.is_empty()
+            && self.severity_changes.is_empty()
+    }
+}
+
+/// Mood this week against the week before
+#[derive(Serialize, Deserialize, specta::Type, Clone, Debug, PartialEq)]
+pub struct MoodInsight {
+    pub average_mood: Option<f64>, // None without check-ins
+    pub checkin_count: i32,
+    pub previous_average_mood: Option<f64>,
+    pub change: Option<f64>, // This week minus last week; positive is better
+}
+
+/// Average mood of one local day
+#[derive(Serialize, Deserialize, specta::Type, Clone, Debug, PartialEq)]
+pub struct DayMood {
+    pub date: String, // YYYY-MM-DD
+    pub average_mood: f64,
+    pub checkin_count: i32,
+}
+
+/// An activity whose check-ins averaged above the week's mood
+#[derive(Serialize, Deserialize, specta::Type, Clone, Debug, PartialEq)]
+pub struct ActivityInsight {
+    pub activity_id: i32,
+    pub activity_name: String,
+    pub average_mood: f64,
+    pub checkin_count: i32,
+    pub difference: f64, // Above the week's average mood
+}
+
+/// Progress of one goal at the end of the week
+#[derive(Serialize, Deserialize, specta::Type, Clone, Debug, PartialEq)]
+pub struct GoalInsight {
+    pub goal_id: i32,
+    pub target_name: String, // Activity or group name
+    pub current_value: i32,
+    pub target_value: i32,
+    pub is_achieved: bool,
+}
+
+/// Reminders of one schedule missed during the week
+#[derive(Serialize, Deserialize, specta::Type, Clone, Debug, PartialEq)]
+pub struct MissedReminderInsight {
+    pub schedule_id: i32,
+    pub target_name: String,
+    pub count: i32,
+}
+
+/// An assessment whose severity level changed during the week
+#[derive(Serialize, Deserialize, specta::Type, Clone, Debug, PartialEq)]
+pub struct SeverityChange {
+    pub assessment_type_code: String,
+    pub assessment_type_name: String,
+    pub previous_severity: SeverityLevel, // Latest before the week
+    pub current_severity: SeverityLevel,  // Latest in the week
+    pub previous_score: i32,
+    pub current_score: i32,
+    pub improved: Option<bool>, // None if either level is unknown
+}
@@ -0,0 +1,22 @@
+// Insights queries - Read operations (Tauri commands)
+
+use super::models::*;
+use super::repository::InsightsRepository;
+use crate::{errors::ToCommandError, AppState, CommandError};
+use tauri::State;
+use tracing::error;
+
+/// Stored weekly digests, newest week first (12 unless `limit` is given)
+#[tauri::command]
+#[specta::specta]
+pub async fn get_weekly_digests(
+    limit: Option<i32>,
+    state: State<'_, AppState>,
+) -> Result<Vec<WeeklyDigest>, CommandError> {
+    let repo = InsightsRepository::new(state.db.clone());
+
+    repo.get_weekly_digests(limit).map_err(|e| {
+        error!("get_weekly_digests error: {} (limit: {:?})", e, limit);
+        e.to_command_error()
+    })
+}
@@ -0,0 +1,286 @@
+// Insights repository - Weekly digest generation and storage
+//
+// A digest is assembled from the other features' repositories (mood stats,
+// goal progress, missed reminders, assessment history), so each figure means
+// the same as on its own screen. Those repositories take the connection lock
+// themselves; it is only held here around this feature's own queries.
+
+use std::sync::Arc;
+
+use rusqlite::OptionalExtension;
+
+use super::digest::{
+    best_and_worst_days, day_moods, sentences, severity_changes, top_activities, DigestWeek,
+};
+use super::models::*;
+use crate::db::Database;
+use crate::features::activities::repository::ActivityRepository;
+use crate::features::assessments::repository::AssessmentRepository;
+use crate::features::mood::repository::MoodRepository;
+use crate::features::scheduling::repository::SchedulingRepository;
+
+/// Digests returned when no limit is given
+const DEFAULT_DIGEST_LIMIT: i32 = 12;
+
+pub struct InsightsRepository {
+    db: Arc<Database>,
+    mood_repo: MoodRepository,
+    activity_repo: ActivityRepository,
+    assessment_repo: AssessmentRepository,
+    scheduling_repo: SchedulingRepository,
+}
+
+impl InsightsRepository {
+    pub fn new(db: Arc<Database>) -> Self {
+        Self {
+            mood_repo: MoodRepository::new(Arc::clone(&db)),
+            activity_repo: ActivityRepository::new(Arc::clone(&db)),
+            assessment_repo: AssessmentRepository::new(Arc::clone(&db)),
+            scheduling_repo: SchedulingRepository::new(Arc::clone(&db)),
+            db,
+        }
+    }
+
+    /// Compute the digest for `week` without storing it
+    pub fn build_weekly_digest(
+        &self,
+        week: &DigestWeek,
+        generated_at: &str,
+    ) -> Result<WeeklyDigest, InsightsError> {
+        let (from, to) = week.utc_bounds();
+        let (previous_from, previous_to) = week.previous().utc_bounds();
+
+        // Mood against last week
+        let stats = self
+            .mood_repo
+            .get_mood_stats(Some(from.clone()), Some(to.clone()))?;
+        let previous_stats = self
+            .mood_repo
+            .get_mood_stats(Some(previous_from), Some(previous_to))?;
+        // get_mood_stats reports 0.0 without check-ins
+        let average_mood = (stats.total_checkins > 0).then_some(stats.average_mood);
+        let previous_average_mood =
+            (previous_stats.total_checkins > 0).then_some(previous_stats.average_mood);
+        let mood = MoodInsight {
+            average_mood,
+            checkin_count: stats.total_checkins,
+            previous_average_mood,
+            change: average_mood
+                .zip(previous_average_mood)
+                .map(|(current, previous)| current - previous),
+        };
+
+        let days = day_moods(&self.get_checkins(&from, &to)?, week);
+        let (best_day, worst_day) = best_and_worst_days(&days);
+        let top_activities = average_mood
+            .map(|average| top_activities(&stats.activity_correlations, average))
+            .unwrap_or_default();
+
+        // Goal progress as of the end of the week
+        let mut goals = Vec::new();
+        for (goal_id, target_name) in self.get_goal_targets()? {
+            let progress = self.activity_repo.check_goal_progress(goal_id, &to)?;
+            goals.push(GoalInsight {
+                goal_id,
+                target_name,
+                current_value: progress.current_value,
+                target_value: progress.target_value,
+                is_achieved: progress.is_achieved,
+            });
+        }
+
+        let mut missed_reminders: Vec<MissedReminderInsight> = Vec::new();
+        for missed in self.scheduling_repo.get_missed_reminders(true)? {
+            if missed.scheduled_for < from || missed.scheduled_for > to {
+                continue;
+            }
+            match missed_reminders
+                .iter_mut()
+                .find(|m| m.schedule_id == missed.schedule_id)
+            {
+                Some(insight) => insight.count += 1,
+                None => missed_reminders.push(MissedReminderInsight {
+                    schedule_id: missed.schedule_id,
+                    target_name: missed.target_name,
+                    count: 1,
+                }),
+            }
+        }
+        missed_reminders.sort_by(|a, b| {
+            b.count
+                .cmp(&a.count)
+                .then_with(|| a.target_name.cmp(&b.target_name))
+        });
+
+        let history =
+            self.assessment_repo
+                .get_assessment_history(None, None, Some(to.clone()), None)?;
+        let severity_changes = severity_changes(&history, &from);
+
+        let mut digest = WeeklyDigest {
+            id: 0,
+            week_start: week.start_label(),
+            week_end: week.end_label(),
+            timezone: week.tz.name().to_string(),
+            mood,
+            best_day,
+            worst_day,
+            top_activities,
+            goals,
+            missed_reminders,
+            severity_changes,
+            sentences: Vec::new(),
+            generated_at: generated_at.to_string(),
+            notified_at: None,
+        };
+        digest.sentences = sentences(&digest);
+        Ok(digest)
+    }
+
+    /// Compute and store the digest for `week`, unless it already exists.
+    /// Returns the new digest, or None if the week already had one.
+    pub fn record_weekly_digest(
+        &self,
+        week: &DigestWeek,
+        generated_at: &str,
+    ) -> Result<Option<WeeklyDigest>, InsightsError> {
+        if self.get_weekly_digest(&week.start_label())?.is_some() {
+            return Ok(None);
+        }
+
+        let mut digest = self.build_weekly_digest(week, generated_at)?;
+        let json = serde_json::to_string(&digest)?;
+
+        let conn = self.db.get_connection();
+        let conn = conn.lock();
+        let inserted = conn.execute(
+            "INSERT INTO weekly_insights (week_start, week_end, timezone, digest, generated_at)
+             VALUES (?, ?, ?, ?, ?)
+             ON CONFLICT(week_start) DO NOTHING",
+            rusqlite::params![
+                digest.week_start,
+                digest.week_end,
+                digest.timezone,
+                json,
+                generated_at
+            ],
+        )?;
+        if inserted == 0 {
+            return Ok(None);
+        }
+        digest.id = conn.last_insert_rowid() as i32;
+
+        Ok(Some(digest))
+    }
+
+    /// The stored digest of the week starting on `week_start` (YYYY-MM-DD)
+    pub fn get_weekly_digest(
+        &self,
+        week_start: &str,
+    ) -> Result<Option<WeeklyDigest>, InsightsError> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock();
+
+        let row = conn
+            .query_row(
+                &format!("{} WHERE week_start = ?", DIGEST_SELECT),
+                [week_start],
+                read_digest_row,
+            )
+            .optional()?;
+        row.map(digest_from_row).transpose()
+    }
+
+    /// Stored digests, newest week first
+    pub fn get_weekly_digests(
+        &self,
+        limit: Option<i32>,
+    ) -> Result<Vec<WeeklyDigest>, InsightsError> {
+        let limit = limit.unwrap_or(DEFAULT_DIGEST_LIMIT);
+        if !(1..=MAX_DIGEST_LIMIT).contains(&limit) {
+            return Err(InsightsError::InvalidLimit(limit));
+        }
+
+        let conn = self.db.get_connection();
+        let conn = conn.lock();
+
+        let mut stmt = conn.prepare(&format!(
+            "{} ORDER BY week_start DESC LIMIT ?",
+            DIGEST_SELECT
+        ))?;
+        let rows = stmt
+            .query_map([limit], read_digest_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+        rows.into_iter().map(digest_from_row).collect()
+    }
+
+    /// Record that a digest was announced, or deliberately not announced
+    pub fn mark_digest_notified(&self, id: i32, notified_at: &str) -> Result<(), InsightsError> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock();
+
+        conn.execute(
+            "UPDATE weekly_insights SET notified_at = ? WHERE id = ?",
+            rusqlite::params![notified_at, id],
+        )?;
+        Ok(())
+    }
+
+    /// (created_at, rating) of the check-ins between the stored-timestamp bounds
+    fn get_checkins(&self, from: &str, to: &str) -> Result<Vec<(String, i32)>, InsightsError> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock();
+
+        let mut stmt = conn.prepare(
+            "SELECT created_at, mood_rating
+             FROM mood_checkins
+             WHERE created_at >= ? AND created_at <= ?
+             ORDER BY created_at",
+        )?;
+        let rows = stmt
+            .query_map([from, to], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(rows)
+    }
+
+    /// (goal id, activity or group name) of every goal whose target still exists
+    fn get_goal_targets(&self) -> Result<Vec<(i32, String)>, InsightsError> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock();
+
+        let mut stmt = conn.prepare(
+            "SELECT g.id, COALESCE(a.name, ag.name)
+             FROM activity_goals g
+             LEFT JOIN activities a ON a.id = g.activity_id AND a.deleted_at IS NULL
+             LEFT JOIN activity_groups ag ON ag.id = g.group_id AND ag.deleted_at IS NULL
+             WHERE g.deleted_at IS NULL
+               AND (a.id IS NOT NULL OR ag.id IS NOT NULL)
+             ORDER BY g.id",
+        )?;
+        let rows = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(rows)
+    }
+}
+
+const DIGEST_SELECT: &str =
+    "SELECT id, digest, CAST(generated_at AS VARCHAR), CAST(notified_at AS VARCHAR)
+     FROM weekly_insights";
+
+type DigestRow = (i32, String, String, Option<String>);
+
+fn read_digest_row(row: &rusqlite::Row) -> rusqlite::Result<DigestRow> {
+    Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+}
+
+/// The stored JSON, with the columns that change after it was written
+fn digest_from_row(
+    (id, json, generated_at, notified_at): DigestRow,
+) -> Result<WeeklyDigest, InsightsError> {
+    let mut digest: WeeklyDigest = serde_json::from_str(&json)?;
+    digest.id = id;
+    digest.generated_at = generated_at;
+    digest.notified_at = notified_at;
+    Ok(digest)
+}
@@ -1,6 +1,10 @@
 // Feature modules organized in vertical slices
 pub mod activities;
 pub mod assessments;
+pub mod backup;
+pub mod goal_templates;
+pub mod insights;
 pub mod mood;
 pub mod scheduling;
+pub mod streaks;
 pub mod visualization;
@@ -479,10 +479,24 @@ impl MoodRepository {
 
         // Insert activity and get all fields using RETURNING
         // The partial unique index will enforce uniqueness atomically
+        // Missing color/icon fall back to the group's defaults
         let result: ActivityInsertResult = conn.query_row(
-            "INSERT INTO activities (name, color, icon, group_id) VALUES (?, ?, ?, ?) RETURNING id, name, color, icon, CAST(created_at AS VARCHAR)",
+            "INSERT INTO activities (name, color, icon, group_id)
+             VALUES (?1,
+                     COALESCE(?2, (SELECT color FROM activity_groups WHERE id = ?4)),
+                     COALESCE(?3, (SELECT icon FROM activity_groups WHERE id = ?4)),
+                     ?4)
+             RETURNING id, name, color, icon, CAST(created_at AS VARCHAR)",
             rusqlite::params![trimmed_name, color, icon, group_id],
-            |row| Ok((row.get(0)?, row.get(1)?, row.get::<_, Option<HexColor>>(2)?, row.get(3)?, row.get(4)?)),
+            |row| {
+                Ok((
+                    row.get(0)?,
+                    row.get(1)?,
+                    row.get::<_, Option<HexColor>>(2)?,
+                    row.get(3)?,
+                    row.get(4)?,
+                ))
+            },
         );
 
         // Handle constraint violation with proper error
@@ -690,6 +704,7 @@ impl MoodRepository {
     ///
     /// # Arguments
     /// * `include_deleted` - If true, includes soft-deleted activities; if false, only active ones
+    ///   (activities of archived groups are excluded as well)
     ///
     /// # Returns
     /// * `Ok(Vec<Activity>)` - List of activities ordered by created_at DESC
@@ -701,7 +716,11 @@ impl MoodRepository {
         let query = if include_deleted {
             "SELECT id, group_id, name, color, icon, CAST(created_at AS VARCHAR), CAST(deleted_at AS VARCHAR) FROM activities ORDER BY name"
         } else {
-            "SELECT id, group_id, name, color, icon, CAST(created_at AS VARCHAR), CAST(deleted_at AS VARCHAR) FROM activities WHERE deleted_at IS NULL ORDER BY name"
+            // Activities of archived groups are hidden from pickers
+            "SELECT id, group_id, name, color, icon, CAST(created_at AS VARCHAR), CAST(deleted_at AS VARCHAR) FROM activities
+             WHERE deleted_at IS NULL
+               AND group_id NOT IN (SELECT id FROM activity_groups WHERE archived_at IS NOT NULL)
+             ORDER BY name"
         };
 
         let mut stmt = conn.prepare(query)?;
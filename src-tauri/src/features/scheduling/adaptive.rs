@@ -0,0 +1,482 @@
+// Adaptive assessment frequency
+//
+// An assessment schedule can carry ordered rules that switch its frequency
+// based on the latest results of its assessment, e.g. "moderate or worse ->
+// weekly" followed by "two consecutive minimal results -> monthly". Rules are
+// checked in order against the newest completed responses; the first one that
+// matches decides the frequency. Each automatic change is logged with a
+// human-readable reason.
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::types::assessment::SeverityLevel;
+
+use super::models::{AssessmentSchedule, ScheduleFrequency, ScheduleRecurrence, SchedulingError};
+use super::recurrence::parse_dtstart;
+
+/// Most adaptive rules a schedule can have
+pub const MAX_ADAPTIVE_RULES: usize = 10;
+
+/// Most consecutive results a rule can require
+pub const MAX_CONSECUTIVE_RESULTS: i32 = 10;
+
+/// How a result's severity is compared with a rule's threshold
+#[derive(Serialize, Deserialize, specta::Type, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SeverityComparison {
+    /// The threshold or worse
+    AtLeast,
+    /// The threshold or better
+    AtMost,
+}
+
+impl SeverityComparison {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SeverityComparison::AtLeast => "at_least",
+            SeverityComparison::AtMost => "at_most",
+        }
+    }
+}
+
+impl FromStr for SeverityComparison {
+    type Err = SchedulingError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "at_least" => Ok(SeverityComparison::AtLeast),
+            "at_most" => Ok(SeverityComparison::AtMost),
+            _ => Err(SchedulingError::InvalidAdaptiveRule(format!(
+                "Unknown comparison: {}",
+                s
+            ))),
+        }
+    }
+}
+
+/// A rule as submitted when replacing a schedule's rules
+#[derive(Serialize, Deserialize, specta::Type, Clone, Debug, PartialEq)]
+pub struct AdaptiveRuleRequest {
+    pub comparison: SeverityComparison,
+    pub severity_level: SeverityLevel,
+    pub consecutive_count: i32, // How many of the newest results must match
+    pub frequency: ScheduleFrequency,
+    pub day_of_week: Option<i32>,  // Required for weekly/biweekly
+    pub day_of_month: Option<i32>, // Required for monthly
+}
+
+impl AdaptiveRuleRequest {
+    pub fn validate(&self) -> Result<(), SchedulingError> {
+        if self.severity_level == SeverityLevel::Unknown {
+            return Err(SchedulingError::InvalidAdaptiveRule(
+                "severity_level must be a known level".to_string(),
+            ));
+        }
+        if !(1..=MAX_CONSECUTIVE_RESULTS).contains(&self.consecutive_count) {
+            return Err(SchedulingError::InvalidAdaptiveRule(format!(
+                "consecutive_count must be between 1 and {}",
+                MAX_CONSECUTIVE_RESULTS
+            )));
+        }
+        if let Some(day) = self.day_of_week {
+            if !(0..=6).contains(&day) {
+                return Err(SchedulingError::InvalidDayOfWeek(day));
+            }
+        }
+        if let Some(day) = self.day_of_month {
+            if !(1..=31).contains(&day) {
+                return Err(SchedulingError::InvalidDayOfMonth(day));
+            }
+        }
+        match self.frequency {
+            ScheduleFrequency::Weekly | ScheduleFrequency::Biweekly
+                if self.day_of_week.is_none() =>
+            {
+                Err(SchedulingError::InvalidAdaptiveRule(
+                    "day_of_week required for weekly/biweekly rules".to_string(),
+                ))
+            }
+            ScheduleFrequency::Monthly if self.day_of_month.is_none() => {
+                Err(SchedulingError::InvalidAdaptiveRule(
+                    "day_of_month required for monthly rules".to_string(),
+                ))
+            }
+            ScheduleFrequency::Custom => Err(SchedulingError::InvalidAdaptiveRule(
+                "Rules can't switch to a custom frequency".to_string(),
+            )),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Validate a full rule list for one schedule
+pub fn validate_adaptive_rules(rules: &[AdaptiveRuleRequest]) -> Result<(), SchedulingError> {
+    if rules.len() > MAX_ADAPTIVE_RULES {
+        return Err(SchedulingError::InvalidAdaptiveRule(format!(
+            "At most {} rules are allowed per schedule",
+            MAX_ADAPTIVE_RULES
+        )));
+    }
+    rules.iter().try_for_each(AdaptiveRuleRequest::validate)
+}
+
+/// A stored rule; lower `position` is checked first
+#[derive(Serialize, Deserialize, specta::Type, Clone, Debug, PartialEq)]
+pub struct AdaptiveRule {
+    pub id: i32,
+    pub schedule_id: i32,
+    pub position: i32,
+    pub comparison: SeverityComparison,
+    pub severity_level: SeverityLevel,
+    pub consecutive_count: i32,
+    pub frequency: ScheduleFrequency,
+    pub day_of_week: Option<i32>,
+    pub day_of_month: Option<i32>,
+}
+
+/// A completed response as seen by rule evaluation
+#[derive(Clone, Debug, PartialEq)]
+pub struct RecentResult {
+    pub response_id: i32,
+    pub total_score: i32,
+    pub severity_level: SeverityLevel,
+}
+
+impl AdaptiveRule {
+    /// Whether a single severity satisfies the rule's threshold.
+    /// Unknown severities never match.
+    pub fn matches(&self, severity: SeverityLevel) -> bool {
+        match (severity.compare(&self.severity_level), self.comparison) {
+            (Some(ordering), SeverityComparison::AtLeast) => ordering.is_ge(),
+            (Some(ordering), SeverityComparison::AtMost) => ordering.is_le(),
+            (None, _) => false,
+        }
+    }
+
+    /// Whether the newest `consecutive_count` results (newest first) all match
+    pub fn matches_results(&self, recent: &[RecentResult]) -> bool {
+        let needed = self.consecutive_count as usize;
+        recent.len() >= needed
+            && recent[..needed]
+                .iter()
+                .all(|result| self.matches(result.severity_level))
+    }
+
+    /// Recurrence columns of `schedule` after switching to this rule's frequency.
+    /// Time of day, start and exceptions are kept.
+    pub fn recurrence_for(
+        &self,
+        schedule: &AssessmentSchedule,
+    ) -> Result<ScheduleRecurrence, SchedulingError> {
+        ScheduleRecurrence::new(
+            &self.frequency,
+            &schedule.time_of_day,
+            self.day_of_week,
+            self.day_of_month,
+            None,
+            parse_dtstart(&schedule.dtstart)?,
+            &schedule.exdates,
+        )
+    }
+}
+
+impl fmt::Display for AdaptiveRule {
+    /// The threshold in words, e.g. "moderately severe or worse"
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let direction = match self.comparison {
+            SeverityComparison::AtLeast => "or worse",
+            SeverityComparison::AtMost => "or better",
+        };
+        write!(
+            f,
+            "{} {}",
+            self.severity_level.as_str().replace('_', " "),
+            direction
+        )
+    }
+}
+
+/// First rule (by position) matched by the newest results
+pub fn select_rule<'a>(
+    rules: &'a [AdaptiveRule],
+    recent: &[RecentResult],
+) -> Option<&'a AdaptiveRule> {
+    rules.iter().find(|rule| rule.matches_results(recent))
+}
+
+/// Why `rule` changed `schedule`, for the frequency change log
+pub fn change_reason(
+    rule: &AdaptiveRule,
+    schedule: &AssessmentSchedule,
+    latest: &RecentResult,
+) -> String {
+    let results = if rule.consecutive_count > 1 {
+        format!(
+            "Last {} {} results were",
+            rule.consecutive_count, schedule.target_name
+        )
+    } else {
+        format!("Latest {} result was", schedule.target_name)
+    };
+    format!(
+        "{} {} (latest: {}, score {}), so reminders changed from {} to {}",
+        results,
+        rule,
+        latest.severity_level.as_str().replace('_', " "),
+        latest.total_score,
+        schedule.frequency.as_str(),
+        rule.frequency.as_str()
+    )
+}
+
+/// A frequency change made by an adaptive rule
+#[derive(Serialize, Deserialize, specta::Type, Clone, Debug, PartialEq)]
+pub struct FrequencyChange {
+    pub id: i32,
+    pub schedule_id: i32,
+    pub rule_id: Option<i32>, // None once the rule has been replaced
+    pub from_frequency: ScheduleFrequency,
+    pub to_frequency: ScheduleFrequency,
+    pub from_rrule: String,
+    pub to_rrule: String,
+    pub reason: String,
+    pub assessment_response_id: Option<i32>,
+    pub changed_at: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(
+        comparison: SeverityComparison,
+        severity_level: SeverityLevel,
+        consecutive_count: i32,
+        frequency: ScheduleFrequency,
+    ) -> AdaptiveRule {
+        AdaptiveRule {
+            id: 1,
+            schedule_id: 1,
+            position: 0,
+            comparison,
+            severity_level,
+            consecutive_count,
+            day_of_week: Some(1),
+            day_of_month: Some(1),
+            frequency,
+        }
+    }
+
+    fn results(levels: &[SeverityLevel]) -> Vec<RecentResult> {
+        levels
+            .iter()
+            .enumerate()
+            .map(|(i, &severity_level)| RecentResult {
+                response_id: i as i32 + 1,
+                total_score: 0,
+                severity_level,
+            })
+            .collect()
+    }
+
+    fn request(frequency: ScheduleFrequency) -> AdaptiveRuleRequest {
+        AdaptiveRuleRequest {
+            comparison: SeverityComparison::AtLeast,
+            severity_level: SeverityLevel::Moderate,
+            consecutive_count: 1,
+            frequency,
+            day_of_week: None,
+            day_of_month: None,
+        }
+    }
+
+    // ========================================================================
+    // Matching
+    // ========================================================================
+
+    #[test]
+    fn test_threshold_comparisons() {
+        let worse = rule(
+            SeverityComparison::AtLeast,
+            SeverityLevel::Moderate,
+            1,
+            ScheduleFrequency::Weekly,
+        );
+        assert!(worse.matches(SeverityLevel::Moderate));
+        assert!(worse.matches(SeverityLevel::Severe));
+        assert!(!worse.matches(SeverityLevel::Mild));
+        assert!(!worse.matches(SeverityLevel::Unknown));
+
+        let better = rule(
+            SeverityComparison::AtMost,
+            SeverityLevel::Mild,
+            1,
+            ScheduleFrequency::Monthly,
+        );
+        assert!(better.matches(SeverityLevel::Minimal));
+        assert!(better.matches(SeverityLevel::Mild));
+        assert!(!better.matches(SeverityLevel::Moderate));
+    }
+
+    #[test]
+    fn test_consecutive_results_newest_first() {
+        let calm = rule(
+            SeverityComparison::AtMost,
+            SeverityLevel::Minimal,
+            2,
+            ScheduleFrequency::Monthly,
+        );
+        use SeverityLevel::*;
+
+        assert!(calm.matches_results(&results(&[Minimal, Minimal, Severe])));
+        assert!(!calm.matches_results(&results(&[Minimal, Mild, Minimal])));
+        // Not enough results yet
+        assert!(!calm.matches_results(&results(&[Minimal])));
+    }
+
+    #[test]
+    fn test_first_matching_rule_wins() {
+        use SeverityLevel::*;
+        let rules = vec![
+            rule(
+                SeverityComparison::AtLeast,
+                Moderate,
+                1,
+                ScheduleFrequency::Weekly,
+            ),
+            rule(
+                SeverityComparison::AtMost,
+                Minimal,
+                2,
+                ScheduleFrequency::Monthly,
+            ),
+        ];
+
+        assert_eq!(
+            select_rule(&rules, &results(&[Severe, Minimal])).map(|r| &r.frequency),
+            Some(&ScheduleFrequency::Weekly)
+        );
+        assert_eq!(
+            select_rule(&rules, &results(&[Minimal, Minimal])).map(|r| &r.frequency),
+            Some(&ScheduleFrequency::Monthly)
+        );
+        assert_eq!(select_rule(&rules, &results(&[Minimal, Mild])), None);
+        assert_eq!(select_rule(&rules, &[]), None);
+    }
+
+    #[test]
+    fn test_rule_description() {
+        let calm = rule(
+            SeverityComparison::AtMost,
+            SeverityLevel::Minimal,
+            2,
+            ScheduleFrequency::Monthly,
+        );
+        assert_eq!(calm.to_string(), "minimal or better");
+
+        let worse = rule(
+            SeverityComparison::AtLeast,
+            SeverityLevel::ModeratelySevere,
+            1,
+            ScheduleFrequency::Weekly,
+        );
+        assert_eq!(worse.to_string(), "moderately severe or worse");
+    }
+
+    #[test]
+    fn test_change_reason() {
+        let calm = rule(
+            SeverityComparison::AtMost,
+            SeverityLevel::Minimal,
+            2,
+            ScheduleFrequency::Monthly,
+        );
+        let latest = RecentResult {
+            response_id: 7,
+            total_score: 3,
+            severity_level: SeverityLevel::Minimal,
+        };
+        let schedule = AssessmentSchedule {
+            id: 1,
+            target: super::super::models::ScheduleTarget::Assessment {
+                assessment_type_id: 1,
+            },
+            target_name: "PHQ-9".to_string(),
+            assessment_type_id: Some(1),
+            assessment_type_code: Some("PHQ9".to_string()),
+            assessment_type_name: Some("PHQ-9".to_string()),
+            frequency: ScheduleFrequency::Weekly,
+            time_of_day: "09:00".to_string(),
+            day_of_week: Some(1),
+            day_of_month: None,
+            rrule: "FREQ=WEEKLY;BYDAY=MO;BYHOUR=9;BYMINUTE=0".to_string(),
+            dtstart: "2025-01-06T09:00:00".to_string(),
+            exdates: Vec::new(),
+            timezone: "UTC".to_string(),
+            enabled: true,
+            missed_policy: super::super::models::MissedReminderPolicy::Coalesce,
+            last_triggered_at: None,
+            created_at: "2025-01-01 00:00:00".to_string(),
+            updated_at: "2025-01-01 00:00:00".to_string(),
+        };
+
+        assert_eq!(
+            change_reason(&calm, &schedule, &latest),
+            "Last 2 PHQ-9 results were minimal or better (latest: minimal, score 3), \
+             so reminders changed from weekly to monthly"
+        );
+
+        let recurrence = calm.recurrence_for(&schedule).expect("Failed to switch");
+        assert_eq!(recurrence.frequency, ScheduleFrequency::Monthly);
+        assert_eq!(recurrence.day_of_month, Some(1));
+        assert_eq!(recurrence.time_of_day, "09:00");
+        assert_eq!(recurrence.dtstart, schedule.dtstart);
+    }
+
+    // ========================================================================
+    // Validation
+    // ========================================================================
+
+    #[test]
+    fn test_validate_rule_requests() {
+        assert!(request(ScheduleFrequency::Daily).validate().is_ok());
+
+        // Weekly and monthly need their day
+        assert!(request(ScheduleFrequency::Weekly).validate().is_err());
+        assert!(request(ScheduleFrequency::Monthly).validate().is_err());
+        let weekly = AdaptiveRuleRequest {
+            day_of_week: Some(1),
+            ..request(ScheduleFrequency::Weekly)
+        };
+        assert!(weekly.validate().is_ok());
+        let monthly = AdaptiveRuleRequest {
+            day_of_month: Some(32),
+            ..request(ScheduleFrequency::Monthly)
+        };
+        assert!(matches!(
+            monthly.validate(),
+            Err(SchedulingError::InvalidDayOfMonth(32))
+        ));
+
+        assert!(request(ScheduleFrequency::Custom).validate().is_err());
+        let unknown = AdaptiveRuleRequest {
+            severity_level: SeverityLevel::Unknown,
+            ..request(ScheduleFrequency::Daily)
+        };
+        assert!(unknown.validate().is_err());
+        let too_many = AdaptiveRuleRequest {
+            consecutive_count: MAX_CONSECUTIVE_RESULTS + 1,
+            ..request(ScheduleFrequency::Daily)
+        };
+        assert!(too_many.validate().is_err());
+
+        let rules = vec![request(ScheduleFrequency::Daily); MAX_ADAPTIVE_RULES + 1];
+        assert!(matches!(
+            validate_adaptive_rules(&rules),
+            Err(SchedulingError::InvalidAdaptiveRule(_))
+        ));
+    }
+}
@@ -0,0 +1,62 @@
+// Time source for the scheduler
+//
+// The scheduler asks a `Clock` for the current time instead of calling
+// `Utc::now()` itself, so tests can drive it through virtual time.
+
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, Utc};
+use parking_lot::Mutex;
+
+/// Supplies the current time
+pub trait Clock: Send + Sync {
+    fn now(&self) -> DateTime<Utc>;
+}
+
+impl<T: Clock + ?Sized> Clock for Arc<T> {
+    fn now(&self) -> DateTime<Utc> {
+        (**self).now()
+    }
+}
+
+/// The real wall clock
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// A clock that only moves when told to
+#[derive(Debug)]
+pub struct ManualClock {
+    now: Mutex<DateTime<Utc>>,
+}
+
+impl ManualClock {
+    pub fn new(start: DateTime<Utc>) -> Self {
+        Self {
+            now: Mutex::new(start),
+        }
+    }
+
+    /// Jump to `now`
+    pub fn set(&self, now: DateTime<Utc>) {
+        *self.now.lock() = now;
+    }
+
+    /// Move forward by `step` and return the new time
+    pub fn advance(&self, step: Duration) -> DateTime<Utc> {
+        let mut now = self.now.lock();
+        *now += step;
+        *now
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.now.lock()
+    }
+}
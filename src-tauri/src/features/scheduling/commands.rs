@@ -1,12 +1,22 @@
 // Scheduling commands (User Story 6)
 // T165-T167: Tauri commands for schedule management
 
-use tauri::State;
+use chrono::{DateTime, Utc};
+use tauri::{AppHandle, State};
+use tauri_plugin_dialog::DialogExt;
 use tracing::error;
 
+use crate::config::NotificationSettings;
+use crate::features::activities::ActivityRepository;
 use crate::{errors::ToCommandError, AppState, CommandError};
 
-use super::models::{AssessmentSchedule, CreateScheduleRequest, UpdateScheduleRequest};
+use super::adaptive::{validate_adaptive_rules, AdaptiveRule, AdaptiveRuleRequest};
+use super::ical::{export_calendar, parse_calendar, CalendarImportResult};
+use super::models::{
+    AssessmentSchedule, CreateScheduleRequest, ReminderEvent, SchedulingError,
+    UpdateScheduleRequest,
+};
+use super::quiet_hours::paused_until;
 use super::repository::SchedulingRepository;
 use super::repository_trait::SchedulingRepositoryTrait;
 
@@ -32,9 +42,10 @@ fn create_schedule_impl(
     repo.create_schedule(request.clone())
         .map_err(|e| {
             error!(
-                "create_schedule error: {} (assessment_type_id: {}, frequency: {:?}, time: {}, day_of_week: {:?}, day_of_month: {:?})",
+                "create_schedule error: {} (assessment_type_id: {:?}, target: {:?}, frequency: {:?}, time: {}, day_of_week: {:?}, day_of_month: {:?})",
                 e,
                 request.assessment_type_id,
+                request.target,
                 request.frequency,
                 request.time_of_day,
                 request.day_of_week,
@@ -100,12 +111,282 @@ fn delete_schedule_impl(
     })
 }
 
+/// Snooze a reminder notification; it fires again after `minutes`
+#[tauri::command]
+#[specta::specta]
+pub fn snooze_reminder(
+    event_id: i32,
+    minutes: i32,
+    state: State<AppState>,
+) -> Result<ReminderEvent, CommandError> {
+    let repo = SchedulingRepository::new(state.db.clone());
+    snooze_reminder_impl(&repo, event_id, minutes, Utc::now())
+}
+
+/// Business logic for snoozing a reminder - uses trait bound for testability
+fn snooze_reminder_impl(
+    repo: &impl SchedulingRepositoryTrait,
+    event_id: i32,
+    minutes: i32,
+    now: DateTime<Utc>,
+) -> Result<ReminderEvent, CommandError> {
+    repo.snooze_reminder(event_id, minutes, now).map_err(|e| {
+        error!(
+            "snooze_reminder error: {} (event_id: {}, minutes: {})",
+            e, event_id, minutes
+        );
+        e.to_command_error()
+    })
+}
+
+/// Dismiss a reminder notification without completing it
+#[tauri::command]
+#[specta::specta]
+pub fn dismiss_reminder(
+    event_id: i32,
+    state: State<AppState>,
+) -> Result<ReminderEvent, CommandError> {
+    let repo = SchedulingRepository::new(state.db.clone());
+    dismiss_reminder_impl(&repo, event_id, Utc::now())
+}
+
+/// Business logic for dismissing a reminder - uses trait bound for testability
+fn dismiss_reminder_impl(
+    repo: &impl SchedulingRepositoryTrait,
+    event_id: i32,
+    now: DateTime<Utc>,
+) -> Result<ReminderEvent, CommandError> {
+    repo.dismiss_reminder(event_id, now).map_err(|e| {
+        error!("dismiss_reminder error: {} (event_id: {})", e, event_id);
+        e.to_command_error()
+    })
+}
+
+/// Mark a reminder as done. Assessment reminders are completed automatically
+/// when the assessment is submitted.
+#[tauri::command]
+#[specta::specta]
+pub fn complete_reminder(
+    event_id: i32,
+    state: State<AppState>,
+) -> Result<ReminderEvent, CommandError> {
+    let repo = SchedulingRepository::new(state.db.clone());
+    complete_reminder_impl(&repo, event_id, Utc::now())
+}
+
+/// Business logic for completing a reminder - uses trait bound for testability
+fn complete_reminder_impl(
+    repo: &impl SchedulingRepositoryTrait,
+    event_id: i32,
+    now: DateTime<Utc>,
+) -> Result<ReminderEvent, CommandError> {
+    repo.complete_reminder(event_id, now).map_err(|e| {
+        error!("complete_reminder error: {} (event_id: {})", e, event_id);
+        e.to_command_error()
+    })
+}
+
+/// Mark missed reminders as seen so they no longer show up
+#[tauri::command]
+#[specta::specta]
+pub fn acknowledge_missed_reminders(
+    ids: Vec<i32>,
+    state: State<AppState>,
+) -> Result<(), CommandError> {
+    let repo = SchedulingRepository::new(state.db.clone());
+
+    repo.acknowledge_missed_reminders(&ids, Utc::now())
+        .map(|_| ())
+        .map_err(|e| {
+            error!("acknowledge_missed_reminders error: {} (ids: {:?})", e, ids);
+            e.to_command_error()
+        })
+}
+
+/// Replace a schedule's adaptive frequency rules (an empty list removes them)
+#[tauri::command]
+#[specta::specta]
+pub fn set_adaptive_rules(
+    schedule_id: i32,
+    rules: Vec<AdaptiveRuleRequest>,
+    state: State<AppState>,
+) -> Result<Vec<AdaptiveRule>, CommandError> {
+    validate_adaptive_rules(&rules).map_err(|e| e.to_command_error())?;
+
+    let repo = SchedulingRepository::new(state.db.clone());
+    set_adaptive_rules_impl(&repo, schedule_id, rules)
+}
+
+/// Business logic for replacing adaptive rules - uses trait bound for testability
+fn set_adaptive_rules_impl(
+    repo: &impl SchedulingRepositoryTrait,
+    schedule_id: i32,
+    rules: Vec<AdaptiveRuleRequest>,
+) -> Result<Vec<AdaptiveRule>, CommandError> {
+    let count = rules.len();
+    repo.set_adaptive_rules(schedule_id, rules).map_err(|e| {
+        error!(
+            "set_adaptive_rules error: {} (schedule_id: {}, rules: {})",
+            e, schedule_id, count
+        );
+        e.to_command_error()
+    })
+}
+
+/// Replace quiet hours, quiet-time policy, pause and the notification switch
+#[tauri::command]
+#[specta::specta]
+pub fn update_notification_settings(
+    settings: NotificationSettings,
+    state: State<AppState>,
+) -> Result<NotificationSettings, CommandError> {
+    store_notification_settings(&state, settings).map_err(|e| {
+        error!("update_notification_settings error: {}", e);
+        e.to_command_error()
+    })
+}
+
+/// Pause all reminders until the given RFC 3339 time
+#[tauri::command]
+#[specta::specta]
+pub fn pause_reminders(
+    until: String,
+    state: State<AppState>,
+) -> Result<NotificationSettings, CommandError> {
+    let current = state.config.lock().notification_settings();
+    paused_until(current, &until, Utc::now())
+        .and_then(|settings| store_notification_settings(&state, settings))
+        .map_err(|e| {
+            error!("pause_reminders error: {} (until: {})", e, until);
+            e.to_command_error()
+        })
+}
+
+/// End a pause started with `pause_reminders`
+#[tauri::command]
+#[specta::specta]
+pub fn resume_reminders(state: State<AppState>) -> Result<NotificationSettings, CommandError> {
+    let settings = NotificationSettings {
+        paused_until: None,
+        ..state.config.lock().notification_settings()
+    };
+    store_notification_settings(&state, settings).map_err(|e| {
+        error!("resume_reminders error: {}", e);
+        e.to_command_error()
+    })
+}
+
+/// Validate and persist notification settings, keeping the previous ones if saving fails
+fn store_notification_settings(
+    state: &AppState,
+    settings: NotificationSettings,
+) -> Result<NotificationSettings, SchedulingError> {
+    settings.validate()?;
+
+    let mut config = state.config.lock();
+    let previous = config.notification_settings();
+    config.set_notification_settings(settings);
+    if let Err(e) = config.save() {
+        config.set_notification_settings(previous);
+        return Err(SchedulingError::Config(e.to_string()));
+    }
+
+    Ok(config.notification_settings())
+}
+
+/// Write all enabled schedules and planned activities to an iCalendar (.ics)
+/// file the user picks in a save dialog.
+/// Returns the number of events written, or None if the dialog was cancelled.
+#[tauri::command]
+#[specta::specta]
+pub async fn export_schedules_ical(
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<Option<u32>, CommandError> {
+    // Build the file first so a failure is reported before the user picks a file
+    let plans = ActivityRepository::new(state.db.clone())
+        .get_planned_activities_with_names()
+        .map_err(|e| {
+            error!("export_schedules_ical error: {}", e);
+            e.to_command_error()
+        })?;
+    let repo = SchedulingRepository::new(state.db.clone());
+    let file = repo
+        .get_schedules(true)
+        .and_then(|schedules| export_calendar(&schedules, &plans, Utc::now()))
+        .map_err(|e| {
+            error!("export_schedules_ical error: {}", e);
+            e.to_command_error()
+        })?;
+
+    let Some(file_path) = app
+        .dialog()
+        .file()
+        .add_filter("iCalendar", &["ics"])
+        .set_file_name("reminders.ics")
+        .blocking_save_file()
+    else {
+        return Ok(None);
+    };
+
+    let path = file_path
+        .into_path()
+        .map_err(|e| SchedulingError::Io(e.to_string()).to_command_error())?;
+    std::fs::write(&path, file.ics).map_err(|e| {
+        error!(
+            "export_schedules_ical error: {} (path: {})",
+            e,
+            path.display()
+        );
+        SchedulingError::Io(format!("{}: {}", path.display(), e)).to_command_error()
+    })?;
+
+    Ok(Some(file.event_count))
+}
+
+/// Create schedules and planned activities from the VEVENTs of an iCalendar
+/// (.ics) file the user picks in an open dialog.
+/// Returns None if the dialog was cancelled.
+#[tauri::command]
+#[specta::specta]
+pub async fn import_schedules_ical(
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<Option<CalendarImportResult>, CommandError> {
+    let Some(file_path) = app
+        .dialog()
+        .file()
+        .add_filter("iCalendar", &["ics"])
+        .blocking_pick_file()
+    else {
+        return Ok(None);
+    };
+
+    let path = file_path
+        .into_path()
+        .map_err(|e| SchedulingError::Io(e.to_string()).to_command_error())?;
+    let repo = SchedulingRepository::new(state.db.clone());
+    std::fs::read_to_string(&path)
+        .map_err(|e| SchedulingError::Io(format!("{}: {}", path.display(), e)))
+        .and_then(|ics| parse_calendar(&ics))
+        .and_then(|calendar| repo.import_calendar(calendar))
+        .map(Some)
+        .map_err(|e| {
+            error!(
+                "import_schedules_ical error: {} (path: {})",
+                e,
+                path.display()
+            );
+            e.to_command_error()
+        })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::features::scheduling::{
-        repository_trait::MockSchedulingRepositoryTrait, ScheduleFrequency, SchedulingError,
-        SchedulingRepositoryTrait,
+        models::ReminderEventType, repository_trait::MockSchedulingRepositoryTrait,
+        ScheduleFrequency, SchedulingError, SchedulingRepositoryTrait,
     };
 
     // ========================================================================
@@ -115,11 +396,17 @@ mod tests {
     #[test]
     fn test_create_schedule_request_validation_valid_time() {
         let request = CreateScheduleRequest {
-            assessment_type_id: 1,
+            assessment_type_id: Some(1),
             frequency: ScheduleFrequency::Daily,
             time_of_day: "09:00".to_string(),
             day_of_week: None,
             day_of_month: None,
+            timezone: None,
+            target: None,
+            rrule: None,
+            dtstart: None,
+            exdates: None,
+            missed_policy: None,
         };
 
         assert!(request.validate().is_ok());
@@ -128,11 +415,17 @@ mod tests {
     #[test]
     fn test_create_schedule_request_validation_invalid_time_format() {
         let request = CreateScheduleRequest {
-            assessment_type_id: 1,
+            assessment_type_id: Some(1),
             frequency: ScheduleFrequency::Daily,
             time_of_day: "9:00".to_string(), // Invalid: needs HH:MM
             day_of_week: None,
             day_of_month: None,
+            timezone: None,
+            target: None,
+            rrule: None,
+            dtstart: None,
+            exdates: None,
+            missed_policy: None,
         };
 
         assert!(request.validate().is_err());
@@ -141,11 +434,17 @@ mod tests {
     #[test]
     fn test_create_schedule_request_validation_invalid_time_hour() {
         let request = CreateScheduleRequest {
-            assessment_type_id: 1,
+            assessment_type_id: Some(1),
             frequency: ScheduleFrequency::Daily,
             time_of_day: "25:00".to_string(), // Hour > 23
             day_of_week: None,
             day_of_month: None,
+            timezone: None,
+            target: None,
+            rrule: None,
+            dtstart: None,
+            exdates: None,
+            missed_policy: None,
         };
 
         assert!(request.validate().is_err());
@@ -154,11 +453,17 @@ mod tests {
     #[test]
     fn test_create_schedule_request_validation_day_of_week_in_range() {
         let request = CreateScheduleRequest {
-            assessment_type_id: 1,
+            assessment_type_id: Some(1),
             frequency: ScheduleFrequency::Weekly,
             time_of_day: "09:00".to_string(),
             day_of_week: Some(3), // Wednesday
             day_of_month: None,
+            timezone: None,
+            target: None,
+            rrule: None,
+            dtstart: None,
+            exdates: None,
+            missed_policy: None,
         };
 
         assert!(request.validate().is_ok());
@@ -167,11 +472,17 @@ mod tests {
     #[test]
     fn test_create_schedule_request_validation_day_of_week_out_of_range() {
         let request = CreateScheduleRequest {
-            assessment_type_id: 1,
+            assessment_type_id: Some(1),
             frequency: ScheduleFrequency::Weekly,
             time_of_day: "09:00".to_string(),
             day_of_week: Some(7), // Invalid: 0-6 only
             day_of_month: None,
+            timezone: None,
+            target: None,
+            rrule: None,
+            dtstart: None,
+            exdates: None,
+            missed_policy: None,
         };
 
         assert!(request.validate().is_err());
@@ -180,11 +491,17 @@ mod tests {
     #[test]
     fn test_create_schedule_request_validation_day_of_month_in_range() {
         let request = CreateScheduleRequest {
-            assessment_type_id: 1,
+            assessment_type_id: Some(1),
             frequency: ScheduleFrequency::Monthly,
             time_of_day: "09:00".to_string(),
             day_of_week: None,
             day_of_month: Some(15),
+            timezone: None,
+            target: None,
+            rrule: None,
+            dtstart: None,
+            exdates: None,
+            missed_policy: None,
         };
 
         assert!(request.validate().is_ok());
@@ -193,11 +510,17 @@ mod tests {
     #[test]
     fn test_create_schedule_request_validation_day_of_month_out_of_range() {
         let request = CreateScheduleRequest {
-            assessment_type_id: 1,
+            assessment_type_id: Some(1),
             frequency: ScheduleFrequency::Monthly,
             time_of_day: "09:00".to_string(),
             day_of_week: None,
             day_of_month: Some(32), // Invalid: 1-31 only
+            timezone: None,
+            target: None,
+            rrule: None,
+            dtstart: None,
+            exdates: None,
+            missed_policy: None,
         };
 
         assert!(request.validate().is_err());
@@ -206,11 +529,17 @@ mod tests {
     #[test]
     fn test_create_schedule_request_validation_weekly_requires_day_of_week() {
         let request = CreateScheduleRequest {
-            assessment_type_id: 1,
+            assessment_type_id: Some(1),
             frequency: ScheduleFrequency::Weekly,
             time_of_day: "09:00".to_string(),
             day_of_week: None, // Missing required field
             day_of_month: None,
+            timezone: None,
+            target: None,
+            rrule: None,
+            dtstart: None,
+            exdates: None,
+            missed_policy: None,
         };
 
         assert!(request.validate().is_err());
@@ -219,11 +548,17 @@ mod tests {
     #[test]
     fn test_create_schedule_request_validation_monthly_requires_day_of_month() {
         let request = CreateScheduleRequest {
-            assessment_type_id: 1,
+            assessment_type_id: Some(1),
             frequency: ScheduleFrequency::Monthly,
             time_of_day: "09:00".to_string(),
             day_of_week: None,
             day_of_month: None, // Missing required field
+            timezone: None,
+            target: None,
+            rrule: None,
+            dtstart: None,
+            exdates: None,
+            missed_policy: None,
         };
 
         assert!(request.validate().is_err());
@@ -237,6 +572,12 @@ mod tests {
             day_of_week: Some(2),
             day_of_month: Some(10),
             enabled: Some(false),
+            timezone: None,
+            target: None,
+            rrule: None,
+            dtstart: None,
+            exdates: None,
+            missed_policy: None,
         };
 
         assert!(request.validate().is_ok());
@@ -250,6 +591,12 @@ mod tests {
             day_of_week: None,
             day_of_month: None,
             enabled: None,
+            timezone: None,
+            target: None,
+            rrule: None,
+            dtstart: None,
+            exdates: None,
+            missed_policy: None,
         };
 
         assert!(request.validate().is_err());
@@ -277,11 +624,17 @@ mod tests {
             .returning(|_| Err(SchedulingError::InvalidTimeFormat("25:00".to_string())));
 
         let request = CreateScheduleRequest {
-            assessment_type_id: 1,
+            assessment_type_id: Some(1),
             frequency: ScheduleFrequency::Daily,
             time_of_day: "25:00".to_string(),
             day_of_week: None,
             day_of_month: None,
+            timezone: None,
+            target: None,
+            rrule: None,
+            dtstart: None,
+            exdates: None,
+            missed_policy: None,
         };
 
         let result = create_schedule_with_trait(&mock_repo, request);
@@ -300,11 +653,17 @@ mod tests {
             .returning(|_| Err(SchedulingError::NotFound(999)));
 
         let request = CreateScheduleRequest {
-            assessment_type_id: 999,
+            assessment_type_id: Some(999),
             frequency: ScheduleFrequency::Daily,
             time_of_day: "09:00".to_string(),
             day_of_week: None,
             day_of_month: None,
+            timezone: None,
+            target: None,
+            rrule: None,
+            dtstart: None,
+            exdates: None,
+            missed_policy: None,
         };
 
         let result = create_schedule_with_trait(&mock_repo, request);
@@ -337,6 +696,12 @@ mod tests {
             day_of_week: Some(1),
             day_of_month: None,
             enabled: None,
+            timezone: None,
+            target: None,
+            rrule: None,
+            dtstart: None,
+            exdates: None,
+            missed_policy: None,
         };
 
         let result = update_schedule_with_trait(&mock_repo, 999, request);
@@ -396,11 +761,17 @@ mod tests {
             .returning(|_| Err(SchedulingError::Database(rusqlite::Error::InvalidQuery)));
 
         let request = CreateScheduleRequest {
-            assessment_type_id: 1,
+            assessment_type_id: Some(1),
             frequency: ScheduleFrequency::Daily,
             time_of_day: "09:00".to_string(),
             day_of_week: None,
             day_of_month: None,
+            timezone: None,
+            target: None,
+            rrule: None,
+            dtstart: None,
+            exdates: None,
+            missed_policy: None,
         };
 
         let result = create_schedule_with_trait(&mock_repo, request);
@@ -416,11 +787,17 @@ mod tests {
     #[test]
     fn test_daily_schedule_no_day_requirements() {
         let request = CreateScheduleRequest {
-            assessment_type_id: 1,
+            assessment_type_id: Some(1),
             frequency: ScheduleFrequency::Daily,
             time_of_day: "09:00".to_string(),
             day_of_week: None,
             day_of_month: None,
+            timezone: None,
+            target: None,
+            rrule: None,
+            dtstart: None,
+            exdates: None,
+            missed_policy: None,
         };
 
         // Daily schedule should not require day_of_week or day_of_month
@@ -430,20 +807,32 @@ mod tests {
     #[test]
     fn test_weekly_schedule_requires_day_of_week() {
         let valid_request = CreateScheduleRequest {
-            assessment_type_id: 1,
+            assessment_type_id: Some(1),
             frequency: ScheduleFrequency::Weekly,
             time_of_day: "09:00".to_string(),
             day_of_week: Some(1), // Monday
             day_of_month: None,
+            timezone: None,
+            target: None,
+            rrule: None,
+            dtstart: None,
+            exdates: None,
+            missed_policy: None,
         };
         assert!(valid_request.validate().is_ok());
 
         let invalid_request = CreateScheduleRequest {
-            assessment_type_id: 1,
+            assessment_type_id: Some(1),
             frequency: ScheduleFrequency::Weekly,
             time_of_day: "09:00".to_string(),
             day_of_week: None, // Missing required field
             day_of_month: None,
+            timezone: None,
+            target: None,
+            rrule: None,
+            dtstart: None,
+            exdates: None,
+            missed_policy: None,
         };
         assert!(invalid_request.validate().is_err());
     }
@@ -451,20 +840,32 @@ mod tests {
     #[test]
     fn test_monthly_schedule_requires_day_of_month() {
         let valid_request = CreateScheduleRequest {
-            assessment_type_id: 1,
+            assessment_type_id: Some(1),
             frequency: ScheduleFrequency::Monthly,
             time_of_day: "09:00".to_string(),
             day_of_week: None,
             day_of_month: Some(15), // 15th of month
+            timezone: None,
+            target: None,
+            rrule: None,
+            dtstart: None,
+            exdates: None,
+            missed_policy: None,
         };
         assert!(valid_request.validate().is_ok());
 
         let invalid_request = CreateScheduleRequest {
-            assessment_type_id: 1,
+            assessment_type_id: Some(1),
             frequency: ScheduleFrequency::Monthly,
             time_of_day: "09:00".to_string(),
             day_of_week: None,
             day_of_month: None, // Missing required field
+            timezone: None,
+            target: None,
+            rrule: None,
+            dtstart: None,
+            exdates: None,
+            missed_policy: None,
         };
         assert!(invalid_request.validate().is_err());
     }
@@ -472,20 +873,32 @@ mod tests {
     #[test]
     fn test_biweekly_schedule_requires_day_of_week() {
         let valid_request = CreateScheduleRequest {
-            assessment_type_id: 1,
+            assessment_type_id: Some(1),
             frequency: ScheduleFrequency::Biweekly,
             time_of_day: "09:00".to_string(),
             day_of_week: Some(3), // Wednesday
             day_of_month: None,
+            timezone: None,
+            target: None,
+            rrule: None,
+            dtstart: None,
+            exdates: None,
+            missed_policy: None,
         };
         assert!(valid_request.validate().is_ok());
 
         let invalid_request = CreateScheduleRequest {
-            assessment_type_id: 1,
+            assessment_type_id: Some(1),
             frequency: ScheduleFrequency::Biweekly,
             time_of_day: "09:00".to_string(),
             day_of_week: None, // Missing required field
             day_of_month: None,
+            timezone: None,
+            target: None,
+            rrule: None,
+            dtstart: None,
+            exdates: None,
+            missed_policy: None,
         };
         assert!(invalid_request.validate().is_err());
     }
@@ -498,39 +911,63 @@ mod tests {
     fn test_time_format_edge_cases() {
         // Valid edge cases
         let midnight_request = CreateScheduleRequest {
-            assessment_type_id: 1,
+            assessment_type_id: Some(1),
             frequency: ScheduleFrequency::Daily,
             time_of_day: "00:00".to_string(),
             day_of_week: None,
             day_of_month: None,
+            timezone: None,
+            target: None,
+            rrule: None,
+            dtstart: None,
+            exdates: None,
+            missed_policy: None,
         };
         assert!(midnight_request.validate().is_ok());
 
         let last_minute_request = CreateScheduleRequest {
-            assessment_type_id: 1,
+            assessment_type_id: Some(1),
             frequency: ScheduleFrequency::Daily,
             time_of_day: "23:59".to_string(),
             day_of_week: None,
             day_of_month: None,
+            timezone: None,
+            target: None,
+            rrule: None,
+            dtstart: None,
+            exdates: None,
+            missed_policy: None,
         };
         assert!(last_minute_request.validate().is_ok());
 
         // Invalid edge cases
         let invalid_hour = CreateScheduleRequest {
-            assessment_type_id: 1,
+            assessment_type_id: Some(1),
             frequency: ScheduleFrequency::Daily,
             time_of_day: "24:00".to_string(),
             day_of_week: None,
             day_of_month: None,
+            timezone: None,
+            target: None,
+            rrule: None,
+            dtstart: None,
+            exdates: None,
+            missed_policy: None,
         };
         assert!(invalid_hour.validate().is_err());
 
         let invalid_minute = CreateScheduleRequest {
-            assessment_type_id: 1,
+            assessment_type_id: Some(1),
             frequency: ScheduleFrequency::Daily,
             time_of_day: "09:60".to_string(),
             day_of_week: None,
             day_of_month: None,
+            timezone: None,
+            target: None,
+            rrule: None,
+            dtstart: None,
+            exdates: None,
+            missed_policy: None,
         };
         assert!(invalid_minute.validate().is_err());
     }
@@ -539,50 +976,221 @@ mod tests {
     fn test_day_boundary_values() {
         // day_of_week boundaries (0-6)
         let sunday = CreateScheduleRequest {
-            assessment_type_id: 1,
+            assessment_type_id: Some(1),
             frequency: ScheduleFrequency::Weekly,
             time_of_day: "09:00".to_string(),
             day_of_week: Some(0), // Sunday
             day_of_month: None,
+            timezone: None,
+            target: None,
+            rrule: None,
+            dtstart: None,
+            exdates: None,
+            missed_policy: None,
         };
         assert!(sunday.validate().is_ok());
 
         let saturday = CreateScheduleRequest {
-            assessment_type_id: 1,
+            assessment_type_id: Some(1),
             frequency: ScheduleFrequency::Weekly,
             time_of_day: "09:00".to_string(),
             day_of_week: Some(6), // Saturday
             day_of_month: None,
+            timezone: None,
+            target: None,
+            rrule: None,
+            dtstart: None,
+            exdates: None,
+            missed_policy: None,
         };
         assert!(saturday.validate().is_ok());
 
         // day_of_month boundaries (1-31)
         let first_day = CreateScheduleRequest {
-            assessment_type_id: 1,
+            assessment_type_id: Some(1),
             frequency: ScheduleFrequency::Monthly,
             time_of_day: "09:00".to_string(),
             day_of_week: None,
             day_of_month: Some(1),
+            timezone: None,
+            target: None,
+            rrule: None,
+            dtstart: None,
+            exdates: None,
+            missed_policy: None,
         };
         assert!(first_day.validate().is_ok());
 
         let last_day = CreateScheduleRequest {
-            assessment_type_id: 1,
+            assessment_type_id: Some(1),
             frequency: ScheduleFrequency::Monthly,
             time_of_day: "09:00".to_string(),
             day_of_week: None,
             day_of_month: Some(31),
+            timezone: None,
+            target: None,
+            rrule: None,
+            dtstart: None,
+            exdates: None,
+            missed_policy: None,
         };
         assert!(last_day.validate().is_ok());
 
         // Out of range
         let invalid_day_of_month = CreateScheduleRequest {
-            assessment_type_id: 1,
+            assessment_type_id: Some(1),
             frequency: ScheduleFrequency::Monthly,
             time_of_day: "09:00".to_string(),
             day_of_week: None,
             day_of_month: Some(0), // Invalid: must be 1-31
+            timezone: None,
+            target: None,
+            rrule: None,
+            dtstart: None,
+            exdates: None,
+            missed_policy: None,
         };
         assert!(invalid_day_of_month.validate().is_err());
     }
+
+    // ========================================================================
+    // Unit Tests: Reminder Responses
+    // ========================================================================
+
+    fn reminder_event(event_type: ReminderEventType) -> ReminderEvent {
+        ReminderEvent {
+            id: 2,
+            schedule_id: 1,
+            event_type,
+            occurred_at: "2025-03-01 09:05:00".to_string(),
+            snooze_until: (event_type == ReminderEventType::Snoozed)
+                .then(|| "2025-03-01 09:20:00".to_string()),
+            related_event_id: Some(1),
+            assessment_response_id: None,
+        }
+    }
+
+    #[test]
+    fn test_snooze_reminder_passes_minutes_and_time() {
+        let now = Utc::now();
+        let mut mock_repo = MockSchedulingRepositoryTrait::new();
+
+        mock_repo
+            .expect_snooze_reminder()
+            .with(
+                mockall::predicate::eq(1),
+                mockall::predicate::eq(15),
+                mockall::predicate::eq(now),
+            )
+            .returning(|_, _, _| Ok(reminder_event(ReminderEventType::Snoozed)));
+
+        let event = snooze_reminder_impl(&mock_repo, 1, 15, now).expect("Failed to snooze");
+
+        assert_eq!(event.event_type, ReminderEventType::Snoozed);
+        assert_eq!(event.reminder_id(), 1);
+    }
+
+    #[test]
+    fn test_snooze_reminder_invalid_minutes_is_validation_error() {
+        let mut mock_repo = MockSchedulingRepositoryTrait::new();
+
+        mock_repo
+            .expect_snooze_reminder()
+            .returning(|_, minutes, _| Err(SchedulingError::InvalidSnooze(minutes)));
+
+        let err = snooze_reminder_impl(&mock_repo, 1, 0, Utc::now()).unwrap_err();
+
+        assert_eq!(err.error_type, crate::errors::ErrorType::Validation);
+    }
+
+    #[test]
+    fn test_complete_reminder_twice_is_rejected() {
+        let mut mock_repo = MockSchedulingRepositoryTrait::new();
+
+        mock_repo
+            .expect_complete_reminder()
+            .returning(|_, _| Err(SchedulingError::ReminderAlreadyCompleted(1)));
+
+        let err = complete_reminder_impl(&mock_repo, 1, Utc::now()).unwrap_err();
+
+        assert!(err.message.contains("already completed"));
+    }
+
+    #[test]
+    fn test_dismiss_unknown_reminder_is_not_found() {
+        let mut mock_repo = MockSchedulingRepositoryTrait::new();
+
+        mock_repo
+            .expect_dismiss_reminder()
+            .returning(|id, _| Err(SchedulingError::ReminderEventNotFound(id)));
+
+        let err = dismiss_reminder_impl(&mock_repo, 99, Utc::now()).unwrap_err();
+
+        assert_eq!(err.error_type, crate::errors::ErrorType::NotFound);
+    }
+
+    // ========================================================================
+    // Unit Tests: Adaptive Rules
+    // ========================================================================
+
+    #[test]
+    fn test_set_adaptive_rules_passes_rules_through() {
+        use crate::features::scheduling::adaptive::SeverityComparison;
+        use crate::types::assessment::SeverityLevel;
+
+        let request = AdaptiveRuleRequest {
+            comparison: SeverityComparison::AtLeast,
+            severity_level: SeverityLevel::Moderate,
+            consecutive_count: 1,
+            frequency: ScheduleFrequency::Weekly,
+            day_of_week: Some(1),
+            day_of_month: None,
+        };
+        let mut mock_repo = MockSchedulingRepositoryTrait::new();
+
+        mock_repo
+            .expect_set_adaptive_rules()
+            .with(
+                mockall::predicate::eq(4),
+                mockall::predicate::eq(vec![request.clone()]),
+            )
+            .returning(|schedule_id, rules| {
+                Ok(rules
+                    .into_iter()
+                    .map(|r| AdaptiveRule {
+                        id: 1,
+                        schedule_id,
+                        position: 0,
+                        comparison: r.comparison,
+                        severity_level: r.severity_level,
+                        consecutive_count: r.consecutive_count,
+                        frequency: r.frequency,
+                        day_of_week: r.day_of_week,
+                        day_of_month: r.day_of_month,
+                    })
+                    .collect())
+            });
+
+        let rules =
+            set_adaptive_rules_impl(&mock_repo, 4, vec![request]).expect("Failed to set rules");
+
+        assert_eq!(rules.len(), 1);
+        assert_eq!(rules[0].schedule_id, 4);
+        assert_eq!(rules[0].frequency, ScheduleFrequency::Weekly);
+    }
+
+    #[test]
+    fn test_set_adaptive_rules_on_non_assessment_is_validation_error() {
+        let mut mock_repo = MockSchedulingRepositoryTrait::new();
+
+        mock_repo.expect_set_adaptive_rules().returning(|_, _| {
+            Err(SchedulingError::InvalidAdaptiveRule(
+                "Adaptive rules need an assessment schedule".to_string(),
+            ))
+        });
+
+        let err = set_adaptive_rules_impl(&mock_repo, 1, Vec::new()).unwrap_err();
+
+        assert_eq!(err.error_type, crate::errors::ErrorType::Validation);
+    }
 }
@@ -0,0 +1,1555 @@
+// iCalendar (RFC 5545) export and import of reminder schedules
+//
+// Each enabled schedule becomes a VEVENT with its RRULE, EXDATEs and a VALARM
+// firing at the start of every occurrence. Times are written with a TZID and a
+// matching VTIMEZONE, so calendar apps show reminders at the same wall-clock
+// time the app fires them. Planned activities are written the same way, with
+// their weekdays and interval as a weekly RRULE and their end date as UNTIL.
+// The importer reads VEVENTs back into schedule and plan requests; events
+// written by this app keep their target through X- properties, any other event
+// becomes a habit reminder named after its SUMMARY.
+
+use std::collections::BTreeMap;
+
+use chrono::{
+    DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Offset, TimeZone, Timelike,
+    Utc, Weekday,
+};
+use chrono_tz::{OffsetName, Tz};
+use serde::{Deserialize, Serialize};
+
+use crate::features::activities::{
+    expand_plan_occurrences, parse_plan_date, CreatePlannedActivityRequest, PlannedActivity,
+};
+
+use super::models::{
+    parse_timezone, resolve_local_time, system_timezone, AssessmentSchedule, CreateScheduleRequest,
+    MissedReminderPolicy, ScheduleFrequency, ScheduleTarget, SchedulingError,
+};
+use super::recurrence::{
+    format_dtstart, ByDay, ExceptionDate, RecurrenceFrequency, RecurrenceRule, RecurrenceSet,
+    RecurrenceUntil,
+};
+
+pub const PRODID: &str = "-//Mental Health Tracker//Reminders//EN";
+
+/// Domain part of exported UIDs ("schedule-7@mental-health-tracker")
+const UID_DOMAIN: &str = "mental-health-tracker";
+
+/// Calendar apps show zero-length events poorly, so reminders get a short slot
+const EVENT_DURATION: &str = "PT15M";
+
+/// Rule for a one-off VEVENT without an RRULE
+const SINGLE_OCCURRENCE_RULE: &str = "FREQ=DAILY;COUNT=1";
+
+// Properties that carry what the reminder is for
+const X_TARGET: &str = "X-MHT-TARGET";
+const X_TARGET_ID: &str = "X-MHT-TARGET-ID";
+const X_ASSESSMENT_CODE: &str = "X-MHT-ASSESSMENT-CODE";
+const X_MISSED_POLICY: &str = "X-MHT-MISSED-POLICY";
+const X_TOLERANCE_MINUTES: &str = "X-MHT-TOLERANCE-MINUTES";
+const X_REMINDER_MINUTES: &str = "X-MHT-REMINDER-MINUTES";
+
+/// X-MHT-TARGET of planned activity events
+const PLANNED_ACTIVITY_TARGET: &str = "planned_activity";
+
+/// Content lines longer than this many octets are folded
+const MAX_LINE_OCTETS: usize = 75;
+
+/// An exported calendar
+#[derive(Debug, Clone, PartialEq)]
+pub struct CalendarFile {
+    pub ics: String,
+    pub event_count: u32,
+}
+
+/// A VEVENT that can become a schedule
+#[derive(Debug, Clone)]
+pub struct CalendarEvent {
+    pub uid: Option<String>,
+    pub summary: String,
+    /// Assessment targets are matched by code first, since IDs differ between databases
+    pub assessment_type_code: Option<String>,
+    pub request: CreateScheduleRequest,
+}
+
+/// A VEVENT that was not imported, and why
+#[derive(Serialize, Deserialize, specta::Type, Clone, Debug, PartialEq)]
+pub struct SkippedCalendarEvent {
+    pub uid: Option<String>,
+    pub summary: Option<String>,
+    pub reason: String,
+}
+
+/// A VEVENT that can become a planned activity
+#[derive(Debug)]
+pub struct CalendarPlan {
+    pub uid: Option<String>,
+    pub summary: String,
+    pub request: CreatePlannedActivityRequest,
+}
+
+/// Events read from an iCalendar file
+#[derive(Debug, Default)]
+pub struct ParsedCalendar {
+    pub events: Vec<CalendarEvent>,
+    pub plans: Vec<CalendarPlan>,
+    pub skipped: Vec<SkippedCalendarEvent>,
+}
+
+/// Outcome of importing an iCalendar file
+#[derive(Serialize, Deserialize, specta::Type, Clone, Debug)]
+pub struct CalendarImportResult {
+    pub imported: Vec<AssessmentSchedule>,
+    pub imported_plans: Vec<PlannedActivity>,
+    pub skipped: Vec<SkippedCalendarEvent>,
+}
+
+// ========================================
+// Export
+// ========================================
+
+/// Write `schedules` and `plans` (each with its activity's name) as a
+/// VCALENDAR. Schedules and plans without any occurrence from their start
+/// are left out.
+pub fn export_calendar(
+    schedules: &[AssessmentSchedule],
+    plans: &[(PlannedActivity, String)],
+    now: DateTime<Utc>,
+) -> Result<CalendarFile, SchedulingError> {
+    let mut events = Vec::new();
+    // Earliest event year per timezone, for its VTIMEZONE
+    let mut timezones: BTreeMap<String, (Tz, i32)> = BTreeMap::new();
+    let mut add = |tz: Tz, lines: EventLines| {
+        timezones
+            .entry(tz.name().to_string())
+            .and_modify(|(_, earliest)| *earliest = (*earliest).min(lines.year))
+            .or_insert((tz, lines.year));
+        events.push(lines.lines);
+    };
+
+    for schedule in schedules {
+        let tz = parse_timezone(&schedule.timezone)?;
+        if let Some(lines) = schedule_event(schedule, tz, now)? {
+            add(tz, lines);
+        }
+    }
+    for (plan, activity_name) in plans {
+        let tz = parse_timezone(&plan.timezone)?;
+        if let Some(lines) = plan_event(plan, activity_name, tz, now)? {
+            add(tz, lines);
+        }
+    }
+
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        format!("PRODID:{}", PRODID),
+        "CALSCALE:GREGORIAN".to_string(),
+        "METHOD:PUBLISH".to_string(),
+    ];
+    for (tz, year) in timezones.values() {
+        lines.extend(vtimezone(*tz, *year));
+    }
+    for event in &events {
+        lines.extend(event.iter().cloned());
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    let mut ics = String::new();
+    for line in &lines {
+        fold_line(line, &mut ics);
+    }
+    Ok(CalendarFile {
+        ics,
+        event_count: events.len() as u32,
+    })
+}
+
+struct EventLines {
+    lines: Vec<String>,
+    year: i32,
+}
+
+fn schedule_event(
+    schedule: &AssessmentSchedule,
+    tz: Tz,
+    now: DateTime<Utc>,
+) -> Result<Option<EventLines>, SchedulingError> {
+    let set = schedule.recurrence_set()?;
+
+    // RFC 5545 counts DTSTART as the first occurrence, so the event starts at
+    // the rule's first occurrence rather than the stored (midnight) anchor.
+    // Exceptions are left out here; they are listed as EXDATEs.
+    let rule_only = RecurrenceSet {
+        exdates: Vec::new(),
+        ..set.clone()
+    };
+    let before_start = resolve_local_time(&tz, set.dtstart) - Duration::seconds(1);
+    let Some((first, _)) = rule_only.occurrences_after(tz, before_start).next() else {
+        return Ok(None);
+    };
+
+    // A TZID start needs a UTC UNTIL
+    let mut rule = set.rule.clone();
+    if let Some(RecurrenceUntil::Local(until)) = rule.until {
+        rule.until = Some(RecurrenceUntil::Utc(resolve_local_time(&tz, until)));
+    }
+
+    let tzid = tz.name();
+    let mut lines = vec![
+        "BEGIN:VEVENT".to_string(),
+        format!("UID:schedule-{}@{}", schedule.id, UID_DOMAIN),
+        format!("DTSTAMP:{}", now.format("%Y%m%dT%H%M%SZ")),
+        format!("DTSTART;TZID={}:{}", tzid, format_ical_local(first)),
+        format!("DURATION:{}", EVENT_DURATION),
+        format!("RRULE:{}", rule),
+    ];
+
+    let exdates = exception_times(&rule_only, &set.exdates, tz);
+    if !exdates.is_empty() {
+        let values: Vec<String> = exdates.into_iter().map(format_ical_local).collect();
+        lines.push(format!("EXDATE;TZID={}:{}", tzid, values.join(",")));
+    }
+
+    let summary = event_summary(schedule);
+    lines.push(format!("SUMMARY:{}", escape_text(&summary)));
+    lines.push(format!("{}:{}", X_TARGET, schedule.target.kind()));
+    match &schedule.target {
+        ScheduleTarget::Assessment { assessment_type_id } => {
+            lines.push(format!("{}:{}", X_TARGET_ID, assessment_type_id));
+            if let Some(code) = &schedule.assessment_type_code {
+                lines.push(format!("{}:{}", X_ASSESSMENT_CODE, escape_text(code)));
+            }
+        }
+        ScheduleTarget::Activity { activity_id } => {
+            lines.push(format!("{}:{}", X_TARGET_ID, activity_id))
+        }
+        ScheduleTarget::ActivityGroup { activity_group_id } => {
+            lines.push(format!("{}:{}", X_TARGET_ID, activity_group_id))
+        }
+        ScheduleTarget::MoodCheckin | ScheduleTarget::Habit { .. } => {}
+    }
+    lines.push(format!(
+        "{}:{}",
+        X_MISSED_POLICY,
+        schedule.missed_policy.as_str()
+    ));
+    lines.extend([
+        "BEGIN:VALARM".to_string(),
+        "ACTION:DISPLAY".to_string(),
+        format!("DESCRIPTION:{}", escape_text(&summary)),
+        "TRIGGER:PT0S".to_string(),
+        "END:VALARM".to_string(),
+        "END:VEVENT".to_string(),
+    ]);
+
+    Ok(Some(EventLines {
+        lines,
+        year: first.year(),
+    }))
+}
+
+fn plan_event(
+    plan: &PlannedActivity,
+    activity_name: &str,
+    tz: Tz,
+    now: DateTime<Utc>,
+) -> Result<Option<EventLines>, SchedulingError> {
+    let invalid = |e: crate::features::activities::ActivityError| {
+        SchedulingError::InvalidRecurrence(e.to_string())
+    };
+    let interval = plan.interval_weeks.max(1);
+
+    // The first occurrence is within one interval of the start date
+    let start = parse_plan_date(&plan.start_date).map_err(invalid)?;
+    let Some(first) =
+        expand_plan_occurrences(plan, start, start + Duration::weeks(interval as i64))
+            .map_err(invalid)?
+            .into_iter()
+            .next()
+    else {
+        return Ok(None);
+    };
+
+    // Occurrences run through the end of the last day, local time
+    let until = match plan.end_date.as_deref() {
+        Some(end) => {
+            let last = parse_plan_date(end)
+                .map_err(invalid)?
+                .and_time(NaiveTime::MIN)
+                + Duration::days(1)
+                - Duration::seconds(1);
+            Some(RecurrenceUntil::Utc(resolve_local_time(&tz, last)))
+        }
+        None => None,
+    };
+    let rule = RecurrenceRule {
+        frequency: RecurrenceFrequency::Weekly,
+        interval: interval as u32,
+        count: None,
+        until,
+        by_month: Vec::new(),
+        by_month_day: Vec::new(),
+        by_day: plan
+            .days_of_week
+            .iter()
+            .filter_map(|day| Weekday::try_from(((day + 6) % 7) as u8).ok())
+            .map(|weekday| ByDay {
+                ordinal: None,
+                weekday,
+            })
+            .collect(),
+        by_hour: vec![first.hour()],
+        by_minute: vec![first.minute()],
+        by_set_pos: Vec::new(),
+        // Plans count their weeks from Sunday
+        week_start: Weekday::Sun,
+    };
+
+    let mut lines = vec![
+        "BEGIN:VEVENT".to_string(),
+        format!("UID:plan-{}@{}", plan.id, UID_DOMAIN),
+        format!("DTSTAMP:{}", now.format("%Y%m%dT%H%M%SZ")),
+        format!("DTSTART;TZID={}:{}", tz.name(), format_ical_local(first)),
+        format!("DURATION:{}", EVENT_DURATION),
+        format!("RRULE:{}", rule),
+        format!("SUMMARY:{}", escape_text(activity_name)),
+        format!("{}:{}", X_TARGET, PLANNED_ACTIVITY_TARGET),
+        format!("{}:{}", X_TARGET_ID, plan.activity_id),
+        format!("{}:{}", X_TOLERANCE_MINUTES, plan.tolerance_minutes),
+    ];
+    if plan.reminder_enabled {
+        lines.extend([
+            format!("{}:{}", X_REMINDER_MINUTES, plan.reminder_minutes_before),
+            "BEGIN:VALARM".to_string(),
+            "ACTION:DISPLAY".to_string(),
+            format!("DESCRIPTION:{}", escape_text(activity_name)),
+            format!("TRIGGER:-PT{}M", plan.reminder_minutes_before),
+            "END:VALARM".to_string(),
+        ]);
+    }
+    lines.push("END:VEVENT".to_string());
+
+    Ok(Some(EventLines {
+        lines,
+        year: first.year(),
+    }))
+}
+
+/// Local times of the occurrences removed by `exdates`. A date-only exception
+/// removes every occurrence that day, which iCalendar can only express by
+/// listing them.
+fn exception_times(
+    rule_only: &RecurrenceSet,
+    exdates: &[ExceptionDate],
+    tz: Tz,
+) -> Vec<NaiveDateTime> {
+    let mut times = Vec::new();
+    for exdate in exdates {
+        match exdate {
+            ExceptionDate::DateTime(dt) => times.push(*dt),
+            ExceptionDate::Date(date) => {
+                let Some(midnight) = date.and_hms_opt(0, 0, 0) else {
+                    continue;
+                };
+                let after = resolve_local_time(&tz, midnight) - Duration::seconds(1);
+                times.extend(
+                    rule_only
+                        .occurrences_after(tz, after)
+                        .map(|(local, _)| local)
+                        .take_while(|local| local.date() == *date),
+                );
+            }
+        }
+    }
+    times.sort_unstable();
+    times.dedup();
+    times
+}
+
+fn event_summary(schedule: &AssessmentSchedule) -> String {
+    match &schedule.target {
+        ScheduleTarget::Assessment { .. } => format!("Complete {}", schedule.target_name),
+        ScheduleTarget::MoodCheckin => "Mood check-in".to_string(),
+        ScheduleTarget::Activity { .. } | ScheduleTarget::ActivityGroup { .. } => {
+            format!("Log {}", schedule.target_name)
+        }
+        ScheduleTarget::Habit { text } => text.clone(),
+    }
+}
+
+fn format_ical_local(dt: NaiveDateTime) -> String {
+    dt.format("%Y%m%dT%H%M%S").to_string()
+}
+
+fn utc_offset(tz: Tz, at: DateTime<Utc>) -> i32 {
+    tz.offset_from_utc_datetime(&at.naive_utc())
+        .fix()
+        .local_minus_utc()
+}
+
+fn offset_name(tz: Tz, at: DateTime<Utc>) -> String {
+    let offset = tz.offset_from_utc_datetime(&at.naive_utc());
+    match offset.abbreviation() {
+        Some(name) => name.to_string(),
+        None => format_offset(offset.fix().local_minus_utc()),
+    }
+}
+
+/// UTC offset as "+HHMM" (or "+HHMMSS" when it has seconds)
+fn format_offset(seconds: i32) -> String {
+    let sign = if seconds < 0 { '-' } else { '+' };
+    let seconds = seconds.abs();
+    let (hours, minutes, secs) = (seconds / 3600, seconds % 3600 / 60, seconds % 60);
+    if secs == 0 {
+        format!("{}{:02}{:02}", sign, hours, minutes)
+    } else {
+        format!("{}{:02}{:02}{:02}", sign, hours, minutes, secs)
+    }
+}
+
+/// An offset change, at the wall-clock time it happens before the change
+struct Transition {
+    local: NaiveDateTime,
+    from: i32,
+    to: i32,
+    name: String,
+}
+
+/// Offset changes of `tz` during `year`
+fn transitions_in_year(tz: Tz, year: i32) -> Vec<Transition> {
+    let Some(start) = NaiveDate::from_ymd_opt(year, 1, 1).and_then(|d| d.and_hms_opt(0, 0, 0))
+    else {
+        return Vec::new();
+    };
+    let start = start.and_utc();
+    let end = start + Duration::days(366);
+
+    let mut transitions = Vec::new();
+    let mut day = start;
+    let mut offset = utc_offset(tz, day);
+    while day < end {
+        let next = day + Duration::days(1);
+        let next_offset = utc_offset(tz, next);
+        if next_offset != offset {
+            // Narrow the change down to the second
+            let (mut lo, mut hi) = (day, next);
+            while hi - lo > Duration::seconds(1) {
+                let mid = lo + (hi - lo) / 2;
+                if utc_offset(tz, mid) == offset {
+                    lo = mid;
+                } else {
+                    hi = mid;
+                }
+            }
+            if hi.year() == year {
+                transitions.push(Transition {
+                    local: hi.naive_utc() + Duration::seconds(offset as i64),
+                    from: offset,
+                    to: next_offset,
+                    name: offset_name(tz, hi),
+                });
+            }
+            offset = next_offset;
+        }
+        day = next;
+    }
+    transitions
+}
+
+/// VTIMEZONE for `tz`, with yearly observances derived from its transitions in
+/// `year`. Importers should prefer the TZID, which is always an IANA name.
+fn vtimezone(tz: Tz, year: i32) -> Vec<String> {
+    let mut lines = vec!["BEGIN:VTIMEZONE".to_string(), format!("TZID:{}", tz.name())];
+
+    let transitions = transitions_in_year(tz, year);
+    if let [a, b] = transitions.as_slice() {
+        if a.from == b.to && a.to == b.from {
+            for transition in [a, b] {
+                let kind = if transition.to > transition.from {
+                    "DAYLIGHT"
+                } else {
+                    "STANDARD"
+                };
+                lines.extend(observance(kind, transition));
+            }
+            lines.push("END:VTIMEZONE".to_string());
+            return lines;
+        }
+    }
+
+    // No (regular) daylight saving time: one fixed observance
+    let Some(mid_year) = NaiveDate::from_ymd_opt(year, 7, 1).and_then(|d| d.and_hms_opt(0, 0, 0))
+    else {
+        lines.push("END:VTIMEZONE".to_string());
+        return lines;
+    };
+    let mid_year = mid_year.and_utc();
+    let offset = format_offset(utc_offset(tz, mid_year));
+    lines.extend([
+        "BEGIN:STANDARD".to_string(),
+        "DTSTART:19700101T000000".to_string(),
+        format!("TZOFFSETFROM:{}", offset),
+        format!("TZOFFSETTO:{}", offset),
+        format!("TZNAME:{}", offset_name(tz, mid_year)),
+        "END:STANDARD".to_string(),
+        "END:VTIMEZONE".to_string(),
+    ]);
+    lines
+}
+
+/// A STANDARD/DAYLIGHT block recurring like `transition` every year since 1970
+fn observance(kind: &str, transition: &Transition) -> Vec<String> {
+    let date = transition.local.date();
+    let weekday = date.weekday();
+    let last_day = NaiveDate::from_ymd_opt(date.year(), date.month(), 1)
+        .and_then(|first| first.checked_add_months(chrono::Months::new(1)))
+        .and_then(|next| next.pred_opt())
+        .map(|last| last.day())
+        .unwrap_or(31);
+    // "Last Sunday" and "second Sunday" style rules
+    let ordinal = if date.day() + 7 > last_day {
+        -1
+    } else {
+        (date.day() as i32 - 1) / 7 + 1
+    };
+    let by_day = ByDay {
+        ordinal: Some(ordinal),
+        weekday,
+    };
+    let first = nth_weekday(1970, date.month(), by_day).unwrap_or(date);
+
+    let rule = RecurrenceRule {
+        frequency: RecurrenceFrequency::Yearly,
+        interval: 1,
+        count: None,
+        until: None,
+        by_month: vec![date.month()],
+        by_month_day: Vec::new(),
+        by_day: vec![by_day],
+        by_hour: Vec::new(),
+        by_minute: Vec::new(),
+        by_set_pos: Vec::new(),
+        week_start: Weekday::Mon,
+    };
+
+    vec![
+        format!("BEGIN:{}", kind),
+        format!(
+            "DTSTART:{}",
+            format_ical_local(first.and_time(transition.local.time()))
+        ),
+        format!("RRULE:{}", rule),
+        format!("TZOFFSETFROM:{}", format_offset(transition.from)),
+        format!("TZOFFSETTO:{}", format_offset(transition.to)),
+        format!("TZNAME:{}", escape_text(&transition.name)),
+        format!("END:{}", kind),
+    ]
+}
+
+/// The `ordinal`-th `weekday` of a month (negative counts from the end)
+fn nth_weekday(year: i32, month: u32, by_day: ByDay) -> Option<NaiveDate> {
+    let ordinal = by_day.ordinal?;
+    if ordinal > 0 {
+        NaiveDate::from_weekday_of_month_opt(year, month, by_day.weekday, ordinal as u8)
+    } else {
+        let mut date = NaiveDate::from_ymd_opt(year, month, 1)?
+            .checked_add_months(chrono::Months::new(1))?
+            .pred_opt()?;
+        while date.weekday() != by_day.weekday {
+            date = date.pred_opt()?;
+        }
+        date.checked_sub_signed(Duration::weeks((-ordinal - 1) as i64))
+    }
+}
+
+/// TEXT value escaping (RFC 5545 section 3.3.11)
+fn escape_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            ';' => escaped.push_str("\\;"),
+            ',' => escaped.push_str("\\,"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn unescape_text(text: &str) -> String {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') | Some('N') => unescaped.push('\n'),
+            Some(other) => unescaped.push(other),
+            None => unescaped.push('\\'),
+        }
+    }
+    unescaped
+}
+
+/// Append `line` with CRLF, folded so no physical line exceeds 75 octets
+fn fold_line(line: &str, out: &mut String) {
+    let mut octets = 0;
+    for c in line.chars() {
+        // Continuation lines start with a space, which counts towards the limit
+        if octets + c.len_utf8() > MAX_LINE_OCTETS {
+            out.push_str("\r\n ");
+            octets = 1;
+        }
+        out.push(c);
+        octets += c.len_utf8();
+    }
+    out.push_str("\r\n");
+}
+
+// ========================================
+// Import
+// ========================================
+
+/// A content line: `NAME;PARAM=value:VALUE`
+#[derive(Debug, Clone, PartialEq)]
+struct Property {
+    name: String,
+    params: Vec<(String, String)>,
+    value: String,
+}
+
+impl Property {
+    fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Whether the value is a DATE rather than a DATE-TIME
+    fn is_date(&self) -> bool {
+        self.param("VALUE") == Some("DATE") || !self.value.contains('T')
+    }
+}
+
+/// Join folded lines back together
+fn unfold(ics: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for raw in ics.split('\n') {
+        let raw = raw.strip_suffix('\r').unwrap_or(raw);
+        match (raw.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(continuation), Some(last)) => last.push_str(continuation),
+            _ if raw.is_empty() => {}
+            _ => lines.push(raw.to_string()),
+        }
+    }
+    lines
+}
+
+fn parse_property(line: &str) -> Option<Property> {
+    // The value starts at the first colon outside a quoted parameter value
+    let mut in_quotes = false;
+    let mut split = None;
+    for (i, c) in line.char_indices() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            ':' if !in_quotes => {
+                split = Some(i);
+                break;
+            }
+            _ => {}
+        }
+    }
+    let split = split?;
+    let (head, value) = (&line[..split], &line[split + 1..]);
+
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    in_quotes = false;
+    for c in head.chars() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            ';' if !in_quotes => parts.push(std::mem::take(&mut current)),
+            c => current.push(c),
+        }
+    }
+    parts.push(current);
+
+    let mut parts = parts.into_iter();
+    let name = parts.next()?.trim().to_ascii_uppercase();
+    if name.is_empty() {
+        return None;
+    }
+    let params = parts
+        .filter_map(|param| {
+            let (key, value) = param.split_once('=')?;
+            Some((key.trim().to_ascii_uppercase(), value.to_string()))
+        })
+        .collect();
+
+    Some(Property {
+        name,
+        params,
+        value: value.to_string(),
+    })
+}
+
+/// Read the VEVENTs of an iCalendar file. Events that can't become a schedule
+/// or plan are returned as skipped; only a file that isn't a calendar is an error.
+pub fn parse_calendar(ics: &str) -> Result<ParsedCalendar, SchedulingError> {
+    let mut calendar = ParsedCalendar::default();
+    let mut stack: Vec<String> = Vec::new();
+    let mut seen_calendar = false;
+    let mut event: Option<Vec<Property>> = None;
+
+    for line in unfold(ics) {
+        let Some(property) = parse_property(&line) else {
+            continue;
+        };
+        match property.name.as_str() {
+            "BEGIN" => {
+                let component = property.value.trim().to_ascii_uppercase();
+                if stack.is_empty() && component != "VCALENDAR" {
+                    return Err(SchedulingError::InvalidCalendar(
+                        "File does not start with BEGIN:VCALENDAR".to_string(),
+                    ));
+                }
+                if component == "VCALENDAR" {
+                    seen_calendar = true;
+                }
+                if component == "VEVENT" && stack.len() == 1 {
+                    event = Some(Vec::new());
+                }
+                stack.push(component);
+            }
+            "END" => {
+                let component = property.value.trim().to_ascii_uppercase();
+                if stack.last() != Some(&component) {
+                    return Err(SchedulingError::InvalidCalendar(format!(
+                        "Unexpected END:{}",
+                        component
+                    )));
+                }
+                stack.pop();
+                if component == "VEVENT" && stack.len() == 1 {
+                    if let Some(properties) = event.take() {
+                        let is_plan = find(&properties, X_TARGET)
+                            .is_some_and(|p| p.value.trim() == PLANNED_ACTIVITY_TARGET);
+                        let parsed = if is_plan {
+                            plan_from_properties(&properties).map(|plan| calendar.plans.push(plan))
+                        } else {
+                            event_from_properties(&properties)
+                                .map(|event| calendar.events.push(event))
+                        };
+                        if let Err(reason) = parsed {
+                            calendar.skipped.push(SkippedCalendarEvent {
+                                uid: property_text(&properties, "UID"),
+                                summary: property_text(&properties, "SUMMARY"),
+                                reason,
+                            });
+                        }
+                    }
+                }
+            }
+            _ => {
+                // Properties of nested components (VALARM) are not the event's
+                if stack.len() == 2 && stack[1] == "VEVENT" {
+                    if let Some(properties) = event.as_mut() {
+                        properties.push(property);
+                    }
+                }
+            }
+        }
+    }
+
+    if !seen_calendar {
+        return Err(SchedulingError::InvalidCalendar(
+            "No VCALENDAR found".to_string(),
+        ));
+    }
+    if !stack.is_empty() {
+        return Err(SchedulingError::InvalidCalendar(format!(
+            "Missing END:{}",
+            stack.last().map(String::as_str).unwrap_or_default()
+        )));
+    }
+    Ok(calendar)
+}
+
+fn find<'a>(properties: &'a [Property], name: &str) -> Option<&'a Property> {
+    properties.iter().find(|p| p.name == name)
+}
+
+fn property_text(properties: &[Property], name: &str) -> Option<String> {
+    find(properties, name).map(|p| unescape_text(&p.value))
+}
+
+/// Parse a DATE-TIME value as local time of `tz`; UTC values are converted
+fn parse_event_time(property: &Property, value: &str, tz: Tz) -> Result<NaiveDateTime, String> {
+    let bad = || format!("Invalid {} value '{}'", property.name, value);
+    if let Some(utc) = value.strip_suffix('Z') {
+        let utc = NaiveDateTime::parse_from_str(utc, "%Y%m%dT%H%M%S").map_err(|_| bad())?;
+        return Ok(utc.and_utc().with_timezone(&tz).naive_local());
+    }
+    let local = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S").map_err(|_| bad())?;
+    match property.param("TZID") {
+        // A different zone than the event's: convert through the instant
+        Some(tzid) if tzid != tz.name() => {
+            let other = parse_tzid(tzid)?;
+            Ok(resolve_local_time(&other, local)
+                .with_timezone(&tz)
+                .naive_local())
+        }
+        _ => Ok(local),
+    }
+}
+
+fn parse_tzid(tzid: &str) -> Result<Tz, String> {
+    // Some producers prefix IANA names with a slash ("/Europe/Berlin")
+    parse_timezone(tzid.trim_start_matches('/')).map_err(|_| {
+        format!(
+            "Unknown timezone '{}'; only IANA names such as Europe/Berlin are supported",
+            tzid
+        )
+    })
+}
+
+/// The start of a recurring event
+struct EventStart {
+    /// Explicit timezone of the event, None for floating times
+    timezone: Option<String>,
+    tz: Tz,
+    local: NaiveDateTime,
+    rule: RecurrenceRule,
+}
+
+/// Checks shared by every importable event, and its start and rule
+fn event_start(properties: &[Property]) -> Result<EventStart, String> {
+    if find(properties, "STATUS").is_some_and(|p| p.value.eq_ignore_ascii_case("CANCELLED")) {
+        return Err("Event is cancelled".to_string());
+    }
+    if find(properties, "RECURRENCE-ID").is_some() {
+        return Err("Changes to single occurrences are not supported".to_string());
+    }
+    if find(properties, "RDATE").is_some() {
+        return Err("RDATE is not supported".to_string());
+    }
+
+    let dtstart = find(properties, "DTSTART").ok_or("Event has no DTSTART")?;
+    if dtstart.is_date() {
+        return Err("All-day events have no time to remind at".to_string());
+    }
+    // UTC start times keep UTC; floating times follow the system timezone
+    let timezone = if dtstart.value.ends_with('Z') {
+        Some("UTC".to_string())
+    } else {
+        match dtstart.param("TZID") {
+            Some(tzid) => Some(parse_tzid(tzid)?.name().to_string()),
+            None => None,
+        }
+    };
+    let tz = parse_tzid(&timezone.clone().unwrap_or_else(system_timezone))?;
+    let local = parse_event_time(dtstart, dtstart.value.trim(), tz)?;
+
+    let rrules: Vec<&Property> = properties.iter().filter(|p| p.name == "RRULE").collect();
+    let rule = match rrules.as_slice() {
+        [] => SINGLE_OCCURRENCE_RULE,
+        [rrule] => rrule.value.as_str(),
+        _ => return Err("Events with more than one RRULE are not supported".to_string()),
+    }
+    .parse::<RecurrenceRule>()
+    .map_err(|e| e.to_string())?;
+
+    Ok(EventStart {
+        timezone,
+        tz,
+        local,
+        rule,
+    })
+}
+
+fn event_from_properties(properties: &[Property]) -> Result<CalendarEvent, String> {
+    let uid = property_text(properties, "UID");
+    let summary = property_text(properties, "SUMMARY")
+        .map(|s| s.trim().to_string())
+        .unwrap_or_default();
+
+    let EventStart {
+        timezone,
+        tz,
+        local: start,
+        rule,
+    } = event_start(properties)?;
+
+    let mut exdates = Vec::new();
+    for exdate in properties.iter().filter(|p| p.name == "EXDATE") {
+        for value in exdate.value.split(',').map(str::trim) {
+            let exception = if exdate.param("VALUE") == Some("DATE") || !value.contains('T') {
+                value.parse::<ExceptionDate>().map_err(|e| e.to_string())?
+            } else {
+                ExceptionDate::DateTime(parse_event_time(exdate, value, tz)?)
+            };
+            exdates.push(exception.to_string());
+        }
+    }
+
+    let (target, assessment_type_code) = event_target(properties, &summary)?;
+    let missed_policy = match find(properties, X_MISSED_POLICY) {
+        Some(p) => Some(
+            p.value
+                .trim()
+                .parse::<MissedReminderPolicy>()
+                .map_err(|e| e.to_string())?,
+        ),
+        None => None,
+    };
+
+    let (frequency, time_of_day, day_of_week, day_of_month, rrule) =
+        match simple_schedule(&rule, start) {
+            Some(simple) => (
+                simple.frequency,
+                simple.time_of_day,
+                simple.day_of_week,
+                simple.day_of_month,
+                None,
+            ),
+            None => (
+                ScheduleFrequency::Custom,
+                rule.first_time_of_day(start),
+                None,
+                None,
+                Some(rule.to_string()),
+            ),
+        };
+
+    Ok(CalendarEvent {
+        uid,
+        summary,
+        assessment_type_code,
+        request: CreateScheduleRequest {
+            assessment_type_id: None,
+            target: Some(target),
+            frequency,
+            time_of_day,
+            day_of_week,
+            day_of_month,
+            timezone,
+            rrule,
+            dtstart: Some(format_dtstart(start)),
+            exdates: (!exdates.is_empty()).then_some(exdates),
+            missed_policy,
+        },
+    })
+}
+
+/// A planned activity event, as written by `plan_event`: a weekly rule on
+/// fixed weekdays at one time of day
+fn plan_from_properties(properties: &[Property]) -> Result<CalendarPlan, String> {
+    let uid = property_text(properties, "UID");
+    let summary = property_text(properties, "SUMMARY")
+        .map(|s| s.trim().to_string())
+        .unwrap_or_default();
+
+    let EventStart {
+        timezone,
+        tz,
+        local: start,
+        rule,
+    } = event_start(properties)?;
+    if find(properties, "EXDATE").is_some() {
+        return Err("Planned activities can't skip single occurrences".to_string());
+    }
+    let weekly_on_fixed_days = rule.frequency == RecurrenceFrequency::Weekly
+        && rule.count.is_none()
+        && rule.by_month.is_empty()
+        && rule.by_month_day.is_empty()
+        && rule.by_set_pos.is_empty()
+        && rule.by_hour.len() <= 1
+        && rule.by_minute.len() <= 1
+        && rule.by_day.iter().all(|day| day.ordinal.is_none());
+    if !weekly_on_fixed_days {
+        return Err(format!(
+            "Planned activities repeat weekly on fixed days at one time, not '{}'",
+            rule
+        ));
+    }
+
+    let activity_id = find(properties, X_TARGET_ID)
+        .and_then(|p| p.value.trim().parse().ok())
+        .ok_or_else(|| format!("Planned activity has no valid {}", X_TARGET_ID))?;
+    let minutes = |name: &str| -> Result<Option<i32>, String> {
+        find(properties, name)
+            .map(|p| {
+                p.value
+                    .trim()
+                    .parse()
+                    .map_err(|_| format!("Invalid {} value '{}'", name, p.value))
+            })
+            .transpose()
+    };
+    let reminder_minutes_before = minutes(X_REMINDER_MINUTES)?;
+
+    // Parts the rule leaves out default to the start, as for schedules
+    let hour = rule.by_hour.first().copied().unwrap_or(start.hour());
+    let minute = rule.by_minute.first().copied().unwrap_or(start.minute());
+    let mut days_of_week: Vec<i32> = rule
+        .by_day
+        .iter()
+        .map(|day| day.weekday.num_days_from_sunday() as i32)
+        .collect();
+    if days_of_week.is_empty() {
+        days_of_week.push(start.weekday().num_days_from_sunday() as i32);
+    }
+    let end_date = rule.until.map(|until| match until {
+        RecurrenceUntil::Utc(utc) => utc.with_timezone(&tz).date_naive(),
+        RecurrenceUntil::Local(local) => local.date(),
+    });
+
+    Ok(CalendarPlan {
+        uid,
+        summary,
+        request: CreatePlannedActivityRequest {
+            activity_id,
+            days_of_week,
+            time_of_day: format!("{:02}:{:02}", hour, minute),
+            timezone,
+            interval_weeks: Some(rule.interval as i32),
+            tolerance_minutes: minutes(X_TOLERANCE_MINUTES)?,
+            start_date: start.date().format("%Y-%m-%d").to_string(),
+            end_date: end_date.map(|date| date.format("%Y-%m-%d").to_string()),
+            reminder_enabled: reminder_minutes_before.is_some(),
+            reminder_minutes_before,
+        },
+    })
+}
+
+/// What an event reminds of: the exported target, or a habit named after its summary
+fn event_target(
+    properties: &[Property],
+    summary: &str,
+) -> Result<(ScheduleTarget, Option<String>), String> {
+    let Some(kind) = find(properties, X_TARGET) else {
+        return Ok((
+            ScheduleTarget::Habit {
+                text: summary.to_string(),
+            },
+            None,
+        ));
+    };
+    let kind = kind.value.trim();
+    let target_id = || -> Result<i32, String> {
+        find(properties, X_TARGET_ID)
+            .and_then(|p| p.value.trim().parse().ok())
+            .ok_or_else(|| format!("{} reminder has no valid {}", kind, X_TARGET_ID))
+    };
+
+    match kind {
+        "assessment" => {
+            let code = property_text(properties, X_ASSESSMENT_CODE);
+            // The code is resolved against the database; the ID is the fallback
+            let assessment_type_id = match code {
+                Some(_) => target_id().unwrap_or_default(),
+                None => target_id()?,
+            };
+            Ok((ScheduleTarget::Assessment { assessment_type_id }, code))
+        }
+        "mood_checkin" => Ok((ScheduleTarget::MoodCheckin, None)),
+        "activity" => Ok((
+            ScheduleTarget::Activity {
+                activity_id: target_id()?,
+            },
+            None,
+        )),
+        "activity_group" => Ok((
+            ScheduleTarget::ActivityGroup {
+                activity_group_id: target_id()?,
+            },
+            None,
+        )),
+        "habit" => Ok((
+            ScheduleTarget::Habit {
+                text: summary.to_string(),
+            },
+            None,
+        )),
+        other => Err(format!("Unknown reminder target '{}'", other)),
+    }
+}
+
+/// Simple frequency fields equivalent to a rule
+#[derive(Debug, Clone, PartialEq)]
+struct SimpleSchedule {
+    frequency: ScheduleFrequency,
+    time_of_day: String,
+    day_of_week: Option<i32>,
+    day_of_month: Option<i32>,
+}
+
+/// The daily/weekly/biweekly/monthly schedule `rule` describes, if any.
+///
+/// Parts the rule leaves out default to `dtstart` as RFC 5545 specifies, so
+/// "FREQ=WEEKLY" starting on a Monday at 09:00 is a weekly Monday 09:00 schedule.
+fn simple_schedule(rule: &RecurrenceRule, dtstart: NaiveDateTime) -> Option<SimpleSchedule> {
+    let mut rule = rule.clone();
+    if rule.by_hour.is_empty() {
+        rule.by_hour = vec![dtstart.hour()];
+    }
+    if rule.by_minute.is_empty() {
+        rule.by_minute = vec![dtstart.minute()];
+    }
+    if rule.frequency == RecurrenceFrequency::Weekly && rule.by_day.is_empty() {
+        rule.by_day = vec![ByDay {
+            ordinal: None,
+            weekday: dtstart.weekday(),
+        }];
+    }
+    if rule.frequency == RecurrenceFrequency::Monthly
+        && rule.by_day.is_empty()
+        && rule.by_month_day.is_empty()
+    {
+        rule.by_month_day = vec![dtstart.day() as i32];
+    }
+
+    let ([hour], [minute]) = (rule.by_hour.as_slice(), rule.by_minute.as_slice()) else {
+        return None;
+    };
+    let time_of_day = format!("{:02}:{:02}", hour, minute);
+    let day_of_week = match rule.by_day.as_slice() {
+        [day] => Some(day.weekday.num_days_from_sunday() as i32),
+        _ => None,
+    };
+    // Days past the 28th are stored as "the last of 28..=N"
+    let day_of_month = match (rule.by_month_day.as_slice(), rule.by_set_pos.as_slice()) {
+        ([day], []) => Some(*day),
+        (days, [-1]) => days.last().copied(),
+        _ => None,
+    };
+
+    let frequency = match (rule.frequency, rule.interval) {
+        (RecurrenceFrequency::Daily, 1) => ScheduleFrequency::Daily,
+        (RecurrenceFrequency::Weekly, 1) => ScheduleFrequency::Weekly,
+        (RecurrenceFrequency::Weekly, 2) => ScheduleFrequency::Biweekly,
+        (RecurrenceFrequency::Monthly, 1) => ScheduleFrequency::Monthly,
+        _ => return None,
+    };
+    let candidate =
+        RecurrenceRule::from_frequency(&frequency, &time_of_day, day_of_week, day_of_month).ok()?;
+    (candidate == rule).then_some(SimpleSchedule {
+        frequency,
+        time_of_day,
+        day_of_week,
+        day_of_month,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn local(s: &str) -> NaiveDateTime {
+        super::super::recurrence::parse_dtstart(s).expect("Invalid test date")
+    }
+
+    fn utc(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s)
+            .expect("Invalid test timestamp")
+            .with_timezone(&Utc)
+    }
+
+    fn schedule(
+        rrule: &str,
+        dtstart: &str,
+        exdates: &[&str],
+        timezone: &str,
+    ) -> AssessmentSchedule {
+        AssessmentSchedule {
+            id: 7,
+            target: ScheduleTarget::Assessment {
+                assessment_type_id: 1,
+            },
+            target_name: "PHQ-9, Depression".to_string(),
+            assessment_type_id: Some(1),
+            assessment_type_code: Some("PHQ9".to_string()),
+            assessment_type_name: Some("PHQ-9, Depression".to_string()),
+            frequency: ScheduleFrequency::Custom,
+            time_of_day: "09:00".to_string(),
+            day_of_week: None,
+            day_of_month: None,
+            rrule: rrule.to_string(),
+            dtstart: dtstart.to_string(),
+            exdates: exdates.iter().map(|e| e.to_string()).collect(),
+            timezone: timezone.to_string(),
+            enabled: true,
+            missed_policy: MissedReminderPolicy::FireAll,
+            last_triggered_at: None,
+            created_at: "2025-01-01 00:00:00".to_string(),
+            updated_at: "2025-01-01 00:00:00".to_string(),
+        }
+    }
+
+    fn plan(days_of_week: Vec<i32>, end_date: Option<&str>, timezone: &str) -> PlannedActivity {
+        PlannedActivity {
+            id: 3,
+            activity_id: 12,
+            days_of_week,
+            time_of_day: "07:30".to_string(),
+            timezone: timezone.to_string(),
+            interval_weeks: 2,
+            tolerance_minutes: 45,
+            start_date: "2025-01-08".to_string(), // Wednesday
+            end_date: end_date.map(str::to_string),
+            reminder_enabled: true,
+            reminder_minutes_before: 10,
+            last_reminded_at: None,
+            created_at: "2025-01-01 00:00:00".to_string(),
+            deleted_at: None,
+        }
+    }
+
+    fn wrap(event: &str) -> String {
+        format!(
+            "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nPRODID:-//Test//EN\r\n{}END:VCALENDAR\r\n",
+            event
+        )
+    }
+
+    // ========================================
+    // Text and Line Tests
+    // ========================================
+
+    #[test]
+    fn test_text_escaping_round_trips() {
+        let text = "Walk, stretch; then rest\\relax\nagain";
+        let escaped = escape_text(text);
+        assert_eq!(escaped, "Walk\\, stretch\\; then rest\\\\relax\\nagain");
+        assert_eq!(unescape_text(&escaped), text);
+    }
+
+    #[test]
+    fn test_long_lines_fold_and_unfold() {
+        let line = format!("SUMMARY:{}", "Tägliche Übung ".repeat(10));
+        let mut folded = String::new();
+        fold_line(&line, &mut folded);
+
+        assert!(folded
+            .split("\r\n")
+            .all(|physical| physical.len() <= MAX_LINE_OCTETS));
+        assert_eq!(unfold(&folded), vec![line]);
+    }
+
+    #[test]
+    fn test_parse_property_with_quoted_parameter() {
+        let property =
+            parse_property("DTSTART;TZID=\"America/New_York\";X-A=\"a:b\":20250106T183000")
+                .expect("Failed to parse property");
+        assert_eq!(property.name, "DTSTART");
+        assert_eq!(property.param("TZID"), Some("America/New_York"));
+        assert_eq!(property.param("X-A"), Some("a:b"));
+        assert_eq!(property.value, "20250106T183000");
+    }
+
+    // ========================================
+    // Export Tests
+    // ========================================
+
+    #[test]
+    fn test_export_starts_event_at_first_occurrence() {
+        let schedule = schedule(
+            "FREQ=WEEKLY;BYDAY=MO,WE,FR;BYHOUR=8,20;BYMINUTE=0",
+            "2025-01-01T00:00:00",
+            &["2025-01-06", "2025-01-08T20:00:00"],
+            "Europe/Berlin",
+        );
+        let file = export_calendar(&[schedule], &[], utc("2025-01-01T12:00:00Z"))
+            .expect("Failed to export");
+        let lines = unfold(&file.ics);
+
+        assert_eq!(file.event_count, 1);
+        assert!(lines.contains(&"DTSTART;TZID=Europe/Berlin:20250101T080000".to_string()));
+        assert!(
+            lines.contains(&"RRULE:FREQ=WEEKLY;BYDAY=MO,WE,FR;BYHOUR=8,20;BYMINUTE=0".to_string())
+        );
+        // The date-only exception becomes both occurrences of that Monday
+        assert!(lines.contains(
+            &"EXDATE;TZID=Europe/Berlin:20250106T080000,20250106T200000,20250108T200000"
+                .to_string()
+        ));
+        assert!(lines.contains(&"SUMMARY:Complete PHQ-9\\, Depression".to_string()));
+        assert!(lines.contains(&"X-MHT-ASSESSMENT-CODE:PHQ9".to_string()));
+        assert!(lines.contains(&"TRIGGER:PT0S".to_string()));
+    }
+
+    #[test]
+    fn test_export_converts_local_until_to_utc() {
+        let schedule = schedule(
+            "FREQ=DAILY;UNTIL=20250301T235959;BYHOUR=9;BYMINUTE=0",
+            "2025-01-01T00:00:00",
+            &[],
+            "America/New_York",
+        );
+        let file = export_calendar(&[schedule], &[], utc("2025-01-01T12:00:00Z"))
+            .expect("Failed to export");
+
+        assert!(unfold(&file.ics)
+            .contains(&"RRULE:FREQ=DAILY;UNTIL=20250302T045959Z;BYHOUR=9;BYMINUTE=0".to_string()));
+    }
+
+    #[test]
+    fn test_export_writes_plans_as_weekly_rules() {
+        let plans = [(
+            plan(vec![2, 4], Some("2025-03-31"), "Europe/Berlin"),
+            "Yoga".to_string(),
+        )];
+        let file =
+            export_calendar(&[], &plans, utc("2025-01-01T12:00:00Z")).expect("Failed to export");
+        let lines = unfold(&file.ics);
+
+        assert_eq!(file.event_count, 1);
+        assert!(lines.contains(&"UID:plan-3@mental-health-tracker".to_string()));
+        // Starts on the first Thursday, not on the (Wednesday) start date
+        assert!(lines.contains(&"DTSTART;TZID=Europe/Berlin:20250109T073000".to_string()));
+        assert!(lines.contains(
+            &"RRULE:FREQ=WEEKLY;INTERVAL=2;UNTIL=20250331T215959Z;BYDAY=TU,TH;BYHOUR=7;BYMINUTE=30;WKST=SU"
+                .to_string()
+        ));
+        assert!(lines.contains(&"SUMMARY:Yoga".to_string()));
+        assert!(lines.contains(&"X-MHT-TARGET:planned_activity".to_string()));
+        assert!(lines.contains(&"TRIGGER:-PT10M".to_string()));
+        assert!(lines.contains(&"BEGIN:VTIMEZONE".to_string()));
+    }
+
+    #[test]
+    fn test_exported_plan_imports_back() {
+        let original = plan(vec![0, 5], Some("2025-06-30"), "America/New_York");
+        let file = export_calendar(
+            &[],
+            &[(original.clone(), "Run".to_string())],
+            utc("2025-01-01T00:00:00Z"),
+        )
+        .expect("Failed to export");
+        let calendar = parse_calendar(&file.ics).expect("Failed to parse calendar");
+        assert!(calendar.events.is_empty() && calendar.skipped.is_empty());
+
+        let request = &calendar.plans[0].request;
+        assert_eq!(calendar.plans[0].summary, "Run");
+        assert_eq!(request.activity_id, 12);
+        assert_eq!(request.days_of_week, vec![0, 5]);
+        assert_eq!(request.time_of_day, "07:30");
+        assert_eq!(request.timezone.as_deref(), Some("America/New_York"));
+        assert_eq!(request.interval_weeks, Some(2));
+        assert_eq!(request.tolerance_minutes, Some(45));
+        assert_eq!(request.end_date.as_deref(), Some("2025-06-30"));
+        assert!(request.reminder_enabled);
+        assert_eq!(request.reminder_minutes_before, Some(10));
+
+        // Same occurrences, even though the start moved to the first one
+        let imported = PlannedActivity {
+            days_of_week: request.days_of_week.clone(),
+            start_date: request.start_date.clone(),
+            end_date: request.end_date.clone(),
+            ..original.clone()
+        };
+        let (from, to) = (local("2025-01-01").date(), local("2025-12-31").date());
+        assert_eq!(
+            expand_plan_occurrences(&imported, from, to).unwrap(),
+            expand_plan_occurrences(&original, from, to).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_import_skips_plans_that_are_not_weekly() {
+        let calendar = parse_calendar(&wrap(
+            "BEGIN:VEVENT\r\nUID:monthly\r\nDTSTART;TZID=UTC:20250106T070000\r\n\
+             RRULE:FREQ=MONTHLY;BYDAY=1MO\r\n\
+             X-MHT-TARGET:planned_activity\r\nX-MHT-TARGET-ID:1\r\nEND:VEVENT\r\n",
+        ))
+        .expect("Failed to parse calendar");
+
+        assert!(calendar.plans.is_empty());
+        assert_eq!(calendar.skipped[0].uid.as_deref(), Some("monthly"));
+        assert!(calendar.skipped[0].reason.contains("weekly"));
+    }
+
+    #[test]
+    fn test_vtimezone_describes_daylight_saving_rules() {
+        let lines = vtimezone(chrono_tz::Europe::Berlin, 2025);
+        let expected = [
+            "BEGIN:DAYLIGHT",
+            "DTSTART:19700329T020000",
+            "RRULE:FREQ=YEARLY;BYMONTH=3;BYDAY=-1SU",
+            "TZOFFSETFROM:+0100",
+            "TZOFFSETTO:+0200",
+            "TZNAME:CEST",
+            "END:DAYLIGHT",
+            "BEGIN:STANDARD",
+            "DTSTART:19701025T030000",
+            "RRULE:FREQ=YEARLY;BYMONTH=10;BYDAY=-1SU",
+            "TZOFFSETFROM:+0200",
+            "TZOFFSETTO:+0100",
+            "TZNAME:CET",
+            "END:STANDARD",
+        ];
+        assert_eq!(&lines[2..lines.len() - 1], expected);
+
+        let new_york = vtimezone(chrono_tz::America::New_York, 2025);
+        assert!(new_york.contains(&"RRULE:FREQ=YEARLY;BYMONTH=3;BYDAY=2SU".to_string()));
+        assert!(new_york.contains(&"RRULE:FREQ=YEARLY;BYMONTH=11;BYDAY=1SU".to_string()));
+
+        let tokyo = vtimezone(chrono_tz::Asia::Tokyo, 2025);
+        assert!(tokyo.contains(&"TZOFFSETTO:+0900".to_string()));
+        assert!(!tokyo.contains(&"BEGIN:DAYLIGHT".to_string()));
+    }
+
+    // ========================================
+    // Import Tests
+    // ========================================
+
+    #[test]
+    fn test_import_recognizes_simple_schedules() {
+        let calendar = parse_calendar(&wrap(
+            "BEGIN:VEVENT\r\nUID:a\r\nSUMMARY:Stretch\r\n\
+             DTSTART;TZID=America/New_York:20250106T183000\r\n\
+             RRULE:FREQ=WEEKLY;INTERVAL=2\r\nEND:VEVENT\r\n",
+        ))
+        .expect("Failed to parse calendar");
+
+        let request = &calendar.events[0].request;
+        assert_eq!(request.frequency, ScheduleFrequency::Biweekly);
+        assert_eq!(request.time_of_day, "18:30");
+        assert_eq!(request.day_of_week, Some(1));
+        assert_eq!(request.rrule, None);
+        assert_eq!(request.timezone.as_deref(), Some("America/New_York"));
+        assert_eq!(request.dtstart.as_deref(), Some("2025-01-06T18:30:00"));
+        assert_eq!(
+            request.target,
+            Some(ScheduleTarget::Habit {
+                text: "Stretch".to_string()
+            })
+        );
+
+        let monthly = simple_schedule(
+            &"FREQ=MONTHLY;BYMONTHDAY=28,29,30,31;BYSETPOS=-1;BYHOUR=9;BYMINUTE=0"
+                .parse()
+                .expect("Failed to parse rule"),
+            local("2025-01-01"),
+        )
+        .expect("Expected a monthly schedule");
+        assert_eq!(monthly.frequency, ScheduleFrequency::Monthly);
+        assert_eq!(monthly.day_of_month, Some(31));
+    }
+
+    #[test]
+    fn test_import_keeps_other_rules_as_custom() {
+        let calendar = parse_calendar(&wrap(
+            "BEGIN:VEVENT\r\nSUMMARY:Review\r\nDTSTART:20250301T070000Z\r\n\
+             RRULE:FREQ=MONTHLY;BYDAY=-1FR\r\n\
+             EXDATE:20250328T070000Z\r\nEXDATE;VALUE=DATE:20250425\r\n\
+             BEGIN:VALARM\r\nTRIGGER:-PT15M\r\nDESCRIPTION:Ignored\r\nEND:VALARM\r\n\
+             END:VEVENT\r\n",
+        ))
+        .expect("Failed to parse calendar");
+
+        let request = &calendar.events[0].request;
+        assert_eq!(request.frequency, ScheduleFrequency::Custom);
+        assert_eq!(request.rrule.as_deref(), Some("FREQ=MONTHLY;BYDAY=-1FR"));
+        assert_eq!(request.timezone.as_deref(), Some("UTC"));
+        assert_eq!(request.time_of_day, "07:00");
+        assert_eq!(
+            request.exdates,
+            Some(vec![
+                "2025-03-28T07:00:00".to_string(),
+                "2025-04-25".to_string()
+            ])
+        );
+    }
+
+    #[test]
+    fn test_import_skips_events_it_cannot_schedule() {
+        let calendar = parse_calendar(&wrap(
+            "BEGIN:VEVENT\r\nUID:all-day\r\nDTSTART;VALUE=DATE:20250101\r\nEND:VEVENT\r\n\
+             BEGIN:VEVENT\r\nUID:windows\r\nDTSTART;TZID=W. Europe Standard Time:20250101T090000\r\nEND:VEVENT\r\n\
+             BEGIN:VEVENT\r\nUID:hourly\r\nDTSTART:20250101T090000\r\nRRULE:FREQ=HOURLY\r\nEND:VEVENT\r\n\
+             BEGIN:VEVENT\r\nUID:once\r\nSUMMARY:Call\r\nDTSTART:20250101T090000\r\nEND:VEVENT\r\n",
+        ))
+        .expect("Failed to parse calendar");
+
+        let skipped: Vec<&str> = calendar
+            .skipped
+            .iter()
+            .filter_map(|s| s.uid.as_deref())
+            .collect();
+        assert_eq!(skipped, vec!["all-day", "windows", "hourly"]);
+        // A one-off event becomes a single-occurrence custom schedule
+        assert_eq!(
+            calendar.events[0].request.rrule.as_deref(),
+            Some(SINGLE_OCCURRENCE_RULE)
+        );
+    }
+
+    #[test]
+    fn test_import_rejects_files_that_are_not_calendars() {
+        for ics in ["", "hello", "BEGIN:VEVENT\r\nEND:VEVENT\r\n"] {
+            assert!(matches!(
+                parse_calendar(ics),
+                Err(SchedulingError::InvalidCalendar(_))
+            ));
+        }
+        assert!(parse_calendar("BEGIN:VCALENDAR\r\nBEGIN:VEVENT\r\n").is_err());
+    }
+
+    #[test]
+    fn test_exported_calendar_imports_back() {
+        let original = schedule(
+            "FREQ=MONTHLY;INTERVAL=2;BYDAY=1MO,-1FR;BYHOUR=7;BYMINUTE=45",
+            "2025-01-01T00:00:00",
+            &["2025-03-03T07:45:00"],
+            "Asia/Kolkata",
+        );
+        let file = export_calendar(
+            std::slice::from_ref(&original),
+            &[],
+            utc("2025-01-01T00:00:00Z"),
+        )
+        .expect("Failed to export");
+        let calendar = parse_calendar(&file.ics).expect("Failed to parse calendar");
+        assert!(calendar.skipped.is_empty());
+
+        let event = &calendar.events[0];
+        assert_eq!(
+            event.uid.as_deref(),
+            Some("schedule-7@mental-health-tracker")
+        );
+        assert_eq!(event.assessment_type_code.as_deref(), Some("PHQ9"));
+        assert_eq!(
+            event.request.missed_policy,
+            Some(MissedReminderPolicy::FireAll)
+        );
+
+        let imported = RecurrenceSet::parse(
+            event
+                .request
+                .rrule
+                .as_deref()
+                .expect("Expected a custom rule"),
+            event.request.dtstart.as_deref().expect("Expected a start"),
+            event.request.exdates.as_deref().unwrap_or_default(),
+        )
+        .expect("Failed to parse imported recurrence");
+        let tz = chrono_tz::Asia::Kolkata;
+        let after = utc("2024-12-31T00:00:00Z");
+        let expected: Vec<_> = original
+            .recurrence_set()
+            .expect("Failed to parse original recurrence")
+            .occurrences_after(tz, after)
+            .take(20)
+            .collect();
+        let actual: Vec<_> = imported.occurrences_after(tz, after).take(20).collect();
+        assert_eq!(actual, expected);
+    }
+}
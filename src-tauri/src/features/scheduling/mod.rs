@@ -1,17 +1,24 @@
 // Scheduling feature module (User Story 6)
 
+pub mod adaptive;
+pub mod clock;
 pub mod commands;
+pub mod ical;
 pub mod models;
+pub mod notifier;
 pub mod queries;
+pub mod quiet_hours;
+pub mod recurrence;
 pub mod repository;
 pub mod repository_trait;
 pub mod scheduler;
 
 // Re-export commonly used types
 pub use models::{
-    AssessmentSchedule, CreateScheduleRequest, ScheduleFrequency, SchedulingError,
-    UpdateScheduleRequest,
+    AssessmentSchedule, CreateScheduleRequest, MissedReminder, MissedReminderPolicy,
+    PreviewOccurrencesRequest, ReminderAdherence, ReminderEvent, ReminderEventType,
+    ScheduleFrequency, ScheduleOccurrence, SchedulingError, UpdateScheduleRequest,
 };
 pub use repository::SchedulingRepository;
 pub use repository_trait::SchedulingRepositoryTrait;
-pub use scheduler::start_scheduler;
+pub use scheduler::{start_scheduler, Scheduler, SchedulerSettings};
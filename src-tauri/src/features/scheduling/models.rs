@@ -1,8 +1,12 @@
 // Scheduling feature models (User Story 6)
 // T156-T159: Models for assessment scheduling
 
+use super::recurrence::{
+    default_dtstart, format_dtstart, parse_dtstart, ExceptionDate, RecurrenceRule, RecurrenceSet,
+};
 use crate::errors::{CommandError, ErrorType, ToCommandError};
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDateTime, NaiveTime, TimeZone, Utc};
+use chrono_tz::Tz;
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 use thiserror::Error;
@@ -26,6 +30,54 @@ pub enum SchedulingError {
     #[error("Invalid day of month: {0}. Must be 1-31")]
     InvalidDayOfMonth(i32),
 
+    #[error("Invalid timezone: {0}. Must be an IANA name such as \"Europe/Berlin\"")]
+    InvalidTimezone(String),
+
+    #[error("Invalid reminder target: {0}")]
+    InvalidTarget(String),
+
+    #[error("Reminder target not found: {kind} {id}")]
+    TargetNotFound { kind: &'static str, id: i32 },
+
+    #[error("Invalid recurrence rule: {0}")]
+    InvalidRecurrence(String),
+
+    #[error("Schedule has no further occurrences")]
+    RecurrenceEnded,
+
+    #[error("Reminder event not found: {0}")]
+    ReminderEventNotFound(i32),
+
+    #[error("Invalid snooze: {0} minutes. Must be 1-{max}", max = MAX_SNOOZE_MINUTES)]
+    InvalidSnooze(i32),
+
+    #[error("Reminder {0} is already completed")]
+    ReminderAlreadyCompleted(i32),
+
+    #[error("Invalid reminder event: {0}")]
+    InvalidReminderEvent(String),
+
+    #[error("Invalid missed reminder policy: {0}")]
+    InvalidMissedPolicy(String),
+
+    #[error("Invalid quiet hours: {0}")]
+    InvalidQuietHours(String),
+
+    #[error("Invalid adaptive rule: {0}")]
+    InvalidAdaptiveRule(String),
+
+    #[error("Invalid pause: {0}")]
+    InvalidPause(String),
+
+    #[error("Failed to save settings: {0}")]
+    Config(String),
+
+    #[error("Invalid calendar file: {0}")]
+    InvalidCalendar(String),
+
+    #[error("File error: {0}")]
+    Io(String),
+
     #[error("Database error: {0}")]
     Database(#[from] rusqlite::Error),
 
@@ -72,6 +124,93 @@ impl ToCommandError for SchedulingError {
                     }),
                 )
             }
+            SchedulingError::InvalidTimezone(tz) => {
+                CommandError::permanent(self.to_string(), ErrorType::Validation).with_details(
+                    serde_json::json!({
+                        "field": "timezone",
+                        "value": tz
+                    }),
+                )
+            }
+            SchedulingError::InvalidTarget(msg) => {
+                CommandError::permanent(self.to_string(), ErrorType::Validation).with_details(
+                    serde_json::json!({
+                        "field": "target",
+                        "details": msg
+                    }),
+                )
+            }
+            SchedulingError::InvalidRecurrence(msg) => {
+                CommandError::permanent(self.to_string(), ErrorType::Validation).with_details(
+                    serde_json::json!({
+                        "field": "rrule",
+                        "details": msg
+                    }),
+                )
+            }
+            SchedulingError::InvalidSnooze(minutes) => {
+                CommandError::permanent(self.to_string(), ErrorType::Validation).with_details(
+                    serde_json::json!({
+                        "field": "minutes",
+                        "value": minutes
+                    }),
+                )
+            }
+            SchedulingError::ReminderAlreadyCompleted(id) => {
+                CommandError::permanent(self.to_string(), ErrorType::Validation).with_details(
+                    serde_json::json!({
+                        "field": "event_id",
+                        "value": id
+                    }),
+                )
+            }
+            SchedulingError::InvalidMissedPolicy(policy) => {
+                CommandError::permanent(self.to_string(), ErrorType::Validation).with_details(
+                    serde_json::json!({
+                        "field": "missed_policy",
+                        "value": policy
+                    }),
+                )
+            }
+            SchedulingError::InvalidQuietHours(msg) => {
+                CommandError::permanent(self.to_string(), ErrorType::Validation).with_details(
+                    serde_json::json!({
+                        "field": "quiet_hours",
+                        "details": msg
+                    }),
+                )
+            }
+            SchedulingError::InvalidAdaptiveRule(msg) => {
+                CommandError::permanent(self.to_string(), ErrorType::Validation).with_details(
+                    serde_json::json!({
+                        "field": "rules",
+                        "details": msg
+                    }),
+                )
+            }
+            SchedulingError::InvalidPause(msg) => {
+                CommandError::permanent(self.to_string(), ErrorType::Validation).with_details(
+                    serde_json::json!({
+                        "field": "paused_until",
+                        "details": msg
+                    }),
+                )
+            }
+            SchedulingError::InvalidCalendar(msg) => {
+                CommandError::permanent(self.to_string(), ErrorType::Validation).with_details(
+                    serde_json::json!({
+                        "field": "ics",
+                        "details": msg
+                    }),
+                )
+            }
+            SchedulingError::InvalidReminderEvent(msg) => {
+                CommandError::permanent(self.to_string(), ErrorType::Validation).with_details(
+                    serde_json::json!({
+                        "details": msg
+                    }),
+                )
+            }
             SchedulingError::DateParseError(msg) => {
                 CommandError::permanent(self.to_string(), ErrorType::Validation).with_details(
                     serde_json::json!({
@@ -90,6 +229,33 @@ impl ToCommandError for SchedulingError {
                 )
             }
 
+            SchedulingError::TargetNotFound { kind, id } => {
+                CommandError::permanent(self.to_string(), ErrorType::NotFound).with_details(
+                    serde_json::json!({
+                        "resource": kind,
+                        "id": id
+                    }),
+                )
+            }
+
+            SchedulingError::ReminderEventNotFound(id) => {
+                CommandError::permanent(self.to_string(), ErrorType::NotFound).with_details(
+                    serde_json::json!({
+                        "resource": "reminder_event",
+                        "id": id
+                    }),
+                )
+            }
+
+            SchedulingError::RecurrenceEnded => {
+                CommandError::permanent(self.to_string(), ErrorType::NoData)
+            }
+
+            SchedulingError::Config(_) => {
+                CommandError::permanent(self.to_string(), ErrorType::Config)
+            }
+            SchedulingError::Io(_) => CommandError::permanent(self.to_string(), ErrorType::IoError),
+
             // Database lock/transient errors - retryable
             SchedulingError::LockPoisoned => {
                 CommandError::retryable(self.to_string(), ErrorType::LockPoisoned)
@@ -107,6 +273,8 @@ pub enum ScheduleFrequency {
     Weekly,
     Biweekly,
     Monthly,
+    /// Any other pattern, described by the schedule's `rrule`
+    Custom,
 }
 
 impl ScheduleFrequency {
@@ -116,6 +284,7 @@ impl ScheduleFrequency {
             ScheduleFrequency::Weekly => "weekly",
             ScheduleFrequency::Biweekly => "biweekly",
             ScheduleFrequency::Monthly => "monthly",
+            ScheduleFrequency::Custom => "custom",
         }
     }
 }
@@ -129,24 +298,158 @@ impl FromStr for ScheduleFrequency {
             "weekly" => Ok(ScheduleFrequency::Weekly),
             "biweekly" => Ok(ScheduleFrequency::Biweekly),
             "monthly" => Ok(ScheduleFrequency::Monthly),
+            "custom" => Ok(ScheduleFrequency::Custom),
             _ => Err(SchedulingError::InvalidFrequency(s.to_string())),
         }
     }
 }
 
-/// Assessment schedule configuration
+/// What to do with occurrences that fell due while the app was closed
+#[derive(Serialize, Deserialize, specta::Type, Clone, Copy, Debug, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum MissedReminderPolicy {
+    /// One catch-up notification covering all missed occurrences
+    #[default]
+    Coalesce,
+    /// One catch-up notification per missed occurrence
+    FireAll,
+    /// No notification; the occurrences are only recorded as missed
+    Skip,
+}
+
+impl MissedReminderPolicy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MissedReminderPolicy::Coalesce => "coalesce",
+            MissedReminderPolicy::FireAll => "fire_all",
+            MissedReminderPolicy::Skip => "skip",
+        }
+    }
+}
+
+impl FromStr for MissedReminderPolicy {
+    type Err = SchedulingError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "coalesce" => Ok(MissedReminderPolicy::Coalesce),
+            "fire_all" => Ok(MissedReminderPolicy::FireAll),
+            "skip" => Ok(MissedReminderPolicy::Skip),
+            _ => Err(SchedulingError::InvalidMissedPolicy(s.to_string())),
+        }
+    }
+}
+
+/// Maximum length of a free-text habit reminder
+pub const MAX_HABIT_TEXT_LENGTH: usize = 200;
+
+/// What a reminder schedule asks the user to do
+#[derive(Serialize, Deserialize, specta::Type, Clone, Debug, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ScheduleTarget {
+    /// Complete an assessment (PHQ-9, GAD-7, ...)
+    Assessment { assessment_type_id: i32 },
+    /// Record a mood check-in
+    MoodCheckin,
+    /// Log a specific activity ("log your walk")
+    Activity { activity_id: i32 },
+    /// Log any activity of a group
+    ActivityGroup { activity_group_id: i32 },
+    /// Free-text habit reminder, nothing to log
+    Habit { text: String },
+}
+
+impl ScheduleTarget {
+    /// Value stored in `assessment_schedules.target_kind`
+    pub fn kind(&self) -> &'static str {
+        match self {
+            ScheduleTarget::Assessment { .. } => "assessment",
+            ScheduleTarget::MoodCheckin => "mood_checkin",
+            ScheduleTarget::Activity { .. } => "activity",
+            ScheduleTarget::ActivityGroup { .. } => "activity_group",
+            ScheduleTarget::Habit { .. } => "habit",
+        }
+    }
+
+    /// Validate target contents that don't need the database
+    pub fn validate(&self) -> Result<(), SchedulingError> {
+        if let ScheduleTarget::Habit { text } = self {
+            let text = text.trim();
+            if text.is_empty() {
+                return Err(SchedulingError::InvalidTarget(
+                    "Habit reminder text cannot be empty".to_string(),
+                ));
+            }
+            if text.chars().count() > MAX_HABIT_TEXT_LENGTH {
+                return Err(SchedulingError::InvalidTarget(format!(
+                    "Habit reminder text cannot exceed {} characters",
+                    MAX_HABIT_TEXT_LENGTH
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Rebuild a target from its database columns
+    pub fn from_columns(
+        kind: &str,
+        assessment_type_id: Option<i32>,
+        activity_id: Option<i32>,
+        activity_group_id: Option<i32>,
+        habit_text: Option<String>,
+    ) -> Result<Self, SchedulingError> {
+        let missing = || SchedulingError::InvalidTarget(format!("{} target is incomplete", kind));
+        match kind {
+            "assessment" => Ok(ScheduleTarget::Assessment {
+                assessment_type_id: assessment_type_id.ok_or_else(missing)?,
+            }),
+            "mood_checkin" => Ok(ScheduleTarget::MoodCheckin),
+            "activity" => Ok(ScheduleTarget::Activity {
+                activity_id: activity_id.ok_or_else(missing)?,
+            }),
+            "activity_group" => Ok(ScheduleTarget::ActivityGroup {
+                activity_group_id: activity_group_id.ok_or_else(missing)?,
+            }),
+            "habit" => Ok(ScheduleTarget::Habit {
+                text: habit_text.ok_or_else(missing)?,
+            }),
+            other => Err(SchedulingError::InvalidTarget(format!(
+                "Unknown target kind: {}",
+                other
+            ))),
+        }
+    }
+}
+
+/// Reminder schedule configuration
+///
+/// The `assessment_type_*` fields are only set for assessment targets; `target_name`
+/// is the display label for every kind (assessment, activity or group name, or
+/// the habit text).
+///
+/// Occurrences come from `rrule` (an RFC 5545 RRULE value) anchored at `dtstart`,
+/// minus `exdates`, all in local time of `timezone`. For daily/weekly/biweekly/
+/// monthly schedules the rule is derived from the simple fields; for custom
+/// schedules `time_of_day` is the rule's earliest time, for display.
 #[derive(Serialize, Deserialize, specta::Type, Clone, Debug)]
 pub struct AssessmentSchedule {
     pub id: i32,
-    pub assessment_type_id: i32,
-    pub assessment_type_code: String, // Denormalized for convenience
-    pub assessment_type_name: String, // Denormalized for convenience
+    pub target: ScheduleTarget,
+    pub target_name: String,
+    pub assessment_type_id: Option<i32>,
+    pub assessment_type_code: Option<String>, // Denormalized for convenience
+    pub assessment_type_name: Option<String>, // Denormalized for convenience
     pub frequency: ScheduleFrequency,
     pub time_of_day: String,       // HH:MM format (e.g., "09:00")
     pub day_of_week: Option<i32>,  // 0-6 (Sunday-Saturday) for weekly/biweekly
     pub day_of_month: Option<i32>, // 1-31 for monthly
+    pub rrule: String,             // e.g. "FREQ=WEEKLY;BYDAY=MO,WE,FR;BYHOUR=8,20;BYMINUTE=0"
+    pub dtstart: String,           // Local "YYYY-MM-DDTHH:MM:SS"
+    pub exdates: Vec<String>,      // Local dates or date-times to skip
+    pub timezone: String,          // IANA name; time_of_day is local to it
     pub enabled: bool,
-    pub last_triggered_at: Option<String>, // ISO 8601 timestamp
+    pub missed_policy: MissedReminderPolicy, // Occurrences missed while the app was closed
+    pub last_triggered_at: Option<String>,   // ISO 8601 timestamp
     pub created_at: String,
     pub updated_at: String,
 }
@@ -154,7 +457,9 @@ pub struct AssessmentSchedule {
 /// Request to create a new schedule
 #[derive(Serialize, Deserialize, specta::Type, Clone, Debug, Validate)]
 pub struct CreateScheduleRequest {
-    pub assessment_type_id: i32,
+    /// Shorthand for an assessment target; use `target` for any other kind
+    pub assessment_type_id: Option<i32>,
+    pub target: Option<ScheduleTarget>,
     pub frequency: ScheduleFrequency,
     #[validate(custom(function = "validate_time_format"))]
     pub time_of_day: String, // HH:MM format
@@ -162,9 +467,31 @@ pub struct CreateScheduleRequest {
     pub day_of_week: Option<i32>, // Required for weekly/biweekly
     #[validate(range(min = 1, max = 31))]
     pub day_of_month: Option<i32>, // Required for monthly
+    pub timezone: Option<String>, // IANA name, defaults to the system timezone
+    /// RRULE for custom schedules (required for, and only allowed with, `custom`)
+    pub rrule: Option<String>,
+    /// Local start ("YYYY-MM-DD" or "YYYY-MM-DDTHH:MM:SS"), defaults to today
+    pub dtstart: Option<String>,
+    /// Local dates ("YYYY-MM-DD", every occurrence that day) or date-times to skip
+    pub exdates: Option<Vec<String>>,
+    /// Defaults to coalescing missed occurrences into one notification
+    pub missed_policy: Option<MissedReminderPolicy>,
 }
 
 impl CreateScheduleRequest {
+    /// The schedule's target, from either `target` or the `assessment_type_id` shorthand
+    pub fn resolved_target(&self) -> Result<ScheduleTarget, SchedulingError> {
+        match (&self.target, self.assessment_type_id) {
+            (Some(target), None) => Ok(target.clone()),
+            (None, Some(assessment_type_id)) => {
+                Ok(ScheduleTarget::Assessment { assessment_type_id })
+            }
+            _ => Err(SchedulingError::InvalidTarget(
+                "Specify exactly one of target or assessment_type_id".to_string(),
+            )),
+        }
+    }
+
     /// Validate the schedule request
     /// Combines validator crate's derive validation with custom frequency logic
     pub fn validate(&self) -> Result<(), SchedulingError> {
@@ -196,6 +523,12 @@ impl CreateScheduleRequest {
             SchedulingError::InvalidTimeFormat(format!("Validation failed: {}", e))
         })?;
 
+        if let Some(ref tz) = self.timezone {
+            parse_timezone(tz)?;
+        }
+
+        self.resolved_target()?.validate()?;
+
         // Then run custom frequency-specific requirement validation
         // Range validation already handled by validator crate above
         // We only need to check frequency-specific requirements (e.g., weekly needs day_of_week)
@@ -217,9 +550,42 @@ impl CreateScheduleRequest {
             ScheduleFrequency::Daily => {
                 // No additional validation needed for daily
             }
+            ScheduleFrequency::Custom => {
+                if self.rrule.is_none() {
+                    return Err(SchedulingError::InvalidRecurrence(
+                        "rrule required for custom schedules".to_string(),
+                    ));
+                }
+            }
         }
 
-        Ok(())
+        validate_recurrence_fields(
+            &self.frequency,
+            self.rrule.as_deref(),
+            self.dtstart.as_deref(),
+            self.exdates.as_deref(),
+        )
+    }
+
+    /// Recurrence columns for a new schedule created at `now`
+    pub fn recurrence(
+        &self,
+        tz: &Tz,
+        now: DateTime<Utc>,
+    ) -> Result<ScheduleRecurrence, SchedulingError> {
+        let dtstart = match &self.dtstart {
+            Some(dtstart) => parse_dtstart(dtstart)?,
+            None => default_dtstart(&self.frequency, self.day_of_week, tz, now),
+        };
+        ScheduleRecurrence::new(
+            &self.frequency,
+            &self.time_of_day,
+            self.day_of_week,
+            self.day_of_month,
+            self.rrule.as_deref(),
+            dtstart,
+            self.exdates.as_deref().unwrap_or_default(),
+        )
     }
 }
 
@@ -233,7 +599,14 @@ pub struct UpdateScheduleRequest {
     pub day_of_week: Option<i32>,
     #[validate(range(min = 1, max = 31))]
     pub day_of_month: Option<i32>,
+    pub timezone: Option<String>,
+    pub target: Option<ScheduleTarget>,
     pub enabled: Option<bool>,
+    /// Setting an rrule without a frequency switches the schedule to `custom`
+    pub rrule: Option<String>,
+    pub dtstart: Option<String>,
+    pub exdates: Option<Vec<String>>,
+    pub missed_policy: Option<MissedReminderPolicy>,
 }
 
 impl UpdateScheduleRequest {
@@ -270,10 +643,383 @@ impl UpdateScheduleRequest {
             SchedulingError::InvalidFrequency(format!("Validation failed: {}", e))
         })?;
 
-        // No additional custom validation needed for update requests
         // Range validation already handled by validator crate above
+        if let Some(ref tz) = self.timezone {
+            parse_timezone(tz)?;
+        }
+        if let Some(ref target) = self.target {
+            target.validate()?;
+        }
+        // Without a frequency, an rrule switches the schedule to custom
+        validate_recurrence_fields(
+            self.frequency
+                .as_ref()
+                .unwrap_or(&ScheduleFrequency::Custom),
+            self.rrule.as_deref(),
+            self.dtstart.as_deref(),
+            self.exdates.as_deref(),
+        )?;
+
         Ok(())
     }
+
+    /// Whether the update touches any field the recurrence rule is derived from
+    pub fn changes_recurrence(&self) -> bool {
+        self.frequency.is_some()
+            || self.time_of_day.is_some()
+            || self.day_of_week.is_some()
+            || self.day_of_month.is_some()
+            || self.rrule.is_some()
+            || self.dtstart.is_some()
+            || self.exdates.is_some()
+    }
+
+    /// Recurrence columns after applying this update to `current`
+    pub fn recurrence(
+        &self,
+        current: &AssessmentSchedule,
+    ) -> Result<ScheduleRecurrence, SchedulingError> {
+        let frequency = match (&self.frequency, &self.rrule) {
+            (Some(frequency), _) => frequency.clone(),
+            (None, Some(_)) => ScheduleFrequency::Custom,
+            (None, None) => current.frequency.clone(),
+        };
+        // Keep the stored rule when a custom schedule only changes its start,
+        // exceptions or time of day; a new time replaces its BYHOUR/BYMINUTE
+        let kept_rule;
+        let rrule = match (&self.rrule, &frequency, &current.frequency) {
+            (Some(rrule), _, _) => Some(rrule.as_str()),
+            (None, ScheduleFrequency::Custom, ScheduleFrequency::Custom) => {
+                let mut rule: RecurrenceRule = current.rrule.parse()?;
+                if let Some(time_of_day) = &self.time_of_day {
+                    rule.set_time_of_day(time_of_day)?;
+                }
+                kept_rule = rule.to_string();
+                Some(kept_rule.as_str())
+            }
+            _ => None,
+        };
+        let dtstart = parse_dtstart(self.dtstart.as_ref().unwrap_or(&current.dtstart))?;
+
+        ScheduleRecurrence::new(
+            &frequency,
+            self.time_of_day.as_ref().unwrap_or(&current.time_of_day),
+            self.day_of_week.or(current.day_of_week),
+            self.day_of_month.or(current.day_of_month),
+            rrule,
+            dtstart,
+            self.exdates.as_ref().unwrap_or(&current.exdates),
+        )
+    }
+}
+
+/// Check rrule/dtstart/exdates syntax and that an rrule only comes with `custom`
+fn validate_recurrence_fields(
+    frequency: &ScheduleFrequency,
+    rrule: Option<&str>,
+    dtstart: Option<&str>,
+    exdates: Option<&[String]>,
+) -> Result<(), SchedulingError> {
+    if let Some(rrule) = rrule {
+        if *frequency != ScheduleFrequency::Custom {
+            return Err(SchedulingError::InvalidRecurrence(format!(
+                "rrule can only be set on custom schedules, not {}",
+                frequency.as_str()
+            )));
+        }
+        rrule.parse::<RecurrenceRule>()?;
+    }
+    if let Some(dtstart) = dtstart {
+        parse_dtstart(dtstart)?;
+    }
+    for exdate in exdates.unwrap_or_default() {
+        exdate.parse::<ExceptionDate>()?;
+    }
+    Ok(())
+}
+
+/// Recurrence-related columns of a schedule, ready to store
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScheduleRecurrence {
+    pub frequency: ScheduleFrequency,
+    pub time_of_day: String,
+    pub day_of_week: Option<i32>,
+    pub day_of_month: Option<i32>,
+    pub rrule: String,
+    pub dtstart: String,
+    pub exdates: Vec<String>,
+}
+
+impl ScheduleRecurrence {
+    /// Build the stored rule: the given `rrule` for custom schedules, otherwise
+    /// the rule equivalent to the simple frequency fields
+    pub fn new(
+        frequency: &ScheduleFrequency,
+        time_of_day: &str,
+        day_of_week: Option<i32>,
+        day_of_month: Option<i32>,
+        rrule: Option<&str>,
+        dtstart: NaiveDateTime,
+        exdates: &[String],
+    ) -> Result<Self, SchedulingError> {
+        let exdates = exdates
+            .iter()
+            .map(|e| e.parse::<ExceptionDate>().map(|e| e.to_string()))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if *frequency == ScheduleFrequency::Custom {
+            let rule: RecurrenceRule = rrule
+                .ok_or_else(|| {
+                    SchedulingError::InvalidRecurrence(
+                        "rrule required for custom schedules".to_string(),
+                    )
+                })?
+                .parse()?;
+            return Ok(ScheduleRecurrence {
+                frequency: ScheduleFrequency::Custom,
+                time_of_day: rule.first_time_of_day(dtstart),
+                day_of_week: None,
+                day_of_month: None,
+                rrule: rule.to_string(),
+                dtstart: format_dtstart(dtstart),
+                exdates,
+            });
+        }
+
+        let rule =
+            RecurrenceRule::from_frequency(frequency, time_of_day, day_of_week, day_of_month)?;
+        let (day_of_week, day_of_month) = match frequency {
+            ScheduleFrequency::Weekly | ScheduleFrequency::Biweekly => (day_of_week, None),
+            ScheduleFrequency::Monthly => (None, day_of_month),
+            _ => (None, None),
+        };
+        Ok(ScheduleRecurrence {
+            frequency: frequency.clone(),
+            time_of_day: time_of_day.to_string(),
+            day_of_week,
+            day_of_month,
+            rrule: rule.to_string(),
+            dtstart: format_dtstart(dtstart),
+            exdates,
+        })
+    }
+}
+
+/// Maximum number of occurrences a preview can list
+pub const MAX_PREVIEW_OCCURRENCES: u32 = 100;
+
+/// Request to list upcoming occurrences of a (possibly unsaved) rule
+#[derive(Serialize, Deserialize, specta::Type, Clone, Debug)]
+pub struct PreviewOccurrencesRequest {
+    pub rrule: String,
+    pub dtstart: Option<String>,  // Defaults to the start of today
+    pub timezone: Option<String>, // Defaults to the system timezone
+    pub exdates: Option<Vec<String>>,
+    pub count: u32,
+}
+
+/// One upcoming occurrence of a schedule
+#[derive(Serialize, Deserialize, specta::Type, Clone, Debug, PartialEq)]
+pub struct ScheduleOccurrence {
+    pub local_time: String, // "YYYY-MM-DDTHH:MM:SS" in the schedule's timezone
+    pub utc_time: String,   // RFC 3339
+}
+
+/// The next `count` occurrences of a rule after `now`
+pub fn preview_occurrences(
+    request: &PreviewOccurrencesRequest,
+    now: DateTime<Utc>,
+) -> Result<Vec<ScheduleOccurrence>, SchedulingError> {
+    if request.count == 0 || request.count > MAX_PREVIEW_OCCURRENCES {
+        return Err(SchedulingError::InvalidRecurrence(format!(
+            "count must be between 1 and {}",
+            MAX_PREVIEW_OCCURRENCES
+        )));
+    }
+
+    let timezone = request.timezone.clone().unwrap_or_else(system_timezone);
+    let tz = parse_timezone(&timezone)?;
+    let dtstart = match &request.dtstart {
+        Some(dtstart) => parse_dtstart(dtstart)?,
+        None => default_dtstart(&ScheduleFrequency::Custom, None, &tz, now),
+    };
+    let set = RecurrenceSet {
+        rule: request.rrule.parse()?,
+        dtstart,
+        exdates: request
+            .exdates
+            .as_deref()
+            .unwrap_or_default()
+            .iter()
+            .map(|e| e.parse())
+            .collect::<Result<Vec<_>, _>>()?,
+    };
+
+    Ok(set
+        .occurrences_after(tz, now)
+        .take(request.count as usize)
+        .map(|(local, instant)| ScheduleOccurrence {
+            local_time: format_dtstart(local),
+            utc_time: instant.to_rfc3339(),
+        })
+        .collect())
+}
+
+/// Longest snooze, in minutes
+pub const MAX_SNOOZE_MINUTES: i32 = 24 * 60;
+
+/// How long after a reminder fired a submitted assessment still counts as completing it
+pub const REMINDER_COMPLETION_WINDOW_HOURS: i64 = 24;
+
+/// What happened to a reminder notification
+#[derive(Serialize, Deserialize, specta::Type, Clone, Copy, Debug, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ReminderEventType {
+    Fired,
+    Snoozed,
+    Dismissed,
+    Completed,
+}
+
+impl ReminderEventType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReminderEventType::Fired => "fired",
+            ReminderEventType::Snoozed => "snoozed",
+            ReminderEventType::Dismissed => "dismissed",
+            ReminderEventType::Completed => "completed",
+        }
+    }
+}
+
+impl FromStr for ReminderEventType {
+    type Err = SchedulingError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "fired" => Ok(ReminderEventType::Fired),
+            "snoozed" => Ok(ReminderEventType::Snoozed),
+            "dismissed" => Ok(ReminderEventType::Dismissed),
+            "completed" => Ok(ReminderEventType::Completed),
+            _ => Err(SchedulingError::InvalidReminderEvent(format!(
+                "Unknown event type: {}",
+                s
+            ))),
+        }
+    }
+}
+
+/// One entry of the reminder event log
+///
+/// `related_event_id` is the original `fired` event the entry belongs to; it is
+/// `None` only for original reminders. A re-fire after a snooze is a `fired`
+/// event with `related_event_id` set.
+#[derive(Serialize, Deserialize, specta::Type, Clone, Debug, PartialEq)]
+pub struct ReminderEvent {
+    pub id: i32,
+    pub schedule_id: i32,
+    pub event_type: ReminderEventType,
+    pub occurred_at: String,          // UTC "YYYY-MM-DD HH:MM:SS"
+    pub snooze_until: Option<String>, // Set for snoozed events
+    pub related_event_id: Option<i32>,
+    pub assessment_response_id: Option<i32>, // Set when an assessment completed the reminder
+}
+
+impl ReminderEvent {
+    /// ID of the original reminder this event belongs to
+    pub fn reminder_id(&self) -> i32 {
+        self.related_event_id.unwrap_or(self.id)
+    }
+}
+
+/// How well the reminders of one schedule were followed
+#[derive(Serialize, Deserialize, specta::Type, Clone, Debug, PartialEq)]
+pub struct ReminderAdherence {
+    pub schedule_id: i32,
+    pub target_name: String,
+    pub reminders_fired: i32, // Original reminders, not re-fires after a snooze
+    pub reminders_completed: i32,
+    pub reminders_snoozed: i32,   // Reminders snoozed at least once
+    pub reminders_dismissed: i32, // Reminders dismissed at least once
+    pub completion_rate: f64,     // reminders_completed / reminders_fired, 0 without reminders
+    pub median_minutes_to_completion: Option<f64>, // From original fire to completion
+}
+
+/// Median of a list of values, `None` if empty
+pub fn median(values: &mut [f64]) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_by(|a, b| a.total_cmp(b));
+    let mid = values.len() / 2;
+    Some(if values.len().is_multiple_of(2) {
+        (values[mid - 1] + values[mid]) / 2.0
+    } else {
+        values[mid]
+    })
+}
+
+/// Most occurrences recorded per schedule when catching up; older ones are dropped
+pub const MAX_MISSED_OCCURRENCES: usize = 100;
+
+/// An occurrence that fell due while the app was closed
+#[derive(Serialize, Deserialize, specta::Type, Clone, Debug, PartialEq)]
+pub struct MissedReminder {
+    pub id: i32,
+    pub schedule_id: i32,
+    pub target: ScheduleTarget,
+    pub target_name: String,
+    pub scheduled_for: String, // UTC "YYYY-MM-DD HH:MM:SS"
+    pub local_time: String,    // "YYYY-MM-DDTHH:MM:SS" in the schedule's timezone
+    pub policy: MissedReminderPolicy,
+    pub reminder_event_id: Option<i32>, // Catch-up notification, None when skipped
+    pub detected_at: String,
+    pub acknowledged_at: Option<String>,
+}
+
+/// A catch-up notification to send for missed occurrences of a schedule
+#[derive(Clone, Debug)]
+pub struct MissedReminderNotice {
+    pub schedule: AssessmentSchedule,
+    pub event: ReminderEvent, // The `fired` event logged for the notification
+    pub missed: Vec<MissedReminder>, // Occurrences it covers, oldest first
+}
+
+/// Occurrences of a schedule after it last fired or was changed, up to and
+/// including `now`, oldest first.
+///
+/// Counting from the last change keeps occurrences of an old rule, or from
+/// while the schedule was disabled, from being reported. At most
+/// [`MAX_MISSED_OCCURRENCES`] are returned, keeping the most recent.
+pub fn missed_occurrences(
+    schedule: &AssessmentSchedule,
+    now: DateTime<Utc>,
+) -> Result<Vec<(NaiveDateTime, DateTime<Utc>)>, SchedulingError> {
+    let parse = |timestamp: &str| {
+        crate::utils::parse_timestamp(timestamp).ok_or_else(|| {
+            SchedulingError::DateParseError(format!("Failed to parse timestamp: {}", timestamp))
+        })
+    };
+    let mut from = parse(&schedule.updated_at)?;
+    if let Some(last_triggered) = &schedule.last_triggered_at {
+        from = from.max(parse(last_triggered)?);
+    }
+    let from = from.and_utc();
+    let tz = parse_timezone(&schedule.timezone)?;
+
+    let mut missed = std::collections::VecDeque::new();
+    for occurrence in schedule
+        .recurrence_set()?
+        .occurrences_after(tz, from)
+        .take_while(|(_, instant)| *instant <= now)
+    {
+        if missed.len() == MAX_MISSED_OCCURRENCES {
+            missed.pop_front();
+        }
+        missed.push_back(occurrence);
+    }
+
+    Ok(missed.into())
 }
 
 /// Helper function to validate time format (HH:MM)
@@ -306,170 +1052,107 @@ fn validate_time_format(time: &str) -> Result<(), validator::ValidationError> {
     Ok(())
 }
 
-/// Calculate next trigger time for a schedule
-pub fn calculate_next_trigger(
-    schedule: &AssessmentSchedule,
-    from: DateTime<Utc>,
-) -> Result<DateTime<Utc>, SchedulingError> {
-    use chrono::{Datelike, Duration, NaiveTime};
-
-    // Parse time_of_day
-    let parts: Vec<&str> = schedule.time_of_day.split(':').collect();
-    let hour: u32 = parts[0]
-        .parse()
-        .map_err(|_| SchedulingError::InvalidTimeFormat(schedule.time_of_day.clone()))?;
-    let minute: u32 = parts[1]
-        .parse()
-        .map_err(|_| SchedulingError::InvalidTimeFormat(schedule.time_of_day.clone()))?;
-
-    let target_time = NaiveTime::from_hms_opt(hour, minute, 0)
-        .ok_or_else(|| SchedulingError::InvalidTimeFormat(schedule.time_of_day.clone()))?;
-
-    let next_trigger = match schedule.frequency {
-        ScheduleFrequency::Daily => {
-            // Set to target time today
-            let mut trigger = from.date_naive().and_time(target_time).and_utc();
-
-            // If time has passed today, move to tomorrow
-            if trigger <= from {
-                trigger += Duration::days(1);
-            }
-            trigger
-        }
-        ScheduleFrequency::Weekly => {
-            let target_day = schedule.day_of_week.ok_or_else(|| {
-                SchedulingError::InvalidFrequency(
-                    "day_of_week missing for weekly schedule".to_string(),
-                )
-            })?;
+/// Parse an IANA timezone name (e.g. "America/New_York")
+pub fn parse_timezone(name: &str) -> Result<Tz, SchedulingError> {
+    name.parse::<Tz>()
+        .map_err(|_| SchedulingError::InvalidTimezone(name.to_string()))
+}
 
-            let current_day = from.weekday().num_days_from_sunday() as i32;
-            let days_until_target = (target_day - current_day + 7) % 7;
+/// IANA name of the system timezone, falling back to UTC if it can't be determined
+pub fn system_timezone() -> String {
+    iana_time_zone::get_timezone()
+        .ok()
+        .filter(|name| parse_timezone(name).is_ok())
+        .unwrap_or_else(|| "UTC".to_string())
+}
 
-            let mut trigger = (from + Duration::days(days_until_target as i64))
-                .date_naive()
-                .and_time(target_time)
-                .and_utc();
+/// Resolve a local wall-clock time to an instant, with defined DST behaviour:
+///
+/// - **Gap** (spring forward, e.g. 02:30 when clocks jump 02:00 → 03:00): the
+///   time doesn't exist, so the reminder fires at the first valid local time
+///   after it (03:00 in the example). It fires once, never skipped.
+/// - **Overlap** (fall back, e.g. 01:30 occurs twice): the reminder fires at
+///   the first (earlier) occurrence only.
+pub fn resolve_local_time(tz: &Tz, local: NaiveDateTime) -> DateTime<Utc> {
+    use chrono::LocalResult;
 
-            // If same day but time passed, move to next week
-            if days_until_target == 0 && trigger <= from {
-                trigger += Duration::weeks(1);
-            }
-            trigger
-        }
-        ScheduleFrequency::Biweekly => {
-            // BIWEEKLY SCHEDULING ALGORITHM
-            //
-            // For biweekly schedules, we must maintain strict 14-day intervals to prevent
-            // "drift" where the schedule would trigger every occurrence of the target weekday
-            // instead of maintaining proper 2-week spacing.
-            //
-            // Example problem without this logic:
-            //   Schedule: Every other Monday at 9:00 AM
-            //   Last trigger: Monday, Oct 1
-            //   Without proper tracking: Would trigger again on Oct 8 (only 7 days later!)
-            //   With proper tracking: Correctly triggers on Oct 15 (14 days later)
-
-            let target_day = schedule.day_of_week.ok_or_else(|| {
-                SchedulingError::InvalidFrequency(
-                    "day_of_week missing for biweekly schedule".to_string(),
-                )
-            })?;
-
-            let trigger = if let Some(last_triggered) = &schedule.last_triggered_at {
-                // RECURRING SCHEDULE: Calculate from last trigger to ensure 14-day intervals
-                // This prevents "drift" where the schedule would trigger every occurrence of
-                // the target weekday instead of maintaining strict 2-week spacing.
-                let last_trigger_dt = chrono::DateTime::parse_from_rfc3339(last_triggered)
-                    .map_err(|e| {
-                        SchedulingError::DateParseError(format!(
-                            "Failed to parse last_triggered_at: {}",
-                            e
-                        ))
-                    })?
-                    .with_timezone(&chrono::Utc);
-                let mut t = last_trigger_dt + Duration::weeks(2);
-
-                // CATCH-UP LOGIC: If calculated trigger is in the past (e.g., app was offline
-                // for multiple weeks), keep adding 2-week intervals until we reach a future date.
-                // This ensures we don't miss multiple triggers or create notification spam.
-                while t <= from {
-                    t += Duration::weeks(2);
-                }
-                t
-            } else {
-                // FIRST-TIME SCHEDULE: Find next occurrence of target weekday
-                // Subsequent triggers will use the path above to maintain 14-day spacing.
-                let current_day = from.weekday().num_days_from_sunday() as i32;
-                let days_until_target = (target_day - current_day + 7) % 7;
-
-                let mut t = (from + Duration::days(days_until_target as i64))
-                    .date_naive()
-                    .and_time(target_time)
-                    .and_utc();
-
-                // If target day is today but time has passed, move to next week
-                if days_until_target == 0 && t <= from {
-                    t += Duration::weeks(1);
-                }
-                t
-            };
-            trigger
+    let mut candidate = local;
+    // Gaps are at most a few hours; walk forward minute by minute to the end of the gap
+    for _ in 0..(24 * 60) {
+        match tz.from_local_datetime(&candidate) {
+            LocalResult::Single(dt) => return dt.with_timezone(&Utc),
+            LocalResult::Ambiguous(earliest, _) => return earliest.with_timezone(&Utc),
+            LocalResult::None => candidate += chrono::Duration::minutes(1),
         }
-        ScheduleFrequency::Monthly => {
-            let target_day = schedule.day_of_month.ok_or_else(|| {
-                SchedulingError::InvalidFrequency(
-                    "day_of_month missing for monthly schedule".to_string(),
-                )
-            })?;
-
-            let current_day = from.day() as i32;
-
-            // Try current month first
-            let mut year = from.year();
-            let mut month = from.month();
+    }
 
-            if target_day < current_day || (target_day == current_day && from.time() >= target_time)
-            {
-                // Move to next month
-                month += 1;
-                if month > 12 {
-                    month = 1;
-                    year += 1;
-                }
-            }
+    // Unreachable for real tz data; treat the wall-clock time as UTC
+    local.and_utc()
+}
 
-            // Handle months with fewer days (e.g., February, April)
-            let day = target_day.min(days_in_month(year, month)? as i32);
+impl AssessmentSchedule {
+    /// The schedule's parsed rule, start and exception dates
+    pub fn recurrence_set(&self) -> Result<RecurrenceSet, SchedulingError> {
+        RecurrenceSet::parse(&self.rrule, &self.dtstart, &self.exdates)
+    }
+}
 
-            chrono::NaiveDate::from_ymd_opt(year, month, day as u32)
-                .ok_or_else(|| SchedulingError::DateParseError("Invalid date".to_string()))?
-                .and_time(target_time)
-                .and_utc()
-        }
-    };
+/// Calculate next trigger time for a schedule
+///
+/// Occurrences of the schedule's rule are interpreted in its timezone, so
+/// "09:00" stays 09:00 local across DST changes. See [`resolve_local_time`]
+/// for how non-existent and repeated local times are handled. Returns
+/// [`SchedulingError::RecurrenceEnded`] once a rule with COUNT or UNTIL is
+/// exhausted.
+pub fn calculate_next_trigger(
+    schedule: &AssessmentSchedule,
+    from: DateTime<Utc>,
+) -> Result<DateTime<Utc>, SchedulingError> {
+    let tz = parse_timezone(&schedule.timezone)?;
 
-    Ok(next_trigger)
+    schedule
+        .recurrence_set()?
+        .occurrences_after(tz, from)
+        .next()
+        .map(|(_, instant)| instant)
+        .ok_or(SchedulingError::RecurrenceEnded)
 }
 
-/// Get number of days in a month
-fn days_in_month(year: i32, month: u32) -> Result<u32, SchedulingError> {
-    use chrono::NaiveDate;
+/// Whether a schedule should fire at `now`.
+///
+/// A schedule that has fired before is due once its next occurrence after
+/// `last_triggered_at` has arrived. A schedule that has never fired is due if
+/// an occurrence falls between local midnight and `now`, so a reminder set up
+/// for a time that already passed today fires right away, while one set up
+/// for later today waits.
+pub fn is_schedule_due(
+    schedule: &AssessmentSchedule,
+    now: DateTime<Utc>,
+) -> Result<bool, SchedulingError> {
+    if !schedule.enabled {
+        return Ok(false);
+    }
 
-    let first_of_next_month = if month == 12 {
-        NaiveDate::from_ymd_opt(year + 1, 1, 1)
-    } else {
-        NaiveDate::from_ymd_opt(year, month + 1, 1)
+    let from = match &schedule.last_triggered_at {
+        Some(last_triggered) => crate::utils::parse_timestamp(last_triggered)
+            .ok_or_else(|| {
+                SchedulingError::DateParseError(format!(
+                    "Failed to parse last_triggered_at: {}",
+                    last_triggered
+                ))
+            })?
+            .and_utc(),
+        None => {
+            let tz = parse_timezone(&schedule.timezone)?;
+            let midnight = now.with_timezone(&tz).date_naive().and_time(NaiveTime::MIN);
+            resolve_local_time(&tz, midnight) - chrono::Duration::seconds(1)
+        }
     };
 
-    let first_of_month = NaiveDate::from_ymd_opt(year, month, 1);
-
-    match (first_of_next_month, first_of_month) {
-        (Some(next), Some(current)) => Ok(next.signed_duration_since(current).num_days() as u32),
-        _ => Err(SchedulingError::DateParseError(format!(
-            "Invalid year/month combination: year={}, month={}",
-            year, month
-        ))),
+    match calculate_next_trigger(schedule, from) {
+        Ok(next) => Ok(next <= now),
+        Err(SchedulingError::RecurrenceEnded) => Ok(false),
+        Err(e) => Err(e),
     }
 }
 
@@ -492,41 +1175,65 @@ mod tests {
     fn test_create_schedule_request_validation() {
         // Valid daily schedule
         let req = CreateScheduleRequest {
-            assessment_type_id: 1,
+            assessment_type_id: Some(1),
             frequency: ScheduleFrequency::Daily,
             time_of_day: "09:00".to_string(),
             day_of_week: None,
             day_of_month: None,
+            timezone: None,
+            target: None,
+            rrule: None,
+            dtstart: None,
+            exdates: None,
+            missed_policy: None,
         };
         assert!(req.validate().is_ok());
 
         // Invalid time format
         let req = CreateScheduleRequest {
-            assessment_type_id: 1,
+            assessment_type_id: Some(1),
             frequency: ScheduleFrequency::Daily,
             time_of_day: "25:00".to_string(),
             day_of_week: None,
             day_of_month: None,
+            timezone: None,
+            target: None,
+            rrule: None,
+            dtstart: None,
+            exdates: None,
+            missed_policy: None,
         };
         assert!(req.validate().is_err());
 
         // Weekly without day_of_week
         let req = CreateScheduleRequest {
-            assessment_type_id: 1,
+            assessment_type_id: Some(1),
             frequency: ScheduleFrequency::Weekly,
             time_of_day: "09:00".to_string(),
             day_of_week: None,
             day_of_month: None,
+            timezone: None,
+            target: None,
+            rrule: None,
+            dtstart: None,
+            exdates: None,
+            missed_policy: None,
         };
         assert!(req.validate().is_err());
 
         // Monthly with valid day
         let req = CreateScheduleRequest {
-            assessment_type_id: 1,
+            assessment_type_id: Some(1),
             frequency: ScheduleFrequency::Monthly,
             time_of_day: "09:00".to_string(),
             day_of_week: None,
             day_of_month: Some(15),
+            timezone: None,
+            target: None,
+            rrule: None,
+            dtstart: None,
+            exdates: None,
+            missed_policy: None,
         };
         assert!(req.validate().is_ok());
     }
@@ -543,4 +1250,288 @@ mod tests {
         );
         assert!(ScheduleFrequency::from_str("invalid").is_err());
     }
+
+    #[test]
+    fn test_schedule_target_columns_round_trip() {
+        let habit = ScheduleTarget::from_columns("habit", None, None, None, Some("Stretch".into()))
+            .expect("Failed to rebuild habit target");
+        assert_eq!(habit.kind(), "habit");
+        assert_eq!(
+            ScheduleTarget::from_columns("activity", None, Some(4), None, None)
+                .expect("Failed to rebuild activity target"),
+            ScheduleTarget::Activity { activity_id: 4 }
+        );
+        assert!(ScheduleTarget::from_columns("activity_group", None, None, None, None).is_err());
+        assert!(ScheduleTarget::from_columns("podcast", None, None, None, None).is_err());
+    }
+
+    #[test]
+    fn test_habit_target_text_validation() {
+        let habit = |text: &str| ScheduleTarget::Habit {
+            text: text.to_string(),
+        };
+        assert!(habit("Drink water").validate().is_ok());
+        assert!(habit("  ").validate().is_err());
+        assert!(habit(&"a".repeat(MAX_HABIT_TEXT_LENGTH + 1))
+            .validate()
+            .is_err());
+    }
+
+    // ========================================
+    // Timezone and DST Tests
+    // ========================================
+
+    fn schedule(
+        frequency: ScheduleFrequency,
+        time_of_day: &str,
+        timezone: &str,
+    ) -> AssessmentSchedule {
+        AssessmentSchedule {
+            id: 1,
+            target: ScheduleTarget::Assessment {
+                assessment_type_id: 1,
+            },
+            target_name: "PHQ-9".to_string(),
+            assessment_type_id: Some(1),
+            assessment_type_code: Some("PHQ9".to_string()),
+            assessment_type_name: Some("PHQ-9".to_string()),
+            frequency: frequency.clone(),
+            time_of_day: time_of_day.to_string(),
+            day_of_week: None,
+            day_of_month: None,
+            rrule: RecurrenceRule::from_frequency(&frequency, time_of_day, None, None)
+                .map(|rule| rule.to_string())
+                .unwrap_or_default(),
+            dtstart: "2025-01-01T00:00:00".to_string(),
+            exdates: Vec::new(),
+            timezone: timezone.to_string(),
+            enabled: true,
+            missed_policy: MissedReminderPolicy::Coalesce,
+            last_triggered_at: None,
+            created_at: "2025-01-01 00:00:00".to_string(),
+            updated_at: "2025-01-01 00:00:00".to_string(),
+        }
+    }
+
+    /// Re-derive the rule after changing a test schedule's day fields
+    fn refresh_rule(s: &mut AssessmentSchedule) {
+        s.rrule = RecurrenceRule::from_frequency(
+            &s.frequency,
+            &s.time_of_day,
+            s.day_of_week,
+            s.day_of_month,
+        )
+        .expect("Failed to build rule")
+        .to_string();
+    }
+
+    fn utc(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s)
+            .expect("Invalid test timestamp")
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_parse_timezone() {
+        assert!(parse_timezone("Europe/Berlin").is_ok());
+        assert!(parse_timezone("UTC").is_ok());
+        assert!(matches!(
+            parse_timezone("Mars/Olympus_Mons"),
+            Err(SchedulingError::InvalidTimezone(_))
+        ));
+        assert!(parse_timezone(&system_timezone()).is_ok());
+    }
+
+    #[test]
+    fn test_daily_trigger_uses_local_time() {
+        let s = schedule(ScheduleFrequency::Daily, "09:00", "America/New_York");
+
+        // 07:00 EST -> 09:00 EST the same day
+        let next = calculate_next_trigger(&s, utc("2025-01-15T12:00:00Z")).unwrap();
+        assert_eq!(next, utc("2025-01-15T14:00:00Z"));
+    }
+
+    #[test]
+    fn test_daily_trigger_keeps_local_hour_across_spring_forward() {
+        // New York springs forward on 2025-03-09 (EST -> EDT)
+        let s = schedule(ScheduleFrequency::Daily, "09:00", "America/New_York");
+
+        let next = calculate_next_trigger(&s, utc("2025-03-08T14:30:00Z")).unwrap();
+        assert_eq!(next, utc("2025-03-09T13:00:00Z")); // 09:00 EDT
+    }
+
+    #[test]
+    fn test_spring_forward_gap_fires_at_end_of_gap() {
+        // 02:30 does not exist on 2025-03-09 in New York (02:00 -> 03:00)
+        let s = schedule(ScheduleFrequency::Daily, "02:30", "America/New_York");
+
+        let next = calculate_next_trigger(&s, utc("2025-03-09T05:00:00Z")).unwrap();
+        assert_eq!(next, utc("2025-03-09T07:00:00Z")); // 03:00 EDT
+
+        // Back to the normal time the next day
+        let after = calculate_next_trigger(&s, next).unwrap();
+        assert_eq!(after, utc("2025-03-10T06:30:00Z")); // 02:30 EDT
+    }
+
+    #[test]
+    fn test_fall_back_overlap_fires_once() {
+        // 01:30 happens twice on 2025-11-02 in New York (02:00 EDT -> 01:00 EST)
+        let s = schedule(ScheduleFrequency::Daily, "01:30", "America/New_York");
+
+        let first = calculate_next_trigger(&s, utc("2025-11-02T04:00:00Z")).unwrap();
+        assert_eq!(first, utc("2025-11-02T05:30:00Z")); // first 01:30 (EDT)
+
+        // The repeated 01:30 EST (06:30Z) is skipped; next is the following day
+        let next = calculate_next_trigger(&s, first).unwrap();
+        assert_eq!(next, utc("2025-11-03T06:30:00Z"));
+
+        let mut triggered = s.clone();
+        triggered.last_triggered_at = Some("2025-11-02 05:30:10".to_string());
+        assert!(!is_schedule_due(&triggered, utc("2025-11-02T06:45:00Z")).unwrap());
+    }
+
+    #[test]
+    fn test_weekly_trigger_uses_local_weekday() {
+        let mut s = schedule(ScheduleFrequency::Weekly, "08:00", "Pacific/Auckland");
+        s.day_of_week = Some(1); // Monday
+        refresh_rule(&mut s);
+
+        // Sunday 20:00 UTC is already Monday 09:00 in Auckland (NZDT, +13)
+        let next = calculate_next_trigger(&s, utc("2025-01-05T20:00:00Z")).unwrap();
+        assert_eq!(next, utc("2025-01-12T19:00:00Z")); // Monday 2025-01-13 08:00 NZDT
+    }
+
+    #[test]
+    fn test_biweekly_trigger_across_dst_change() {
+        // Berlin switches to CEST on 2025-03-30
+        let mut s = schedule(ScheduleFrequency::Biweekly, "09:00", "Europe/Berlin");
+        s.day_of_week = Some(1);
+        refresh_rule(&mut s);
+        s.last_triggered_at = Some("2025-03-24 08:00:05".to_string()); // 09:00 CET
+
+        let next = calculate_next_trigger(&s, utc("2025-03-25T00:00:00Z")).unwrap();
+        assert_eq!(next, utc("2025-04-07T07:00:00Z")); // 09:00 CEST
+    }
+
+    #[test]
+    fn test_monthly_trigger_clamps_short_month() {
+        let mut s = schedule(ScheduleFrequency::Monthly, "09:00", "UTC");
+        s.day_of_month = Some(31);
+        refresh_rule(&mut s);
+
+        let next = calculate_next_trigger(&s, utc("2025-02-10T00:00:00Z")).unwrap();
+        assert_eq!(next, utc("2025-02-28T09:00:00Z"));
+
+        let next = calculate_next_trigger(&s, utc("2025-02-28T10:00:00Z")).unwrap();
+        assert_eq!(next, utc("2025-03-31T09:00:00Z"));
+    }
+
+    #[test]
+    fn test_is_schedule_due_never_triggered() {
+        let s = schedule(ScheduleFrequency::Daily, "09:00", "Europe/Berlin");
+
+        // 08:30 CET: not yet
+        assert!(!is_schedule_due(&s, utc("2025-01-15T07:30:00Z")).unwrap());
+        // 10:00 CET: today's occurrence has passed
+        assert!(is_schedule_due(&s, utc("2025-01-15T09:00:00Z")).unwrap());
+    }
+
+    #[test]
+    fn test_custom_schedule_follows_rrule() {
+        let mut s = schedule(ScheduleFrequency::Custom, "08:00", "Europe/Berlin");
+        s.rrule = "FREQ=WEEKLY;BYDAY=MO,WE,FR;BYHOUR=8,20;BYMINUTE=0".to_string();
+        s.exdates = vec!["2025-03-05T08:00:00".to_string()];
+
+        // Monday 2025-03-03 21:00 CET -> Wednesday 20:00 (08:00 is excluded)
+        let next = calculate_next_trigger(&s, utc("2025-03-03T20:00:00Z")).unwrap();
+        assert_eq!(next, utc("2025-03-05T19:00:00Z"));
+    }
+
+    #[test]
+    fn test_finished_rule_is_never_due() {
+        let mut s = schedule(ScheduleFrequency::Custom, "09:00", "UTC");
+        s.rrule = "FREQ=DAILY;COUNT=2;BYHOUR=9;BYMINUTE=0".to_string();
+        s.last_triggered_at = Some("2025-01-02 09:00:05".to_string());
+
+        assert!(matches!(
+            calculate_next_trigger(&s, utc("2025-01-02T09:00:05Z")),
+            Err(SchedulingError::RecurrenceEnded)
+        ));
+        assert!(!is_schedule_due(&s, utc("2025-02-01T00:00:00Z")).unwrap());
+    }
+
+    #[test]
+    fn test_preview_occurrences() {
+        let request = PreviewOccurrencesRequest {
+            rrule: "FREQ=MONTHLY;BYDAY=-1SU;BYHOUR=10;BYMINUTE=0".to_string(),
+            dtstart: Some("2025-01-01".to_string()),
+            timezone: Some("Europe/Berlin".to_string()),
+            exdates: Some(vec!["2025-02-23".to_string()]),
+            count: 2,
+        };
+
+        let occurrences = preview_occurrences(&request, utc("2025-01-01T00:00:00Z")).unwrap();
+        assert_eq!(
+            occurrences,
+            vec![
+                ScheduleOccurrence {
+                    local_time: "2025-01-26T10:00:00".to_string(),
+                    utc_time: "2025-01-26T09:00:00+00:00".to_string(),
+                },
+                ScheduleOccurrence {
+                    local_time: "2025-03-30T10:00:00".to_string(),
+                    utc_time: "2025-03-30T08:00:00+00:00".to_string(), // CEST
+                },
+            ]
+        );
+
+        let too_many = PreviewOccurrencesRequest {
+            count: MAX_PREVIEW_OCCURRENCES + 1,
+            ..request
+        };
+        assert!(preview_occurrences(&too_many, utc("2025-01-01T00:00:00Z")).is_err());
+    }
+
+    #[test]
+    fn test_is_schedule_due_after_trigger() {
+        let mut s = schedule(ScheduleFrequency::Daily, "09:00", "Europe/Berlin");
+        s.last_triggered_at = Some("2025-01-15 08:00:30".to_string());
+
+        assert!(!is_schedule_due(&s, utc("2025-01-15T20:00:00Z")).unwrap());
+        assert!(is_schedule_due(&s, utc("2025-01-16T08:00:00Z")).unwrap());
+
+        s.enabled = false;
+        assert!(!is_schedule_due(&s, utc("2025-01-16T08:00:00Z")).unwrap());
+    }
+
+    #[test]
+    fn test_missed_occurrences_since_last_trigger_or_change() {
+        let mut s = schedule(ScheduleFrequency::Daily, "09:00", "UTC");
+        s.last_triggered_at = Some("2025-01-10 09:00:10".to_string());
+        s.updated_at = "2025-01-01 00:00:00".to_string();
+
+        let missed = missed_occurrences(&s, utc("2025-01-13T12:00:00Z")).unwrap();
+        let local: Vec<_> = missed.iter().map(|(l, _)| format_dtstart(*l)).collect();
+        assert_eq!(
+            local,
+            vec![
+                "2025-01-11T09:00:00",
+                "2025-01-12T09:00:00",
+                "2025-01-13T09:00:00"
+            ]
+        );
+
+        // Edited after the last trigger: older occurrences belong to the old rule
+        s.updated_at = "2025-01-12 10:00:00".to_string();
+        let missed = missed_occurrences(&s, utc("2025-01-13T12:00:00Z")).unwrap();
+        assert_eq!(missed.len(), 1);
+
+        // Long absences keep only the most recent occurrences
+        let missed = missed_occurrences(&s, utc("2026-01-01T00:00:00Z")).unwrap();
+        assert_eq!(missed.len(), MAX_MISSED_OCCURRENCES);
+        assert_eq!(
+            format_dtstart(missed.last().unwrap().0),
+            "2025-12-31T09:00:00"
+        );
+    }
 }
@@ -0,0 +1,91 @@
+// Notification delivery for the scheduler
+//
+// The scheduler builds `Notification`s and hands them to a `Notifier`. The app
+// uses `TauriNotifier`; tests use `RecordingNotifier` to see what was sent.
+
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+use tauri::AppHandle;
+
+/// Title, body and deep-link payload of a notification
+#[derive(Debug, Clone, PartialEq)]
+pub struct Notification {
+    pub title: &'static str,
+    pub body: String,
+    pub extra: Vec<(&'static str, serde_json::Value)>,
+}
+
+impl Notification {
+    /// Value of a payload key
+    pub fn extra(&self, key: &str) -> Option<&serde_json::Value> {
+        self.extra.iter().find(|(k, _)| *k == key).map(|(_, v)| v)
+    }
+}
+
+/// Shows notifications to the user
+pub trait Notifier: Send + Sync {
+    fn notify(&self, notification: &Notification) -> anyhow::Result<()>;
+}
+
+impl<T: Notifier + ?Sized> Notifier for Arc<T> {
+    fn notify(&self, notification: &Notification) -> anyhow::Result<()> {
+        (**self).notify(notification)
+    }
+}
+
+/// System notifications through tauri-plugin-notification
+pub struct TauriNotifier {
+    app_handle: AppHandle,
+}
+
+impl TauriNotifier {
+    pub fn new(app_handle: AppHandle) -> Self {
+        Self { app_handle }
+    }
+}
+
+impl Notifier for TauriNotifier {
+    fn notify(&self, notification: &Notification) -> anyhow::Result<()> {
+        use tauri_plugin_notification::NotificationExt;
+
+        let mut builder = self
+            .app_handle
+            .notification()
+            .builder()
+            .title(notification.title)
+            .body(&notification.body)
+            .icon("icon"); // Use app icon
+        for (key, value) in &notification.extra {
+            builder = builder.extra(*key, value);
+        }
+
+        builder
+            .show()
+            .map_err(|e| anyhow::anyhow!("Failed to send notification: {}", e))
+    }
+}
+
+/// Keeps every notification instead of showing it
+#[derive(Debug, Default)]
+pub struct RecordingNotifier {
+    sent: Mutex<Vec<Notification>>,
+}
+
+impl RecordingNotifier {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Remove and return everything sent so far
+    pub fn take(&self) -> Vec<Notification> {
+        std::mem::take(&mut *self.sent.lock())
+    }
+}
+
+impl Notifier for RecordingNotifier {
+    fn notify(&self, notification: &Notification) -> anyhow::Result<()> {
+        self.sent.lock().push(notification.clone());
+        Ok(())
+    }
+}
@@ -3,9 +3,14 @@
 
 use tauri::State;
 
+use crate::config::NotificationSettings;
 use crate::{errors::ToCommandError, AppState, CommandError};
 
-use super::models::AssessmentSchedule;
+use super::adaptive::{AdaptiveRule, FrequencyChange};
+use super::models::{
+    preview_occurrences, AssessmentSchedule, MissedReminder, PreviewOccurrencesRequest,
+    ReminderAdherence, ReminderEvent, ScheduleOccurrence,
+};
 use super::repository::SchedulingRepository;
 
 /// T168: Get all schedules (optionally filtered to enabled only)
@@ -29,3 +34,87 @@ pub fn get_schedule(id: i32, state: State<AppState>) -> Result<AssessmentSchedul
 
     repo.get_schedule(id).map_err(|e| e.to_command_error())
 }
+
+/// List the next occurrences of a recurrence rule, e.g. to preview it while editing
+#[tauri::command]
+#[specta::specta]
+pub fn preview_schedule_occurrences(
+    request: PreviewOccurrencesRequest,
+) -> Result<Vec<ScheduleOccurrence>, CommandError> {
+    preview_occurrences(&request, chrono::Utc::now()).map_err(|e| e.to_command_error())
+}
+
+/// Reminder events (fired, snoozed, dismissed, completed) of a schedule, newest first
+#[tauri::command]
+#[specta::specta]
+pub fn get_reminder_events(
+    schedule_id: i32,
+    limit: Option<i32>,
+    state: State<AppState>,
+) -> Result<Vec<ReminderEvent>, CommandError> {
+    let repo = SchedulingRepository::new(state.db.clone());
+
+    repo.get_reminder_events(schedule_id, limit)
+        .map_err(|e| e.to_command_error())
+}
+
+/// Per-schedule adherence for reminders fired between `from_date` and `to_date` (UTC)
+#[tauri::command]
+#[specta::specta]
+pub fn get_reminder_adherence(
+    from_date: Option<String>,
+    to_date: Option<String>,
+    state: State<AppState>,
+) -> Result<Vec<ReminderAdherence>, CommandError> {
+    let repo = SchedulingRepository::new(state.db.clone());
+
+    repo.get_reminder_adherence(from_date, to_date)
+        .map_err(|e| e.to_command_error())
+}
+
+/// Reminders that fell due while the app was closed, newest first
+#[tauri::command]
+#[specta::specta]
+pub fn get_missed_reminders(
+    include_acknowledged: bool,
+    state: State<AppState>,
+) -> Result<Vec<MissedReminder>, CommandError> {
+    let repo = SchedulingRepository::new(state.db.clone());
+
+    repo.get_missed_reminders(include_acknowledged)
+        .map_err(|e| e.to_command_error())
+}
+
+/// Adaptive frequency rules of a schedule, in evaluation order
+#[tauri::command]
+#[specta::specta]
+pub fn get_adaptive_rules(
+    schedule_id: i32,
+    state: State<AppState>,
+) -> Result<Vec<AdaptiveRule>, CommandError> {
+    let repo = SchedulingRepository::new(state.db.clone());
+
+    repo.get_adaptive_rules(schedule_id)
+        .map_err(|e| e.to_command_error())
+}
+
+/// Frequency changes made by adaptive rules, newest first, with their reasons
+#[tauri::command]
+#[specta::specta]
+pub fn get_frequency_changes(
+    schedule_id: Option<i32>,
+    limit: Option<i32>,
+    state: State<AppState>,
+) -> Result<Vec<FrequencyChange>, CommandError> {
+    let repo = SchedulingRepository::new(state.db.clone());
+
+    repo.get_frequency_changes(schedule_id, limit)
+        .map_err(|e| e.to_command_error())
+}
+
+/// Current quiet hours, quiet-time policy, pause and notification switch
+#[tauri::command]
+#[specta::specta]
+pub fn get_notification_settings(state: State<AppState>) -> NotificationSettings {
+    state.config.lock().notification_settings()
+}
@@ -0,0 +1,291 @@
+// Quiet hours and do-not-disturb for reminder notifications
+//
+// Quiet-hours windows are set per weekday in the system timezone. A window
+// whose end is not after its start runs overnight into the next day, so
+// "22:00-07:00" on Friday covers Friday 22:00 to Saturday 07:00. Reminders can
+// also be paused for everything until a given instant.
+
+use chrono::{DateTime, Datelike, NaiveTime, Timelike, Utc};
+use chrono_tz::Tz;
+
+use super::models::SchedulingError;
+use crate::config::{NotificationSettings, QuietHoursWindow, QuietTimePolicy};
+
+/// Most quiet-hours windows that can be configured
+pub const MAX_QUIET_HOURS_WINDOWS: usize = 28;
+
+/// Whether the scheduler may show notifications right now
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum NotificationGate {
+    /// Send notifications as usual
+    Allow,
+    /// Keep everything pending until notifications are allowed again
+    Defer,
+    /// Consume due notifications without showing them
+    Drop,
+}
+
+impl QuietHoursWindow {
+    fn times(&self) -> Result<(NaiveTime, NaiveTime), SchedulingError> {
+        let parse = |time: &str| {
+            NaiveTime::parse_from_str(time, "%H:%M")
+                .ok()
+                .filter(|_| time.len() == 5)
+                .ok_or_else(|| SchedulingError::InvalidTimeFormat(time.to_string()))
+        };
+        Ok((parse(&self.start)?, parse(&self.end)?))
+    }
+
+    /// Whether the local time falls inside this window
+    fn contains(&self, weekday: i32, time: NaiveTime) -> bool {
+        let Ok((start, end)) = self.times() else {
+            return false;
+        };
+        let previous_day = (weekday + 6) % 7;
+
+        if end > start {
+            self.weekday == weekday && time >= start && time < end
+        } else {
+            // Overnight (or a full 24 hours when start == end)
+            (self.weekday == weekday && time >= start)
+                || (self.weekday == previous_day && time < end)
+        }
+    }
+}
+
+impl NotificationSettings {
+    /// Validate windows and the pause time
+    pub fn validate(&self) -> Result<(), SchedulingError> {
+        if self.quiet_hours.len() > MAX_QUIET_HOURS_WINDOWS {
+            return Err(SchedulingError::InvalidQuietHours(format!(
+                "At most {} quiet-hours windows are allowed",
+                MAX_QUIET_HOURS_WINDOWS
+            )));
+        }
+        for window in &self.quiet_hours {
+            if !(0..=6).contains(&window.weekday) {
+                return Err(SchedulingError::InvalidDayOfWeek(window.weekday));
+            }
+            window.times()?;
+        }
+        if let Some(until) = &self.paused_until {
+            parse_pause_time(until)?;
+        }
+        Ok(())
+    }
+
+    /// Whether `now` falls inside a quiet-hours window, in timezone `tz`
+    pub fn is_quiet_time(&self, now: DateTime<Utc>, tz: &Tz) -> bool {
+        let local = now.with_timezone(tz);
+        let weekday = local.weekday().num_days_from_sunday() as i32;
+        let time =
+            NaiveTime::from_hms_opt(local.hour(), local.minute(), 0).unwrap_or(NaiveTime::MIN);
+
+        self.quiet_hours
+            .iter()
+            .any(|window| window.contains(weekday, time))
+    }
+
+    /// Whether reminders are paused at `now`
+    pub fn is_paused(&self, now: DateTime<Utc>) -> bool {
+        self.paused_until
+            .as_deref()
+            .and_then(|until| parse_pause_time(until).ok())
+            .is_some_and(|until| now < until)
+    }
+
+    /// What the scheduler should do with notifications due at `now`.
+    ///
+    /// Disabled notifications are always dropped, so enabling them again
+    /// doesn't release a backlog. Quiet hours and pauses follow
+    /// `quiet_time_policy`.
+    pub fn gate(&self, now: DateTime<Utc>, tz: &Tz) -> NotificationGate {
+        if !self.notification_enabled {
+            return NotificationGate::Drop;
+        }
+        if self.is_paused(now) || self.is_quiet_time(now, tz) {
+            return match self.quiet_time_policy {
+                QuietTimePolicy::Defer => NotificationGate::Defer,
+                QuietTimePolicy::Drop => NotificationGate::Drop,
+            };
+        }
+        NotificationGate::Allow
+    }
+}
+
+/// Settings with reminders paused until `until`, which must be in the future
+pub fn paused_until(
+    settings: NotificationSettings,
+    until: &str,
+    now: DateTime<Utc>,
+) -> Result<NotificationSettings, SchedulingError> {
+    if parse_pause_time(until)? <= now {
+        return Err(SchedulingError::InvalidPause(
+            "Pause time must be in the future".to_string(),
+        ));
+    }
+    Ok(NotificationSettings {
+        paused_until: Some(until.to_string()),
+        ..settings
+    })
+}
+
+/// Parse an RFC 3339 pause end time
+pub fn parse_pause_time(until: &str) -> Result<DateTime<Utc>, SchedulingError> {
+    DateTime::parse_from_rfc3339(until)
+        .map(|t| t.with_timezone(&Utc))
+        .map_err(|_| SchedulingError::InvalidPause(format!("Not an RFC 3339 time: {}", until)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s)
+            .expect("Invalid test timestamp")
+            .with_timezone(&Utc)
+    }
+
+    fn window(weekday: i32, start: &str, end: &str) -> QuietHoursWindow {
+        QuietHoursWindow {
+            weekday,
+            start: start.to_string(),
+            end: end.to_string(),
+        }
+    }
+
+    fn settings(quiet_hours: Vec<QuietHoursWindow>) -> NotificationSettings {
+        NotificationSettings {
+            notification_enabled: true,
+            quiet_hours,
+            quiet_time_policy: QuietTimePolicy::Defer,
+            paused_until: None,
+        }
+    }
+
+    // ========================================================================
+    // Quiet-hours windows
+    // ========================================================================
+
+    #[test]
+    fn test_same_day_window() {
+        // Wednesday 12:00-13:00
+        let s = settings(vec![window(3, "12:00", "13:00")]);
+        let tz = Tz::UTC;
+
+        assert!(s.is_quiet_time(utc("2025-03-05T12:00:00Z"), &tz)); // Wednesday
+        assert!(s.is_quiet_time(utc("2025-03-05T12:59:00Z"), &tz));
+        assert!(!s.is_quiet_time(utc("2025-03-05T13:00:00Z"), &tz));
+        assert!(!s.is_quiet_time(utc("2025-03-06T12:30:00Z"), &tz)); // Thursday
+    }
+
+    #[test]
+    fn test_overnight_window_spills_into_next_day() {
+        // Friday 22:00 to Saturday 07:00
+        let s = settings(vec![window(5, "22:00", "07:00")]);
+        let tz = Tz::UTC;
+
+        assert!(!s.is_quiet_time(utc("2025-03-07T21:59:00Z"), &tz));
+        assert!(s.is_quiet_time(utc("2025-03-07T23:30:00Z"), &tz)); // Friday night
+        assert!(s.is_quiet_time(utc("2025-03-08T06:59:00Z"), &tz)); // Saturday morning
+        assert!(!s.is_quiet_time(utc("2025-03-08T07:00:00Z"), &tz));
+        // Saturday night is not covered
+        assert!(!s.is_quiet_time(utc("2025-03-08T23:00:00Z"), &tz));
+        // Sunday -> Saturday wrap for a Saturday window
+        let s = settings(vec![window(6, "23:00", "08:00")]);
+        assert!(s.is_quiet_time(utc("2025-03-09T07:00:00Z"), &tz)); // Sunday morning
+    }
+
+    #[test]
+    fn test_windows_use_local_time() {
+        // 22:00-07:00 every night in Berlin (UTC+1 in winter)
+        let s = settings((0..7).map(|d| window(d, "22:00", "07:00")).collect());
+        let tz: Tz = "Europe/Berlin".parse().unwrap();
+
+        assert!(s.is_quiet_time(utc("2025-01-15T21:30:00Z"), &tz)); // 22:30 local
+        assert!(!s.is_quiet_time(utc("2025-01-15T20:30:00Z"), &tz)); // 21:30 local
+        assert!(!s.is_quiet_time(utc("2025-01-16T06:00:00Z"), &tz)); // 07:00 local
+    }
+
+    #[test]
+    fn test_validate_windows() {
+        assert!(settings(vec![window(0, "22:00", "07:00")])
+            .validate()
+            .is_ok());
+        assert!(matches!(
+            settings(vec![window(7, "22:00", "07:00")]).validate(),
+            Err(SchedulingError::InvalidDayOfWeek(7))
+        ));
+        assert!(matches!(
+            settings(vec![window(1, "9:00", "10:00")]).validate(),
+            Err(SchedulingError::InvalidTimeFormat(_))
+        ));
+        assert!(matches!(
+            settings(vec![window(1, "09:00", "24:00")]).validate(),
+            Err(SchedulingError::InvalidTimeFormat(_))
+        ));
+        assert!(settings(vec![
+            window(1, "09:00", "10:00");
+            MAX_QUIET_HOURS_WINDOWS + 1
+        ])
+        .validate()
+        .is_err());
+
+        let mut paused = settings(Vec::new());
+        paused.paused_until = Some("tomorrow".to_string());
+        assert!(matches!(
+            paused.validate(),
+            Err(SchedulingError::InvalidPause(_))
+        ));
+    }
+
+    #[test]
+    fn test_paused_until_must_be_in_the_future() {
+        let now = utc("2025-03-05T12:00:00Z");
+
+        let paused = paused_until(settings(Vec::new()), "2025-03-05T18:00:00+01:00", now)
+            .expect("Failed to pause");
+        assert!(paused.is_paused(now));
+        assert!(!paused.is_paused(utc("2025-03-05T17:00:00Z")));
+
+        assert!(matches!(
+            paused_until(settings(Vec::new()), "2025-03-05T11:00:00Z", now),
+            Err(SchedulingError::InvalidPause(_))
+        ));
+    }
+
+    // ========================================================================
+    // Gate
+    // ========================================================================
+
+    #[test]
+    fn test_gate_follows_policy_pause_and_enabled_flag() {
+        let tz = Tz::UTC;
+        let mut s = settings(vec![window(3, "12:00", "13:00")]);
+        let quiet = utc("2025-03-05T12:30:00Z");
+        let loud = utc("2025-03-05T14:00:00Z");
+
+        assert_eq!(s.gate(loud, &tz), NotificationGate::Allow);
+        assert_eq!(s.gate(quiet, &tz), NotificationGate::Defer);
+
+        s.quiet_time_policy = QuietTimePolicy::Drop;
+        assert_eq!(s.gate(quiet, &tz), NotificationGate::Drop);
+
+        // Paused until 15:00 behaves like quiet time
+        s.paused_until = Some("2025-03-05T15:00:00Z".to_string());
+        assert_eq!(s.gate(loud, &tz), NotificationGate::Drop);
+        assert_eq!(
+            s.gate(utc("2025-03-05T15:00:00Z"), &tz),
+            NotificationGate::Allow
+        );
+
+        // Disabled notifications are never deferred
+        s.quiet_time_policy = QuietTimePolicy::Defer;
+        s.notification_enabled = false;
+        assert_eq!(
+            s.gate(utc("2025-03-06T10:00:00Z"), &tz),
+            NotificationGate::Drop
+        );
+    }
+}
@@ -0,0 +1,1162 @@
+// Scheduling recurrence rules
+// RFC 5545 RRULE/EXDATE subset used to expand reminder occurrences
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::str::FromStr;
+
+use chrono::{
+    DateTime, Datelike, Duration, Months, NaiveDate, NaiveDateTime, NaiveTime, Timelike, Utc,
+    Weekday,
+};
+use chrono_tz::Tz;
+
+use super::models::{resolve_local_time, ScheduleFrequency, SchedulingError};
+
+/// How far past the last occurrence (or the search start) expansion keeps looking
+/// before concluding a rule has no further occurrences. Leap-day rules can go
+/// eight years without one.
+const LOOKAHEAD_YEARS: i32 = 10;
+
+/// Supported RRULE frequencies. Sub-daily frequencies (HOURLY, MINUTELY,
+/// SECONDLY) are rejected; use BYHOUR/BYMINUTE for several times a day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecurrenceFrequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+impl RecurrenceFrequency {
+    fn as_str(&self) -> &'static str {
+        match self {
+            RecurrenceFrequency::Daily => "DAILY",
+            RecurrenceFrequency::Weekly => "WEEKLY",
+            RecurrenceFrequency::Monthly => "MONTHLY",
+            RecurrenceFrequency::Yearly => "YEARLY",
+        }
+    }
+}
+
+/// UNTIL bound: a UTC instant (`...Z`) or a local (floating) date-time
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecurrenceUntil {
+    Utc(DateTime<Utc>),
+    Local(NaiveDateTime),
+}
+
+/// BYDAY entry, e.g. `MO`, `2TU` (second Tuesday) or `-1SU` (last Sunday)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ByDay {
+    pub ordinal: Option<i32>,
+    pub weekday: Weekday,
+}
+
+/// A parsed RRULE value, e.g. `FREQ=WEEKLY;BYDAY=MO,WE,FR;BYHOUR=8,20;BYMINUTE=0`
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecurrenceRule {
+    pub frequency: RecurrenceFrequency,
+    pub interval: u32,
+    pub count: Option<u32>,
+    pub until: Option<RecurrenceUntil>,
+    pub by_month: Vec<u32>,
+    pub by_month_day: Vec<i32>,
+    pub by_day: Vec<ByDay>,
+    pub by_hour: Vec<u32>,
+    pub by_minute: Vec<u32>,
+    pub by_set_pos: Vec<i32>,
+    pub week_start: Weekday,
+}
+
+fn weekday_code(weekday: Weekday) -> &'static str {
+    match weekday {
+        Weekday::Mon => "MO",
+        Weekday::Tue => "TU",
+        Weekday::Wed => "WE",
+        Weekday::Thu => "TH",
+        Weekday::Fri => "FR",
+        Weekday::Sat => "SA",
+        Weekday::Sun => "SU",
+    }
+}
+
+fn parse_weekday_code(code: &str) -> Option<Weekday> {
+    match code {
+        "MO" => Some(Weekday::Mon),
+        "TU" => Some(Weekday::Tue),
+        "WE" => Some(Weekday::Wed),
+        "TH" => Some(Weekday::Thu),
+        "FR" => Some(Weekday::Fri),
+        "SA" => Some(Weekday::Sat),
+        "SU" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+fn invalid(message: impl Into<String>) -> SchedulingError {
+    SchedulingError::InvalidRecurrence(message.into())
+}
+
+/// Parse a comma-separated list of integers, each within `range`
+fn parse_int_list<T>(
+    name: &str,
+    value: &str,
+    range: std::ops::RangeInclusive<i32>,
+    allow_negative: bool,
+) -> Result<Vec<T>, SchedulingError>
+where
+    T: TryFrom<i32>,
+{
+    value
+        .split(',')
+        .map(|item| {
+            let n: i32 = item
+                .parse()
+                .map_err(|_| invalid(format!("{} value '{}' is not a number", name, item)))?;
+            let magnitude = if allow_negative { n.abs() } else { n };
+            if n == 0 && !range.contains(&0) || !range.contains(&magnitude) {
+                return Err(invalid(format!("{} value {} is out of range", name, n)));
+            }
+            T::try_from(n).map_err(|_| invalid(format!("{} value {} is out of range", name, n)))
+        })
+        .collect()
+}
+
+fn parse_by_day(value: &str) -> Result<Vec<ByDay>, SchedulingError> {
+    value
+        .split(',')
+        .map(|item| {
+            if item.len() < 2 {
+                return Err(invalid(format!("Invalid BYDAY value '{}'", item)));
+            }
+            let (ordinal, code) = item.split_at(item.len() - 2);
+            let weekday = parse_weekday_code(code)
+                .ok_or_else(|| invalid(format!("Invalid weekday in BYDAY '{}'", item)))?;
+            let ordinal = if ordinal.is_empty() {
+                None
+            } else {
+                let n: i32 = ordinal
+                    .trim_start_matches('+')
+                    .parse()
+                    .map_err(|_| invalid(format!("Invalid BYDAY ordinal '{}'", item)))?;
+                if n == 0 || n.abs() > 53 {
+                    return Err(invalid(format!("BYDAY ordinal {} is out of range", n)));
+                }
+                Some(n)
+            };
+            Ok(ByDay { ordinal, weekday })
+        })
+        .collect()
+}
+
+fn parse_until(value: &str) -> Result<RecurrenceUntil, SchedulingError> {
+    let bad = || invalid(format!("Invalid UNTIL value '{}'", value));
+    if let Some(utc) = value.strip_suffix('Z') {
+        let dt = NaiveDateTime::parse_from_str(utc, "%Y%m%dT%H%M%S").map_err(|_| bad())?;
+        Ok(RecurrenceUntil::Utc(dt.and_utc()))
+    } else if value.contains('T') {
+        let dt = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S").map_err(|_| bad())?;
+        Ok(RecurrenceUntil::Local(dt))
+    } else {
+        // A date-only UNTIL includes the whole day
+        let date = NaiveDate::parse_from_str(value, "%Y%m%d").map_err(|_| bad())?;
+        Ok(RecurrenceUntil::Local(
+            date.and_hms_opt(23, 59, 59).ok_or_else(bad)?,
+        ))
+    }
+}
+
+impl FromStr for RecurrenceRule {
+    type Err = SchedulingError;
+
+    /// Parse an RRULE value, with or without the `RRULE:` prefix
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let upper = s.trim().to_ascii_uppercase();
+        let body = upper.strip_prefix("RRULE:").unwrap_or(&upper);
+        if body.is_empty() {
+            return Err(invalid("Recurrence rule is empty"));
+        }
+
+        let mut frequency = None;
+        let mut rule = RecurrenceRule {
+            frequency: RecurrenceFrequency::Daily,
+            interval: 1,
+            count: None,
+            until: None,
+            by_month: Vec::new(),
+            by_month_day: Vec::new(),
+            by_day: Vec::new(),
+            by_hour: Vec::new(),
+            by_minute: Vec::new(),
+            by_set_pos: Vec::new(),
+            week_start: Weekday::Mon,
+        };
+        let mut seen: Vec<&str> = Vec::new();
+
+        for part in body.split(';').filter(|p| !p.is_empty()) {
+            let (name, value) = part
+                .split_once('=')
+                .ok_or_else(|| invalid(format!("Malformed rule part '{}'", part)))?;
+            if seen.contains(&name) {
+                return Err(invalid(format!("{} is given more than once", name)));
+            }
+            seen.push(name);
+
+            match name {
+                "FREQ" => {
+                    frequency = Some(match value {
+                        "DAILY" => RecurrenceFrequency::Daily,
+                        "WEEKLY" => RecurrenceFrequency::Weekly,
+                        "MONTHLY" => RecurrenceFrequency::Monthly,
+                        "YEARLY" => RecurrenceFrequency::Yearly,
+                        "HOURLY" | "MINUTELY" | "SECONDLY" => {
+                            return Err(invalid(format!(
+                        "FREQ={} is not supported; use BYHOUR/BYMINUTE for several reminders a day",
+                        value
+                    )))
+                        }
+                        other => return Err(invalid(format!("Unknown FREQ '{}'", other))),
+                    })
+                }
+                "INTERVAL" => {
+                    rule.interval = value
+                        .parse()
+                        .ok()
+                        .filter(|n| *n >= 1)
+                        .ok_or_else(|| invalid("INTERVAL must be a positive number"))?
+                }
+                "COUNT" => {
+                    rule.count = Some(
+                        value
+                            .parse()
+                            .ok()
+                            .filter(|n| *n >= 1)
+                            .ok_or_else(|| invalid("COUNT must be a positive number"))?,
+                    )
+                }
+                "UNTIL" => rule.until = Some(parse_until(value)?),
+                "BYMONTH" => rule.by_month = parse_int_list("BYMONTH", value, 1..=12, false)?,
+                "BYMONTHDAY" => {
+                    rule.by_month_day = parse_int_list("BYMONTHDAY", value, 1..=31, true)?
+                }
+                "BYDAY" => rule.by_day = parse_by_day(value)?,
+                "BYHOUR" => rule.by_hour = parse_int_list("BYHOUR", value, 0..=23, false)?,
+                "BYMINUTE" => rule.by_minute = parse_int_list("BYMINUTE", value, 0..=59, false)?,
+                "BYSECOND" => {
+                    if value != "0" {
+                        return Err(invalid("BYSECOND is not supported"));
+                    }
+                }
+                "BYSETPOS" => rule.by_set_pos = parse_int_list("BYSETPOS", value, 1..=366, true)?,
+                "WKST" => {
+                    rule.week_start = parse_weekday_code(value)
+                        .ok_or_else(|| invalid(format!("Invalid WKST '{}'", value)))?
+                }
+                "BYWEEKNO" | "BYYEARDAY" => {
+                    return Err(invalid(format!("{} is not supported", name)))
+                }
+                other => return Err(invalid(format!("Unknown rule part '{}'", other))),
+            }
+        }
+
+        rule.frequency = frequency.ok_or_else(|| invalid("FREQ is required"))?;
+
+        if rule.count.is_some() && rule.until.is_some() {
+            return Err(invalid("COUNT and UNTIL cannot both be set"));
+        }
+        let has_ordinals = rule.by_day.iter().any(|d| d.ordinal.is_some());
+        if has_ordinals
+            && !matches!(
+                rule.frequency,
+                RecurrenceFrequency::Monthly | RecurrenceFrequency::Yearly
+            )
+        {
+            return Err(invalid(
+                "Numbered BYDAY values (e.g. -1SU) need FREQ=MONTHLY or FREQ=YEARLY",
+            ));
+        }
+        if !rule.by_month_day.is_empty() && rule.frequency == RecurrenceFrequency::Weekly {
+            return Err(invalid("BYMONTHDAY cannot be used with FREQ=WEEKLY"));
+        }
+        if !rule.by_set_pos.is_empty()
+            && rule.by_month.is_empty()
+            && rule.by_month_day.is_empty()
+            && rule.by_day.is_empty()
+            && rule.by_hour.is_empty()
+            && rule.by_minute.is_empty()
+        {
+            return Err(invalid("BYSETPOS needs another BY... rule part"));
+        }
+
+        for list in [&mut rule.by_hour, &mut rule.by_minute, &mut rule.by_month] {
+            list.sort_unstable();
+            list.dedup();
+        }
+
+        Ok(rule)
+    }
+}
+
+fn join<T: ToString>(values: &[T]) -> String {
+    values
+        .iter()
+        .map(|v| v.to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+impl fmt::Display for RecurrenceRule {
+    /// Canonical RRULE value (without the `RRULE:` prefix)
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "FREQ={}", self.frequency.as_str())?;
+        if self.interval != 1 {
+            write!(f, ";INTERVAL={}", self.interval)?;
+        }
+        if let Some(count) = self.count {
+            write!(f, ";COUNT={}", count)?;
+        }
+        match self.until {
+            Some(RecurrenceUntil::Utc(dt)) => write!(f, ";UNTIL={}", dt.format("%Y%m%dT%H%M%SZ"))?,
+            Some(RecurrenceUntil::Local(dt)) => write!(f, ";UNTIL={}", dt.format("%Y%m%dT%H%M%S"))?,
+            None => {}
+        }
+        if !self.by_month.is_empty() {
+            write!(f, ";BYMONTH={}", join(&self.by_month))?;
+        }
+        if !self.by_month_day.is_empty() {
+            write!(f, ";BYMONTHDAY={}", join(&self.by_month_day))?;
+        }
+        if !self.by_day.is_empty() {
+            let days: Vec<String> = self
+                .by_day
+                .iter()
+                .map(|d| match d.ordinal {
+                    Some(n) => format!("{}{}", n, weekday_code(d.weekday)),
+                    None => weekday_code(d.weekday).to_string(),
+                })
+                .collect();
+            write!(f, ";BYDAY={}", days.join(","))?;
+        }
+        if !self.by_hour.is_empty() {
+            write!(f, ";BYHOUR={}", join(&self.by_hour))?;
+        }
+        if !self.by_minute.is_empty() {
+            write!(f, ";BYMINUTE={}", join(&self.by_minute))?;
+        }
+        if !self.by_set_pos.is_empty() {
+            write!(f, ";BYSETPOS={}", join(&self.by_set_pos))?;
+        }
+        if self.week_start != Weekday::Mon {
+            write!(f, ";WKST={}", weekday_code(self.week_start))?;
+        }
+        Ok(())
+    }
+}
+
+fn days_in_month(date: NaiveDate) -> u32 {
+    let first = date.with_day(1).unwrap_or(date);
+    let next = first + Months::new(1);
+    (next - first).num_days() as u32
+}
+
+impl RecurrenceRule {
+    /// Equivalent rule for a simple frequency/time/day schedule.
+    ///
+    /// Monthly days past the 28th become "the last of days 28..=N" so short
+    /// months clamp to their final day instead of being skipped.
+    pub fn from_frequency(
+        frequency: &ScheduleFrequency,
+        time_of_day: &str,
+        day_of_week: Option<i32>,
+        day_of_month: Option<i32>,
+    ) -> Result<Self, SchedulingError> {
+        let time = NaiveTime::parse_from_str(time_of_day, "%H:%M")
+            .map_err(|_| SchedulingError::InvalidTimeFormat(time_of_day.to_string()))?;
+
+        let weekday = |kind: &str| -> Result<Vec<ByDay>, SchedulingError> {
+            let day = day_of_week.ok_or_else(|| {
+                SchedulingError::InvalidFrequency(format!(
+                    "day_of_week required for {} schedules",
+                    kind
+                ))
+            })?;
+            if !(0..=6).contains(&day) {
+                return Err(SchedulingError::InvalidDayOfWeek(day));
+            }
+            // day_of_week counts from Sunday, chrono from Monday
+            let weekday = Weekday::try_from(((day + 6) % 7) as u8)
+                .map_err(|_| SchedulingError::InvalidDayOfWeek(day))?;
+            Ok(vec![ByDay {
+                ordinal: None,
+                weekday,
+            }])
+        };
+
+        let mut rule = RecurrenceRule {
+            frequency: RecurrenceFrequency::Daily,
+            interval: 1,
+            count: None,
+            until: None,
+            by_month: Vec::new(),
+            by_month_day: Vec::new(),
+            by_day: Vec::new(),
+            by_hour: vec![time.hour()],
+            by_minute: vec![time.minute()],
+            by_set_pos: Vec::new(),
+            week_start: Weekday::Mon,
+        };
+
+        match frequency {
+            ScheduleFrequency::Daily => {}
+            ScheduleFrequency::Weekly => {
+                rule.frequency = RecurrenceFrequency::Weekly;
+                rule.by_day = weekday("weekly")?;
+            }
+            ScheduleFrequency::Biweekly => {
+                rule.frequency = RecurrenceFrequency::Weekly;
+                rule.interval = 2;
+                rule.by_day = weekday("biweekly")?;
+            }
+            ScheduleFrequency::Monthly => {
+                let day = day_of_month.ok_or_else(|| {
+                    SchedulingError::InvalidFrequency(
+                        "day_of_month required for monthly schedules".to_string(),
+                    )
+                })?;
+                if !(1..=31).contains(&day) {
+                    return Err(SchedulingError::InvalidDayOfMonth(day));
+                }
+                rule.frequency = RecurrenceFrequency::Monthly;
+                if day <= 28 {
+                    rule.by_month_day = vec![day];
+                } else {
+                    rule.by_month_day = (28..=day).collect();
+                    rule.by_set_pos = vec![-1];
+                }
+            }
+            ScheduleFrequency::Custom => {
+                return Err(SchedulingError::InvalidRecurrence(
+                    "Custom schedules need an rrule".to_string(),
+                ))
+            }
+        }
+
+        Ok(rule)
+    }
+
+    /// Fire at `time_of_day` ("HH:MM") instead. Rules with several times a
+    /// day are rejected, since one time can't say which of them to move.
+    pub fn set_time_of_day(&mut self, time_of_day: &str) -> Result<(), SchedulingError> {
+        let time = NaiveTime::parse_from_str(time_of_day, "%H:%M")
+            .map_err(|_| SchedulingError::InvalidTimeFormat(time_of_day.to_string()))?;
+        if self.by_hour.len() > 1 || self.by_minute.len() > 1 {
+            return Err(SchedulingError::InvalidRecurrence(
+                "time_of_day can't change a rule with several times a day; update its rrule instead"
+                    .to_string(),
+            ));
+        }
+        self.by_hour = vec![time.hour()];
+        self.by_minute = vec![time.minute()];
+        Ok(())
+    }
+
+    /// Earliest wall-clock time of day the rule fires at, as "HH:MM"
+    pub fn first_time_of_day(&self, dtstart: NaiveDateTime) -> String {
+        let hour = self.by_hour.first().copied().unwrap_or(dtstart.hour());
+        let minute = self.by_minute.first().copied().unwrap_or(dtstart.minute());
+        format!("{:02}:{:02}", hour, minute)
+    }
+
+    /// First date of period `index` (0 = the period containing `dtstart`)
+    fn period_start(&self, dtstart: NaiveDate, index: i64) -> Option<NaiveDate> {
+        let step = index.checked_mul(self.interval as i64)?;
+        match self.frequency {
+            RecurrenceFrequency::Daily => dtstart.checked_add_signed(Duration::try_days(step)?),
+            RecurrenceFrequency::Weekly => {
+                let offset = (7 + dtstart.weekday().num_days_from_monday()
+                    - self.week_start.num_days_from_monday())
+                    % 7;
+                let week_start = dtstart - Duration::days(offset as i64);
+                week_start.checked_add_signed(Duration::try_weeks(step)?)
+            }
+            RecurrenceFrequency::Monthly => dtstart
+                .with_day(1)?
+                .checked_add_months(Months::new(u32::try_from(step).ok()?)),
+            RecurrenceFrequency::Yearly => NaiveDate::from_ymd_opt(
+                dtstart.year().checked_add(i32::try_from(step).ok()?)?,
+                1,
+                1,
+            ),
+        }
+    }
+
+    /// Index of the last period that starts well before `date`, for skipping ahead
+    fn period_before(&self, dtstart: NaiveDate, date: NaiveDate) -> i64 {
+        let first = match self.period_start(dtstart, 0) {
+            Some(first) => first,
+            None => return 0,
+        };
+        let units = match self.frequency {
+            RecurrenceFrequency::Daily => (date - first).num_days(),
+            RecurrenceFrequency::Weekly => (date - first).num_days() / 7,
+            RecurrenceFrequency::Monthly => {
+                (date.year() - first.year()) as i64 * 12 + date.month() as i64
+                    - first.month() as i64
+            }
+            RecurrenceFrequency::Yearly => (date.year() - first.year()) as i64,
+        };
+        (units / self.interval as i64 - 1).max(0)
+    }
+
+    fn matches_month(&self, date: NaiveDate) -> bool {
+        self.by_month.is_empty() || self.by_month.contains(&date.month())
+    }
+
+    fn matches_month_day(&self, date: NaiveDate) -> bool {
+        let day = date.day() as i32;
+        let last = days_in_month(date) as i32;
+        self.by_month_day.iter().any(|&md| {
+            if md > 0 {
+                md == day
+            } else {
+                last + md + 1 == day
+            }
+        })
+    }
+
+    /// Whether `date` matches BYDAY within `scope` (the month or year for numbered entries)
+    fn matches_by_day(&self, date: NaiveDate, scope: (NaiveDate, NaiveDate)) -> bool {
+        self.by_day.iter().any(|by_day| {
+            if by_day.weekday != date.weekday() {
+                return false;
+            }
+            match by_day.ordinal {
+                None => true,
+                Some(n) if n > 0 => (date - scope.0).num_days() / 7 == (n - 1) as i64,
+                Some(n) => (scope.1 - date).num_days() / 7 == (-n - 1) as i64,
+            }
+        })
+    }
+
+    /// Dates of a month or year selected by BYMONTHDAY and BYDAY, or the
+    /// day of month of `dtstart` when neither is set
+    fn dates_in_scope(&self, scope: (NaiveDate, NaiveDate), dtstart: NaiveDate) -> Vec<NaiveDate> {
+        scope
+            .0
+            .iter_days()
+            .take_while(|d| *d <= scope.1)
+            .filter(|d| {
+                if self.by_month_day.is_empty() && self.by_day.is_empty() {
+                    d.day() == dtstart.day()
+                } else {
+                    (self.by_month_day.is_empty() || self.matches_month_day(*d))
+                        && (self.by_day.is_empty() || self.matches_by_day(*d, scope))
+                }
+            })
+            .collect()
+    }
+
+    fn month_scope(first: NaiveDate) -> (NaiveDate, NaiveDate) {
+        let last = first + Duration::days(days_in_month(first) as i64 - 1);
+        (first, last)
+    }
+
+    fn candidate_dates(&self, dtstart: NaiveDate, period: NaiveDate) -> Vec<NaiveDate> {
+        match self.frequency {
+            RecurrenceFrequency::Daily => {
+                let keep = self.matches_month(period)
+                    && (self.by_month_day.is_empty() || self.matches_month_day(period))
+                    && (self.by_day.is_empty()
+                        || self.by_day.iter().any(|d| d.weekday == period.weekday()));
+                if keep {
+                    vec![period]
+                } else {
+                    Vec::new()
+                }
+            }
+            RecurrenceFrequency::Weekly => period
+                .iter_days()
+                .take(7)
+                .filter(|d| {
+                    self.matches_month(*d)
+                        && if self.by_day.is_empty() {
+                            d.weekday() == dtstart.weekday()
+                        } else {
+                            self.by_day.iter().any(|b| b.weekday == d.weekday())
+                        }
+                })
+                .collect(),
+            RecurrenceFrequency::Monthly => {
+                if self.matches_month(period) {
+                    self.dates_in_scope(Self::month_scope(period), dtstart)
+                } else {
+                    Vec::new()
+                }
+            }
+            RecurrenceFrequency::Yearly => {
+                let year = period.year();
+                if self.by_month.is_empty()
+                    && self.by_month_day.is_empty()
+                    && self.by_day.is_empty()
+                {
+                    // Anniversary of dtstart (skipped in years without that date, e.g. Feb 29)
+                    NaiveDate::from_ymd_opt(year, dtstart.month(), dtstart.day())
+                        .into_iter()
+                        .collect()
+                } else if self.by_month.is_empty() && self.by_month_day.is_empty() {
+                    // BYDAY alone: numbered entries count within the year
+                    let last = NaiveDate::from_ymd_opt(year, 12, 31).unwrap_or(period);
+                    self.dates_in_scope((period, last), dtstart)
+                } else {
+                    let months: Vec<u32> = if self.by_month.is_empty() {
+                        (1..=12).collect()
+                    } else {
+                        self.by_month.clone()
+                    };
+                    months
+                        .into_iter()
+                        .filter_map(|m| NaiveDate::from_ymd_opt(year, m, 1))
+                        .flat_map(|first| self.dates_in_scope(Self::month_scope(first), dtstart))
+                        .collect()
+                }
+            }
+        }
+    }
+
+    /// Local occurrence times of the period starting at `period`, sorted, after BYSETPOS
+    fn period_occurrences(&self, dtstart: NaiveDateTime, period: NaiveDate) -> Vec<NaiveDateTime> {
+        let hours = if self.by_hour.is_empty() {
+            vec![dtstart.hour()]
+        } else {
+            self.by_hour.clone()
+        };
+        let minutes = if self.by_minute.is_empty() {
+            vec![dtstart.minute()]
+        } else {
+            self.by_minute.clone()
+        };
+
+        let times: Vec<NaiveTime> = hours
+            .iter()
+            .flat_map(|&h| {
+                minutes
+                    .iter()
+                    .filter_map(move |&m| NaiveTime::from_hms_opt(h, m, dtstart.second()))
+            })
+            .collect();
+        let mut occurrences: Vec<NaiveDateTime> = self
+            .candidate_dates(dtstart.date(), period)
+            .into_iter()
+            .flat_map(|date| times.iter().map(move |t| date.and_time(*t)))
+            .collect();
+        occurrences.sort_unstable();
+
+        if !self.by_set_pos.is_empty() {
+            let n = occurrences.len() as i32;
+            let mut picked: Vec<NaiveDateTime> = self
+                .by_set_pos
+                .iter()
+                .filter_map(|&pos| {
+                    let i = if pos > 0 { pos - 1 } else { n + pos };
+                    (0..n).contains(&i).then(|| occurrences[i as usize])
+                })
+                .collect();
+            picked.sort_unstable();
+            picked.dedup();
+            occurrences = picked;
+        }
+
+        occurrences
+    }
+}
+
+/// EXDATE entry: a specific occurrence, or every occurrence on a date
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExceptionDate {
+    Date(NaiveDate),
+    DateTime(NaiveDateTime),
+}
+
+impl ExceptionDate {
+    fn excludes(&self, local: NaiveDateTime) -> bool {
+        match self {
+            ExceptionDate::Date(date) => local.date() == *date,
+            ExceptionDate::DateTime(dt) => local == *dt,
+        }
+    }
+}
+
+impl FromStr for ExceptionDate {
+    type Err = SchedulingError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match parse_local(s) {
+            Some((date, None)) => Ok(ExceptionDate::Date(date)),
+            Some((date, Some(time))) => Ok(ExceptionDate::DateTime(date.and_time(time))),
+            None => Err(invalid(format!("Invalid exception date '{}'", s))),
+        }
+    }
+}
+
+impl fmt::Display for ExceptionDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExceptionDate::Date(date) => write!(f, "{}", date.format("%Y-%m-%d")),
+            ExceptionDate::DateTime(dt) => write!(f, "{}", dt.format("%Y-%m-%dT%H:%M:%S")),
+        }
+    }
+}
+
+/// Parse a local date or date-time in ISO 8601 ("2025-03-10", "2025-03-10T08:00[:00]")
+/// or iCalendar basic format ("20250310", "20250310T080000")
+fn parse_local(s: &str) -> Option<(NaiveDate, Option<NaiveTime>)> {
+    let s = s.trim();
+    for format in [
+        "%Y-%m-%dT%H:%M:%S",
+        "%Y-%m-%dT%H:%M",
+        "%Y-%m-%d %H:%M:%S",
+        "%Y-%m-%d %H:%M",
+        "%Y%m%dT%H%M%S",
+    ] {
+        if let Ok(dt) = NaiveDateTime::parse_from_str(s, format) {
+            return Some((dt.date(), Some(dt.time())));
+        }
+    }
+    for format in ["%Y-%m-%d", "%Y%m%d"] {
+        if let Ok(date) = NaiveDate::parse_from_str(s, format) {
+            return Some((date, None));
+        }
+    }
+    None
+}
+
+/// Parse a local DTSTART; a bare date starts at midnight
+pub fn parse_dtstart(s: &str) -> Result<NaiveDateTime, SchedulingError> {
+    parse_local(s)
+        .map(|(date, time)| date.and_time(time.unwrap_or(NaiveTime::MIN)))
+        .ok_or_else(|| invalid(format!("Invalid start date '{}'", s)))
+}
+
+/// Canonical storage format of a DTSTART
+pub fn format_dtstart(dtstart: NaiveDateTime) -> String {
+    dtstart.format("%Y-%m-%dT%H:%M:%S").to_string()
+}
+
+/// A rule anchored at a local DTSTART, minus exception dates
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecurrenceSet {
+    pub rule: RecurrenceRule,
+    pub dtstart: NaiveDateTime,
+    pub exdates: Vec<ExceptionDate>,
+}
+
+impl RecurrenceSet {
+    pub fn parse(rrule: &str, dtstart: &str, exdates: &[String]) -> Result<Self, SchedulingError> {
+        Ok(RecurrenceSet {
+            rule: rrule.parse()?,
+            dtstart: parse_dtstart(dtstart)?,
+            exdates: exdates
+                .iter()
+                .map(|e| e.parse())
+                .collect::<Result<Vec<_>, _>>()?,
+        })
+    }
+
+    /// Occurrences strictly after `after`, as (local wall-clock time, instant) pairs.
+    ///
+    /// Local times are resolved with [`resolve_local_time`], so DST gaps and
+    /// overlaps behave like simple schedules. COUNT counts excluded (EXDATE)
+    /// occurrences too, as RFC 5545 requires.
+    pub fn occurrences_after(&self, tz: Tz, after: DateTime<Utc>) -> Occurrences<'_> {
+        let after_local = after.with_timezone(&tz).naive_local();
+        let dtstart = self.dtstart.date();
+
+        // Without COUNT, periods that end before `after` can be skipped
+        let period = if self.rule.count.is_none() {
+            self.rule
+                .period_before(dtstart, after_local.date() - Duration::days(2))
+        } else {
+            0
+        };
+        let horizon_from = after_local.date().max(dtstart);
+
+        Occurrences {
+            set: self,
+            tz,
+            after,
+            period,
+            buffer: VecDeque::new(),
+            generated: 0,
+            last: None,
+            horizon: horizon_from
+                .checked_add_months(Months::new(12 * LOOKAHEAD_YEARS as u32))
+                .unwrap_or(NaiveDate::MAX),
+            done: false,
+        }
+    }
+}
+
+/// Lazy iterator over a recurrence set's occurrences
+pub struct Occurrences<'a> {
+    set: &'a RecurrenceSet,
+    tz: Tz,
+    after: DateTime<Utc>,
+    period: i64,
+    buffer: VecDeque<NaiveDateTime>,
+    generated: u32,
+    last: Option<DateTime<Utc>>,
+    horizon: NaiveDate,
+    done: bool,
+}
+
+impl Iterator for Occurrences<'_> {
+    type Item = (NaiveDateTime, DateTime<Utc>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let rule = &self.set.rule;
+        loop {
+            if let Some(local) = self.buffer.pop_front() {
+                if local < self.set.dtstart {
+                    continue;
+                }
+                let instant = resolve_local_time(&self.tz, local);
+                let past_until = match rule.until {
+                    Some(RecurrenceUntil::Local(until)) => local > until,
+                    Some(RecurrenceUntil::Utc(until)) => instant > until,
+                    None => false,
+                };
+                if past_until {
+                    self.done = true;
+                    self.buffer.clear();
+                    return None;
+                }
+                if let Some(count) = rule.count {
+                    if self.generated >= count {
+                        self.done = true;
+                        self.buffer.clear();
+                        return None;
+                    }
+                    self.generated += 1;
+                }
+                if self.set.exdates.iter().any(|e| e.excludes(local)) {
+                    continue;
+                }
+                // Two local times can resolve to the same instant inside a DST gap
+                if instant <= self.after || self.last == Some(instant) {
+                    continue;
+                }
+                self.last = Some(instant);
+                if let Some(horizon) = local
+                    .date()
+                    .checked_add_months(Months::new(12 * LOOKAHEAD_YEARS as u32))
+                {
+                    self.horizon = self.horizon.max(horizon);
+                }
+                return Some((local, instant));
+            }
+
+            if self.done {
+                return None;
+            }
+
+            let period_start = match rule.period_start(self.set.dtstart.date(), self.period) {
+                Some(start) if start <= self.horizon => start,
+                _ => {
+                    self.done = true;
+                    return None;
+                }
+            };
+            if let Some(RecurrenceUntil::Local(until)) = rule.until {
+                if period_start > until.date() {
+                    self.done = true;
+                    return None;
+                }
+            }
+            self.buffer
+                .extend(rule.period_occurrences(self.set.dtstart, period_start));
+            self.period += 1;
+        }
+    }
+}
+
+/// Default DTSTART for a simple schedule created at `now`: local midnight today,
+/// or for biweekly schedules the next matching weekday so the first reminder
+/// comes on the first such day (not up to two weeks later).
+pub fn default_dtstart(
+    frequency: &ScheduleFrequency,
+    day_of_week: Option<i32>,
+    tz: &Tz,
+    now: DateTime<Utc>,
+) -> NaiveDateTime {
+    let today = now.with_timezone(tz).date_naive();
+    let date = match (frequency, day_of_week) {
+        (ScheduleFrequency::Biweekly, Some(day)) => {
+            let current = today.weekday().num_days_from_sunday() as i64;
+            today + Duration::days((day as i64 - current).rem_euclid(7))
+        }
+        _ => today,
+    };
+    date.and_time(NaiveTime::MIN)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn local(s: &str) -> NaiveDateTime {
+        parse_dtstart(s).expect("Invalid test date")
+    }
+
+    fn utc(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s)
+            .expect("Invalid test timestamp")
+            .with_timezone(&Utc)
+    }
+
+    fn next_local(set: &RecurrenceSet, after: &str, n: usize) -> Vec<String> {
+        set.occurrences_after(Tz::UTC, utc(after))
+            .take(n)
+            .map(|(local, _)| format_dtstart(local))
+            .collect()
+    }
+
+    fn set(rrule: &str, dtstart: &str) -> RecurrenceSet {
+        RecurrenceSet::parse(rrule, dtstart, &[]).expect("Failed to parse recurrence")
+    }
+
+    // ========================================
+    // Parsing Tests
+    // ========================================
+
+    #[test]
+    fn test_parse_and_canonical_display() {
+        let rule: RecurrenceRule = "rrule:freq=weekly;byday=mo,we,fr;byminute=0;byhour=20,8"
+            .parse()
+            .expect("Failed to parse rule");
+
+        assert_eq!(rule.frequency, RecurrenceFrequency::Weekly);
+        assert_eq!(rule.by_hour, vec![8, 20]);
+        assert_eq!(
+            rule.to_string(),
+            "FREQ=WEEKLY;BYDAY=MO,WE,FR;BYHOUR=8,20;BYMINUTE=0"
+        );
+
+        let last_sunday: RecurrenceRule = "FREQ=MONTHLY;BYDAY=-1SU;BYHOUR=10;BYMINUTE=0"
+            .parse()
+            .expect("Failed to parse rule");
+        assert_eq!(
+            last_sunday.by_day,
+            vec![ByDay {
+                ordinal: Some(-1),
+                weekday: Weekday::Sun
+            }]
+        );
+        assert_eq!(
+            last_sunday.to_string(),
+            "FREQ=MONTHLY;BYDAY=-1SU;BYHOUR=10;BYMINUTE=0"
+        );
+    }
+
+    #[test]
+    fn test_set_time_of_day() {
+        let mut rule: RecurrenceRule = "FREQ=WEEKLY;BYDAY=MO".parse().unwrap();
+        rule.set_time_of_day("07:45").expect("Failed to set time");
+        assert_eq!(
+            rule.to_string(),
+            "FREQ=WEEKLY;BYDAY=MO;BYHOUR=7;BYMINUTE=45"
+        );
+
+        let mut twice_daily: RecurrenceRule = "FREQ=DAILY;BYHOUR=8,20;BYMINUTE=0".parse().unwrap();
+        assert!(matches!(
+            twice_daily.set_time_of_day("09:00"),
+            Err(SchedulingError::InvalidRecurrence(_))
+        ));
+        assert!(matches!(
+            rule.set_time_of_day("7:45pm"),
+            Err(SchedulingError::InvalidTimeFormat(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_rules() {
+        for rule in [
+            "",
+            "BYDAY=MO",
+            "FREQ=HOURLY",
+            "FREQ=FORTNIGHTLY",
+            "FREQ=DAILY;COUNT=3;UNTIL=20250101",
+            "FREQ=WEEKLY;BYDAY=-1SU",
+            "FREQ=WEEKLY;BYMONTHDAY=3",
+            "FREQ=DAILY;BYHOUR=24",
+            "FREQ=MONTHLY;BYMONTHDAY=0",
+            "FREQ=DAILY;INTERVAL=0",
+            "FREQ=DAILY;FREQ=WEEKLY",
+            "FREQ=DAILY;BYSETPOS=1",
+            "FREQ=YEARLY;BYWEEKNO=1",
+        ] {
+            assert!(
+                matches!(
+                    rule.parse::<RecurrenceRule>(),
+                    Err(SchedulingError::InvalidRecurrence(_))
+                ),
+                "expected '{}' to be rejected",
+                rule
+            );
+        }
+    }
+
+    // ========================================
+    // Expansion Tests
+    // ========================================
+
+    #[test]
+    fn test_mon_wed_fri_twice_a_day() {
+        let s = set(
+            "FREQ=WEEKLY;BYDAY=MO,WE,FR;BYHOUR=8,20;BYMINUTE=0",
+            "2025-01-01",
+        );
+
+        // 2025-03-03 is a Monday
+        assert_eq!(
+            next_local(&s, "2025-03-03T09:00:00Z", 5),
+            vec![
+                "2025-03-03T20:00:00",
+                "2025-03-05T08:00:00",
+                "2025-03-05T20:00:00",
+                "2025-03-07T08:00:00",
+                "2025-03-07T20:00:00",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_last_sunday_of_month() {
+        let s = set("FREQ=MONTHLY;BYDAY=-1SU;BYHOUR=10;BYMINUTE=0", "2025-01-01");
+
+        assert_eq!(
+            next_local(&s, "2025-01-01T00:00:00Z", 3),
+            vec![
+                "2025-01-26T10:00:00",
+                "2025-02-23T10:00:00",
+                "2025-03-30T10:00:00",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_monthly_day_31_clamps_like_legacy_schedules() {
+        let rule =
+            RecurrenceRule::from_frequency(&ScheduleFrequency::Monthly, "09:00", None, Some(31))
+                .expect("Failed to build rule");
+        let s = RecurrenceSet {
+            rule,
+            dtstart: local("2025-01-01"),
+            exdates: Vec::new(),
+        };
+
+        assert_eq!(
+            next_local(&s, "2025-01-31T10:00:00Z", 3),
+            vec![
+                "2025-02-28T09:00:00",
+                "2025-03-31T09:00:00",
+                "2025-04-30T09:00:00",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_interval_is_anchored_to_dtstart() {
+        // Every other Monday starting the week of Monday 2025-01-06
+        let s = set(
+            "FREQ=WEEKLY;INTERVAL=2;BYDAY=MO;BYHOUR=9;BYMINUTE=0",
+            "2025-01-06",
+        );
+
+        assert_eq!(
+            next_local(&s, "2025-03-01T00:00:00Z", 2),
+            vec!["2025-03-03T09:00:00", "2025-03-17T09:00:00"]
+        );
+    }
+
+    #[test]
+    fn test_count_until_and_exdates() {
+        let mut s = set("FREQ=DAILY;COUNT=3;BYHOUR=9;BYMINUTE=0", "2025-05-01");
+        s.exdates = vec!["2025-05-02".parse().expect("Invalid exdate")];
+
+        // The excluded 2 May still counts towards COUNT
+        assert_eq!(
+            next_local(&s, "2025-04-01T00:00:00Z", 10),
+            vec!["2025-05-01T09:00:00", "2025-05-03T09:00:00"]
+        );
+
+        let s = set(
+            "FREQ=DAILY;UNTIL=20250503T090000Z;BYHOUR=9;BYMINUTE=0",
+            "2025-05-01",
+        );
+        assert_eq!(next_local(&s, "2025-04-01T00:00:00Z", 10).len(), 3);
+
+        let mut s = set("FREQ=DAILY;BYHOUR=8,20;BYMINUTE=0", "2025-05-01");
+        s.exdates = vec!["2025-05-01T20:00:00".parse().expect("Invalid exdate")];
+        assert_eq!(
+            next_local(&s, "2025-05-01T00:00:00Z", 2),
+            vec!["2025-05-01T08:00:00", "2025-05-02T08:00:00"]
+        );
+    }
+
+    #[test]
+    fn test_yearly_leap_day_and_impossible_rules_end() {
+        let s = set(
+            "FREQ=YEARLY;BYMONTH=2;BYMONTHDAY=29;BYHOUR=9;BYMINUTE=0",
+            "2025-01-01",
+        );
+        assert_eq!(
+            next_local(&s, "2025-01-01T00:00:00Z", 2),
+            vec!["2028-02-29T09:00:00", "2032-02-29T09:00:00"]
+        );
+
+        let never = set("FREQ=MONTHLY;BYMONTH=2;BYMONTHDAY=30", "2025-01-01");
+        assert!(next_local(&never, "2025-01-01T00:00:00Z", 1).is_empty());
+    }
+
+    #[test]
+    fn test_skip_ahead_matches_full_expansion() {
+        let s = set("FREQ=DAILY;INTERVAL=3;BYHOUR=7;BYMINUTE=30", "2020-01-01");
+
+        // 2020-01-01 + 3k days; 2025-06-01 is day 1978 (≡ 1 mod 3)
+        assert_eq!(
+            next_local(&s, "2025-06-01T00:00:00Z", 2),
+            vec!["2025-06-03T07:30:00", "2025-06-06T07:30:00"]
+        );
+    }
+
+    #[test]
+    fn test_occurrences_resolve_in_schedule_timezone() {
+        let s = set("FREQ=DAILY;BYHOUR=2;BYMINUTE=30", "2025-03-01");
+        let tz: Tz = "America/New_York".parse().expect("Invalid timezone");
+
+        // 02:30 doesn't exist on 2025-03-09; it fires at 03:00 EDT instead
+        let (_, instant) = s
+            .occurrences_after(tz, utc("2025-03-09T05:00:00Z"))
+            .next()
+            .expect("Expected an occurrence");
+        assert_eq!(instant, utc("2025-03-09T07:00:00Z"));
+    }
+
+    #[test]
+    fn test_default_dtstart() {
+        let tz = Tz::UTC;
+        // Wednesday 2025-03-05
+        let now = utc("2025-03-05T12:00:00Z");
+
+        assert_eq!(
+            default_dtstart(&ScheduleFrequency::Daily, None, &tz, now),
+            local("2025-03-05")
+        );
+        // Next Monday for a biweekly Monday schedule
+        assert_eq!(
+            default_dtstart(&ScheduleFrequency::Biweekly, Some(1), &tz, now),
+            local("2025-03-10")
+        );
+    }
+}
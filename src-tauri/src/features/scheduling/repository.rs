@@ -4,15 +4,28 @@
 use std::str::FromStr;
 use std::sync::Arc;
 
-use rusqlite::params;
-use tracing::info;
+use chrono::{DateTime, NaiveDateTime, Utc};
+use rusqlite::{params, OptionalExtension};
+use tracing::{info, warn};
+use validator::Validate;
 
 use crate::db::Database;
+use crate::features::activities::{ActivityError, ActivityRepository};
+use crate::types::assessment::SeverityLevel;
 
+use super::adaptive::{
+    change_reason, select_rule, validate_adaptive_rules, AdaptiveRule, AdaptiveRuleRequest,
+    FrequencyChange, RecentResult, SeverityComparison,
+};
+use super::ical::{CalendarImportResult, ParsedCalendar, SkippedCalendarEvent};
 use super::models::{
-    AssessmentSchedule, CreateScheduleRequest, ScheduleFrequency, SchedulingError,
-    UpdateScheduleRequest,
+    is_schedule_due, median, missed_occurrences, parse_timezone, system_timezone,
+    AssessmentSchedule, CreateScheduleRequest, MissedReminder, MissedReminderNotice,
+    MissedReminderPolicy, ReminderAdherence, ReminderEvent, ReminderEventType, ScheduleFrequency,
+    ScheduleTarget, SchedulingError, UpdateScheduleRequest, MAX_SNOOZE_MINUTES,
+    REMINDER_COMPLETION_WINDOW_HOURS,
 };
+use super::recurrence::format_dtstart;
 
 pub struct SchedulingRepository {
     db: Arc<Database>,
@@ -40,61 +53,164 @@ impl SchedulingRepository {
             .transaction_with_behavior(rusqlite::TransactionBehavior::Immediate)
             .map_err(SchedulingError::Database)?;
 
-        // Verify assessment type exists
-        let assessment_type_exists: bool = tx
-            .query_row(
-                "SELECT COUNT(*) FROM assessment_types WHERE id = ?",
-                params![request.assessment_type_id],
-                |row| row.get::<_, i32>(0).map(|count| count > 0),
-            )
-            .unwrap_or(false);
+        let schedule = self.insert_schedule_with_conn(&tx, request)?;
 
-        if !assessment_type_exists {
-            return Err(SchedulingError::NotFound(request.assessment_type_id));
-        }
+        // Commit transaction (auto-rollback on drop if not committed or on panic)
+        tx.commit().map_err(SchedulingError::Database)?;
+
+        info!(
+            schedule_id = schedule.id,
+            target_kind = schedule.target.kind(),
+            target_name = %schedule.target_name,
+            frequency = %schedule.frequency.as_str(),
+            "Created assessment schedule"
+        );
+
+        Ok(schedule)
+    }
+
+    /// Insert a validated schedule and read it back
+    fn insert_schedule_with_conn(
+        &self,
+        conn: &rusqlite::Connection,
+        request: &CreateScheduleRequest,
+    ) -> Result<AssessmentSchedule, SchedulingError> {
+        // Verify the assessment type, activity or group exists
+        let target = request.resolved_target()?;
+        verify_target_exists(conn, &target)?;
+        let columns = TargetColumns::from(&target);
+
+        // Schedules without an explicit timezone follow the system timezone
+        let timezone = request.timezone.clone().unwrap_or_else(system_timezone);
+        let recurrence = request.recurrence(&parse_timezone(&timezone)?, Utc::now())?;
 
         // Insert schedule
-        tx.execute(
+        conn.execute(
             "INSERT INTO assessment_schedules
-             (assessment_type_id, frequency, time_of_day, day_of_week, day_of_month, enabled, created_at, updated_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, 1, CURRENT_TIMESTAMP, CURRENT_TIMESTAMP)",
+             (target_kind, assessment_type_id, activity_id, activity_group_id, habit_text,
+              frequency, time_of_day, day_of_week, day_of_month, rrule, dtstart, exdates,
+              timezone, missed_policy, enabled, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, 1,
+                     CURRENT_TIMESTAMP, CURRENT_TIMESTAMP)",
             params![
-                request.assessment_type_id,
-                request.frequency.as_str(),
-                request.time_of_day,
-                request.day_of_week,
-                request.day_of_month,
+                columns.kind,
+                columns.assessment_type_id,
+                columns.activity_id,
+                columns.activity_group_id,
+                columns.habit_text,
+                recurrence.frequency.as_str(),
+                recurrence.time_of_day,
+                recurrence.day_of_week,
+                recurrence.day_of_month,
+                recurrence.rrule,
+                recurrence.dtstart,
+                recurrence.exdates.join(","),
+                timezone,
+                request.missed_policy.unwrap_or_default().as_str(),
             ],
         )?;
 
-        let schedule_id = tx.last_insert_rowid() as i32;
+        let schedule_id = conn.last_insert_rowid() as i32;
 
-        // Fetch created schedule within transaction
-        let schedule = tx
+        // Fetch created schedule within the caller's transaction
+        let schedule = conn
             .query_row(
-                "SELECT s.id, s.assessment_type_id, a.code, a.name, s.frequency, s.time_of_day,
-                        s.day_of_week, s.day_of_month, s.enabled, s.last_triggered_at,
-                        s.created_at, s.updated_at
-                 FROM assessment_schedules s
-                 JOIN assessment_types a ON s.assessment_type_id = a.id
-                 WHERE s.id = ?",
+                &format!("{} WHERE s.id = ?", SCHEDULE_SELECT),
                 params![schedule_id],
                 |row| self.map_schedule_row(row),
             )
             .map_err(|_| SchedulingError::NotFound(schedule_id))?;
 
-        // Commit transaction (auto-rollback on drop if not committed or on panic)
+        Ok(schedule)
+    }
+
+    /// Create schedules and planned activities from imported calendar events
+    /// in one transaction.
+    ///
+    /// Events whose target or recurrence is invalid are reported as skipped
+    /// instead of failing the import; database errors roll everything back.
+    pub fn import_calendar(
+        &self,
+        calendar: ParsedCalendar,
+    ) -> Result<CalendarImportResult, SchedulingError> {
+        let conn = self.db.get_connection();
+        let mut conn = conn.lock();
+        let tx = conn
+            .transaction_with_behavior(rusqlite::TransactionBehavior::Immediate)
+            .map_err(SchedulingError::Database)?;
+
+        let mut result = CalendarImportResult {
+            imported: Vec::new(),
+            imported_plans: Vec::new(),
+            skipped: calendar.skipped,
+        };
+        for mut event in calendar.events {
+            // Assessment type IDs differ between databases; codes don't
+            if let Some(code) = &event.assessment_type_code {
+                let id: Option<i32> = tx
+                    .query_row(
+                        "SELECT id FROM assessment_types WHERE code = ?",
+                        params![code],
+                        |row| row.get(0),
+                    )
+                    .optional()?;
+                if let Some(assessment_type_id) = id {
+                    event.request.target = Some(ScheduleTarget::Assessment { assessment_type_id });
+                }
+            }
+
+            let created = event
+                .request
+                .validate()
+                .and_then(|_| self.insert_schedule_with_conn(&tx, &event.request));
+            match created {
+                Ok(schedule) => result.imported.push(schedule),
+                Err(e @ (SchedulingError::Database(_) | SchedulingError::LockPoisoned)) => {
+                    return Err(e)
+                }
+                Err(e) => {
+                    warn!(uid = ?event.uid, error = %e, "Skipped calendar event");
+                    result.skipped.push(SkippedCalendarEvent {
+                        uid: event.uid,
+                        summary: Some(event.summary),
+                        reason: e.to_string(),
+                    });
+                }
+            }
+        }
+
+        let activity_repo = ActivityRepository::new(Arc::clone(&self.db));
+        for plan in calendar.plans {
+            let created = plan
+                .request
+                .validate()
+                .map_err(|e| ActivityError::InvalidPlan(e.to_string()))
+                .and_then(|_| activity_repo.create_planned_activity_with_conn(&tx, &plan.request));
+            match created {
+                Ok(created) => result.imported_plans.push(created),
+                Err(ActivityError::Database(e)) => return Err(SchedulingError::Database(e)),
+                Err(ActivityError::LockPoisoned) => return Err(SchedulingError::LockPoisoned),
+                Err(e) => {
+                    warn!(uid = ?plan.uid, error = %e, "Skipped calendar event");
+                    result.skipped.push(SkippedCalendarEvent {
+                        uid: plan.uid,
+                        summary: Some(plan.summary),
+                        reason: e.to_string(),
+                    });
+                }
+            }
+        }
+
         tx.commit().map_err(SchedulingError::Database)?;
 
         info!(
-            schedule_id = schedule.id,
-            assessment_type_id = schedule.assessment_type_id,
-            assessment_type_code = %schedule.assessment_type_code,
-            frequency = %schedule.frequency.as_str(),
-            "Created assessment schedule"
+            imported = result.imported.len(),
+            imported_plans = result.imported_plans.len(),
+            skipped = result.skipped.len(),
+            "Imported schedules from calendar"
         );
 
-        Ok(schedule)
+        Ok(result)
     }
 
     /// T161: Update an existing schedule
@@ -111,36 +227,54 @@ impl SchedulingRepository {
 
         // Build dynamic update query using safe predefined clauses
         // Each clause is a constant string to prevent SQL injection
-        const FREQUENCY_CLAUSE: &str = "frequency = ?";
-        const TIME_CLAUSE: &str = "time_of_day = ?";
-        const DAY_OF_WEEK_CLAUSE: &str = "day_of_week = ?";
-        const DAY_OF_MONTH_CLAUSE: &str = "day_of_month = ?";
+        const RECURRENCE_CLAUSE: &str = "frequency = ?, time_of_day = ?, day_of_week = ?, \
+             day_of_month = ?, rrule = ?, dtstart = ?, exdates = ?";
+        const TIMEZONE_CLAUSE: &str = "timezone = ?";
+        const TARGET_CLAUSE: &str = "target_kind = ?, assessment_type_id = ?, activity_id = ?, \
+             activity_group_id = ?, habit_text = ?";
         const ENABLED_CLAUSE: &str = "enabled = ?";
+        const MISSED_POLICY_CLAUSE: &str = "missed_policy = ?";
         const UPDATED_AT_CLAUSE: &str = "updated_at = CURRENT_TIMESTAMP";
 
         let mut clauses = Vec::new();
         let mut params_vec: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
 
-        if let Some(ref freq) = request.frequency {
-            clauses.push(FREQUENCY_CLAUSE);
-            params_vec.push(Box::new(freq.as_str().to_string()));
-        }
-        if let Some(ref time) = request.time_of_day {
-            clauses.push(TIME_CLAUSE);
-            params_vec.push(Box::new(time.clone()));
+        // The rule is derived from frequency, time and days together, so any
+        // change to them rewrites all recurrence columns
+        if request.changes_recurrence() {
+            let current = self.get_schedule_with_conn(&conn, id)?;
+            let recurrence = request.recurrence(&current)?;
+            clauses.push(RECURRENCE_CLAUSE);
+            params_vec.push(Box::new(recurrence.frequency.as_str().to_string()));
+            params_vec.push(Box::new(recurrence.time_of_day));
+            params_vec.push(Box::new(recurrence.day_of_week));
+            params_vec.push(Box::new(recurrence.day_of_month));
+            params_vec.push(Box::new(recurrence.rrule));
+            params_vec.push(Box::new(recurrence.dtstart));
+            params_vec.push(Box::new(recurrence.exdates.join(",")));
         }
-        if let Some(day) = request.day_of_week {
-            clauses.push(DAY_OF_WEEK_CLAUSE);
-            params_vec.push(Box::new(day));
+        if let Some(ref tz) = request.timezone {
+            clauses.push(TIMEZONE_CLAUSE);
+            params_vec.push(Box::new(tz.clone()));
         }
-        if let Some(day) = request.day_of_month {
-            clauses.push(DAY_OF_MONTH_CLAUSE);
-            params_vec.push(Box::new(day));
+        if let Some(ref target) = request.target {
+            verify_target_exists(&conn, target)?;
+            let columns = TargetColumns::from(target);
+            clauses.push(TARGET_CLAUSE);
+            params_vec.push(Box::new(columns.kind));
+            params_vec.push(Box::new(columns.assessment_type_id));
+            params_vec.push(Box::new(columns.activity_id));
+            params_vec.push(Box::new(columns.activity_group_id));
+            params_vec.push(Box::new(columns.habit_text));
         }
         if let Some(enabled) = request.enabled {
             clauses.push(ENABLED_CLAUSE);
             params_vec.push(Box::new(enabled));
         }
+        if let Some(policy) = request.missed_policy {
+            clauses.push(MISSED_POLICY_CLAUSE);
+            params_vec.push(Box::new(policy.as_str()));
+        }
 
         if clauses.is_empty() {
             // Nothing to update, just return current schedule
@@ -212,9 +346,17 @@ impl SchedulingRepository {
 
     /// T164: Get schedules that are due for triggering
     pub fn get_due_schedules(&self) -> Result<Vec<AssessmentSchedule>, SchedulingError> {
+        self.get_due_schedules_at(Utc::now())
+    }
+
+    /// Get schedules that are due at the given instant
+    pub fn get_due_schedules_at(
+        &self,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<AssessmentSchedule>, SchedulingError> {
         let conn = self.db.get_connection();
         let conn = conn.lock();
-        self.get_due_schedules_with_conn(&conn)
+        self.get_due_schedules_with_conn(&conn, now)
     }
 
     /// Mark a schedule as triggered
@@ -238,9 +380,16 @@ impl SchedulingRepository {
 
     /// Mark multiple schedules as triggered in a single transaction
     /// More efficient than calling mark_triggered in a loop
-    pub fn mark_multiple_triggered(&self, schedule_ids: &[i32]) -> Result<(), SchedulingError> {
+    ///
+    /// Also logs a `fired` reminder event per schedule and returns them in
+    /// the order of `schedule_ids`.
+    pub fn mark_multiple_triggered(
+        &self,
+        schedule_ids: &[i32],
+        now: DateTime<Utc>,
+    ) -> Result<Vec<ReminderEvent>, SchedulingError> {
         if schedule_ids.is_empty() {
-            return Ok(());
+            return Ok(Vec::new());
         }
 
         let conn = self.db.get_connection();
@@ -251,16 +400,28 @@ impl SchedulingRepository {
             .transaction_with_behavior(rusqlite::TransactionBehavior::Immediate)
             .map_err(SchedulingError::Database)?;
 
+        let now = format_timestamp(now);
+        let mut events = Vec::with_capacity(schedule_ids.len());
+
         // Use prepared statement caching for efficiency
         {
             let mut stmt = tx
                 .prepare_cached(
-                    "UPDATE assessment_schedules SET last_triggered_at = CURRENT_TIMESTAMP WHERE id = ?",
+                    "UPDATE assessment_schedules SET last_triggered_at = ? WHERE id = ?",
                 )
                 .map_err(SchedulingError::Database)?;
 
             for id in schedule_ids {
-                stmt.execute(params![id])?;
+                stmt.execute(params![now, id])?;
+                events.push(insert_reminder_event(
+                    &tx,
+                    *id,
+                    ReminderEventType::Fired,
+                    &now,
+                    None,
+                    None,
+                    None,
+                )?);
             }
         } // Drop stmt before committing
 
@@ -272,83 +433,923 @@ impl SchedulingRepository {
             "Marked multiple schedules as triggered"
         );
 
+        Ok(events)
+    }
+
+    /// Move schedules past their due occurrence without notifying, for
+    /// notifications dropped during quiet time or while notifications are off.
+    /// No reminder event is logged, so dropped occurrences don't count
+    /// against adherence.
+    pub fn mark_multiple_dropped(
+        &self,
+        schedule_ids: &[i32],
+        now: DateTime<Utc>,
+    ) -> Result<(), SchedulingError> {
+        if schedule_ids.is_empty() {
+            return Ok(());
+        }
+
+        let conn = self.db.get_connection();
+        let mut conn = conn.lock();
+
+        let tx = conn
+            .transaction_with_behavior(rusqlite::TransactionBehavior::Immediate)
+            .map_err(SchedulingError::Database)?;
+
+        let now = format_timestamp(now);
+        {
+            let mut stmt = tx
+                .prepare_cached(
+                    "UPDATE assessment_schedules SET last_triggered_at = ? WHERE id = ?",
+                )
+                .map_err(SchedulingError::Database)?;
+
+            for id in schedule_ids {
+                stmt.execute(params![now, id])?;
+            }
+        }
+
+        tx.commit().map_err(SchedulingError::Database)?;
+
+        info!(
+            count = schedule_ids.len(),
+            "Dropped notifications for due schedules"
+        );
+
         Ok(())
     }
 
-    // Helper methods
+    /// Get a reminder event by ID
+    pub fn get_reminder_event(&self, id: i32) -> Result<ReminderEvent, SchedulingError> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock();
 
-    /// Get schedules that are due for triggering based on frequency and last trigger time.
+        get_reminder_event_with_conn(&conn, id)
+    }
+
+    /// Reminder events of a schedule, newest first
+    pub fn get_reminder_events(
+        &self,
+        schedule_id: i32,
+        limit: Option<i32>,
+    ) -> Result<Vec<ReminderEvent>, SchedulingError> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock();
+
+        // Fails with NotFound for unknown schedules instead of an empty list
+        self.get_schedule_with_conn(&conn, schedule_id)?;
+
+        let mut stmt = conn.prepare(&format!(
+            "{} WHERE schedule_id = ? ORDER BY occurred_at DESC, id DESC LIMIT ?",
+            REMINDER_EVENT_SELECT
+        ))?;
+        let events = stmt
+            .query_map(
+                params![schedule_id, limit.unwrap_or(-1)],
+                map_reminder_event_row,
+            )?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(events)
+    }
+
+    /// Snooze a reminder so it fires again `minutes` after `now`
     ///
-    /// This method implements frequency-aware logic to prevent schedules from triggering
-    /// too frequently. It uses SQLite's julianday function for accurate day calculations
-    /// across all frequency types.
+    /// `event_id` may be the original `fired` event or any event belonging to it.
+    pub fn snooze_reminder(
+        &self,
+        event_id: i32,
+        minutes: i32,
+        now: DateTime<Utc>,
+    ) -> Result<ReminderEvent, SchedulingError> {
+        if !(1..=MAX_SNOOZE_MINUTES).contains(&minutes) {
+            return Err(SchedulingError::InvalidSnooze(minutes));
+        }
+
+        let snooze_until = format_timestamp(now + chrono::Duration::minutes(minutes as i64));
+        let event = self.respond_to_reminder(
+            event_id,
+            ReminderEventType::Snoozed,
+            now,
+            Some(&snooze_until),
+        )?;
+
+        info!(
+            reminder_id = event.reminder_id(),
+            minutes, "Snoozed reminder"
+        );
+
+        Ok(event)
+    }
+
+    /// Dismiss a reminder without completing it
+    pub fn dismiss_reminder(
+        &self,
+        event_id: i32,
+        now: DateTime<Utc>,
+    ) -> Result<ReminderEvent, SchedulingError> {
+        let event = self.respond_to_reminder(event_id, ReminderEventType::Dismissed, now, None)?;
+
+        info!(reminder_id = event.reminder_id(), "Dismissed reminder");
+
+        Ok(event)
+    }
+
+    /// Mark a reminder as done, e.g. after logging the reminded activity
+    pub fn complete_reminder(
+        &self,
+        event_id: i32,
+        now: DateTime<Utc>,
+    ) -> Result<ReminderEvent, SchedulingError> {
+        let event = self.respond_to_reminder(event_id, ReminderEventType::Completed, now, None)?;
+
+        info!(reminder_id = event.reminder_id(), "Completed reminder");
+
+        Ok(event)
+    }
+
+    /// Link a submitted assessment to the reminder that asked for it.
     ///
-    /// # Frequency Logic
+    /// Picks the most recent uncompleted reminder of a schedule for
+    /// `assessment_type_id` that fired within
+    /// [`REMINDER_COMPLETION_WINDOW_HOURS`] before `completed_at`, and records
+    /// a `completed` event for it. Dismissed reminders still count: the user
+    /// may have closed the notification and done the assessment later.
+    /// Returns `None` if no reminder qualifies.
+    pub fn link_assessment_completion(
+        &self,
+        assessment_type_id: i32,
+        assessment_response_id: i32,
+        completed_at: DateTime<Utc>,
+    ) -> Result<Option<ReminderEvent>, SchedulingError> {
+        let conn = self.db.get_connection();
+        let mut conn = conn.lock();
+
+        let tx = conn
+            .transaction_with_behavior(rusqlite::TransactionBehavior::Immediate)
+            .map_err(SchedulingError::Database)?;
+
+        let window_start = format_timestamp(
+            completed_at - chrono::Duration::hours(REMINDER_COMPLETION_WINDOW_HOURS),
+        );
+        let completed_at = format_timestamp(completed_at);
+
+        let reminder_id: Option<i32> = match tx.query_row(
+            "SELECT e.id FROM reminder_events e
+             JOIN assessment_schedules s ON s.id = e.schedule_id
+             WHERE s.target_kind = 'assessment' AND s.assessment_type_id = ?1
+               AND e.event_type = 'fired' AND e.related_event_id IS NULL
+               AND e.occurred_at BETWEEN ?2 AND ?3
+               AND NOT EXISTS (SELECT 1 FROM reminder_events c
+                               WHERE c.related_event_id = e.id AND c.event_type = 'completed')
+             ORDER BY e.occurred_at DESC, e.id DESC
+             LIMIT 1",
+            params![assessment_type_id, window_start, completed_at],
+            |row| row.get(0),
+        ) {
+            Ok(id) => Some(id),
+            Err(rusqlite::Error::QueryReturnedNoRows) => None,
+            Err(e) => return Err(e.into()),
+        };
+
+        let Some(reminder_id) = reminder_id else {
+            return Ok(None);
+        };
+
+        let reminder = get_reminder_event_with_conn(&tx, reminder_id)?;
+        let event = insert_reminder_event(
+            &tx,
+            reminder.schedule_id,
+            ReminderEventType::Completed,
+            &completed_at,
+            None,
+            Some(reminder_id),
+            Some(assessment_response_id),
+        )?;
+
+        tx.commit().map_err(SchedulingError::Database)?;
+
+        info!(
+            reminder_id,
+            assessment_response_id, "Linked assessment to reminder"
+        );
+
+        Ok(Some(event))
+    }
+
+    /// Snoozes that have run out at `now` and haven't been followed by any
+    /// other event of their reminder, for enabled schedules only
+    pub fn get_due_snoozes(
+        &self,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<ReminderEvent>, SchedulingError> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock();
+
+        let mut stmt = conn.prepare(&format!(
+            "{} WHERE event_type = 'snoozed' AND snooze_until <= ?
+               AND schedule_id IN (SELECT id FROM assessment_schedules WHERE enabled = 1)
+               AND NOT EXISTS (SELECT 1 FROM reminder_events later
+                               WHERE later.related_event_id = reminder_events.related_event_id
+                                 AND later.id > reminder_events.id)
+             ORDER BY snooze_until, id",
+            REMINDER_EVENT_SELECT
+        ))?;
+        let snoozes = stmt
+            .query_map(params![format_timestamp(now)], map_reminder_event_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(snoozes)
+    }
+
+    /// Record the re-fire of each snoozed reminder as a `fired` event
+    /// pointing at the original reminder
+    pub fn mark_snoozes_refired(
+        &self,
+        snoozes: &[ReminderEvent],
+        now: DateTime<Utc>,
+    ) -> Result<Vec<ReminderEvent>, SchedulingError> {
+        if snoozes.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let conn = self.db.get_connection();
+        let mut conn = conn.lock();
+
+        let tx = conn
+            .transaction_with_behavior(rusqlite::TransactionBehavior::Immediate)
+            .map_err(SchedulingError::Database)?;
+
+        let now = format_timestamp(now);
+        let refires = snoozes
+            .iter()
+            .map(|snooze| {
+                insert_reminder_event(
+                    &tx,
+                    snooze.schedule_id,
+                    ReminderEventType::Fired,
+                    &now,
+                    None,
+                    Some(snooze.reminder_id()),
+                    None,
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        tx.commit().map_err(SchedulingError::Database)?;
+
+        info!(count = refires.len(), "Re-fired snoozed reminders");
+
+        Ok(refires)
+    }
+
+    /// Handle occurrences that fell due while the app was closed.
+    ///
+    /// Records every missed occurrence of each active schedule, logs `fired`
+    /// events according to the schedule's [`MissedReminderPolicy`] and moves
+    /// `last_triggered_at` to `now` so the regular due check doesn't fire the
+    /// same occurrences again. Returns the catch-up notifications to send.
+    pub fn reconcile_missed_reminders(
+        &self,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<MissedReminderNotice>, SchedulingError> {
+        let conn = self.db.get_connection();
+        let mut conn = conn.lock();
+
+        let tx = conn
+            .transaction_with_behavior(rusqlite::TransactionBehavior::Immediate)
+            .map_err(SchedulingError::Database)?;
+
+        let schedules = self.get_active_schedules_with_conn(&tx)?;
+        let now_str = format_timestamp(now);
+        let mut notices = Vec::new();
+        let mut missed_count = 0;
+
+        for schedule in schedules {
+            let occurrences = match missed_occurrences(&schedule, now) {
+                Ok(occurrences) => occurrences,
+                Err(e) => {
+                    warn!(
+                        schedule_id = schedule.id,
+                        error = %e,
+                        "Skipping schedule with invalid configuration"
+                    );
+                    continue;
+                }
+            };
+            if occurrences.is_empty() {
+                continue;
+            }
+            missed_count += occurrences.len();
+
+            let fire = || {
+                insert_reminder_event(
+                    &tx,
+                    schedule.id,
+                    ReminderEventType::Fired,
+                    &now_str,
+                    None,
+                    None,
+                    None,
+                )
+            };
+            let record = |occurrence: &(NaiveDateTime, DateTime<Utc>), event_id: Option<i32>| {
+                insert_missed_reminder(&tx, &schedule, occurrence, event_id, &now_str)
+            };
+
+            match schedule.missed_policy {
+                MissedReminderPolicy::Coalesce => {
+                    let event = fire()?;
+                    let missed = occurrences
+                        .iter()
+                        .map(|occurrence| record(occurrence, Some(event.id)))
+                        .collect::<Result<Vec<_>, _>>()?;
+                    notices.push(MissedReminderNotice {
+                        schedule: schedule.clone(),
+                        event,
+                        missed,
+                    });
+                }
+                MissedReminderPolicy::FireAll => {
+                    for occurrence in &occurrences {
+                        let event = fire()?;
+                        let missed = record(occurrence, Some(event.id))?;
+                        notices.push(MissedReminderNotice {
+                            schedule: schedule.clone(),
+                            event,
+                            missed: vec![missed],
+                        });
+                    }
+                }
+                MissedReminderPolicy::Skip => {
+                    for occurrence in &occurrences {
+                        record(occurrence, None)?;
+                    }
+                }
+            }
+
+            tx.execute(
+                "UPDATE assessment_schedules SET last_triggered_at = ? WHERE id = ?",
+                params![now_str, schedule.id],
+            )?;
+        }
+
+        tx.commit().map_err(SchedulingError::Database)?;
+
+        if missed_count > 0 {
+            info!(
+                missed = missed_count,
+                notifications = notices.len(),
+                "Reconciled missed reminders"
+            );
+        }
+
+        Ok(notices)
+    }
+
+    /// Missed reminders, newest first; acknowledged ones only if requested
+    pub fn get_missed_reminders(
+        &self,
+        include_acknowledged: bool,
+    ) -> Result<Vec<MissedReminder>, SchedulingError> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock();
+
+        let query = if include_acknowledged {
+            format!(
+                "{} ORDER BY scheduled_for DESC, id DESC",
+                MISSED_REMINDER_SELECT
+            )
+        } else {
+            format!(
+                "{} WHERE acknowledged_at IS NULL ORDER BY scheduled_for DESC, id DESC",
+                MISSED_REMINDER_SELECT
+            )
+        };
+
+        let mut stmt = conn.prepare(&query)?;
+        let rows = stmt
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, i32>(1)?,
+                    MissedReminder {
+                        id: row.get(0)?,
+                        schedule_id: row.get(1)?,
+                        target: ScheduleTarget::MoodCheckin,
+                        target_name: String::new(),
+                        scheduled_for: row.get(2)?,
+                        local_time: row.get(3)?,
+                        policy: parse_policy(row.get(4)?)?,
+                        reminder_event_id: row.get(5)?,
+                        detected_at: row.get(6)?,
+                        acknowledged_at: row.get(7)?,
+                    },
+                ))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Fill in the target from each schedule, looked up once per schedule
+        let mut schedules = std::collections::HashMap::new();
+        let mut missed = Vec::with_capacity(rows.len());
+        for (schedule_id, mut reminder) in rows {
+            let schedule = match schedules.entry(schedule_id) {
+                std::collections::hash_map::Entry::Occupied(entry) => entry.into_mut(),
+                std::collections::hash_map::Entry::Vacant(entry) => {
+                    entry.insert(self.get_schedule_with_conn(&conn, schedule_id)?)
+                }
+            };
+            reminder.target = schedule.target.clone();
+            reminder.target_name = schedule.target_name.clone();
+            missed.push(reminder);
+        }
+
+        Ok(missed)
+    }
+
+    /// Mark missed reminders as seen so they drop out of the default list.
+    /// Returns how many were newly acknowledged.
+    pub fn acknowledge_missed_reminders(
+        &self,
+        ids: &[i32],
+        now: DateTime<Utc>,
+    ) -> Result<usize, SchedulingError> {
+        if ids.is_empty() {
+            return Ok(0);
+        }
+
+        let conn = self.db.get_connection();
+        let mut conn = conn.lock();
+
+        let tx = conn
+            .transaction_with_behavior(rusqlite::TransactionBehavior::Immediate)
+            .map_err(SchedulingError::Database)?;
+
+        let now = format_timestamp(now);
+        let mut acknowledged = 0;
+        {
+            let mut stmt = tx.prepare_cached(
+                "UPDATE missed_reminders SET acknowledged_at = ?
+                 WHERE id = ? AND acknowledged_at IS NULL",
+            )?;
+            for id in ids {
+                acknowledged += stmt.execute(params![now, id])?;
+            }
+        }
+
+        tx.commit().map_err(SchedulingError::Database)?;
+
+        info!(count = acknowledged, "Acknowledged missed reminders");
+
+        Ok(acknowledged)
+    }
+
+    /// Adherence per schedule for reminders that fired within the date range.
     ///
-    /// - **Daily**: Triggers if last_triggered_at is NULL or on a different date than today
-    ///   - Example: Schedule at 09:00, last triggered 2024-10-28 08:00 → triggers again on 2024-10-29
+    /// Every schedule is listed, including ones without reminders in the range.
+    pub fn get_reminder_adherence(
+        &self,
+        from_date: Option<String>,
+        to_date: Option<String>,
+    ) -> Result<Vec<ReminderAdherence>, SchedulingError> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock();
+
+        let schedules = self.get_schedules_with_conn(&conn, false)?;
+
+        let (date_filter, date_params) = crate::db::query_builder::DateFilterBuilder::new()
+            .with_from_date(from_date.as_deref(), "r.occurred_at")
+            .with_to_date(to_date.as_deref(), "r.occurred_at")
+            .build();
+
+        // One row per original reminder with the outcome of its follow-up events
+        let query = format!(
+            "SELECT r.schedule_id, r.occurred_at,
+                    (SELECT MIN(c.occurred_at) FROM reminder_events c
+                     WHERE c.related_event_id = r.id AND c.event_type = 'completed'),
+                    EXISTS (SELECT 1 FROM reminder_events sn
+                            WHERE sn.related_event_id = r.id AND sn.event_type = 'snoozed'),
+                    EXISTS (SELECT 1 FROM reminder_events d
+                            WHERE d.related_event_id = r.id AND d.event_type = 'dismissed')
+             FROM reminder_events r
+             WHERE r.event_type = 'fired' AND r.related_event_id IS NULL{}",
+            date_filter
+        );
+        let params_refs: Vec<&dyn rusqlite::ToSql> =
+            date_params.iter().map(|p| p.as_ref()).collect();
+
+        let mut stmt = conn.prepare(&query)?;
+        let reminders = stmt
+            .query_map(params_refs.as_slice(), |row| {
+                Ok((
+                    row.get::<_, i32>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, Option<String>>(2)?,
+                    row.get::<_, bool>(3)?,
+                    row.get::<_, bool>(4)?,
+                ))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let report = schedules
+            .into_iter()
+            .map(|schedule| {
+                let mut adherence = ReminderAdherence {
+                    schedule_id: schedule.id,
+                    target_name: schedule.target_name,
+                    reminders_fired: 0,
+                    reminders_completed: 0,
+                    reminders_snoozed: 0,
+                    reminders_dismissed: 0,
+                    completion_rate: 0.0,
+                    median_minutes_to_completion: None,
+                };
+                let mut minutes_to_completion = Vec::new();
+
+                for (schedule_id, fired_at, completed_at, snoozed, dismissed) in &reminders {
+                    if *schedule_id != schedule.id {
+                        continue;
+                    }
+                    adherence.reminders_fired += 1;
+                    adherence.reminders_snoozed += *snoozed as i32;
+                    adherence.reminders_dismissed += *dismissed as i32;
+                    if let Some(completed_at) = completed_at {
+                        adherence.reminders_completed += 1;
+                        if let (Some(fired), Some(completed)) = (
+                            crate::utils::parse_timestamp(fired_at),
+                            crate::utils::parse_timestamp(completed_at),
+                        ) {
+                            minutes_to_completion
+                                .push((completed - fired).num_seconds() as f64 / 60.0);
+                        }
+                    }
+                }
+
+                if adherence.reminders_fired > 0 {
+                    adherence.completion_rate =
+                        adherence.reminders_completed as f64 / adherence.reminders_fired as f64;
+                }
+                adherence.median_minutes_to_completion = median(&mut minutes_to_completion);
+                adherence
+            })
+            .collect();
+
+        Ok(report)
+    }
+
+    /// Replace a schedule's adaptive rules; their order is the evaluation order.
     ///
-    /// - **Weekly**: Triggers if ≥7 days have elapsed since last_triggered_at
-    ///   - Example: Last triggered 2024-10-22 → triggers again on/after 2024-10-29
+    /// The schedule is re-evaluated against its latest result on the next pass,
+    /// so new rules take effect without waiting for another submission.
+    pub fn set_adaptive_rules(
+        &self,
+        schedule_id: i32,
+        rules: &[AdaptiveRuleRequest],
+    ) -> Result<Vec<AdaptiveRule>, SchedulingError> {
+        // Defense-in-depth - commands also validate
+        validate_adaptive_rules(rules)?;
+
+        let conn = self.db.get_connection();
+        let mut conn = conn.lock();
+
+        let tx = conn
+            .transaction_with_behavior(rusqlite::TransactionBehavior::Immediate)
+            .map_err(SchedulingError::Database)?;
+
+        let schedule = self.get_schedule_with_conn(&tx, schedule_id)?;
+        if !rules.is_empty() && !matches!(schedule.target, ScheduleTarget::Assessment { .. }) {
+            return Err(SchedulingError::InvalidAdaptiveRule(
+                "Adaptive rules need an assessment schedule".to_string(),
+            ));
+        }
+
+        tx.execute(
+            "DELETE FROM schedule_adaptive_rules WHERE schedule_id = ?",
+            params![schedule_id],
+        )?;
+        {
+            let mut stmt = tx.prepare_cached(
+                "INSERT INTO schedule_adaptive_rules
+                 (schedule_id, position, comparison, severity_level, consecutive_count,
+                  frequency, day_of_week, day_of_month)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+            )?;
+            for (position, rule) in rules.iter().enumerate() {
+                stmt.execute(params![
+                    schedule_id,
+                    position as i32,
+                    rule.comparison.as_str(),
+                    rule.severity_level,
+                    rule.consecutive_count,
+                    rule.frequency.as_str(),
+                    rule.day_of_week,
+                    rule.day_of_month,
+                ])?;
+            }
+        }
+        tx.execute(
+            "UPDATE assessment_schedules SET adaptive_response_id = NULL WHERE id = ?",
+            params![schedule_id],
+        )?;
+
+        let stored = get_adaptive_rules_with_conn(&tx, schedule_id)?;
+        tx.commit().map_err(SchedulingError::Database)?;
+
+        info!(
+            schedule_id = schedule_id,
+            rules = stored.len(),
+            "Replaced adaptive rules"
+        );
+
+        Ok(stored)
+    }
+
+    /// Adaptive rules of a schedule in evaluation order
+    pub fn get_adaptive_rules(
+        &self,
+        schedule_id: i32,
+    ) -> Result<Vec<AdaptiveRule>, SchedulingError> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock();
+
+        self.get_schedule_with_conn(&conn, schedule_id)?;
+        get_adaptive_rules_with_conn(&conn, schedule_id)
+    }
+
+    /// Evaluate adaptive rules against each schedule's newest completed results.
     ///
-    /// - **Biweekly**: Triggers if ≥14 days have elapsed since last_triggered_at
-    ///   - Example: Last triggered 2024-10-15 → triggers again on/after 2024-10-29
+    /// Only schedules with a result they haven't been evaluated against yet are
+    /// considered, so manual edits stick until the next submission. Limit to one
+    /// assessment type with `assessment_type_id`. Returns the changes made.
+    pub fn apply_adaptive_rules(
+        &self,
+        assessment_type_id: Option<i32>,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<FrequencyChange>, SchedulingError> {
+        let conn = self.db.get_connection();
+        let mut conn = conn.lock();
+
+        let tx = conn
+            .transaction_with_behavior(rusqlite::TransactionBehavior::Immediate)
+            .map_err(SchedulingError::Database)?;
+
+        // (schedule, newest response it was evaluated against)
+        let candidates = {
+            let mut stmt = tx.prepare(
+                "SELECT s.id, s.adaptive_response_id
+                 FROM assessment_schedules s
+                 WHERE s.enabled = 1
+                   AND s.target_kind = 'assessment'
+                   AND (?1 IS NULL OR s.assessment_type_id = ?1)
+                   AND EXISTS (SELECT 1 FROM schedule_adaptive_rules r WHERE r.schedule_id = s.id)",
+            )?;
+            let rows = stmt
+                .query_map(params![assessment_type_id], |row| {
+                    Ok((row.get::<_, i32>(0)?, row.get::<_, Option<i32>>(1)?))
+                })?
+                .collect::<Result<Vec<_>, _>>()?;
+            rows
+        };
+
+        let now_str = format_timestamp(now);
+        let mut changes = Vec::new();
+
+        for (schedule_id, evaluated_response_id) in candidates {
+            let schedule = self.get_schedule_with_conn(&tx, schedule_id)?;
+            let rules = get_adaptive_rules_with_conn(&tx, schedule_id)?;
+            let needed = rules.iter().map(|r| r.consecutive_count).max().unwrap_or(1);
+            let recent = get_recent_results_with_conn(&tx, schedule.assessment_type_id, needed)?;
+
+            let Some(latest) = recent.first() else {
+                continue;
+            };
+            if evaluated_response_id == Some(latest.response_id) {
+                continue;
+            }
+            tx.execute(
+                "UPDATE assessment_schedules SET adaptive_response_id = ? WHERE id = ?",
+                params![latest.response_id, schedule_id],
+            )?;
+
+            let Some(rule) = select_rule(&rules, &recent) else {
+                continue;
+            };
+            let recurrence = match rule.recurrence_for(&schedule) {
+                Ok(recurrence) => recurrence,
+                Err(e) => {
+                    warn!(
+                        schedule_id = schedule_id,
+                        rule_id = rule.id,
+                        error = %e,
+                        "Skipping adaptive rule that doesn't fit its schedule"
+                    );
+                    continue;
+                }
+            };
+            if recurrence.frequency == schedule.frequency && recurrence.rrule == schedule.rrule {
+                continue;
+            }
+
+            tx.execute(
+                "UPDATE assessment_schedules
+                 SET frequency = ?, day_of_week = ?, day_of_month = ?, rrule = ?,
+                     updated_at = ?
+                 WHERE id = ?",
+                params![
+                    recurrence.frequency.as_str(),
+                    recurrence.day_of_week,
+                    recurrence.day_of_month,
+                    recurrence.rrule,
+                    now_str,
+                    schedule_id,
+                ],
+            )?;
+            tx.execute(
+                "INSERT INTO schedule_frequency_changes
+                 (schedule_id, rule_id, from_frequency, to_frequency, from_rrule, to_rrule,
+                  reason, assessment_response_id, changed_at)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)",
+                params![
+                    schedule_id,
+                    rule.id,
+                    schedule.frequency.as_str(),
+                    recurrence.frequency.as_str(),
+                    schedule.rrule,
+                    recurrence.rrule,
+                    change_reason(rule, &schedule, latest),
+                    latest.response_id,
+                    now_str,
+                ],
+            )?;
+            let change = get_frequency_change_with_conn(&tx, tx.last_insert_rowid() as i32)?;
+
+            info!(
+                schedule_id = schedule_id,
+                from = change.from_frequency.as_str(),
+                to = change.to_frequency.as_str(),
+                reason = %change.reason,
+                "Adaptive rule changed schedule frequency"
+            );
+            changes.push(change);
+        }
+
+        tx.commit().map_err(SchedulingError::Database)?;
+
+        Ok(changes)
+    }
+
+    /// Frequency changes made by adaptive rules, newest first, optionally for one schedule
+    pub fn get_frequency_changes(
+        &self,
+        schedule_id: Option<i32>,
+        limit: Option<i32>,
+    ) -> Result<Vec<FrequencyChange>, SchedulingError> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock();
+
+        if let Some(id) = schedule_id {
+            self.get_schedule_with_conn(&conn, id)?;
+        }
+
+        let mut stmt = conn.prepare(&format!(
+            "{} WHERE (?1 IS NULL OR schedule_id = ?1)
+             ORDER BY changed_at DESC, id DESC LIMIT ?2",
+            FREQUENCY_CHANGE_SELECT
+        ))?;
+        let changes = stmt
+            .query_map(
+                params![schedule_id, limit.unwrap_or(-1)],
+                map_frequency_change_row,
+            )?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(changes)
+    }
+
+    // Helper methods
+
+    /// Record the user's response to a reminder
     ///
-    /// - **Monthly**: Triggers if DATE(last_triggered_at, '+1 month') ≤ DATE('now')
-    ///   - Example: Last triggered 2024-09-28 → triggers again on/after 2024-10-28
+    /// Resolves `event_id` to its original reminder and refuses to act on a
+    /// reminder that is already completed.
+    fn respond_to_reminder(
+        &self,
+        event_id: i32,
+        event_type: ReminderEventType,
+        now: DateTime<Utc>,
+        snooze_until: Option<&str>,
+    ) -> Result<ReminderEvent, SchedulingError> {
+        let conn = self.db.get_connection();
+        let mut conn = conn.lock();
+
+        let tx = conn
+            .transaction_with_behavior(rusqlite::TransactionBehavior::Immediate)
+            .map_err(SchedulingError::Database)?;
+
+        let event = get_reminder_event_with_conn(&tx, event_id)?;
+        let reminder_id = event.reminder_id();
+
+        let completed: bool = tx.query_row(
+            "SELECT EXISTS (SELECT 1 FROM reminder_events
+                            WHERE related_event_id = ? AND event_type = 'completed')",
+            params![reminder_id],
+            |row| row.get(0),
+        )?;
+        if completed {
+            return Err(SchedulingError::ReminderAlreadyCompleted(reminder_id));
+        }
+
+        let created = insert_reminder_event(
+            &tx,
+            event.schedule_id,
+            event_type,
+            &format_timestamp(now),
+            snooze_until,
+            Some(reminder_id),
+            None,
+        )?;
+
+        tx.commit().map_err(SchedulingError::Database)?;
+
+        Ok(created)
+    }
+
+    /// Get schedules that are due for triggering based on frequency and last trigger time.
+    ///
+    /// Due-ness is evaluated in Rust (see `models::is_schedule_due`) rather than
+    /// in SQL, because `time_of_day` is a wall-clock time in each schedule's own
+    /// timezone and SQLite has no timezone database.
+    ///
+    /// # Frequency Logic
+    ///
+    /// A schedule that has fired before is due once the next occurrence after
+    /// `last_triggered_at` has arrived:
     ///
-    /// # Time-of-Day Filtering
+    /// - **Daily**: next local day at `time_of_day`
+    /// - **Weekly**: next `day_of_week` at `time_of_day`
+    /// - **Biweekly**: next `day_of_week` in every other week counted from `dtstart`
+    /// - **Monthly**: next `day_of_month` (clamped to short months)
+    /// - **Custom**: next occurrence of `rrule` not listed in `exdates`
     ///
-    /// Only returns schedules where `time_of_day <= current_time` to prevent premature triggers.
-    /// For example, if current time is 14:30, schedules set for 09:00 and 14:00 are returned,
-    /// but schedules set for 15:00 are not.
+    /// A schedule that has never fired is due if an occurrence falls between
+    /// local midnight and now.
     ///
     /// # Examples
     ///
     /// ```text
-    /// Current date/time: 2024-10-29 14:30
-    ///
-    /// Schedule A (Daily, 09:00, last_triggered: 2024-10-28 09:05):
-    ///   → RETURNED (different date, time passed)
+    /// Now: 2024-10-29 14:30 Europe/Berlin
     ///
-    /// Schedule B (Weekly, 14:00, last_triggered: 2024-10-22 14:05):
-    ///   → RETURNED (≥7 days elapsed, time passed)
+    /// Schedule A (Daily, 09:00 Europe/Berlin, last_triggered: 2024-10-28 09:05 local):
+    ///   → RETURNED (today's 09:00 occurrence has passed)
     ///
-    /// Schedule C (Daily, 15:00, last_triggered: 2024-10-28 15:05):
-    ///   → NOT RETURNED (time hasn't arrived yet)
+    /// Schedule C (Daily, 15:00 Europe/Berlin, last_triggered: 2024-10-28 15:05 local):
+    ///   → NOT RETURNED (today's occurrence hasn't arrived yet)
     ///
     /// Schedule D (Weekly, 09:00, last_triggered: 2024-10-27 09:05):
-    ///   → NOT RETURNED (only 2 days elapsed, need 7)
+    ///   → NOT RETURNED (next occurrence is 2024-11-03)
     /// ```
     fn get_due_schedules_with_conn(
         &self,
         conn: &rusqlite::Connection,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<AssessmentSchedule>, SchedulingError> {
+        let schedules = self.get_active_schedules_with_conn(conn)?;
+
+        let mut due = Vec::new();
+        for schedule in schedules {
+            match is_schedule_due(&schedule, now) {
+                Ok(true) => due.push(schedule),
+                Ok(false) => {}
+                // A single malformed schedule must not block every other reminder
+                Err(e) => warn!(
+                    schedule_id = schedule.id,
+                    error = %e,
+                    "Skipping schedule with invalid configuration"
+                ),
+            }
+        }
+
+        // Earliest time of day first, as before
+        due.sort_by(|a, b| a.time_of_day.cmp(&b.time_of_day));
+
+        Ok(due)
+    }
+
+    /// Enabled schedules whose target can still be acted on.
+    /// Reminders for deleted activities/groups or archived groups stay quiet.
+    fn get_active_schedules_with_conn(
+        &self,
+        conn: &rusqlite::Connection,
     ) -> Result<Vec<AssessmentSchedule>, SchedulingError> {
-        // Get current time - use SQLite's built-in datetime for consistency
-        let now = chrono::Utc::now();
-        let current_time = now.format("%H:%M").to_string();
-
-        // Frequency-aware query to prevent schedules from triggering too frequently
-        // Uses julianday for day calculations to handle all frequency types correctly
-        let query = "SELECT s.id, s.assessment_type_id, a.code, a.name, s.frequency, s.time_of_day,
-                            s.day_of_week, s.day_of_month, s.enabled, s.last_triggered_at,
-                            s.created_at, s.updated_at
-                     FROM assessment_schedules s
-                     JOIN assessment_types a ON s.assessment_type_id = a.id
-                     WHERE s.enabled = 1
-                       AND s.time_of_day <= ?
-                       AND (
-                         s.last_triggered_at IS NULL OR
-                         (s.frequency = 'daily' AND DATE(s.last_triggered_at) < DATE('now')) OR
-                         (s.frequency = 'weekly' AND julianday('now') - julianday(s.last_triggered_at) >= 7) OR
-                         (s.frequency = 'biweekly' AND julianday('now') - julianday(s.last_triggered_at) >= 14) OR
-                         (s.frequency = 'monthly' AND DATE(s.last_triggered_at, '+1 month') <= DATE('now'))
-                       )
-                     ORDER BY s.time_of_day ASC";
-
-        let mut stmt = conn.prepare(query)?;
+        let query = format!(
+            "{} WHERE s.enabled = 1
+               AND act.deleted_at IS NULL
+               AND g.deleted_at IS NULL AND g.archived_at IS NULL
+               AND ag.deleted_at IS NULL AND ag.archived_at IS NULL",
+            SCHEDULE_SELECT
+        );
+        let mut stmt = conn.prepare(&query)?;
         let schedules = stmt
-            .query_map(params![current_time], |row| self.map_schedule_row(row))?
+            .query_map([], |row| self.map_schedule_row(row))?
             .collect::<Result<Vec<_>, _>>()?;
 
         Ok(schedules)
@@ -360,23 +1361,15 @@ impl SchedulingRepository {
         enabled_only: bool,
     ) -> Result<Vec<AssessmentSchedule>, SchedulingError> {
         let query = if enabled_only {
-            "SELECT s.id, s.assessment_type_id, a.code, a.name, s.frequency, s.time_of_day,
-                    s.day_of_week, s.day_of_month, s.enabled, s.last_triggered_at,
-                    s.created_at, s.updated_at
-             FROM assessment_schedules s
-             JOIN assessment_types a ON s.assessment_type_id = a.id
-             WHERE s.enabled = 1
-             ORDER BY s.created_at DESC"
+            format!(
+                "{} WHERE s.enabled = 1 ORDER BY s.created_at DESC",
+                SCHEDULE_SELECT
+            )
         } else {
-            "SELECT s.id, s.assessment_type_id, a.code, a.name, s.frequency, s.time_of_day,
-                    s.day_of_week, s.day_of_month, s.enabled, s.last_triggered_at,
-                    s.created_at, s.updated_at
-             FROM assessment_schedules s
-             JOIN assessment_types a ON s.assessment_type_id = a.id
-             ORDER BY s.created_at DESC"
+            format!("{} ORDER BY s.created_at DESC", SCHEDULE_SELECT)
         };
 
-        let mut stmt = conn.prepare(query)?;
+        let mut stmt = conn.prepare(&query)?;
         let schedules = stmt
             .query_map([], |row| self.map_schedule_row(row))?
             .collect::<Result<Vec<_>, _>>()?;
@@ -390,12 +1383,7 @@ impl SchedulingRepository {
         id: i32,
     ) -> Result<AssessmentSchedule, SchedulingError> {
         conn.query_row(
-            "SELECT s.id, s.assessment_type_id, a.code, a.name, s.frequency, s.time_of_day,
-                    s.day_of_week, s.day_of_month, s.enabled, s.last_triggered_at,
-                    s.created_at, s.updated_at
-             FROM assessment_schedules s
-             JOIN assessment_types a ON s.assessment_type_id = a.id
-             WHERE s.id = ?",
+            &format!("{} WHERE s.id = ?", SCHEDULE_SELECT),
             params![id],
             |row| self.map_schedule_row(row),
         )
@@ -406,27 +1394,365 @@ impl SchedulingRepository {
     }
 
     fn map_schedule_row(&self, row: &rusqlite::Row) -> rusqlite::Result<AssessmentSchedule> {
-        let frequency_str: String = row.get(4)?;
-        let frequency = ScheduleFrequency::from_str(&frequency_str)
-            .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
+        let to_sql_err = |e: SchedulingError| rusqlite::Error::ToSqlConversionFailure(Box::new(e));
+
+        let kind: String = row.get(1)?;
+        let assessment_type_name: Option<String> = row.get(4)?;
+        let activity_name: Option<String> = row.get(6)?;
+        let group_name: Option<String> = row.get(8)?;
+        let target =
+            ScheduleTarget::from_columns(&kind, row.get(2)?, row.get(5)?, row.get(7)?, row.get(9)?)
+                .map_err(to_sql_err)?;
+
+        let target_name = match &target {
+            ScheduleTarget::Assessment { .. } => assessment_type_name.clone().unwrap_or_default(),
+            ScheduleTarget::MoodCheckin => "Mood check-in".to_string(),
+            ScheduleTarget::Activity { .. } => activity_name.unwrap_or_default(),
+            ScheduleTarget::ActivityGroup { .. } => group_name.unwrap_or_default(),
+            ScheduleTarget::Habit { text } => text.clone(),
+        };
+
+        let frequency_str: String = row.get(10)?;
+        let frequency = ScheduleFrequency::from_str(&frequency_str).map_err(to_sql_err)?;
+        let exdates: String = row.get(21)?;
+        let missed_policy: String = row.get(22)?;
 
         Ok(AssessmentSchedule {
             id: row.get(0)?,
-            assessment_type_id: row.get(1)?,
-            assessment_type_code: row.get(2)?,
-            assessment_type_name: row.get(3)?,
+            target,
+            target_name,
+            assessment_type_id: row.get(2)?,
+            assessment_type_code: row.get(3)?,
+            assessment_type_name,
             frequency,
-            time_of_day: row.get(5)?,
-            day_of_week: row.get(6)?,
-            day_of_month: row.get(7)?,
-            enabled: row.get(8)?,
-            last_triggered_at: row.get(9)?,
-            created_at: row.get(10)?,
-            updated_at: row.get(11)?,
+            time_of_day: row.get(11)?,
+            day_of_week: row.get(12)?,
+            day_of_month: row.get(13)?,
+            rrule: row.get(19)?,
+            dtstart: row.get(20)?,
+            exdates: exdates
+                .split(',')
+                .filter(|e| !e.is_empty())
+                .map(String::from)
+                .collect(),
+            timezone: row.get(18)?,
+            enabled: row.get(14)?,
+            missed_policy: MissedReminderPolicy::from_str(&missed_policy).map_err(to_sql_err)?,
+            last_triggered_at: row.get(15)?,
+            created_at: row.get(16)?,
+            updated_at: row.get(17)?,
         })
     }
 }
 
+/// Schedule columns plus the display names of every target kind.
+/// Targets are LEFT JOINed because only the column matching `target_kind` is set;
+/// `ag` is the group of an activity target.
+const SCHEDULE_SELECT: &str = "SELECT s.id, s.target_kind, s.assessment_type_id, a.code, a.name,
+        s.activity_id, act.name, s.activity_group_id, g.name, s.habit_text,
+        s.frequency, s.time_of_day, s.day_of_week, s.day_of_month, s.enabled,
+        s.last_triggered_at, s.created_at, s.updated_at, s.timezone,
+        s.rrule, s.dtstart, s.exdates, s.missed_policy
+     FROM assessment_schedules s
+     LEFT JOIN assessment_types a ON s.assessment_type_id = a.id
+     LEFT JOIN activities act ON s.activity_id = act.id
+     LEFT JOIN activity_groups g ON s.activity_group_id = g.id
+     LEFT JOIN activity_groups ag ON act.group_id = ag.id";
+
+const REMINDER_EVENT_SELECT: &str = "SELECT id, schedule_id, event_type, occurred_at, snooze_until,
+        related_event_id, assessment_response_id
+     FROM reminder_events";
+
+fn map_reminder_event_row(row: &rusqlite::Row) -> rusqlite::Result<ReminderEvent> {
+    let event_type: String = row.get(2)?;
+    Ok(ReminderEvent {
+        id: row.get(0)?,
+        schedule_id: row.get(1)?,
+        event_type: ReminderEventType::from_str(&event_type)
+            .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?,
+        occurred_at: row.get(3)?,
+        snooze_until: row.get(4)?,
+        related_event_id: row.get(5)?,
+        assessment_response_id: row.get(6)?,
+    })
+}
+
+fn get_reminder_event_with_conn(
+    conn: &rusqlite::Connection,
+    id: i32,
+) -> Result<ReminderEvent, SchedulingError> {
+    conn.query_row(
+        &format!("{} WHERE id = ?", REMINDER_EVENT_SELECT),
+        params![id],
+        map_reminder_event_row,
+    )
+    .map_err(|e| match e {
+        rusqlite::Error::QueryReturnedNoRows => SchedulingError::ReminderEventNotFound(id),
+        other => SchedulingError::Database(other),
+    })
+}
+
+/// Append an event to the reminder log and return it
+fn insert_reminder_event(
+    conn: &rusqlite::Connection,
+    schedule_id: i32,
+    event_type: ReminderEventType,
+    occurred_at: &str,
+    snooze_until: Option<&str>,
+    related_event_id: Option<i32>,
+    assessment_response_id: Option<i32>,
+) -> Result<ReminderEvent, SchedulingError> {
+    conn.execute(
+        "INSERT INTO reminder_events
+         (schedule_id, event_type, occurred_at, snooze_until, related_event_id,
+          assessment_response_id)
+         VALUES (?, ?, ?, ?, ?, ?)",
+        params![
+            schedule_id,
+            event_type.as_str(),
+            occurred_at,
+            snooze_until,
+            related_event_id,
+            assessment_response_id,
+        ],
+    )?;
+
+    get_reminder_event_with_conn(conn, conn.last_insert_rowid() as i32)
+}
+
+const MISSED_REMINDER_SELECT: &str = "SELECT id, schedule_id, scheduled_for, local_time, policy,
+        reminder_event_id, detected_at, acknowledged_at
+     FROM missed_reminders";
+
+fn parse_policy(policy: String) -> rusqlite::Result<MissedReminderPolicy> {
+    MissedReminderPolicy::from_str(&policy)
+        .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))
+}
+
+/// Record a missed occurrence of a schedule
+fn insert_missed_reminder(
+    conn: &rusqlite::Connection,
+    schedule: &AssessmentSchedule,
+    (local, instant): &(NaiveDateTime, DateTime<Utc>),
+    reminder_event_id: Option<i32>,
+    detected_at: &str,
+) -> Result<MissedReminder, SchedulingError> {
+    let missed = MissedReminder {
+        id: 0,
+        schedule_id: schedule.id,
+        target: schedule.target.clone(),
+        target_name: schedule.target_name.clone(),
+        scheduled_for: format_timestamp(*instant),
+        local_time: format_dtstart(*local),
+        policy: schedule.missed_policy,
+        reminder_event_id,
+        detected_at: detected_at.to_string(),
+        acknowledged_at: None,
+    };
+
+    conn.execute(
+        "INSERT INTO missed_reminders
+         (schedule_id, scheduled_for, local_time, policy, reminder_event_id, detected_at)
+         VALUES (?, ?, ?, ?, ?, ?)",
+        params![
+            missed.schedule_id,
+            missed.scheduled_for,
+            missed.local_time,
+            missed.policy.as_str(),
+            missed.reminder_event_id,
+            missed.detected_at,
+        ],
+    )?;
+
+    Ok(MissedReminder {
+        id: conn.last_insert_rowid() as i32,
+        ..missed
+    })
+}
+
+const ADAPTIVE_RULE_SELECT: &str = "SELECT id, schedule_id, position, comparison, severity_level,
+        consecutive_count, frequency, day_of_week, day_of_month
+     FROM schedule_adaptive_rules";
+
+fn get_adaptive_rules_with_conn(
+    conn: &rusqlite::Connection,
+    schedule_id: i32,
+) -> Result<Vec<AdaptiveRule>, SchedulingError> {
+    let to_sql_err = |e: SchedulingError| rusqlite::Error::ToSqlConversionFailure(Box::new(e));
+
+    let mut stmt = conn.prepare_cached(&format!(
+        "{} WHERE schedule_id = ? ORDER BY position",
+        ADAPTIVE_RULE_SELECT
+    ))?;
+    let rules = stmt
+        .query_map(params![schedule_id], |row| {
+            let comparison: String = row.get(3)?;
+            let frequency: String = row.get(6)?;
+            Ok(AdaptiveRule {
+                id: row.get(0)?,
+                schedule_id: row.get(1)?,
+                position: row.get(2)?,
+                comparison: SeverityComparison::from_str(&comparison).map_err(to_sql_err)?,
+                severity_level: row.get(4)?,
+                consecutive_count: row.get(5)?,
+                frequency: ScheduleFrequency::from_str(&frequency).map_err(to_sql_err)?,
+                day_of_week: row.get(7)?,
+                day_of_month: row.get(8)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(rules)
+}
+
+/// Newest completed responses of an assessment type, newest first.
+/// Unrecognised severities come back as `Unknown`, which no rule matches.
+fn get_recent_results_with_conn(
+    conn: &rusqlite::Connection,
+    assessment_type_id: Option<i32>,
+    limit: i32,
+) -> Result<Vec<RecentResult>, SchedulingError> {
+    let mut stmt = conn.prepare_cached(
+        "SELECT id, total_score, severity_level
+         FROM assessment_responses
+         WHERE assessment_type_id = ? AND status = 'completed'
+         ORDER BY completed_at DESC, id DESC
+         LIMIT ?",
+    )?;
+    let results = stmt
+        .query_map(params![assessment_type_id, limit], |row| {
+            let severity: Option<String> = row.get(2)?;
+            Ok(RecentResult {
+                response_id: row.get(0)?,
+                total_score: row.get(1)?,
+                severity_level: severity
+                    .and_then(|s| SeverityLevel::from_str(&s).ok())
+                    .unwrap_or(SeverityLevel::Unknown),
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(results)
+}
+
+const FREQUENCY_CHANGE_SELECT: &str = "SELECT id, schedule_id, rule_id, from_frequency,
+        to_frequency, from_rrule, to_rrule, reason, assessment_response_id, changed_at
+     FROM schedule_frequency_changes";
+
+fn map_frequency_change_row(row: &rusqlite::Row) -> rusqlite::Result<FrequencyChange> {
+    let to_sql_err = |e: SchedulingError| rusqlite::Error::ToSqlConversionFailure(Box::new(e));
+    let from_frequency: String = row.get(3)?;
+    let to_frequency: String = row.get(4)?;
+
+    Ok(FrequencyChange {
+        id: row.get(0)?,
+        schedule_id: row.get(1)?,
+        rule_id: row.get(2)?,
+        from_frequency: ScheduleFrequency::from_str(&from_frequency).map_err(to_sql_err)?,
+        to_frequency: ScheduleFrequency::from_str(&to_frequency).map_err(to_sql_err)?,
+        from_rrule: row.get(5)?,
+        to_rrule: row.get(6)?,
+        reason: row.get(7)?,
+        assessment_response_id: row.get(8)?,
+        changed_at: row.get(9)?,
+    })
+}
+
+fn get_frequency_change_with_conn(
+    conn: &rusqlite::Connection,
+    id: i32,
+) -> Result<FrequencyChange, SchedulingError> {
+    Ok(conn.query_row(
+        &format!("{} WHERE id = ?", FREQUENCY_CHANGE_SELECT),
+        params![id],
+        map_frequency_change_row,
+    )?)
+}
+
+/// UTC timestamp in the format SQLite's CURRENT_TIMESTAMP uses
+fn format_timestamp(instant: DateTime<Utc>) -> String {
+    instant.format("%Y-%m-%d %H:%M:%S").to_string()
+}
+
+/// A target spread over the `assessment_schedules` target columns
+struct TargetColumns {
+    kind: &'static str,
+    assessment_type_id: Option<i32>,
+    activity_id: Option<i32>,
+    activity_group_id: Option<i32>,
+    habit_text: Option<String>,
+}
+
+impl From<&ScheduleTarget> for TargetColumns {
+    fn from(target: &ScheduleTarget) -> Self {
+        let mut columns = TargetColumns {
+            kind: target.kind(),
+            assessment_type_id: None,
+            activity_id: None,
+            activity_group_id: None,
+            habit_text: None,
+        };
+        match target {
+            ScheduleTarget::Assessment { assessment_type_id } => {
+                columns.assessment_type_id = Some(*assessment_type_id)
+            }
+            ScheduleTarget::MoodCheckin => {}
+            ScheduleTarget::Activity { activity_id } => columns.activity_id = Some(*activity_id),
+            ScheduleTarget::ActivityGroup { activity_group_id } => {
+                columns.activity_group_id = Some(*activity_group_id)
+            }
+            ScheduleTarget::Habit { text } => columns.habit_text = Some(text.trim().to_string()),
+        }
+        columns
+    }
+}
+
+/// Check that the assessment type, activity or group a schedule points at exists
+fn verify_target_exists(
+    conn: &rusqlite::Connection,
+    target: &ScheduleTarget,
+) -> Result<(), SchedulingError> {
+    let exists = |sql: &str, id: i32| -> Result<bool, SchedulingError> {
+        Ok(conn.query_row(sql, params![id], |row| row.get::<_, i32>(0))? > 0)
+    };
+
+    match target {
+        ScheduleTarget::Assessment { assessment_type_id } => {
+            if !exists(
+                "SELECT COUNT(*) FROM assessment_types WHERE id = ?",
+                *assessment_type_id,
+            )? {
+                return Err(SchedulingError::NotFound(*assessment_type_id));
+            }
+        }
+        ScheduleTarget::Activity { activity_id } => {
+            if !exists(
+                "SELECT COUNT(*) FROM activities WHERE id = ? AND deleted_at IS NULL",
+                *activity_id,
+            )? {
+                return Err(SchedulingError::TargetNotFound {
+                    kind: "activity",
+                    id: *activity_id,
+                });
+            }
+        }
+        ScheduleTarget::ActivityGroup { activity_group_id } => {
+            if !exists(
+                "SELECT COUNT(*) FROM activity_groups WHERE id = ? AND deleted_at IS NULL",
+                *activity_group_id,
+            )? {
+                return Err(SchedulingError::TargetNotFound {
+                    kind: "activity_group",
+                    id: *activity_group_id,
+                });
+            }
+        }
+        ScheduleTarget::MoodCheckin | ScheduleTarget::Habit { .. } => {}
+    }
+
+    Ok(())
+}
+
 // Trait implementation for testing with mocks
 use super::repository_trait::SchedulingRepositoryTrait;
 
@@ -449,4 +1775,37 @@ impl SchedulingRepositoryTrait for SchedulingRepository {
     fn delete_schedule(&self, id: i32) -> Result<(), SchedulingError> {
         self.delete_schedule(id)
     }
+
+    fn snooze_reminder(
+        &self,
+        event_id: i32,
+        minutes: i32,
+        now: DateTime<Utc>,
+    ) -> Result<ReminderEvent, SchedulingError> {
+        self.snooze_reminder(event_id, minutes, now)
+    }
+
+    fn dismiss_reminder(
+        &self,
+        event_id: i32,
+        now: DateTime<Utc>,
+    ) -> Result<ReminderEvent, SchedulingError> {
+        self.dismiss_reminder(event_id, now)
+    }
+
+    fn complete_reminder(
+        &self,
+        event_id: i32,
+        now: DateTime<Utc>,
+    ) -> Result<ReminderEvent, SchedulingError> {
+        self.complete_reminder(event_id, now)
+    }
+
+    fn set_adaptive_rules(
+        &self,
+        schedule_id: i32,
+        rules: Vec<AdaptiveRuleRequest>,
+    ) -> Result<Vec<AdaptiveRule>, SchedulingError> {
+        self.set_adaptive_rules(schedule_id, &rules)
+    }
 }
@@ -1,5 +1,8 @@
 // Scheduling repository trait - Interface for schedule data operations
 
+use chrono::{DateTime, Utc};
+
+use super::adaptive::{AdaptiveRule, AdaptiveRuleRequest};
 use super::models::*;
 
 #[cfg(test)]
@@ -27,4 +30,33 @@ pub trait SchedulingRepositoryTrait {
 
     /// Delete a schedule
     fn delete_schedule(&self, id: i32) -> Result<(), SchedulingError>;
+
+    /// Snooze a reminder for `minutes` from `now`
+    fn snooze_reminder(
+        &self,
+        event_id: i32,
+        minutes: i32,
+        now: DateTime<Utc>,
+    ) -> Result<ReminderEvent, SchedulingError>;
+
+    /// Dismiss a reminder
+    fn dismiss_reminder(
+        &self,
+        event_id: i32,
+        now: DateTime<Utc>,
+    ) -> Result<ReminderEvent, SchedulingError>;
+
+    /// Mark a reminder as done
+    fn complete_reminder(
+        &self,
+        event_id: i32,
+        now: DateTime<Utc>,
+    ) -> Result<ReminderEvent, SchedulingError>;
+
+    /// Replace a schedule's adaptive frequency rules
+    fn set_adaptive_rules(
+        &self,
+        schedule_id: i32,
+        rules: Vec<AdaptiveRuleRequest>,
+    ) -> Result<Vec<AdaptiveRule>, SchedulingError>;
 }
@@ -4,11 +4,25 @@
 use std::sync::Arc;
 use std::time::Duration;
 
-use tauri::AppHandle;
+use chrono::{DateTime, NaiveDate, Utc};
+use chrono_tz::Tz;
+use tauri::{AppHandle, Manager};
 use tokio::time::sleep;
 
+use crate::config::{AppConfig, NotificationSettings};
 use crate::db::Database;
+use crate::features::activities::{ActivityRepository, PlannedActivityReminder};
+use crate::features::insights::{DigestWeek, InsightsRepository, WeeklyDigest};
+use crate::features::streaks::models::MAX_GRACE_DAYS;
+use crate::features::streaks::{StreakMilestone, StreakRepository};
+use crate::AppState;
 
+use super::clock::{Clock, SystemClock};
+use super::models::{
+    parse_timezone, system_timezone, AssessmentSchedule, MissedReminderNotice, ScheduleTarget,
+};
+use super::notifier::{Notification, Notifier, TauriNotifier};
+use super::quiet_hours::NotificationGate;
 use super::repository::SchedulingRepository;
 
 /// Start the background scheduler
@@ -16,13 +30,14 @@ use super::repository::SchedulingRepository;
 pub fn start_scheduler(app_handle: AppHandle, db: Arc<Database>) {
     // Use Tauri's async runtime instead of tokio::spawn directly
     tauri::async_runtime::spawn(async move {
-        let repo = SchedulingRepository::new(db);
+        let mut scheduler = Scheduler::new(db, SystemClock, TauriNotifier::new(app_handle.clone()));
 
         loop {
-            // Check for due schedules and send notifications
-            if let Err(e) = check_and_notify(&app_handle, &repo).await {
-                eprintln!("[SCHEDULER][ERROR] Failed to check and notify: {}", e);
-            }
+            let settings = app_handle
+                .try_state::<AppState>()
+                .map(|state| SchedulerSettings::from_config(&state.config.lock()))
+                .unwrap_or_default();
+            scheduler.tick(&settings);
 
             // Wait 1 minute before next check
             sleep(Duration::from_secs(60)).await;
@@ -30,92 +45,580 @@ pub fn start_scheduler(app_handle: AppHandle, db: Arc<Database>) {
     });
 }
 
-/// Check for due schedules and send notifications
-async fn check_and_notify(
-    app_handle: &AppHandle,
-    repo: &SchedulingRepository,
-) -> anyhow::Result<()> {
-    // Get all due schedules
-    let due_schedules = repo.get_due_schedules()?;
-
-    if due_schedules.is_empty() {
-        return Ok(());
-    }
-
-    // Mark all schedules as triggered in a single batch transaction
-    // This is more efficient and ensures all-or-nothing marking
-    let schedule_ids: Vec<i32> = due_schedules.iter().map(|s| s.id).collect();
-    if let Err(e) = repo.mark_multiple_triggered(&schedule_ids) {
-        eprintln!(
-            "[SCHEDULER][WARN] Failed to mark schedules as triggered: {}",
-            e
-        );
-        return Ok(()); // Skip all notifications if marking failed
+/// Settings the scheduler reads on every pass
+#[derive(Debug, Clone)]
+pub struct SchedulerSettings {
+    pub notifications: NotificationSettings,
+    /// Timezone quiet hours, digest weeks and streak days are interpreted in
+    pub timezone: Tz,
+    pub streak_grace_days: u32,
+    /// Announce weekly digests; they are generated either way
+    pub weekly_digest_notification: bool,
+}
+
+impl SchedulerSettings {
+    /// Settings from the app config; quiet hours follow the system timezone
+    pub fn from_config(config: &AppConfig) -> Self {
+        Self {
+            notifications: config.notification_settings(),
+            timezone: parse_timezone(&system_timezone()).unwrap_or(chrono_tz::UTC),
+            streak_grace_days: config.streak_grace_days.min(MAX_GRACE_DAYS as u32),
+            weekly_digest_notification: config.weekly_digest_notification,
+        }
+    }
+}
+
+impl Default for SchedulerSettings {
+    fn default() -> Self {
+        Self::from_config(&AppConfig::default())
+    }
+}
+
+/// Reminder scheduler driven by an injectable clock and notifier.
+///
+/// Each [`tick`](Scheduler::tick) is one pass over everything that may be due
+/// at the clock's current time. The app ticks once a minute on the system
+/// clock; tests tick through virtual time with a manual clock.
+pub struct Scheduler<C: Clock, N: Notifier> {
+    repo: SchedulingRepository,
+    streak_repo: StreakRepository,
+    activity_repo: ActivityRepository,
+    insights_repo: InsightsRepository,
+    clock: C,
+    notifier: N,
+    reconciled: bool,
+    /// Monday of the last week whose digest is known to be recorded
+    digest_week: Option<NaiveDate>,
+    /// Inputs of the last milestone evaluation, to skip unchanged ones
+    milestone_check: Option<MilestoneCheck>,
+}
+
+/// What streak milestones depend on: the local day, the settings and the logs
+#[derive(Debug, Clone, PartialEq)]
+struct MilestoneCheck {
+    day: NaiveDate,
+    timezone: Tz,
+    grace_days: u32,
+    latest_log_ids: (i64, i64),
+}
+
+impl<C: Clock, N: Notifier> Scheduler<C, N> {
+    pub fn new(db: Arc<Database>, clock: C, notifier: N) -> Self {
+        Self {
+            repo: SchedulingRepository::new(Arc::clone(&db)),
+            streak_repo: StreakRepository::new(Arc::clone(&db)),
+            activity_repo: ActivityRepository::new(Arc::clone(&db)),
+            insights_repo: InsightsRepository::new(db),
+            clock,
+            notifier,
+            reconciled: false,
+            digest_week: None,
+            milestone_check: None,
+        }
     }
 
-    // Send notifications (failures don't affect database state)
-    for schedule in due_schedules {
-        if let Err(e) = send_notification(
-            app_handle,
-            &schedule.assessment_type_name,
-            &schedule.assessment_type_code,
-            schedule.id,
-        ) {
+    /// Run one scheduler pass at the clock's current time
+    pub fn tick(&mut self, settings: &SchedulerSettings) {
+        let now = self.clock.now();
+
+        // Adjust adaptive schedules to new results before checking what's due.
+        // Submissions do this too; this catches any that failed
+        if let Err(e) = self.apply_adaptive_rules(now) {
+            eprintln!("[SCHEDULER][ERROR] Failed to apply adaptive rules: {}", e);
+        }
+
+        let gate = settings.notifications.gate(now, &settings.timezone);
+
+        // Deferred notifications stay pending until the next allowed check
+        if gate == NotificationGate::Defer {
+            return;
+        }
+
+        // Catch up on reminders that fell due while the app was closed,
+        // once per run and only when they can actually be shown
+        if !self.reconciled && gate == NotificationGate::Allow {
+            if let Err(e) = self.reconcile_missed_reminders(now) {
+                eprintln!(
+                    "[SCHEDULER][ERROR] Failed to reconcile missed reminders: {}",
+                    e
+                );
+            }
+            self.reconciled = true;
+        }
+
+        // Check for due schedules and send notifications
+        if let Err(e) = self.check_and_notify(now, gate) {
+            eprintln!("[SCHEDULER][ERROR] Failed to check and notify: {}", e);
+        }
+
+        // Fire snoozed reminders again once their snooze runs out.
+        // The user asked for these, so they wait out quiet time instead of being dropped
+        if gate == NotificationGate::Allow {
+            if let Err(e) = self.check_snoozed_reminders(now) {
+                eprintln!(
+                    "[SCHEDULER][ERROR] Failed to check snoozed reminders: {}",
+                    e
+                );
+            }
+        }
+
+        // Remind about planned activities coming up
+        if let Err(e) = self.check_plan_reminders(now, gate) {
+            eprintln!("[SCHEDULER][ERROR] Failed to check plan reminders: {}", e);
+        }
+
+        // Announce streak milestones reached since the last check
+        if let Err(e) = self.check_streak_milestones(now, gate, settings) {
             eprintln!(
-                "[SCHEDULER][WARN] Failed to send notification for schedule {} ({}): {}",
-                schedule.id, schedule.assessment_type_name, e
+                "[SCHEDULER][ERROR] Failed to check streak milestones: {}",
+                e
             );
         }
-    }
 
-    Ok(())
-}
+        // Summarize the week that just ended
+        if let Err(e) = self.check_weekly_digest(now, gate, settings) {
+            eprintln!("[SCHEDULER][ERROR] Failed to check weekly digest: {}", e);
+        }
+    }
 
-/// Send a notification using tauri-plugin-notification
-/// T179: Notification stores assessment type code for click navigation
-fn send_notification(
-    app_handle: &AppHandle,
-    assessment_name: &str,
-    assessment_type_code: &str,
-    schedule_id: i32,
-) -> anyhow::Result<()> {
-    use tauri_plugin_notification::NotificationExt;
-
-    // T179: Store the assessment type code in the notification
-    // The frontend listener will use this to navigate to the correct assessment
-    let notification_body = format!(
-        "Time to complete: {}. Click to open assessment.",
-        assessment_name
-    );
-
-    let notification_result = app_handle
-        .notification()
-        .builder()
-        .title("Assessment Reminder")
-        .body(notification_body)
-        .icon("icon") // Use app icon
-        // T179: Store assessment info using extra key-value pairs
-        .extra("assessment_type_code", assessment_type_code)
-        .extra("assessment_name", assessment_name)
-        .extra("schedule_id", schedule_id)
-        .show();
-
-    match notification_result {
-        Ok(_) => {
+    /// Switch schedule frequencies whose adaptive rules match new results
+    fn apply_adaptive_rules(&self, now: DateTime<Utc>) -> anyhow::Result<()> {
+        for change in self.repo.apply_adaptive_rules(None, now)? {
             println!(
-                "[SCHEDULER][INFO] Notification sent for schedule {} - {} (code: {})",
-                schedule_id, assessment_name, assessment_type_code
+                "[SCHEDULER][INFO] Schedule {} changed from {} to {}: {}",
+                change.schedule_id,
+                change.from_frequency.as_str(),
+                change.to_frequency.as_str(),
+                change.reason
             );
-            Ok(())
         }
-        Err(e) => {
-            eprintln!(
-                "[SCHEDULER][WARN] Failed to send notification for schedule {} - {} (code: {}): {}",
-                schedule_id, assessment_name, assessment_type_code, e
+        Ok(())
+    }
+
+    /// Check for due schedules and send notifications
+    ///
+    /// With [`NotificationGate::Drop`] due schedules move on to their next
+    /// occurrence without a notification.
+    fn check_and_notify(&self, now: DateTime<Utc>, gate: NotificationGate) -> anyhow::Result<()> {
+        // Get all due schedules
+        let due_schedules = self.repo.get_due_schedules_at(now)?;
+
+        if due_schedules.is_empty() {
+            return Ok(());
+        }
+
+        // Mark all schedules as triggered in a single batch transaction
+        // This is more efficient and ensures all-or-nothing marking
+        let schedule_ids: Vec<i32> = due_schedules.iter().map(|s| s.id).collect();
+
+        if gate == NotificationGate::Drop {
+            self.repo.mark_multiple_dropped(&schedule_ids, now)?;
+            println!(
+                "[SCHEDULER][INFO] Dropped {} reminder(s) during quiet time",
+                schedule_ids.len()
             );
-            Err(anyhow::anyhow!("Failed to send notification: {}", e))
+            return Ok(());
         }
+
+        let events = match self.repo.mark_multiple_triggered(&schedule_ids, now) {
+            Ok(events) => events,
+            Err(e) => {
+                eprintln!(
+                    "[SCHEDULER][WARN] Failed to mark schedules as triggered: {}",
+                    e
+                );
+                return Ok(()); // Skip all notifications if marking failed
+            }
+        };
+
+        // Send notifications (failures don't affect database state)
+        for (schedule, event) in due_schedules.iter().zip(&events) {
+            self.show_reminder(schedule, reminder_notification(schedule, event.id));
+        }
+
+        Ok(())
+    }
+
+    /// Record occurrences missed while the app was closed and send the catch-up
+    /// notifications their schedules' policies ask for
+    fn reconcile_missed_reminders(&self, now: DateTime<Utc>) -> anyhow::Result<()> {
+        let notices = self.repo.reconcile_missed_reminders(now)?;
+
+        for notice in &notices {
+            self.show_reminder(&notice.schedule, missed_reminder_notification(notice));
+        }
+
+        Ok(())
     }
+
+    /// Re-send reminders whose snooze has run out
+    fn check_snoozed_reminders(&self, now: DateTime<Utc>) -> anyhow::Result<()> {
+        let snoozes = self.repo.get_due_snoozes(now)?;
+
+        if snoozes.is_empty() {
+            return Ok(());
+        }
+
+        // Record first so a failing notification can't cause repeated re-fires
+        self.repo.mark_snoozes_refired(&snoozes, now)?;
+
+        for snooze in &snoozes {
+            match self.repo.get_schedule(snooze.schedule_id) {
+                Ok(schedule) => self.show_reminder(
+                    &schedule,
+                    reminder_notification(&schedule, snooze.reminder_id()),
+                ),
+                Err(e) => eprintln!(
+                    "[SCHEDULER][WARN] Failed to re-send snoozed reminder {} for schedule {}: {}",
+                    snooze.reminder_id(),
+                    snooze.schedule_id,
+                    e
+                ),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Send reminders for planned activities whose reminder time has come
+    fn check_plan_reminders(
+        &self,
+        now: DateTime<Utc>,
+        gate: NotificationGate,
+    ) -> anyhow::Result<()> {
+        let now = now.naive_utc();
+        let reminders = self.activity_repo.get_due_plan_reminders(now)?;
+
+        if reminders.is_empty() {
+            return Ok(());
+        }
+
+        // Mark first so a failing notification can't cause repeated reminders
+        let plan_ids: Vec<i32> = reminders.iter().map(|r| r.planned_activity_id).collect();
+        self.activity_repo.mark_plans_reminded(&plan_ids, now)?;
+
+        if gate == NotificationGate::Drop {
+            return Ok(());
+        }
+
+        for reminder in &reminders {
+            match self.notifier.notify(&plan_reminder_notification(reminder)) {
+                Ok(()) => println!(
+                    "[SCHEDULER][INFO] Plan reminder sent for planned activity {} - {}",
+                    reminder.planned_activity_id, reminder.activity_name
+                ),
+                Err(e) => eprintln!(
+                    "[SCHEDULER][WARN] Failed to send plan reminder for planned activity {} ({}): {}",
+                    reminder.planned_activity_id, reminder.activity_name, e
+                ),
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Record newly reached streak milestones and notify about every milestone
+    /// not yet announced, so ones whose notification failed are retried.
+    ///
+    /// Streaks are only re-evaluated when the local day, the settings or the
+    /// logs changed since the last pass, since that scans the full history.
+    fn check_streak_milestones(
+        &mut self,
+        now: DateTime<Utc>,
+        gate: NotificationGate,
+        settings: &SchedulerSettings,
+    ) -> anyhow::Result<()> {
+        let check = MilestoneCheck {
+            day: now.with_timezone(&settings.timezone).date_naive(),
+            timezone: settings.timezone,
+            grace_days: settings.streak_grace_days,
+            latest_log_ids: self.streak_repo.latest_log_ids()?,
+        };
+        if self.milestone_check.as_ref() != Some(&check) {
+            let now = now.format("%Y-%m-%d %H:%M:%S").to_string();
+            self.streak_repo.record_new_milestones(
+                settings.streak_grace_days as i32,
+                &now,
+                settings.timezone,
+            )?;
+            self.milestone_check = Some(check);
+        }
+        let pending = self.streak_repo.get_pending_milestones()?;
+
+        // Milestones reached while notifications are dropped are recorded silently
+        if gate == NotificationGate::Drop {
+            let ids: Vec<i32> = pending.iter().map(|m| m.id).collect();
+            self.streak_repo.mark_milestones_notified(&ids)?;
+            return Ok(());
+        }
+
+        // Only the highest milestone per streak is announced; an older one still
+        // pending after a failed notification is superseded and handled silently
+        let highest = highest_milestone_per_streak(&pending);
+        let mut handled_ids: Vec<i32> = pending
+            .iter()
+            .filter(|m| !highest.iter().any(|h| h.id == m.id))
+            .map(|m| m.id)
+            .collect();
+        for milestone in highest {
+            match self.notifier.notify(&streak_notification(milestone)) {
+                Ok(()) => {
+                    println!(
+                        "[SCHEDULER][INFO] Streak milestone notification sent - {} ({} days)",
+                        milestone.target_name, milestone.milestone
+                    );
+                    handled_ids.push(milestone.id);
+                }
+                // Left pending, so the next pass tries again
+                Err(e) => eprintln!(
+                    "[SCHEDULER][WARN] Failed to send streak notification for {} ({} days), will retry: {}",
+                    milestone.target_name, milestone.milestone, e
+                ),
+            }
+        }
+
+        self.streak_repo.mark_milestones_notified(&handled_ids)?;
+
+        Ok(())
+    }
+
+    /// Record the digest of the last completed week and announce it.
+    ///
+    /// Each week's digest is generated and announced once. Weeks with nothing
+    /// to report, dropped notifications and a disabled setting are marked as
+    /// handled without a notification.
+    fn check_weekly_digest(
+        &mut self,
+        now: DateTime<Utc>,
+        gate: NotificationGate,
+        settings: &SchedulerSettings,
+    ) -> anyhow::Result<()> {
+        let week = DigestWeek::completed_before(now, settings.timezone);
+        if self.digest_week == Some(week.start) {
+            return Ok(());
+        }
+
+        let generated_at = now.format("%Y-%m-%d %H:%M:%S").to_string();
+        let recorded = self
+            .insights_repo
+            .record_weekly_digest(&week, &generated_at)?;
+        self.digest_week = Some(week.start);
+
+        let Some(digest) = recorded else {
+            return Ok(());
+        };
+        println!(
+            "[SCHEDULER][INFO] Weekly digest recorded for week of {}",
+            digest.week_start
+        );
+
+        let announce = settings.weekly_digest_notification
+            && gate == NotificationGate::Allow
+            && !digest.is_empty();
+        if announce {
+            if let Err(e) = self.notifier.notify(&weekly_digest_notification(&digest)) {
+                eprintln!(
+                    "[SCHEDULER][WARN] Failed to send weekly digest for week of {}: {}",
+                    digest.week_start, e
+                );
+                return Ok(());
+            }
+        }
+        self.insights_repo
+            .mark_digest_notified(digest.id, &generated_at)?;
+
+        Ok(())
+    }
+
+    /// Show a schedule reminder; failures are logged and don't affect database state
+    fn show_reminder(&self, schedule: &AssessmentSchedule, reminder: Notification) {
+        match self.notifier.notify(&reminder) {
+            Ok(()) => println!(
+                "[SCHEDULER][INFO] Notification sent for schedule {} - {} ({})",
+                schedule.id,
+                schedule.target_name,
+                schedule.target.kind()
+            ),
+            Err(e) => eprintln!(
+                "[SCHEDULER][WARN] Failed to send notification for schedule {} - {} ({}): {}",
+                schedule.id,
+                schedule.target_name,
+                schedule.target.kind(),
+                e
+            ),
+        }
+    }
+}
+
+/// Build a planned activity reminder
+fn plan_reminder_notification(reminder: &PlannedActivityReminder) -> Notification {
+    use serde_json::json;
+
+    Notification {
+        title: "Planned Activity",
+        body: format!("Coming up: {}", reminder.activity_name),
+        extra: vec![
+            ("planned_activity_id", json!(reminder.planned_activity_id)),
+            ("activity_id", json!(reminder.activity_id)),
+            ("scheduled_at", json!(reminder.scheduled_at)),
+        ],
+    }
+}
+
+/// Build the weekly digest notification from its first sentences
+fn weekly_digest_notification(digest: &WeeklyDigest) -> Notification {
+    use serde_json::json;
+
+    Notification {
+        title: "Your Week in Review",
+        body: digest
+            .sentences
+            .iter()
+            .take(2)
+            .cloned()
+            .collect::<Vec<_>>()
+            .join(" "),
+        extra: vec![
+            ("weekly_digest_id", json!(digest.id)),
+            ("week_start", json!(digest.week_start)),
+            ("route", json!("/charts")),
+        ],
+    }
+}
+
+/// Keep only the largest milestone of each (kind, target, run)
+fn highest_milestone_per_streak(milestones: &[StreakMilestone]) -> Vec<&StreakMilestone> {
+    let mut highest: Vec<&StreakMilestone> = Vec::new();
+    for milestone in milestones {
+        match highest.iter_mut().find(|m| {
+            m.kind == milestone.kind
+                && m.target_id == milestone.target_id
+                && m.streak_start_date == milestone.streak_start_date
+        }) {
+            Some(existing) if existing.milestone < milestone.milestone => *existing = milestone,
+            Some(_) => {}
+            None => highest.push(milestone),
+        }
+    }
+    highest
+}
+
+/// Build a streak milestone notification
+fn streak_notification(milestone: &StreakMilestone) -> Notification {
+    use serde_json::json;
+
+    Notification {
+        title: "Streak Milestone",
+        body: format!(
+            "{}: {} day streak! Keep it going.",
+            milestone.target_name, milestone.milestone
+        ),
+        extra: vec![
+            ("streak_kind", json!(milestone.kind.as_str())),
+            ("target_id", json!(milestone.target_id)),
+            ("milestone", json!(milestone.milestone)),
+        ],
+    }
+}
+
+/// Build the reminder for a schedule's target.
+///
+/// Every payload carries `schedule_id`, `reminder_event_id` (to snooze, dismiss
+/// or complete the reminder), `target_kind` and a frontend `route`; the
+/// remaining keys identify the target. Assessment reminders keep the
+/// `assessment_type_code`/`assessment_name` keys the notification listener used before.
+fn reminder_notification(schedule: &AssessmentSchedule, reminder_id: i32) -> Notification {
+    use serde_json::json;
+
+    let mut extra = vec![
+        ("schedule_id", json!(schedule.id)),
+        ("reminder_event_id", json!(reminder_id)),
+        ("target_kind", json!(schedule.target.kind())),
+    ];
+
+    let (title, body, route) = match &schedule.target {
+        ScheduleTarget::Assessment { .. } => {
+            let code = schedule.assessment_type_code.clone().unwrap_or_default();
+            extra.push(("assessment_type_code", json!(code)));
+            extra.push(("assessment_name", json!(schedule.target_name)));
+            (
+                "Assessment Reminder",
+                format!(
+                    "Time to complete: {}. Click to open assessment.",
+                    schedule.target_name
+                ),
+                format!("/assessments/{}", code.to_lowercase()),
+            )
+        }
+        ScheduleTarget::MoodCheckin => (
+            "Mood Check-in",
+            "How are you feeling? Click to record a mood check-in.".to_string(),
+            "/mood".to_string(),
+        ),
+        ScheduleTarget::Activity { activity_id } => {
+            extra.push(("activity_id", json!(activity_id)));
+            extra.push(("activity_name", json!(schedule.target_name)));
+            (
+                "Activity Reminder",
+                format!("Time to log: {}.", schedule.target_name),
+                format!("/activity-groups?activity_id={}", activity_id),
+            )
+        }
+        ScheduleTarget::ActivityGroup { activity_group_id } => {
+            extra.push(("activity_group_id", json!(activity_group_id)));
+            extra.push(("activity_group_name", json!(schedule.target_name)));
+            (
+                "Activity Reminder",
+                format!("Time to log an activity from {}.", schedule.target_name),
+                format!("/activity-groups?group_id={}", activity_group_id),
+            )
+        }
+        ScheduleTarget::Habit { text } => {
+            extra.push(("habit_text", json!(text)));
+            ("Habit Reminder", text.clone(), "/".to_string())
+        }
+    };
+    extra.push(("route", json!(route)));
+
+    Notification { title, body, extra }
+}
+
+/// Build the catch-up reminder for occurrences missed while the app was closed.
+///
+/// Same payload as [`reminder_notification`] plus `missed_count` and
+/// `missed_reminder_ids`; the body says when the reminder was due.
+fn missed_reminder_notification(notice: &MissedReminderNotice) -> Notification {
+    use serde_json::json;
+
+    let mut reminder = reminder_notification(&notice.schedule, notice.event.id);
+
+    let first_due = notice
+        .missed
+        .first()
+        .map(|m| format_local_time(&m.local_time))
+        .unwrap_or_default();
+    let prefix = match notice.missed.len() {
+        1 => format!("Missed reminder from {}.", first_due),
+        n => format!("Missed {} reminders since {}.", n, first_due),
+    };
+
+    reminder.title = "Missed Reminder";
+    reminder.body = format!("{} {}", prefix, reminder.body);
+    reminder
+        .extra
+        .push(("missed_count", json!(notice.missed.len())));
+    reminder.extra.push((
+        "missed_reminder_ids",
+        json!(notice.missed.iter().map(|m| m.id).collect::<Vec<_>>()),
+    ));
+    reminder
+}
+
+/// "2025-03-03T09:00:00" → "Mon 3 Mar 09:00"
+fn format_local_time(local_time: &str) -> String {
+    chrono::NaiveDateTime::parse_from_str(local_time, "%Y-%m-%dT%H:%M:%S")
+        .map(|t| t.format("%a %-d %b %H:%M").to_string())
+        .unwrap_or_else(|_| local_time.to_string())
 }
 
 #[cfg(test)]
@@ -123,6 +626,7 @@ mod tests {
     use super::*;
     use crate::db::Database;
     use crate::features::scheduling::models::CreateScheduleRequest;
+    use serde_json::json;
     use tempfile::TempDir;
 
     fn setup_test_repo() -> (SchedulingRepository, TempDir) {
@@ -149,11 +653,17 @@ mod tests {
 
         // Create a schedule for tomorrow at this time
         let request = CreateScheduleRequest {
-            assessment_type_id: 1, // PHQ-9
+            assessment_type_id: Some(1), // PHQ-9
             frequency: super::super::models::ScheduleFrequency::Daily,
             time_of_day: "23:59".to_string(), // Late time that hasn't occurred yet today
             day_of_week: None,
             day_of_month: None,
+            timezone: None,
+            target: None,
+            rrule: None,
+            dtstart: None,
+            exdates: None,
+            missed_policy: None,
         };
 
         let _schedule = repo
@@ -165,4 +675,181 @@ mod tests {
             .expect("Failed to get due schedules");
         assert_eq!(due.len(), 0, "Future schedule should not be due");
     }
+
+    fn schedule_for(target: ScheduleTarget, target_name: &str) -> AssessmentSchedule {
+        let is_assessment = matches!(target, ScheduleTarget::Assessment { .. });
+        AssessmentSchedule {
+            id: 7,
+            target,
+            target_name: target_name.to_string(),
+            assessment_type_id: is_assessment.then_some(1),
+            assessment_type_code: is_assessment.then(|| "PHQ9".to_string()),
+            assessment_type_name: is_assessment.then(|| target_name.to_string()),
+            frequency: super::super::models::ScheduleFrequency::Daily,
+            time_of_day: "09:00".to_string(),
+            day_of_week: None,
+            day_of_month: None,
+            rrule: "FREQ=DAILY;BYHOUR=9;BYMINUTE=0".to_string(),
+            dtstart: "2025-01-01T00:00:00".to_string(),
+            exdates: Vec::new(),
+            timezone: "UTC".to_string(),
+            enabled: true,
+            missed_policy: super::super::models::MissedReminderPolicy::Coalesce,
+            last_triggered_at: None,
+            created_at: "2025-01-01 00:00:00".to_string(),
+            updated_at: "2025-01-01 00:00:00".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_reminder_notification_assessment_keeps_legacy_keys() {
+        let schedule = schedule_for(
+            ScheduleTarget::Assessment {
+                assessment_type_id: 1,
+            },
+            "PHQ-9",
+        );
+
+        let reminder = reminder_notification(&schedule, 42);
+
+        assert_eq!(reminder.title, "Assessment Reminder");
+        assert!(reminder.body.contains("Time to complete: PHQ-9"));
+        assert_eq!(reminder.extra("assessment_type_code"), Some(&json!("PHQ9")));
+        assert_eq!(reminder.extra("target_kind"), Some(&json!("assessment")));
+        assert_eq!(reminder.extra("route"), Some(&json!("/assessments/phq9")));
+        assert_eq!(reminder.extra("schedule_id"), Some(&json!(7)));
+        assert_eq!(reminder.extra("reminder_event_id"), Some(&json!(42)));
+    }
+
+    #[test]
+    fn test_reminder_notification_per_target_payload() {
+        let mood = reminder_notification(
+            &schedule_for(ScheduleTarget::MoodCheckin, "Mood check-in"),
+            1,
+        );
+        assert_eq!(mood.title, "Mood Check-in");
+        assert_eq!(mood.extra("route"), Some(&json!("/mood")));
+        assert_eq!(mood.extra("assessment_type_code"), None);
+
+        let walk = reminder_notification(
+            &schedule_for(ScheduleTarget::Activity { activity_id: 3 }, "Walk"),
+            1,
+        );
+        assert_eq!(walk.body, "Time to log: Walk.");
+        assert_eq!(walk.extra("activity_id"), Some(&json!(3)));
+        assert_eq!(
+            walk.extra("route"),
+            Some(&json!("/activity-groups?activity_id=3"))
+        );
+
+        let group = reminder_notification(
+            &schedule_for(
+                ScheduleTarget::ActivityGroup {
+                    activity_group_id: 5,
+                },
+                "Exercise",
+            ),
+            1,
+        );
+        assert_eq!(group.extra("activity_group_id"), Some(&json!(5)));
+        assert_eq!(group.extra("target_kind"), Some(&json!("activity_group")));
+
+        let habit = reminder_notification(
+            &schedule_for(
+                ScheduleTarget::Habit {
+                    text: "Stretch for 5 minutes".to_string(),
+                },
+                "Stretch for 5 minutes",
+            ),
+            1,
+        );
+        assert_eq!(habit.title, "Habit Reminder");
+        assert_eq!(habit.body, "Stretch for 5 minutes");
+        assert_eq!(
+            habit.extra("habit_text"),
+            Some(&json!("Stretch for 5 minutes"))
+        );
+    }
+
+    #[test]
+    fn test_missed_reminder_notification() {
+        use crate::features::scheduling::models::{
+            MissedReminder, MissedReminderPolicy, ReminderEvent, ReminderEventType,
+        };
+
+        let schedule = schedule_for(
+            ScheduleTarget::Assessment {
+                assessment_type_id: 1,
+            },
+            "PHQ-9",
+        );
+        let missed = |id: i32, local_time: &str| MissedReminder {
+            id,
+            schedule_id: schedule.id,
+            target: schedule.target.clone(),
+            target_name: schedule.target_name.clone(),
+            scheduled_for: local_time.replace('T', " "),
+            local_time: local_time.to_string(),
+            policy: MissedReminderPolicy::Coalesce,
+            reminder_event_id: Some(9),
+            detected_at: "2025-03-05 12:00:00".to_string(),
+            acknowledged_at: None,
+        };
+        let mut notice = MissedReminderNotice {
+            schedule: schedule.clone(),
+            event: ReminderEvent {
+                id: 9,
+                schedule_id: schedule.id,
+                event_type: ReminderEventType::Fired,
+                occurred_at: "2025-03-05 12:00:00".to_string(),
+                snooze_until: None,
+                related_event_id: None,
+                assessment_response_id: None,
+            },
+            missed: vec![missed(1, "2025-03-03T09:00:00")],
+        };
+
+        let single = missed_reminder_notification(&notice);
+        assert_eq!(single.title, "Missed Reminder");
+        assert!(single
+            .body
+            .starts_with("Missed reminder from Mon 3 Mar 09:00. Time to complete: PHQ-9"));
+        assert_eq!(single.extra("reminder_event_id"), Some(&json!(9)));
+        assert_eq!(single.extra("route"), Some(&json!("/assessments/phq9")));
+
+        notice.missed.push(missed(2, "2025-03-04T09:00:00"));
+        let coalesced = missed_reminder_notification(&notice);
+        assert!(coalesced
+            .body
+            .starts_with("Missed 2 reminders since Mon 3 Mar 09:00."));
+        assert_eq!(coalesced.extra("missed_count"), Some(&json!(2)));
+        assert_eq!(coalesced.extra("missed_reminder_ids"), Some(&json!([1, 2])));
+    }
+
+    #[test]
+    fn test_highest_milestone_per_streak() {
+        use crate::features::streaks::StreakKind;
+
+        let milestone = |id: i32, target_id: Option<i32>, value: i32| StreakMilestone {
+            id,
+            kind: StreakKind::Activity,
+            target_id,
+            target_name: "Running".to_string(),
+            milestone: value,
+            streak_start_date: "2025-01-01".to_string(),
+            achieved_at: "2025-01-07 10:00:00".to_string(),
+            notified_at: None,
+        };
+        let milestones = vec![
+            milestone(1, Some(1), 3),
+            milestone(2, Some(1), 7),
+            milestone(3, Some(2), 3),
+        ];
+
+        let highest = highest_milestone_per_streak(&milestones);
+
+        assert_eq!(highest.len(), 2);
+        assert_eq!(highest[0].id, 2);
+        assert_eq!(highest[1].id, 3);
+    }
 }
@@ -0,0 +1,10 @@
+// Streak feature module
+// Current/longest daily streaks for activities, activity groups, and mood check-ins
+
+pub mod models;
+pub mod queries;
+pub mod repository;
+
+// Re-export commonly used types
+pub use models::{Streak, StreakError, StreakKind, StreakMilestone, StreakRun};
+pub use repository::StreakRepository;
@@ -0,0 +1,439 @@
+// Streak feature models
+// Consecutive-day streaks for activities, activity groups, and mood check-ins
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::errors::{CommandError, ErrorType, ToCommandError};
+
+/// Maximum number of rest days allowed between two active days
+///
+/// Anything larger stops being a "streak" in a meaningful sense (a weekly habit
+/// would always be on a streak), so requests above this are rejected.
+pub const MAX_GRACE_DAYS: i32 = 6;
+
+/// Streak lengths (in active days) that are announced as milestones
+pub const STREAK_MILESTONES: [i32; 8] = [3, 7, 14, 30, 60, 100, 180, 365];
+
+/// Streak-specific errors
+#[derive(Error, Debug)]
+pub enum StreakError {
+    #[error("Activity not found: {0}")]
+    ActivityNotFound(i32),
+
+    #[error("Activity group not found: {0}")]
+    GroupNotFound(i32),
+
+    #[error("Grace days must be between 0 and {max}: {0}", max = MAX_GRACE_DAYS)]
+    InvalidGraceDays(i32),
+
+    #[error("Invalid date: {0}")]
+    InvalidDate(String),
+
+    #[error("Invalid timezone: {0}")]
+    InvalidTimezone(String),
+
+    #[error("Invalid streak kind: {0}")]
+    InvalidKind(String),
+
+    #[error("Database error: {0}")]
+    Database(#[from] rusqlite::Error),
+
+    #[error("Database lock issue. This request will be retried automatically.")]
+    LockPoisoned,
+}
+
+impl ToCommandError for StreakError {
+    fn to_command_error(&self) -> CommandError {
+        match self {
+            StreakError::ActivityNotFound(id) => {
+                CommandError::permanent(self.to_string(), ErrorType::NotFound)
+                    .with_details(serde_json::json!({ "activity_id": id }))
+            }
+            StreakError::GroupNotFound(id) => {
+                CommandError::permanent(self.to_string(), ErrorType::NotFound)
+                    .with_details(serde_json::json!({ "group_id": id }))
+            }
+            StreakError::InvalidGraceDays(value) => {
+                CommandError::permanent(self.to_string(), ErrorType::Validation).with_details(
+                    serde_json::json!({
+                        "field": "grace_days",
+                        "value": value,
+                        "max": MAX_GRACE_DAYS
+                    }),
+                )
+            }
+            StreakError::InvalidDate(value) => {
+                CommandError::permanent(self.to_string(), ErrorType::Validation)
+                    .with_details(serde_json::json!({ "value": value }))
+            }
+            StreakError::InvalidTimezone(name) => {
+                CommandError::permanent(self.to_string(), ErrorType::Validation).with_details(
+                    serde_json::json!({
+                        "field": "timezone",
+                        "value": name
+                    }),
+                )
+            }
+            StreakError::InvalidKind(_) => {
+                CommandError::permanent(self.to_string(), ErrorType::Internal)
+            }
+            StreakError::Database(e) => CommandError::from_rusqlite_error(e),
+            StreakError::LockPoisoned => CommandError::retryable(
+                "Database lock issue. This request will be retried automatically.".to_string(),
+                ErrorType::LockPoisoned,
+            ),
+        }
+    }
+}
+
+/// What a streak is measured over
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, specta::Type, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum StreakKind {
+    /// Days with at least one log of a single activity
+    Activity,
+    /// Days with at least one log of any (non-deleted) activity in a group
+    ActivityGroup,
+    /// Days with at least one mood check-in
+    MoodCheckin,
+}
+
+impl StreakKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StreakKind::Activity => "activity",
+            StreakKind::ActivityGroup => "activity_group",
+            StreakKind::MoodCheckin => "mood_checkin",
+        }
+    }
+}
+
+impl std::str::FromStr for StreakKind {
+    type Err = StreakError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "activity" => Ok(StreakKind::Activity),
+            "activity_group" => Ok(StreakKind::ActivityGroup),
+            "mood_checkin" => Ok(StreakKind::MoodCheckin),
+            _ => Err(StreakError::InvalidKind(s.to_string())),
+        }
+    }
+}
+
+/// A single run of active days
+///
+/// Grace days inside the run are not counted in `length` and do not appear
+/// in `dates`; they only keep the run from breaking.
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type, PartialEq)]
+pub struct StreakRun {
+    /// First active day of the run (YYYY-MM-DD)
+    pub start_date: String,
+    /// Last active day of the run (YYYY-MM-DD)
+    pub end_date: String,
+    /// Number of active days in the run
+    pub length: i32,
+    /// Every active day in the run, oldest first (YYYY-MM-DD)
+    pub dates: Vec<String>,
+}
+
+/// Streak report for an activity, activity group, or mood check-ins
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct Streak {
+    pub kind: StreakKind,
+    /// Activity or group ID (None for mood check-ins)
+    pub target_id: Option<i32>,
+    /// Rest days allowed between active days without breaking the streak
+    pub grace_days: i32,
+    /// Day the streak was evaluated at (YYYY-MM-DD)
+    pub as_of: String,
+    /// Run that is still alive as of `as_of` (None if the streak is broken)
+    pub current: Option<StreakRun>,
+    /// Longest run ever recorded (most recent wins on ties)
+    pub longest: Option<StreakRun>,
+    /// Most recent active day (YYYY-MM-DD)
+    pub last_active_date: Option<String>,
+}
+
+impl Streak {
+    /// Length of the current run (0 if broken)
+    pub fn current_length(&self) -> i32 {
+        self.current.as_ref().map(|r| r.length).unwrap_or(0)
+    }
+
+    /// Length of the longest run (0 if never active)
+    pub fn longest_length(&self) -> i32 {
+        self.longest.as_ref().map(|r| r.length).unwrap_or(0)
+    }
+}
+
+/// A milestone reached by a current streak
+#[derive(Debug, Clone, Serialize, Deserialize, specta::Type)]
+pub struct StreakMilestone {
+    pub id: i32,
+    pub kind: StreakKind,
+    pub target_id: Option<i32>,
+    /// Display name of the activity/group ("Mood check-in" for mood)
+    pub target_name: String,
+    /// Milestone length in active days (one of STREAK_MILESTONES)
+    pub milestone: i32,
+    /// Start date of the run that reached the milestone (YYYY-MM-DD)
+    pub streak_start_date: String,
+    pub achieved_at: String,
+    /// When a notification was sent (None if not yet notified)
+    pub notified_at: Option<String>,
+}
+
+/// Validate the grace days parameter
+pub fn validate_grace_days(grace_days: i32) -> Result<(), StreakError> {
+    if !(0..=MAX_GRACE_DAYS).contains(&grace_days) {
+        return Err(StreakError::InvalidGraceDays(grace_days));
+    }
+    Ok(())
+}
+
+/// Split active days into streak runs.
+///
+/// Two active days belong to the same run when the number of inactive days
+/// between them is at most `grace_days`. Input may be unsorted and contain
+/// duplicates.
+pub fn build_streak_runs(dates: &[NaiveDate], grace_days: i32) -> Vec<Vec<NaiveDate>> {
+    let mut sorted = dates.to_vec();
+    sorted.sort();
+    sorted.dedup();
+
+    let mut runs: Vec<Vec<NaiveDate>> = Vec::new();
+    for date in sorted {
+        match runs.last_mut() {
+            Some(run)
+                if gap_days(*run.last().expect("runs are never empty"), date) <= grace_days =>
+            {
+                run.push(date)
+            }
+            _ => runs.push(vec![date]),
+        }
+    }
+    runs
+}
+
+/// Calculate current and longest streaks as of `today`.
+///
+/// Active days after `today` are ignored. The last run is still "current" when
+/// the days missed since its end (not counting `today`, which may not be
+/// logged yet) fit within the grace allowance.
+pub fn calculate_streak(
+    kind: StreakKind,
+    target_id: Option<i32>,
+    dates: &[NaiveDate],
+    today: NaiveDate,
+    grace_days: i32,
+) -> Streak {
+    let past: Vec<NaiveDate> = dates.iter().copied().filter(|d| *d <= today).collect();
+    let runs = build_streak_runs(&past, grace_days);
+
+    let current = runs
+        .last()
+        .filter(|run| gap_days(*run.last().expect("runs are never empty"), today) <= grace_days)
+        .map(|run| to_streak_run(run));
+
+    // max_by_key returns the last maximum, so ties resolve to the most recent run
+    let longest = runs
+        .iter()
+        .max_by_key(|run| run.len())
+        .map(|run| to_streak_run(run));
+
+    Streak {
+        kind,
+        target_id,
+        grace_days,
+        as_of: today.format("%Y-%m-%d").to_string(),
+        current,
+        longest,
+        last_active_date: past.iter().max().map(|d| d.format("%Y-%m-%d").to_string()),
+    }
+}
+
+/// Milestones crossed by a run of the given length
+pub fn milestones_reached(length: i32) -> impl Iterator<Item = i32> {
+    STREAK_MILESTONES.into_iter().filter(move |m| *m <= length)
+}
+
+/// Number of inactive days strictly between two dates (negative if `to` precedes `from`)
+fn gap_days(from: NaiveDate, to: NaiveDate) -> i32 {
+    ((to - from).num_days() - 1) as i32
+}
+
+fn to_streak_run(run: &[NaiveDate]) -> StreakRun {
+    let dates: Vec<String> = run
+        .iter()
+        .map(|d| d.format("%Y-%m-%d").to_string())
+        .collect();
+    StreakRun {
+        start_date: dates.first().cloned().unwrap_or_default(),
+        end_date: dates.last().cloned().unwrap_or_default(),
+        length: dates.len() as i32,
+        dates,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn d(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").expect("valid date")
+    }
+
+    #[test]
+    fn test_no_activity_has_no_streak() {
+        let streak = calculate_streak(StreakKind::MoodCheckin, None, &[], d("2025-01-10"), 0);
+
+        assert!(streak.current.is_none());
+        assert!(streak.longest.is_none());
+        assert_eq!(streak.current_length(), 0);
+        assert_eq!(streak.last_active_date, None);
+    }
+
+    #[test]
+    fn test_consecutive_days_form_current_streak() {
+        let dates = [d("2025-01-08"), d("2025-01-09"), d("2025-01-10")];
+        let streak = calculate_streak(StreakKind::Activity, Some(1), &dates, d("2025-01-10"), 0);
+
+        assert_eq!(streak.current_length(), 3);
+        assert_eq!(streak.longest_length(), 3);
+        let current = streak.current.expect("current streak");
+        assert_eq!(current.start_date, "2025-01-08");
+        assert_eq!(current.end_date, "2025-01-10");
+        assert_eq!(
+            current.dates,
+            vec!["2025-01-08", "2025-01-09", "2025-01-10"]
+        );
+    }
+
+    #[test]
+    fn test_streak_still_current_when_today_not_logged_yet() {
+        let dates = [d("2025-01-08"), d("2025-01-09")];
+        let streak = calculate_streak(StreakKind::Activity, Some(1), &dates, d("2025-01-10"), 0);
+
+        assert_eq!(streak.current_length(), 2);
+    }
+
+    #[test]
+    fn test_missed_day_breaks_streak_without_grace() {
+        let dates = [
+            d("2025-01-01"),
+            d("2025-01-02"),
+            d("2025-01-03"),
+            d("2025-01-05"),
+        ];
+        let streak = calculate_streak(StreakKind::Activity, Some(1), &dates, d("2025-01-05"), 0);
+
+        assert_eq!(streak.current_length(), 1);
+        assert_eq!(streak.longest_length(), 3);
+        assert_eq!(streak.longest.expect("longest").start_date, "2025-01-01");
+    }
+
+    #[test]
+    fn test_grace_day_bridges_gap() {
+        let dates = [
+            d("2025-01-01"),
+            d("2025-01-02"),
+            d("2025-01-04"),
+            d("2025-01-05"),
+        ];
+        let streak = calculate_streak(StreakKind::Activity, Some(1), &dates, d("2025-01-05"), 1);
+
+        // Grace day (Jan 3) keeps the run alive but is not counted
+        let current = streak.current.expect("current streak");
+        assert_eq!(current.length, 4);
+        assert_eq!(current.start_date, "2025-01-01");
+        assert!(!current.dates.contains(&"2025-01-03".to_string()));
+    }
+
+    #[test]
+    fn test_grace_days_apply_to_current_streak_end() {
+        let dates = [d("2025-01-01"), d("2025-01-02")];
+
+        // Two missed days (Jan 3, 4) with grace 2 -> still current on Jan 5
+        let streak = calculate_streak(StreakKind::Activity, Some(1), &dates, d("2025-01-05"), 2);
+        assert_eq!(streak.current_length(), 2);
+
+        // Same gap with grace 1 -> broken
+        let streak = calculate_streak(StreakKind::Activity, Some(1), &dates, d("2025-01-05"), 1);
+        assert!(streak.current.is_none());
+        assert_eq!(streak.longest_length(), 2);
+    }
+
+    #[test]
+    fn test_future_dates_ignored() {
+        let dates = [d("2025-01-09"), d("2025-01-10"), d("2025-01-11")];
+        let streak = calculate_streak(StreakKind::Activity, Some(1), &dates, d("2025-01-10"), 0);
+
+        assert_eq!(streak.current_length(), 2);
+        assert_eq!(streak.last_active_date.as_deref(), Some("2025-01-10"));
+    }
+
+    #[test]
+    fn test_longest_tie_prefers_most_recent_run() {
+        let dates = [
+            d("2025-01-01"),
+            d("2025-01-02"),
+            d("2025-01-05"),
+            d("2025-01-06"),
+        ];
+        let streak = calculate_streak(StreakKind::Activity, Some(1), &dates, d("2025-01-20"), 0);
+
+        assert!(streak.current.is_none());
+        assert_eq!(streak.longest.expect("longest").start_date, "2025-01-05");
+    }
+
+    #[test]
+    fn test_build_streak_runs_dedups_and_sorts() {
+        let dates = [
+            d("2025-01-03"),
+            d("2025-01-01"),
+            d("2025-01-02"),
+            d("2025-01-02"),
+        ];
+        let runs = build_streak_runs(&dates, 0);
+
+        assert_eq!(runs.len(), 1);
+        assert_eq!(runs[0].len(), 3);
+    }
+
+    #[test]
+    fn test_validate_grace_days() {
+        assert!(validate_grace_days(0).is_ok());
+        assert!(validate_grace_days(MAX_GRACE_DAYS).is_ok());
+        assert!(matches!(
+            validate_grace_days(-1),
+            Err(StreakError::InvalidGraceDays(-1))
+        ));
+        assert!(matches!(
+            validate_grace_days(MAX_GRACE_DAYS + 1),
+            Err(StreakError::InvalidGraceDays(_))
+        ));
+    }
+
+    #[test]
+    fn test_milestones_reached() {
+        assert_eq!(milestones_reached(2).count(), 0);
+        assert_eq!(milestones_reached(7).collect::<Vec<_>>(), vec![3, 7]);
+        assert_eq!(milestones_reached(400).count(), STREAK_MILESTONES.len());
+    }
+
+    #[test]
+    fn test_streak_kind_round_trip() {
+        use std::str::FromStr;
+        for kind in [
+            StreakKind::Activity,
+            StreakKind::ActivityGroup,
+            StreakKind::MoodCheckin,
+        ] {
+            assert_eq!(StreakKind::from_str(kind.as_str()).unwrap(), kind);
+        }
+        assert!(StreakKind::from_str("unknown").is_err());
+    }
+}
@@ -0,0 +1,87 @@
+// Streak queries - Read operations (Tauri commands)
+
+use super::models::*;
+use super::repository::StreakRepository;
+use crate::{errors::ToCommandError, AppState, CommandError};
+use tauri::State;
+use tracing::error;
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_activity_streak(
+    activity_id: i32,
+    grace_days: i32,
+    current_time: String,
+    timezone: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Streak, CommandError> {
+    let repo = StreakRepository::new(state.db.clone());
+
+    repo.get_activity_streak(activity_id, grace_days, &current_time, timezone.as_deref())
+        .map_err(|e| {
+            error!(
+                "get_activity_streak error: {} (activity_id: {}, grace_days: {}, current_time: {})",
+                e, activity_id, grace_days, current_time
+            );
+            e.to_command_error()
+        })
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_group_streak(
+    group_id: i32,
+    grace_days: i32,
+    current_time: String,
+    timezone: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Streak, CommandError> {
+    let repo = StreakRepository::new(state.db.clone());
+
+    repo.get_group_streak(group_id, grace_days, &current_time, timezone.as_deref())
+        .map_err(|e| {
+            error!(
+                "get_group_streak error: {} (group_id: {}, grace_days: {}, current_time: {})",
+                e, group_id, grace_days, current_time
+            );
+            e.to_command_error()
+        })
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_mood_checkin_streak(
+    grace_days: i32,
+    current_time: String,
+    timezone: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Streak, CommandError> {
+    let repo = StreakRepository::new(state.db.clone());
+
+    repo.get_mood_checkin_streak(grace_days, &current_time, timezone.as_deref())
+        .map_err(|e| {
+            error!(
+                "get_mood_checkin_streak error: {} (grace_days: {}, current_time: {})",
+                e, grace_days, current_time
+            );
+            e.to_command_error()
+        })
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_streak_milestones(
+    kind: Option<StreakKind>,
+    target_id: Option<i32>,
+    state: State<'_, AppState>,
+) -> Result<Vec<StreakMilestone>, CommandError> {
+    let repo = StreakRepository::new(state.db.clone());
+
+    repo.get_streak_milestones(kind, target_id).map_err(|e| {
+        error!(
+            "get_streak_milestones error: {} (kind: {:?}, target_id: {:?})",
+            e, kind, target_id
+        );
+        e.to_command_error()
+    })
+}
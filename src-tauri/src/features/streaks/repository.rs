@@ -0,0 +1,751 @@
+// Streak repository - Loads active days and tracks announced milestones
+//
+// Streak computation itself is pure Rust (see models::calculate_streak); this
+// repository only collects the active days for each streak kind. Days are
+// local calendar days in the streak's timezone, the same days the heatmap uses.
+
+use std::sync::Arc;
+
+use chrono::NaiveDate;
+use chrono_tz::Tz;
+use rusqlite::OptionalExtension;
+use tracing::info;
+
+use super::models::*;
+use crate::db::Database;
+use crate::features::scheduling::models::{parse_timezone, system_timezone};
+use crate::features::visualization::heatmap::local_day;
+
+pub struct StreakRepository {
+    db: Arc<Database>,
+}
+
+impl StreakRepository {
+    pub fn new(db: Arc<Database>) -> Self {
+        Self { db }
+    }
+
+    /// Calculates the streak of a single activity.
+    ///
+    /// # Arguments
+    /// * `activity_id` - Activity ID to analyze
+    /// * `grace_days` - Rest days allowed between active days (0..=MAX_GRACE_DAYS)
+    /// * `current_time` - Time to evaluate the streak at (ISO 8601)
+    /// * `timezone` - IANA timezone days are counted in, defaults to the system timezone
+    ///
+    /// # Returns
+    /// * `Ok(Streak)` - Current and longest streaks with their dates
+    /// * `Err(StreakError::ActivityNotFound)` - If activity doesn't exist or is deleted
+    pub fn get_activity_streak(
+        &self,
+        activity_id: i32,
+        grace_days: i32,
+        current_time: &str,
+        timezone: Option<&str>,
+    ) -> Result<Streak, StreakError> {
+        validate_grace_days(grace_days)?;
+        let tz = streak_timezone(timezone)?;
+
+        let conn = self.db.get_connection();
+        let conn = conn.lock();
+
+        self.get_activity_streak_with_conn(&conn, activity_id, grace_days, current_time, tz)
+    }
+
+    /// Calculates the streak of an activity group.
+    ///
+    /// A day counts as active when any non-deleted activity in the group was logged.
+    pub fn get_group_streak(
+        &self,
+        group_id: i32,
+        grace_days: i32,
+        current_time: &str,
+        timezone: Option<&str>,
+    ) -> Result<Streak, StreakError> {
+        validate_grace_days(grace_days)?;
+        let tz = streak_timezone(timezone)?;
+
+        let conn = self.db.get_connection();
+        let conn = conn.lock();
+
+        self.get_group_streak_with_conn(&conn, group_id, grace_days, current_time, tz)
+    }
+
+    /// Calculates the mood check-in streak.
+    pub fn get_mood_checkin_streak(
+        &self,
+        grace_days: i32,
+        current_time: &str,
+        timezone: Option<&str>,
+    ) -> Result<Streak, StreakError> {
+        validate_grace_days(grace_days)?;
+        let tz = streak_timezone(timezone)?;
+
+        let conn = self.db.get_connection();
+        let conn = conn.lock();
+
+        self.get_mood_checkin_streak_with_conn(&conn, grace_days, current_time, tz)
+    }
+
+    /// Records milestones newly reached by any current streak.
+    ///
+    /// Evaluates every non-deleted activity, every non-deleted group, and mood
+    /// check-ins, with days in `tz`. Milestones already recorded for the same
+    /// run are skipped, so calling this repeatedly is safe.
+    ///
+    /// Only the highest milestone of a streak is left pending for announcement;
+    /// lower ones reached at the same time (e.g. the first evaluation of an
+    /// existing streak) are recorded as already notified.
+    ///
+    /// # Returns
+    /// * `Ok(Vec<StreakMilestone>)` - Milestones inserted by this call
+    pub fn record_new_milestones(
+        &self,
+        grace_days: i32,
+        current_time: &str,
+        tz: Tz,
+    ) -> Result<Vec<StreakMilestone>, StreakError> {
+        validate_grace_days(grace_days)?;
+
+        let conn = self.db.get_connection();
+        let mut conn = conn.lock();
+
+        // Collect current streaks for all targets
+        let mut streaks: Vec<(Streak, String)> = Vec::new();
+
+        let activities: Vec<(i32, String)> = {
+            let mut stmt =
+                conn.prepare("SELECT id, name FROM activities WHERE deleted_at IS NULL")?;
+            let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+            rows.collect::<Result<Vec<_>, _>>()?
+        };
+        for (id, name) in activities {
+            let streak =
+                self.get_activity_streak_with_conn(&conn, id, grace_days, current_time, tz)?;
+            streaks.push((streak, name));
+        }
+
+        let groups: Vec<(i32, String)> = {
+            let mut stmt =
+                conn.prepare("SELECT id, name FROM activity_groups WHERE deleted_at IS NULL")?;
+            let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+            rows.collect::<Result<Vec<_>, _>>()?
+        };
+        for (id, name) in groups {
+            let streak =
+                self.get_group_streak_with_conn(&conn, id, grace_days, current_time, tz)?;
+            streaks.push((streak, name));
+        }
+
+        let mood_streak =
+            self.get_mood_checkin_streak_with_conn(&conn, grace_days, current_time, tz)?;
+        streaks.push((mood_streak, "Mood check-in".to_string()));
+
+        // Insert reached milestones in a single transaction
+        let tx = conn.transaction_with_behavior(rusqlite::TransactionBehavior::Immediate)?;
+        let mut recorded = Vec::new();
+
+        for (streak, name) in streaks {
+            let Some(run) = &streak.current else {
+                continue;
+            };
+
+            let highest = milestones_reached(run.length).max();
+            for milestone in milestones_reached(run.length) {
+                let inserted = tx
+                    .query_row(
+                        "INSERT OR IGNORE INTO streak_milestones
+                             (streak_kind, target_id, milestone, streak_start_date, notified_at)
+                         VALUES (?1, ?2, ?3, ?4, CASE WHEN ?5 THEN datetime('now') END)
+                         RETURNING id, CAST(achieved_at AS VARCHAR), CAST(notified_at AS VARCHAR)",
+                        rusqlite::params![
+                            streak.kind.as_str(),
+                            streak.target_id,
+                            milestone,
+                            &run.start_date,
+                            Some(milestone) != highest
+                        ],
+                        |row| {
+                            Ok((
+                                row.get::<_, i32>(0)?,
+                                row.get::<_, String>(1)?,
+                                row.get::<_, Option<String>>(2)?,
+                            ))
+                        },
+                    )
+                    .optional()?;
+
+                if let Some((id, achieved_at, notified_at)) = inserted {
+                    recorded.push(StreakMilestone {
+                        id,
+                        kind: streak.kind,
+                        target_id: streak.target_id,
+                        target_name: name.clone(),
+                        milestone,
+                        streak_start_date: run.start_date.clone(),
+                        achieved_at,
+                        notified_at,
+                    });
+                }
+            }
+        }
+
+        tx.commit()?;
+
+        if !recorded.is_empty() {
+            info!(count = recorded.len(), "Recorded new streak milestones");
+        }
+
+        Ok(recorded)
+    }
+
+    /// IDs of the newest activity log and mood check-in (0 when there are none).
+    ///
+    /// Lets callers skip `record_new_milestones` when nothing was logged since
+    /// their last call.
+    pub fn latest_log_ids(&self) -> Result<(i64, i64), StreakError> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock();
+
+        let ids = conn.query_row(
+            "SELECT (SELECT COALESCE(MAX(id), 0) FROM activity_logs),
+                    (SELECT COALESCE(MAX(id), 0) FROM mood_checkins)",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )?;
+
+        Ok(ids)
+    }
+
+    /// Marks milestones as notified
+    pub fn mark_milestones_notified(&self, milestone_ids: &[i32]) -> Result<(), StreakError> {
+        if milestone_ids.is_empty() {
+            return Ok(());
+        }
+
+        let conn = self.db.get_connection();
+        let conn = conn.lock();
+
+        let query = format!(
+            "UPDATE streak_milestones SET notified_at = datetime('now') WHERE id IN {}",
+            crate::db::query_builder::generate_in_clause(milestone_ids.len())
+        );
+        let params: Vec<&dyn rusqlite::ToSql> = milestone_ids
+            .iter()
+            .map(|id| id as &dyn rusqlite::ToSql)
+            .collect();
+        conn.execute(&query, params.as_slice())?;
+
+        Ok(())
+    }
+
+    /// Lists recorded milestones, most recent first.
+    ///
+    /// # Arguments
+    /// * `kind` - Optional streak kind filter
+    /// * `target_id` - Optional activity/group ID filter
+    pub fn get_streak_milestones(
+        &self,
+        kind: Option<StreakKind>,
+        target_id: Option<i32>,
+    ) -> Result<Vec<StreakMilestone>, StreakError> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock();
+
+        load_milestones_with_conn(
+            &conn,
+            "WHERE (?1 IS NULL OR m.streak_kind = ?1)
+               AND (?2 IS NULL OR m.target_id = ?2)
+             ORDER BY m.achieved_at DESC, m.milestone DESC",
+            rusqlite::params![kind.map(|k| k.as_str()), target_id],
+        )
+    }
+
+    /// Lists milestones not yet announced, oldest first. A milestone stays
+    /// pending until it is marked notified, so failed notifications are retried.
+    pub fn get_pending_milestones(&self) -> Result<Vec<StreakMilestone>, StreakError> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock();
+
+        load_milestones_with_conn(
+            &conn,
+            "WHERE m.notified_at IS NULL
+             ORDER BY m.achieved_at, m.milestone",
+            [],
+        )
+    }
+
+    // ========================================
+    // Helpers
+    // ========================================
+
+    fn get_activity_streak_with_conn(
+        &self,
+        conn: &rusqlite::Connection,
+        activity_id: i32,
+        grace_days: i32,
+        current_time: &str,
+        tz: Tz,
+    ) -> Result<Streak, StreakError> {
+        let exists: bool = conn.query_row(
+            "SELECT EXISTS(SELECT 1 FROM activities WHERE id = ? AND deleted_at IS NULL)",
+            [activity_id],
+            |row| row.get(0),
+        )?;
+        if !exists {
+            return Err(StreakError::ActivityNotFound(activity_id));
+        }
+
+        let today = resolve_local_date(current_time, tz)?;
+        let dates = load_local_dates_with_conn(
+            conn,
+            "SELECT logged_at
+             FROM activity_logs
+             WHERE activity_id = ?1
+               AND deleted_at IS NULL",
+            [activity_id],
+            tz,
+        )?;
+
+        Ok(calculate_streak(
+            StreakKind::Activity,
+            Some(activity_id),
+            &dates,
+            today,
+            grace_days,
+        ))
+    }
+
+    fn get_group_streak_with_conn(
+        &self,
+        conn: &rusqlite::Connection,
+        group_id: i32,
+        grace_days: i32,
+        current_time: &str,
+        tz: Tz,
+    ) -> Result<Streak, StreakError> {
+        let exists: bool = conn.query_row(
+            "SELECT EXISTS(SELECT 1 FROM activity_groups WHERE id = ? AND deleted_at IS NULL)",
+            [group_id],
+            |row| row.get(0),
+        )?;
+        if !exists {
+            return Err(StreakError::GroupNotFound(group_id));
+        }
+
+        let today = resolve_local_date(current_time, tz)?;
+        let dates = load_local_dates_with_conn(
+            conn,
+            "SELECT l.logged_at
+             FROM activity_logs l
+             JOIN activities a ON a.id = l.activity_id
+             WHERE a.group_id = ?1
+               AND a.deleted_at IS NULL
+               AND l.deleted_at IS NULL",
+            [group_id],
+            tz,
+        )?;
+
+        Ok(calculate_streak(
+            StreakKind::ActivityGroup,
+            Some(group_id),
+            &dates,
+            today,
+            grace_days,
+        ))
+    }
+
+    fn get_mood_checkin_streak_with_conn(
+        &self,
+        conn: &rusqlite::Connection,
+        grace_days: i32,
+        current_time: &str,
+        tz: Tz,
+    ) -> Result<Streak, StreakError> {
+        let today = resolve_local_date(current_time, tz)?;
+        let dates =
+            load_local_dates_with_conn(conn, "SELECT created_at FROM mood_checkins", [], tz)?;
+
+        Ok(calculate_streak(
+            StreakKind::MoodCheckin,
+            None,
+            &dates,
+            today,
+            grace_days,
+        ))
+    }
+}
+
+/// Loads milestones with their target names; `filter` is the WHERE/ORDER BY tail
+fn load_milestones_with_conn(
+    conn: &rusqlite::Connection,
+    filter: &str,
+    params: impl rusqlite::Params,
+) -> Result<Vec<StreakMilestone>, StreakError> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT m.id, m.streak_kind, m.target_id,
+                COALESCE(a.name, g.name, 'Mood check-in'),
+                m.milestone, m.streak_start_date,
+                CAST(m.achieved_at AS VARCHAR), CAST(m.notified_at AS VARCHAR)
+         FROM streak_milestones m
+         LEFT JOIN activities a ON m.streak_kind = 'activity' AND a.id = m.target_id
+         LEFT JOIN activity_groups g ON m.streak_kind = 'activity_group' AND g.id = m.target_id
+         {}",
+        filter
+    ))?;
+
+    let rows = stmt.query_map(params, |row| {
+        Ok((
+            row.get::<_, i32>(0)?,
+            row.get::<_, String>(1)?,
+            row.get::<_, Option<i32>>(2)?,
+            row.get::<_, String>(3)?,
+            row.get::<_, i32>(4)?,
+            row.get::<_, String>(5)?,
+            row.get::<_, String>(6)?,
+            row.get::<_, Option<String>>(7)?,
+        ))
+    })?;
+
+    let mut milestones = Vec::new();
+    for row in rows {
+        let (id, kind, target_id, target_name, milestone, start, achieved_at, notified_at) = row?;
+        milestones.push(StreakMilestone {
+            id,
+            kind: kind.parse()?,
+            target_id,
+            target_name,
+            milestone,
+            streak_start_date: start,
+            achieved_at,
+            notified_at,
+        });
+    }
+
+    Ok(milestones)
+}
+
+/// Resolves an optional IANA timezone name, defaulting to the system timezone
+fn streak_timezone(timezone: Option<&str>) -> Result<Tz, StreakError> {
+    let timezone = timezone.map(str::to_string).unwrap_or_else(system_timezone);
+    parse_timezone(&timezone).map_err(|_| StreakError::InvalidTimezone(timezone))
+}
+
+/// Local calendar day in `tz` of an ISO 8601 timestamp
+fn resolve_local_date(current_time: &str, tz: Tz) -> Result<NaiveDate, StreakError> {
+    local_day(current_time, tz).ok_or_else(|| StreakError::InvalidDate(current_time.to_string()))
+}
+
+/// Local days in `tz` of the timestamps returned by `query`, sorted and deduplicated
+fn load_local_dates_with_conn(
+    conn: &rusqlite::Connection,
+    query: &str,
+    params: impl rusqlite::Params,
+    tz: Tz,
+) -> Result<Vec<NaiveDate>, StreakError> {
+    let mut stmt = conn.prepare(query)?;
+    let rows = stmt.query_map(params, |row| row.get::<_, Option<String>>(0))?;
+
+    let mut dates = Vec::new();
+    for row in rows {
+        // Skip unparseable timestamps rather than fail
+        if let Some(date) = row?.and_then(|ts| local_day(&ts, tz)) {
+            dates.push(date);
+        }
+    }
+    dates.sort();
+    dates.dedup();
+
+    Ok(dates)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::activities::repository::ActivityRepository;
+    use tempfile::TempDir;
+
+    fn setup_test_repo() -> (StreakRepository, ActivityRepository, Arc<Database>, TempDir) {
+        let temp_dir = TempDir::new().expect("Failed to create temp directory");
+        let db = Arc::new(
+            Database::new(temp_dir.path().to_path_buf()).expect("Failed to create database"),
+        );
+        (
+            StreakRepository::new(db.clone()),
+            ActivityRepository::new(db.clone()),
+            db,
+            temp_dir,
+        )
+    }
+
+    fn insert_checkin(db: &Database, created_at: &str) {
+        let conn = db.get_connection();
+        let conn = conn.lock();
+        conn.execute(
+            "INSERT INTO mood_checkins (mood_rating, created_at) VALUES (5, ?)",
+            [created_at],
+        )
+        .expect("Failed to insert check-in");
+    }
+
+    #[test]
+    fn test_get_activity_streak() {
+        let (repo, activities, _db, _temp_dir) = setup_test_repo();
+
+        let group = activities
+            .create_activity_group("Exercise", None)
+            .expect("Failed to create group");
+        let activity = activities
+            .create_activity(group.id, "Running", None, None)
+            .expect("Failed to create activity");
+
+        for ts in [
+            "2025-01-01T10:00:00Z",
+            "2025-01-02T10:00:00Z",
+            "2025-01-02T18:00:00Z", // Same day - counts once
+            "2025-01-03T10:00:00Z",
+            "2025-01-06T10:00:00Z",
+            "2025-01-07T10:00:00Z",
+        ] {
+            activities
+                .log_activity(activity.id, ts, None)
+                .expect("Failed to log");
+        }
+
+        let streak = repo
+            .get_activity_streak(activity.id, 0, "2025-01-07T20:00:00Z", Some("UTC"))
+            .expect("Failed to get streak");
+
+        assert_eq!(streak.kind, StreakKind::Activity);
+        assert_eq!(streak.target_id, Some(activity.id));
+        assert_eq!(streak.current_length(), 2);
+        assert_eq!(streak.longest_length(), 3);
+        assert_eq!(
+            streak.longest.expect("longest").dates,
+            vec!["2025-01-01", "2025-01-02", "2025-01-03"]
+        );
+    }
+
+    #[test]
+    fn test_get_activity_streak_with_grace_days() {
+        let (repo, activities, _db, _temp_dir) = setup_test_repo();
+
+        let group = activities
+            .create_activity_group("Exercise", None)
+            .expect("Failed to create group");
+        let activity = activities
+            .create_activity(group.id, "Running", None, None)
+            .expect("Failed to create activity");
+
+        for ts in [
+            "2025-01-01T10:00:00Z",
+            "2025-01-03T10:00:00Z",
+            "2025-01-05T10:00:00Z",
+        ] {
+            activities
+                .log_activity(activity.id, ts, None)
+                .expect("Failed to log");
+        }
+
+        let streak = repo
+            .get_activity_streak(activity.id, 1, "2025-01-05T20:00:00Z", Some("UTC"))
+            .expect("Failed to get streak");
+
+        assert_eq!(streak.current_length(), 3);
+        assert_eq!(streak.current.expect("current").start_date, "2025-01-01");
+    }
+
+    #[test]
+    fn test_get_activity_streak_not_found() {
+        let (repo, _activities, _db, _temp_dir) = setup_test_repo();
+
+        let result = repo.get_activity_streak(999, 0, "2025-01-01T00:00:00Z", Some("UTC"));
+        assert!(matches!(result, Err(StreakError::ActivityNotFound(999))));
+    }
+
+    #[test]
+    fn test_get_activity_streak_invalid_grace_days() {
+        let (repo, _activities, _db, _temp_dir) = setup_test_repo();
+
+        let result =
+            repo.get_activity_streak(1, MAX_GRACE_DAYS + 1, "2025-01-01T00:00:00Z", Some("UTC"));
+        assert!(matches!(result, Err(StreakError::InvalidGraceDays(_))));
+    }
+
+    #[test]
+    fn test_get_activity_streak_invalid_date() {
+        let (repo, activities, _db, _temp_dir) = setup_test_repo();
+
+        let group = activities
+            .create_activity_group("Exercise", None)
+            .expect("Failed to create group");
+        let activity = activities
+            .create_activity(group.id, "Running", None, None)
+            .expect("Failed to create activity");
+
+        let result = repo.get_activity_streak(activity.id, 0, "not a date", Some("UTC"));
+        assert!(matches!(result, Err(StreakError::InvalidDate(_))));
+    }
+
+    #[test]
+    fn test_get_group_streak_unions_activities() {
+        let (repo, activities, _db, _temp_dir) = setup_test_repo();
+
+        let group = activities
+            .create_activity_group("Exercise", None)
+            .expect("Failed to create group");
+        let running = activities
+            .create_activity(group.id, "Running", None, None)
+            .expect("Failed to create activity");
+        let swimming = activities
+            .create_activity(group.id, "Swimming", None, None)
+            .expect("Failed to create activity");
+
+        activities
+            .log_activity(running.id, "2025-01-01T10:00:00Z", None)
+            .expect("Failed to log");
+        activities
+            .log_activity(swimming.id, "2025-01-02T10:00:00Z", None)
+            .expect("Failed to log");
+        activities
+            .log_activity(running.id, "2025-01-03T10:00:00Z", None)
+            .expect("Failed to log");
+
+        let streak = repo
+            .get_group_streak(group.id, 0, "2025-01-03T20:00:00Z", Some("UTC"))
+            .expect("Failed to get streak");
+
+        assert_eq!(streak.kind, StreakKind::ActivityGroup);
+        assert_eq!(streak.current_length(), 3);
+    }
+
+    #[test]
+    fn test_get_mood_checkin_streak() {
+        let (repo, _activities, db, _temp_dir) = setup_test_repo();
+
+        insert_checkin(&db, "2025-01-01 09:00:00");
+        insert_checkin(&db, "2025-01-02 09:00:00");
+        insert_checkin(&db, "2025-01-02 21:00:00");
+
+        let streak = repo
+            .get_mood_checkin_streak(0, "2025-01-03T08:00:00Z", Some("UTC"))
+            .expect("Failed to get streak");
+
+        assert_eq!(streak.kind, StreakKind::MoodCheckin);
+        assert_eq!(streak.target_id, None);
+        assert_eq!(streak.current_length(), 2);
+        assert_eq!(streak.last_active_date.as_deref(), Some("2025-01-02"));
+    }
+
+    #[test]
+    fn test_record_new_milestones_only_once_per_run() {
+        let (repo, _activities, db, _temp_dir) = setup_test_repo();
+
+        for day in 1..=3 {
+            insert_checkin(&db, &format!("2025-01-0{} 09:00:00", day));
+        }
+
+        let first = repo
+            .record_new_milestones(0, "2025-01-03T20:00:00Z", chrono_tz::UTC)
+            .expect("Failed to record milestones");
+        assert_eq!(first.len(), 1);
+        assert_eq!(first[0].kind, StreakKind::MoodCheckin);
+        assert_eq!(first[0].milestone, 3);
+        assert_eq!(first[0].streak_start_date, "2025-01-01");
+
+        // Re-evaluating the same run must not produce duplicates
+        let second = repo
+            .record_new_milestones(0, "2025-01-03T21:00:00Z", chrono_tz::UTC)
+            .expect("Failed to record milestones");
+        assert!(second.is_empty());
+
+        repo.mark_milestones_notified(&[first[0].id])
+            .expect("Failed to mark notified");
+        let stored = repo
+            .get_streak_milestones(Some(StreakKind::MoodCheckin), None)
+            .expect("Failed to get milestones");
+        assert_eq!(stored.len(), 1);
+        assert!(stored[0].notified_at.is_some());
+    }
+
+    #[test]
+    fn test_record_new_milestones_leaves_only_highest_pending() {
+        let (repo, _activities, db, _temp_dir) = setup_test_repo();
+
+        for day in 1..=7 {
+            insert_checkin(&db, &format!("2025-01-0{} 09:00:00", day));
+        }
+
+        // First evaluation of an existing 7-day streak reaches 3 and 7 at once
+        let recorded = repo
+            .record_new_milestones(0, "2025-01-07T20:00:00Z", chrono_tz::UTC)
+            .expect("Failed to record milestones");
+        assert_eq!(recorded.len(), 2);
+        assert!(recorded
+            .iter()
+            .any(|m| m.milestone == 3 && m.notified_at.is_some()));
+
+        let pending = repo
+            .get_pending_milestones()
+            .expect("Failed to get pending milestones");
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].milestone, 7);
+    }
+
+    #[test]
+    fn test_record_new_milestones_uses_local_days() {
+        let (repo, _activities, db, _temp_dir) = setup_test_repo();
+
+        // Evenings in New York are the next UTC day
+        insert_checkin(&db, "2025-01-02 03:00:00"); // Jan 1, 22:00 in New York
+        insert_checkin(&db, "2025-01-02 20:00:00"); // Jan 2, 15:00
+        insert_checkin(&db, "2025-01-04 02:00:00"); // Jan 3, 21:00
+
+        let utc = repo
+            .get_mood_checkin_streak(0, "2025-01-04T03:00:00Z", Some("UTC"))
+            .expect("Failed to get streak");
+        assert_eq!(utc.current_length(), 1);
+
+        let new_york: Tz = "America/New_York".parse().unwrap();
+        let recorded = repo
+            .record_new_milestones(0, "2025-01-04T03:00:00Z", new_york)
+            .expect("Failed to record milestones");
+        assert_eq!(recorded.len(), 1);
+        assert_eq!(recorded[0].milestone, 3);
+        assert_eq!(recorded[0].streak_start_date, "2025-01-01");
+    }
+
+    #[test]
+    fn test_get_streak_invalid_timezone() {
+        let (repo, _activities, _db, _temp_dir) = setup_test_repo();
+
+        let result = repo.get_mood_checkin_streak(0, "2025-01-01T00:00:00Z", Some("Mars/Base"));
+        assert!(matches!(result, Err(StreakError::InvalidTimezone(_))));
+    }
+
+    #[test]
+    fn test_pending_milestones_until_notified() {
+        let (repo, _activities, db, _temp_dir) = setup_test_repo();
+
+        for day in 1..=3 {
+            insert_checkin(&db, &format!("2025-01-0{} 09:00:00", day));
+        }
+        let recorded = repo
+            .record_new_milestones(0, "2025-01-03T20:00:00Z", chrono_tz::UTC)
+            .expect("Failed to record milestones");
+
+        let pending = repo
+            .get_pending_milestones()
+            .expect("Failed to get pending milestones");
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].id, recorded[0].id);
+        assert_eq!(pending[0].target_name, "Mood check-in");
+
+        repo.mark_milestones_notified(&[recorded[0].id])
+            .expect("Failed to mark notified");
+        assert!(repo
+            .get_pending_milestones()
+            .expect("Failed to get pending milestones")
+            .is_empty());
+    }
+}
@@ -0,0 +1,73 @@
+// Visualization commands - chart export (Tauri commands)
+
+use super::models::*;
+use super::queries::resolve_time_range;
+use super::render::ChartImageFormat;
+use super::repository::VisualizationRepository;
+use crate::{errors::ToCommandError, AppState, CommandError};
+use tauri::{AppHandle, State};
+use tauri_plugin_dialog::DialogExt;
+use tracing::error;
+
+/// Render the assessment chart for `assessment_type_code`, or the mood chart
+/// if None, and save it where the user picks in a save dialog.
+/// Returns the saved path, or None if the dialog was cancelled.
+#[tauri::command]
+#[specta::specta]
+pub async fn save_chart_image(
+    app: AppHandle,
+    assessment_type_code: Option<String>,
+    time_range: TimeRange,
+    from_date: Option<String>,
+    to_date: Option<String>,
+    format: ChartImageFormat,
+    state: State<'_, AppState>,
+) -> Result<Option<String>, CommandError> {
+    let (from, to) = resolve_time_range(time_range, from_date, to_date)?;
+
+    // Render first so a failure is reported before the user picks a file
+    let repo = VisualizationRepository::new(state.db.clone());
+    let image = repo
+        .render_chart(
+            assessment_type_code.as_deref(),
+            from.as_deref(),
+            to.as_deref(),
+            format,
+        )
+        .map_err(|e| {
+            error!(
+                "save_chart_image error: {} (assessment_type_code: {:?})",
+                e, assessment_type_code
+            );
+            e.to_command_error()
+        })?;
+
+    let extension = format.extension();
+    let file_name = format!(
+        "{}-chart.{}",
+        assessment_type_code
+            .as_deref()
+            .unwrap_or("mood")
+            .to_lowercase(),
+        extension
+    );
+    let Some(file_path) = app
+        .dialog()
+        .file()
+        .add_filter(extension.to_uppercase(), &[extension])
+        .set_file_name(file_name)
+        .blocking_save_file()
+    else {
+        return Ok(None);
+    };
+
+    let path = file_path.into_path().map_err(|e| {
+        VisualizationError::Io(std::io::Error::other(e.to_string())).to_command_error()
+    })?;
+    std::fs::write(&path, image).map_err(|e| {
+        error!("save_chart_image error: {} (path: {})", e, path.display());
+        VisualizationError::Io(e).to_command_error()
+    })?;
+
+    Ok(Some(path.display().to_string()))
+}
@@ -0,0 +1,74 @@
+// Period-over-period comparison
+//
+// Statistics for a period next to those of its previous equivalent period
+// (see TimeRange::comparison_periods_at), for mood and each assessment type.
+
+use serde::{Deserialize, Serialize};
+
+use super::models::{ChartStatistics, DateBounds, MoodStatistics};
+
+/// Mood and assessment statistics for two periods side by side
+#[derive(Serialize, Deserialize, specta::Type, Clone, Debug)]
+pub struct PeriodComparison {
+    pub current_period: DateBounds,
+    pub previous_period: DateBounds,
+    pub mood: MoodPeriodComparison,
+    pub assessments: Vec<AssessmentPeriodComparison>, // Types with data in either period
+}
+
+/// Mood statistics per period; None for a period without check-ins
+#[derive(Serialize, Deserialize, specta::Type, Clone, Debug)]
+pub struct MoodPeriodComparison {
+    pub current: Option<MoodStatistics>,
+    pub previous: Option<MoodStatistics>,
+    pub average_change: Option<f64>, // Current minus previous; positive is better
+}
+
+/// Assessment statistics per period; None for a period without assessments
+#[derive(Serialize, Deserialize, specta::Type, Clone, Debug)]
+pub struct AssessmentPeriodComparison {
+    pub assessment_type_code: String,
+    pub assessment_type_name: String,
+    pub current: Option<ChartStatistics>,
+    pub previous: Option<ChartStatistics>,
+    pub average_change: Option<f64>, // Current minus previous; negative is better
+}
+
+impl MoodPeriodComparison {
+    pub fn new(current: Option<MoodStatistics>, previous: Option<MoodStatistics>) -> Self {
+        let average_change = average_change(
+            current.as_ref().map(|s| s.average),
+            previous.as_ref().map(|s| s.average),
+        );
+        MoodPeriodComparison {
+            current,
+            previous,
+            average_change,
+        }
+    }
+}
+
+impl AssessmentPeriodComparison {
+    pub fn new(
+        assessment_type_code: String,
+        assessment_type_name: String,
+        current: Option<ChartStatistics>,
+        previous: Option<ChartStatistics>,
+    ) -> Self {
+        let average_change = average_change(
+            current.as_ref().map(|s| s.average),
+            previous.as_ref().map(|s| s.average),
+        );
+        AssessmentPeriodComparison {
+            assessment_type_code,
+            assessment_type_name,
+            current,
+            previous,
+            average_change,
+        }
+    }
+}
+
+fn average_change(current: Option<f64>, previous: Option<f64>) -> Option<f64> {
+    Some(current? - previous?)
+}
@@ -0,0 +1,323 @@
+// Score distribution and time spent per severity level
+//
+// Each result's severity is carried forward until the next administration,
+// so the share of days per level answers "how much of the range was spent
+// in moderate-or-worse?" even when assessments are irregular. The latest
+// result before the range is carried into it; time before the first known
+// result is untracked and left out of the shares. Episodes are runs of
+// carried-forward severity at or above a chosen level.
+
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+use crate::features::assessments::models::AssessmentType;
+use crate::types::assessment::SeverityLevel;
+
+/// Most bins in a score histogram; wider scales get wider bins
+pub const MAX_HISTOGRAM_BINS: i32 = 30;
+
+const TIMESTAMP_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+const SECONDS_PER_DAY: f64 = 86_400.0;
+
+/// Severity time shares, score histogram and episodes for one assessment type
+#[derive(Serialize, Deserialize, specta::Type, Clone, Debug)]
+pub struct SeverityDistribution {
+    pub assessment_type: AssessmentType,
+    pub from_date: String,                   // Start of the covered period
+    pub to_date: String,                     // End of the covered period, at most now
+    pub tracked_days: f64,                   // From the first known result to the end
+    pub untracked_days: f64,                 // Before the first known result
+    pub severity_shares: Vec<SeverityShare>, // Least to most severe
+    pub score_histogram: Vec<ScoreBin>,      // Results completed within the range
+    pub episode_severity: SeverityLevel,
+    pub episodes: Vec<SeverityEpisode>, // Oldest first
+    pub longest_episode_days: Option<f64>,
+    pub average_episode_days: Option<f64>,
+}
+
+/// Time spent at one severity level
+#[derive(Serialize, Deserialize, specta::Type, Clone, Debug, PartialEq)]
+pub struct SeverityShare {
+    pub severity: SeverityLevel,
+    pub days: f64,
+    pub share: f64, // Of tracked days, 0.0 to 1.0
+}
+
+/// Number of results with a score in [min_score, max_score]
+#[derive(Serialize, Deserialize, specta::Type, Clone, Debug, PartialEq)]
+pub struct ScoreBin {
+    pub min_score: i32,
+    pub max_score: i32,
+    pub count: i32,
+}
+
+/// A run of results at or above the episode severity
+#[derive(Serialize, Deserialize, specta::Type, Clone, Debug, PartialEq)]
+pub struct SeverityEpisode {
+    pub start: String, // First result at or above, or the range start if carried in
+    pub end: String,   // First result below, or the range end if ongoing
+    pub days: f64,
+    pub ongoing: bool, // Still at or above at the end of the range
+    pub peak_severity: SeverityLevel,
+    pub peak_score: i32,
+    pub result_count: i32, // Results in the episode, including one carried in
+}
+
+/// A completed result
+#[derive(Clone, Debug, PartialEq)]
+pub struct SeverityResult {
+    pub completed_at: NaiveDateTime,
+    pub score: i32,
+    pub severity: SeverityLevel,
+}
+
+/// A result's severity held from `start` until `end`
+#[derive(Clone, Debug, PartialEq)]
+pub struct SeveritySegment {
+    pub start: NaiveDateTime,
+    pub end: NaiveDateTime,
+    pub result: SeverityResult,
+}
+
+/// Carry each result forward until the next one, within [start, end].
+/// `carried_in` is the latest result before `start`; `results` are the
+/// results within the range, oldest first.
+pub fn carry_forward(
+    carried_in: Option<&SeverityResult>,
+    results: &[SeverityResult],
+    start: NaiveDateTime,
+    end: NaiveDateTime,
+) -> Vec<SeveritySegment> {
+    let mut segments: Vec<SeveritySegment> = Vec::new();
+    let held = carried_in.map(|result| (start, result));
+    let starts = results.iter().map(|result| (result.completed_at, result));
+    for (from, result) in held.into_iter().chain(starts) {
+        let from = from.clamp(start, end);
+        if let Some(last) = segments.last_mut() {
+            last.end = from;
+        }
+        segments.push(SeveritySegment {
+            start: from,
+            end,
+            result: result.clone(),
+        });
+    }
+    // A result replaced at the same instant, or completed at the very end
+    segments.retain(|s| s.end > s.start);
+    segments
+}
+
+/// Days at each of `levels`, plus Unknown if any time was spent there
+pub fn severity_shares(
+    segments: &[SeveritySegment],
+    levels: &[SeverityLevel],
+) -> Vec<SeverityShare> {
+    let tracked = tracked_days(segments);
+    let days_at = |level: SeverityLevel| -> f64 {
+        segments
+            .iter()
+            .filter(|s| s.result.severity == level)
+            .map(|s| fractional_days(s.start, s.end))
+            .sum()
+    };
+
+    let mut levels = levels.to_vec();
+    // A result outside the type's thresholds still takes up time
+    for segment in segments {
+        if !levels.contains(&segment.result.severity) {
+            levels.push(segment.result.severity);
+        }
+    }
+    levels.sort_by_key(|level| severity_rank(*level));
+
+    levels
+        .into_iter()
+        .map(|severity| {
+            let days = days_at(severity);
+            SeverityShare {
+                severity,
+                days,
+                share: if tracked > 0.0 { days / tracked } else { 0.0 },
+            }
+        })
+        .collect()
+}
+
+/// Counts of `scores` in equal bins from `min_score` to `max_score`, at most
+/// MAX_HISTOGRAM_BINS of them. Scores outside the scale are clamped into it.
+pub fn score_histogram(scores: &[i32], min_score: i32, max_score: i32) -> Vec<ScoreBin> {
+    let max_score = max_score.max(min_score);
+    let range = max_score - min_score + 1;
+    let width = (range + MAX_HISTOGRAM_BINS - 1) / MAX_HISTOGRAM_BINS;
+
+    let mut bins: Vec<ScoreBin> = (min_score..=max_score)
+        .step_by(width as usize)
+        .map(|low| ScoreBin {
+            min_score: low,
+            max_score: (low + width - 1).min(max_score),
+            count: 0,
+        })
+        .collect();
+    for score in scores {
+        let index = (score.clamp(&min_score, &max_score) - min_score) / width;
+        bins[index as usize].count += 1;
+    }
+    bins
+}
+
+/// Runs of segments at or above `severity`. Unknown levels end an episode.
+pub fn severity_episodes(
+    segments: &[SeveritySegment],
+    severity: SeverityLevel,
+    range_end: NaiveDateTime,
+) -> Vec<SeverityEpisode> {
+    let at_or_above =
+        |level: SeverityLevel| level.compare(&severity).is_some_and(|order| order.is_ge());
+
+    let mut episodes = Vec::new();
+    for run in
+        segments.chunk_by(|a, b| at_or_above(a.result.severity) == at_or_above(b.result.severity))
+    {
+        let (Some(first), Some(last)) = (run.first(), run.last()) else {
+            continue;
+        };
+        if !at_or_above(first.result.severity) {
+            continue;
+        }
+        let peak = run
+            .iter()
+            .map(|s| &s.result)
+            .max_by_key(|r| (severity_rank(r.severity), r.score))
+            .unwrap_or(&first.result);
+        episodes.push(SeverityEpisode {
+            start: first.start.format(TIMESTAMP_FORMAT).to_string(),
+            end: last.end.format(TIMESTAMP_FORMAT).to_string(),
+            days: fractional_days(first.start, last.end),
+            ongoing: last.end >= range_end,
+            peak_severity: peak.severity,
+            peak_score: peak.score,
+            result_count: run.len() as i32,
+        });
+    }
+    episodes
+}
+
+/// Days covered by `segments`
+pub fn tracked_days(segments: &[SeveritySegment]) -> f64 {
+    segments
+        .iter()
+        .map(|s| fractional_days(s.start, s.end))
+        .sum()
+}
+
+/// Fractional days from `start` to `end`
+pub fn fractional_days(start: NaiveDateTime, end: NaiveDateTime) -> f64 {
+    (end - start).num_seconds() as f64 / SECONDS_PER_DAY
+}
+
+/// Position in severity order; Unknown sorts last
+fn severity_rank(level: SeverityLevel) -> usize {
+    SeverityLevel::severity_order()
+        .iter()
+        .position(|l| *l == level)
+        .unwrap_or(usize::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(timestamp: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(timestamp, TIMESTAMP_FORMAT).unwrap()
+    }
+
+    fn result(timestamp: &str, score: i32, severity: SeverityLevel) -> SeverityResult {
+        SeverityResult {
+            completed_at: at(timestamp),
+            score,
+            severity,
+        }
+    }
+
+    #[test]
+    fn test_shares_and_episodes_carry_forward() {
+        let carried_in = result("2024-12-20 09:00:00", 12, SeverityLevel::Moderate);
+        let results = [
+            result("2025-01-05 00:00:00", 17, SeverityLevel::ModeratelySevere),
+            result("2025-01-11 00:00:00", 7, SeverityLevel::Mild),
+            result("2025-01-21 00:00:00", 11, SeverityLevel::Moderate),
+        ];
+        let (start, end) = (at("2025-01-01 00:00:00"), at("2025-01-31 00:00:00"));
+
+        let segments = carry_forward(Some(&carried_in), &results, start, end);
+        assert_eq!(segments.len(), 4);
+        assert_eq!(tracked_days(&segments), 30.0);
+
+        let shares = severity_shares(&segments, SeverityLevel::severity_order());
+        let days: Vec<(SeverityLevel, f64)> = shares.iter().map(|s| (s.severity, s.days)).collect();
+        assert_eq!(
+            days,
+            [
+                (SeverityLevel::Minimal, 0.0),
+                (SeverityLevel::Mild, 10.0),
+                (SeverityLevel::Moderate, 14.0),
+                (SeverityLevel::ModeratelySevere, 6.0),
+                (SeverityLevel::Severe, 0.0),
+            ]
+        );
+        assert_eq!(shares[2].share, 14.0 / 30.0);
+
+        let episodes = severity_episodes(&segments, SeverityLevel::Moderate, end);
+        assert_eq!(episodes.len(), 2);
+        assert_eq!(episodes[0].start, "2025-01-01 00:00:00");
+        assert_eq!(episodes[0].end, "2025-01-11 00:00:00");
+        assert_eq!(episodes[0].days, 10.0);
+        assert_eq!(episodes[0].peak_severity, SeverityLevel::ModeratelySevere);
+        assert_eq!(episodes[0].result_count, 2);
+        assert!(!episodes[0].ongoing);
+        assert_eq!(episodes[1].days, 10.0);
+        assert!(episodes[1].ongoing);
+    }
+
+    #[test]
+    fn test_unknown_levels_are_shared_but_end_episodes() {
+        let results = [
+            result("2025-01-01 00:00:00", 20, SeverityLevel::Severe),
+            result("2025-01-02 00:00:00", 99, SeverityLevel::Unknown),
+            result("2025-01-03 00:00:00", 21, SeverityLevel::Severe),
+        ];
+        let end = at("2025-01-04 00:00:00");
+        let segments = carry_forward(None, &results, results[0].completed_at, end);
+
+        let shares = severity_shares(&segments, &[SeverityLevel::Severe]);
+        assert_eq!(shares.len(), 2);
+        assert_eq!(shares[1].severity, SeverityLevel::Unknown);
+        assert_eq!(shares[1].days, 1.0);
+
+        let episodes = severity_episodes(&segments, SeverityLevel::Severe, end);
+        assert_eq!(episodes.len(), 2);
+    }
+
+    #[test]
+    fn test_score_histogram_bins() {
+        // PHQ-9: one bin per score
+        let bins = score_histogram(&[0, 5, 5, 27], 0, 27);
+        assert_eq!(bins.len(), 28);
+        assert_eq!(bins[5].count, 2);
+        assert_eq!(bins[27].count, 1);
+
+        // CES-D: 61 scores in bins of three, the last one narrower
+        let bins = score_histogram(&[0, 2, 3, 60, 75], 0, 60);
+        assert_eq!(bins.len(), 21);
+        assert_eq!(bins[0].count, 2);
+        assert_eq!(bins[1].count, 1);
+        assert_eq!(
+            bins[20],
+            ScoreBin {
+                min_score: 60,
+                max_score: 60,
+                count: 2
+            }
+        );
+    }
+}
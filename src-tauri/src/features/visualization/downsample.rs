@@ -0,0 +1,145 @@
+// Chart downsampling
+//
+// Largest-Triangle-Three-Buckets (Steinarsson, 2013): keeps the first and
+// last points and, from each bucket in between, the point forming the largest
+// triangle with the previously kept point and the next bucket's average. The
+// result is a subset of real points, so peaks, dips and severity labels
+// survive, unlike averaging into buckets.
+
+use super::models::ChartDataPoint;
+use super::trend::parse_chart_timestamp;
+
+/// Smallest max_points a chart query accepts (first, last and one in between)
+pub const MIN_CHART_POINTS: usize = 3;
+
+/// Reduce `points` (in time order) to at most `max_points`, or return them
+/// unchanged if they already fit
+pub fn downsample_lttb(points: &[ChartDataPoint], max_points: usize) -> Vec<ChartDataPoint> {
+    let n = points.len();
+    if max_points >= n || max_points < MIN_CHART_POINTS {
+        return points.to_vec();
+    }
+
+    let xs = time_axis(points);
+    let y = |i: usize| points[i].value;
+
+    let mut sampled = Vec::with_capacity(max_points);
+    sampled.push(points[0].clone());
+
+    // Everything but the first and last point is split into equal buckets
+    let bucket_size = (n - 2) as f64 / (max_points - 2) as f64;
+    let mut previous = 0;
+    for bucket in 0..max_points - 2 {
+        let start = (bucket as f64 * bucket_size) as usize + 1;
+        let end = ((bucket + 1) as f64 * bucket_size) as usize + 1;
+
+        // Average of the next bucket (just the last point for the final bucket)
+        let next_start = end;
+        let next_end = (((bucket + 2) as f64 * bucket_size) as usize + 1).min(n);
+        let (next_x, next_y) = if next_start < next_end {
+            let count = (next_end - next_start) as f64;
+            let (sx, sy) =
+                (next_start..next_end).fold((0.0, 0.0), |(sx, sy), i| (sx + xs[i], sy + y(i)));
+            (sx / count, sy / count)
+        } else {
+            (xs[n - 1], y(n - 1))
+        };
+
+        let (prev_x, prev_y) = (xs[previous], y(previous));
+        let mut chosen = start;
+        let mut largest_area = -1.0;
+        for (i, x) in xs.iter().enumerate().take(end.min(n - 1)).skip(start) {
+            let area =
+                ((prev_x - next_x) * (y(i) - prev_y) - (prev_x - x) * (next_y - prev_y)).abs();
+            if area > largest_area {
+                largest_area = area;
+                chosen = i;
+            }
+        }
+
+        sampled.push(points[chosen].clone());
+        previous = chosen;
+    }
+
+    sampled.push(points[n - 1].clone());
+    sampled
+}
+
+/// Seconds since the first point; an unparseable timestamp reuses the previous
+/// position so the axis stays in order
+fn time_axis(points: &[ChartDataPoint]) -> Vec<f64> {
+    let mut last = 0.0;
+    let origin = points
+        .iter()
+        .find_map(|p| parse_chart_timestamp(&p.timestamp))
+        .map(|at| at.and_utc().timestamp())
+        .unwrap_or(0);
+    points
+        .iter()
+        .map(|p| {
+            if let Some(at) = parse_chart_timestamp(&p.timestamp) {
+                last = (at.and_utc().timestamp() - origin) as f64;
+            }
+            last
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hourly(values: &[f64]) -> Vec<ChartDataPoint> {
+        let start =
+            chrono::NaiveDateTime::parse_from_str("2025-01-01 00:00:00", "%Y-%m-%d %H:%M:%S")
+                .unwrap();
+        values
+            .iter()
+            .enumerate()
+            .map(|(i, value)| ChartDataPoint {
+                timestamp: (start + chrono::Duration::hours(i as i64))
+                    .format("%Y-%m-%d %H:%M:%S")
+                    .to_string(),
+                value: *value,
+                label: Some(format!("p{i}")),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_short_series_unchanged() {
+        let points = hourly(&[1.0, 2.0, 3.0]);
+        assert_eq!(downsample_lttb(&points, 10).len(), 3);
+        assert_eq!(downsample_lttb(&points, 3).len(), 3);
+    }
+
+    #[test]
+    fn test_keeps_ends_and_limit() {
+        let values: Vec<f64> = (0..10_000).map(|i| ((i as f64) / 50.0).sin()).collect();
+        let points = hourly(&values);
+        let sampled = downsample_lttb(&points, 500);
+
+        assert_eq!(sampled.len(), 500);
+        assert_eq!(sampled[0].label.as_deref(), Some("p0"));
+        assert_eq!(sampled[499].label.as_deref(), Some("p9999"));
+        // Still in time order, and only real points
+        assert!(sampled.windows(2).all(|w| w[0].timestamp < w[1].timestamp));
+        assert!(sampled.iter().all(|p| points
+            .iter()
+            .any(|q| q.timestamp == p.timestamp && q.value == p.value)));
+    }
+
+    #[test]
+    fn test_preserves_spikes() {
+        // A flat series with one high and one low outlier
+        let mut values = vec![4.0; 1000];
+        values[300] = 7.0;
+        values[700] = 1.0;
+        let sampled = downsample_lttb(&hourly(&values), 20);
+
+        let max = sampled.iter().map(|p| p.value).fold(f64::MIN, f64::max);
+        let min = sampled.iter().map(|p| p.value).fold(f64::MAX, f64::min);
+        assert_eq!(max, 7.0);
+        assert_eq!(min, 1.0);
+    }
+}
@@ -0,0 +1,275 @@
+// Activity-group mood breakdown
+//
+// Mood per activity group, with the group's activities for drill-down. Each
+// group and activity is compared with the check-ins that did not include it
+// (the baseline), and the days it was logged with the days it was not, using
+// the mean of all check-ins on a day. Days are calendar days of the stored
+// timestamps, as in the combined timeline.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use super::timeline::timeline_day;
+use crate::features::mood::models::Activity;
+
+/// Fewest check-ins a group or activity needs to be included
+pub const MIN_BREAKDOWN_CHECKINS: usize = 2;
+
+/// Mood for one activity group, with its activities for drill-down
+#[derive(Serialize, Deserialize, specta::Type, Clone, Debug)]
+pub struct ActivityGroupMoodData {
+    pub group_id: i32,
+    pub group_name: String,
+    pub comparison: MoodComparison, // Check-ins with any activity of the group
+    pub activities: Vec<ActivityMoodComparison>, // Best average mood first
+}
+
+/// Mood for one activity within its group
+#[derive(Serialize, Deserialize, specta::Type, Clone, Debug)]
+pub struct ActivityMoodComparison {
+    pub activity: Activity,
+    pub comparison: MoodComparison,
+}
+
+/// Mood with a group or activity against mood without it
+#[derive(Serialize, Deserialize, specta::Type, Clone, Debug, PartialEq)]
+pub struct MoodComparison {
+    pub checkin_count: i32,
+    pub average_mood: f64,
+    pub baseline_mood: Option<f64>, // Check-ins without it; None if there are none
+    pub difference: Option<f64>,    // Average minus baseline; positive is better
+    pub days_with: i32,
+    pub days_without: i32,                 // Days with check-ins but without it
+    pub days_with_average: Option<f64>,    // Mean of those days' daily means
+    pub days_without_average: Option<f64>, // Mean of those days' daily means
+    pub days_difference: Option<f64>,      // Days with minus days without
+}
+
+struct Checkin {
+    day: NaiveDate,
+    rating: i32,
+}
+
+/// Accumulates check-ins and their activities into per-group comparisons
+#[derive(Default)]
+pub struct GroupBreakdownBuilder {
+    checkins: BTreeMap<i64, Checkin>,
+    groups: BTreeMap<i32, String>,
+    activities: BTreeMap<i32, Activity>,
+    tags: BTreeMap<i32, BTreeSet<i64>>, // Activity id -> check-in ids
+}
+
+impl GroupBreakdownBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Every check-in of the range, with or without activities
+    pub fn add_checkin(&mut self, id: i64, created_at: &str, rating: i32) {
+        if let Some(day) = timeline_day(created_at) {
+            self.checkins.insert(id, Checkin { day, rating });
+        }
+    }
+
+    /// An activity of check-in `checkin_id`; callers leave out deleted
+    /// activities and activities of deleted groups
+    pub fn add_activity(&mut self, checkin_id: i64, activity: Activity, group_name: &str) {
+        self.groups
+            .entry(activity.group_id)
+            .or_insert_with(|| group_name.to_string());
+        self.tags.entry(activity.id).or_default().insert(checkin_id);
+        self.activities.entry(activity.id).or_insert(activity);
+    }
+
+    /// Groups with at least MIN_BREAKDOWN_CHECKINS check-ins, best average
+    /// mood first; activities below the minimum are left out of drill-down
+    pub fn build(self) -> Vec<ActivityGroupMoodData> {
+        let daily_means = self.daily_means();
+
+        let mut breakdown: Vec<ActivityGroupMoodData> = self
+            .groups
+            .iter()
+            .filter_map(|(group_id, group_name)| {
+                let members: Vec<&Activity> = self
+                    .activities
+                    .values()
+                    .filter(|a| a.group_id == *group_id)
+                    .collect();
+                let with: BTreeSet<i64> = members
+                    .iter()
+                    .filter_map(|a| self.tags.get(&a.id))
+                    .flatten()
+                    .copied()
+                    .collect();
+                let comparison = self.compare(&with, &daily_means)?;
+
+                let mut activities: Vec<ActivityMoodComparison> = members
+                    .into_iter()
+                    .filter_map(|activity| {
+                        let comparison =
+                            self.compare(self.tags.get(&activity.id)?, &daily_means)?;
+                        Some(ActivityMoodComparison {
+                            activity: activity.clone(),
+                            comparison,
+                        })
+                    })
+                    .collect();
+                activities.sort_by(|a, b| {
+                    b.comparison
+                        .average_mood
+                        .total_cmp(&a.comparison.average_mood)
+                });
+
+                Some(ActivityGroupMoodData {
+                    group_id: *group_id,
+                    group_name: group_name.clone(),
+                    comparison,
+                    activities,
+                })
+            })
+            .collect();
+
+        breakdown.sort_by(|a, b| {
+            b.comparison
+                .average_mood
+                .total_cmp(&a.comparison.average_mood)
+        });
+        breakdown
+    }
+
+    fn daily_means(&self) -> BTreeMap<NaiveDate, f64> {
+        let mut days: BTreeMap<NaiveDate, (i64, i64)> = BTreeMap::new();
+        for checkin in self.checkins.values() {
+            let entry = days.entry(checkin.day).or_insert((0, 0));
+            entry.0 += checkin.rating as i64;
+            entry.1 += 1;
+        }
+        days.into_iter()
+            .map(|(day, (sum, count))| (day, sum as f64 / count as f64))
+            .collect()
+    }
+
+    /// Compare the check-ins in `with` against all others
+    fn compare(
+        &self,
+        with: &BTreeSet<i64>,
+        daily_means: &BTreeMap<NaiveDate, f64>,
+    ) -> Option<MoodComparison> {
+        let (with_ratings, without_ratings): (Vec<_>, Vec<_>) =
+            self.checkins.iter().partition(|(id, _)| with.contains(*id));
+        if with_ratings.len() < MIN_BREAKDOWN_CHECKINS {
+            return None;
+        }
+
+        let days: BTreeSet<NaiveDate> = with_ratings.iter().map(|(_, c)| c.day).collect();
+        let (days_with, days_without): (Vec<f64>, Vec<f64>) = {
+            let (with, without): (Vec<_>, Vec<_>) =
+                daily_means.iter().partition(|(day, _)| days.contains(*day));
+            (
+                with.into_iter().map(|(_, mean)| *mean).collect(),
+                without.into_iter().map(|(_, mean)| *mean).collect(),
+            )
+        };
+
+        let average_mood = mean(with_ratings.iter().map(|(_, c)| c.rating as f64))?;
+        let baseline_mood = mean(without_ratings.iter().map(|(_, c)| c.rating as f64));
+        let days_with_average = mean(days_with.iter().copied());
+        let days_without_average = mean(days_without.iter().copied());
+
+        Some(MoodComparison {
+            checkin_count: with_ratings.len() as i32,
+            average_mood,
+            baseline_mood,
+            difference: baseline_mood.map(|baseline| average_mood - baseline),
+            days_with: days_with.len() as i32,
+            days_without: days_without.len() as i32,
+            days_with_average,
+            days_without_average,
+            days_difference: days_with_average
+                .zip(days_without_average)
+                .map(|(with, without)| with - without),
+        })
+    }
+}
+
+fn mean(values: impl Iterator<Item = f64>) -> Option<f64> {
+    let (sum, count) = values.fold((0.0, 0), |(sum, count), v| (sum + v, count + 1));
+    (count > 0).then(|| sum / count as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn activity(id: i32, group_id: i32) -> Activity {
+        Activity {
+            id,
+            group_id,
+            name: format!("Activity {id}"),
+            color: None,
+            icon: None,
+            created_at: String::new(),
+            deleted_at: None,
+        }
+    }
+
+    #[test]
+    fn test_group_against_baseline() {
+        let mut builder = GroupBreakdownBuilder::new();
+        // Day 1: exercise twice (6, 7), nothing once (3)
+        builder.add_checkin(1, "2025-01-01 08:00:00", 6);
+        builder.add_checkin(2, "2025-01-01 12:00:00", 7);
+        builder.add_checkin(3, "2025-01-01 20:00:00", 3);
+        // Day 2: work only (2, 4)
+        builder.add_checkin(4, "2025-01-02 08:00:00", 2);
+        builder.add_checkin(5, "2025-01-02 20:00:00", 4);
+        builder.add_activity(1, activity(10, 1), "Exercise");
+        builder.add_activity(2, activity(11, 1), "Exercise");
+        builder.add_activity(2, activity(10, 1), "Exercise");
+        builder.add_activity(4, activity(20, 2), "Work");
+        builder.add_activity(5, activity(20, 2), "Work");
+
+        let breakdown = builder.build();
+        assert_eq!(breakdown.len(), 2);
+
+        let exercise = &breakdown[0];
+        assert_eq!(exercise.group_name, "Exercise");
+        assert_eq!(
+            exercise.comparison,
+            MoodComparison {
+                checkin_count: 2,
+                average_mood: 6.5,
+                baseline_mood: Some(3.0),
+                difference: Some(3.5),
+                days_with: 1,
+                days_without: 1,
+                days_with_average: Some(16.0 / 3.0),
+                days_without_average: Some(3.0),
+                days_difference: Some(16.0 / 3.0 - 3.0),
+            }
+        );
+        // Activity 11 has a single check-in, below the minimum
+        assert_eq!(exercise.activities.len(), 1);
+        assert_eq!(exercise.activities[0].activity.id, 10);
+
+        assert_eq!(breakdown[1].group_name, "Work");
+        assert_eq!(breakdown[1].comparison.baseline_mood, Some(16.0 / 3.0));
+    }
+
+    #[test]
+    fn test_no_baseline_when_always_present() {
+        let mut builder = GroupBreakdownBuilder::new();
+        builder.add_checkin(1, "2025-01-01 08:00:00", 4);
+        builder.add_checkin(2, "2025-01-02 08:00:00", 5);
+        builder.add_activity(1, activity(10, 1), "Sleep");
+        builder.add_activity(2, activity(10, 1), "Sleep");
+
+        let comparison = &builder.build()[0].comparison;
+        assert_eq!(comparison.baseline_mood, None);
+        assert_eq!(comparison.difference, None);
+        assert_eq!(comparison.days_without, 0);
+        assert_eq!(comparison.days_difference, None);
+    }
+}
@@ -0,0 +1,187 @@
+// Calendar heatmap
+//
+// One record per local calendar day, for year-in-pixels style views. Stored
+// timestamps are UTC, so the day a check-in belongs to depends on the
+// timezone (and its DST rules on that date); rows are fetched for a window a
+// day wider than the range on each side and bucketed here.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use chrono::NaiveDate;
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+
+use super::trend::parse_chart_timestamp;
+
+/// Longest range one heatmap query may cover (about ten years)
+pub const MAX_HEATMAP_DAYS: i64 = 3660;
+
+/// Per-day aggregates for a range of local calendar days
+#[derive(Serialize, Deserialize, specta::Type, Clone, Debug)]
+pub struct CalendarHeatmap {
+    pub timezone: String, // IANA name the days were computed in
+    pub days: Vec<HeatmapDay>,
+    pub activity_groups: Vec<HeatmapActivityGroup>, // Groups logged anywhere in the range
+}
+
+/// Aggregates for one local day; days without data have no mood and zero counts
+#[derive(Serialize, Deserialize, specta::Type, Clone, Debug, PartialEq)]
+pub struct HeatmapDay {
+    pub date: String, // YYYY-MM-DD
+    pub average_mood: Option<f64>,
+    pub checkin_count: i32,
+    pub activity_group_ids: Vec<i32>, // Groups with at least one log that day
+    pub assessment_completed: bool,
+}
+
+/// Activity group referenced by heatmap days
+#[derive(Serialize, Deserialize, specta::Type, Clone, Debug, PartialEq)]
+pub struct HeatmapActivityGroup {
+    pub id: i32,
+    pub name: String,
+}
+
+/// Accumulates stored UTC rows into local days
+pub struct HeatmapBuilder {
+    tz: Tz,
+    first: NaiveDate,
+    last: NaiveDate,
+    mood: BTreeMap<NaiveDate, (i64, i32)>,
+    groups: BTreeMap<NaiveDate, BTreeSet<i32>>,
+    assessments: BTreeSet<NaiveDate>,
+}
+
+impl HeatmapBuilder {
+    pub fn new(tz: Tz, first: NaiveDate, last: NaiveDate) -> Self {
+        HeatmapBuilder {
+            tz,
+            first,
+            last,
+            mood: BTreeMap::new(),
+            groups: BTreeMap::new(),
+            assessments: BTreeSet::new(),
+        }
+    }
+
+    /// Local day of a stored UTC timestamp, if it falls in the range
+    fn local_day(&self, timestamp: &str) -> Option<NaiveDate> {
+        let day = local_day(timestamp, self.tz)?;
+        (self.first..=self.last).contains(&day).then_some(day)
+    }
+
+    pub fn add_checkin(&mut self, created_at: &str, rating: i32) {
+        if let Some(day) = self.local_day(created_at) {
+            let entry = self.mood.entry(day).or_insert((0, 0));
+            entry.0 += rating as i64;
+            entry.1 += 1;
+        }
+    }
+
+    pub fn add_activity_log(&mut self, logged_at: &str, group_id: i32) {
+        if let Some(day) = self.local_day(logged_at) {
+            self.groups.entry(day).or_default().insert(group_id);
+        }
+    }
+
+    pub fn add_assessment(&mut self, completed_at: &str) {
+        if let Some(day) = self.local_day(completed_at) {
+            self.assessments.insert(day);
+        }
+    }
+
+    /// Ids of every group logged on some day of the range
+    pub fn logged_group_ids(&self) -> BTreeSet<i32> {
+        self.groups.values().flatten().copied().collect()
+    }
+
+    /// One record for every day of the range, in order
+    pub fn build(self) -> Vec<HeatmapDay> {
+        self.first
+            .iter_days()
+            .take_while(|day| *day <= self.last)
+            .map(|day| {
+                let (sum, count) = self.mood.get(&day).copied().unwrap_or((0, 0));
+                HeatmapDay {
+                    date: day.format("%Y-%m-%d").to_string(),
+                    average_mood: (count > 0).then(|| sum as f64 / count as f64),
+                    checkin_count: count,
+                    activity_group_ids: self
+                        .groups
+                        .get(&day)
+                        .map(|ids| ids.iter().copied().collect())
+                        .unwrap_or_default(),
+                    assessment_completed: self.assessments.contains(&day),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Local calendar day in `tz` of a stored UTC timestamp
+pub fn local_day(timestamp: &str, tz: Tz) -> Option<NaiveDate> {
+    Some(
+        parse_chart_timestamp(timestamp)?
+            .and_utc()
+            .with_timezone(&tz)
+            .date_naive(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_every_day_is_present() {
+        let mut builder = HeatmapBuilder::new(chrono_tz::UTC, day("2024-02-27"), day("2024-03-01"));
+        builder.add_checkin("2024-02-28 09:00:00", 4);
+        builder.add_checkin("2024-02-28 21:00:00", 7);
+        builder.add_assessment("2024-03-01 12:00:00");
+        builder.add_activity_log("2024-02-28T10:00:00Z", 3);
+        builder.add_activity_log("2024-02-28T11:00:00Z", 1);
+        builder.add_activity_log("2024-02-28T12:00:00Z", 3);
+
+        let days = builder.build();
+        let dates: Vec<&str> = days.iter().map(|d| d.date.as_str()).collect();
+        // Leap day included
+        assert_eq!(
+            dates,
+            vec!["2024-02-27", "2024-02-28", "2024-02-29", "2024-03-01"]
+        );
+        assert_eq!(
+            days[0],
+            HeatmapDay {
+                date: "2024-02-27".to_string(),
+                average_mood: None,
+                checkin_count: 0,
+                activity_group_ids: vec![],
+                assessment_completed: false,
+            }
+        );
+        assert_eq!(days[1].average_mood, Some(5.5));
+        assert_eq!(days[1].checkin_count, 2);
+        assert_eq!(days[1].activity_group_ids, vec![1, 3]);
+        assert!(days[3].assessment_completed);
+    }
+
+    #[test]
+    fn test_days_are_local() {
+        let tz: Tz = "America/New_York".parse().unwrap();
+        let mut builder = HeatmapBuilder::new(tz, day("2025-03-08"), day("2025-03-09"));
+        // 03:30 UTC is still the previous evening in New York (EST, UTC-5)
+        builder.add_checkin("2025-03-09 03:30:00", 2);
+        // After the switch to EDT (UTC-4) on the 9th, 03:30 UTC is 23:30 on the 9th
+        builder.add_checkin("2025-03-10 03:30:00", 6);
+        // Outside the range once converted
+        builder.add_checkin("2025-03-08 04:30:00", 1);
+
+        let days = builder.build();
+        assert_eq!(days[0].average_mood, Some(2.0));
+        assert_eq!(days[1].average_mood, Some(6.0));
+        assert_eq!(days.iter().map(|d| d.checkin_count).sum::<i32>(), 2);
+    }
+}
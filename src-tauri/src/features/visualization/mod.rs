@@ -1,11 +1,30 @@
 // Visualization feature module
 // User Story 4 & 5: Assessment and Mood chart visualization
 
+pub mod commands;
+pub mod comparison;
+pub mod distribution;
+pub mod downsample;
+pub mod group_breakdown;
+pub mod heatmap;
 pub mod models;
 pub mod queries;
+pub mod render;
 pub mod repository;
+pub mod series;
+pub mod timeline;
+pub mod trend;
 
 // Re-export commonly used types
+pub use comparison::*;
+pub use distribution::*;
+pub use downsample::*;
+pub use group_breakdown::*;
+pub use heatmap::*;
 pub use models::*;
 pub use queries::*;
+pub use render::*;
 pub use repository::*;
+pub use series::*;
+pub use timeline::*;
+pub use trend::*;
@@ -1,12 +1,19 @@
 // Visualization feature models (User Story 4 & 5)
 // T121-T123, T139-T141: Data types for chart visualization
 
+use chrono::{DateTime, Datelike, Duration, Months, NaiveDateTime, NaiveTime, Utc};
+use chrono_tz::Tz;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 use crate::errors::{CommandError, ErrorType, ToCommandError};
 use crate::features::assessments::models::AssessmentType;
 use crate::features::mood::models::Activity;
+use crate::features::scheduling::models::{parse_timezone, resolve_local_time, system_timezone};
+
+use super::group_breakdown::ActivityGroupMoodData;
+use super::series::{DerivedSeries, DerivedSeriesKind};
+use super::trend::{parse_chart_timestamp, TrendAnalysis};
 
 /// Visualization-specific errors
 #[derive(Error, Debug)]
@@ -17,6 +24,18 @@ pub enum VisualizationError {
     #[error("Invalid assessment type: {0}")]
     InvalidAssessmentType(String),
 
+    #[error("Invalid date range: {0}")]
+    InvalidDateRange(String),
+
+    #[error("Invalid timezone: {0}")]
+    InvalidTimezone(String),
+
+    #[error("max_points must be at least {min}, got {value}")]
+    InvalidMaxPoints { value: usize, min: usize },
+
+    #[error("Episode severity must be a known severity level")]
+    InvalidEpisodeSeverity,
+
     #[error("Failed to calculate statistics: {0}")]
     StatisticsError(String),
 
@@ -28,6 +47,12 @@ pub enum VisualizationError {
 
     #[error("JSON serialization error: {0}")]
     JsonError(#[from] serde_json::Error),
+
+    #[error("Failed to render chart: {0}")]
+    Render(String),
+
+    #[error("Failed to save chart: {0}")]
+    Io(#[from] std::io::Error),
 }
 
 impl ToCommandError for VisualizationError {
@@ -42,6 +67,37 @@ impl ToCommandError for VisualizationError {
                     }),
                 )
             }
+            VisualizationError::InvalidDateRange(_) => {
+                CommandError::permanent(self.to_string(), ErrorType::Validation).with_details(
+                    serde_json::json!({
+                        "field": "date_range"
+                    }),
+                )
+            }
+            VisualizationError::InvalidTimezone(name) => {
+                CommandError::permanent(self.to_string(), ErrorType::Validation).with_details(
+                    serde_json::json!({
+                        "field": "timezone",
+                        "value": name
+                    }),
+                )
+            }
+            VisualizationError::InvalidMaxPoints { value, min } => {
+                CommandError::permanent(self.to_string(), ErrorType::Validation).with_details(
+                    serde_json::json!({
+                        "field": "max_points",
+                        "value": value,
+                        "min": min
+                    }),
+                )
+            }
+            VisualizationError::InvalidEpisodeSeverity => {
+                CommandError::permanent(self.to_string(), ErrorType::Validation).with_details(
+                    serde_json::json!({
+                        "field": "episode_severity"
+                    }),
+                )
+            }
             VisualizationError::NoData => {
                 CommandError::permanent(self.to_string(), ErrorType::NoData)
             }
@@ -62,10 +118,31 @@ impl ToCommandError for VisualizationError {
             VisualizationError::JsonError(_) => {
                 CommandError::permanent(self.to_string(), ErrorType::Internal)
             }
+            VisualizationError::Render(_) => {
+                CommandError::permanent(self.to_string(), ErrorType::Internal)
+            }
+
+            // File errors - not retryable
+            VisualizationError::Io(_) => {
+                CommandError::permanent(self.to_string(), ErrorType::IoError)
+            }
         }
     }
 }
 
+/// Optional extras for a chart data query; the default is the plain chart
+#[derive(Debug, Clone, Default)]
+pub struct ChartQueryOptions {
+    /// Derived series to build from the chart's points
+    pub series: Vec<DerivedSeriesKind>,
+    /// Downsample each series to at most this many points; statistics
+    /// still cover every point
+    pub max_points: Option<usize>,
+    /// IANA name of the timezone whose days derived series use, defaulting
+    /// to the system timezone
+    pub timezone: Option<String>,
+}
+
 /// Chart data point for time-series visualization
 #[derive(Serialize, Deserialize, specta::Type, Clone, Debug)]
 pub struct ChartDataPoint {
@@ -81,6 +158,7 @@ pub struct AssessmentChartData {
     pub data_points: Vec<ChartDataPoint>,
     pub thresholds: Vec<ThresholdLine>,
     pub statistics: ChartStatistics,
+    pub derived_series: Vec<DerivedSeries>, // Only the series requested
 }
 
 /// Threshold line for severity level visualization
@@ -97,17 +175,18 @@ pub struct ChartStatistics {
     pub min: f64,
     pub max: f64,
     pub average: f64,
-    pub trend: TrendDirection,
+    pub trend: TrendDirection, // Same as trend_analysis.direction
+    pub trend_analysis: TrendAnalysis,
     pub total_assessments: i32,
 }
 
-/// Trend direction for assessment scores
-#[derive(Serialize, Deserialize, specta::Type, Clone, Debug)]
+/// Trend direction for assessment scores and mood
+#[derive(Serialize, Deserialize, specta::Type, Clone, Copy, Debug, PartialEq)]
 #[serde(rename_all = "lowercase")]
 pub enum TrendDirection {
-    Improving, // Scores decreasing (lower is better)
-    Worsening, // Scores increasing
-    Stable,    // No significant change
+    Improving, // Scores decreasing (lower is better), mood rising
+    Worsening, // Scores increasing, mood falling
+    Stable,    // No significant change, or too little data
 }
 
 /// Mood chart data with activity breakdown
@@ -115,7 +194,9 @@ pub enum TrendDirection {
 pub struct MoodChartData {
     pub data_points: Vec<ChartDataPoint>,
     pub activity_breakdown: Vec<ActivityMoodData>,
+    pub group_breakdown: Vec<ActivityGroupMoodData>, // Filled with activity_breakdown
     pub statistics: MoodStatistics,
+    pub derived_series: Vec<DerivedSeries>, // Only the series requested
 }
 
 /// Activity-specific mood data for correlation analysis
@@ -136,57 +217,177 @@ pub struct MoodStatistics {
     pub mode: i32, // Most common mood rating
     pub total_checkins: i32,
     pub checkins_per_day: f64, // Average
+    pub trend_analysis: TrendAnalysis,
 }
 
 /// Time range for chart data queries
-#[derive(Serialize, Deserialize, specta::Type, Clone, Debug)]
+///
+/// Rolling ranges end now. Calendar ranges follow local calendar boundaries
+/// (weeks start on Monday) in the user's timezone.
+#[derive(Serialize, Deserialize, specta::Type, Clone, Debug, PartialEq)]
 #[serde(rename_all = "lowercase")]
 pub enum TimeRange {
-    Week,    // Last 7 days
-    Month,   // Last 30 days
-    Quarter, // Last 90 days
-    Year,    // Last 365 days
-    AllTime, // All data
-    Custom,  // Custom date range
+    Week,              // Last 7 days
+    Month,             // Last 30 days
+    Quarter,           // Last 90 days
+    Year,              // Last 365 days
+    AllTime,           // All data
+    Custom,            // Custom date range
+    ThisWeek,          // Monday 00:00 to now
+    LastWeek,          // The previous Monday to Sunday
+    ThisMonth,         // The 1st to now
+    LastMonth,         // The previous calendar month
+    YearToDate,        // January 1st to now
+    SameMonthLastYear, // This calendar month, one year ago
+}
+
+/// Resolved bounds of a period, as UTC timestamps in the stored format (both inclusive)
+#[derive(Serialize, Deserialize, specta::Type, Clone, Debug, PartialEq)]
+pub struct DateBounds {
+    pub from_date: String,
+    pub to_date: String,
 }
 
+/// Distance from a period to its previous equivalent
+enum PeriodStep {
+    Days(i64),
+    Months(u32),
+}
+
+const DATE_BOUND_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
 impl TimeRange {
-    /// Convert time range to date bounds (from_date, to_date)
+    /// Convert time range to date bounds (from_date, to_date), with calendar
+    /// boundaries in the system timezone
     pub fn to_date_range(&self) -> Option<(String, String)> {
-        use chrono::{Duration, Utc};
-
-        let now = Utc::now();
-        let to_date = now.format("%Y-%m-%d %H:%M:%S").to_string();
+        let tz = parse_timezone(&system_timezone()).unwrap_or(chrono_tz::UTC);
+        self.to_date_range_at(Utc::now(), tz)
+    }
 
-        let from_date = match self {
-            TimeRange::Week => now.checked_sub_signed(Duration::days(7)),
-            TimeRange::Month => now.checked_sub_signed(Duration::days(30)),
-            TimeRange::Quarter => now.checked_sub_signed(Duration::days(90)),
-            TimeRange::Year => now.checked_sub_signed(Duration::days(365)),
-            TimeRange::AllTime => return None, // No date filter
-            TimeRange::Custom => return None,  // Dates provided separately
-        };
+    /// Date bounds as of `now`, with calendar boundaries in `tz`.
+    /// None for AllTime and Custom, which have no bounds of their own.
+    pub fn to_date_range_at(&self, now: DateTime<Utc>, tz: Tz) -> Option<(String, String)> {
+        self.period_at(now, tz, 0)
+    }
 
-        from_date.map(|from| (from.format("%Y-%m-%d %H:%M:%S").to_string(), to_date))
+    /// Bounds of the previous equivalent period: the same length before a
+    /// rolling range, the previous week/month/year for calendar ranges, and
+    /// the same elapsed part of it for ranges ending now (Monday to now is
+    /// compared with last Monday to the same time).
+    pub fn previous_date_range_at(&self, now: DateTime<Utc>, tz: Tz) -> Option<(String, String)> {
+        self.period_at(now, tz, 1)
     }
-}
 
-/// Calculate trend direction based on first and last scores
-/// T117: Unit test - Trend calculation (improving/worsening/stable)
-pub fn calculate_trend(first_score: f64, last_score: f64) -> TrendDirection {
-    if first_score == 0.0 {
-        return TrendDirection::Stable;
+    /// A period and its previous equivalent. Custom ranges use the given
+    /// bounds and the span of the same length just before them; AllTime has
+    /// no previous period.
+    pub fn comparison_periods_at(
+        &self,
+        from_date: Option<&str>,
+        to_date: Option<&str>,
+        now: DateTime<Utc>,
+        tz: Tz,
+    ) -> Option<(DateBounds, DateBounds)> {
+        let bounds = |(from_date, to_date)| DateBounds { from_date, to_date };
+        match self {
+            TimeRange::AllTime => None,
+            TimeRange::Custom => {
+                let from = parse_chart_timestamp(from_date?)?;
+                let to = parse_chart_timestamp(to_date?)?;
+                if to < from {
+                    return None;
+                }
+                let previous_to = from - Duration::seconds(1);
+                let previous_from = previous_to - (to - from);
+                Some((
+                    bounds((
+                        from.format(DATE_BOUND_FORMAT).to_string(),
+                        to.format(DATE_BOUND_FORMAT).to_string(),
+                    )),
+                    bounds((
+                        previous_from.format(DATE_BOUND_FORMAT).to_string(),
+                        previous_to.format(DATE_BOUND_FORMAT).to_string(),
+                    )),
+                ))
+            }
+            _ => Some((
+                bounds(self.to_date_range_at(now, tz)?),
+                bounds(self.previous_date_range_at(now, tz)?),
+            )),
+        }
     }
 
-    let change_percent = ((last_score - first_score) / first_score).abs();
+    /// Bounds of the period `periods_back` steps before the current one
+    fn period_at(&self, now: DateTime<Utc>, tz: Tz, periods_back: u32) -> Option<(String, String)> {
+        let local = now.with_timezone(&tz).naive_local();
+        let today = local.date().and_time(NaiveTime::MIN);
+        let monday = today - Duration::days(today.weekday().num_days_from_monday() as i64);
+        let month_start = today.with_day(1)?;
+        let one_month = Months::new(1);
+
+        // (start, end, whether end is the start of the next period, step)
+        let (start, end, whole_period, step) = match self {
+            TimeRange::Week => (local - Duration::days(7), local, false, PeriodStep::Days(7)),
+            TimeRange::Month => (
+                local - Duration::days(30),
+                local,
+                false,
+                PeriodStep::Days(30),
+            ),
+            TimeRange::Quarter => (
+                local - Duration::days(90),
+                local,
+                false,
+                PeriodStep::Days(90),
+            ),
+            TimeRange::Year => (
+                local - Duration::days(365),
+                local,
+                false,
+                PeriodStep::Days(365),
+            ),
+            TimeRange::AllTime => return None, // No date filter
+            TimeRange::Custom => return None,  // Dates provided separately
+            TimeRange::ThisWeek => (monday, local, false, PeriodStep::Days(7)),
+            TimeRange::LastWeek => (
+                monday - Duration::days(7),
+                monday,
+                true,
+                PeriodStep::Days(7),
+            ),
+            TimeRange::ThisMonth => (month_start, local, false, PeriodStep::Months(1)),
+            TimeRange::LastMonth => (
+                month_start.checked_sub_months(one_month)?,
+                month_start,
+                true,
+                PeriodStep::Months(1),
+            ),
+            TimeRange::YearToDate => (today.with_ordinal(1)?, local, false, PeriodStep::Months(12)),
+            TimeRange::SameMonthLastYear => (
+                month_start.checked_sub_months(Months::new(12))?,
+                month_start.checked_sub_months(Months::new(11))?,
+                true,
+                PeriodStep::Months(12),
+            ),
+        };
+
+        // Month steps clamp to the end of shorter months (March 31st -> February 28th)
+        let shift = |at: NaiveDateTime| match step {
+            PeriodStep::Days(days) => {
+                at.checked_sub_signed(Duration::days(days * periods_back as i64))
+            }
+            PeriodStep::Months(months) => at.checked_sub_months(Months::new(months * periods_back)),
+        };
+        let from = resolve_local_time(&tz, shift(start)?);
+        let mut to = resolve_local_time(&tz, shift(end)?);
+        if whole_period {
+            to -= Duration::seconds(1);
+        }
 
-    // Lower scores are better for depression/anxiety assessments
-    if last_score < first_score && change_percent > 0.20 {
-        TrendDirection::Improving
-    } else if last_score > first_score && change_percent > 0.20 {
-        TrendDirection::Worsening
-    } else {
-        TrendDirection::Stable
+        Some((
+            from.format(DATE_BOUND_FORMAT).to_string(),
+            to.format(DATE_BOUND_FORMAT).to_string(),
+        ))
     }
 }
 
@@ -226,6 +427,7 @@ pub fn calculate_mood_statistics(ratings: &[i32]) -> Option<MoodStatistics> {
         mode,
         total_checkins: ratings.len() as i32,
         checkins_per_day: 0.0, // Calculated in repository with date range
+        trend_analysis: TrendAnalysis::default(), // Needs timestamps, set in repository
     })
 }
 
@@ -233,31 +435,6 @@ pub fn calculate_mood_statistics(ratings: &[i32]) -> Option<MoodStatistics> {
 mod tests {
     use super::*;
 
-    // T117: Unit test - Trend calculation
-    #[test]
-    fn test_trend_improving() {
-        let trend = calculate_trend(20.0, 10.0);
-        assert!(matches!(trend, TrendDirection::Improving));
-    }
-
-    #[test]
-    fn test_trend_worsening() {
-        let trend = calculate_trend(10.0, 20.0);
-        assert!(matches!(trend, TrendDirection::Worsening));
-    }
-
-    #[test]
-    fn test_trend_stable() {
-        let trend = calculate_trend(10.0, 11.0); // 10% change, below 20% threshold
-        assert!(matches!(trend, TrendDirection::Stable));
-    }
-
-    #[test]
-    fn test_trend_zero_baseline() {
-        let trend = calculate_trend(0.0, 10.0);
-        assert!(matches!(trend, TrendDirection::Stable));
-    }
-
     #[test]
     fn test_mood_statistics_calculation() {
         let ratings = vec![3, 4, 4, 5, 3, 2, 4];
@@ -291,4 +468,121 @@ mod tests {
         assert!(TimeRange::AllTime.to_date_range().is_none());
         assert!(TimeRange::Custom.to_date_range().is_none());
     }
+
+    fn at(timestamp: &str) -> DateTime<Utc> {
+        NaiveDateTime::parse_from_str(timestamp, "%Y-%m-%d %H:%M:%S")
+            .unwrap()
+            .and_utc()
+    }
+
+    fn range(from: &str, to: &str) -> Option<(String, String)> {
+        Some((from.to_string(), to.to_string()))
+    }
+
+    #[test]
+    fn test_calendar_ranges_use_local_boundaries() {
+        // Thursday 13 March 2025, 16:30 in Berlin (UTC+1)
+        let now = at("2025-03-13 15:30:00");
+        let tz: Tz = "Europe/Berlin".parse().unwrap();
+
+        assert_eq!(
+            TimeRange::ThisWeek.to_date_range_at(now, tz),
+            range("2025-03-09 23:00:00", "2025-03-13 15:30:00")
+        );
+        assert_eq!(
+            TimeRange::LastWeek.to_date_range_at(now, tz),
+            range("2025-03-02 23:00:00", "2025-03-09 22:59:59")
+        );
+        assert_eq!(
+            TimeRange::ThisMonth.to_date_range_at(now, tz),
+            range("2025-02-28 23:00:00", "2025-03-13 15:30:00")
+        );
+        assert_eq!(
+            TimeRange::LastMonth.to_date_range_at(now, tz),
+            range("2025-01-31 23:00:00", "2025-02-28 22:59:59")
+        );
+        assert_eq!(
+            TimeRange::YearToDate.to_date_range_at(now, tz),
+            range("2024-12-31 23:00:00", "2025-03-13 15:30:00")
+        );
+        // March 2024 ends after the switch to summer time (UTC+2)
+        assert_eq!(
+            TimeRange::SameMonthLastYear.to_date_range_at(now, tz),
+            range("2024-02-29 23:00:00", "2024-03-31 21:59:59")
+        );
+        assert_eq!(
+            TimeRange::Week.to_date_range_at(now, tz),
+            range("2025-03-06 15:30:00", "2025-03-13 15:30:00")
+        );
+    }
+
+    #[test]
+    fn test_previous_periods() {
+        let now = at("2025-03-13 15:30:00");
+        let tz: Tz = "Europe/Berlin".parse().unwrap();
+
+        // Same elapsed part of the previous week
+        assert_eq!(
+            TimeRange::ThisWeek.previous_date_range_at(now, tz),
+            range("2025-03-02 23:00:00", "2025-03-06 15:30:00")
+        );
+        assert_eq!(
+            TimeRange::LastMonth.previous_date_range_at(now, tz),
+            range("2024-12-31 23:00:00", "2025-01-31 22:59:59")
+        );
+        assert_eq!(
+            TimeRange::YearToDate.previous_date_range_at(now, tz),
+            range("2023-12-31 23:00:00", "2024-03-13 15:30:00")
+        );
+        // 16:30 local on both ends; September is summer time (UTC+2)
+        assert_eq!(
+            TimeRange::Quarter.previous_date_range_at(now, tz),
+            range("2024-09-14 14:30:00", "2024-12-13 15:30:00")
+        );
+
+        // March 31st compares with the end of February, not March 3rd
+        let now = at("2025-03-31 10:00:00");
+        assert_eq!(
+            TimeRange::ThisMonth.previous_date_range_at(now, tz),
+            range("2025-01-31 23:00:00", "2025-02-28 11:00:00")
+        );
+    }
+
+    #[test]
+    fn test_comparison_periods() {
+        let now = at("2025-03-13 15:30:00");
+
+        let (current, previous) = TimeRange::Custom
+            .comparison_periods_at(
+                Some("2025-03-10 00:00:00"),
+                Some("2025-03-16 23:59:59"),
+                now,
+                chrono_tz::UTC,
+            )
+            .unwrap();
+        assert_eq!(current.from_date, "2025-03-10 00:00:00");
+        assert_eq!(previous.from_date, "2025-03-03 00:00:00");
+        assert_eq!(previous.to_date, "2025-03-09 23:59:59");
+
+        let (current, previous) = TimeRange::LastWeek
+            .comparison_periods_at(None, None, now, chrono_tz::UTC)
+            .unwrap();
+        assert_eq!(current.from_date, "2025-03-03 00:00:00");
+        assert_eq!(previous.to_date, "2025-03-02 23:59:59");
+
+        assert!(TimeRange::AllTime
+            .comparison_periods_at(None, None, now, chrono_tz::UTC)
+            .is_none());
+        assert!(TimeRange::Custom
+            .comparison_periods_at(Some("2025-03-10 00:00:00"), None, now, chrono_tz::UTC)
+            .is_none());
+        assert!(TimeRange::Custom
+            .comparison_periods_at(
+                Some("2025-03-10 00:00:00"),
+                Some("2025-03-01 00:00:00"),
+                now,
+                chrono_tz::UTC
+            )
+            .is_none());
+    }
 }
@@ -1,30 +1,31 @@
 // Visualization queries (User Story 4 & 5)
 // T126, T144: Tauri commands for chart data retrieval
 
+use chrono::Utc;
 use tauri::State;
 
 use crate::{
     errors::{ErrorType, ToCommandError},
+    features::scheduling::models::{parse_timezone, system_timezone},
+    types::assessment::SeverityLevel,
     AppState, CommandError,
 };
 
+use super::comparison::PeriodComparison;
+use super::distribution::SeverityDistribution;
+use super::heatmap::CalendarHeatmap;
 use super::models::*;
 use super::repository::VisualizationRepository;
+use super::series::DerivedSeriesKind;
+use super::timeline::CombinedTimeline;
 
-/// T126: Get assessment chart data for visualization
-#[tauri::command]
-#[specta::specta]
-pub fn get_assessment_chart_data(
-    code: String,
+/// Resolve a time range to (from_date, to_date) bounds
+pub(crate) fn resolve_time_range(
     time_range: TimeRange,
     from_date: Option<String>,
     to_date: Option<String>,
-    state: State<AppState>,
-) -> Result<AssessmentChartData, CommandError> {
-    let repo = VisualizationRepository::new(state.db.clone());
-
-    // Resolve time range to dates
-    let (from, to) = match time_range {
+) -> Result<(Option<String>, Option<String>), CommandError> {
+    match time_range {
         TimeRange::Custom => {
             // Custom range requires both dates
             if from_date.is_none() || to_date.is_none() {
@@ -33,23 +34,51 @@ pub fn get_assessment_chart_data(
                     ErrorType::Validation,
                 ));
             }
-            (from_date, to_date)
+            Ok((from_date, to_date))
         }
-        TimeRange::AllTime => (None, None),
+        TimeRange::AllTime => Ok((None, None)),
         _ => {
             // Use predefined range
             match time_range.to_date_range() {
-                Some((from_str, to_str)) => (Some(from_str), Some(to_str)),
-                None => (None, None),
+                Some((from_str, to_str)) => Ok((Some(from_str), Some(to_str))),
+                None => Ok((None, None)),
             }
         }
+    }
+}
+
+/// T126: Get assessment chart data for visualization. Derived series use
+/// days in `timezone`, defaulting to the system timezone.
+#[allow(clippy::too_many_arguments)]
+#[tauri::command]
+#[specta::specta]
+pub fn get_assessment_chart_data(
+    code: String,
+    time_range: TimeRange,
+    from_date: Option<String>,
+    to_date: Option<String>,
+    series: Option<Vec<DerivedSeriesKind>>,
+    max_points: Option<u32>,
+    timezone: Option<String>,
+    state: State<AppState>,
+) -> Result<AssessmentChartData, CommandError> {
+    let repo = VisualizationRepository::new(state.db.clone());
+
+    let (from, to) = resolve_time_range(time_range, from_date, to_date)?;
+
+    let options = ChartQueryOptions {
+        series: series.unwrap_or_default(),
+        max_points: max_points.map(|n| n as usize),
+        timezone,
     };
 
-    repo.get_assessment_chart_data(&code, from.as_deref(), to.as_deref())
+    repo.get_assessment_chart_data_with_options(&code, from.as_deref(), to.as_deref(), &options)
         .map_err(|e| e.to_command_error())
 }
 
-/// T144: Get mood chart data for visualization
+/// T144: Get mood chart data for visualization. Derived series use days in
+/// `timezone`, defaulting to the system timezone.
+#[allow(clippy::too_many_arguments)]
 #[tauri::command]
 #[specta::specta]
 pub fn get_mood_chart_data(
@@ -57,32 +86,115 @@ pub fn get_mood_chart_data(
     from_date: Option<String>,
     to_date: Option<String>,
     group_by_activity: bool,
+    series: Option<Vec<DerivedSeriesKind>>,
+    max_points: Option<u32>,
+    timezone: Option<String>,
     state: State<AppState>,
 ) -> Result<MoodChartData, CommandError> {
     let repo = VisualizationRepository::new(state.db.clone());
 
-    // Resolve time range to dates
-    let (from, to) = match time_range {
-        TimeRange::Custom => {
-            // Custom range requires both dates
-            if from_date.is_none() || to_date.is_none() {
-                return Err(CommandError::permanent(
-                    "from_date and to_date required for custom time range".to_string(),
-                    ErrorType::Validation,
-                ));
-            }
-            (from_date, to_date)
-        }
-        TimeRange::AllTime => (None, None),
-        _ => {
-            // Use predefined range
-            match time_range.to_date_range() {
-                Some((from_str, to_str)) => (Some(from_str), Some(to_str)),
-                None => (None, None),
-            }
-        }
+    let (from, to) = resolve_time_range(time_range, from_date, to_date)?;
+
+    let options = ChartQueryOptions {
+        series: series.unwrap_or_default(),
+        max_points: max_points.map(|n| n as usize),
+        timezone,
     };
 
-    repo.get_mood_chart_data(from.as_deref(), to.as_deref(), group_by_activity)
+    repo.get_mood_chart_data_with_options(
+        from.as_deref(),
+        to.as_deref(),
+        group_by_activity,
+        &options,
+    )
+    .map_err(|e| e.to_command_error())
+}
+
+/// Get mood, assessments and activity groups as aligned daily series,
+/// with the correlation between mood and each assessment
+#[tauri::command]
+#[specta::specta]
+pub fn get_combined_timeline(
+    time_range: TimeRange,
+    from_date: Option<String>,
+    to_date: Option<String>,
+    state: State<AppState>,
+) -> Result<CombinedTimeline, CommandError> {
+    let repo = VisualizationRepository::new(state.db.clone());
+
+    let (from, to) = resolve_time_range(time_range, from_date, to_date)?;
+
+    repo.get_combined_timeline(from.as_deref(), to.as_deref())
+        .map_err(|e| e.to_command_error())
+}
+
+/// Get per-day mood, check-in, activity group and assessment aggregates
+/// for a calendar heatmap. Days are YYYY-MM-DD in `timezone`, defaulting
+/// to the system timezone.
+#[tauri::command]
+#[specta::specta]
+pub fn get_calendar_heatmap(
+    from_day: String,
+    to_day: String,
+    timezone: Option<String>,
+    state: State<AppState>,
+) -> Result<CalendarHeatmap, CommandError> {
+    let repo = VisualizationRepository::new(state.db.clone());
+
+    repo.get_calendar_heatmap(&from_day, &to_day, timezone.as_deref())
+        .map_err(|e| e.to_command_error())
+}
+
+/// Get mood and assessment statistics for a period next to its previous
+/// equivalent period. Calendar ranges are resolved in the system timezone.
+#[tauri::command]
+#[specta::specta]
+pub fn get_period_comparison(
+    time_range: TimeRange,
+    from_date: Option<String>,
+    to_date: Option<String>,
+    state: State<AppState>,
+) -> Result<PeriodComparison, CommandError> {
+    let repo = VisualizationRepository::new(state.db.clone());
+
+    let tz = parse_timezone(&system_timezone()).unwrap_or(chrono_tz::UTC);
+    let (current, previous) = time_range
+        .comparison_periods_at(from_date.as_deref(), to_date.as_deref(), Utc::now(), tz)
+        .ok_or_else(|| {
+            CommandError::permanent(
+                "Comparison needs a range other than all time, or custom dates in order"
+                    .to_string(),
+                ErrorType::Validation,
+            )
+        })?;
+
+    repo.get_period_comparison(&current, &previous)
         .map_err(|e| e.to_command_error())
 }
+
+/// Get the time-weighted share of days at each severity level of an
+/// assessment type, a histogram of its scores, and the episodes at or above
+/// `episode_severity` (moderate unless given)
+#[tauri::command]
+#[specta::specta]
+pub fn get_severity_distribution(
+    code: String,
+    time_range: TimeRange,
+    from_date: Option<String>,
+    to_date: Option<String>,
+    episode_severity: Option<SeverityLevel>,
+    state: State<AppState>,
+) -> Result<SeverityDistribution, CommandError> {
+    let repo = VisualizationRepository::new(state.db.clone());
+
+    let (from, to) = resolve_time_range(time_range, from_date, to_date)?;
+
+    repo.get_severity_distribution(
+        &code,
+        from.as_deref(),
+        to.as_deref(),
+        episode_severity.unwrap_or(SeverityLevel::Moderate),
+        Utc::now(),
+    )
+    .map_err(|e| e.to_command_error())
+}
@@ -0,0 +1,466 @@
+// Chart rendering (SVG and PNG)
+//
+// Charts are drawn as SVG by hand so the output is fully deterministic, then
+// rasterized on the CPU with resvg for PNG. Text in PNGs uses the system
+// fonts; labels are simply left out if none are found.
+
+use std::fmt::Write;
+use std::sync::{Arc, OnceLock};
+
+use resvg::{tiny_skia, usvg};
+use serde::{Deserialize, Serialize};
+
+use super::models::{
+    AssessmentChartData, ChartDataPoint, MoodChartData, ThresholdLine, VisualizationError,
+};
+use super::series::DerivedSeriesKind;
+use super::trend::parse_chart_timestamp;
+use crate::types::mood::MoodRating;
+
+pub const DEFAULT_CHART_WIDTH: u32 = 800;
+pub const DEFAULT_CHART_HEIGHT: u32 = 400;
+
+const MARGIN_LEFT: f64 = 48.0;
+const MARGIN_RIGHT: f64 = 24.0;
+const MARGIN_TOP: f64 = 40.0;
+const MARGIN_BOTTOM: f64 = 36.0;
+
+const FONT_FAMILY: &str = "DejaVu Sans, Arial, Helvetica, sans-serif";
+const TEXT_COLOR: &str = "#424242";
+const AXIS_COLOR: &str = "#9E9E9E";
+const GRID_COLOR: &str = "#EEEEEE";
+const ASSESSMENT_COLOR: &str = "#1565C0";
+const MOOD_POINT_COLOR: &str = "#CE93D8";
+const MOOD_LINE_COLOR: &str = "#6A1B9A";
+const DERIVED_COLORS: [&str; 4] = ["#00897B", "#F4511E", "#3949AB", "#7CB342"];
+const BAND_OPACITY: f64 = 0.15;
+
+/// Output format of a rendered chart
+#[derive(Serialize, Deserialize, specta::Type, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ChartImageFormat {
+    Svg,
+    Png,
+}
+
+impl ChartImageFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ChartImageFormat::Svg => "svg",
+            ChartImageFormat::Png => "png",
+        }
+    }
+}
+
+/// Pixel size of a rendered chart
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChartSize {
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Default for ChartSize {
+    fn default() -> Self {
+        ChartSize {
+            width: DEFAULT_CHART_WIDTH,
+            height: DEFAULT_CHART_HEIGHT,
+        }
+    }
+}
+
+/// Assessment scores over time on the type's full scale, with a shaded band
+/// and dashed line per severity threshold
+pub fn render_assessment_chart_svg(data: &AssessmentChartData, size: ChartSize) -> String {
+    let y_min = data.assessment_type.min_score as f64;
+    let y_max = (data.assessment_type.max_score as f64).max(y_min + 1.0);
+    let plot = Plot::new(size, &[&data.data_points], y_min, y_max);
+
+    let mut svg = String::new();
+    plot.threshold_bands(&mut svg, &data.thresholds);
+    plot.axes(&mut svg, nice_step(y_max - y_min));
+    plot.line(&mut svg, &data.data_points, ASSESSMENT_COLOR, 2.0, false);
+    plot.points(&mut svg, &data.data_points, ASSESSMENT_COLOR, 3.0);
+
+    document(size, &data.assessment_type.name, &svg)
+}
+
+/// Mood check-ins on the 1-7 scale, with each derived series as a line.
+/// Without derived series the check-ins themselves are joined.
+pub fn render_mood_chart_svg(data: &MoodChartData, size: ChartSize) -> String {
+    let mut series: Vec<&[ChartDataPoint]> = vec![&data.data_points];
+    series.extend(data.derived_series.iter().map(|s| s.data_points.as_slice()));
+    let plot = Plot::new(
+        size,
+        &series,
+        MoodRating::MIN as f64,
+        MoodRating::MAX as f64,
+    );
+
+    let mut svg = String::new();
+    plot.axes(&mut svg, 1.0);
+    if data.derived_series.is_empty() {
+        plot.line(&mut svg, &data.data_points, MOOD_LINE_COLOR, 1.5, false);
+    }
+    plot.points(&mut svg, &data.data_points, MOOD_POINT_COLOR, 2.5);
+
+    let mut legend = vec![("Check-ins", MOOD_POINT_COLOR)];
+    for (series, color) in data
+        .derived_series
+        .iter()
+        .zip(DERIVED_COLORS.iter().cycle())
+    {
+        // Smoothed series dashed, to tell them apart from the daily mean
+        let dashed = series.kind != DerivedSeriesKind::DailyMean;
+        plot.line(&mut svg, &series.data_points, color, 2.0, dashed);
+        legend.push((series.name.as_str(), color));
+    }
+    plot.legend(&mut svg, &legend);
+
+    document(size, "Mood", &svg)
+}
+
+/// Encode an SVG chart in `format`, rasterizing with `fontdb` for PNG
+pub fn encode_chart(
+    svg: String,
+    format: ChartImageFormat,
+    fontdb: Arc<usvg::fontdb::Database>,
+) -> Result<Vec<u8>, VisualizationError> {
+    match format {
+        ChartImageFormat::Svg => Ok(svg.into_bytes()),
+        ChartImageFormat::Png => svg_to_png(&svg, fontdb),
+    }
+}
+
+/// Rasterize an SVG document at its own size
+pub fn svg_to_png(
+    svg: &str,
+    fontdb: Arc<usvg::fontdb::Database>,
+) -> Result<Vec<u8>, VisualizationError> {
+    let options = usvg::Options {
+        fontdb,
+        ..Default::default()
+    };
+    let tree = usvg::Tree::from_str(svg, &options)
+        .map_err(|e| VisualizationError::Render(e.to_string()))?;
+    let size = tree.size().to_int_size();
+    let mut pixmap = tiny_skia::Pixmap::new(size.width(), size.height())
+        .ok_or_else(|| VisualizationError::Render("Chart has no area".to_string()))?;
+    resvg::render(&tree, tiny_skia::Transform::default(), &mut pixmap.as_mut());
+    pixmap
+        .encode_png()
+        .map_err(|e| VisualizationError::Render(e.to_string()))
+}
+
+/// System fonts, loaded once
+pub fn system_fonts() -> Arc<usvg::fontdb::Database> {
+    static FONTS: OnceLock<Arc<usvg::fontdb::Database>> = OnceLock::new();
+    FONTS
+        .get_or_init(|| {
+            let mut fontdb = usvg::fontdb::Database::new();
+            fontdb.load_system_fonts();
+            Arc::new(fontdb)
+        })
+        .clone()
+}
+
+/// Plot area and scales of one chart
+struct Plot {
+    left: f64,
+    top: f64,
+    width: f64,
+    height: f64,
+    y_min: f64,
+    y_max: f64,
+    /// Seconds since the epoch of the first and last point
+    x_range: Option<(f64, f64)>,
+}
+
+impl Plot {
+    /// The time axis spans every point of every series in `series`
+    fn new(size: ChartSize, series: &[&[ChartDataPoint]], y_min: f64, y_max: f64) -> Self {
+        let x_range = series
+            .iter()
+            .flat_map(|points| points.iter().filter_map(point_time))
+            .fold(None, |range: Option<(f64, f64)>, t| match range {
+                None => Some((t, t)),
+                Some((lo, hi)) => Some((lo.min(t), hi.max(t))),
+            });
+        Plot {
+            left: MARGIN_LEFT,
+            top: MARGIN_TOP,
+            width: (size.width as f64 - MARGIN_LEFT - MARGIN_RIGHT).max(1.0),
+            height: (size.height as f64 - MARGIN_TOP - MARGIN_BOTTOM).max(1.0),
+            y_min,
+            y_max,
+            x_range,
+        }
+    }
+
+    fn right(&self) -> f64 {
+        self.left + self.width
+    }
+
+    fn bottom(&self) -> f64 {
+        self.top + self.height
+    }
+
+    /// A single instant is drawn in the middle
+    fn x(&self, t: f64) -> f64 {
+        match self.x_range {
+            Some((lo, hi)) if hi > lo => self.left + (t - lo) / (hi - lo) * self.width,
+            _ => self.left + self.width / 2.0,
+        }
+    }
+
+    fn y(&self, value: f64) -> f64 {
+        let value = value.clamp(self.y_min, self.y_max);
+        self.top + (self.y_max - value) / (self.y_max - self.y_min) * self.height
+    }
+
+    /// Thresholds are the upper bounds of their severity level
+    fn threshold_bands(&self, svg: &mut String, thresholds: &[ThresholdLine]) {
+        let mut sorted: Vec<&ThresholdLine> = thresholds.iter().collect();
+        sorted.sort_by(|a, b| a.value.total_cmp(&b.value));
+
+        let mut lower = self.y_min;
+        for threshold in sorted {
+            let upper = threshold.value.min(self.y_max);
+            if upper <= lower {
+                continue;
+            }
+            let (y_top, y_bottom) = (self.y(upper), self.y(lower));
+            let _ = writeln!(
+                svg,
+                r#"<rect x="{:.1}" y="{:.1}" width="{:.1}" height="{:.1}" fill="{}" fill-opacity="{}"/>"#,
+                self.left,
+                y_top,
+                self.width,
+                y_bottom - y_top,
+                threshold.color,
+                BAND_OPACITY
+            );
+            if upper < self.y_max {
+                let _ = writeln!(
+                    svg,
+                    r#"<line x1="{:.1}" y1="{:.1}" x2="{:.1}" y2="{:.1}" stroke="{}" stroke-width="1" stroke-dasharray="4 3"/>"#,
+                    self.left,
+                    y_top,
+                    self.right(),
+                    y_top,
+                    threshold.color
+                );
+            }
+            let _ = writeln!(
+                svg,
+                r#"<text x="{:.1}" y="{:.1}" font-size="10" text-anchor="end" fill="{}">{}</text>"#,
+                self.right() - 4.0,
+                y_top + 12.0,
+                TEXT_COLOR,
+                escape(&threshold.label)
+            );
+            lower = upper;
+        }
+    }
+
+    /// Gridlines every `y_step`, both axes and first/last dates
+    fn axes(&self, svg: &mut String, y_step: f64) {
+        let mut value = (self.y_min / y_step).ceil() * y_step;
+        while value <= self.y_max + f64::EPSILON {
+            let y = self.y(value);
+            let _ = writeln!(
+                svg,
+                r#"<line x1="{:.1}" y1="{:.1}" x2="{:.1}" y2="{:.1}" stroke="{}" stroke-width="1"/>"#,
+                self.left,
+                y,
+                self.right(),
+                y,
+                GRID_COLOR
+            );
+            let _ = writeln!(
+                svg,
+                r#"<text x="{:.1}" y="{:.1}" font-size="11" text-anchor="end" fill="{}">{}</text>"#,
+                self.left - 6.0,
+                y + 4.0,
+                TEXT_COLOR,
+                value
+            );
+            value += y_step;
+        }
+
+        let _ = writeln!(
+            svg,
+            r#"<path d="M{:.1} {:.1}V{:.1}H{:.1}" fill="none" stroke="{}" stroke-width="1"/>"#,
+            self.left,
+            self.top,
+            self.bottom(),
+            self.right(),
+            AXIS_COLOR
+        );
+
+        if let Some((lo, hi)) = self.x_range {
+            let labels = if hi > lo {
+                vec![(lo, "start"), (hi, "end")]
+            } else {
+                vec![(lo, "middle")]
+            };
+            for (t, anchor) in labels {
+                let _ = writeln!(
+                    svg,
+                    r#"<text x="{:.1}" y="{:.1}" font-size="11" text-anchor="{}" fill="{}">{}</text>"#,
+                    self.x(t),
+                    self.bottom() + 18.0,
+                    anchor,
+                    TEXT_COLOR,
+                    date_label(t)
+                );
+            }
+        }
+    }
+
+    fn line(
+        &self,
+        svg: &mut String,
+        points: &[ChartDataPoint],
+        color: &str,
+        width: f64,
+        dashed: bool,
+    ) {
+        let mut path = String::new();
+        for (i, (t, value)) in plotted(points).enumerate() {
+            let _ = write!(
+                path,
+                "{}{:.1} {:.1}",
+                if i == 0 { "M" } else { "L" },
+                self.x(t),
+                self.y(value)
+            );
+        }
+        if path.is_empty() {
+            return;
+        }
+        let _ = writeln!(
+            svg,
+            r#"<path d="{}" fill="none" stroke="{}" stroke-width="{}" stroke-linejoin="round"{}/>"#,
+            path,
+            color,
+            width,
+            if dashed {
+                r#" stroke-dasharray="6 4""#
+            } else {
+                ""
+            }
+        );
+    }
+
+    fn points(&self, svg: &mut String, points: &[ChartDataPoint], color: &str, radius: f64) {
+        for (t, value) in plotted(points) {
+            let _ = writeln!(
+                svg,
+                r#"<circle cx="{:.1}" cy="{:.1}" r="{}" fill="{}"/>"#,
+                self.x(t),
+                self.y(value),
+                radius,
+                color
+            );
+        }
+    }
+
+    /// Legend entries right-aligned above the plot
+    fn legend(&self, svg: &mut String, entries: &[(&str, &str)]) {
+        let mut x = self.right();
+        for (label, color) in entries.iter().rev() {
+            let _ = writeln!(
+                svg,
+                r#"<text x="{:.1}" y="{:.1}" font-size="11" text-anchor="end" fill="{}">{}</text>"#,
+                x,
+                self.top - 10.0,
+                TEXT_COLOR,
+                escape(label)
+            );
+            // Approximate width; exact metrics depend on the font
+            x -= label.chars().count() as f64 * 6.5 + 6.0;
+            let _ = writeln!(
+                svg,
+                r#"<rect x="{:.1}" y="{:.1}" width="10" height="10" fill="{}"/>"#,
+                x - 10.0,
+                self.top - 19.0,
+                color
+            );
+            x -= 24.0;
+        }
+    }
+}
+
+fn document(size: ChartSize, title: &str, body: &str) -> String {
+    format!(
+        concat!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}" font-family="{font}">"#,
+            "\n",
+            r##"<rect width="{w}" height="{h}" fill="#FFFFFF"/>"##,
+            "\n",
+            r#"<text x="{x}" y="22" font-size="15" font-weight="bold" fill="{color}">{title}</text>"#,
+            "\n{body}</svg>\n"
+        ),
+        w = size.width,
+        h = size.height,
+        font = FONT_FAMILY,
+        x = MARGIN_LEFT,
+        color = TEXT_COLOR,
+        title = escape(title),
+        body = body
+    )
+}
+
+fn point_time(point: &ChartDataPoint) -> Option<f64> {
+    parse_chart_timestamp(&point.timestamp).map(|at| at.and_utc().timestamp() as f64)
+}
+
+/// (time, value) of the points that can be placed on the chart
+fn plotted(points: &[ChartDataPoint]) -> impl Iterator<Item = (f64, f64)> + '_ {
+    points
+        .iter()
+        .filter(|p| p.value.is_finite())
+        .filter_map(|p| Some((point_time(p)?, p.value)))
+}
+
+fn date_label(t: f64) -> String {
+    chrono::DateTime::from_timestamp(t as i64, 0)
+        .map(|at| at.format("%Y-%m-%d").to_string())
+        .unwrap_or_default()
+}
+
+/// 1, 2 or 5 times a power of ten, giving at most 6 gridlines
+fn nice_step(range: f64) -> f64 {
+    let rough = range / 5.0;
+    let magnitude = 10f64.powf(rough.log10().floor());
+    [1.0, 2.0, 5.0, 10.0]
+        .iter()
+        .map(|m| m * magnitude)
+        .find(|step| range / step <= 5.0)
+        .unwrap_or(10.0 * magnitude)
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nice_step() {
+        assert_eq!(nice_step(27.0), 10.0);
+        assert_eq!(nice_step(21.0), 5.0);
+        assert_eq!(nice_step(60.0), 20.0);
+        assert_eq!(nice_step(6.0), 2.0);
+    }
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(escape("A & <B>"), "A &amp; &lt;B&gt;");
+    }
+}
@@ -1,13 +1,44 @@
 // Visualization repository (User Story 4 & 5)
 // T124-T125, T142-T143: Database queries for chart data
 
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use chrono_tz::Tz;
+use rusqlite::OptionalExtension;
+
 use crate::db::Database;
 use crate::features::assessments::models::AssessmentType;
 use crate::features::mood::models::Activity;
+use crate::features::scheduling::models::{parse_timezone, system_timezone};
+use crate::types::assessment::SeverityLevel;
+use crate::types::mood::MoodRating;
 
+use super::comparison::{AssessmentPeriodComparison, MoodPeriodComparison, PeriodComparison};
+use super::distribution::{
+    carry_forward, fractional_days, score_histogram, severity_episodes, severity_shares,
+    tracked_days, SeverityDistribution, SeverityResult,
+};
+use super::downsample::{downsample_lttb, MIN_CHART_POINTS};
+use super::group_breakdown::{ActivityGroupMoodData, GroupBreakdownBuilder};
+use super::heatmap::{
+    local_day, CalendarHeatmap, HeatmapActivityGroup, HeatmapBuilder, MAX_HEATMAP_DAYS,
+};
 use super::models::*;
+use super::render::{
+    encode_chart, render_assessment_chart_svg, render_mood_chart_svg, system_fonts,
+    ChartImageFormat, ChartSize,
+};
+use super::series::{derive_series, DerivedSeries, DerivedSeriesKind};
+use super::timeline::{
+    align_daily, days_between, mood_correlation, timeline_day, ActivityGroupTimelineSeries,
+    AssessmentTimelineSeries, CombinedTimeline, MAX_TIMELINE_DAYS,
+};
+use super::trend::{analyze_trend, parse_chart_timestamp, ScoreDirection};
+
+/// Group id -> (group name, logs per day)
+type GroupDailyCounts = BTreeMap<i32, (String, BTreeMap<NaiveDate, i32>)>;
 
 pub struct VisualizationRepository {
     db: Arc<Database>,
@@ -25,6 +56,26 @@ impl VisualizationRepository {
         from_date: Option<&str>,
         to_date: Option<&str>,
     ) -> Result<AssessmentChartData, VisualizationError> {
+        self.get_assessment_chart_data_with_options(
+            code,
+            from_date,
+            to_date,
+            &ChartQueryOptions::default(),
+        )
+    }
+
+    /// Assessment chart data plus any derived series in `options`, built
+    /// from the same points, downsampled to `options.max_points`
+    pub fn get_assessment_chart_data_with_options(
+        &self,
+        code: &str,
+        from_date: Option<&str>,
+        to_date: Option<&str>,
+        options: &ChartQueryOptions,
+    ) -> Result<AssessmentChartData, VisualizationError> {
+        validate_max_points(options.max_points)?;
+        let tz = chart_timezone(options.timezone.as_deref())?;
+
         let conn = self.db.get_connection();
         let conn = conn.lock();
 
@@ -75,11 +126,16 @@ impl VisualizationRepository {
         // Calculate statistics
         let statistics = self.calculate_statistics(&data_points, &assessment_type)?;
 
+        let derived_series = derive_series(&data_points, &options.series, tz);
+        let (data_points, derived_series) =
+            downsample_chart(data_points, derived_series, options.max_points);
+
         Ok(AssessmentChartData {
             assessment_type,
             data_points,
             thresholds,
             statistics,
+            derived_series,
         })
     }
 
@@ -90,6 +146,26 @@ impl VisualizationRepository {
         to_date: Option<&str>,
         group_by_activity: bool,
     ) -> Result<MoodChartData, VisualizationError> {
+        self.get_mood_chart_data_with_options(
+            from_date,
+            to_date,
+            group_by_activity,
+            &ChartQueryOptions::default(),
+        )
+    }
+
+    /// Mood chart data plus any derived series in `options`, built from
+    /// the same points, downsampled to `options.max_points`
+    pub fn get_mood_chart_data_with_options(
+        &self,
+        from_date: Option<&str>,
+        to_date: Option<&str>,
+        group_by_activity: bool,
+        options: &ChartQueryOptions,
+    ) -> Result<MoodChartData, VisualizationError> {
+        validate_max_points(options.max_points)?;
+        let tz = chart_timezone(options.timezone.as_deref())?;
+
         let conn = self.db.get_connection();
         let conn = conn.lock();
 
@@ -132,6 +208,13 @@ impl VisualizationRepository {
             VisualizationError::StatisticsError("No ratings available".to_string())
         })?;
 
+        // Trend over the whole series; higher ratings are better
+        statistics.trend_analysis = analyze_trend(
+            &data_points,
+            (MoodRating::MAX - MoodRating::MIN) as f64,
+            ScoreDirection::HigherIsBetter,
+        );
+
         // Calculate checkins per day
         if let (Some(from), Some(to)) = (from_date, to_date) {
             if let (Ok(from_dt), Ok(to_dt)) = (
@@ -143,21 +226,541 @@ impl VisualizationRepository {
             }
         }
 
-        // Get activity breakdown if requested
-        let activity_breakdown = if group_by_activity {
-            self.get_activity_mood_breakdown(&conn, from_date, to_date)?
+        // Get activity and activity-group breakdowns if requested
+        let (activity_breakdown, group_breakdown) = if group_by_activity {
+            (
+                self.get_activity_mood_breakdown(&conn, from_date, to_date)?,
+                self.get_group_mood_breakdown(&conn, from_date, to_date)?,
+            )
         } else {
-            vec![]
+            (vec![], vec![])
         };
 
+        let derived_series = derive_series(&data_points, &options.series, tz);
+        let (data_points, derived_series) =
+            downsample_chart(data_points, derived_series, options.max_points);
+
         Ok(MoodChartData {
             data_points,
             activity_breakdown,
+            group_breakdown,
             statistics,
+            derived_series,
+        })
+    }
+
+    /// Mood, assessment and activity-group series on one shared list of days.
+    /// Mood and assessments come from the chart queries' daily means; the
+    /// range runs from the given bounds, or the first and last day with data,
+    /// and may cover at most MAX_TIMELINE_DAYS days.
+    pub fn get_combined_timeline(
+        &self,
+        from_date: Option<&str>,
+        to_date: Option<&str>,
+    ) -> Result<CombinedTimeline, VisualizationError> {
+        let daily = ChartQueryOptions {
+            series: vec![DerivedSeriesKind::DailyMean],
+            ..Default::default()
+        };
+        let daily_points = |series: Vec<DerivedSeries>| {
+            series
+                .into_iter()
+                .next()
+                .map(|s| s.data_points)
+                .unwrap_or_default()
+        };
+
+        let mood_points =
+            match self.get_mood_chart_data_with_options(from_date, to_date, false, &daily) {
+                Ok(data) => daily_points(data.derived_series),
+                Err(VisualizationError::NoData) => vec![],
+                Err(e) => return Err(e),
+            };
+
+        let mut assessment_points = Vec::new();
+        for code in self.get_assessment_type_codes()? {
+            match self.get_assessment_chart_data_with_options(&code, from_date, to_date, &daily) {
+                Ok(data) if data.assessment_type.max_score > 0 => {
+                    let max_score = data.assessment_type.max_score as f64;
+                    let mut points = daily_points(data.derived_series);
+                    for point in &mut points {
+                        point.value /= max_score;
+                    }
+                    assessment_points.push((data.assessment_type, points));
+                }
+                Ok(_) | Err(VisualizationError::NoData) => {}
+                Err(e) => return Err(e),
+            }
+        }
+
+        let tz = chart_timezone(daily.timezone.as_deref())?;
+        let group_counts = self.get_daily_group_activity_counts(from_date, to_date, tz)?;
+
+        let data_days: Vec<NaiveDate> = mood_points
+            .iter()
+            .chain(assessment_points.iter().flat_map(|(_, points)| points))
+            .filter_map(|p| timeline_day(&p.timestamp))
+            .chain(
+                group_counts
+                    .values()
+                    .flat_map(|(_, counts)| counts.keys().copied()),
+            )
+            .collect();
+        if data_days.is_empty() {
+            return Err(VisualizationError::NoData);
+        }
+        let first = from_date
+            .and_then(timeline_day)
+            .or_else(|| data_days.iter().min().copied());
+        let last = to_date
+            .and_then(timeline_day)
+            .or_else(|| data_days.iter().max().copied());
+        let (Some(first), Some(last)) = (first, last) else {
+            return Err(VisualizationError::NoData);
+        };
+        if (last - first).num_days() >= MAX_TIMELINE_DAYS {
+            return Err(VisualizationError::InvalidDateRange(format!(
+                "At most {} days can be requested at once",
+                MAX_TIMELINE_DAYS
+            )));
+        }
+        let days = days_between(first, last);
+
+        let mood = align_daily(&mood_points, &days);
+        let assessments = assessment_points
+            .into_iter()
+            .map(|(assessment_type, points)| {
+                let values = align_daily(&points, &days);
+                AssessmentTimelineSeries {
+                    mood_correlation: mood_correlation(&mood, &values),
+                    assessment_type_code: assessment_type.code.to_string(),
+                    assessment_type_name: assessment_type.name,
+                    max_score: assessment_type.max_score,
+                    values,
+                }
+            })
+            .collect();
+        let activity_groups = group_counts
+            .into_iter()
+            .map(
+                |(group_id, (group_name, counts))| ActivityGroupTimelineSeries {
+                    group_id,
+                    group_name,
+                    counts: days
+                        .iter()
+                        .map(|day| counts.get(day).copied().unwrap_or(0))
+                        .collect(),
+                },
+            )
+            .collect();
+
+        Ok(CombinedTimeline {
+            days: days
+                .iter()
+                .map(|day| day.format("%Y-%m-%d").to_string())
+                .collect(),
+            mood,
+            assessments,
+            activity_groups,
+        })
+    }
+
+    /// Mood and per-assessment statistics for `current` next to `previous`,
+    /// computed by the chart queries. Assessment types without data in
+    /// either period are left out.
+    pub fn get_period_comparison(
+        &self,
+        current: &DateBounds,
+        previous: &DateBounds,
+    ) -> Result<PeriodComparison, VisualizationError> {
+        // Only the statistics are used, which still cover every point
+        let mood_statistics = |bounds: &DateBounds| match self.get_mood_chart_data_with_options(
+            Some(&bounds.from_date),
+            Some(&bounds.to_date),
+            false,
+            &ChartQueryOptions {
+                max_points: Some(MIN_CHART_POINTS),
+                ..Default::default()
+            },
+        ) {
+            Ok(data) => Ok(Some(data.statistics)),
+            Err(VisualizationError::NoData) => Ok(None),
+            Err(e) => Err(e),
+        };
+        let mood = MoodPeriodComparison::new(mood_statistics(current)?, mood_statistics(previous)?);
+
+        let mut assessments = Vec::new();
+        for code in self.get_assessment_type_codes()? {
+            let chart_data = |bounds: &DateBounds| match self
+                .get_assessment_chart_data_with_options(
+                    &code,
+                    Some(&bounds.from_date),
+                    Some(&bounds.to_date),
+                    &ChartQueryOptions {
+                        max_points: Some(MIN_CHART_POINTS),
+                        ..Default::default()
+                    },
+                ) {
+                Ok(data) => Ok(Some(data)),
+                Err(VisualizationError::NoData) => Ok(None),
+                Err(e) => Err(e),
+            };
+            let (current_data, previous_data) = (chart_data(current)?, chart_data(previous)?);
+            let Some(assessment_type) = current_data
+                .as_ref()
+                .or(previous_data.as_ref())
+                .map(|data| data.assessment_type.clone())
+            else {
+                continue;
+            };
+            assessments.push(AssessmentPeriodComparison::new(
+                assessment_type.code.to_string(),
+                assessment_type.name,
+                current_data.map(|data| data.statistics),
+                previous_data.map(|data| data.statistics),
+            ));
+        }
+
+        Ok(PeriodComparison {
+            current_period: current.clone(),
+            previous_period: previous.clone(),
+            mood,
+            assessments,
+        })
+    }
+
+    /// Assessment chart for `assessment_code`, or the mood chart if None,
+    /// rendered at the default size in `format`. Each series is downsampled
+    /// to one point per two pixels of width.
+    pub fn render_chart(
+        &self,
+        assessment_code: Option<&str>,
+        from_date: Option<&str>,
+        to_date: Option<&str>,
+        format: ChartImageFormat,
+    ) -> Result<Vec<u8>, VisualizationError> {
+        let size = ChartSize::default();
+        let max_points = Some(size.width as usize / 2);
+        let svg = match assessment_code {
+            Some(code) => {
+                let data = self.get_assessment_chart_data_with_options(
+                    code,
+                    from_date,
+                    to_date,
+                    &ChartQueryOptions {
+                        max_points,
+                        ..Default::default()
+                    },
+                )?;
+                render_assessment_chart_svg(&data, size)
+            }
+            None => {
+                let options = ChartQueryOptions {
+                    series: vec![
+                        DerivedSeriesKind::DailyMean,
+                        DerivedSeriesKind::RollingAverage7Day,
+                    ],
+                    max_points,
+                    ..Default::default()
+                };
+                let data =
+                    self.get_mood_chart_data_with_options(from_date, to_date, false, &options)?;
+                render_mood_chart_svg(&data, size)
+            }
+        };
+        encode_chart(svg, format, system_fonts())
+    }
+
+    /// One record per local calendar day from `from_day` to `to_day`
+    /// (YYYY-MM-DD, inclusive) in `timezone`, or the system timezone.
+    /// Days without data are included with no mood and zero counts.
+    pub fn get_calendar_heatmap(
+        &self,
+        from_day: &str,
+        to_day: &str,
+        timezone: Option<&str>,
+    ) -> Result<CalendarHeatmap, VisualizationError> {
+        let parse_day = |day: &str| {
+            NaiveDate::parse_from_str(day, "%Y-%m-%d").map_err(|_| {
+                VisualizationError::InvalidDateRange(format!("{} is not a YYYY-MM-DD date", day))
+            })
+        };
+        let (first, last) = (parse_day(from_day)?, parse_day(to_day)?);
+        if last < first {
+            return Err(VisualizationError::InvalidDateRange(
+                "to_day is before from_day".to_string(),
+            ));
+        }
+        if (last - first).num_days() >= MAX_HEATMAP_DAYS {
+            return Err(VisualizationError::InvalidDateRange(format!(
+                "At most {} days can be requested at once",
+                MAX_HEATMAP_DAYS
+            )));
+        }
+        let timezone = timezone.map(str::to_string).unwrap_or_else(system_timezone);
+        let tz = chart_timezone(Some(&timezone))?;
+
+        // Local days are within a day of their UTC dates; comparing date-only
+        // bounds keeps the timestamp indexes usable whatever the stored format
+        let window = (
+            (first - chrono::Duration::days(1))
+                .format("%Y-%m-%d")
+                .to_string(),
+            (last + chrono::Duration::days(2))
+                .format("%Y-%m-%d")
+                .to_string(),
+        );
+        let mut builder = HeatmapBuilder::new(tz, first, last);
+
+        let conn = self.db.get_connection();
+        let conn = conn.lock();
+
+        let mut stmt = conn.prepare(
+            "SELECT created_at, mood_rating
+             FROM mood_checkins
+             WHERE created_at >= ?1 AND created_at < ?2",
+        )?;
+        let mut rows = stmt.query([&window.0, &window.1])?;
+        while let Some(row) = rows.next()? {
+            builder.add_checkin(&row.get::<_, String>(0)?, row.get(1)?);
+        }
+
+        let mut stmt = conn.prepare(
+            "SELECT l.logged_at, a.group_id
+             FROM activity_logs l
+             JOIN activities a ON a.id = l.activity_id
+             JOIN activity_groups g ON g.id = a.group_id
+             WHERE l.deleted_at IS NULL
+               AND g.deleted_at IS NULL
+               AND l.logged_at >= ?1 AND l.logged_at < ?2",
+        )?;
+        let mut rows = stmt.query([&window.0, &window.1])?;
+        while let Some(row) = rows.next()? {
+            builder.add_activity_log(&row.get::<_, String>(0)?, row.get(1)?);
+        }
+
+        let mut stmt = conn.prepare(
+            "SELECT completed_at
+             FROM assessment_responses
+             WHERE status = 'completed'
+               AND completed_at >= ?1 AND completed_at < ?2",
+        )?;
+        let mut rows = stmt.query([&window.0, &window.1])?;
+        while let Some(row) = rows.next()? {
+            builder.add_assessment(&row.get::<_, String>(0)?);
+        }
+
+        let mut activity_groups = Vec::new();
+        let mut stmt = conn.prepare("SELECT name FROM activity_groups WHERE id = ?")?;
+        for id in builder.logged_group_ids() {
+            activity_groups.push(HeatmapActivityGroup {
+                id,
+                name: stmt.query_row([id], |row| row.get(0))?,
+            });
+        }
+
+        Ok(CalendarHeatmap {
+            timezone,
+            days: builder.build(),
+            activity_groups,
         })
     }
 
-    /// Get activity-specific mood data for correlation analysis
+    /// Time-weighted share of days per severity level of `code`, with a
+    /// histogram of the scores and the episodes at or above
+    /// `episode_severity`. The latest result before `from_date` is carried
+    /// into the range; without `from_date` it starts at the first result.
+    /// The range ends at `to_date` or `now`, whichever is earlier.
+    pub fn get_severity_distribution(
+        &self,
+        code: &str,
+        from_date: Option<&str>,
+        to_date: Option<&str>,
+        episode_severity: SeverityLevel,
+        now: DateTime<Utc>,
+    ) -> Result<SeverityDistribution, VisualizationError> {
+        if episode_severity == SeverityLevel::Unknown {
+            return Err(VisualizationError::InvalidEpisodeSeverity);
+        }
+        let from = from_date.map(parse_date_bound).transpose()?;
+        let now = now.naive_utc();
+        let to = to_date
+            .map(parse_date_bound)
+            .transpose()?
+            .map_or(now, |to| to.min(now));
+        let bound = |at: NaiveDateTime| at.format("%Y-%m-%d %H:%M:%S").to_string();
+
+        let conn = self.db.get_connection();
+        let conn = conn.lock();
+
+        let assessment_type = self.get_assessment_type_by_code(&conn, code)?;
+        let read_result = |row: &rusqlite::Row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, i32>(1)?,
+                row.get::<_, SeverityLevel>(2)?,
+            ))
+        };
+        let to_result = |(completed_at, score, severity): (String, i32, SeverityLevel)| {
+            Some(SeverityResult {
+                completed_at: parse_chart_timestamp(&completed_at)?,
+                score,
+                severity,
+            })
+        };
+
+        let (from_bound, to_bound) = (from.map(bound), bound(to));
+        let (date_filter, params) = crate::db::query_builder::DateFilterBuilder::new()
+            .with_from_date(from_bound.as_deref(), "completed_at")
+            .with_to_date(Some(&to_bound), "completed_at")
+            .build();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT completed_at, total_score, severity_level
+             FROM assessment_responses
+             WHERE assessment_type_id = ? AND status = 'completed'
+             {}
+             ORDER BY completed_at ASC, id ASC",
+            date_filter
+        ))?;
+        let mut query_params: Vec<&dyn rusqlite::ToSql> = vec![&assessment_type.id];
+        for param in &params {
+            query_params.push(param.as_ref());
+        }
+        let results: Vec<SeverityResult> = stmt
+            .query_map(&query_params[..], read_result)?
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .filter_map(to_result)
+            .collect();
+
+        let carried_in = match from {
+            Some(from) => conn
+                .query_row(
+                    "SELECT completed_at, total_score, severity_level
+                     FROM assessment_responses
+                     WHERE assessment_type_id = ? AND status = 'completed'
+                       AND completed_at < ?
+                     ORDER BY completed_at DESC, id DESC
+                     LIMIT 1",
+                    rusqlite::params![assessment_type.id, bound(from)],
+                    read_result,
+                )
+                .optional()?
+                .and_then(to_result),
+            None => None,
+        };
+
+        if results.is_empty() && carried_in.is_none() {
+            return Err(VisualizationError::NoData);
+        }
+        let start = from.unwrap_or_else(|| results[0].completed_at);
+        if to < start {
+            return Err(VisualizationError::InvalidDateRange(
+                "The range ends before it starts".to_string(),
+            ));
+        }
+
+        // Levels this type's thresholds define, least severe first
+        let levels: Vec<SeverityLevel> = SeverityLevel::severity_order()
+            .iter()
+            .copied()
+            .filter(|level| assessment_type.thresholds.get(level.as_str()).is_some())
+            .collect();
+        let segments = carry_forward(carried_in.as_ref(), &results, start, to);
+        let tracked = tracked_days(&segments);
+        let scores: Vec<i32> = results.iter().map(|r| r.score).collect();
+        let episodes = severity_episodes(&segments, episode_severity, to);
+        let episode_days: Vec<f64> = episodes.iter().map(|e| e.days).collect();
+
+        Ok(SeverityDistribution {
+            from_date: bound(start),
+            to_date: bound(to),
+            tracked_days: tracked,
+            untracked_days: fractional_days(start, to) - tracked,
+            severity_shares: severity_shares(&segments, &levels),
+            score_histogram: score_histogram(
+                &scores,
+                assessment_type.min_score,
+                assessment_type.max_score,
+            ),
+            episode_severity,
+            longest_episode_days: episode_days.iter().copied().reduce(f64::max),
+            average_episode_days: (!episode_days.is_empty())
+                .then(|| episode_days.iter().sum::<f64>() / episode_days.len() as f64),
+            episodes,
+            assessment_type,
+        })
+    }
+
+    /// Codes of all assessment types, in display order
+    fn get_assessment_type_codes(&self) -> Result<Vec<String>, VisualizationError> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock();
+
+        let mut stmt = conn.prepare("SELECT code FROM assessment_types ORDER BY id")?;
+        let codes = stmt
+            .query_map([], |row| row.get(0))?
+            .collect::<Result<Vec<String>, _>>()?;
+        Ok(codes)
+    }
+
+    /// Activity logs per local day in `tz` for each activity group with any
+    /// in the range, leaving out deleted logs and deleted groups
+    fn get_daily_group_activity_counts(
+        &self,
+        from_date: Option<&str>,
+        to_date: Option<&str>,
+        tz: Tz,
+    ) -> Result<GroupDailyCounts, VisualizationError> {
+        let conn = self.db.get_connection();
+        let conn = conn.lock();
+
+        // logged_at may be RFC 3339; normalise before comparing with the bounds
+        let (date_filter, params) = crate::db::query_builder::DateFilterBuilder::new()
+            .with_from_date(from_date, "datetime(l.logged_at)")
+            .with_to_date(to_date, "datetime(l.logged_at)")
+            .build();
+
+        let query = format!(
+            "SELECT g.id, g.name, datetime(l.logged_at)
+             FROM activity_logs l
+             JOIN activities a ON a.id = l.activity_id
+             JOIN activity_groups g ON g.id = a.group_id
+             WHERE l.deleted_at IS NULL
+               AND g.deleted_at IS NULL
+               AND datetime(l.logged_at) IS NOT NULL
+             {}",
+            date_filter
+        );
+
+        let mut stmt = conn.prepare(&query)?;
+        let query_params: Vec<&dyn rusqlite::ToSql> = params.iter().map(|p| p.as_ref()).collect();
+        let rows = stmt
+            .query_map(&query_params[..], |row| {
+                Ok((
+                    row.get::<_, i32>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                ))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut groups = GroupDailyCounts::new();
+        for (group_id, group_name, logged_at) in rows {
+            if let Some(day) = local_day(&logged_at, tz) {
+                *groups
+                    .entry(group_id)
+                    .or_insert_with(|| (group_name, BTreeMap::new()))
+                    .1
+                    .entry(day)
+                    .or_insert(0) += 1;
+            }
+        }
+        Ok(groups)
+    }
+
+    /// Get activity-specific mood data for correlation analysis.
+    /// Deleted activities and activities of deleted groups are left out.
     fn get_activity_mood_breakdown(
         &self,
         conn: &rusqlite::Connection,
@@ -176,9 +779,10 @@ impl VisualizationRepository {
                 AVG(mc.mood_rating) as avg_mood,
                 COUNT(mc.id) as checkin_count
              FROM activities a
+             JOIN activity_groups ag ON ag.id = a.group_id
              JOIN mood_checkin_activities mca ON a.id = mca.activity_id
              JOIN mood_checkins mc ON mca.mood_checkin_id = mc.id
-             WHERE a.deleted_at IS NULL
+             WHERE a.deleted_at IS NULL AND ag.deleted_at IS NULL
              {}
              GROUP BY a.id, a.group_id, a.name, a.color, a.icon
              HAVING checkin_count >= 2
@@ -213,6 +817,67 @@ impl VisualizationRepository {
         Ok(breakdown)
     }
 
+    /// Mood per activity group against the check-ins and days without it,
+    /// with per-activity drill-down. Deleted activities and activities of
+    /// deleted groups are left out, as in the activity breakdown; check-ins
+    /// that only had those count towards every baseline.
+    fn get_group_mood_breakdown(
+        &self,
+        conn: &rusqlite::Connection,
+        from_date: Option<&str>,
+        to_date: Option<&str>,
+    ) -> Result<Vec<ActivityGroupMoodData>, VisualizationError> {
+        let date_filter = || {
+            crate::db::query_builder::DateFilterBuilder::new()
+                .with_from_date(from_date, "mc.created_at")
+                .with_to_date(to_date, "mc.created_at")
+        };
+
+        let mut builder = GroupBreakdownBuilder::new();
+
+        // Every check-in, for the baselines
+        let (where_clause, params) = date_filter().build_where();
+        let query_params: Vec<&dyn rusqlite::ToSql> = params.iter().map(|p| p.as_ref()).collect();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT mc.id, mc.created_at, mc.mood_rating
+             FROM mood_checkins mc
+             {}",
+            where_clause
+        ))?;
+        let mut rows = stmt.query(&query_params[..])?;
+        while let Some(row) = rows.next()? {
+            builder.add_checkin(row.get(0)?, &row.get::<_, String>(1)?, row.get(2)?);
+        }
+
+        let (and_clause, params) = date_filter().build();
+        let query_params: Vec<&dyn rusqlite::ToSql> = params.iter().map(|p| p.as_ref()).collect();
+        let mut stmt = conn.prepare(&format!(
+            "SELECT mca.mood_checkin_id, a.id, a.group_id, a.name, a.color, a.icon, ag.name
+             FROM mood_checkin_activities mca
+             JOIN mood_checkins mc ON mc.id = mca.mood_checkin_id
+             JOIN activities a ON a.id = mca.activity_id
+             JOIN activity_groups ag ON ag.id = a.group_id
+             WHERE a.deleted_at IS NULL AND ag.deleted_at IS NULL
+             {}",
+            and_clause
+        ))?;
+        let mut rows = stmt.query(&query_params[..])?;
+        while let Some(row) = rows.next()? {
+            let activity = Activity {
+                id: row.get(1)?,
+                group_id: row.get(2)?,
+                name: row.get(3)?,
+                color: row.get(4)?,
+                icon: row.get(5)?,
+                created_at: String::new(), // Not needed for visualization
+                deleted_at: None,
+            };
+            builder.add_activity(row.get(0)?, activity, &row.get::<_, String>(6)?);
+        }
+
+        Ok(builder.build())
+    }
+
     /// Get assessment type by code
     fn get_assessment_type_by_code(
         &self,
@@ -246,7 +911,7 @@ impl VisualizationRepository {
     fn calculate_statistics(
         &self,
         data_points: &[ChartDataPoint],
-        _assessment_type: &AssessmentType,
+        assessment_type: &AssessmentType,
     ) -> Result<ChartStatistics, VisualizationError> {
         if data_points.is_empty() {
             return Err(VisualizationError::StatisticsError(
@@ -269,16 +934,19 @@ impl VisualizationRepository {
         let sum: f64 = values.iter().sum();
         let average = sum / values.len() as f64;
 
-        // Calculate trend
-        let first_score = values.first().copied().unwrap_or(0.0);
-        let last_score = values.last().copied().unwrap_or(0.0);
-        let trend = calculate_trend(first_score, last_score);
+        // Trend over the whole series; lower scores are better
+        let trend_analysis = analyze_trend(
+            data_points,
+            (assessment_type.max_score - assessment_type.min_score) as f64,
+            ScoreDirection::LowerIsBetter,
+        );
 
         Ok(ChartStatistics {
             min,
             max,
             average,
-            trend,
+            trend: trend_analysis.direction,
+            trend_analysis,
             total_assessments: data_points.len() as i32,
         })
     }
@@ -319,3 +987,45 @@ impl VisualizationRepository {
         Ok(thresholds)
     }
 }
+
+/// A range bound: a stored timestamp, or a YYYY-MM-DD date at midnight
+fn parse_date_bound(bound: &str) -> Result<NaiveDateTime, VisualizationError> {
+    parse_chart_timestamp(bound)
+        .or_else(|| {
+            NaiveDate::parse_from_str(bound, "%Y-%m-%d")
+                .ok()
+                .and_then(|day| day.and_hms_opt(0, 0, 0))
+        })
+        .ok_or_else(|| VisualizationError::InvalidDateRange(format!("{} is not a date", bound)))
+}
+
+/// The named timezone, or the system timezone
+fn chart_timezone(timezone: Option<&str>) -> Result<Tz, VisualizationError> {
+    let timezone = timezone.map(str::to_string).unwrap_or_else(system_timezone);
+    parse_timezone(&timezone).map_err(|_| VisualizationError::InvalidTimezone(timezone))
+}
+
+fn validate_max_points(max_points: Option<usize>) -> Result<(), VisualizationError> {
+    match max_points {
+        Some(value) if value < MIN_CHART_POINTS => Err(VisualizationError::InvalidMaxPoints {
+            value,
+            min: MIN_CHART_POINTS,
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// Downsample the raw and derived series of a chart to `max_points` each
+fn downsample_chart(
+    data_points: Vec<ChartDataPoint>,
+    mut derived_series: Vec<DerivedSeries>,
+    max_points: Option<usize>,
+) -> (Vec<ChartDataPoint>, Vec<DerivedSeries>) {
+    let Some(max_points) = max_points else {
+        return (data_points, derived_series);
+    };
+    for series in &mut derived_series {
+        series.data_points = downsample_lttb(&series.data_points, max_points);
+    }
+    (downsample_lttb(&data_points, max_points), derived_series)
+}
@@ -0,0 +1,288 @@
+// Derived chart series
+//
+// Smoothed views of a raw series are built from daily means, so a day with
+// five check-ins counts as much as a day with one. Days are local calendar
+// days in the chart's timezone, the same days the heatmap uses. Windows are measured in
+// calendar days rather than points: a rolling average after a two-week gap
+// covers only what falls inside its window, and the exponential moving
+// average decays once per elapsed day, not once per point. Days without data
+// get no derived point.
+
+use std::collections::{BTreeMap, VecDeque};
+
+use chrono::{Duration, NaiveDate};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+
+use super::heatmap::local_day;
+use super::models::ChartDataPoint;
+
+/// A series derived from the raw data points of a chart
+#[derive(Serialize, Deserialize, specta::Type, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum DerivedSeriesKind {
+    DailyMean,
+    RollingAverage7Day,
+    RollingAverage30Day,
+    ExponentialMovingAverage7Day,
+    ExponentialMovingAverage30Day,
+}
+
+impl DerivedSeriesKind {
+    /// Stable series name for chart legends and lookups
+    pub fn name(&self) -> &'static str {
+        match self {
+            DerivedSeriesKind::DailyMean => "daily_mean",
+            DerivedSeriesKind::RollingAverage7Day => "rolling_average_7d",
+            DerivedSeriesKind::RollingAverage30Day => "rolling_average_30d",
+            DerivedSeriesKind::ExponentialMovingAverage7Day => "ema_7d",
+            DerivedSeriesKind::ExponentialMovingAverage30Day => "ema_30d",
+        }
+    }
+}
+
+/// A named series next to a chart's raw data points
+#[derive(Serialize, Deserialize, specta::Type, Clone, Debug)]
+pub struct DerivedSeries {
+    pub kind: DerivedSeriesKind,
+    pub name: String,
+    /// One point per local day with data, timestamped at the start of the day
+    pub data_points: Vec<ChartDataPoint>,
+}
+
+/// Build each requested series once, in the order first requested,
+/// with days in `tz`
+pub fn derive_series(
+    points: &[ChartDataPoint],
+    kinds: &[DerivedSeriesKind],
+    tz: Tz,
+) -> Vec<DerivedSeries> {
+    if kinds.is_empty() {
+        return vec![];
+    }
+
+    let daily = daily_means(points, tz);
+    let mut derived: Vec<DerivedSeries> = Vec::with_capacity(kinds.len());
+    for kind in kinds {
+        if derived.iter().any(|series| series.kind == *kind) {
+            continue;
+        }
+        let values = match kind {
+            DerivedSeriesKind::DailyMean => daily.clone(),
+            DerivedSeriesKind::RollingAverage7Day => rolling_average(&daily, 7),
+            DerivedSeriesKind::RollingAverage30Day => rolling_average(&daily, 30),
+            DerivedSeriesKind::ExponentialMovingAverage7Day => {
+                exponential_moving_average(&daily, 7)
+            }
+            DerivedSeriesKind::ExponentialMovingAverage30Day => {
+                exponential_moving_average(&daily, 30)
+            }
+        };
+        derived.push(DerivedSeries {
+            kind: *kind,
+            name: kind.name().to_string(),
+            data_points: values
+                .into_iter()
+                .map(|(day, value)| ChartDataPoint {
+                    timestamp: day.format("%Y-%m-%d 00:00:00").to_string(),
+                    value,
+                    label: None,
+                })
+                .collect(),
+        });
+    }
+    derived
+}
+
+/// Mean value per local day, in date order; unparseable timestamps are skipped
+fn daily_means(points: &[ChartDataPoint], tz: Tz) -> Vec<(NaiveDate, f64)> {
+    let mut days: BTreeMap<NaiveDate, (f64, u32)> = BTreeMap::new();
+    for point in points.iter().filter(|p| p.value.is_finite()) {
+        if let Some(date) = local_day(&point.timestamp, tz) {
+            let day = days.entry(date).or_insert((0.0, 0));
+            day.0 += point.value;
+            day.1 += 1;
+        }
+    }
+    days.into_iter()
+        .map(|(date, (sum, count))| (date, sum / count as f64))
+        .collect()
+}
+
+/// Mean of the daily means within the `window_days` days ending on each day
+fn rolling_average(daily: &[(NaiveDate, f64)], window_days: i64) -> Vec<(NaiveDate, f64)> {
+    let mut window: VecDeque<(NaiveDate, f64)> = VecDeque::new();
+    let mut sum = 0.0;
+    daily
+        .iter()
+        .map(|&(date, value)| {
+            window.push_back((date, value));
+            sum += value;
+            let first_day = date - Duration::days(window_days - 1);
+            while let Some(&(oldest, old_value)) = window.front() {
+                if oldest >= first_day {
+                    break;
+                }
+                sum -= old_value;
+                window.pop_front();
+            }
+            (date, sum / window.len() as f64)
+        })
+        .collect()
+}
+
+/// EMA over the daily means with the usual 2 / (span + 1) smoothing per day.
+/// The previous average decays once for every day elapsed, so after a gap
+/// the next value pulls it further than on consecutive days.
+fn exponential_moving_average(daily: &[(NaiveDate, f64)], span_days: i64) -> Vec<(NaiveDate, f64)> {
+    let retain_per_day = 1.0 - 2.0 / (span_days as f64 + 1.0);
+    let mut previous: Option<(NaiveDate, f64)> = None;
+    daily
+        .iter()
+        .map(|&(date, value)| {
+            let average = match previous {
+                None => value,
+                Some((last_date, last_average)) => {
+                    let retain = retain_per_day.powi((date - last_date).num_days() as i32);
+                    retain * last_average + (1.0 - retain) * value
+                }
+            };
+            previous = Some((date, average));
+            (date, average)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono_tz::UTC;
+
+    fn point(timestamp: &str, value: f64) -> ChartDataPoint {
+        ChartDataPoint {
+            timestamp: timestamp.to_string(),
+            value,
+            label: None,
+        }
+    }
+
+    fn values(series: &DerivedSeries) -> Vec<(String, f64)> {
+        series
+            .data_points
+            .iter()
+            .map(|p| (p.timestamp.clone(), p.value))
+            .collect()
+    }
+
+    #[test]
+    fn test_daily_mean_groups_by_day() {
+        let points = vec![
+            point("2025-03-01 08:00:00", 2.0),
+            point("2025-03-01 20:00:00", 4.0),
+            point("2025-03-01 21:00:00", 6.0),
+            point("2025-03-03T09:00:00Z", 5.0),
+            point("not a timestamp", 1.0),
+        ];
+        let derived = derive_series(&points, &[DerivedSeriesKind::DailyMean], UTC);
+        assert_eq!(derived.len(), 1);
+        assert_eq!(derived[0].name, "daily_mean");
+        assert_eq!(
+            values(&derived[0]),
+            vec![
+                ("2025-03-01 00:00:00".to_string(), 4.0),
+                ("2025-03-03 00:00:00".to_string(), 5.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rolling_average_window_is_in_days() {
+        // Days 1, 2, 3 then a gap until day 12
+        let points = vec![
+            point("2025-03-01 09:00:00", 1.0),
+            point("2025-03-02 09:00:00", 2.0),
+            point("2025-03-03 09:00:00", 3.0),
+            point("2025-03-07 09:00:00", 6.0),
+            point("2025-03-12 09:00:00", 7.0),
+        ];
+        let derived = derive_series(&points, &[DerivedSeriesKind::RollingAverage7Day], UTC);
+        let rolled: Vec<f64> = derived[0].data_points.iter().map(|p| p.value).collect();
+        // Mar 7 still sees Mar 1-3; Mar 12 sees only Mar 7 and itself
+        assert_eq!(rolled, vec![1.0, 1.5, 2.0, 3.0, 6.5]);
+        assert_eq!(derived[0].data_points[4].timestamp, "2025-03-12 00:00:00");
+    }
+
+    #[test]
+    fn test_rolling_average_weights_days_equally() {
+        // Many check-ins on one day don't outweigh a single one the next
+        let mut points: Vec<ChartDataPoint> = (0..9)
+            .map(|h| point(&format!("2025-03-01 1{h}:00:00"), 2.0))
+            .collect();
+        points.push(point("2025-03-02 10:00:00", 6.0));
+        let derived = derive_series(&points, &[DerivedSeriesKind::RollingAverage30Day], UTC);
+        assert_eq!(derived[0].data_points[1].value, 4.0);
+    }
+
+    #[test]
+    fn test_ema_decays_across_gaps() {
+        let consecutive = vec![
+            point("2025-03-01 09:00:00", 0.0),
+            point("2025-03-02 09:00:00", 8.0),
+        ];
+        let gapped = vec![
+            point("2025-03-01 09:00:00", 0.0),
+            point("2025-03-11 09:00:00", 8.0),
+        ];
+        let kinds = [DerivedSeriesKind::ExponentialMovingAverage7Day];
+        let next_day = derive_series(&consecutive, &kinds, UTC)[0].data_points[1].value;
+        let after_gap = derive_series(&gapped, &kinds, UTC)[0].data_points[1].value;
+
+        // alpha = 2 / 8 for a 7-day span
+        assert!((next_day - 2.0).abs() < 1e-9);
+        assert!((after_gap - 8.0 * (1.0 - 0.75f64.powi(10))).abs() < 1e-9);
+        assert!(after_gap > next_day);
+    }
+
+    #[test]
+    fn test_each_kind_derived_once() {
+        let points = vec![point("2025-03-01 09:00:00", 3.0)];
+        let derived = derive_series(
+            &points,
+            &[
+                DerivedSeriesKind::ExponentialMovingAverage30Day,
+                DerivedSeriesKind::DailyMean,
+                DerivedSeriesKind::ExponentialMovingAverage30Day,
+            ],
+            UTC,
+        );
+        let names: Vec<&str> = derived.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["ema_30d", "daily_mean"]);
+        assert!(derive_series(&points, &[], UTC).is_empty());
+    }
+
+    #[test]
+    fn test_daily_mean_uses_local_days() {
+        // 23:30 UTC is 18:30 the same day in New York, and the next
+        // morning in Tokyo
+        let points = vec![
+            point("2025-03-01 23:30:00", 2.0),
+            point("2025-03-02 01:00:00", 4.0),
+        ];
+        let kinds = [DerivedSeriesKind::DailyMean];
+        let new_york: Tz = "America/New_York".parse().unwrap();
+        let tokyo: Tz = "Asia/Tokyo".parse().unwrap();
+
+        let evening = derive_series(&points, &kinds, new_york);
+        assert_eq!(
+            values(&evening[0]),
+            vec![("2025-03-01 00:00:00".to_string(), 3.0)]
+        );
+        let morning = derive_series(&points, &kinds, tokyo);
+        assert_eq!(
+            values(&morning[0]),
+            vec![("2025-03-02 00:00:00".to_string(), 3.0)]
+        );
+        let utc = derive_series(&points, &kinds, UTC);
+        assert_eq!(utc[0].data_points.len(), 2);
+    }
+}
@@ -0,0 +1,206 @@
+// Combined timeline
+//
+// Mood, assessment and activity data on one shared list of days, so a single
+// chart can overlay them. Mood and assessments are daily means (assessments
+// as a share of their maximum score); days without data stay None rather
+// than being interpolated. Activity counts are 0 on days without logs.
+
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use super::models::ChartDataPoint;
+use super::trend::parse_chart_timestamp;
+
+/// Fewer days with both mood and an assessment give no correlation
+pub const MIN_CORRELATION_DAYS: usize = 3;
+
+/// Longest range one timeline may cover (about ten years)
+pub const MAX_TIMELINE_DAYS: i64 = 3660;
+
+/// Aligned daily series for a date range
+#[derive(Serialize, Deserialize, specta::Type, Clone, Debug)]
+pub struct CombinedTimeline {
+    pub days: Vec<String>,      // YYYY-MM-DD, every day in the range
+    pub mood: Vec<Option<f64>>, // Daily mean rating, aligned with days
+    pub assessments: Vec<AssessmentTimelineSeries>,
+    pub activity_groups: Vec<ActivityGroupTimelineSeries>,
+}
+
+/// Daily assessment scores normalised to the type's maximum (0-1)
+#[derive(Serialize, Deserialize, specta::Type, Clone, Debug)]
+pub struct AssessmentTimelineSeries {
+    pub assessment_type_code: String,
+    pub assessment_type_name: String,
+    pub max_score: i32,
+    pub values: Vec<Option<f64>>, // Aligned with days
+    /// Against daily mood; None below MIN_CORRELATION_DAYS or if either side never varies
+    pub mood_correlation: Option<MoodCorrelation>,
+}
+
+/// Correlation between daily mood and a daily assessment score
+#[derive(Serialize, Deserialize, specta::Type, Clone, Debug, PartialEq)]
+pub struct MoodCorrelation {
+    pub pearson: f64,
+    pub spearman: f64,
+    pub paired_days: i32, // Days with both a mood check-in and this assessment
+}
+
+/// Activity logs per day for one activity group
+#[derive(Serialize, Deserialize, specta::Type, Clone, Debug)]
+pub struct ActivityGroupTimelineSeries {
+    pub group_id: i32,
+    pub group_name: String,
+    pub counts: Vec<i32>, // Aligned with days
+}
+
+/// Calendar day of a date bound or chart timestamp
+pub fn timeline_day(timestamp: &str) -> Option<NaiveDate> {
+    parse_chart_timestamp(timestamp)
+        .map(|at| at.date())
+        .or_else(|| NaiveDate::parse_from_str(timestamp.get(..10)?, "%Y-%m-%d").ok())
+}
+
+/// Every day from `first` to `last` inclusive
+pub fn days_between(first: NaiveDate, last: NaiveDate) -> Vec<NaiveDate> {
+    first.iter_days().take_while(|day| *day <= last).collect()
+}
+
+/// Place daily points on `days`; days without a point are None
+pub fn align_daily(points: &[ChartDataPoint], days: &[NaiveDate]) -> Vec<Option<f64>> {
+    let by_day: HashMap<NaiveDate, f64> = points
+        .iter()
+        .filter_map(|p| Some((timeline_day(&p.timestamp)?, p.value)))
+        .collect();
+    days.iter().map(|day| by_day.get(day).copied()).collect()
+}
+
+/// Pearson and Spearman correlation over the days where both series have a value
+pub fn mood_correlation(mood: &[Option<f64>], scores: &[Option<f64>]) -> Option<MoodCorrelation> {
+    let (xs, ys): (Vec<f64>, Vec<f64>) = mood
+        .iter()
+        .zip(scores)
+        .filter_map(|(m, s)| Some(((*m)?, (*s)?)))
+        .unzip();
+    if xs.len() < MIN_CORRELATION_DAYS {
+        return None;
+    }
+
+    Some(MoodCorrelation {
+        pearson: pearson(&xs, &ys)?,
+        spearman: pearson(&ranks(&xs), &ranks(&ys))?,
+        paired_days: xs.len() as i32,
+    })
+}
+
+/// None when either series is constant
+fn pearson(xs: &[f64], ys: &[f64]) -> Option<f64> {
+    let n = xs.len() as f64;
+    let mean_x = xs.iter().sum::<f64>() / n;
+    let mean_y = ys.iter().sum::<f64>() / n;
+    let (mut covariance, mut var_x, mut var_y) = (0.0, 0.0, 0.0);
+    for (x, y) in xs.iter().zip(ys) {
+        let (dx, dy) = (x - mean_x, y - mean_y);
+        covariance += dx * dy;
+        var_x += dx * dx;
+        var_y += dy * dy;
+    }
+    if var_x <= f64::EPSILON || var_y <= f64::EPSILON {
+        return None;
+    }
+    Some((covariance / (var_x * var_y).sqrt()).clamp(-1.0, 1.0))
+}
+
+/// 1-based ranks, ties sharing their average rank
+fn ranks(values: &[f64]) -> Vec<f64> {
+    let mut order: Vec<usize> = (0..values.len()).collect();
+    order.sort_by(|&a, &b| values[a].total_cmp(&values[b]));
+
+    let mut ranks = vec![0.0; values.len()];
+    let mut start = 0;
+    while start < order.len() {
+        let mut end = start + 1;
+        while end < order.len() && values[order[end]] == values[order[start]] {
+            end += 1;
+        }
+        let rank = (start + end + 1) as f64 / 2.0;
+        for &index in &order[start..end] {
+            ranks[index] = rank;
+        }
+        start = end;
+    }
+    ranks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_align_daily_leaves_gaps_empty() {
+        let days = days_between(day("2025-03-01"), day("2025-03-04"));
+        assert_eq!(days.len(), 4);
+
+        let points = vec![
+            ChartDataPoint {
+                timestamp: "2025-03-01 00:00:00".to_string(),
+                value: 3.0,
+                label: None,
+            },
+            ChartDataPoint {
+                timestamp: "2025-03-04 00:00:00".to_string(),
+                value: 5.5,
+                label: None,
+            },
+        ];
+        assert_eq!(
+            align_daily(&points, &days),
+            vec![Some(3.0), None, None, Some(5.5)]
+        );
+    }
+
+    #[test]
+    fn test_timeline_day_accepts_date_bounds() {
+        assert_eq!(timeline_day("2025-03-01"), Some(day("2025-03-01")));
+        assert_eq!(timeline_day("2025-03-01 23:59:59"), Some(day("2025-03-01")));
+        assert_eq!(
+            timeline_day("2025-03-01T10:00:00Z"),
+            Some(day("2025-03-01"))
+        );
+        assert_eq!(timeline_day("March"), None);
+    }
+
+    #[test]
+    fn test_correlation_of_opposite_series() {
+        // Higher scores on lower-mood days, but not linearly
+        let mood = vec![Some(6.0), Some(5.0), None, Some(3.0), Some(1.0)];
+        let scores = vec![Some(0.1), Some(0.2), Some(0.9), Some(0.3), Some(0.95)];
+        let correlation = mood_correlation(&mood, &scores).unwrap();
+        assert_eq!(correlation.paired_days, 4);
+        assert!((correlation.spearman - -1.0).abs() < 1e-9);
+        assert!(correlation.pearson < -0.8 && correlation.pearson > -1.0);
+    }
+
+    #[test]
+    fn test_correlation_needs_varying_paired_days() {
+        let scores = vec![Some(0.2), Some(0.4), Some(0.6)];
+        assert_eq!(
+            mood_correlation(&[Some(4.0), None, Some(5.0)], &scores),
+            None
+        );
+        assert_eq!(
+            mood_correlation(&[Some(4.0), Some(4.0), Some(4.0)], &scores),
+            None
+        );
+    }
+
+    #[test]
+    fn test_ranks_share_ties() {
+        assert_eq!(ranks(&[3.0, 1.0, 3.0, 2.0]), vec![3.5, 1.0, 3.5, 2.0]);
+    }
+}
@@ -0,0 +1,360 @@
+// Trend detection for chart series
+//
+// The slope is the Theil–Sen estimator (median of all pairwise slopes), so a
+// single outlier can't flip the direction. Whether the series moves at all is
+// judged with the Mann–Kendall test, and the fitted change must also be large
+// enough relative to the score scale to matter.
+
+use chrono::{DateTime, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+
+use super::models::{ChartDataPoint, TrendDirection};
+
+/// Fewer points than this never show a trend
+pub const MIN_TREND_POINTS: usize = 4;
+
+/// The points must cover at least this many days
+pub const MIN_TREND_SPAN_DAYS: f64 = 14.0;
+
+/// Mann–Kendall confidence needed to call a series improving or worsening
+pub const TREND_CONFIDENCE_LEVEL: f64 = 0.9;
+
+/// The fitted change over the whole span must be at least this share of the score scale
+pub const MIN_TREND_CHANGE_FRACTION: f64 = 0.1;
+
+/// Longer series are averaged into this many time-ordered buckets first,
+/// keeping the pairwise work bounded
+const MAX_TREND_POINTS: usize = 1000;
+
+const DAYS_PER_WEEK: f64 = 7.0;
+
+/// Trend of a series over its whole range
+#[derive(Serialize, Deserialize, specta::Type, Clone, Debug, PartialEq)]
+pub struct TrendAnalysis {
+    pub direction: TrendDirection,
+    /// Theil–Sen slope in score points per week; None without enough data
+    pub slope_per_week: Option<f64>,
+    /// Confidence (0-1) that the series moves at all, from the Mann–Kendall test
+    pub confidence: f64,
+    /// Whether there were enough points over enough time to judge a trend
+    pub sufficient_data: bool,
+    pub points_used: i32,
+}
+
+impl Default for TrendAnalysis {
+    fn default() -> Self {
+        TrendAnalysis::insufficient(0)
+    }
+}
+
+impl TrendAnalysis {
+    fn insufficient(points_used: usize) -> Self {
+        TrendAnalysis {
+            direction: TrendDirection::Stable,
+            slope_per_week: None,
+            confidence: 0.0,
+            sufficient_data: false,
+            points_used: points_used as i32,
+        }
+    }
+}
+
+/// Which way is better on a scale
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScoreDirection {
+    /// Symptom scores (PHQ-9, GAD-7, ...)
+    LowerIsBetter,
+    /// Mood ratings
+    HigherIsBetter,
+}
+
+/// Parse a chart timestamp ("YYYY-MM-DD HH:MM:SS" or RFC 3339)
+pub fn parse_chart_timestamp(timestamp: &str) -> Option<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(timestamp, "%Y-%m-%d %H:%M:%S")
+        .or_else(|_| NaiveDateTime::parse_from_str(timestamp, "%Y-%m-%dT%H:%M:%S"))
+        .ok()
+        .or_else(|| {
+            DateTime::parse_from_rfc3339(timestamp)
+                .ok()
+                .map(|dt| dt.naive_utc())
+        })
+}
+
+/// Analyze the trend of `points` on a scale `scale_range` points wide
+pub fn analyze_trend(
+    points: &[ChartDataPoint],
+    scale_range: f64,
+    direction: ScoreDirection,
+) -> TrendAnalysis {
+    let mut series: Vec<(f64, f64)> = points
+        .iter()
+        .filter_map(|p| {
+            let at = parse_chart_timestamp(&p.timestamp)?;
+            Some((at.and_utc().timestamp() as f64 / 86_400.0, p.value))
+        })
+        .filter(|(_, value)| value.is_finite())
+        .collect();
+    series.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let points_used = series.len();
+    let span_days = match (series.first(), series.last()) {
+        (Some(first), Some(last)) => last.0 - first.0,
+        _ => 0.0,
+    };
+    if points_used < MIN_TREND_POINTS || span_days < MIN_TREND_SPAN_DAYS {
+        return TrendAnalysis::insufficient(points_used);
+    }
+
+    let series = downsample(series);
+    let Some(slope_per_day) = theil_sen_slope(&series) else {
+        return TrendAnalysis::insufficient(points_used);
+    };
+    let confidence = mann_kendall_confidence(&series);
+
+    let change = slope_per_day * span_days;
+    let meaningful = confidence >= TREND_CONFIDENCE_LEVEL
+        && change.abs() >= MIN_TREND_CHANGE_FRACTION * scale_range;
+    let trend = match (meaningful, change < 0.0, direction) {
+        (false, _, _) => TrendDirection::Stable,
+        (true, true, ScoreDirection::LowerIsBetter)
+        | (true, false, ScoreDirection::HigherIsBetter) => TrendDirection::Improving,
+        (true, _, _) => TrendDirection::Worsening,
+    };
+
+    TrendAnalysis {
+        direction: trend,
+        slope_per_week: Some(slope_per_day * DAYS_PER_WEEK),
+        confidence,
+        sufficient_data: true,
+        points_used: points_used as i32,
+    }
+}
+
+/// Average a time-sorted series into at most MAX_TREND_POINTS buckets
+fn downsample(series: Vec<(f64, f64)>) -> Vec<(f64, f64)> {
+    if series.len() <= MAX_TREND_POINTS {
+        return series;
+    }
+    let chunk = series.len().div_ceil(MAX_TREND_POINTS);
+    series
+        .chunks(chunk)
+        .map(|bucket| {
+            let n = bucket.len() as f64;
+            let (x, y) = bucket
+                .iter()
+                .fold((0.0, 0.0), |(x, y), p| (x + p.0, y + p.1));
+            (x / n, y / n)
+        })
+        .collect()
+}
+
+/// Median of the slopes between every pair of points at different times
+fn theil_sen_slope(series: &[(f64, f64)]) -> Option<f64> {
+    let mut slopes = Vec::with_capacity(series.len() * (series.len() - 1) / 2);
+    for (i, a) in series.iter().enumerate() {
+        for b in &series[i + 1..] {
+            if b.0 > a.0 {
+                slopes.push((b.1 - a.1) / (b.0 - a.0));
+            }
+        }
+    }
+    if slopes.is_empty() {
+        return None;
+    }
+
+    let (len, mid) = (slopes.len(), slopes.len() / 2);
+    let (lower, median, _) = slopes.select_nth_unstable_by(mid, |a, b| a.total_cmp(b));
+    let median = *median;
+    if len % 2 == 1 {
+        Some(median)
+    } else {
+        let below = lower.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        Some((below + median) / 2.0)
+    }
+}
+
+/// 1 - p of the two-sided Mann–Kendall test, with the tie correction for
+/// repeated values (common with integer scores)
+fn mann_kendall_confidence(series: &[(f64, f64)]) -> f64 {
+    let n = series.len() as f64;
+    let mut s = 0.0;
+    for (i, a) in series.iter().enumerate() {
+        for b in &series[i + 1..] {
+            if b.0 > a.0 && b.1 != a.1 {
+                s += (b.1 - a.1).signum();
+            }
+        }
+    }
+
+    let mut values: Vec<f64> = series.iter().map(|p| p.1).collect();
+    values.sort_by(|a, b| a.total_cmp(b));
+    let ties: f64 = values
+        .chunk_by(|a, b| a == b)
+        .map(|group| {
+            let t = group.len() as f64;
+            t * (t - 1.0) * (2.0 * t + 5.0)
+        })
+        .sum();
+    let variance = (n * (n - 1.0) * (2.0 * n + 5.0) - ties) / 18.0;
+    if variance <= 0.0 || s == 0.0 {
+        return 0.0;
+    }
+
+    // Continuity correction
+    let z = (s.abs() - 1.0) / variance.sqrt();
+    let p = 2.0 * (1.0 - normal_cdf(z));
+    (1.0 - p).clamp(0.0, 1.0)
+}
+
+fn normal_cdf(z: f64) -> f64 {
+    0.5 * (1.0 + erf(z / std::f64::consts::SQRT_2))
+}
+
+/// Abramowitz & Stegun 7.1.26 (absolute error below 1.5e-7)
+fn erf(x: f64) -> f64 {
+    let sign = x.signum();
+    let x = x.abs();
+    let t = 1.0 / (1.0 + 0.327_591_1 * x);
+    let poly = t
+        * (0.254_829_592
+            + t * (-0.284_496_736
+                + t * (1.421_413_741 + t * (-1.453_152_027 + t * 1.061_405_429))));
+    sign * (1.0 - poly * (-x * x).exp())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// One point every `days` days starting 2025-01-01
+    fn series(values: &[f64], days: i64) -> Vec<ChartDataPoint> {
+        let start = NaiveDateTime::parse_from_str("2025-01-01 09:00:00", "%Y-%m-%d %H:%M:%S")
+            .expect("Invalid start");
+        values
+            .iter()
+            .enumerate()
+            .map(|(i, value)| ChartDataPoint {
+                timestamp: (start + chrono::Duration::days(i as i64 * days))
+                    .format("%Y-%m-%d %H:%M:%S")
+                    .to_string(),
+                value: *value,
+                label: None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_decreasing_scores_are_improving() {
+        let trend = analyze_trend(
+            &series(&[20.0, 18.0, 15.0, 12.0, 10.0], 7),
+            27.0,
+            ScoreDirection::LowerIsBetter,
+        );
+        assert_eq!(trend.direction, TrendDirection::Improving);
+        assert!(trend.sufficient_data);
+        // Median of the ten pairwise slopes (-2.67 and -2.5 in the middle)
+        assert!((trend.slope_per_week.unwrap() - -2.583).abs() < 0.01);
+        assert!(trend.confidence > 0.95);
+    }
+
+    #[test]
+    fn test_rising_mood_is_improving() {
+        let trend = analyze_trend(
+            &series(&[2.0, 3.0, 3.0, 4.0, 5.0, 5.0], 7),
+            6.0,
+            ScoreDirection::HigherIsBetter,
+        );
+        assert_eq!(trend.direction, TrendDirection::Improving);
+
+        let trend = analyze_trend(
+            &series(&[5.0, 5.0, 4.0, 3.0, 3.0, 2.0], 7),
+            6.0,
+            ScoreDirection::HigherIsBetter,
+        );
+        assert_eq!(trend.direction, TrendDirection::Worsening);
+    }
+
+    #[test]
+    fn test_single_outlier_does_not_flip_trend() {
+        // The last score alone would read as a large worsening
+        let trend = analyze_trend(
+            &series(
+                &[20.0, 19.0, 18.0, 17.0, 15.0, 14.0, 13.0, 12.0, 11.0, 26.0],
+                7,
+            ),
+            27.0,
+            ScoreDirection::LowerIsBetter,
+        );
+        assert_eq!(trend.direction, TrendDirection::Improving);
+        assert!(trend.slope_per_week.unwrap() < 0.0);
+    }
+
+    #[test]
+    fn test_zero_start_can_still_worsen() {
+        let trend = analyze_trend(
+            &series(&[0.0, 2.0, 4.0, 6.0, 9.0], 14),
+            27.0,
+            ScoreDirection::LowerIsBetter,
+        );
+        assert_eq!(trend.direction, TrendDirection::Worsening);
+    }
+
+    #[test]
+    fn test_noisy_flat_series_is_stable() {
+        let trend = analyze_trend(
+            &series(&[10.0, 10.0, 11.0, 10.0, 11.0, 9.0, 10.0], 7),
+            27.0,
+            ScoreDirection::LowerIsBetter,
+        );
+        assert_eq!(trend.direction, TrendDirection::Stable);
+        assert!(trend.sufficient_data);
+        assert!(trend.confidence < TREND_CONFIDENCE_LEVEL);
+    }
+
+    #[test]
+    fn test_tiny_consistent_drift_is_stable() {
+        // Significant, but well under a tenth of the scale over the whole span
+        let values: Vec<f64> = (0..20).map(|i| 10.0 + i as f64 * 0.05).collect();
+        let trend = analyze_trend(&series(&values, 7), 27.0, ScoreDirection::LowerIsBetter);
+        assert!(trend.confidence > TREND_CONFIDENCE_LEVEL);
+        assert_eq!(trend.direction, TrendDirection::Stable);
+    }
+
+    #[test]
+    fn test_minimum_data_rule() {
+        // Too few points
+        let trend = analyze_trend(
+            &series(&[20.0, 10.0, 5.0], 7),
+            27.0,
+            ScoreDirection::LowerIsBetter,
+        );
+        assert!(!trend.sufficient_data);
+        assert_eq!(trend.slope_per_week, None);
+        assert_eq!(trend.direction, TrendDirection::Stable);
+
+        // Enough points, but all within a few days
+        let trend = analyze_trend(
+            &series(&[20.0, 15.0, 10.0, 5.0, 1.0], 1),
+            27.0,
+            ScoreDirection::LowerIsBetter,
+        );
+        assert!(!trend.sufficient_data);
+        assert_eq!(trend.points_used, 5);
+    }
+
+    #[test]
+    fn test_long_series_is_downsampled() {
+        let values: Vec<f64> = (0..3000).map(|i| 20.0 - i as f64 / 200.0).collect();
+        let trend = analyze_trend(&series(&values, 1), 27.0, ScoreDirection::LowerIsBetter);
+        assert_eq!(trend.points_used, 3000);
+        assert_eq!(trend.direction, TrendDirection::Improving);
+        assert!((trend.slope_per_week.unwrap() - -0.035).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_normal_cdf() {
+        assert!((normal_cdf(0.0) - 0.5).abs() < 1e-6);
+        assert!((normal_cdf(1.959_964) - 0.975).abs() < 1e-5);
+        assert!((normal_cdf(-1.644_854) - 0.05).abs() < 1e-5);
+    }
+}
@@ -12,7 +12,7 @@ pub mod types;
 pub mod utils;
 
 // Re-export for easier access
-pub use config::AppConfig;
+pub use config::{AppConfig, NotificationSettings, QuietHoursWindow, QuietTimePolicy};
 pub use constants::*;
 pub use db::Database;
 pub use errors::{AppError, AppResult, CommandError};
@@ -54,16 +54,44 @@ pub fn run() {
         features::mood::queries::get_activities,
         features::visualization::queries::get_assessment_chart_data,
         features::visualization::queries::get_mood_chart_data,
+        features::visualization::queries::get_combined_timeline,
+        features::visualization::queries::get_calendar_heatmap,
+        features::visualization::queries::get_period_comparison,
+        features::visualization::queries::get_severity_distribution,
+        features::visualization::commands::save_chart_image,
         features::scheduling::commands::create_schedule,
         features::scheduling::commands::update_schedule,
         features::scheduling::commands::delete_schedule,
+        features::scheduling::commands::snooze_reminder,
+        features::scheduling::commands::dismiss_reminder,
+        features::scheduling::commands::complete_reminder,
+        features::scheduling::commands::acknowledge_missed_reminders,
+        features::scheduling::commands::set_adaptive_rules,
+        features::scheduling::commands::update_notification_settings,
+        features::scheduling::commands::pause_reminders,
+        features::scheduling::commands::resume_reminders,
+        features::scheduling::commands::export_schedules_ical,
+        features::scheduling::commands::import_schedules_ical,
         features::scheduling::queries::get_schedules,
         features::scheduling::queries::get_schedule,
+        features::scheduling::queries::preview_schedule_occurrences,
+        features::scheduling::queries::get_reminder_events,
+        features::scheduling::queries::get_reminder_adherence,
+        features::scheduling::queries::get_missed_reminders,
+        features::scheduling::queries::get_adaptive_rules,
+        features::scheduling::queries::get_frequency_changes,
+        features::scheduling::queries::get_notification_settings,
         // Activity Groups (Week 2)
         features::activities::commands::create_activity_group,
         features::activities::commands::update_activity_group,
         features::activities::commands::delete_activity_group,
         features::activities::queries::get_activity_groups,
+        features::activities::queries::get_archived_activity_groups,
+        features::activities::commands::set_activity_group_appearance,
+        features::activities::commands::archive_activity_group,
+        features::activities::commands::unarchive_activity_group,
+        features::activities::commands::reorder_activity_groups,
+        features::activities::commands::reorder_activities,
         // Activity Logging (Week 2)
         features::activities::commands::log_activity,
         features::activities::commands::update_activity_log,
@@ -77,6 +105,31 @@ pub fn run() {
         features::activities::queries::get_activity_frequency,
         features::activities::queries::get_activity_trend,
         features::activities::queries::check_goal_progress,
+        features::activities::queries::get_group_frequency,
+        features::activities::queries::get_group_trend,
+        features::activities::queries::get_weekly_group_summary,
+        // Planned Activities
+        features::activities::commands::create_planned_activity,
+        features::activities::commands::update_planned_activity,
+        features::activities::commands::delete_planned_activity,
+        features::activities::queries::get_planned_activities,
+        features::activities::queries::get_activity_adherence,
+        features::activities::queries::get_group_adherence,
+        // Goal Templates
+        features::goal_templates::queries::get_goal_templates,
+        features::goal_templates::commands::apply_goal_template,
+        features::goal_templates::commands::save_goal_template,
+        features::goal_templates::commands::delete_goal_template,
+        // Streaks
+        features::streaks::queries::get_activity_streak,
+        features::streaks::queries::get_group_streak,
+        features::streaks::queries::get_mood_checkin_streak,
+        features::streaks::queries::get_streak_milestones,
+        // Insights
+        features::insights::queries::get_weekly_digests,
+        // Backup
+        features::backup::commands::export_data,
+        features::backup::commands::import_data,
     ]);
 
     #[cfg(debug_assertions)]
@@ -169,16 +222,44 @@ mod tests {
             features::mood::queries::get_activities,
             features::visualization::queries::get_assessment_chart_data,
             features::visualization::queries::get_mood_chart_data,
+            features::visualization::queries::get_combined_timeline,
+            features::visualization::queries::get_calendar_heatmap,
+            features::visualization::queries::get_period_comparison,
+            features::visualization::queries::get_severity_distribution,
+            features::visualization::commands::save_chart_image,
             features::scheduling::commands::create_schedule,
             features::scheduling::commands::update_schedule,
             features::scheduling::commands::delete_schedule,
+            features::scheduling::commands::snooze_reminder,
+            features::scheduling::commands::dismiss_reminder,
+            features::scheduling::commands::complete_reminder,
+            features::scheduling::commands::acknowledge_missed_reminders,
+            features::scheduling::commands::set_adaptive_rules,
+            features::scheduling::commands::update_notification_settings,
+            features::scheduling::commands::pause_reminders,
+            features::scheduling::commands::resume_reminders,
+            features::scheduling::commands::export_schedules_ical,
+            features::scheduling::commands::import_schedules_ical,
             features::scheduling::queries::get_schedules,
             features::scheduling::queries::get_schedule,
+            features::scheduling::queries::preview_schedule_occurrences,
+            features::scheduling::queries::get_reminder_events,
+            features::scheduling::queries::get_reminder_adherence,
+            features::scheduling::queries::get_missed_reminders,
+            features::scheduling::queries::get_adaptive_rules,
+            features::scheduling::queries::get_frequency_changes,
+            features::scheduling::queries::get_notification_settings,
             // Activity Groups (Week 2)
             features::activities::commands::create_activity_group,
             features::activities::commands::update_activity_group,
             features::activities::commands::delete_activity_group,
             features::activities::queries::get_activity_groups,
+            features::activities::queries::get_archived_activity_groups,
+            features::activities::commands::set_activity_group_appearance,
+            features::activities::commands::archive_activity_group,
+            features::activities::commands::unarchive_activity_group,
+            features::activities::commands::reorder_activity_groups,
+            features::activities::commands::reorder_activities,
             // Activity Logging (Week 2)
             features::activities::commands::log_activity,
             features::activities::commands::update_activity_log,
@@ -192,6 +273,31 @@ mod tests {
             features::activities::queries::get_activity_frequency,
             features::activities::queries::get_activity_trend,
             features::activities::queries::check_goal_progress,
+            features::activities::queries::get_group_frequency,
+            features::activities::queries::get_group_trend,
+            features::activities::queries::get_weekly_group_summary,
+            // Planned Activities
+            features::activities::commands::create_planned_activity,
+            features::activities::commands::update_planned_activity,
+            features::activities::commands::delete_planned_activity,
+            features::activities::queries::get_planned_activities,
+            features::activities::queries::get_activity_adherence,
+            features::activities::queries::get_group_adherence,
+            // Goal Templates
+            features::goal_templates::queries::get_goal_templates,
+            features::goal_templates::commands::apply_goal_template,
+            features::goal_templates::commands::save_goal_template,
+            features::goal_templates::commands::delete_goal_template,
+            // Streaks
+            features::streaks::queries::get_activity_streak,
+            features::streaks::queries::get_group_streak,
+            features::streaks::queries::get_mood_checkin_streak,
+            features::streaks::queries::get_streak_milestones,
+            // Insights
+            features::insights::queries::get_weekly_digests,
+            // Backup
+            features::backup::commands::export_data,
+            features::backup::commands::import_data,
         ]);
 
         builder
@@ -26,10 +26,54 @@ pub fn sanitize_optional_text(text: Option<String>) -> Option<String> {
         .map(|s| s.to_string())
 }
 
+/// Parses the timestamp formats stored in the database into a UTC `NaiveDateTime`.
+///
+/// Timestamps reach the database from two sources: RFC 3339 strings sent by the
+/// frontend (`2025-01-01T10:00:00Z`, `2025-01-01T10:00:00+02:00`) and SQLite's
+/// `datetime('now')` default (`2025-01-01 10:00:00`). Offsets are converted to UTC.
+///
+/// # Examples
+///
+/// ```
+/// use tauri_sveltekit_modern_lib::utils::parse_timestamp;
+///
+/// let a = parse_timestamp("2025-01-01T12:00:00+02:00").unwrap();
+/// let b = parse_timestamp("2025-01-01 10:00:00").unwrap();
+/// assert_eq!(a, b);
+/// assert!(parse_timestamp("not a timestamp").is_none());
+/// ```
+pub fn parse_timestamp(value: &str) -> Option<chrono::NaiveDateTime> {
+    if let Ok(dt) = chrono::DateTime::parse_from_rfc3339(value) {
+        return Some(dt.naive_utc());
+    }
+
+    [
+        "%Y-%m-%d %H:%M:%S",
+        "%Y-%m-%dT%H:%M:%S",
+        "%Y-%m-%d %H:%M:%S%.f",
+    ]
+    .iter()
+    .find_map(|format| chrono::NaiveDateTime::parse_from_str(value, format).ok())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_timestamp_formats() {
+        let expected = chrono::NaiveDate::from_ymd_opt(2025, 1, 1)
+            .unwrap()
+            .and_hms_opt(10, 0, 0)
+            .unwrap();
+
+        assert_eq!(parse_timestamp("2025-01-01T10:00:00Z"), Some(expected));
+        assert_eq!(parse_timestamp("2025-01-01T11:00:00+01:00"), Some(expected));
+        assert_eq!(parse_timestamp("2025-01-01 10:00:00"), Some(expected));
+        assert_eq!(parse_timestamp("2025-01-01T10:00:00"), Some(expected));
+        assert_eq!(parse_timestamp("2025-01-01"), None);
+    }
+
     #[test]
     fn test_sanitize_optional_text_trims_whitespace() {
         let input = Some("  hello world  ".to_string());
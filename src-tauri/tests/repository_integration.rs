@@ -247,8 +247,9 @@ fn test_delete_assessment_type_blocked_when_schedules_exist() {
     let conn_guard = conn.lock();
     conn_guard
         .execute(
-            "INSERT INTO assessment_schedules (assessment_type_id, frequency, time_of_day, enabled)
-             VALUES (?, 'daily', '09:00:00', true)",
+            "INSERT INTO assessment_schedules
+             (assessment_type_id, frequency, time_of_day, rrule, dtstart, enabled)
+             VALUES (?, 'daily', '09:00', 'FREQ=DAILY;BYHOUR=9;BYMINUTE=0', '2025-01-01T00:00:00', true)",
             [assessment_type.id],
         )
         .expect("Failed to insert schedule");
@@ -5,6 +5,7 @@
 
 use std::sync::Arc;
 use tauri_sveltekit_modern_lib::db::Database;
+use tauri_sveltekit_modern_lib::features::activities::repository::ActivityRepository;
 use tauri_sveltekit_modern_lib::features::mood::repository::MoodRepository;
 use tauri_sveltekit_modern_lib::types::activity::HexColor;
 use tempfile::TempDir;
@@ -290,6 +291,45 @@ fn test_get_activities_filter_deleted() {
     assert_eq!(all.len(), 3);
 }
 
+#[test]
+fn test_get_activities_hides_archived_groups() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let db =
+        Arc::new(Database::new(temp_dir.path().to_path_buf()).expect("Failed to create database"));
+    let activity_repo = ActivityRepository::new(db.clone());
+    let mood_repo = MoodRepository::new(db);
+
+    let exercise = activity_repo
+        .create_activity_group("Exercise", None)
+        .expect("Failed to create group");
+    let social = activity_repo
+        .create_activity_group("Social", None)
+        .expect("Failed to create group");
+    mood_repo
+        .create_activity("Running", None, None, exercise.id)
+        .expect("Failed to create activity");
+    mood_repo
+        .create_activity("Phone call", None, None, social.id)
+        .expect("Failed to create activity");
+
+    activity_repo
+        .archive_activity_group(exercise.id)
+        .expect("Failed to archive group");
+
+    // Pickers no longer offer the archived group's activities...
+    let active = mood_repo
+        .get_activities(false)
+        .expect("Failed to get active activities");
+    assert_eq!(active.len(), 1);
+    assert_eq!(active[0].name, "Phone call");
+
+    // ...but they still exist for history
+    let all = mood_repo
+        .get_activities(true)
+        .expect("Failed to get all activities");
+    assert_eq!(all.len(), 2);
+}
+
 #[test]
 fn test_delete_activity_not_found() {
     let (repo, _temp_dir, group_id) = setup_test_repo();
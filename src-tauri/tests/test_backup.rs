@@ -0,0 +1,442 @@
+// Integration tests for full export and import
+
+use std::sync::Arc;
+
+use tauri_sveltekit_modern_lib::db::Database;
+use tauri_sveltekit_modern_lib::features::activities::repository::ActivityRepository;
+use tauri_sveltekit_modern_lib::features::assessments::repository::AssessmentRepository;
+use tauri_sveltekit_modern_lib::features::backup::models::EXPORT_SCHEMA_VERSION;
+use tauri_sveltekit_modern_lib::features::backup::{
+    BackupError, BackupRepository, ExportDocument, ImportMode,
+};
+use tauri_sveltekit_modern_lib::features::mood::repository::MoodRepository;
+use tauri_sveltekit_modern_lib::features::scheduling::models::*;
+use tauri_sveltekit_modern_lib::features::scheduling::repository::SchedulingRepository;
+use tauri_sveltekit_modern_lib::types::activity::GoalType;
+use tauri_sveltekit_modern_lib::types::assessment::{AssessmentStatus, SeverityLevel};
+use tempfile::TempDir;
+
+const EXPORTED_AT: &str = "2025-03-01 12:00:00";
+
+fn setup_db() -> (Arc<Database>, TempDir) {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let db =
+        Arc::new(Database::new(temp_dir.path().to_path_buf()).expect("Failed to create database"));
+    (db, temp_dir)
+}
+
+/// One row or more in most tables, including references between rows of
+/// the same table (a snooze of a fired reminder) and by kind (a streak)
+fn populate(db: &Arc<Database>) {
+    let activity_repo = ActivityRepository::new(db.clone());
+    let group = activity_repo
+        .create_activity_group("Exercise", None)
+        .expect("Failed to create group");
+    let walk = activity_repo
+        .create_activity(group.id, "Walk", None, None)
+        .expect("Failed to create activity");
+    activity_repo
+        .set_activity_goal(Some(walk.id), None, GoalType::DaysPerPeriod, 3, 7)
+        .expect("Failed to set goal");
+    activity_repo
+        .log_activity(walk.id, "2025-02-20 08:00:00", Some("Park"))
+        .expect("Failed to log activity");
+
+    let mood_repo = MoodRepository::new(db.clone());
+    mood_repo
+        .create_mood_checkin(5, vec![walk.id], Some("Good day"))
+        .expect("Failed to create check-in");
+    mood_repo
+        .create_mood_checkin(3, vec![], None)
+        .expect("Failed to create check-in");
+
+    AssessmentRepository::new(db.clone())
+        .save_assessment(
+            1,
+            &[1; 9],
+            9,
+            SeverityLevel::Mild,
+            None,
+            AssessmentStatus::Completed,
+        )
+        .expect("Failed to create assessment");
+
+    let schedule = SchedulingRepository::new(db.clone())
+        .create_schedule(&CreateScheduleRequest {
+            assessment_type_id: Some(1),
+            frequency: ScheduleFrequency::Daily,
+            time_of_day: "09:00".to_string(),
+            day_of_week: None,
+            day_of_month: None,
+            timezone: None,
+            target: None,
+            rrule: None,
+            dtstart: None,
+            exdates: None,
+            missed_policy: None,
+        })
+        .expect("Failed to create schedule");
+
+    let conn = db.get_connection();
+    let conn = conn.lock();
+    conn.execute(
+        "INSERT INTO reminder_events (schedule_id, event_type, occurred_at)
+         VALUES (?, 'fired', '2025-02-21 09:00:00')",
+        [schedule.id],
+    )
+    .expect("Failed to insert fired event");
+    let fired_id = conn.last_insert_rowid();
+    conn.execute(
+        "INSERT INTO reminder_events
+         (schedule_id, event_type, occurred_at, snooze_until, related_event_id)
+         VALUES (?, 'snoozed', '2025-02-21 09:05:00', '2025-02-21 09:35:00', ?)",
+        rusqlite::params![schedule.id, fired_id],
+    )
+    .expect("Failed to insert snoozed event");
+    conn.execute(
+        "INSERT INTO missed_reminders
+         (schedule_id, scheduled_for, local_time, policy, reminder_event_id, detected_at)
+         VALUES (?, '2025-02-22 09:00:00', '2025-02-22T09:00:00', 'coalesce', ?,
+                 '2025-02-23 08:00:00')",
+        rusqlite::params![schedule.id, fired_id],
+    )
+    .expect("Failed to insert missed reminder");
+    conn.execute(
+        "INSERT INTO streak_milestones (streak_kind, target_id, milestone, streak_start_date)
+         VALUES ('activity', ?, 7, '2025-02-01')",
+        [walk.id],
+    )
+    .expect("Failed to insert streak milestone");
+}
+
+fn export(db: &Arc<Database>) -> ExportDocument {
+    BackupRepository::new(db.clone())
+        .export_document(EXPORTED_AT)
+        .expect("Failed to export")
+}
+
+fn row_count(db: &Arc<Database>, table: &str) -> i64 {
+    let conn = db.get_connection();
+    let conn = conn.lock();
+    conn.query_row(&format!("SELECT COUNT(*) FROM {}", table), [], |row| {
+        row.get(0)
+    })
+    .expect("Failed to count rows")
+}
+
+#[test]
+fn test_export_document_header() {
+    let (db, _temp_dir) = setup_db();
+    populate(&db);
+
+    let document = export(&db);
+    assert_eq!(document.format, "mental-health-tracker-export");
+    assert_eq!(document.schema_version, EXPORT_SCHEMA_VERSION);
+    assert_eq!(document.app_version, env!("CARGO_PKG_VERSION"));
+    assert!(document.database_version >= 18);
+    assert_eq!(document.exported_at, EXPORTED_AT);
+
+    assert_eq!(document.tables["assessment_types"].len(), 4);
+    assert_eq!(document.tables["mood_checkins"].len(), 2);
+    assert_eq!(document.tables["reminder_events"].len(), 2);
+    let checkin = &document.tables["mood_checkins"][0];
+    assert_eq!(checkin["mood_rating"], 5);
+    assert_eq!(checkin["notes"], "Good day");
+
+    // Survives being written and read back
+    let json = serde_json::to_string(&document).expect("Failed to serialize");
+    let parsed: ExportDocument = serde_json::from_str(&json).expect("Failed to parse");
+    assert_eq!(parsed, document);
+}
+
+#[test]
+fn test_restore_round_trip() {
+    let (source, _source_dir) = setup_db();
+    populate(&source);
+    let document = export(&source);
+
+    let (target, _target_dir) = setup_db();
+    let report = BackupRepository::new(target.clone())
+        .import_document(&document, ImportMode::Restore, false)
+        .expect("Failed to restore");
+
+    assert!(!report.dry_run);
+    assert_eq!(report.duplicates(), 0);
+    let exported_rows: usize = document
+        .tables
+        .iter()
+        .filter(|(table, _)| *table != "assessment_types")
+        .map(|(_, rows)| rows.len())
+        .sum();
+    assert_eq!(report.imported() as usize, exported_rows);
+
+    // A fresh database hands out the same ids, so the copy is identical
+    assert_eq!(export(&target).tables, document.tables);
+}
+
+#[test]
+fn test_dry_run_writes_nothing() {
+    let (source, _source_dir) = setup_db();
+    populate(&source);
+    let document = export(&source);
+
+    let (target, _target_dir) = setup_db();
+    let repo = BackupRepository::new(target.clone());
+    let dry_run = repo
+        .import_document(&document, ImportMode::Restore, true)
+        .expect("Failed to dry-run");
+    assert!(dry_run.dry_run);
+    assert_eq!(row_count(&target, "mood_checkins"), 0);
+    assert_eq!(row_count(&target, "activities"), 0);
+
+    // The dry run reports what the real import then does
+    let report = repo
+        .import_document(&document, ImportMode::Restore, false)
+        .expect("Failed to restore");
+    assert_eq!(report.tables, dry_run.tables);
+}
+
+#[test]
+fn test_restore_requires_empty_database() {
+    let (db, _temp_dir) = setup_db();
+    populate(&db);
+    let document = export(&db);
+
+    let result =
+        BackupRepository::new(db.clone()).import_document(&document, ImportMode::Restore, false);
+    assert!(matches!(result, Err(BackupError::DatabaseNotEmpty(_))));
+}
+
+#[test]
+fn test_merge_skips_duplicates() {
+    let (db, _temp_dir) = setup_db();
+    populate(&db);
+    let document = export(&db);
+
+    let report = BackupRepository::new(db.clone())
+        .import_document(&document, ImportMode::Merge, false)
+        .expect("Failed to merge");
+    assert_eq!(report.imported(), 0);
+    assert!(report.duplicates() > 0);
+    assert_eq!(export(&db).tables, document.tables);
+}
+
+#[test]
+fn test_merge_remaps_ids() {
+    let (source, _source_dir) = setup_db();
+    populate(&source);
+    let document = export(&source);
+
+    // The target already has rows with the ids the export uses
+    let (target, _target_dir) = setup_db();
+    let activity_repo = ActivityRepository::new(target.clone());
+    let group = activity_repo
+        .create_activity_group("Work", None)
+        .expect("Failed to create group");
+    let typing = activity_repo
+        .create_activity(group.id, "Typing", None, None)
+        .expect("Failed to create activity");
+    MoodRepository::new(target.clone())
+        .create_mood_checkin(2, vec![typing.id], None)
+        .expect("Failed to create check-in");
+
+    let repo = BackupRepository::new(target.clone());
+    let report = repo
+        .import_document(&document, ImportMode::Merge, false)
+        .expect("Failed to merge");
+    assert_eq!(report.duplicates(), 0);
+    assert_eq!(row_count(&target, "activities"), 2);
+    assert_eq!(row_count(&target, "mood_checkins"), 3);
+
+    let conn = target.get_connection();
+    let conn = conn.lock();
+    let activity: String = conn
+        .query_row(
+            "SELECT a.name
+             FROM mood_checkins mc
+             JOIN mood_checkin_activities mca ON mca.mood_checkin_id = mc.id
+             JOIN activities a ON a.id = mca.activity_id
+             WHERE mc.notes = 'Good day'",
+            [],
+            |row| row.get(0),
+        )
+        .expect("Imported check-in should keep its activity");
+    assert_eq!(activity, "Walk");
+
+    let (group_name, streak_activity): (String, String) = conn
+        .query_row(
+            "SELECT g.name, a.name
+             FROM streak_milestones s
+             JOIN activities a ON a.id = s.target_id
+             JOIN activity_groups g ON g.id = a.group_id",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .expect("Imported streak should keep its activity");
+    assert_eq!(
+        (group_name.as_str(), streak_activity.as_str()),
+        ("Exercise", "Walk")
+    );
+
+    let related_type: String = conn
+        .query_row(
+            "SELECT related.event_type
+             FROM reminder_events snooze
+             JOIN reminder_events related ON related.id = snooze.related_event_id
+             WHERE snooze.event_type = 'snoozed'
+               AND related.schedule_id = snooze.schedule_id",
+            [],
+            |row| row.get(0),
+        )
+        .expect("Snooze should refer to the imported fired event");
+    assert_eq!(related_type, "fired");
+    drop(conn);
+
+    // Merging again finds everything already there
+    let again = repo
+        .import_document(&document, ImportMode::Merge, false)
+        .expect("Failed to merge");
+    assert_eq!(again.imported(), 0);
+}
+
+#[test]
+fn test_merge_matches_live_names_and_drafts() {
+    let (source, _source_dir) = setup_db();
+    populate(&source);
+    AssessmentRepository::new(source.clone())
+        .save_assessment(
+            1,
+            &[0; 9],
+            0,
+            SeverityLevel::Minimal,
+            None,
+            AssessmentStatus::Draft,
+        )
+        .expect("Failed to save draft");
+    let document = export(&source);
+
+    // Another device created the same group and activity at another time,
+    // and has its own draft of the same assessment
+    let (target, _target_dir) = setup_db();
+    let activity_repo = ActivityRepository::new(target.clone());
+    let group = activity_repo
+        .create_activity_group("Exercise", None)
+        .expect("Failed to create group");
+    let walk = activity_repo
+        .create_activity(group.id, "Walk", None, None)
+        .expect("Failed to create activity");
+    AssessmentRepository::new(target.clone())
+        .save_assessment(
+            1,
+            &[2; 9],
+            18,
+            SeverityLevel::ModeratelySevere,
+            None,
+            AssessmentStatus::Draft,
+        )
+        .expect("Failed to save draft");
+    {
+        let conn = target.get_connection();
+        let conn = conn.lock();
+        conn.execute(
+            "UPDATE activity_groups SET created_at = '2020-01-01 00:00:00'",
+            [],
+        )
+        .expect("Failed to backdate group");
+        conn.execute(
+            "UPDATE activities SET created_at = '2020-01-01 00:00:00'",
+            [],
+        )
+        .expect("Failed to backdate activity");
+    }
+
+    let report = BackupRepository::new(target.clone())
+        .import_document(&document, ImportMode::Merge, false)
+        .expect("Failed to merge");
+    let count = |table: &str| {
+        report
+            .tables
+            .iter()
+            .find(|count| count.table == table)
+            .map(|count| (count.imported, count.duplicates))
+            .expect("Table should be reported")
+    };
+    assert_eq!(count("activity_groups"), (0, 1));
+    assert_eq!(count("activities"), (0, 1));
+    assert_eq!(count("assessment_responses"), (1, 1));
+    assert_eq!(row_count(&target, "activities"), 1);
+
+    let conn = target.get_connection();
+    let conn = conn.lock();
+    let activity_id: i32 = conn
+        .query_row(
+            "SELECT mca.activity_id
+             FROM mood_checkins mc
+             JOIN mood_checkin_activities mca ON mca.mood_checkin_id = mc.id
+             WHERE mc.notes = 'Good day'",
+            [],
+            |row| row.get(0),
+        )
+        .expect("Imported check-in should keep its activity");
+    assert_eq!(activity_id, walk.id);
+    let draft_score: i32 = conn
+        .query_row(
+            "SELECT total_score FROM assessment_responses WHERE status = 'draft'",
+            [],
+            |row| row.get(0),
+        )
+        .expect("Target should keep its one draft");
+    assert_eq!(draft_score, 18);
+}
+
+#[test]
+fn test_import_rejects_invalid_documents() {
+    let (source, _source_dir) = setup_db();
+    populate(&source);
+    let document = export(&source);
+    let (target, _target_dir) = setup_db();
+    let repo = BackupRepository::new(target.clone());
+    let import =
+        |document: &ExportDocument| repo.import_document(document, ImportMode::Restore, false);
+
+    let mut newer = document.clone();
+    newer.schema_version = EXPORT_SCHEMA_VERSION + 1;
+    assert!(matches!(
+        import(&newer),
+        Err(BackupError::UnsupportedSchemaVersion { .. })
+    ));
+
+    let mut other = document.clone();
+    other.format = "something-else".to_string();
+    assert!(matches!(
+        import(&other),
+        Err(BackupError::UnsupportedFormat)
+    ));
+
+    let mut extra_column = document.clone();
+    extra_column.tables.get_mut("mood_checkins").unwrap()[0]
+        .insert("mood".to_string(), serde_json::json!(5));
+    assert!(matches!(
+        import(&extra_column),
+        Err(BackupError::UnknownColumn { .. })
+    ));
+
+    let mut unknown_type = document.clone();
+    unknown_type.tables.get_mut("assessment_types").unwrap()[0]
+        .insert("code".to_string(), serde_json::json!("XYZ"));
+    assert!(matches!(
+        import(&unknown_type),
+        Err(BackupError::UnknownAssessmentType(code)) if code == "XYZ"
+    ));
+
+    // A row the database rejects rolls back the rows before it
+    let mut invalid_row = document.clone();
+    invalid_row.tables.get_mut("mood_checkins").unwrap()[1]
+        .insert("mood_rating".to_string(), serde_json::json!(9));
+    assert!(matches!(
+        import(&invalid_row),
+        Err(BackupError::InvalidRow { row: 1, .. })
+    ));
+    assert_eq!(row_count(&target, "mood_checkins"), 0);
+    assert_eq!(row_count(&target, "activities"), 0);
+}
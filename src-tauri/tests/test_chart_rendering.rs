@@ -0,0 +1,239 @@
+// Golden-image tests for chart rendering
+//
+// Charts are rendered from fixed data and compared against tests/golden:
+// SVG byte for byte, PNG pixel by pixel with a small tolerance for
+// rasterizer differences. PNGs are rendered without fonts so text does not
+// depend on the machine. Run with UPDATE_GOLDEN=1 to regenerate after an
+// intended change, and review the new images.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use resvg::tiny_skia::Pixmap;
+use resvg::usvg::fontdb;
+use tauri_sveltekit_modern_lib::db::Database;
+use tauri_sveltekit_modern_lib::features::assessments::repository::AssessmentRepository;
+use tauri_sveltekit_modern_lib::features::mood::repository::MoodRepository;
+use tauri_sveltekit_modern_lib::features::visualization::models::{
+    ChartQueryOptions, VisualizationError,
+};
+use tauri_sveltekit_modern_lib::features::visualization::render::{
+    render_assessment_chart_svg, render_mood_chart_svg, svg_to_png, ChartImageFormat, ChartSize,
+};
+use tauri_sveltekit_modern_lib::features::visualization::repository::VisualizationRepository;
+use tauri_sveltekit_modern_lib::features::visualization::series::DerivedSeriesKind;
+use tauri_sveltekit_modern_lib::types::assessment::{AssessmentStatus, SeverityLevel};
+use tempfile::TempDir;
+
+/// Largest per-channel difference still counted as the same pixel
+const CHANNEL_TOLERANCE: u8 = 8;
+/// Share of pixels allowed to differ by more than the tolerance
+const MAX_DIFFERING_PIXELS: f64 = 0.001;
+
+/// A database with six weekly PHQ-9 scores and two weeks of mood check-ins,
+/// all at fixed timestamps
+fn setup_chart_data() -> (VisualizationRepository, TempDir) {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let db =
+        Arc::new(Database::new(temp_dir.path().to_path_buf()).expect("Failed to create database"));
+
+    let assessment_repo = AssessmentRepository::new(db.clone());
+    for (score, severity) in [
+        (21, SeverityLevel::Severe),
+        (17, SeverityLevel::ModeratelySevere),
+        (14, SeverityLevel::Moderate),
+        (11, SeverityLevel::Moderate),
+        (8, SeverityLevel::Mild),
+        (4, SeverityLevel::Minimal),
+    ] {
+        assessment_repo
+            .save_assessment(
+                1,
+                &[1; 9],
+                score,
+                severity,
+                None,
+                AssessmentStatus::Completed,
+            )
+            .expect("Failed to create assessment");
+    }
+
+    let mood_repo = MoodRepository::new(db.clone());
+    let ratings = [3, 4, 2, 3, 4, 5, 4, 3, 5, 6, 5, 4, 6, 7];
+    for rating in ratings {
+        mood_repo
+            .create_mood_checkin(rating, vec![], None)
+            .expect("Failed to create check-in");
+    }
+
+    {
+        let conn = db.get_connection();
+        let conn = conn.lock();
+        conn.execute(
+            "UPDATE assessment_responses
+             SET completed_at = datetime('2025-01-06 09:00:00', printf('+%d days', (id - 1) * 7))",
+            [],
+        )
+        .expect("Failed to set assessment timestamps");
+        // Two check-ins a day, morning and evening
+        conn.execute(
+            "UPDATE mood_checkins
+             SET created_at = datetime('2025-03-01 08:00:00',
+                                       printf('+%d hours', ((id - 1) / 2) * 24 + ((id - 1) % 2) * 12))",
+            [],
+        )
+        .expect("Failed to set check-in timestamps");
+    }
+
+    (VisualizationRepository::new(db), temp_dir)
+}
+
+fn golden_path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("golden")
+        .join(name)
+}
+
+fn update_golden() -> bool {
+    std::env::var_os("UPDATE_GOLDEN").is_some()
+}
+
+fn assert_matches_golden_svg(name: &str, svg: &str) {
+    let path = golden_path(name);
+    if update_golden() {
+        std::fs::write(&path, svg).expect("Failed to write golden SVG");
+        return;
+    }
+    let expected = std::fs::read_to_string(&path)
+        .unwrap_or_else(|e| panic!("Missing golden {}: {}", path.display(), e));
+    assert_eq!(svg, expected, "{} differs from its golden copy", name);
+}
+
+fn assert_matches_golden_png(name: &str, png: &[u8]) {
+    let path = golden_path(name);
+    if update_golden() {
+        std::fs::write(&path, png).expect("Failed to write golden PNG");
+        return;
+    }
+    let expected = Pixmap::load_png(&path)
+        .unwrap_or_else(|e| panic!("Missing golden {}: {}", path.display(), e));
+    let actual = Pixmap::decode_png(png).expect("Rendered PNG should decode");
+
+    assert_eq!(
+        (actual.width(), actual.height()),
+        (expected.width(), expected.height()),
+        "{} has a different size",
+        name
+    );
+    let differing = actual
+        .data()
+        .chunks(4)
+        .zip(expected.data().chunks(4))
+        .filter(|(a, b)| {
+            a.iter()
+                .zip(b.iter())
+                .any(|(x, y)| x.abs_diff(*y) > CHANNEL_TOLERANCE)
+        })
+        .count();
+    let total = (actual.width() * actual.height()) as f64;
+    assert!(
+        differing as f64 / total <= MAX_DIFFERING_PIXELS,
+        "{}: {} of {} pixels differ from the golden copy",
+        name,
+        differing,
+        total
+    );
+}
+
+/// No fonts, so PNG output is the same on every machine
+fn no_fonts() -> Arc<fontdb::Database> {
+    Arc::new(fontdb::Database::new())
+}
+
+fn assessment_chart_svg(repo: &VisualizationRepository) -> String {
+    let data = repo
+        .get_assessment_chart_data("PHQ9", None, None)
+        .expect("Failed to get chart data");
+    render_assessment_chart_svg(&data, ChartSize::default())
+}
+
+fn mood_chart_svg(repo: &VisualizationRepository) -> String {
+    let data = repo
+        .get_mood_chart_data_with_options(
+            None,
+            None,
+            false,
+            &ChartQueryOptions {
+                series: vec![
+                    DerivedSeriesKind::DailyMean,
+                    DerivedSeriesKind::RollingAverage7Day,
+                ],
+                ..Default::default()
+            },
+        )
+        .expect("Failed to get mood chart data");
+    render_mood_chart_svg(&data, ChartSize::default())
+}
+
+#[test]
+fn test_assessment_chart_svg_golden() {
+    let (repo, _temp_dir) = setup_chart_data();
+    let svg = assessment_chart_svg(&repo);
+
+    // One shaded band per severity level, a dashed line between each
+    assert_eq!(svg.matches("fill-opacity").count(), 5);
+    assert_eq!(svg.matches("stroke-dasharray").count(), 4);
+    assert_eq!(svg.matches("<circle").count(), 6);
+    assert_matches_golden_svg("assessment_chart.svg", &svg);
+}
+
+#[test]
+fn test_mood_chart_svg_golden() {
+    let (repo, _temp_dir) = setup_chart_data();
+    let svg = mood_chart_svg(&repo);
+
+    assert_eq!(svg.matches("<circle").count(), 14);
+    assert!(svg.contains("daily_mean"));
+    assert!(svg.contains("rolling_average_7d"));
+    assert_matches_golden_svg("mood_chart.svg", &svg);
+}
+
+#[test]
+fn test_assessment_chart_png_golden() {
+    let (repo, _temp_dir) = setup_chart_data();
+    let png = svg_to_png(&assessment_chart_svg(&repo), no_fonts()).expect("Failed to render PNG");
+    assert_matches_golden_png("assessment_chart.png", &png);
+}
+
+#[test]
+fn test_mood_chart_png_golden() {
+    let (repo, _temp_dir) = setup_chart_data();
+    let png = svg_to_png(&mood_chart_svg(&repo), no_fonts()).expect("Failed to render PNG");
+    assert_matches_golden_png("mood_chart.png", &png);
+}
+
+#[test]
+fn test_render_chart_formats() {
+    let (repo, _temp_dir) = setup_chart_data();
+
+    let svg = repo
+        .render_chart(Some("PHQ9"), None, None, ChartImageFormat::Svg)
+        .expect("Failed to render SVG");
+    assert!(svg.starts_with(b"<svg"));
+
+    let png = repo
+        .render_chart(None, None, None, ChartImageFormat::Png)
+        .expect("Failed to render PNG");
+    let pixmap = Pixmap::decode_png(&png).expect("Rendered PNG should decode");
+    let size = ChartSize::default();
+    assert_eq!((pixmap.width(), pixmap.height()), (size.width, size.height));
+}
+
+#[test]
+fn test_render_chart_no_data() {
+    let (repo, _temp_dir) = setup_chart_data();
+
+    let result = repo.render_chart(Some("GAD7"), None, None, ChartImageFormat::Png);
+    assert!(matches!(result, Err(VisualizationError::NoData)));
+}
@@ -0,0 +1,276 @@
+// Integration tests for weekly insight digests
+
+use std::sync::Arc;
+
+use chrono::NaiveDate;
+use tauri_sveltekit_modern_lib::db::Database;
+use tauri_sveltekit_modern_lib::features::activities::repository::ActivityRepository;
+use tauri_sveltekit_modern_lib::features::assessments::repository::AssessmentRepository;
+use tauri_sveltekit_modern_lib::features::insights::{
+    DigestWeek, InsightsError, InsightsRepository,
+};
+use tauri_sveltekit_modern_lib::features::mood::repository::MoodRepository;
+use tauri_sveltekit_modern_lib::features::scheduling::models::*;
+use tauri_sveltekit_modern_lib::features::scheduling::repository::SchedulingRepository;
+use tauri_sveltekit_modern_lib::types::activity::GoalType;
+use tauri_sveltekit_modern_lib::types::assessment::{AssessmentStatus, SeverityLevel};
+use tempfile::TempDir;
+
+const GENERATED_AT: &str = "2025-03-10 09:00:00";
+
+fn week_of(start: &str) -> DigestWeek {
+    DigestWeek {
+        start: NaiveDate::parse_from_str(start, "%Y-%m-%d").unwrap(),
+        tz: chrono_tz::UTC,
+    }
+}
+
+/// A database with the week of 2025-03-03 and the week before it filled in:
+/// check-ins (one activity), a goal, missed reminders and two PHQ-9 results
+fn setup_week() -> (InsightsRepository, TempDir) {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let db =
+        Arc::new(Database::new(temp_dir.path().to_path_buf()).expect("Failed to create database"));
+
+    let activity_repo = ActivityRepository::new(db.clone());
+    let group = activity_repo
+        .create_activity_group("Movement", None)
+        .expect("Failed to create group");
+    let walk = activity_repo
+        .create_activity(group.id, "Walk", None, None)
+        .expect("Failed to create activity");
+    activity_repo
+        .set_activity_goal(Some(walk.id), None, GoalType::DaysPerPeriod, 2, 7)
+        .expect("Failed to set goal");
+    for logged_at in ["2025-03-03 08:00:00", "2025-03-05 08:00:00"] {
+        activity_repo
+            .log_activity(walk.id, logged_at, None)
+            .expect("Failed to log activity");
+    }
+
+    // Last week: 3 and 3. This week: Monday 5 and 7 (walks), Wednesday 2,
+    // Friday 6 (walk). Walks need three check-ins to count as a correlation
+    let mood_repo = MoodRepository::new(db.clone());
+    for (rating, activities) in [
+        (3, vec![]),
+        (3, vec![]),
+        (5, vec![walk.id]),
+        (7, vec![walk.id]),
+        (2, vec![]),
+        (6, vec![walk.id]),
+    ] {
+        mood_repo
+            .create_mood_checkin(rating, activities, None)
+            .expect("Failed to create check-in");
+    }
+
+    let assessment_repo = AssessmentRepository::new(db.clone());
+    for (score, severity) in [(14, SeverityLevel::Moderate), (8, SeverityLevel::Mild)] {
+        assessment_repo
+            .save_assessment(
+                1,
+                &[1; 9],
+                score,
+                severity,
+                None,
+                AssessmentStatus::Completed,
+            )
+            .expect("Failed to create assessment");
+    }
+
+    let schedule = SchedulingRepository::new(db.clone())
+        .create_schedule(&CreateScheduleRequest {
+            assessment_type_id: Some(1),
+            frequency: ScheduleFrequency::Daily,
+            time_of_day: "09:00".to_string(),
+            day_of_week: None,
+            day_of_month: None,
+            timezone: None,
+            target: None,
+            rrule: None,
+            dtstart: None,
+            exdates: None,
+            missed_policy: None,
+        })
+        .expect("Failed to create schedule");
+
+    {
+        let conn = db.get_connection();
+        let conn = conn.lock();
+        conn.execute(
+            "UPDATE mood_checkins SET created_at = CASE id
+                 WHEN 1 THEN '2025-02-25 08:00:00'
+                 WHEN 2 THEN '2025-02-25 20:00:00'
+                 WHEN 3 THEN '2025-03-03 08:00:00'
+                 WHEN 4 THEN '2025-03-03 20:00:00'
+                 WHEN 5 THEN '2025-03-05 08:00:00'
+                 ELSE '2025-03-07 08:00:00'
+             END",
+            [],
+        )
+        .expect("Failed to set check-in timestamps");
+        conn.execute(
+            "UPDATE assessment_responses SET completed_at = CASE id
+                 WHEN 1 THEN '2025-02-20 09:00:00'
+                 ELSE '2025-03-06 09:00:00'
+             END",
+            [],
+        )
+        .expect("Failed to set assessment timestamps");
+        // Two misses this week, one the week after
+        for scheduled_for in [
+            "2025-03-04 09:00:00",
+            "2025-03-08 09:00:00",
+            "2025-03-11 09:00:00",
+        ] {
+            conn.execute(
+                "INSERT INTO missed_reminders
+                 (schedule_id, scheduled_for, local_time, policy, detected_at)
+                 VALUES (?, ?, ?, 'coalesce', ?)",
+                rusqlite::params![
+                    schedule.id,
+                    scheduled_for,
+                    scheduled_for.replace(' ', "T"),
+                    scheduled_for
+                ],
+            )
+            .expect("Failed to insert missed reminder");
+        }
+    }
+
+    (InsightsRepository::new(db), temp_dir)
+}
+
+#[test]
+fn test_weekly_digest_contents() {
+    let (repo, _temp_dir) = setup_week();
+
+    let digest = repo
+        .record_weekly_digest(&week_of("2025-03-03"), GENERATED_AT)
+        .expect("Failed to record digest")
+        .expect("Digest should be new");
+
+    assert!(digest.id > 0);
+    assert_eq!(digest.week_start, "2025-03-03");
+    assert_eq!(digest.week_end, "2025-03-09");
+    assert!(!digest.is_empty());
+
+    assert_eq!(digest.mood.average_mood, Some(5.0));
+    assert_eq!(digest.mood.checkin_count, 4);
+    assert_eq!(digest.mood.previous_average_mood, Some(3.0));
+    assert_eq!(digest.mood.change, Some(2.0));
+
+    let best = digest.best_day.as_ref().expect("Should have a best day");
+    assert_eq!((best.date.as_str(), best.average_mood), ("2025-03-03", 6.0));
+    let worst = digest.worst_day.as_ref().expect("Should have a worst day");
+    assert_eq!(
+        (worst.date.as_str(), worst.average_mood),
+        ("2025-03-05", 2.0)
+    );
+
+    assert_eq!(digest.top_activities.len(), 1);
+    assert_eq!(digest.top_activities[0].activity_name, "Walk");
+    assert_eq!(digest.top_activities[0].difference, 1.0);
+
+    assert_eq!(digest.goals.len(), 1);
+    assert!(digest.goals[0].is_achieved);
+
+    assert_eq!(digest.missed_reminders.len(), 1);
+    assert_eq!(digest.missed_reminders[0].count, 2);
+
+    assert_eq!(digest.severity_changes.len(), 1);
+    let change = &digest.severity_changes[0];
+    assert_eq!(change.previous_severity, SeverityLevel::Moderate);
+    assert_eq!(change.current_severity, SeverityLevel::Mild);
+    assert_eq!(change.improved, Some(true));
+
+    assert_eq!(
+        digest.sentences[..3],
+        [
+            "Your average mood was 5.0 from 4 check-ins, up 2.0 from last week.",
+            "Your best day was Monday (6.0) and your hardest was Wednesday (2.0).",
+            "Your mood was higher with Walk (+1.0).",
+        ]
+    );
+    assert_eq!(digest.sentences[3], "You reached your Walk goal.");
+    assert!(digest.sentences[4].starts_with("You missed 2 reminders: "));
+    assert!(digest.sentences[5].contains("improved from moderate to mild (score 14 to 8)"));
+
+    let stored = repo
+        .get_weekly_digest("2025-03-03")
+        .expect("Failed to get digest")
+        .expect("Digest should be stored");
+    assert_eq!(stored, digest);
+}
+
+#[test]
+fn test_weekly_digest_recorded_once() {
+    let (repo, _temp_dir) = setup_week();
+    let week = week_of("2025-03-03");
+
+    let digest = repo
+        .record_weekly_digest(&week, GENERATED_AT)
+        .expect("Failed to record digest")
+        .expect("Digest should be new");
+    let again = repo
+        .record_weekly_digest(&week, "2025-03-11 09:00:00")
+        .expect("Failed to record digest");
+    assert!(again.is_none());
+
+    repo.mark_digest_notified(digest.id, GENERATED_AT)
+        .expect("Failed to mark digest");
+    let digests = repo
+        .get_weekly_digests(None)
+        .expect("Failed to get digests");
+    assert_eq!(digests.len(), 1);
+    assert_eq!(digests[0].generated_at, GENERATED_AT);
+    assert_eq!(digests[0].notified_at.as_deref(), Some(GENERATED_AT));
+}
+
+#[test]
+fn test_weekly_digest_quiet_week() {
+    let (repo, _temp_dir) = setup_week();
+
+    let digest = repo
+        .build_weekly_digest(&week_of("2025-01-06"), GENERATED_AT)
+        .expect("Failed to build digest");
+
+    assert!(digest.is_empty());
+    assert_eq!(digest.mood.average_mood, None);
+    assert_eq!(digest.best_day, None);
+    assert!(digest.top_activities.is_empty());
+    assert_eq!(
+        digest.sentences,
+        [
+            "No mood check-ins were recorded this week.",
+            "You did not reach your Walk goal.",
+        ]
+    );
+}
+
+#[test]
+fn test_weekly_digests_newest_first() {
+    let (repo, _temp_dir) = setup_week();
+    for start in ["2025-02-24", "2025-03-03", "2025-02-17"] {
+        repo.record_weekly_digest(&week_of(start), GENERATED_AT)
+            .expect("Failed to record digest");
+    }
+
+    let starts: Vec<String> = repo
+        .get_weekly_digests(Some(2))
+        .expect("Failed to get digests")
+        .into_iter()
+        .map(|d| d.week_start)
+        .collect();
+    assert_eq!(starts, ["2025-03-03", "2025-02-24"]);
+}
+
+#[test]
+fn test_weekly_digests_invalid_limit() {
+    let (repo, _temp_dir) = setup_week();
+
+    for limit in [0, 521] {
+        let result = repo.get_weekly_digests(Some(limit));
+        assert!(matches!(result, Err(InsightsError::InvalidLimit(l)) if l == limit));
+    }
+}
@@ -0,0 +1,470 @@
+// Scheduler simulation tests
+// Drives the scheduler through virtual time with a manual clock and checks
+// exactly when reminders fire, across every frequency, timezones and DST.
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveTime, TimeZone, Utc, Weekday};
+use chrono_tz::Tz;
+use tauri_sveltekit_modern_lib::db::Database;
+use tauri_sveltekit_modern_lib::features::insights::InsightsRepository;
+use tauri_sveltekit_modern_lib::features::mood::repository::MoodRepository;
+use tauri_sveltekit_modern_lib::features::scheduling::clock::{Clock, ManualClock};
+use tauri_sveltekit_modern_lib::features::scheduling::models::*;
+use tauri_sveltekit_modern_lib::features::scheduling::notifier::{
+    Notification, Notifier, RecordingNotifier,
+};
+use tauri_sveltekit_modern_lib::features::scheduling::repository::SchedulingRepository;
+use tauri_sveltekit_modern_lib::features::scheduling::{Scheduler, SchedulerSettings};
+use tauri_sveltekit_modern_lib::features::streaks::StreakRepository;
+use tauri_sveltekit_modern_lib::{QuietHoursWindow, QuietTimePolicy};
+use tempfile::TempDir;
+
+/// Scheduler on a manual clock, recording what it sends
+struct Simulation {
+    scheduler: Scheduler<Arc<ManualClock>, Arc<RecordingNotifier>>,
+    clock: Arc<ManualClock>,
+    notifier: Arc<RecordingNotifier>,
+    repo: SchedulingRepository,
+    db: Arc<Database>,
+    settings: SchedulerSettings,
+    _temp_dir: TempDir,
+}
+
+impl Simulation {
+    fn new(start: DateTime<Utc>) -> Self {
+        let temp_dir = TempDir::new().expect("Failed to create temp directory");
+        let db = Arc::new(
+            Database::new(temp_dir.path().to_path_buf()).expect("Failed to create database"),
+        );
+        let clock = Arc::new(ManualClock::new(start));
+        let notifier = Arc::new(RecordingNotifier::new());
+
+        Self {
+            scheduler: Scheduler::new(Arc::clone(&db), Arc::clone(&clock), Arc::clone(&notifier)),
+            clock,
+            notifier,
+            repo: SchedulingRepository::new(Arc::clone(&db)),
+            db,
+            settings: SchedulerSettings {
+                timezone: chrono_tz::UTC,
+                ..SchedulerSettings::default()
+            },
+            _temp_dir: temp_dir,
+        }
+    }
+
+    /// Tick every `step` until `end` (exclusive); returns when each schedule fired
+    fn run_until(
+        &mut self,
+        end: DateTime<Utc>,
+        step: Duration,
+    ) -> BTreeMap<i32, Vec<DateTime<Utc>>> {
+        let mut fired: BTreeMap<i32, Vec<DateTime<Utc>>> = BTreeMap::new();
+
+        while self.clock.now() < end {
+            self.scheduler.tick(&self.settings);
+            let now = self.clock.now();
+            for notification in self.notifier.take() {
+                let schedule_id = notification
+                    .extra("schedule_id")
+                    .and_then(|v| v.as_i64())
+                    .expect("Reminder without schedule_id")
+                    as i32;
+                fired.entry(schedule_id).or_default().push(now);
+            }
+            self.clock.advance(step);
+        }
+
+        fired
+    }
+
+    fn create(&self, request: CreateScheduleRequest) -> AssessmentSchedule {
+        self.repo
+            .create_schedule(&request)
+            .expect("Failed to create schedule")
+    }
+}
+
+fn utc(s: &str) -> DateTime<Utc> {
+    DateTime::parse_from_rfc3339(s)
+        .expect("Invalid test timestamp")
+        .with_timezone(&Utc)
+}
+
+fn request(
+    frequency: ScheduleFrequency,
+    time_of_day: &str,
+    timezone: &str,
+) -> CreateScheduleRequest {
+    CreateScheduleRequest {
+        assessment_type_id: Some(1),
+        frequency,
+        time_of_day: time_of_day.to_string(),
+        day_of_week: None,
+        day_of_month: None,
+        timezone: Some(timezone.to_string()),
+        target: None,
+        rrule: None,
+        dtstart: Some("2025-01-01".to_string()),
+        exdates: None,
+        missed_policy: None,
+    }
+}
+
+/// Local dates of 2025 matching `keep`, at `time` in `tz`, as UTC instants
+fn expected_2025(tz: Tz, time: &str, keep: impl Fn(NaiveDate) -> bool) -> Vec<DateTime<Utc>> {
+    let time = NaiveTime::parse_from_str(time, "%H:%M").expect("Invalid test time");
+    NaiveDate::from_ymd_opt(2025, 1, 1)
+        .unwrap()
+        .iter_days()
+        .take_while(|d| d.year() == 2025)
+        .filter(|d| keep(*d))
+        .map(|d| {
+            tz.from_local_datetime(&d.and_time(time))
+                .single()
+                .expect("Test times avoid DST gaps")
+                .with_timezone(&Utc)
+        })
+        .collect()
+}
+
+fn is_last_day_of_month(date: NaiveDate) -> bool {
+    date.succ_opt()
+        .is_some_and(|next| next.month() != date.month())
+}
+
+// ============================================================================
+// YEAR SIMULATION
+// ============================================================================
+
+#[test]
+fn test_year_of_reminders_fires_exactly_on_schedule() {
+    let mut sim = Simulation::new(utc("2025-01-01T00:00:00Z"));
+
+    // 09:00 in Berlin, across both DST changes
+    let daily = sim.create(request(ScheduleFrequency::Daily, "09:00", "Europe/Berlin"));
+
+    // Mondays 18:30 in New York
+    let weekly = sim.create(CreateScheduleRequest {
+        day_of_week: Some(1),
+        ..request(ScheduleFrequency::Weekly, "18:30", "America/New_York")
+    });
+
+    // Every other Friday 07:30 UTC, counted from the week of dtstart
+    let biweekly = sim.create(CreateScheduleRequest {
+        day_of_week: Some(5),
+        ..request(ScheduleFrequency::Biweekly, "07:30", "UTC")
+    });
+
+    // "The 31st" falls back to the last day of shorter months
+    let monthly = sim.create(CreateScheduleRequest {
+        day_of_month: Some(31),
+        ..request(ScheduleFrequency::Monthly, "12:00", "Asia/Tokyo")
+    });
+
+    // Twice a day on Monday, Wednesday and Friday
+    let custom = sim.create(CreateScheduleRequest {
+        rrule: Some("FREQ=WEEKLY;BYDAY=MO,WE,FR;BYHOUR=8,20;BYMINUTE=0".to_string()),
+        ..request(ScheduleFrequency::Custom, "08:00", "UTC")
+    });
+
+    // Every time above is on the half hour, so 30-minute ticks land on it exactly
+    let fired = sim.run_until(utc("2026-01-01T00:00:00Z"), Duration::minutes(30));
+
+    let berlin: Tz = "Europe/Berlin".parse().unwrap();
+    let new_york: Tz = "America/New_York".parse().unwrap();
+    let tokyo: Tz = "Asia/Tokyo".parse().unwrap();
+
+    let expected_daily = expected_2025(berlin, "09:00", |_| true);
+    assert_eq!(expected_daily.len(), 365);
+    assert_eq!(fired[&daily.id], expected_daily);
+    // 08:00 UTC in winter, 07:00 UTC in summer
+    assert_eq!(fired[&daily.id][0], utc("2025-01-01T08:00:00Z"));
+    assert_eq!(fired[&daily.id][180], utc("2025-06-30T07:00:00Z"));
+
+    let expected_weekly = expected_2025(new_york, "18:30", |d| d.weekday() == Weekday::Mon);
+    assert_eq!(expected_weekly.len(), 52);
+    assert_eq!(fired[&weekly.id], expected_weekly);
+
+    let first_friday = NaiveDate::from_ymd_opt(2025, 1, 3).unwrap();
+    let expected_biweekly = expected_2025(chrono_tz::UTC, "07:30", |d| {
+        d.weekday() == Weekday::Fri && (d - first_friday).num_days() % 14 == 0
+    });
+    assert_eq!(expected_biweekly.len(), 26);
+    assert_eq!(fired[&biweekly.id], expected_biweekly);
+
+    let expected_monthly = expected_2025(tokyo, "12:00", is_last_day_of_month);
+    assert_eq!(expected_monthly.len(), 12);
+    assert_eq!(fired[&monthly.id], expected_monthly);
+    assert_eq!(fired[&monthly.id][1], utc("2025-02-28T03:00:00Z"));
+
+    let mut expected_custom: Vec<_> = ["08:00", "20:00"]
+        .iter()
+        .flat_map(|time| {
+            expected_2025(chrono_tz::UTC, time, |d| {
+                matches!(d.weekday(), Weekday::Mon | Weekday::Wed | Weekday::Fri)
+            })
+        })
+        .collect();
+    expected_custom.sort();
+    assert_eq!(fired[&custom.id], expected_custom);
+
+    // Every firing is logged once
+    for (schedule_id, times) in &fired {
+        let events = sim
+            .repo
+            .get_reminder_events(*schedule_id, None)
+            .expect("Failed to get events");
+        assert_eq!(events.len(), times.len());
+        assert!(events
+            .iter()
+            .all(|e| e.event_type == ReminderEventType::Fired));
+    }
+}
+
+// ============================================================================
+// QUIET HOURS, SNOOZE AND EXCEPTIONS
+// ============================================================================
+
+#[test]
+fn test_quiet_hours_defer_or_drop_reminders() {
+    // Quiet 08:00-10:00 on Sundays; 2025-03-02 is a Sunday
+    let quiet_sundays = vec![QuietHoursWindow {
+        weekday: 0,
+        start: "08:00".to_string(),
+        end: "10:00".to_string(),
+    }];
+
+    let mut sim = Simulation::new(utc("2025-03-01T00:00:00Z"));
+    let daily = sim.create(request(ScheduleFrequency::Daily, "09:00", "UTC"));
+    sim.settings.notifications.quiet_hours = quiet_sundays.clone();
+
+    let fired = sim.run_until(utc("2025-03-04T00:00:00Z"), Duration::minutes(15));
+    assert_eq!(
+        fired[&daily.id],
+        vec![
+            utc("2025-03-01T09:00:00Z"),
+            utc("2025-03-02T10:00:00Z"), // Deferred to the end of quiet time
+            utc("2025-03-03T09:00:00Z"),
+        ]
+    );
+
+    let mut sim = Simulation::new(utc("2025-03-01T00:00:00Z"));
+    let daily = sim.create(request(ScheduleFrequency::Daily, "09:00", "UTC"));
+    sim.settings.notifications.quiet_hours = quiet_sundays;
+    sim.settings.notifications.quiet_time_policy = QuietTimePolicy::Drop;
+
+    let fired = sim.run_until(utc("2025-03-04T00:00:00Z"), Duration::minutes(15));
+    assert_eq!(
+        fired[&daily.id],
+        vec![utc("2025-03-01T09:00:00Z"), utc("2025-03-03T09:00:00Z")]
+    );
+}
+
+#[test]
+fn test_disabled_notifications_fire_nothing() {
+    let mut sim = Simulation::new(utc("2025-03-01T00:00:00Z"));
+    let daily = sim.create(request(ScheduleFrequency::Daily, "09:00", "UTC"));
+    sim.settings.notifications.notification_enabled = false;
+
+    let fired = sim.run_until(utc("2025-03-08T00:00:00Z"), Duration::minutes(15));
+    assert!(fired.is_empty());
+
+    // Turning them back on doesn't release a backlog
+    sim.settings.notifications.notification_enabled = true;
+    let fired = sim.run_until(utc("2025-03-09T00:00:00Z"), Duration::minutes(15));
+    assert_eq!(fired[&daily.id], vec![utc("2025-03-08T09:00:00Z")]);
+}
+
+#[test]
+fn test_snoozed_reminder_fires_again_after_snooze() {
+    let mut sim = Simulation::new(utc("2025-03-01T08:00:00Z"));
+    let daily = sim.create(request(ScheduleFrequency::Daily, "09:00", "UTC"));
+
+    let fired = sim.run_until(utc("2025-03-01T09:05:00Z"), Duration::minutes(5));
+    assert_eq!(fired[&daily.id], vec![utc("2025-03-01T09:00:00Z")]);
+
+    let reminder = sim
+        .repo
+        .get_reminder_events(daily.id, Some(1))
+        .expect("Failed to get events")
+        .remove(0);
+    sim.repo
+        .snooze_reminder(reminder.id, 30, sim.clock.now())
+        .expect("Failed to snooze");
+
+    let fired = sim.run_until(utc("2025-03-02T00:00:00Z"), Duration::minutes(5));
+    assert_eq!(fired[&daily.id], vec![utc("2025-03-01T09:35:00Z")]);
+}
+
+#[test]
+fn test_exception_dates_and_disabled_schedules_are_skipped() {
+    let mut sim = Simulation::new(utc("2025-03-01T00:00:00Z"));
+    let daily = sim.create(CreateScheduleRequest {
+        exdates: Some(vec!["2025-03-02".to_string()]),
+        ..request(ScheduleFrequency::Daily, "09:00", "UTC")
+    });
+    let paused = sim.create(request(ScheduleFrequency::Daily, "09:00", "UTC"));
+    sim.repo
+        .update_schedule(
+            paused.id,
+            &UpdateScheduleRequest {
+                frequency: None,
+                time_of_day: None,
+                day_of_week: None,
+                day_of_month: None,
+                timezone: None,
+                target: None,
+                enabled: Some(false),
+                rrule: None,
+                dtstart: None,
+                exdates: None,
+                missed_policy: None,
+            },
+        )
+        .expect("Failed to disable schedule");
+
+    let fired = sim.run_until(utc("2025-03-04T00:00:00Z"), Duration::hours(1));
+    assert_eq!(
+        fired[&daily.id],
+        vec![utc("2025-03-01T09:00:00Z"), utc("2025-03-03T09:00:00Z")]
+    );
+    assert!(!fired.contains_key(&paused.id));
+}
+
+#[test]
+fn test_weekly_digest_announced_once() {
+    // Wednesday; the last completed week started on 2025-02-24
+    let mut sim = Simulation::new(utc("2025-03-05T10:00:00Z"));
+    MoodRepository::new(Arc::clone(&sim.db))
+        .create_mood_checkin(5, vec![], None)
+        .expect("Failed to create check-in");
+    {
+        let conn = sim.db.get_connection();
+        let conn = conn.lock();
+        conn.execute(
+            "UPDATE mood_checkins SET created_at = '2025-02-26 08:00:00'",
+            [],
+        )
+        .expect("Failed to set check-in timestamp");
+    }
+
+    sim.scheduler.tick(&sim.settings);
+    let sent = sim.notifier.take();
+    assert_eq!(sent.len(), 1);
+    assert_eq!(sent[0].title, "Your Week in Review");
+    assert_eq!(
+        sent[0].extra("week_start").and_then(|v| v.as_str()),
+        Some("2025-02-24")
+    );
+
+    sim.clock.advance(Duration::hours(1));
+    sim.scheduler.tick(&sim.settings);
+    assert!(sim.notifier.take().is_empty());
+
+    // The next week had no check-ins: stored, but not announced
+    sim.clock.set(utc("2025-03-10T10:00:00Z"));
+    sim.scheduler.tick(&sim.settings);
+    assert!(sim.notifier.take().is_empty());
+    let digests = InsightsRepository::new(Arc::clone(&sim.db))
+        .get_weekly_digests(None)
+        .expect("Failed to get digests");
+    let starts: Vec<&str> = digests.iter().map(|d| d.week_start.as_str()).collect();
+    assert_eq!(starts, ["2025-03-03", "2025-02-24"]);
+    assert!(digests.iter().all(|d| d.notified_at.is_some()));
+}
+
+/// Records notifications, or fails every one while `failing` is set
+#[derive(Default)]
+struct FlakyNotifier {
+    failing: AtomicBool,
+    sent: RecordingNotifier,
+}
+
+impl Notifier for FlakyNotifier {
+    fn notify(&self, notification: &Notification) -> anyhow::Result<()> {
+        if self.failing.load(Ordering::SeqCst) {
+            anyhow::bail!("Notification service unavailable");
+        }
+        self.sent.notify(notification)
+    }
+}
+
+#[test]
+fn test_failed_streak_notification_is_retried() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let db =
+        Arc::new(Database::new(temp_dir.path().to_path_buf()).expect("Failed to create database"));
+    {
+        let conn = db.get_connection();
+        let conn = conn.lock();
+        for day in ["2025-03-03", "2025-03-04", "2025-03-05"] {
+            conn.execute(
+                "INSERT INTO mood_checkins (mood_rating, created_at) VALUES (5, ?)",
+                [format!("{} 08:00:00", day)],
+            )
+            .expect("Failed to insert check-in");
+        }
+    }
+    let clock = Arc::new(ManualClock::new(utc("2025-03-05T20:00:00Z")));
+    let notifier = Arc::new(FlakyNotifier::default());
+    let mut scheduler = Scheduler::new(Arc::clone(&db), Arc::clone(&clock), Arc::clone(&notifier));
+    let settings = SchedulerSettings {
+        timezone: chrono_tz::UTC,
+        ..SchedulerSettings::default()
+    };
+    let streak_notifications = || {
+        notifier
+            .sent
+            .take()
+            .into_iter()
+            .filter(|n| n.title == "Streak Milestone")
+            .count()
+    };
+
+    notifier.failing.store(true, Ordering::SeqCst);
+    scheduler.tick(&settings);
+    assert_eq!(streak_notifications(), 0);
+    let pending = StreakRepository::new(Arc::clone(&db))
+        .get_pending_milestones()
+        .expect("Failed to get pending milestones");
+    assert_eq!(pending.len(), 1);
+
+    // Once notifications work again the milestone is announced exactly once
+    notifier.failing.store(false, Ordering::SeqCst);
+    clock.advance(Duration::minutes(1));
+    scheduler.tick(&settings);
+    assert_eq!(streak_notifications(), 1);
+
+    clock.advance(Duration::minutes(1));
+    scheduler.tick(&settings);
+    assert_eq!(streak_notifications(), 0);
+}
+
+#[test]
+fn test_streak_milestone_recorded_after_new_checkin() {
+    let mut sim = Simulation::new(utc("2025-03-05T07:00:00Z"));
+    let insert_checkin = |created_at: &str| {
+        let conn = sim.db.get_connection();
+        let conn = conn.lock();
+        conn.execute(
+            "INSERT INTO mood_checkins (mood_rating, created_at) VALUES (5, ?)",
+            [created_at],
+        )
+        .expect("Failed to insert check-in");
+    };
+    insert_checkin("2025-03-03 08:00:00");
+    insert_checkin("2025-03-04 08:00:00");
+
+    sim.scheduler.tick(&sim.settings);
+    assert!(sim.notifier.take().is_empty());
+
+    // A check-in later the same day completes the 3-day streak
+    insert_checkin("2025-03-05 08:00:00");
+    sim.clock.advance(Duration::hours(1));
+    sim.scheduler.tick(&sim.settings);
+    let titles: Vec<&str> = sim.notifier.take().into_iter().map(|n| n.title).collect();
+    assert_eq!(titles, ["Streak Milestone"]);
+}
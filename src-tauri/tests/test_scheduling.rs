@@ -3,6 +3,15 @@
 use std::str::FromStr;
 use std::sync::Arc;
 use tauri_sveltekit_modern_lib::db::Database;
+use tauri_sveltekit_modern_lib::features::activities::{
+    expand_plan_occurrences, ActivityRepository, CreatePlannedActivityRequest,
+};
+use tauri_sveltekit_modern_lib::features::assessments::models::{AssessmentStatus, SeverityLevel};
+use tauri_sveltekit_modern_lib::features::assessments::repository::AssessmentRepository;
+use tauri_sveltekit_modern_lib::features::scheduling::adaptive::{
+    AdaptiveRuleRequest, SeverityComparison,
+};
+use tauri_sveltekit_modern_lib::features::scheduling::ical::{export_calendar, parse_calendar};
 use tauri_sveltekit_modern_lib::features::scheduling::models::*;
 use tauri_sveltekit_modern_lib::features::scheduling::repository::SchedulingRepository;
 use tempfile::TempDir;
@@ -26,19 +35,25 @@ fn test_create_schedule_daily() {
     let (repo, _temp_dir) = setup_test_repo();
 
     let request = CreateScheduleRequest {
-        assessment_type_id: 1, // PHQ-9
+        assessment_type_id: Some(1), // PHQ-9
         frequency: ScheduleFrequency::Daily,
         time_of_day: "09:00".to_string(),
         day_of_week: None,
         day_of_month: None,
+        timezone: None,
+        target: None,
+        rrule: None,
+        dtstart: None,
+        exdates: None,
+        missed_policy: None,
     };
 
     let schedule = repo
         .create_schedule(&request)
         .expect("Failed to create daily schedule");
 
-    assert_eq!(schedule.assessment_type_id, 1);
-    assert_eq!(schedule.assessment_type_code, "PHQ9");
+    assert_eq!(schedule.assessment_type_id, Some(1));
+    assert_eq!(schedule.assessment_type_code.as_deref(), Some("PHQ9"));
     assert_eq!(schedule.frequency, ScheduleFrequency::Daily);
     assert_eq!(schedule.time_of_day, "09:00");
     assert_eq!(schedule.day_of_week, None);
@@ -52,18 +67,24 @@ fn test_create_schedule_weekly() {
     let (repo, _temp_dir) = setup_test_repo();
 
     let request = CreateScheduleRequest {
-        assessment_type_id: 2, // GAD-7
+        assessment_type_id: Some(2), // GAD-7
         frequency: ScheduleFrequency::Weekly,
         time_of_day: "14:30".to_string(),
         day_of_week: Some(3), // Wednesday
         day_of_month: None,
+        timezone: None,
+        target: None,
+        rrule: None,
+        dtstart: None,
+        exdates: None,
+        missed_policy: None,
     };
 
     let schedule = repo
         .create_schedule(&request)
         .expect("Failed to create weekly schedule");
 
-    assert_eq!(schedule.assessment_type_code, "GAD7");
+    assert_eq!(schedule.assessment_type_code.as_deref(), Some("GAD7"));
     assert_eq!(schedule.frequency, ScheduleFrequency::Weekly);
     assert_eq!(schedule.time_of_day, "14:30");
     assert_eq!(schedule.day_of_week, Some(3));
@@ -75,18 +96,24 @@ fn test_create_schedule_biweekly() {
     let (repo, _temp_dir) = setup_test_repo();
 
     let request = CreateScheduleRequest {
-        assessment_type_id: 3, // CESD
+        assessment_type_id: Some(3), // CESD
         frequency: ScheduleFrequency::Biweekly,
         time_of_day: "10:00".to_string(),
         day_of_week: Some(1), // Monday
         day_of_month: None,
+        timezone: None,
+        target: None,
+        rrule: None,
+        dtstart: None,
+        exdates: None,
+        missed_policy: None,
     };
 
     let schedule = repo
         .create_schedule(&request)
         .expect("Failed to create biweekly schedule");
 
-    assert_eq!(schedule.assessment_type_code, "CESD");
+    assert_eq!(schedule.assessment_type_code.as_deref(), Some("CESD"));
     assert_eq!(schedule.frequency, ScheduleFrequency::Biweekly);
     assert_eq!(schedule.day_of_week, Some(1));
 }
@@ -96,18 +123,24 @@ fn test_create_schedule_monthly() {
     let (repo, _temp_dir) = setup_test_repo();
 
     let request = CreateScheduleRequest {
-        assessment_type_id: 4, // OASIS
+        assessment_type_id: Some(4), // OASIS
         frequency: ScheduleFrequency::Monthly,
         time_of_day: "08:00".to_string(),
         day_of_week: None,
         day_of_month: Some(15),
+        timezone: None,
+        target: None,
+        rrule: None,
+        dtstart: None,
+        exdates: None,
+        missed_policy: None,
     };
 
     let schedule = repo
         .create_schedule(&request)
         .expect("Failed to create monthly schedule");
 
-    assert_eq!(schedule.assessment_type_code, "OASIS");
+    assert_eq!(schedule.assessment_type_code.as_deref(), Some("OASIS"));
     assert_eq!(schedule.frequency, ScheduleFrequency::Monthly);
     assert_eq!(schedule.day_of_week, None);
     assert_eq!(schedule.day_of_month, Some(15));
@@ -118,11 +151,17 @@ fn test_create_schedule_invalid_assessment_type() {
     let (repo, _temp_dir) = setup_test_repo();
 
     let request = CreateScheduleRequest {
-        assessment_type_id: 999, // Non-existent
+        assessment_type_id: Some(999), // Non-existent
         frequency: ScheduleFrequency::Daily,
         time_of_day: "09:00".to_string(),
         day_of_week: None,
         day_of_month: None,
+        timezone: None,
+        target: None,
+        rrule: None,
+        dtstart: None,
+        exdates: None,
+        missed_policy: None,
     };
 
     let result = repo.create_schedule(&request);
@@ -134,11 +173,17 @@ fn test_create_schedule_invalid_time_format() {
     let (repo, _temp_dir) = setup_test_repo();
 
     let request = CreateScheduleRequest {
-        assessment_type_id: 1,
+        assessment_type_id: Some(1),
         frequency: ScheduleFrequency::Daily,
         time_of_day: "25:00".to_string(), // Invalid hour
         day_of_week: None,
         day_of_month: None,
+        timezone: None,
+        target: None,
+        rrule: None,
+        dtstart: None,
+        exdates: None,
+        missed_policy: None,
     };
 
     let result = repo.create_schedule(&request);
@@ -150,11 +195,17 @@ fn test_create_schedule_weekly_missing_day_of_week() {
     let (repo, _temp_dir) = setup_test_repo();
 
     let request = CreateScheduleRequest {
-        assessment_type_id: 1,
+        assessment_type_id: Some(1),
         frequency: ScheduleFrequency::Weekly,
         time_of_day: "09:00".to_string(),
         day_of_week: None, // Required for weekly
         day_of_month: None,
+        timezone: None,
+        target: None,
+        rrule: None,
+        dtstart: None,
+        exdates: None,
+        missed_policy: None,
     };
 
     let result = repo.create_schedule(&request);
@@ -169,11 +220,17 @@ fn test_create_schedule_monthly_missing_day_of_month() {
     let (repo, _temp_dir) = setup_test_repo();
 
     let request = CreateScheduleRequest {
-        assessment_type_id: 1,
+        assessment_type_id: Some(1),
         frequency: ScheduleFrequency::Monthly,
         time_of_day: "09:00".to_string(),
         day_of_week: None,
         day_of_month: None, // Required for monthly
+        timezone: None,
+        target: None,
+        rrule: None,
+        dtstart: None,
+        exdates: None,
+        missed_policy: None,
     };
 
     let result = repo.create_schedule(&request);
@@ -188,11 +245,17 @@ fn test_create_schedule_invalid_day_of_week() {
     let (repo, _temp_dir) = setup_test_repo();
 
     let request = CreateScheduleRequest {
-        assessment_type_id: 1,
+        assessment_type_id: Some(1),
         frequency: ScheduleFrequency::Weekly,
         time_of_day: "09:00".to_string(),
         day_of_week: Some(7), // Invalid (0-6 only)
         day_of_month: None,
+        timezone: None,
+        target: None,
+        rrule: None,
+        dtstart: None,
+        exdates: None,
+        missed_policy: None,
     };
 
     let result = repo.create_schedule(&request);
@@ -204,11 +267,17 @@ fn test_create_schedule_invalid_day_of_month() {
     let (repo, _temp_dir) = setup_test_repo();
 
     let request = CreateScheduleRequest {
-        assessment_type_id: 1,
+        assessment_type_id: Some(1),
         frequency: ScheduleFrequency::Monthly,
         time_of_day: "09:00".to_string(),
         day_of_week: None,
         day_of_month: Some(32), // Invalid (1-31 only)
+        timezone: None,
+        target: None,
+        rrule: None,
+        dtstart: None,
+        exdates: None,
+        missed_policy: None,
     };
 
     let result = repo.create_schedule(&request);
@@ -225,11 +294,17 @@ fn test_update_schedule_frequency() {
 
     // Create initial schedule
     let request = CreateScheduleRequest {
-        assessment_type_id: 1,
+        assessment_type_id: Some(1),
         frequency: ScheduleFrequency::Daily,
         time_of_day: "09:00".to_string(),
         day_of_week: None,
         day_of_month: None,
+        timezone: None,
+        target: None,
+        rrule: None,
+        dtstart: None,
+        exdates: None,
+        missed_policy: None,
     };
 
     let schedule = repo
@@ -243,6 +318,12 @@ fn test_update_schedule_frequency() {
         day_of_week: Some(3), // Required for weekly
         day_of_month: None,
         enabled: None,
+        timezone: None,
+        target: None,
+        rrule: None,
+        dtstart: None,
+        exdates: None,
+        missed_policy: None,
     };
 
     let updated = repo
@@ -258,11 +339,17 @@ fn test_update_schedule_time() {
     let (repo, _temp_dir) = setup_test_repo();
 
     let request = CreateScheduleRequest {
-        assessment_type_id: 1,
+        assessment_type_id: Some(1),
         frequency: ScheduleFrequency::Daily,
         time_of_day: "09:00".to_string(),
         day_of_week: None,
         day_of_month: None,
+        timezone: None,
+        target: None,
+        rrule: None,
+        dtstart: None,
+        exdates: None,
+        missed_policy: None,
     };
 
     let schedule = repo
@@ -275,6 +362,12 @@ fn test_update_schedule_time() {
         day_of_week: None,
         day_of_month: None,
         enabled: None,
+        timezone: None,
+        target: None,
+        rrule: None,
+        dtstart: None,
+        exdates: None,
+        missed_policy: None,
     };
 
     let updated = repo
@@ -289,11 +382,17 @@ fn test_update_schedule_disable() {
     let (repo, _temp_dir) = setup_test_repo();
 
     let request = CreateScheduleRequest {
-        assessment_type_id: 1,
+        assessment_type_id: Some(1),
         frequency: ScheduleFrequency::Daily,
         time_of_day: "09:00".to_string(),
         day_of_week: None,
         day_of_month: None,
+        timezone: None,
+        target: None,
+        rrule: None,
+        dtstart: None,
+        exdates: None,
+        missed_policy: None,
     };
 
     let schedule = repo
@@ -307,6 +406,12 @@ fn test_update_schedule_disable() {
         day_of_week: None,
         day_of_month: None,
         enabled: Some(false),
+        timezone: None,
+        target: None,
+        rrule: None,
+        dtstart: None,
+        exdates: None,
+        missed_policy: None,
     };
 
     let updated = repo
@@ -321,11 +426,17 @@ fn test_update_schedule_invalid_time() {
     let (repo, _temp_dir) = setup_test_repo();
 
     let request = CreateScheduleRequest {
-        assessment_type_id: 1,
+        assessment_type_id: Some(1),
         frequency: ScheduleFrequency::Daily,
         time_of_day: "09:00".to_string(),
         day_of_week: None,
         day_of_month: None,
+        timezone: None,
+        target: None,
+        rrule: None,
+        dtstart: None,
+        exdates: None,
+        missed_policy: None,
     };
 
     let schedule = repo
@@ -338,6 +449,12 @@ fn test_update_schedule_invalid_time() {
         day_of_week: None,
         day_of_month: None,
         enabled: None,
+        timezone: None,
+        target: None,
+        rrule: None,
+        dtstart: None,
+        exdates: None,
+        missed_policy: None,
     };
 
     let result = repo.update_schedule(schedule.id, &update);
@@ -354,6 +471,12 @@ fn test_update_schedule_not_found() {
         day_of_week: None,
         day_of_month: None,
         enabled: None,
+        timezone: None,
+        target: None,
+        rrule: None,
+        dtstart: None,
+        exdates: None,
+        missed_policy: None,
     };
 
     let result = repo.update_schedule(999, &update);
@@ -365,11 +488,17 @@ fn test_update_schedule_no_changes() {
     let (repo, _temp_dir) = setup_test_repo();
 
     let request = CreateScheduleRequest {
-        assessment_type_id: 1,
+        assessment_type_id: Some(1),
         frequency: ScheduleFrequency::Daily,
         time_of_day: "09:00".to_string(),
         day_of_week: None,
         day_of_month: None,
+        timezone: None,
+        target: None,
+        rrule: None,
+        dtstart: None,
+        exdates: None,
+        missed_policy: None,
     };
 
     let schedule = repo
@@ -383,6 +512,12 @@ fn test_update_schedule_no_changes() {
         day_of_week: None,
         day_of_month: None,
         enabled: None,
+        timezone: None,
+        target: None,
+        rrule: None,
+        dtstart: None,
+        exdates: None,
+        missed_policy: None,
     };
 
     let updated = repo
@@ -403,11 +538,17 @@ fn test_delete_schedule() {
     let (repo, _temp_dir) = setup_test_repo();
 
     let request = CreateScheduleRequest {
-        assessment_type_id: 1,
+        assessment_type_id: Some(1),
         frequency: ScheduleFrequency::Daily,
         time_of_day: "09:00".to_string(),
         day_of_week: None,
         day_of_month: None,
+        timezone: None,
+        target: None,
+        rrule: None,
+        dtstart: None,
+        exdates: None,
+        missed_policy: None,
     };
 
     let schedule = repo
@@ -448,19 +589,31 @@ fn test_get_schedules_all() {
 
     // Create multiple schedules
     let request1 = CreateScheduleRequest {
-        assessment_type_id: 1,
+        assessment_type_id: Some(1),
         frequency: ScheduleFrequency::Daily,
         time_of_day: "09:00".to_string(),
         day_of_week: None,
         day_of_month: None,
+        timezone: None,
+        target: None,
+        rrule: None,
+        dtstart: None,
+        exdates: None,
+        missed_policy: None,
     };
 
     let request2 = CreateScheduleRequest {
-        assessment_type_id: 2,
+        assessment_type_id: Some(2),
         frequency: ScheduleFrequency::Weekly,
         time_of_day: "14:00".to_string(),
         day_of_week: Some(1),
         day_of_month: None,
+        timezone: None,
+        target: None,
+        rrule: None,
+        dtstart: None,
+        exdates: None,
+        missed_policy: None,
     };
 
     repo.create_schedule(&request1)
@@ -478,11 +631,17 @@ fn test_get_schedules_enabled_only() {
 
     // Create enabled schedule
     let request1 = CreateScheduleRequest {
-        assessment_type_id: 1,
+        assessment_type_id: Some(1),
         frequency: ScheduleFrequency::Daily,
         time_of_day: "09:00".to_string(),
         day_of_week: None,
         day_of_month: None,
+        timezone: None,
+        target: None,
+        rrule: None,
+        dtstart: None,
+        exdates: None,
+        missed_policy: None,
     };
 
     let schedule1 = repo
@@ -491,11 +650,17 @@ fn test_get_schedules_enabled_only() {
 
     // Create and disable second schedule
     let request2 = CreateScheduleRequest {
-        assessment_type_id: 2,
+        assessment_type_id: Some(2),
         frequency: ScheduleFrequency::Weekly,
         time_of_day: "14:00".to_string(),
         day_of_week: Some(1),
         day_of_month: None,
+        timezone: None,
+        target: None,
+        rrule: None,
+        dtstart: None,
+        exdates: None,
+        missed_policy: None,
     };
 
     let schedule2 = repo
@@ -508,6 +673,12 @@ fn test_get_schedules_enabled_only() {
         day_of_week: None,
         day_of_month: None,
         enabled: Some(false),
+        timezone: None,
+        target: None,
+        rrule: None,
+        dtstart: None,
+        exdates: None,
+        missed_policy: None,
     };
 
     repo.update_schedule(schedule2.id, &update)
@@ -532,11 +703,17 @@ fn test_get_schedule_by_id() {
     let (repo, _temp_dir) = setup_test_repo();
 
     let request = CreateScheduleRequest {
-        assessment_type_id: 1,
+        assessment_type_id: Some(1),
         frequency: ScheduleFrequency::Daily,
         time_of_day: "09:00".to_string(),
         day_of_week: None,
         day_of_month: None,
+        timezone: None,
+        target: None,
+        rrule: None,
+        dtstart: None,
+        exdates: None,
+        missed_policy: None,
     };
 
     let created = repo
@@ -580,11 +757,17 @@ fn test_get_due_schedules_future_time() {
 
     // Create schedule for late in the day (likely future)
     let request = CreateScheduleRequest {
-        assessment_type_id: 1,
+        assessment_type_id: Some(1),
         frequency: ScheduleFrequency::Daily,
         time_of_day: "23:59".to_string(),
         day_of_week: None,
         day_of_month: None,
+        timezone: None,
+        target: None,
+        rrule: None,
+        dtstart: None,
+        exdates: None,
+        missed_policy: None,
     };
 
     repo.create_schedule(&request)
@@ -603,11 +786,17 @@ fn test_get_due_schedules_past_time() {
 
     // Create schedule for early in the day (likely past)
     let request = CreateScheduleRequest {
-        assessment_type_id: 1,
+        assessment_type_id: Some(1),
         frequency: ScheduleFrequency::Daily,
         time_of_day: "00:01".to_string(),
         day_of_week: None,
         day_of_month: None,
+        timezone: None,
+        target: None,
+        rrule: None,
+        dtstart: None,
+        exdates: None,
+        missed_policy: None,
     };
 
     repo.create_schedule(&request)
@@ -625,11 +814,17 @@ fn test_get_due_schedules_disabled_excluded() {
     let (repo, _temp_dir) = setup_test_repo();
 
     let request = CreateScheduleRequest {
-        assessment_type_id: 1,
+        assessment_type_id: Some(1),
         frequency: ScheduleFrequency::Daily,
         time_of_day: "00:01".to_string(),
         day_of_week: None,
         day_of_month: None,
+        timezone: None,
+        target: None,
+        rrule: None,
+        dtstart: None,
+        exdates: None,
+        missed_policy: None,
     };
 
     let schedule = repo
@@ -643,6 +838,12 @@ fn test_get_due_schedules_disabled_excluded() {
         day_of_week: None,
         day_of_month: None,
         enabled: Some(false),
+        timezone: None,
+        target: None,
+        rrule: None,
+        dtstart: None,
+        exdates: None,
+        missed_policy: None,
     };
 
     repo.update_schedule(schedule.id, &update)
@@ -664,11 +865,17 @@ fn test_mark_triggered() {
     let (repo, _temp_dir) = setup_test_repo();
 
     let request = CreateScheduleRequest {
-        assessment_type_id: 1,
+        assessment_type_id: Some(1),
         frequency: ScheduleFrequency::Daily,
         time_of_day: "09:00".to_string(),
         day_of_week: None,
         day_of_month: None,
+        timezone: None,
+        target: None,
+        rrule: None,
+        dtstart: None,
+        exdates: None,
+        missed_policy: None,
     };
 
     let schedule = repo
@@ -699,11 +906,17 @@ fn test_mark_triggered_excludes_from_due() {
 
     // Create schedule with past time
     let request = CreateScheduleRequest {
-        assessment_type_id: 1,
+        assessment_type_id: Some(1),
         frequency: ScheduleFrequency::Daily,
         time_of_day: "00:01".to_string(),
         day_of_week: None,
         day_of_month: None,
+        timezone: None,
+        target: None,
+        rrule: None,
+        dtstart: None,
+        exdates: None,
+        missed_policy: None,
     };
 
     let schedule = repo
@@ -727,6 +940,1631 @@ fn test_mark_triggered_excludes_from_due() {
     assert_eq!(due_after.len(), 0);
 }
 
+// ============================================================================
+// TIMEZONE TESTS
+// ============================================================================
+
+fn utc(s: &str) -> chrono::DateTime<chrono::Utc> {
+    chrono::DateTime::parse_from_rfc3339(s)
+        .expect("Invalid test timestamp")
+        .with_timezone(&chrono::Utc)
+}
+
+#[test]
+fn test_create_schedule_with_timezone() {
+    let (repo, _temp_dir) = setup_test_repo();
+
+    let request = CreateScheduleRequest {
+        assessment_type_id: Some(1),
+        frequency: ScheduleFrequency::Daily,
+        time_of_day: "09:00".to_string(),
+        day_of_week: None,
+        day_of_month: None,
+        timezone: Some("Europe/Berlin".to_string()),
+        target: None,
+        rrule: None,
+        dtstart: None,
+        exdates: None,
+        missed_policy: None,
+    };
+
+    let schedule = repo
+        .create_schedule(&request)
+        .expect("Failed to create schedule");
+    assert_eq!(schedule.timezone, "Europe/Berlin");
+
+    // Without a timezone the system timezone is stored
+    let request = CreateScheduleRequest {
+        timezone: None,
+        ..request
+    };
+    let schedule = repo
+        .create_schedule(&request)
+        .expect("Failed to create schedule");
+    assert_eq!(schedule.timezone, system_timezone());
+}
+
+#[test]
+fn test_create_schedule_invalid_timezone() {
+    let (repo, _temp_dir) = setup_test_repo();
+
+    let request = CreateScheduleRequest {
+        assessment_type_id: Some(1),
+        frequency: ScheduleFrequency::Daily,
+        time_of_day: "09:00".to_string(),
+        day_of_week: None,
+        day_of_month: None,
+        timezone: Some("Not/A_Zone".to_string()),
+        target: None,
+        rrule: None,
+        dtstart: None,
+        exdates: None,
+        missed_policy: None,
+    };
+
+    let result = repo.create_schedule(&request);
+    assert!(matches!(result, Err(SchedulingError::InvalidTimezone(_))));
+}
+
+#[test]
+fn test_update_schedule_timezone() {
+    let (repo, _temp_dir) = setup_test_repo();
+
+    let request = CreateScheduleRequest {
+        assessment_type_id: Some(1),
+        frequency: ScheduleFrequency::Daily,
+        time_of_day: "09:00".to_string(),
+        day_of_week: None,
+        day_of_month: None,
+        timezone: Some("UTC".to_string()),
+        target: None,
+        rrule: None,
+        dtstart: None,
+        exdates: None,
+        missed_policy: None,
+    };
+    let schedule = repo
+        .create_schedule(&request)
+        .expect("Failed to create schedule");
+
+    let update = UpdateScheduleRequest {
+        frequency: None,
+        time_of_day: None,
+        day_of_week: None,
+        day_of_month: None,
+        timezone: Some("Asia/Tokyo".to_string()),
+        enabled: None,
+        target: None,
+        rrule: None,
+        dtstart: None,
+        exdates: None,
+        missed_policy: None,
+    };
+    let updated = repo
+        .update_schedule(schedule.id, &update)
+        .expect("Failed to update schedule");
+    assert_eq!(updated.timezone, "Asia/Tokyo");
+}
+
+#[test]
+fn test_get_due_schedules_at_uses_schedule_timezone() {
+    let (repo, _temp_dir) = setup_test_repo();
+
+    for timezone in ["Asia/Tokyo", "America/New_York"] {
+        let request = CreateScheduleRequest {
+            assessment_type_id: Some(1),
+            frequency: ScheduleFrequency::Daily,
+            time_of_day: "09:00".to_string(),
+            day_of_week: None,
+            day_of_month: None,
+            timezone: Some(timezone.to_string()),
+            target: None,
+            rrule: None,
+            // Schedules don't fire before their start
+            dtstart: Some("2025-06-01".to_string()),
+            exdates: None,
+            missed_policy: None,
+        };
+        repo.create_schedule(&request)
+            .expect("Failed to create schedule");
+    }
+
+    // 2025-06-10 01:00Z = 10:00 JST (due) and 21:00 EDT on 06-09 (due)
+    let due = repo
+        .get_due_schedules_at(utc("2025-06-10T01:00:00Z"))
+        .expect("Failed to get due schedules");
+    assert_eq!(due.len(), 2);
+
+    // 2025-06-10 23:30Z = 08:30 JST on 06-11 (not yet), 19:30 EDT on 06-10 (due)
+    let due = repo
+        .get_due_schedules_at(utc("2025-06-10T23:30:00Z"))
+        .expect("Failed to get due schedules");
+    assert_eq!(due.len(), 1);
+    assert_eq!(due[0].timezone, "America/New_York");
+}
+
+// ============================================================================
+// TARGET TESTS
+// ============================================================================
+
+fn setup_test_repos() -> (SchedulingRepository, ActivityRepository, TempDir) {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let db =
+        Arc::new(Database::new(temp_dir.path().to_path_buf()).expect("Failed to create database"));
+
+    (
+        SchedulingRepository::new(Arc::clone(&db)),
+        ActivityRepository::new(db),
+        temp_dir,
+    )
+}
+
+fn target_request(target: ScheduleTarget) -> CreateScheduleRequest {
+    CreateScheduleRequest {
+        assessment_type_id: None,
+        target: Some(target),
+        frequency: ScheduleFrequency::Daily,
+        time_of_day: "09:00".to_string(),
+        day_of_week: None,
+        day_of_month: None,
+        timezone: Some("UTC".to_string()),
+        rrule: None,
+        dtstart: None,
+        exdates: None,
+        missed_policy: None,
+    }
+}
+
+#[test]
+fn test_create_schedule_for_each_target_kind() {
+    let (repo, activity_repo, _temp_dir) = setup_test_repos();
+    let group = activity_repo
+        .create_activity_group("Exercise", None)
+        .expect("Failed to create group");
+    let walk = activity_repo
+        .create_activity(group.id, "Walk", None, None)
+        .expect("Failed to create activity");
+
+    let mood = repo
+        .create_schedule(&target_request(ScheduleTarget::MoodCheckin))
+        .expect("Failed to create mood schedule");
+    assert_eq!(mood.target, ScheduleTarget::MoodCheckin);
+    assert_eq!(mood.target_name, "Mood check-in");
+    assert_eq!(mood.assessment_type_id, None);
+
+    let activity = repo
+        .create_schedule(&target_request(ScheduleTarget::Activity {
+            activity_id: walk.id,
+        }))
+        .expect("Failed to create activity schedule");
+    assert_eq!(activity.target_name, "Walk");
+
+    let group_schedule = repo
+        .create_schedule(&target_request(ScheduleTarget::ActivityGroup {
+            activity_group_id: group.id,
+        }))
+        .expect("Failed to create group schedule");
+    assert_eq!(group_schedule.target_name, "Exercise");
+
+    let habit = repo
+        .create_schedule(&target_request(ScheduleTarget::Habit {
+            text: "  Drink a glass of water ".to_string(),
+        }))
+        .expect("Failed to create habit schedule");
+    assert_eq!(
+        habit.target,
+        ScheduleTarget::Habit {
+            text: "Drink a glass of water".to_string()
+        }
+    );
+
+    let assessment = repo
+        .create_schedule(&target_request(ScheduleTarget::Assessment {
+            assessment_type_id: 2,
+        }))
+        .expect("Failed to create assessment schedule");
+    assert_eq!(assessment.assessment_type_code.as_deref(), Some("GAD7"));
+    assert_eq!(assessment.target_name, "Generalized Anxiety Disorder-7");
+
+    let all = repo.get_schedules(false).expect("Failed to get schedules");
+    assert_eq!(all.len(), 5);
+}
+
+#[test]
+fn test_create_schedule_target_validation() {
+    let (repo, _activity_repo, _temp_dir) = setup_test_repos();
+
+    // Both shorthand and explicit target
+    let mut request = target_request(ScheduleTarget::MoodCheckin);
+    request.assessment_type_id = Some(1);
+    assert!(matches!(
+        repo.create_schedule(&request),
+        Err(SchedulingError::InvalidTarget(_))
+    ));
+
+    // Neither
+    request.assessment_type_id = None;
+    request.target = None;
+    assert!(matches!(
+        repo.create_schedule(&request),
+        Err(SchedulingError::InvalidTarget(_))
+    ));
+
+    let blank_habit = target_request(ScheduleTarget::Habit {
+        text: "   ".to_string(),
+    });
+    assert!(matches!(
+        repo.create_schedule(&blank_habit),
+        Err(SchedulingError::InvalidTarget(_))
+    ));
+
+    let missing_activity = target_request(ScheduleTarget::Activity { activity_id: 999 });
+    assert!(matches!(
+        repo.create_schedule(&missing_activity),
+        Err(SchedulingError::TargetNotFound {
+            kind: "activity",
+            id: 999
+        })
+    ));
+}
+
+#[test]
+fn test_update_schedule_target() {
+    let (repo, _activity_repo, _temp_dir) = setup_test_repos();
+
+    let created = repo
+        .create_schedule(&target_request(ScheduleTarget::Assessment {
+            assessment_type_id: 1,
+        }))
+        .expect("Failed to create schedule");
+
+    let update = UpdateScheduleRequest {
+        frequency: None,
+        time_of_day: None,
+        day_of_week: None,
+        day_of_month: None,
+        timezone: None,
+        target: Some(ScheduleTarget::MoodCheckin),
+        enabled: None,
+        rrule: None,
+        dtstart: None,
+        exdates: None,
+        missed_policy: None,
+    };
+    let updated = repo
+        .update_schedule(created.id, &update)
+        .expect("Failed to update schedule");
+
+    assert_eq!(updated.target, ScheduleTarget::MoodCheckin);
+    assert_eq!(updated.assessment_type_id, None);
+    assert_eq!(updated.assessment_type_code, None);
+}
+
+#[test]
+fn test_due_schedules_skip_deleted_and_archived_targets() {
+    let (repo, activity_repo, _temp_dir) = setup_test_repos();
+    let deleted_group = activity_repo
+        .create_activity_group("Old hobbies", None)
+        .expect("Failed to create group");
+    let knitting = activity_repo
+        .create_activity(deleted_group.id, "Knitting", None, None)
+        .expect("Failed to create activity");
+    let archived_group = activity_repo
+        .create_activity_group("Seasonal", None)
+        .expect("Failed to create group");
+
+    repo.create_schedule(&target_request(ScheduleTarget::Activity {
+        activity_id: knitting.id,
+    }))
+    .expect("Failed to create activity schedule");
+    repo.create_schedule(&target_request(ScheduleTarget::ActivityGroup {
+        activity_group_id: archived_group.id,
+    }))
+    .expect("Failed to create group schedule");
+    repo.create_schedule(&target_request(ScheduleTarget::MoodCheckin))
+        .expect("Failed to create mood schedule");
+
+    activity_repo
+        .delete_activity_group(deleted_group.id)
+        .expect("Failed to delete group");
+    activity_repo
+        .archive_activity_group(archived_group.id)
+        .expect("Failed to archive group");
+
+    let due = repo
+        .get_due_schedules_at(utc("2099-01-01T10:00:00Z"))
+        .expect("Failed to get due schedules");
+    assert_eq!(due.len(), 1);
+    assert_eq!(due[0].target, ScheduleTarget::MoodCheckin);
+
+    // Still listed so the user can retarget or delete them
+    let all = repo.get_schedules(true).expect("Failed to get schedules");
+    assert_eq!(all.len(), 3);
+}
+
+// ============================================================================
+// RECURRENCE RULE TESTS
+// ============================================================================
+
+fn custom_request(rrule: &str, dtstart: &str) -> CreateScheduleRequest {
+    CreateScheduleRequest {
+        assessment_type_id: Some(1),
+        frequency: ScheduleFrequency::Custom,
+        time_of_day: "00:00".to_string(),
+        day_of_week: None,
+        day_of_month: None,
+        timezone: Some("UTC".to_string()),
+        target: None,
+        rrule: Some(rrule.to_string()),
+        dtstart: Some(dtstart.to_string()),
+        exdates: None,
+        missed_policy: None,
+    }
+}
+
+#[test]
+fn test_create_custom_schedule_stores_canonical_rule() {
+    let (repo, _temp_dir) = setup_test_repo();
+
+    let schedule = repo
+        .create_schedule(&custom_request(
+            "RRULE:FREQ=WEEKLY;BYHOUR=20,8;BYMINUTE=0;BYDAY=MO,WE,FR",
+            "2025-03-01",
+        ))
+        .expect("Failed to create schedule");
+
+    assert_eq!(schedule.frequency, ScheduleFrequency::Custom);
+    assert_eq!(
+        schedule.rrule,
+        "FREQ=WEEKLY;BYDAY=MO,WE,FR;BYHOUR=8,20;BYMINUTE=0"
+    );
+    assert_eq!(schedule.dtstart, "2025-03-01T00:00:00");
+    assert_eq!(schedule.time_of_day, "08:00"); // Earliest time, for display
+    assert_eq!(schedule.day_of_week, None);
+
+    // Wednesday 2025-03-05: not due before 08:00, due once it has passed
+    assert!(repo
+        .get_due_schedules_at(utc("2025-03-05T07:00:00Z"))
+        .expect("Failed to get due schedules")
+        .is_empty());
+    assert_eq!(
+        repo.get_due_schedules_at(utc("2025-03-05T08:30:00Z"))
+            .expect("Failed to get due schedules")
+            .len(),
+        1
+    );
+}
+
+#[test]
+fn test_create_schedule_derives_rule_from_frequency() {
+    let (repo, _temp_dir) = setup_test_repo();
+
+    let request = CreateScheduleRequest {
+        assessment_type_id: Some(1),
+        frequency: ScheduleFrequency::Monthly,
+        time_of_day: "07:30".to_string(),
+        day_of_week: None,
+        day_of_month: Some(31),
+        timezone: Some("UTC".to_string()),
+        target: None,
+        rrule: None,
+        dtstart: None,
+        exdates: None,
+        missed_policy: None,
+    };
+    let schedule = repo
+        .create_schedule(&request)
+        .expect("Failed to create schedule");
+
+    assert_eq!(schedule.frequency, ScheduleFrequency::Monthly);
+    assert_eq!(
+        schedule.rrule,
+        "FREQ=MONTHLY;BYMONTHDAY=28,29,30,31;BYHOUR=7;BYMINUTE=30;BYSETPOS=-1"
+    );
+    assert!(schedule.dtstart.ends_with("T00:00:00"));
+}
+
+#[test]
+fn test_create_schedule_rrule_validation() {
+    let (repo, _temp_dir) = setup_test_repo();
+
+    // Custom without a rule
+    let mut request = custom_request("FREQ=DAILY", "2025-03-01");
+    request.rrule = None;
+    assert!(matches!(
+        repo.create_schedule(&request),
+        Err(SchedulingError::InvalidRecurrence(_))
+    ));
+
+    // A rule on a simple frequency
+    let mut request = custom_request("FREQ=DAILY", "2025-03-01");
+    request.frequency = ScheduleFrequency::Daily;
+    assert!(matches!(
+        repo.create_schedule(&request),
+        Err(SchedulingError::InvalidRecurrence(_))
+    ));
+
+    for (rrule, dtstart) in [
+        ("FREQ=MINUTELY", "2025-03-01"),
+        ("FREQ=WEEKLY;BYDAY=-1SU", "2025-03-01"),
+        ("FREQ=DAILY", "next tuesday"),
+    ] {
+        assert!(matches!(
+            repo.create_schedule(&custom_request(rrule, dtstart)),
+            Err(SchedulingError::InvalidRecurrence(_))
+        ));
+    }
+
+    let mut request = custom_request("FREQ=DAILY", "2025-03-01");
+    request.exdates = Some(vec!["someday".to_string()]);
+    assert!(matches!(
+        repo.create_schedule(&request),
+        Err(SchedulingError::InvalidRecurrence(_))
+    ));
+}
+
+#[test]
+fn test_exdates_skip_occurrences() {
+    let (repo, _temp_dir) = setup_test_repo();
+
+    let mut request = custom_request("FREQ=DAILY;BYHOUR=9;BYMINUTE=0", "2025-06-01");
+    request.exdates = Some(vec!["2025-06-10".to_string()]);
+    let schedule = repo
+        .create_schedule(&request)
+        .expect("Failed to create schedule");
+    assert_eq!(schedule.exdates, vec!["2025-06-10"]);
+
+    assert!(repo
+        .get_due_schedules_at(utc("2025-06-10T12:00:00Z"))
+        .expect("Failed to get due schedules")
+        .is_empty());
+    assert_eq!(
+        repo.get_due_schedules_at(utc("2025-06-11T12:00:00Z"))
+            .expect("Failed to get due schedules")
+            .len(),
+        1
+    );
+}
+
+#[test]
+fn test_update_schedule_recurrence() {
+    let (repo, _temp_dir) = setup_test_repo();
+
+    let request = CreateScheduleRequest {
+        assessment_type_id: Some(1),
+        frequency: ScheduleFrequency::Weekly,
+        time_of_day: "09:00".to_string(),
+        day_of_week: Some(1),
+        day_of_month: None,
+        timezone: Some("UTC".to_string()),
+        target: None,
+        rrule: None,
+        dtstart: None,
+        exdates: None,
+        missed_policy: None,
+    };
+    let schedule = repo
+        .create_schedule(&request)
+        .expect("Failed to create schedule");
+    assert_eq!(schedule.rrule, "FREQ=WEEKLY;BYDAY=MO;BYHOUR=9;BYMINUTE=0");
+
+    let no_changes = UpdateScheduleRequest {
+        frequency: None,
+        time_of_day: None,
+        day_of_week: None,
+        day_of_month: None,
+        enabled: None,
+        timezone: None,
+        target: None,
+        rrule: None,
+        dtstart: None,
+        exdates: None,
+        missed_policy: None,
+    };
+
+    // Changing the time re-derives the rule
+    let update = UpdateScheduleRequest {
+        time_of_day: Some("18:15".to_string()),
+        ..no_changes.clone()
+    };
+    let updated = repo
+        .update_schedule(schedule.id, &update)
+        .expect("Failed to update schedule");
+    assert_eq!(updated.rrule, "FREQ=WEEKLY;BYDAY=MO;BYHOUR=18;BYMINUTE=15");
+    assert_eq!(updated.dtstart, schedule.dtstart);
+
+    // An rrule alone switches to custom
+    let update = UpdateScheduleRequest {
+        rrule: Some("FREQ=MONTHLY;BYDAY=-1SU;BYHOUR=10;BYMINUTE=0".to_string()),
+        ..no_changes.clone()
+    };
+    let updated = repo
+        .update_schedule(schedule.id, &update)
+        .expect("Failed to update schedule");
+    assert_eq!(updated.frequency, ScheduleFrequency::Custom);
+    assert_eq!(updated.time_of_day, "10:00");
+    assert_eq!(updated.day_of_week, None);
+
+    // Exceptions keep the custom rule
+    let update = UpdateScheduleRequest {
+        exdates: Some(vec!["2025-12-28".to_string()]),
+        ..no_changes.clone()
+    };
+    let updated = repo
+        .update_schedule(schedule.id, &update)
+        .expect("Failed to update schedule");
+    assert_eq!(
+        updated.rrule,
+        "FREQ=MONTHLY;BYDAY=-1SU;BYHOUR=10;BYMINUTE=0"
+    );
+    assert_eq!(updated.exdates, vec!["2025-12-28"]);
+
+    // A new time moves the custom rule
+    let update = UpdateScheduleRequest {
+        time_of_day: Some("08:30".to_string()),
+        ..no_changes.clone()
+    };
+    let updated = repo
+        .update_schedule(schedule.id, &update)
+        .expect("Failed to update schedule");
+    assert_eq!(
+        updated.rrule,
+        "FREQ=MONTHLY;BYDAY=-1SU;BYHOUR=8;BYMINUTE=30"
+    );
+    assert_eq!(updated.time_of_day, "08:30");
+
+    // Back to a simple frequency
+    let update = UpdateScheduleRequest {
+        frequency: Some(ScheduleFrequency::Daily),
+        time_of_day: Some("07:00".to_string()),
+        ..no_changes
+    };
+    let updated = repo
+        .update_schedule(schedule.id, &update)
+        .expect("Failed to update schedule");
+    assert_eq!(updated.frequency, ScheduleFrequency::Daily);
+    assert_eq!(updated.rrule, "FREQ=DAILY;BYHOUR=7;BYMINUTE=0");
+}
+
+// ============================================================================
+// REMINDER EVENT TESTS
+// ============================================================================
+
+fn setup_with_assessments() -> (SchedulingRepository, AssessmentRepository, TempDir) {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let db =
+        Arc::new(Database::new(temp_dir.path().to_path_buf()).expect("Failed to create database"));
+
+    (
+        SchedulingRepository::new(Arc::clone(&db)),
+        AssessmentRepository::new(db),
+        temp_dir,
+    )
+}
+
+/// Create a daily PHQ-9 schedule and fire it once
+fn fire_reminder(repo: &SchedulingRepository) -> ReminderEvent {
+    let schedule = repo
+        .create_schedule(&CreateScheduleRequest {
+            assessment_type_id: Some(1),
+            frequency: ScheduleFrequency::Daily,
+            time_of_day: "09:00".to_string(),
+            day_of_week: None,
+            day_of_month: None,
+            timezone: Some("UTC".to_string()),
+            target: None,
+            rrule: None,
+            dtstart: None,
+            exdates: None,
+            missed_policy: None,
+        })
+        .expect("Failed to create schedule");
+
+    repo.mark_multiple_triggered(&[schedule.id], chrono::Utc::now())
+        .expect("Failed to mark triggered")
+        .remove(0)
+}
+
+fn fired_at(event: &ReminderEvent) -> chrono::DateTime<chrono::Utc> {
+    chrono::NaiveDateTime::parse_from_str(&event.occurred_at, "%Y-%m-%d %H:%M:%S")
+        .expect("Invalid event timestamp")
+        .and_utc()
+}
+
+fn save_phq9(repo: &AssessmentRepository) -> i32 {
+    repo.save_assessment(
+        1,
+        &[1; 9],
+        9,
+        SeverityLevel::Mild,
+        None,
+        AssessmentStatus::Completed,
+    )
+    .expect("Failed to save assessment")
+}
+
+#[test]
+fn test_mark_triggered_logs_fired_events() {
+    let (repo, _temp_dir) = setup_test_repo();
+
+    let fired = fire_reminder(&repo);
+
+    assert_eq!(fired.event_type, ReminderEventType::Fired);
+    assert_eq!(fired.related_event_id, None);
+    assert_eq!(fired.reminder_id(), fired.id);
+
+    let schedule = repo
+        .get_schedule(fired.schedule_id)
+        .expect("Failed to get schedule");
+    assert_eq!(
+        schedule.last_triggered_at.as_deref(),
+        Some(fired.occurred_at.as_str())
+    );
+
+    let events = repo
+        .get_reminder_events(fired.schedule_id, None)
+        .expect("Failed to get events");
+    assert_eq!(events, vec![fired]);
+}
+
+#[test]
+fn test_snoozed_reminder_fires_again_once() {
+    let (repo, _temp_dir) = setup_test_repo();
+    let fired = fire_reminder(&repo);
+    let now = fired_at(&fired);
+
+    let snooze = repo
+        .snooze_reminder(fired.id, 15, now)
+        .expect("Failed to snooze");
+    assert_eq!(snooze.related_event_id, Some(fired.id));
+    assert_eq!(
+        snooze.snooze_until,
+        Some(
+            (now + chrono::Duration::minutes(15))
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string()
+        )
+    );
+
+    // Not due before the snooze runs out
+    let due = repo
+        .get_due_snoozes(now + chrono::Duration::minutes(14))
+        .expect("Failed to get due snoozes");
+    assert!(due.is_empty());
+
+    let later = now + chrono::Duration::minutes(15);
+    let due = repo
+        .get_due_snoozes(later)
+        .expect("Failed to get due snoozes");
+    assert_eq!(due, vec![snooze.clone()]);
+
+    let refires = repo
+        .mark_snoozes_refired(&due, later)
+        .expect("Failed to re-fire");
+    assert_eq!(refires[0].event_type, ReminderEventType::Fired);
+    assert_eq!(refires[0].related_event_id, Some(fired.id));
+
+    // Fired once per snooze
+    let due = repo
+        .get_due_snoozes(later + chrono::Duration::hours(1))
+        .expect("Failed to get due snoozes");
+    assert!(due.is_empty());
+
+    // Snoozing the re-fire applies to the original reminder
+    let second = repo
+        .snooze_reminder(refires[0].id, 5, later)
+        .expect("Failed to snooze again");
+    assert_eq!(second.reminder_id(), fired.id);
+}
+
+#[test]
+fn test_dismiss_cancels_pending_snooze() {
+    let (repo, _temp_dir) = setup_test_repo();
+    let fired = fire_reminder(&repo);
+    let now = fired_at(&fired);
+
+    repo.snooze_reminder(fired.id, 10, now)
+        .expect("Failed to snooze");
+    repo.dismiss_reminder(fired.id, now + chrono::Duration::minutes(2))
+        .expect("Failed to dismiss");
+
+    let due = repo
+        .get_due_snoozes(now + chrono::Duration::hours(1))
+        .expect("Failed to get due snoozes");
+    assert!(due.is_empty());
+}
+
+#[test]
+fn test_reminder_response_validation() {
+    let (repo, _temp_dir) = setup_test_repo();
+    let fired = fire_reminder(&repo);
+    let now = fired_at(&fired);
+
+    assert!(matches!(
+        repo.snooze_reminder(fired.id, 0, now),
+        Err(SchedulingError::InvalidSnooze(0))
+    ));
+    assert!(matches!(
+        repo.snooze_reminder(fired.id, MAX_SNOOZE_MINUTES + 1, now),
+        Err(SchedulingError::InvalidSnooze(_))
+    ));
+    assert!(matches!(
+        repo.dismiss_reminder(9999, now),
+        Err(SchedulingError::ReminderEventNotFound(9999))
+    ));
+
+    repo.complete_reminder(fired.id, now)
+        .expect("Failed to complete");
+    assert!(matches!(
+        repo.snooze_reminder(fired.id, 10, now),
+        Err(SchedulingError::ReminderAlreadyCompleted(id)) if id == fired.id
+    ));
+    assert!(matches!(
+        repo.complete_reminder(fired.id, now),
+        Err(SchedulingError::ReminderAlreadyCompleted(_))
+    ));
+}
+
+#[test]
+fn test_assessment_completes_reminder_within_window() {
+    let (repo, assessment_repo, _temp_dir) = setup_with_assessments();
+    let fired = fire_reminder(&repo);
+    let response_id = save_phq9(&assessment_repo);
+
+    // Another assessment type doesn't complete a PHQ-9 reminder
+    let other = repo
+        .link_assessment_completion(2, response_id, fired_at(&fired))
+        .expect("Failed to link");
+    assert_eq!(other, None);
+
+    let completed = repo
+        .link_assessment_completion(
+            1,
+            response_id,
+            fired_at(&fired) + chrono::Duration::hours(2),
+        )
+        .expect("Failed to link")
+        .expect("Reminder should be completed");
+    assert_eq!(completed.event_type, ReminderEventType::Completed);
+    assert_eq!(completed.related_event_id, Some(fired.id));
+    assert_eq!(completed.assessment_response_id, Some(response_id));
+
+    // A second submission has no open reminder left
+    let again = repo
+        .link_assessment_completion(
+            1,
+            response_id,
+            fired_at(&fired) + chrono::Duration::hours(3),
+        )
+        .expect("Failed to link");
+    assert_eq!(again, None);
+}
+
+#[test]
+fn test_assessment_outside_window_not_linked() {
+    let (repo, assessment_repo, _temp_dir) = setup_with_assessments();
+    let fired = fire_reminder(&repo);
+    let response_id = save_phq9(&assessment_repo);
+
+    let too_late = fired_at(&fired)
+        + chrono::Duration::hours(REMINDER_COMPLETION_WINDOW_HOURS)
+        + chrono::Duration::minutes(1);
+    let linked = repo
+        .link_assessment_completion(1, response_id, too_late)
+        .expect("Failed to link");
+    assert_eq!(linked, None);
+
+    // Submitted before the reminder fired
+    let too_early = fired_at(&fired) - chrono::Duration::minutes(1);
+    let linked = repo
+        .link_assessment_completion(1, response_id, too_early)
+        .expect("Failed to link");
+    assert_eq!(linked, None);
+}
+
+#[test]
+fn test_reminder_adherence_report() {
+    let (repo, _temp_dir) = setup_test_repo();
+    let first = fire_reminder(&repo);
+    let schedule_id = first.schedule_id;
+    let second = repo
+        .mark_multiple_triggered(&[schedule_id], chrono::Utc::now())
+        .expect("Failed to mark triggered")
+        .remove(0);
+    let third = repo
+        .mark_multiple_triggered(&[schedule_id], chrono::Utc::now())
+        .expect("Failed to mark triggered")
+        .remove(0);
+
+    // First: snoozed, re-fired, completed 30 minutes after the original fire
+    let now = fired_at(&first);
+    let snooze = repo
+        .snooze_reminder(first.id, 20, now)
+        .expect("Failed to snooze");
+    repo.mark_snoozes_refired(&[snooze], now + chrono::Duration::minutes(20))
+        .expect("Failed to re-fire");
+    repo.complete_reminder(first.id, now + chrono::Duration::minutes(30))
+        .expect("Failed to complete");
+    // Second: completed after 60 minutes; third: dismissed
+    repo.complete_reminder(second.id, fired_at(&second) + chrono::Duration::minutes(60))
+        .expect("Failed to complete");
+    repo.dismiss_reminder(third.id, fired_at(&third))
+        .expect("Failed to dismiss");
+
+    let report = repo
+        .get_reminder_adherence(None, None)
+        .expect("Failed to get adherence");
+    assert_eq!(report.len(), 1);
+    let adherence = &report[0];
+    assert_eq!(adherence.schedule_id, schedule_id);
+    assert_eq!(adherence.target_name, "Patient Health Questionnaire-9");
+    assert_eq!(adherence.reminders_fired, 3); // Re-fire not counted
+    assert_eq!(adherence.reminders_completed, 2);
+    assert_eq!(adherence.reminders_snoozed, 1);
+    assert_eq!(adherence.reminders_dismissed, 1);
+    assert!((adherence.completion_rate - 2.0 / 3.0).abs() < 1e-9);
+    assert_eq!(adherence.median_minutes_to_completion, Some(45.0));
+
+    // Outside the date range
+    let report = repo
+        .get_reminder_adherence(
+            Some("2000-01-01".to_string()),
+            Some("2000-12-31".to_string()),
+        )
+        .expect("Failed to get adherence");
+    assert_eq!(report[0].reminders_fired, 0);
+    assert_eq!(report[0].completion_rate, 0.0);
+    assert_eq!(report[0].median_minutes_to_completion, None);
+}
+
+#[test]
+fn test_deleting_schedule_removes_its_events() {
+    let (repo, _temp_dir) = setup_test_repo();
+    let fired = fire_reminder(&repo);
+
+    repo.delete_schedule(fired.schedule_id)
+        .expect("Failed to delete schedule");
+
+    assert!(matches!(
+        repo.get_reminder_event(fired.id),
+        Err(SchedulingError::ReminderEventNotFound(_))
+    ));
+}
+
+// ============================================================================
+// MISSED REMINDER TESTS
+// ============================================================================
+
+/// Daily 09:00 UTC PHQ-9 schedule with the given missed-reminder policy
+fn create_daily_with_policy(
+    repo: &SchedulingRepository,
+    policy: Option<MissedReminderPolicy>,
+) -> AssessmentSchedule {
+    repo.create_schedule(&CreateScheduleRequest {
+        assessment_type_id: Some(1),
+        frequency: ScheduleFrequency::Daily,
+        time_of_day: "09:00".to_string(),
+        day_of_week: None,
+        day_of_month: None,
+        timezone: Some("UTC".to_string()),
+        target: None,
+        rrule: None,
+        // Starting tomorrow, so no occurrence falls between creation and dtstart
+        dtstart: Some(
+            (chrono::Utc::now().date_naive() + chrono::Duration::days(1))
+                .format("%Y-%m-%d")
+                .to_string(),
+        ),
+        exdates: None,
+        missed_policy: policy,
+    })
+    .expect("Failed to create schedule")
+}
+
+/// 12:00 UTC on the third day of the schedule: three 09:00 occurrences missed
+fn three_days_later(schedule: &AssessmentSchedule) -> chrono::DateTime<chrono::Utc> {
+    let dtstart = chrono::NaiveDateTime::parse_from_str(&schedule.dtstart, "%Y-%m-%dT%H:%M:%S")
+        .expect("Invalid dtstart");
+    (dtstart.date() + chrono::Duration::days(2))
+        .and_hms_opt(12, 0, 0)
+        .expect("Invalid time")
+        .and_utc()
+}
+
+#[test]
+fn test_missed_policy_defaults_to_coalesce_and_updates() {
+    let (repo, _temp_dir) = setup_test_repo();
+    let schedule = create_daily_with_policy(&repo, None);
+    assert_eq!(schedule.missed_policy, MissedReminderPolicy::Coalesce);
+
+    let updated = repo
+        .update_schedule(
+            schedule.id,
+            &UpdateScheduleRequest {
+                frequency: None,
+                time_of_day: None,
+                day_of_week: None,
+                day_of_month: None,
+                timezone: None,
+                target: None,
+                enabled: None,
+                rrule: None,
+                dtstart: None,
+                exdates: None,
+                missed_policy: Some(MissedReminderPolicy::Skip),
+            },
+        )
+        .expect("Failed to update schedule");
+    assert_eq!(updated.missed_policy, MissedReminderPolicy::Skip);
+}
+
+#[test]
+fn test_reconcile_coalesces_missed_occurrences() {
+    let (repo, _temp_dir) = setup_test_repo();
+    let schedule = create_daily_with_policy(&repo, Some(MissedReminderPolicy::Coalesce));
+    let now = three_days_later(&schedule);
+
+    let notices = repo
+        .reconcile_missed_reminders(now)
+        .expect("Failed to reconcile");
+
+    assert_eq!(notices.len(), 1);
+    let notice = &notices[0];
+    assert_eq!(notice.schedule.id, schedule.id);
+    assert_eq!(notice.event.event_type, ReminderEventType::Fired);
+    assert_eq!(notice.missed.len(), 3);
+    assert!(notice
+        .missed
+        .iter()
+        .all(|m| m.reminder_event_id == Some(notice.event.id)));
+    assert!(notice.missed[2].local_time.ends_with("T09:00:00"));
+    assert!(notice.missed[0].scheduled_for < notice.missed[2].scheduled_for);
+
+    // The regular due check doesn't fire the same occurrences again
+    let due = repo
+        .get_due_schedules_at(now)
+        .expect("Failed to get due schedules");
+    assert!(due.is_empty());
+
+    // Reconciling again finds nothing new
+    let again = repo
+        .reconcile_missed_reminders(now)
+        .expect("Failed to reconcile");
+    assert!(again.is_empty());
+
+    let missed = repo
+        .get_missed_reminders(false)
+        .expect("Failed to get missed reminders");
+    assert_eq!(missed.len(), 3);
+    assert_eq!(missed[0].target_name, "Patient Health Questionnaire-9");
+    assert_eq!(missed[0].policy, MissedReminderPolicy::Coalesce);
+    assert!(missed[0].scheduled_for > missed[2].scheduled_for); // Newest first
+}
+
+#[test]
+fn test_reconcile_fire_all_and_skip() {
+    let (repo, _temp_dir) = setup_test_repo();
+    let fire_all = create_daily_with_policy(&repo, Some(MissedReminderPolicy::FireAll));
+    let skip = create_daily_with_policy(&repo, Some(MissedReminderPolicy::Skip));
+    let now = three_days_later(&fire_all);
+
+    let notices = repo
+        .reconcile_missed_reminders(now)
+        .expect("Failed to reconcile");
+
+    // One notification per occurrence for fire_all, none for skip
+    assert_eq!(notices.len(), 3);
+    assert!(notices.iter().all(|n| n.schedule.id == fire_all.id));
+    assert!(notices.iter().all(|n| n.missed.len() == 1));
+
+    let missed = repo
+        .get_missed_reminders(false)
+        .expect("Failed to get missed reminders");
+    let skipped: Vec<_> = missed.iter().filter(|m| m.schedule_id == skip.id).collect();
+    assert_eq!(skipped.len(), 3);
+    assert!(skipped.iter().all(|m| m.reminder_event_id.is_none()));
+
+    // Skipped schedules are still marked as caught up
+    let skip = repo.get_schedule(skip.id).expect("Failed to get schedule");
+    assert!(skip.last_triggered_at.is_some());
+}
+
+#[test]
+fn test_reconcile_ignores_disabled_schedules() {
+    let (repo, _temp_dir) = setup_test_repo();
+    let schedule = create_daily_with_policy(&repo, None);
+    repo.update_schedule(
+        schedule.id,
+        &UpdateScheduleRequest {
+            frequency: None,
+            time_of_day: None,
+            day_of_week: None,
+            day_of_month: None,
+            timezone: None,
+            target: None,
+            enabled: Some(false),
+            rrule: None,
+            dtstart: None,
+            exdates: None,
+            missed_policy: None,
+        },
+    )
+    .expect("Failed to disable schedule");
+
+    let notices = repo
+        .reconcile_missed_reminders(three_days_later(&schedule))
+        .expect("Failed to reconcile");
+    assert!(notices.is_empty());
+    assert!(repo
+        .get_missed_reminders(true)
+        .expect("Failed to get missed reminders")
+        .is_empty());
+}
+
+#[test]
+fn test_acknowledge_missed_reminders() {
+    let (repo, _temp_dir) = setup_test_repo();
+    let schedule = create_daily_with_policy(&repo, Some(MissedReminderPolicy::Skip));
+    let now = three_days_later(&schedule);
+    repo.reconcile_missed_reminders(now)
+        .expect("Failed to reconcile");
+
+    let missed = repo
+        .get_missed_reminders(false)
+        .expect("Failed to get missed reminders");
+    let acknowledged = repo
+        .acknowledge_missed_reminders(&[missed[0].id, missed[1].id], now)
+        .expect("Failed to acknowledge");
+    assert_eq!(acknowledged, 2);
+
+    // Acknowledging twice is a no-op
+    let acknowledged = repo
+        .acknowledge_missed_reminders(&[missed[0].id], now)
+        .expect("Failed to acknowledge");
+    assert_eq!(acknowledged, 0);
+
+    let pending = repo
+        .get_missed_reminders(false)
+        .expect("Failed to get missed reminders");
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0].id, missed[2].id);
+
+    let all = repo
+        .get_missed_reminders(true)
+        .expect("Failed to get missed reminders");
+    assert_eq!(all.len(), 3);
+    assert!(all[0].acknowledged_at.is_some());
+}
+
+#[test]
+fn test_dropped_reminders_advance_schedule_without_events() {
+    let (repo, _temp_dir) = setup_test_repo();
+    let fired = fire_reminder(&repo);
+    let other = create_daily_with_policy(&repo, None);
+
+    repo.mark_multiple_dropped(&[other.id], chrono::Utc::now())
+        .expect("Failed to drop reminders");
+
+    let dropped = repo.get_schedule(other.id).expect("Failed to get schedule");
+    assert!(dropped.last_triggered_at.is_some());
+    assert!(repo
+        .get_reminder_events(other.id, None)
+        .expect("Failed to get events")
+        .is_empty());
+    // The other schedule's log is untouched
+    assert_eq!(
+        repo.get_reminder_events(fired.schedule_id, None)
+            .expect("Failed to get events"),
+        vec![fired]
+    );
+
+    repo.mark_multiple_dropped(&[], chrono::Utc::now())
+        .expect("Empty drop should succeed");
+}
+
+// ============================================================================
+// ADAPTIVE FREQUENCY TESTS
+// ============================================================================
+
+/// "Moderate or worse -> weekly on Monday; two minimal results -> monthly on the 1st"
+fn adaptive_rules() -> Vec<AdaptiveRuleRequest> {
+    vec![
+        AdaptiveRuleRequest {
+            comparison: SeverityComparison::AtLeast,
+            severity_level: SeverityLevel::Moderate,
+            consecutive_count: 1,
+            frequency: ScheduleFrequency::Weekly,
+            day_of_week: Some(1),
+            day_of_month: None,
+        },
+        AdaptiveRuleRequest {
+            comparison: SeverityComparison::AtMost,
+            severity_level: SeverityLevel::Minimal,
+            consecutive_count: 2,
+            frequency: ScheduleFrequency::Monthly,
+            day_of_week: None,
+            day_of_month: Some(1),
+        },
+    ]
+}
+
+/// Monthly PHQ-9 schedule with the rules from [`adaptive_rules`]
+fn create_adaptive_schedule(repo: &SchedulingRepository) -> AssessmentSchedule {
+    let schedule = repo
+        .create_schedule(&CreateScheduleRequest {
+            assessment_type_id: Some(1),
+            frequency: ScheduleFrequency::Monthly,
+            time_of_day: "09:00".to_string(),
+            day_of_week: None,
+            day_of_month: Some(1),
+            timezone: Some("UTC".to_string()),
+            target: None,
+            rrule: None,
+            dtstart: None,
+            exdates: None,
+            missed_policy: None,
+        })
+        .expect("Failed to create schedule");
+    repo.set_adaptive_rules(schedule.id, &adaptive_rules())
+        .expect("Failed to set rules");
+    schedule
+}
+
+fn save_phq9_with(repo: &AssessmentRepository, score: i32, severity: SeverityLevel) -> i32 {
+    repo.save_assessment(
+        1,
+        &[0; 9],
+        score,
+        severity,
+        None,
+        AssessmentStatus::Completed,
+    )
+    .expect("Failed to save assessment")
+}
+
+#[test]
+fn test_adaptive_rules_escalate_and_relax() {
+    let (repo, assessments, _temp_dir) = setup_with_assessments();
+    let schedule = create_adaptive_schedule(&repo);
+    let now = chrono::Utc::now();
+
+    // No results yet
+    assert!(repo.apply_adaptive_rules(None, now).unwrap().is_empty());
+
+    let moderate = save_phq9_with(&assessments, 12, SeverityLevel::Moderate);
+    let changes = repo
+        .apply_adaptive_rules(Some(1), now)
+        .expect("Failed to apply rules");
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0].from_frequency, ScheduleFrequency::Monthly);
+    assert_eq!(changes[0].to_frequency, ScheduleFrequency::Weekly);
+    assert_eq!(changes[0].assessment_response_id, Some(moderate));
+    assert!(changes[0].reason.contains("moderate or worse"));
+    assert!(changes[0].reason.contains("score 12"));
+
+    let weekly = repo.get_schedule(schedule.id).unwrap();
+    assert_eq!(weekly.frequency, ScheduleFrequency::Weekly);
+    assert_eq!(weekly.day_of_week, Some(1));
+    assert_eq!(weekly.rrule, changes[0].to_rrule);
+    assert_eq!(weekly.time_of_day, "09:00");
+
+    // Evaluating the same result again changes nothing
+    assert!(repo.apply_adaptive_rules(None, now).unwrap().is_empty());
+
+    // One minimal result isn't enough to relax, two are
+    save_phq9_with(&assessments, 2, SeverityLevel::Minimal);
+    assert!(repo.apply_adaptive_rules(None, now).unwrap().is_empty());
+    save_phq9_with(&assessments, 3, SeverityLevel::Minimal);
+    let changes = repo.apply_adaptive_rules(None, now).unwrap();
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0].to_frequency, ScheduleFrequency::Monthly);
+    assert!(changes[0].reason.starts_with("Last 2 "));
+
+    let history = repo
+        .get_frequency_changes(Some(schedule.id), None)
+        .expect("Failed to get changes");
+    assert_eq!(history.len(), 2);
+    assert_eq!(history[0].to_frequency, ScheduleFrequency::Monthly);
+    assert_eq!(history[1].to_frequency, ScheduleFrequency::Weekly);
+    assert_eq!(
+        repo.get_frequency_changes(None, Some(1)).unwrap(),
+        vec![history[0].clone()]
+    );
+}
+
+#[test]
+fn test_adaptive_rules_respect_manual_edits() {
+    let (repo, assessments, _temp_dir) = setup_with_assessments();
+    let schedule = create_adaptive_schedule(&repo);
+    let now = chrono::Utc::now();
+
+    save_phq9_with(&assessments, 16, SeverityLevel::ModeratelySevere);
+    assert_eq!(repo.apply_adaptive_rules(None, now).unwrap().len(), 1);
+
+    // The user goes back to daily by hand; the same result doesn't undo that
+    let update = UpdateScheduleRequest {
+        frequency: Some(ScheduleFrequency::Daily),
+        time_of_day: None,
+        day_of_week: None,
+        day_of_month: None,
+        timezone: None,
+        target: None,
+        enabled: None,
+        rrule: None,
+        dtstart: None,
+        exdates: None,
+        missed_policy: None,
+    };
+    repo.update_schedule(schedule.id, &update).unwrap();
+    assert!(repo.apply_adaptive_rules(None, now).unwrap().is_empty());
+
+    // A new result is evaluated again
+    save_phq9_with(&assessments, 11, SeverityLevel::Moderate);
+    assert_eq!(repo.apply_adaptive_rules(None, now).unwrap().len(), 1);
+    assert_eq!(
+        repo.get_schedule(schedule.id).unwrap().frequency,
+        ScheduleFrequency::Weekly
+    );
+
+    // Drafts and other assessment types are ignored
+    assessments
+        .save_assessment(
+            1,
+            &[0; 9],
+            0,
+            SeverityLevel::Minimal,
+            None,
+            AssessmentStatus::Draft,
+        )
+        .unwrap();
+    assert!(repo.apply_adaptive_rules(Some(2), now).unwrap().is_empty());
+    assert!(repo.apply_adaptive_rules(None, now).unwrap().is_empty());
+}
+
+#[test]
+fn test_replacing_rules_reevaluates_latest_result() {
+    let (repo, assessments, _temp_dir) = setup_with_assessments();
+    let schedule = create_adaptive_schedule(&repo);
+    let now = chrono::Utc::now();
+
+    save_phq9_with(&assessments, 7, SeverityLevel::Mild);
+    assert!(repo.apply_adaptive_rules(None, now).unwrap().is_empty());
+
+    // A stricter rule applies to the result already on file
+    let mut rules = adaptive_rules();
+    rules[0].severity_level = SeverityLevel::Mild;
+    let stored = repo.set_adaptive_rules(schedule.id, &rules).unwrap();
+    assert_eq!(stored.len(), 2);
+    assert_eq!(stored[0].position, 0);
+    assert_eq!(stored[1].consecutive_count, 2);
+    assert_eq!(repo.get_adaptive_rules(schedule.id).unwrap(), stored);
+
+    let changes = repo.apply_adaptive_rules(None, now).unwrap();
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0].rule_id, Some(stored[0].id));
+
+    // Replacing the rules keeps the history but unlinks the old rule
+    repo.set_adaptive_rules(schedule.id, &[]).unwrap();
+    let history = repo.get_frequency_changes(Some(schedule.id), None).unwrap();
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0].rule_id, None);
+}
+
+#[test]
+fn test_adaptive_rule_validation() {
+    let (repo, _temp_dir) = setup_test_repo();
+    let schedule = create_daily_with_policy(&repo, None);
+
+    let mut missing_day = adaptive_rules();
+    missing_day[0].day_of_week = None;
+    assert!(matches!(
+        repo.set_adaptive_rules(schedule.id, &missing_day),
+        Err(SchedulingError::InvalidAdaptiveRule(_))
+    ));
+
+    let mood = repo
+        .create_schedule(&CreateScheduleRequest {
+            assessment_type_id: None,
+            frequency: ScheduleFrequency::Daily,
+            time_of_day: "20:00".to_string(),
+            day_of_week: None,
+            day_of_month: None,
+            timezone: None,
+            target: Some(ScheduleTarget::MoodCheckin),
+            rrule: None,
+            dtstart: None,
+            exdates: None,
+            missed_policy: None,
+        })
+        .unwrap();
+    assert!(matches!(
+        repo.set_adaptive_rules(mood.id, &adaptive_rules()),
+        Err(SchedulingError::InvalidAdaptiveRule(_))
+    ));
+
+    assert!(matches!(
+        repo.set_adaptive_rules(999, &adaptive_rules()),
+        Err(SchedulingError::NotFound(999))
+    ));
+
+    // Rules go with their schedule
+    repo.set_adaptive_rules(schedule.id, &adaptive_rules())
+        .unwrap();
+    repo.delete_schedule(schedule.id).unwrap();
+    assert!(matches!(
+        repo.get_adaptive_rules(schedule.id),
+        Err(SchedulingError::NotFound(_))
+    ));
+}
+
+// ============================================================================
+// ICALENDAR EXPORT/IMPORT TESTS
+// ============================================================================
+
+fn ical_request(
+    target: ScheduleTarget,
+    frequency: ScheduleFrequency,
+    time_of_day: &str,
+    timezone: &str,
+) -> CreateScheduleRequest {
+    CreateScheduleRequest {
+        frequency,
+        time_of_day: time_of_day.to_string(),
+        timezone: Some(timezone.to_string()),
+        dtstart: Some("2025-01-01".to_string()),
+        ..target_request(target)
+    }
+}
+
+#[test]
+fn test_exported_schedules_import_into_another_database() {
+    let (repo, activity_repo, _temp_dir) = setup_test_repos();
+    let group = activity_repo
+        .create_activity_group("Exercise", None)
+        .expect("Failed to create group");
+    let walk = activity_repo
+        .create_activity(group.id, "Walk", None, None)
+        .expect("Failed to create activity");
+
+    let requests = [
+        CreateScheduleRequest {
+            day_of_week: Some(1),
+            exdates: Some(vec!["2025-04-21".to_string()]),
+            missed_policy: Some(MissedReminderPolicy::FireAll),
+            ..ical_request(
+                ScheduleTarget::Assessment {
+                    assessment_type_id: 1,
+                },
+                ScheduleFrequency::Weekly,
+                "09:00",
+                "Europe/Berlin",
+            )
+        },
+        CreateScheduleRequest {
+            day_of_week: Some(5),
+            ..ical_request(
+                ScheduleTarget::Assessment {
+                    assessment_type_id: 2,
+                },
+                ScheduleFrequency::Biweekly,
+                "18:30",
+                "America/New_York",
+            )
+        },
+        CreateScheduleRequest {
+            day_of_month: Some(31),
+            ..ical_request(
+                ScheduleTarget::MoodCheckin,
+                ScheduleFrequency::Monthly,
+                "07:15",
+                "Asia/Tokyo",
+            )
+        },
+        CreateScheduleRequest {
+            rrule: Some("FREQ=MONTHLY;BYDAY=-1SU;BYHOUR=10,21;BYMINUTE=0".to_string()),
+            exdates: Some(vec!["2025-03-30T21:00:00".to_string()]),
+            ..ical_request(
+                ScheduleTarget::Habit {
+                    text: "Review the month; plan, rest".to_string(),
+                },
+                ScheduleFrequency::Custom,
+                "00:00",
+                "Australia/Sydney",
+            )
+        },
+        ical_request(
+            ScheduleTarget::Activity {
+                activity_id: walk.id,
+            },
+            ScheduleFrequency::Daily,
+            "12:00",
+            "UTC",
+        ),
+    ];
+    let originals: Vec<AssessmentSchedule> = requests
+        .iter()
+        .map(|r| repo.create_schedule(r).expect("Failed to create schedule"))
+        .collect();
+
+    // Disabled schedules are not exported
+    let disabled = repo
+        .create_schedule(&ical_request(
+            ScheduleTarget::MoodCheckin,
+            ScheduleFrequency::Daily,
+            "22:00",
+            "UTC",
+        ))
+        .expect("Failed to create schedule");
+    repo.update_schedule(
+        disabled.id,
+        &UpdateScheduleRequest {
+            frequency: None,
+            time_of_day: None,
+            day_of_week: None,
+            day_of_month: None,
+            timezone: None,
+            target: None,
+            enabled: Some(false),
+            rrule: None,
+            dtstart: None,
+            exdates: None,
+            missed_policy: None,
+        },
+    )
+    .expect("Failed to disable schedule");
+
+    let file = export_calendar(
+        &repo.get_schedules(true).expect("Failed to get schedules"),
+        &[],
+        utc("2025-01-01T00:00:00Z"),
+    )
+    .expect("Failed to export schedules");
+    assert_eq!(file.event_count, 5);
+
+    // The walk doesn't exist in the other database
+    let (other, _other_dir) = setup_test_repo();
+    let result = other
+        .import_calendar(parse_calendar(&file.ics).expect("Failed to parse calendar"))
+        .expect("Failed to import calendar");
+    assert_eq!(result.imported.len(), 4);
+    assert_eq!(result.skipped.len(), 1);
+    assert_eq!(
+        result.skipped[0].uid,
+        Some(format!(
+            "schedule-{}@mental-health-tracker",
+            originals[4].id
+        ))
+    );
+
+    let after = utc("2025-01-01T00:00:00Z");
+    for (original, imported) in originals.iter().zip(&result.imported) {
+        assert_eq!(imported.target, original.target);
+        assert_eq!(imported.frequency, original.frequency);
+        assert_eq!(imported.time_of_day, original.time_of_day);
+        assert_eq!(imported.day_of_week, original.day_of_week);
+        assert_eq!(imported.day_of_month, original.day_of_month);
+        assert_eq!(imported.rrule, original.rrule);
+        assert_eq!(imported.timezone, original.timezone);
+        assert_eq!(imported.missed_policy, original.missed_policy);
+
+        let tz = parse_timezone(&original.timezone).unwrap();
+        let occurrences = |schedule: &AssessmentSchedule| -> Vec<_> {
+            schedule
+                .recurrence_set()
+                .expect("Failed to parse recurrence")
+                .occurrences_after(tz, after)
+                .take(60)
+                .collect()
+        };
+        assert_eq!(
+            occurrences(imported),
+            occurrences(original),
+            "{} reminders moved",
+            original.target_name
+        );
+    }
+}
+
+#[test]
+fn test_exported_plans_import_back() {
+    let (repo, activity_repo, _temp_dir) = setup_test_repos();
+    let group = activity_repo
+        .create_activity_group("Exercise", None)
+        .expect("Failed to create group");
+    let yoga = activity_repo
+        .create_activity(group.id, "Yoga", None, None)
+        .expect("Failed to create activity");
+    let original = activity_repo
+        .create_planned_activity(&CreatePlannedActivityRequest {
+            activity_id: yoga.id,
+            days_of_week: vec![2, 4],
+            time_of_day: "07:00".to_string(),
+            timezone: Some("Europe/Berlin".to_string()),
+            interval_weeks: Some(2),
+            tolerance_minutes: Some(30),
+            start_date: "2025-01-06".to_string(),
+            end_date: Some("2025-06-30".to_string()),
+            reminder_enabled: true,
+            reminder_minutes_before: Some(15),
+        })
+        .expect("Failed to create plan");
+
+    let plans = activity_repo
+        .get_planned_activities_with_names()
+        .expect("Failed to get plans");
+    let file =
+        export_calendar(&[], &plans, utc("2025-01-01T00:00:00Z")).expect("Failed to export plans");
+    assert_eq!(file.event_count, 1);
+
+    let result = repo
+        .import_calendar(parse_calendar(&file.ics).expect("Failed to parse calendar"))
+        .expect("Failed to import calendar");
+    assert!(result.imported.is_empty());
+    assert!(result.skipped.is_empty());
+
+    let imported = &result.imported_plans[0];
+    assert_ne!(imported.id, original.id);
+    assert_eq!(imported.activity_id, yoga.id);
+    assert_eq!(imported.days_of_week, original.days_of_week);
+    assert_eq!(imported.time_of_day, original.time_of_day);
+    assert_eq!(imported.timezone, original.timezone);
+    assert_eq!(imported.interval_weeks, original.interval_weeks);
+    assert_eq!(imported.tolerance_minutes, original.tolerance_minutes);
+    assert_eq!(imported.end_date, original.end_date);
+    assert_eq!(imported.reminder_minutes_before, 15);
+
+    let (from, to) = (
+        chrono::NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+        chrono::NaiveDate::from_ymd_opt(2025, 12, 31).unwrap(),
+    );
+    assert_eq!(
+        expand_plan_occurrences(imported, from, to).unwrap(),
+        expand_plan_occurrences(&original, from, to).unwrap()
+    );
+}
+
+#[test]
+fn test_import_calendar_from_another_app() {
+    let (repo, _temp_dir) = setup_test_repo();
+    let ics = "BEGIN:VCALENDAR\r\n\
+        VERSION:2.0\r\n\
+        PRODID:-//Other//Calendar//EN\r\n\
+        BEGIN:VEVENT\r\n\
+        UID:standup@example.com\r\n\
+        SUMMARY:Stretch break\r\n\
+        DTSTART;TZID=Europe/London:20250303T103000\r\n\
+        RRULE:FREQ=WEEKLY;BYDAY=MO,TU,WE,TH,FR\r\n\
+        END:VEVENT\r\n\
+        BEGIN:VEVENT\r\n\
+        UID:text@example.com\r\n\
+        SUMMARY:\r\n\
+        DTSTART:20250303T103000Z\r\n\
+        END:VEVENT\r\n\
+        END:VCALENDAR\r\n";
+
+    let result = repo
+        .import_calendar(parse_calendar(ics).expect("Failed to parse calendar"))
+        .expect("Failed to import calendar");
+
+    assert_eq!(result.imported.len(), 1);
+    let schedule = &result.imported[0];
+    assert_eq!(
+        schedule.target,
+        ScheduleTarget::Habit {
+            text: "Stretch break".to_string()
+        }
+    );
+    assert_eq!(schedule.frequency, ScheduleFrequency::Custom);
+    assert_eq!(schedule.time_of_day, "10:30");
+    assert_eq!(schedule.timezone, "Europe/London");
+    assert_eq!(schedule.dtstart, "2025-03-03T10:30:00");
+
+    // An empty summary is not a valid habit reminder
+    assert_eq!(result.skipped.len(), 1);
+    assert_eq!(result.skipped[0].uid.as_deref(), Some("text@example.com"));
+    assert!(result.skipped[0].reason.contains("cannot be empty"));
+}
+
 // ============================================================================
 // FREQUENCY ENUM TESTS
 // ============================================================================
@@ -737,6 +2575,7 @@ fn test_schedule_frequency_as_str() {
     assert_eq!(ScheduleFrequency::Weekly.as_str(), "weekly");
     assert_eq!(ScheduleFrequency::Biweekly.as_str(), "biweekly");
     assert_eq!(ScheduleFrequency::Monthly.as_str(), "monthly");
+    assert_eq!(ScheduleFrequency::Custom.as_str(), "custom");
 }
 
 #[test]
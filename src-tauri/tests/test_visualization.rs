@@ -4,14 +4,16 @@
 // T136: Integration test - get_mood_chart_data query
 // T137: Integration test - Activity correlation calculation
 
+use chrono::{DateTime, Utc};
 use std::sync::Arc;
 use tauri_sveltekit_modern_lib::db::Database;
 use tauri_sveltekit_modern_lib::features::assessments::repository::AssessmentRepository;
 use tauri_sveltekit_modern_lib::features::mood::repository::MoodRepository;
 use tauri_sveltekit_modern_lib::features::visualization::models::{
-    TimeRange, TrendDirection, VisualizationError,
+    ChartQueryOptions, DateBounds, TimeRange, TrendDirection, VisualizationError,
 };
 use tauri_sveltekit_modern_lib::features::visualization::repository::VisualizationRepository;
+use tauri_sveltekit_modern_lib::features::visualization::series::DerivedSeriesKind;
 use tauri_sveltekit_modern_lib::types::assessment::{
     AssessmentCode, AssessmentStatus, SeverityLevel,
 };
@@ -48,6 +50,44 @@ fn setup_test_repo() -> (
     (viz_repo, assessment_repo, mood_repo, temp_dir, group_id)
 }
 
+/// Move rows of `table` one week apart, the newest at the current time.
+/// Rows saved in a test otherwise share a timestamp, leaving no time span
+/// to measure a trend over.
+fn space_rows_weekly(temp_dir: &TempDir, table: &str, column: &str) {
+    let conn = rusqlite::Connection::open(temp_dir.path().join("mental_health_tracker.db"))
+        .expect("Failed to open database");
+    conn.execute(
+        &format!(
+            "UPDATE {table} SET {column} = datetime('now',
+                 printf('-%d days', ((SELECT MAX(id) FROM {table}) - id) * 7))"
+        ),
+        [],
+    )
+    .expect("Failed to space rows");
+}
+
+/// Set `column` of the rows of `table`, in id order, to `timestamps`
+fn set_timestamps(temp_dir: &TempDir, table: &str, column: &str, timestamps: &[&str]) {
+    let conn = rusqlite::Connection::open(temp_dir.path().join("mental_health_tracker.db"))
+        .expect("Failed to open database");
+    let mut ids = conn
+        .prepare(&format!("SELECT id FROM {table} ORDER BY id"))
+        .expect("Failed to prepare query");
+    let ids: Vec<i64> = ids
+        .query_map([], |row| row.get(0))
+        .expect("Failed to query ids")
+        .collect::<Result<_, _>>()
+        .expect("Failed to read ids");
+    assert_eq!(ids.len(), timestamps.len());
+    for (id, timestamp) in ids.iter().zip(timestamps) {
+        conn.execute(
+            &format!("UPDATE {table} SET {column} = ?1 WHERE id = ?2"),
+            rusqlite::params![timestamp, id],
+        )
+        .expect("Failed to set timestamp");
+    }
+}
+
 // T118: Integration test - get_assessment_chart_data query with time ranges
 #[test]
 fn test_get_assessment_chart_data_with_week_range() {
@@ -194,10 +234,10 @@ fn test_assessment_chart_statistics_calculation() {
 
 #[test]
 fn test_assessment_chart_trend_improving() {
-    let (viz_repo, assessment_repo, _, _temp_dir, _group_id) = setup_test_repo();
+    let (viz_repo, assessment_repo, _, temp_dir, _group_id) = setup_test_repo();
 
     // Create assessments with decreasing scores (improving trend)
-    // 20 -> 10 is 50% reduction (> 20% threshold)
+    // one week apart, 20 -> 10 over four weeks
     for score in [20, 18, 15, 12, 10] {
         let responses: Vec<i32> = vec![1; 9];
         let severity = if score >= 15 {
@@ -218,6 +258,8 @@ fn test_assessment_chart_trend_improving() {
             .expect("Failed to create assessment");
     }
 
+    space_rows_weekly(&temp_dir, "assessment_responses", "completed_at");
+
     let chart_data = viz_repo
         .get_assessment_chart_data("PHQ9", None, None)
         .expect("Failed to get chart data");
@@ -227,14 +269,19 @@ fn test_assessment_chart_trend_improving() {
         chart_data.statistics.trend,
         TrendDirection::Improving
     ));
+    let analysis = &chart_data.statistics.trend_analysis;
+    assert!(analysis.sufficient_data);
+    assert_eq!(analysis.direction, TrendDirection::Improving);
+    assert!(analysis.slope_per_week.unwrap() < -2.0);
+    assert!(analysis.confidence > 0.9);
 }
 
 #[test]
 fn test_assessment_chart_trend_worsening() {
-    let (viz_repo, assessment_repo, _, _temp_dir, _group_id) = setup_test_repo();
+    let (viz_repo, assessment_repo, _, temp_dir, _group_id) = setup_test_repo();
 
     // Create assessments with increasing scores (worsening trend)
-    // 5 -> 15 is 200% increase (> 20% threshold)
+    // one week apart, 5 -> 15 over four weeks
     for score in [5, 7, 10, 12, 15] {
         let responses: Vec<i32> = vec![1; 9];
         let severity = if score >= 10 {
@@ -255,6 +302,8 @@ fn test_assessment_chart_trend_worsening() {
             .expect("Failed to create assessment");
     }
 
+    space_rows_weekly(&temp_dir, "assessment_responses", "completed_at");
+
     let chart_data = viz_repo
         .get_assessment_chart_data("PHQ9", None, None)
         .expect("Failed to get chart data");
@@ -268,10 +317,10 @@ fn test_assessment_chart_trend_worsening() {
 
 #[test]
 fn test_assessment_chart_trend_stable() {
-    let (viz_repo, assessment_repo, _, _temp_dir, _group_id) = setup_test_repo();
+    let (viz_repo, assessment_repo, _, temp_dir, _group_id) = setup_test_repo();
 
-    // Create assessments with stable scores (< 20% change)
-    // 10 -> 11 is 10% change (< 20% threshold)
+    // Create assessments with stable scores one week apart
+    // 10 -> 11 moves less than the minimum meaningful change
     for score in [10, 10, 11, 10, 11] {
         let responses: Vec<i32> = vec![1; 9];
         let severity = SeverityLevel::Mild;
@@ -288,6 +337,8 @@ fn test_assessment_chart_trend_stable() {
             .expect("Failed to create assessment");
     }
 
+    space_rows_weekly(&temp_dir, "assessment_responses", "completed_at");
+
     let chart_data = viz_repo
         .get_assessment_chart_data("PHQ9", None, None)
         .expect("Failed to get chart data");
@@ -297,6 +348,7 @@ fn test_assessment_chart_trend_stable() {
         chart_data.statistics.trend,
         TrendDirection::Stable
     ));
+    assert!(chart_data.statistics.trend_analysis.sufficient_data);
 }
 
 #[test]
@@ -467,6 +519,110 @@ fn test_mood_statistics_calculation() {
     assert!((mood_data.statistics.average - 3.0).abs() < 0.1);
 }
 
+#[test]
+fn test_mood_chart_trend() {
+    let (viz_repo, _, mood_repo, temp_dir, _group_id) = setup_test_repo();
+
+    for rating in [2, 3, 3, 4, 4, 5] {
+        mood_repo
+            .create_mood_checkin(rating, vec![], None)
+            .expect("Failed to create mood check-in");
+    }
+
+    // All saved within a second: too little time to judge a trend
+    let mood_data = viz_repo
+        .get_mood_chart_data(None, None, false)
+        .expect("Failed to get mood chart data");
+    assert!(!mood_data.statistics.trend_analysis.sufficient_data);
+    assert_eq!(
+        mood_data.statistics.trend_analysis.direction,
+        TrendDirection::Stable
+    );
+
+    // Weekly check-ins rising from 2 to 5; higher mood is better
+    space_rows_weekly(&temp_dir, "mood_checkins", "created_at");
+    let mood_data = viz_repo
+        .get_mood_chart_data(None, None, false)
+        .expect("Failed to get mood chart data");
+    let analysis = &mood_data.statistics.trend_analysis;
+    assert!(analysis.sufficient_data);
+    assert_eq!(analysis.points_used, 6);
+    assert_eq!(analysis.direction, TrendDirection::Improving);
+    assert!(analysis.slope_per_week.unwrap() > 0.0);
+}
+
+#[test]
+fn test_mood_chart_derived_series() {
+    let (viz_repo, _, mood_repo, temp_dir, _group_id) = setup_test_repo();
+
+    // Two weeks ago, one week ago, today
+    for rating in [2, 4, 6] {
+        mood_repo
+            .create_mood_checkin(rating, vec![], None)
+            .expect("Failed to create mood check-in");
+    }
+    space_rows_weekly(&temp_dir, "mood_checkins", "created_at");
+    // Two more today
+    for rating in [3, 6] {
+        mood_repo
+            .create_mood_checkin(rating, vec![], None)
+            .expect("Failed to create mood check-in");
+    }
+
+    let mood_data = viz_repo
+        .get_mood_chart_data_with_options(
+            None,
+            None,
+            false,
+            &ChartQueryOptions {
+                series: vec![
+                    DerivedSeriesKind::DailyMean,
+                    DerivedSeriesKind::RollingAverage7Day,
+                    DerivedSeriesKind::RollingAverage30Day,
+                    DerivedSeriesKind::ExponentialMovingAverage7Day,
+                ],
+                ..Default::default()
+            },
+        )
+        .expect("Failed to get mood chart data");
+
+    assert_eq!(mood_data.data_points.len(), 5);
+    let names: Vec<&str> = mood_data
+        .derived_series
+        .iter()
+        .map(|s| s.name.as_str())
+        .collect();
+    assert_eq!(
+        names,
+        vec![
+            "daily_mean",
+            "rolling_average_7d",
+            "rolling_average_30d",
+            "ema_7d"
+        ]
+    );
+
+    let values = |i: usize| -> Vec<f64> {
+        mood_data.derived_series[i]
+            .data_points
+            .iter()
+            .map(|p| p.value)
+            .collect()
+    };
+    // One point per day with check-ins; today averages 6, 3 and 6
+    assert_eq!(values(0), vec![2.0, 4.0, 5.0]);
+    // A week apart, each 7-day window holds a single day
+    assert_eq!(values(1), values(0));
+    assert!((values(2)[2] - 11.0 / 3.0).abs() < 1e-9);
+    assert_eq!(values(3).len(), 3);
+
+    // Nothing requested, nothing derived
+    let mood_data = viz_repo
+        .get_mood_chart_data(None, None, false)
+        .expect("Failed to get mood chart data");
+    assert!(mood_data.derived_series.is_empty());
+}
+
 // T137: Integration test - Activity correlation calculation
 #[test]
 fn test_activity_correlation_calculation() {
@@ -617,3 +773,659 @@ fn test_mood_chart_without_activity_breakdown() {
     // But data points should still be present
     assert_eq!(mood_data.data_points.len(), 2);
 }
+
+#[test]
+fn test_activity_group_mood_breakdown() {
+    let (viz_repo, _, mood_repo, temp_dir, group_id) = setup_test_repo();
+
+    let conn = rusqlite::Connection::open(temp_dir.path().join("mental_health_tracker.db"))
+        .expect("Failed to open database");
+    let create_group = |name: &str| -> i32 {
+        conn.query_row(
+            "INSERT INTO activity_groups (name) VALUES (?) RETURNING id",
+            [name],
+            |row| row.get(0),
+        )
+        .expect("Failed to create activity group")
+    };
+    let chores_id = create_group("Chores");
+    let old_id = create_group("Old");
+
+    let exercise = mood_repo
+        .create_activity("Exercise", None, None, group_id)
+        .unwrap();
+    let walk = mood_repo
+        .create_activity("Walk", None, None, group_id)
+        .unwrap();
+    let stretching = mood_repo
+        .create_activity("Stretching", None, None, group_id)
+        .unwrap();
+    let cleaning = mood_repo
+        .create_activity("Cleaning", None, None, chores_id)
+        .unwrap();
+    let archived = mood_repo
+        .create_activity("Archived", None, None, old_id)
+        .unwrap();
+
+    for (rating, activities) in [
+        (6, vec![exercise.id, stretching.id]),
+        (7, vec![exercise.id, walk.id]),
+        (5, vec![walk.id, stretching.id]),
+        (2, vec![cleaning.id]),
+        (3, vec![cleaning.id, archived.id]),
+        (4, vec![archived.id]),
+    ] {
+        mood_repo
+            .create_mood_checkin(rating, activities, None)
+            .unwrap();
+    }
+    set_timestamps(
+        &temp_dir,
+        "mood_checkins",
+        "created_at",
+        &[
+            "2025-02-01 08:00:00",
+            "2025-02-01 20:00:00",
+            "2025-02-02 08:00:00",
+            "2025-02-03 08:00:00",
+            "2025-02-03 20:00:00",
+            "2025-02-04 08:00:00",
+        ],
+    );
+
+    // A deleted activity and a deleted group's activities count as absent
+    mood_repo.delete_activity(stretching.id).unwrap();
+    conn.execute(
+        "UPDATE activity_groups SET deleted_at = datetime('now') WHERE id = ?",
+        [old_id],
+    )
+    .unwrap();
+
+    let mood_data = viz_repo
+        .get_mood_chart_data(None, None, true)
+        .expect("Failed to get mood chart data");
+
+    let names: Vec<&str> = mood_data
+        .activity_breakdown
+        .iter()
+        .map(|a| a.activity.name.as_str())
+        .collect();
+    assert_eq!(names, vec!["Exercise", "Walk", "Cleaning"]);
+
+    let groups = &mood_data.group_breakdown;
+    assert_eq!(groups.len(), 2);
+
+    // Default group: check-ins 6, 7, 5 against 2, 3, 4;
+    // days 6.5 and 5.0 against 2.5 and 4.0
+    let default_group = &groups[0];
+    assert_eq!(default_group.group_id, group_id);
+    assert_eq!(default_group.comparison.checkin_count, 3);
+    assert_eq!(default_group.comparison.average_mood, 6.0);
+    assert_eq!(default_group.comparison.baseline_mood, Some(3.0));
+    assert_eq!(default_group.comparison.difference, Some(3.0));
+    assert_eq!(default_group.comparison.days_with, 2);
+    assert_eq!(default_group.comparison.days_without, 2);
+    assert_eq!(default_group.comparison.days_with_average, Some(5.75));
+    assert_eq!(default_group.comparison.days_without_average, Some(3.25));
+    assert_eq!(default_group.comparison.days_difference, Some(2.5));
+
+    // Drill-down without the deleted activity
+    let drill_down: Vec<(&str, f64)> = default_group
+        .activities
+        .iter()
+        .map(|a| (a.activity.name.as_str(), a.comparison.average_mood))
+        .collect();
+    assert_eq!(drill_down, vec![("Exercise", 6.5), ("Walk", 6.0)]);
+
+    let chores = &groups[1];
+    assert_eq!(chores.group_name, "Chores");
+    assert_eq!(chores.comparison.average_mood, 2.5);
+    assert_eq!(chores.comparison.baseline_mood, Some(5.5));
+    assert_eq!(chores.comparison.difference, Some(-3.0));
+
+    // Only filled with the activity breakdown
+    let mood_data = viz_repo
+        .get_mood_chart_data(None, None, false)
+        .expect("Failed to get mood chart data");
+    assert!(mood_data.group_breakdown.is_empty());
+}
+
+// Combined timeline
+#[test]
+fn test_combined_timeline_aligns_series() {
+    let (viz_repo, assessment_repo, mood_repo, temp_dir, group_id) = setup_test_repo();
+
+    for rating in [6, 5, 3, 1] {
+        mood_repo
+            .create_mood_checkin(rating, vec![], None)
+            .expect("Failed to create mood check-in");
+    }
+    set_timestamps(
+        &temp_dir,
+        "mood_checkins",
+        "created_at",
+        &[
+            "2025-03-01 10:00:00",
+            "2025-03-02 10:00:00",
+            "2025-03-03 10:00:00",
+            "2025-03-04 10:00:00",
+        ],
+    );
+
+    // PHQ-9 on every mood day but March 3rd
+    for score in [3, 6, 20] {
+        assessment_repo
+            .save_assessment(
+                1,
+                &[1; 9],
+                score,
+                SeverityLevel::Minimal,
+                None,
+                AssessmentStatus::Completed,
+            )
+            .expect("Failed to create assessment");
+    }
+    set_timestamps(
+        &temp_dir,
+        "assessment_responses",
+        "completed_at",
+        &[
+            "2025-03-01 11:00:00",
+            "2025-03-02 11:00:00",
+            "2025-03-04 11:00:00",
+        ],
+    );
+
+    // Three logs in the default group, one of them deleted
+    let walk = mood_repo
+        .create_activity("Walk", None, None, group_id)
+        .expect("Failed to create activity");
+    let conn = rusqlite::Connection::open(temp_dir.path().join("mental_health_tracker.db"))
+        .expect("Failed to open database");
+    for (logged_at, deleted_at) in [
+        ("2025-03-01T08:00:00Z", None),
+        ("2025-03-01T18:00:00Z", None),
+        ("2025-03-03T08:00:00Z", None),
+        ("2025-03-03T09:00:00Z", Some("2025-03-03 10:00:00")),
+    ] {
+        conn.execute(
+            "INSERT INTO activity_logs (activity_id, logged_at, deleted_at) VALUES (?1, ?2, ?3)",
+            rusqlite::params![walk.id, logged_at, deleted_at],
+        )
+        .expect("Failed to log activity");
+    }
+
+    let timeline = viz_repo
+        .get_combined_timeline(Some("2025-02-28 00:00:00"), Some("2025-03-05 23:59:59"))
+        .expect("Failed to get combined timeline");
+
+    assert_eq!(timeline.days.len(), 6);
+    assert_eq!(timeline.days[0], "2025-02-28");
+    assert_eq!(timeline.days[5], "2025-03-05");
+    assert_eq!(
+        timeline.mood,
+        vec![None, Some(6.0), Some(5.0), Some(3.0), Some(1.0), None]
+    );
+
+    // Only types with data in the range
+    assert_eq!(timeline.assessments.len(), 1);
+    let phq9 = &timeline.assessments[0];
+    assert_eq!(phq9.assessment_type_code, "PHQ9");
+    assert_eq!(phq9.max_score, 27);
+    assert_eq!(
+        phq9.values,
+        vec![
+            None,
+            Some(3.0 / 27.0),
+            Some(6.0 / 27.0),
+            None,
+            Some(20.0 / 27.0),
+            None
+        ]
+    );
+
+    // Paired on March 1st, 2nd and 4th: scores rise as mood falls
+    let correlation = phq9
+        .mood_correlation
+        .as_ref()
+        .expect("Expected correlation");
+    assert_eq!(correlation.paired_days, 3);
+    assert!((correlation.spearman - -1.0).abs() < 1e-9);
+    assert!(correlation.pearson < -0.9);
+
+    assert_eq!(timeline.activity_groups.len(), 1);
+    assert_eq!(timeline.activity_groups[0].group_id, group_id);
+    assert_eq!(timeline.activity_groups[0].counts, vec![0, 2, 0, 1, 0, 0]);
+
+    // Without bounds the timeline spans the days with data
+    let timeline = viz_repo
+        .get_combined_timeline(None, None)
+        .expect("Failed to get combined timeline");
+    assert_eq!(
+        timeline.days,
+        vec!["2025-03-01", "2025-03-02", "2025-03-03", "2025-03-04"]
+    );
+}
+
+#[test]
+fn test_combined_timeline_no_data() {
+    let (viz_repo, _, _, _temp_dir, _group_id) = setup_test_repo();
+
+    let result = viz_repo.get_combined_timeline(None, None);
+    assert!(matches!(result, Err(VisualizationError::NoData)));
+}
+
+#[test]
+fn test_combined_timeline_rejects_long_ranges() {
+    let (viz_repo, _, mood_repo, _temp_dir, _group_id) = setup_test_repo();
+    mood_repo
+        .create_mood_checkin(4, vec![], None)
+        .expect("Failed to create mood check-in");
+
+    let result = viz_repo.get_combined_timeline(Some("1990-01-01"), Some("2030-01-01"));
+    assert!(matches!(
+        result,
+        Err(VisualizationError::InvalidDateRange(_))
+    ));
+}
+
+// Calendar heatmap
+#[test]
+fn test_calendar_heatmap_includes_every_day() {
+    let (viz_repo, assessment_repo, mood_repo, temp_dir, group_id) = setup_test_repo();
+
+    for rating in [3, 6, 5] {
+        mood_repo
+            .create_mood_checkin(rating, vec![], None)
+            .expect("Failed to create mood check-in");
+    }
+    set_timestamps(
+        &temp_dir,
+        "mood_checkins",
+        "created_at",
+        &[
+            "2024-12-31 23:30:00",
+            "2025-01-02 08:00:00",
+            "2025-01-02 20:00:00",
+        ],
+    );
+
+    // One completed, one draft
+    for status in [AssessmentStatus::Completed, AssessmentStatus::Draft] {
+        assessment_repo
+            .save_assessment(1, &[1; 9], 9, SeverityLevel::Mild, None, status)
+            .expect("Failed to save assessment");
+    }
+    set_timestamps(
+        &temp_dir,
+        "assessment_responses",
+        "completed_at",
+        &["2025-01-03 12:00:00", "2025-01-01 12:00:00"],
+    );
+
+    let walk = mood_repo
+        .create_activity("Walk", None, None, group_id)
+        .expect("Failed to create activity");
+    let conn = rusqlite::Connection::open(temp_dir.path().join("mental_health_tracker.db"))
+        .expect("Failed to open database");
+    conn.execute(
+        "INSERT INTO activity_logs (activity_id, logged_at) VALUES (?1, '2025-01-02T07:00:00Z')",
+        [walk.id],
+    )
+    .expect("Failed to log activity");
+
+    let heatmap = viz_repo
+        .get_calendar_heatmap("2025-01-01", "2025-01-04", Some("UTC"))
+        .expect("Failed to get heatmap");
+
+    assert_eq!(heatmap.timezone, "UTC");
+    let dates: Vec<&str> = heatmap.days.iter().map(|d| d.date.as_str()).collect();
+    assert_eq!(
+        dates,
+        vec!["2025-01-01", "2025-01-02", "2025-01-03", "2025-01-04"]
+    );
+    let counts: Vec<i32> = heatmap.days.iter().map(|d| d.checkin_count).collect();
+    assert_eq!(counts, vec![0, 2, 0, 0]);
+    assert_eq!(heatmap.days[0].average_mood, None);
+    assert_eq!(heatmap.days[1].average_mood, Some(5.5));
+    assert_eq!(heatmap.days[1].activity_group_ids, vec![group_id]);
+    let completed: Vec<bool> = heatmap
+        .days
+        .iter()
+        .map(|d| d.assessment_completed)
+        .collect();
+    assert_eq!(completed, vec![false, false, true, false]);
+    assert_eq!(heatmap.activity_groups.len(), 1);
+    assert_eq!(heatmap.activity_groups[0].name, "Default Group");
+
+    // Half past eleven UTC on New Year's Eve is already January 1st in Berlin
+    let heatmap = viz_repo
+        .get_calendar_heatmap("2025-01-01", "2025-01-01", Some("Europe/Berlin"))
+        .expect("Failed to get heatmap");
+    assert_eq!(heatmap.days.len(), 1);
+    assert_eq!(heatmap.days[0].average_mood, Some(3.0));
+}
+
+#[test]
+fn test_calendar_heatmap_rejects_bad_input() {
+    let (viz_repo, _, _, _temp_dir, _group_id) = setup_test_repo();
+
+    let result = viz_repo.get_calendar_heatmap("2025-01-05", "2025-01-01", Some("UTC"));
+    assert!(matches!(
+        result,
+        Err(VisualizationError::InvalidDateRange(_))
+    ));
+
+    let result = viz_repo.get_calendar_heatmap("01/01/2025", "2025-01-05", Some("UTC"));
+    assert!(matches!(
+        result,
+        Err(VisualizationError::InvalidDateRange(_))
+    ));
+
+    let result = viz_repo.get_calendar_heatmap("2000-01-01", "2025-01-01", Some("UTC"));
+    assert!(matches!(
+        result,
+        Err(VisualizationError::InvalidDateRange(_))
+    ));
+
+    let result = viz_repo.get_calendar_heatmap("2025-01-01", "2025-01-05", Some("Mars/Base"));
+    assert!(matches!(
+        result,
+        Err(VisualizationError::InvalidTimezone(_))
+    ));
+
+    // An empty range still has a record per day
+    let heatmap = viz_repo
+        .get_calendar_heatmap("2025-01-01", "2025-12-31", Some("UTC"))
+        .expect("Failed to get heatmap");
+    assert_eq!(heatmap.days.len(), 365);
+    assert!(heatmap.activity_groups.is_empty());
+}
+
+// Downsampling
+#[test]
+fn test_chart_data_downsampled_to_max_points() {
+    let (viz_repo, assessment_repo, mood_repo, _temp_dir, _group_id) = setup_test_repo();
+
+    for i in 0..60 {
+        mood_repo
+            .create_mood_checkin(1 + i % 7, vec![], None)
+            .expect("Failed to create mood check-in");
+        assessment_repo
+            .save_assessment(
+                1,
+                &[1; 9],
+                i % 28,
+                SeverityLevel::Minimal,
+                None,
+                AssessmentStatus::Completed,
+            )
+            .expect("Failed to create assessment");
+    }
+
+    let mood_data = viz_repo
+        .get_mood_chart_data_with_options(
+            None,
+            None,
+            false,
+            &ChartQueryOptions {
+                max_points: Some(12),
+                ..Default::default()
+            },
+        )
+        .expect("Failed to get mood chart data");
+    assert_eq!(mood_data.data_points.len(), 12);
+    // Statistics still cover every check-in
+    assert_eq!(mood_data.statistics.total_checkins, 60);
+    assert_eq!(mood_data.statistics.min, 1);
+    assert_eq!(mood_data.statistics.max, 7);
+
+    let chart_data = viz_repo
+        .get_assessment_chart_data_with_options(
+            "PHQ9",
+            None,
+            None,
+            &ChartQueryOptions {
+                max_points: Some(12),
+                ..Default::default()
+            },
+        )
+        .expect("Failed to get chart data");
+    assert_eq!(chart_data.data_points.len(), 12);
+    assert_eq!(chart_data.statistics.total_assessments, 60);
+
+    // A limit above the number of points changes nothing
+    let mood_data = viz_repo
+        .get_mood_chart_data_with_options(
+            None,
+            None,
+            false,
+            &ChartQueryOptions {
+                max_points: Some(1000),
+                ..Default::default()
+            },
+        )
+        .expect("Failed to get mood chart data");
+    assert_eq!(mood_data.data_points.len(), 60);
+
+    let result = viz_repo.get_mood_chart_data_with_options(
+        None,
+        None,
+        false,
+        &ChartQueryOptions {
+            max_points: Some(2),
+            ..Default::default()
+        },
+    );
+    assert!(matches!(
+        result,
+        Err(VisualizationError::InvalidMaxPoints { value: 2, min: 3 })
+    ));
+}
+
+// Period comparison
+#[test]
+fn test_period_comparison_side_by_side() {
+    let (viz_repo, assessment_repo, mood_repo, temp_dir, _group_id) = setup_test_repo();
+
+    for rating in [3, 3, 5, 6, 7] {
+        mood_repo
+            .create_mood_checkin(rating, vec![], None)
+            .expect("Failed to create mood check-in");
+    }
+    set_timestamps(
+        &temp_dir,
+        "mood_checkins",
+        "created_at",
+        &[
+            "2025-02-03 09:00:00",
+            "2025-02-05 09:00:00",
+            "2025-02-10 09:00:00",
+            "2025-02-12 09:00:00",
+            "2025-02-14 09:00:00",
+        ],
+    );
+
+    // PHQ-9 only in the current period
+    for score in [8, 6] {
+        assessment_repo
+            .save_assessment(
+                1,
+                &[1; 9],
+                score,
+                SeverityLevel::Mild,
+                None,
+                AssessmentStatus::Completed,
+            )
+            .expect("Failed to create assessment");
+    }
+    set_timestamps(
+        &temp_dir,
+        "assessment_responses",
+        "completed_at",
+        &["2025-02-10 12:00:00", "2025-02-14 12:00:00"],
+    );
+
+    let current = DateBounds {
+        from_date: "2025-02-10 00:00:00".to_string(),
+        to_date: "2025-02-16 23:59:59".to_string(),
+    };
+    let previous = DateBounds {
+        from_date: "2025-02-03 00:00:00".to_string(),
+        to_date: "2025-02-09 23:59:59".to_string(),
+    };
+    let comparison = viz_repo
+        .get_period_comparison(&current, &previous)
+        .expect("Failed to compare periods");
+
+    assert_eq!(comparison.current_period, current);
+    assert_eq!(comparison.previous_period, previous);
+
+    let mood_now = comparison.mood.current.as_ref().expect("Expected mood");
+    let mood_before = comparison.mood.previous.as_ref().expect("Expected mood");
+    assert_eq!(mood_now.total_checkins, 3);
+    assert_eq!(mood_before.total_checkins, 2);
+    assert!((mood_now.average - 6.0).abs() < 1e-9);
+    assert!((comparison.mood.average_change.unwrap() - 3.0).abs() < 1e-9);
+
+    // GAD-7 has no data in either period and is left out
+    assert_eq!(comparison.assessments.len(), 1);
+    let phq9 = &comparison.assessments[0];
+    assert_eq!(phq9.assessment_type_code, "PHQ9");
+    assert_eq!(phq9.current.as_ref().unwrap().total_assessments, 2);
+    assert!(phq9.previous.is_none());
+    assert_eq!(phq9.average_change, None);
+}
+
+#[test]
+fn test_severity_distribution_carries_results_forward() {
+    let (viz_repo, assessment_repo, _mood_repo, temp_dir, _group_id) = setup_test_repo();
+
+    for (score, severity) in [
+        (12, SeverityLevel::Moderate),
+        (22, SeverityLevel::Severe),
+        (7, SeverityLevel::Mild),
+        (16, SeverityLevel::ModeratelySevere),
+    ] {
+        assessment_repo
+            .save_assessment(
+                1,
+                &[1; 9],
+                score,
+                severity,
+                None,
+                AssessmentStatus::Completed,
+            )
+            .expect("Failed to create assessment");
+    }
+    set_timestamps(
+        &temp_dir,
+        "assessment_responses",
+        "completed_at",
+        &[
+            "2025-01-01 00:00:00",
+            "2025-01-11 00:00:00",
+            "2025-01-21 00:00:00",
+            "2025-02-10 00:00:00",
+        ],
+    );
+    let now: DateTime<Utc> = "2025-03-01T00:00:00Z".parse().unwrap();
+
+    // The moderate result from before the range is carried into it
+    let distribution = viz_repo
+        .get_severity_distribution(
+            "PHQ9",
+            Some("2025-01-06 00:00:00"),
+            Some("2025-02-20 00:00:00"),
+            SeverityLevel::Moderate,
+            now,
+        )
+        .expect("Failed to get severity distribution");
+
+    assert_eq!(distribution.tracked_days, 45.0);
+    assert_eq!(distribution.untracked_days, 0.0);
+    let days: Vec<(SeverityLevel, f64)> = distribution
+        .severity_shares
+        .iter()
+        .map(|s| (s.severity, s.days))
+        .collect();
+    assert_eq!(
+        days,
+        [
+            (SeverityLevel::Minimal, 0.0),
+            (SeverityLevel::Mild, 20.0),
+            (SeverityLevel::Moderate, 5.0),
+            (SeverityLevel::ModeratelySevere, 10.0),
+            (SeverityLevel::Severe, 10.0),
+        ]
+    );
+
+    // Only results completed within the range are counted
+    assert_eq!(distribution.score_histogram.len(), 28);
+    let counted: Vec<i32> = distribution
+        .score_histogram
+        .iter()
+        .filter(|bin| bin.count > 0)
+        .map(|bin| bin.min_score)
+        .collect();
+    assert_eq!(counted, [7, 16, 22]);
+
+    assert_eq!(distribution.episodes.len(), 2);
+    let first = &distribution.episodes[0];
+    assert_eq!(first.start, "2025-01-06 00:00:00");
+    assert_eq!(first.end, "2025-01-21 00:00:00");
+    assert_eq!(first.peak_severity, SeverityLevel::Severe);
+    assert_eq!(first.peak_score, 22);
+    assert!(distribution.episodes[1].ongoing);
+    assert_eq!(distribution.longest_episode_days, Some(15.0));
+    assert_eq!(distribution.average_episode_days, Some(12.5));
+
+    // All time runs from the first result to now
+    let distribution = viz_repo
+        .get_severity_distribution("PHQ9", None, None, SeverityLevel::Severe, now)
+        .expect("Failed to get severity distribution");
+    assert_eq!(distribution.from_date, "2025-01-01 00:00:00");
+    assert_eq!(distribution.to_date, "2025-03-01 00:00:00");
+    assert_eq!(distribution.tracked_days, 59.0);
+    assert_eq!(distribution.episodes.len(), 1);
+    assert_eq!(distribution.episodes[0].days, 10.0);
+}
+
+#[test]
+fn test_severity_distribution_rejects_bad_input() {
+    let (viz_repo, assessment_repo, _mood_repo, _temp_dir, _group_id) = setup_test_repo();
+    assessment_repo
+        .save_assessment(
+            1,
+            &[1; 9],
+            8,
+            SeverityLevel::Mild,
+            None,
+            AssessmentStatus::Completed,
+        )
+        .expect("Failed to create assessment");
+    let now = Utc::now();
+
+    let result =
+        viz_repo.get_severity_distribution("GAD7", None, None, SeverityLevel::Moderate, now);
+    assert!(matches!(result, Err(VisualizationError::NoData)));
+
+    let result =
+        viz_repo.get_severity_distribution("PHQ9", None, None, SeverityLevel::Unknown, now);
+    assert!(matches!(
+        result,
+        Err(VisualizationError::InvalidEpisodeSeverity)
+    ));
+
+    let result = viz_repo.get_severity_distribution(
+        "PHQ9",
+        Some("yesterday"),
+        None,
+        SeverityLevel::Moderate,
+        now,
+    );
+    assert!(matches!(
+        result,
+        Err(VisualizationError::InvalidDateRange(_))
+    ));
+}
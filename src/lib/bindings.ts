@@ -223,13 +223,17 @@ export const commands = {
 		}
 	},
 	/**
-	 * T126: Get assessment chart data for visualization
+	 * T126: Get assessment chart data for visualization. Derived series use
+	 * days in `timezone`, defaulting to the system timezone.
 	 */
 	async getAssessmentChartData(
 		code: string,
 		timeRange: TimeRange,
 		fromDate: string | null,
-		toDate: string | null
+		toDate: string | null,
+		series: DerivedSeriesKind[] | null,
+		maxPoints: number | null,
+		timezone: string | null
 	): Promise<Result<AssessmentChartData, CommandError>> {
 		try {
 			return {
@@ -239,6 +243,9 @@ export const commands = {
 					timeRange,
 					fromDate,
 					toDate,
+					series,
+					maxPoints,
+					timezone,
 				}),
 			}
 		} catch (e) {
@@ -247,13 +254,17 @@ export const commands = {
 		}
 	},
 	/**
-	 * T144: Get mood chart data for visualization
+	 * T144: Get mood chart data for visualization. Derived series use days in
+	 * `timezone`, defaulting to the system timezone.
 	 */
 	async getMoodChartData(
 		timeRange: TimeRange,
 		fromDate: string | null,
 		toDate: string | null,
-		groupByActivity: boolean
+		groupByActivity: boolean,
+		series: DerivedSeriesKind[] | null,
+		maxPoints: number | null,
+		timezone: string | null
 	): Promise<Result<MoodChartData, CommandError>> {
 		try {
 			return {
@@ -263,6 +274,123 @@ export const commands = {
 					fromDate,
 					toDate,
 					groupByActivity,
+					series,
+					maxPoints,
+					timezone,
+				}),
+			}
+		} catch (e) {
+			if (e instanceof Error) throw e
+			else return { status: 'error', error: e as any }
+		}
+	},
+	/**
+	 * Get mood, assessments and activity groups as aligned daily series,
+	 * with the correlation between mood and each assessment
+	 */
+	async getCombinedTimeline(
+		timeRange: TimeRange,
+		fromDate: string | null,
+		toDate: string | null
+	): Promise<Result<CombinedTimeline, CommandError>> {
+		try {
+			return {
+				status: 'ok',
+				data: await TAURI_INVOKE('get_combined_timeline', { timeRange, fromDate, toDate }),
+			}
+		} catch (e) {
+			if (e instanceof Error) throw e
+			else return { status: 'error', error: e as any }
+		}
+	},
+	/**
+	 * Get per-day mood, check-in, activity group and assessment aggregates
+	 * for a calendar heatmap. Days are YYYY-MM-DD in `timezone`, defaulting
+	 * to the system timezone.
+	 */
+	async getCalendarHeatmap(
+		fromDay: string,
+		toDay: string,
+		timezone: string | null
+	): Promise<Result<CalendarHeatmap, CommandError>> {
+		try {
+			return {
+				status: 'ok',
+				data: await TAURI_INVOKE('get_calendar_heatmap', { fromDay, toDay, timezone }),
+			}
+		} catch (e) {
+			if (e instanceof Error) throw e
+			else return { status: 'error', error: e as any }
+		}
+	},
+	/**
+	 * Get mood and assessment statistics for a period next to its previous
+	 * equivalent period. Calendar ranges are resolved in the system timezone.
+	 */
+	async getPeriodComparison(
+		timeRange: TimeRange,
+		fromDate: string | null,
+		toDate: string | null
+	): Promise<Result<PeriodComparison, CommandError>> {
+		try {
+			return {
+				status: 'ok',
+				data: await TAURI_INVOKE('get_period_comparison', { timeRange, fromDate, toDate }),
+			}
+		} catch (e) {
+			if (e instanceof Error) throw e
+			else return { status: 'error', error: e as any }
+		}
+	},
+	/**
+	 * Get the time-weighted share of days at each severity level of an
+	 * assessment type, a histogram of its scores, and the episodes at or above
+	 * `episode_severity` (moderate unless given)
+	 */
+	async getSeverityDistribution(
+		code: string,
+		timeRange: TimeRange,
+		fromDate: string | null,
+		toDate: string | null,
+		episodeSeverity: SeverityLevel | null
+	): Promise<Result<SeverityDistribution, CommandError>> {
+		try {
+			return {
+				status: 'ok',
+				data: await TAURI_INVOKE('get_severity_distribution', {
+					code,
+					timeRange,
+					fromDate,
+					toDate,
+					episodeSeverity,
+				}),
+			}
+		} catch (e) {
+			if (e instanceof Error) throw e
+			else return { status: 'error', error: e as any }
+		}
+	},
+	/**
+	 * Render the assessment chart for `assessment_type_code`, or the mood chart
+	 * if None, and save it where the user picks in a save dialog.
+	 * Returns the saved path, or None if the dialog was cancelled.
+	 */
+	async saveChartImage(
+		assessmentTypeCode: string | null,
+		timeRange: TimeRange,
+		fromDate: string | null,
+		toDate: string | null,
+		format: ChartImageFormat
+	): Promise<Result<string | null, CommandError>> {
+		try {
+			return {
+				status: 'ok',
+				data: await TAURI_INVOKE('save_chart_image', {
+					assessmentTypeCode,
+					timeRange,
+					fromDate,
+					toDate,
+					format,
 				}),
 			}
 		} catch (e) {
@@ -309,240 +437,803 @@ export const commands = {
 		}
 	},
 	/**
-	 * T168: Get all schedules (optionally filtered to enabled only)
+	 * Snooze a reminder notification; it fires again after `minutes`
 	 */
-	async getSchedules(enabledOnly: boolean): Promise<Result<AssessmentSchedule[], CommandError>> {
+	async snoozeReminder(
+		eventId: number,
+		minutes: number
+	): Promise<Result<ReminderEvent, CommandError>> {
 		try {
-			return { status: 'ok', data: await TAURI_INVOKE('get_schedules', { enabledOnly }) }
+			return { status: 'ok', data: await TAURI_INVOKE('snooze_reminder', { eventId, minutes }) }
 		} catch (e) {
 			if (e instanceof Error) throw e
 			else return { status: 'error', error: e as any }
 		}
 	},
 	/**
-	 * T169: Get a single schedule by ID
+	 * Dismiss a reminder notification without completing it
 	 */
-	async getSchedule(id: number): Promise<Result<AssessmentSchedule, CommandError>> {
+	async dismissReminder(eventId: number): Promise<Result<ReminderEvent, CommandError>> {
 		try {
-			return { status: 'ok', data: await TAURI_INVOKE('get_schedule', { id }) }
+			return { status: 'ok', data: await TAURI_INVOKE('dismiss_reminder', { eventId }) }
 		} catch (e) {
 			if (e instanceof Error) throw e
 			else return { status: 'error', error: e as any }
 		}
 	},
-	async createActivityGroup(
-		request: CreateActivityGroupRequest
-	): Promise<Result<ActivityGroup, CommandError>> {
+	/**
+	 * Mark a reminder as done. Assessment reminders are completed automatically
+	 * when the assessment is submitted.
+	 */
+	async completeReminder(eventId: number): Promise<Result<ReminderEvent, CommandError>> {
 		try {
-			return { status: 'ok', data: await TAURI_INVOKE('create_activity_group', { request }) }
+			return { status: 'ok', data: await TAURI_INVOKE('complete_reminder', { eventId }) }
 		} catch (e) {
 			if (e instanceof Error) throw e
 			else return { status: 'error', error: e as any }
 		}
 	},
-	async updateActivityGroup(
-		id: number,
-		request: UpdateActivityGroupRequest
-	): Promise<Result<ActivityGroup, CommandError>> {
+	/**
+	 * Mark missed reminders as seen so they no longer show up
+	 */
+	async acknowledgeMissedReminders(ids: number[]): Promise<Result<null, CommandError>> {
 		try {
-			return { status: 'ok', data: await TAURI_INVOKE('update_activity_group', { id, request }) }
+			return { status: 'ok', data: await TAURI_INVOKE('acknowledge_missed_reminders', { ids }) }
 		} catch (e) {
 			if (e instanceof Error) throw e
 			else return { status: 'error', error: e as any }
 		}
 	},
-	async deleteActivityGroup(id: number): Promise<Result<null, CommandError>> {
+	/**
+	 * Replace a schedule's adaptive frequency rules (an empty list removes them)
+	 */
+	async setAdaptiveRules(
+		scheduleId: number,
+		rules: AdaptiveRuleRequest[]
+	): Promise<Result<AdaptiveRule[], CommandError>> {
 		try {
-			return { status: 'ok', data: await TAURI_INVOKE('delete_activity_group', { id }) }
+			return { status: 'ok', data: await TAURI_INVOKE('set_adaptive_rules', { scheduleId, rules }) }
 		} catch (e) {
 			if (e instanceof Error) throw e
 			else return { status: 'error', error: e as any }
 		}
 	},
-	async getActivityGroups(): Promise<Result<ActivityGroup[], CommandError>> {
+	/**
+	 * Replace quiet hours, quiet-time policy, pause and the notification switch
+	 */
+	async updateNotificationSettings(
+		settings: NotificationSettings
+	): Promise<Result<NotificationSettings, CommandError>> {
 		try {
-			return { status: 'ok', data: await TAURI_INVOKE('get_activity_groups') }
+			return {
+				status: 'ok',
+				data: await TAURI_INVOKE('update_notification_settings', { settings }),
+			}
 		} catch (e) {
 			if (e instanceof Error) throw e
 			else return { status: 'error', error: e as any }
 		}
 	},
-	async logActivity(request: LogActivityRequest): Promise<Result<ActivityLog, CommandError>> {
+	/**
+	 * Pause all reminders until the given RFC 3339 time
+	 */
+	async pauseReminders(until: string): Promise<Result<NotificationSettings, CommandError>> {
 		try {
-			return { status: 'ok', data: await TAURI_INVOKE('log_activity', { request }) }
+			return { status: 'ok', data: await TAURI_INVOKE('pause_reminders', { until }) }
 		} catch (e) {
 			if (e instanceof Error) throw e
 			else return { status: 'error', error: e as any }
 		}
 	},
 	/**
-	 * Update notes for an existing activity log.
-	 *
-	 * Frontend usage:
-	 * - Allows editing/clearing notes from ActivityLogHistory
-	 * - Enforces same constraints as log_activity (<= 500 chars, trimmed)
+	 * End a pause started with `pause_reminders`
 	 */
-	async updateActivityLog(
-		id: number,
-		notes: string | null
-	): Promise<Result<ActivityLog, CommandError>> {
+	async resumeReminders(): Promise<Result<NotificationSettings, CommandError>> {
 		try {
-			return { status: 'ok', data: await TAURI_INVOKE('update_activity_log', { id, notes }) }
+			return { status: 'ok', data: await TAURI_INVOKE('resume_reminders') }
 		} catch (e) {
 			if (e instanceof Error) throw e
 			else return { status: 'error', error: e as any }
 		}
 	},
-	async getActivityLogs(
-		activityId: number | null,
-		startDate: string | null,
-		endDate: string | null
-	): Promise<Result<ActivityLog[], CommandError>> {
+	/**
+	 * Write all enabled schedules and planned activities to an iCalendar (.ics)
+	 * file the user picks in a save dialog.
+	 * Returns the number of events written, or None if the dialog was cancelled.
+	 */
+	async exportSchedulesIcal(): Promise<Result<number | null, CommandError>> {
 		try {
-			return {
-				status: 'ok',
-				data: await TAURI_INVOKE('get_activity_logs', { activityId, startDate, endDate }),
-			}
+			return { status: 'ok', data: await TAURI_INVOKE('export_schedules_ical') }
 		} catch (e) {
 			if (e instanceof Error) throw e
 			else return { status: 'error', error: e as any }
 		}
 	},
-	async setActivityGoal(
-		request: SetActivityGoalRequest
-	): Promise<Result<ActivityGoal, CommandError>> {
+	/**
+	 * Create schedules and planned activities from the VEVENTs of an iCalendar
+	 * (.ics) file the user picks in an open dialog.
+	 * Returns None if the dialog was cancelled.
+	 */
+	async importSchedulesIcal(): Promise<Result<CalendarImportResult | null, CommandError>> {
 		try {
-			return { status: 'ok', data: await TAURI_INVOKE('set_activity_goal', { request }) }
+			return { status: 'ok', data: await TAURI_INVOKE('import_schedules_ical') }
 		} catch (e) {
 			if (e instanceof Error) throw e
 			else return { status: 'error', error: e as any }
 		}
 	},
-	async updateActivityGoal(
-		goalId: number,
-		targetValue: number,
-		periodDays: number
-	): Promise<Result<ActivityGoal, CommandError>> {
+	/**
+	 * T168: Get all schedules (optionally filtered to enabled only)
+	 */
+	async getSchedules(enabledOnly: boolean): Promise<Result<AssessmentSchedule[], CommandError>> {
 		try {
-			return {
-				status: 'ok',
-				data: await TAURI_INVOKE('update_activity_goal', { goalId, targetValue, periodDays }),
-			}
+			return { status: 'ok', data: await TAURI_INVOKE('get_schedules', { enabledOnly }) }
 		} catch (e) {
 			if (e instanceof Error) throw e
 			else return { status: 'error', error: e as any }
 		}
 	},
-	async deleteActivityGoal(goalId: number): Promise<Result<null, CommandError>> {
+	/**
+	 * T169: Get a single schedule by ID
+	 */
+	async getSchedule(id: number): Promise<Result<AssessmentSchedule, CommandError>> {
 		try {
-			return { status: 'ok', data: await TAURI_INVOKE('delete_activity_goal', { goalId }) }
+			return { status: 'ok', data: await TAURI_INVOKE('get_schedule', { id }) }
 		} catch (e) {
 			if (e instanceof Error) throw e
 			else return { status: 'error', error: e as any }
 		}
 	},
-	async getActivityGoals(
-		activityId: number | null,
-		groupId: number | null
-	): Promise<Result<ActivityGoal[], CommandError>> {
+	/**
+	 * List the next occurrences of a recurrence rule, e.g. to preview it while editing
+	 */
+	async previewScheduleOccurrences(
+		request: PreviewOccurrencesRequest
+	): Promise<Result<ScheduleOccurrence[], CommandError>> {
+		try {
+			return { status: 'ok', data: await TAURI_INVOKE('preview_schedule_occurrences', { request }) }
+		} catch (e) {
+			if (e instanceof Error) throw e
+			else return { status: 'error', error: e as any }
+		}
+	},
+	/**
+	 * Reminder events (fired, snoozed, dismissed, completed) of a schedule, newest first
+	 */
+	async getReminderEvents(
+		scheduleId: number,
+		limit: number | null
+	): Promise<Result<ReminderEvent[], CommandError>> {
 		try {
 			return {
 				status: 'ok',
-				data: await TAURI_INVOKE('get_activity_goals', { activityId, groupId }),
+				data: await TAURI_INVOKE('get_reminder_events', { scheduleId, limit }),
 			}
 		} catch (e) {
 			if (e instanceof Error) throw e
 			else return { status: 'error', error: e as any }
 		}
 	},
-	async getActivityFrequency(
-		activityId: number,
-		startDate: string,
-		endDate: string
-	): Promise<Result<ActivityFrequency, CommandError>> {
+	/**
+	 * Per-schedule adherence for reminders fired between `from_date` and `to_date` (UTC)
+	 */
+	async getReminderAdherence(
+		fromDate: string | null,
+		toDate: string | null
+	): Promise<Result<ReminderAdherence[], CommandError>> {
 		try {
 			return {
 				status: 'ok',
-				data: await TAURI_INVOKE('get_activity_frequency', { activityId, startDate, endDate }),
+				data: await TAURI_INVOKE('get_reminder_adherence', { fromDate, toDate }),
 			}
 		} catch (e) {
 			if (e instanceof Error) throw e
 			else return { status: 'error', error: e as any }
 		}
 	},
-	async getActivityTrend(
-		activityId: number,
-		periodDays: number,
-		currentTime: string
-	): Promise<Result<ActivityTrend, CommandError>> {
+	/**
+	 * Reminders that fell due while the app was closed, newest first
+	 */
+	async getMissedReminders(
+		includeAcknowledged: boolean
+	): Promise<Result<MissedReminder[], CommandError>> {
 		try {
 			return {
 				status: 'ok',
-				data: await TAURI_INVOKE('get_activity_trend', { activityId, periodDays, currentTime }),
+				data: await TAURI_INVOKE('get_missed_reminders', { includeAcknowledged }),
 			}
 		} catch (e) {
 			if (e instanceof Error) throw e
 			else return { status: 'error', error: e as any }
 		}
 	},
-	async checkGoalProgress(
-		goalId: number,
-		currentTime: string
-	): Promise<Result<GoalProgress, CommandError>> {
+	/**
+	 * Adaptive frequency rules of a schedule, in evaluation order
+	 */
+	async getAdaptiveRules(scheduleId: number): Promise<Result<AdaptiveRule[], CommandError>> {
+		try {
+			return { status: 'ok', data: await TAURI_INVOKE('get_adaptive_rules', { scheduleId }) }
+		} catch (e) {
+			if (e instanceof Error) throw e
+			else return { status: 'error', error: e as any }
+		}
+	},
+	/**
+	 * Frequency changes made by adaptive rules, newest first, with their reasons
+	 */
+	async getFrequencyChanges(
+		scheduleId: number | null,
+		limit: number | null
+	): Promise<Result<FrequencyChange[], CommandError>> {
 		try {
 			return {
 				status: 'ok',
-				data: await TAURI_INVOKE('check_goal_progress', { goalId, currentTime }),
+				data: await TAURI_INVOKE('get_frequency_changes', { scheduleId, limit }),
 			}
 		} catch (e) {
 			if (e instanceof Error) throw e
 			else return { status: 'error', error: e as any }
 		}
 	},
-}
-
-/** user-defined events **/
-
-/** user-defined constants **/
-
-/** user-defined types **/
-
-/**
- * Activity model
- *
- * Represents an activity that can be associated with mood check-ins or tracked
- * independently via activity logging. This is the single source of truth for
- * the Activity type across all features (mood, activities, visualization).
- *
- * The `HexColor` newtype ensures color values are always valid hex format.
- */
-export type Activity = {
-	id: number
-	group_id: number
-	name: string
-	/**
-	 * Optional color in validated hex format (#RGB, #RRGGBB, or #RRGGBBAA)
-	 */
-	color: HexColor | null
-	icon: string | null
-	created_at: string
-	deleted_at: string | null
-}
-/**
- * Activity correlation with mood
- */
-export type ActivityCorrelation = {
-	activity: Activity
-	average_mood: number
-	checkin_count: number
-}
-/**
- * Activity frequency report showing days per week
- */
-export type ActivityFrequency = {
-	activity_id: number
 	/**
-	 * Number of unique days with activity logs
+	 * Current quiet hours, quiet-time policy, pause and notification switch
+	 */
+	async getNotificationSettings(): Promise<NotificationSettings> {
+		return await TAURI_INVOKE('get_notification_settings')
+	},
+	async createActivityGroup(
+		request: CreateActivityGroupRequest
+	): Promise<Result<ActivityGroup, CommandError>> {
+		try {
+			return { status: 'ok', data: await TAURI_INVOKE('create_activity_group', { request }) }
+		} catch (e) {
+			if (e instanceof Error) throw e
+			else return { status: 'error', error: e as any }
+		}
+	},
+	async updateActivityGroup(
+		id: number,
+		request: UpdateActivityGroupRequest
+	): Promise<Result<ActivityGroup, CommandError>> {
+		try {
+			return { status: 'ok', data: await TAURI_INVOKE('update_activity_group', { id, request }) }
+		} catch (e) {
+			if (e instanceof Error) throw e
+			else return { status: 'error', error: e as any }
+		}
+	},
+	async deleteActivityGroup(id: number): Promise<Result<null, CommandError>> {
+		try {
+			return { status: 'ok', data: await TAURI_INVOKE('delete_activity_group', { id }) }
+		} catch (e) {
+			if (e instanceof Error) throw e
+			else return { status: 'error', error: e as any }
+		}
+	},
+	async getActivityGroups(): Promise<Result<ActivityGroup[], CommandError>> {
+		try {
+			return { status: 'ok', data: await TAURI_INVOKE('get_activity_groups') }
+		} catch (e) {
+			if (e instanceof Error) throw e
+			else return { status: 'error', error: e as any }
+		}
+	},
+	async getArchivedActivityGroups(): Promise<Result<ActivityGroup[], CommandError>> {
+		try {
+			return { status: 'ok', data: await TAURI_INVOKE('get_archived_activity_groups') }
+		} catch (e) {
+			if (e instanceof Error) throw e
+			else return { status: 'error', error: e as any }
+		}
+	},
+	async setActivityGroupAppearance(
+		id: number,
+		request: SetActivityGroupAppearanceRequest
+	): Promise<Result<ActivityGroup, CommandError>> {
+		try {
+			return {
+				status: 'ok',
+				data: await TAURI_INVOKE('set_activity_group_appearance', { id, request }),
+			}
+		} catch (e) {
+			if (e instanceof Error) throw e
+			else return { status: 'error', error: e as any }
+		}
+	},
+	/**
+	 * Archive an activity group.
+	 *
+	 * Non-destructive alternative to `delete_activity_group`: the group and its
+	 * activities are hidden from pickers, but logs, goals and reports are kept.
+	 */
+	async archiveActivityGroup(id: number): Promise<Result<ActivityGroup, CommandError>> {
+		try {
+			return { status: 'ok', data: await TAURI_INVOKE('archive_activity_group', { id }) }
+		} catch (e) {
+			if (e instanceof Error) throw e
+			else return { status: 'error', error: e as any }
+		}
+	},
+	async unarchiveActivityGroup(id: number): Promise<Result<ActivityGroup, CommandError>> {
+		try {
+			return { status: 'ok', data: await TAURI_INVOKE('unarchive_activity_group', { id }) }
+		} catch (e) {
+			if (e instanceof Error) throw e
+			else return { status: 'error', error: e as any }
+		}
+	},
+	async reorderActivityGroups(
+		orderedIds: number[]
+	): Promise<Result<ActivityGroup[], CommandError>> {
+		try {
+			return { status: 'ok', data: await TAURI_INVOKE('reorder_activity_groups', { orderedIds }) }
+		} catch (e) {
+			if (e instanceof Error) throw e
+			else return { status: 'error', error: e as any }
+		}
+	},
+	async reorderActivities(
+		groupId: number,
+		orderedIds: number[]
+	): Promise<Result<Activity[], CommandError>> {
+		try {
+			return {
+				status: 'ok',
+				data: await TAURI_INVOKE('reorder_activities', { groupId, orderedIds }),
+			}
+		} catch (e) {
+			if (e instanceof Error) throw e
+			else return { status: 'error', error: e as any }
+		}
+	},
+	async logActivity(request: LogActivityRequest): Promise<Result<ActivityLog, CommandError>> {
+		try {
+			return { status: 'ok', data: await TAURI_INVOKE('log_activity', { request }) }
+		} catch (e) {
+			if (e instanceof Error) throw e
+			else return { status: 'error', error: e as any }
+		}
+	},
+	/**
+	 * Update notes for an existing activity log.
+	 *
+	 * Frontend usage:
+	 * - Allows editing/clearing notes from ActivityLogHistory
+	 * - Enforces same constraints as log_activity (<= 500 chars, trimmed)
+	 */
+	async updateActivityLog(
+		id: number,
+		notes: string | null
+	): Promise<Result<ActivityLog, CommandError>> {
+		try {
+			return { status: 'ok', data: await TAURI_INVOKE('update_activity_log', { id, notes }) }
+		} catch (e) {
+			if (e instanceof Error) throw e
+			else return { status: 'error', error: e as any }
+		}
+	},
+	async getActivityLogs(
+		activityId: number | null,
+		startDate: string | null,
+		endDate: string | null
+	): Promise<Result<ActivityLog[], CommandError>> {
+		try {
+			return {
+				status: 'ok',
+				data: await TAURI_INVOKE('get_activity_logs', { activityId, startDate, endDate }),
+			}
+		} catch (e) {
+			if (e instanceof Error) throw e
+			else return { status: 'error', error: e as any }
+		}
+	},
+	async setActivityGoal(
+		request: SetActivityGoalRequest
+	): Promise<Result<ActivityGoal, CommandError>> {
+		try {
+			return { status: 'ok', data: await TAURI_INVOKE('set_activity_goal', { request }) }
+		} catch (e) {
+			if (e instanceof Error) throw e
+			else return { status: 'error', error: e as any }
+		}
+	},
+	async updateActivityGoal(
+		goalId: number,
+		targetValue: number,
+		periodDays: number
+	): Promise<Result<ActivityGoal, CommandError>> {
+		try {
+			return {
+				status: 'ok',
+				data: await TAURI_INVOKE('update_activity_goal', { goalId, targetValue, periodDays }),
+			}
+		} catch (e) {
+			if (e instanceof Error) throw e
+			else return { status: 'error', error: e as any }
+		}
+	},
+	async deleteActivityGoal(goalId: number): Promise<Result<null, CommandError>> {
+		try {
+			return { status: 'ok', data: await TAURI_INVOKE('delete_activity_goal', { goalId }) }
+		} catch (e) {
+			if (e instanceof Error) throw e
+			else return { status: 'error', error: e as any }
+		}
+	},
+	async getActivityGoals(
+		activityId: number | null,
+		groupId: number | null
+	): Promise<Result<ActivityGoal[], CommandError>> {
+		try {
+			return {
+				status: 'ok',
+				data: await TAURI_INVOKE('get_activity_goals', { activityId, groupId }),
+			}
+		} catch (e) {
+			if (e instanceof Error) throw e
+			else return { status: 'error', error: e as any }
+		}
+	},
+	async getActivityFrequency(
+		activityId: number,
+		startDate: string,
+		endDate: string
+	): Promise<Result<ActivityFrequency, CommandError>> {
+		try {
+			return {
+				status: 'ok',
+				data: await TAURI_INVOKE('get_activity_frequency', { activityId, startDate, endDate }),
+			}
+		} catch (e) {
+			if (e instanceof Error) throw e
+			else return { status: 'error', error: e as any }
+		}
+	},
+	async getActivityTrend(
+		activityId: number,
+		periodDays: number,
+		currentTime: string
+	): Promise<Result<ActivityTrend, CommandError>> {
+		try {
+			return {
+				status: 'ok',
+				data: await TAURI_INVOKE('get_activity_trend', { activityId, periodDays, currentTime }),
+			}
+		} catch (e) {
+			if (e instanceof Error) throw e
+			else return { status: 'error', error: e as any }
+		}
+	},
+	async checkGoalProgress(
+		goalId: number,
+		currentTime: string
+	): Promise<Result<GoalProgress, CommandError>> {
+		try {
+			return {
+				status: 'ok',
+				data: await TAURI_INVOKE('check_goal_progress', { goalId, currentTime }),
+			}
+		} catch (e) {
+			if (e instanceof Error) throw e
+			else return { status: 'error', error: e as any }
+		}
+	},
+	async getGroupFrequency(
+		groupId: number,
+		startDate: string,
+		endDate: string
+	): Promise<Result<GroupFrequency, CommandError>> {
+		try {
+			return {
+				status: 'ok',
+				data: await TAURI_INVOKE('get_group_frequency', { groupId, startDate, endDate }),
+			}
+		} catch (e) {
+			if (e instanceof Error) throw e
+			else return { status: 'error', error: e as any }
+		}
+	},
+	async getGroupTrend(
+		groupId: number,
+		periodDays: number,
+		currentTime: string
+	): Promise<Result<GroupTrend, CommandError>> {
+		try {
+			return {
+				status: 'ok',
+				data: await TAURI_INVOKE('get_group_trend', { groupId, periodDays, currentTime }),
+			}
+		} catch (e) {
+			if (e instanceof Error) throw e
+			else return { status: 'error', error: e as any }
+		}
+	},
+	async getWeeklyGroupSummary(
+		currentTime: string
+	): Promise<Result<GroupWeeklySummary[], CommandError>> {
+		try {
+			return { status: 'ok', data: await TAURI_INVOKE('get_weekly_group_summary', { currentTime }) }
+		} catch (e) {
+			if (e instanceof Error) throw e
+			else return { status: 'error', error: e as any }
+		}
+	},
+	async createPlannedActivity(
+		request: CreatePlannedActivityRequest
+	): Promise<Result<PlannedActivity, CommandError>> {
+		try {
+			return { status: 'ok', data: await TAURI_INVOKE('create_planned_activity', { request }) }
+		} catch (e) {
+			if (e instanceof Error) throw e
+			else return { status: 'error', error: e as any }
+		}
+	},
+	async updatePlannedActivity(
+		id: number,
+		request: UpdatePlannedActivityRequest
+	): Promise<Result<PlannedActivity, CommandError>> {
+		try {
+			return { status: 'ok', data: await TAURI_INVOKE('update_planned_activity', { id, request }) }
+		} catch (e) {
+			if (e instanceof Error) throw e
+			else return { status: 'error', error: e as any }
+		}
+	},
+	async deletePlannedActivity(id: number): Promise<Result<null, CommandError>> {
+		try {
+			return { status: 'ok', data: await TAURI_INVOKE('delete_planned_activity', { id }) }
+		} catch (e) {
+			if (e instanceof Error) throw e
+			else return { status: 'error', error: e as any }
+		}
+	},
+	async getPlannedActivities(
+		activityId: number | null,
+		groupId: number | null
+	): Promise<Result<PlannedActivity[], CommandError>> {
+		try {
+			return {
+				status: 'ok',
+				data: await TAURI_INVOKE('get_planned_activities', { activityId, groupId }),
+			}
+		} catch (e) {
+			if (e instanceof Error) throw e
+			else return { status: 'error', error: e as any }
+		}
+	},
+	async getActivityAdherence(
+		activityId: number,
+		startDate: string,
+		endDate: string,
+		currentTime: string
+	): Promise<Result<AdherenceReport, CommandError>> {
+		try {
+			return {
+				status: 'ok',
+				data: await TAURI_INVOKE('get_activity_adherence', {
+					activityId,
+					startDate,
+					endDate,
+					currentTime,
+				}),
+			}
+		} catch (e) {
+			if (e instanceof Error) throw e
+			else return { status: 'error', error: e as any }
+		}
+	},
+	async getGroupAdherence(
+		groupId: number,
+		startDate: string,
+		endDate: string,
+		currentTime: string
+	): Promise<Result<AdherenceReport, CommandError>> {
+		try {
+			return {
+				status: 'ok',
+				data: await TAURI_INVOKE('get_group_adherence', {
+					groupId,
+					startDate,
+					endDate,
+					currentTime,
+				}),
+			}
+		} catch (e) {
+			if (e instanceof Error) throw e
+			else return { status: 'error', error: e as any }
+		}
+	},
+	/**
+	 * List the built-in template catalogue followed by the user's saved templates.
+	 */
+	async getGoalTemplates(): Promise<Result<GoalTemplate[], CommandError>> {
+		try {
+			return { status: 'ok', data: await TAURI_INVOKE('get_goal_templates') }
+		} catch (e) {
+			if (e instanceof Error) throw e
+			else return { status: 'error', error: e as any }
+		}
+	},
+	/**
+	 * Create the group, activities and goals of a template in one transaction.
+	 */
+	async applyGoalTemplate(
+		request: ApplyGoalTemplateRequest
+	): Promise<Result<AppliedGoalTemplate, CommandError>> {
+		try {
+			return { status: 'ok', data: await TAURI_INVOKE('apply_goal_template', { request }) }
+		} catch (e) {
+			if (e instanceof Error) throw e
+			else return { status: 'error', error: e as any }
+		}
+	},
+	/**
+	 * Save an existing group's activities and goals as a reusable template.
+	 */
+	async saveGoalTemplate(
+		request: SaveGoalTemplateRequest
+	): Promise<Result<GoalTemplate, CommandError>> {
+		try {
+			return { status: 'ok', data: await TAURI_INVOKE('save_goal_template', { request }) }
+		} catch (e) {
+			if (e instanceof Error) throw e
+			else return { status: 'error', error: e as any }
+		}
+	},
+	async deleteGoalTemplate(id: number): Promise<Result<null, CommandError>> {
+		try {
+			return { status: 'ok', data: await TAURI_INVOKE('delete_goal_template', { id }) }
+		} catch (e) {
+			if (e instanceof Error) throw e
+			else return { status: 'error', error: e as any }
+		}
+	},
+	async getActivityStreak(
+		activityId: number,
+		graceDays: number,
+		currentTime: string,
+		timezone: string | null
+	): Promise<Result<Streak, CommandError>> {
+		try {
+			return {
+				status: 'ok',
+				data: await TAURI_INVOKE('get_activity_streak', {
+					activityId,
+					graceDays,
+					currentTime,
+					timezone,
+				}),
+			}
+		} catch (e) {
+			if (e instanceof Error) throw e
+			else return { status: 'error', error: e as any }
+		}
+	},
+	async getGroupStreak(
+		groupId: number,
+		graceDays: number,
+		currentTime: string,
+		timezone: string | null
+	): Promise<Result<Streak, CommandError>> {
+		try {
+			return {
+				status: 'ok',
+				data: await TAURI_INVOKE('get_group_streak', { groupId, graceDays, currentTime, timezone }),
+			}
+		} catch (e) {
+			if (e instanceof Error) throw e
+			else return { status: 'error', error: e as any }
+		}
+	},
+	async getMoodCheckinStreak(
+		graceDays: number,
+		currentTime: string,
+		timezone: string | null
+	): Promise<Result<Streak, CommandError>> {
+		try {
+			return {
+				status: 'ok',
+				data: await TAURI_INVOKE('get_mood_checkin_streak', { graceDays, currentTime, timezone }),
+			}
+		} catch (e) {
+			if (e instanceof Error) throw e
+			else return { status: 'error', error: e as any }
+		}
+	},
+	async getStreakMilestones(
+		kind: StreakKind | null,
+		targetId: number | null
+	): Promise<Result<StreakMilestone[], CommandError>> {
+		try {
+			return { status: 'ok', data: await TAURI_INVOKE('get_streak_milestones', { kind, targetId }) }
+		} catch (e) {
+			if (e instanceof Error) throw e
+			else return { status: 'error', error: e as any }
+		}
+	},
+	/**
+	 * Stored weekly digests, newest week first (12 unless `limit` is given)
+	 */
+	async getWeeklyDigests(limit: number | null): Promise<Result<WeeklyDigest[], CommandError>> {
+		try {
+			return { status: 'ok', data: await TAURI_INVOKE('get_weekly_digests', { limit }) }
+		} catch (e) {
+			if (e instanceof Error) throw e
+			else return { status: 'error', error: e as any }
+		}
+	},
+	/**
+	 * Write every table to a versioned JSON document at `path`
+	 */
+	async exportData(path: string): Promise<Result<ExportSummary, CommandError>> {
+		try {
+			return { status: 'ok', data: await TAURI_INVOKE('export_data', { path }) }
+		} catch (e) {
+			if (e instanceof Error) throw e
+			else return { status: 'error', error: e as any }
+		}
+	},
+	/**
+	 * Import an export document from `path`. Restore needs a database without
+	 * any data; merge skips rows already there. With `dry_run`, reports what
+	 * would be imported without writing anything.
+	 */
+	async importData(
+		path: string,
+		mode: ImportMode,
+		dryRun: boolean
+	): Promise<Result<ImportReport, CommandError>> {
+		try {
+			return { status: 'ok', data: await TAURI_INVOKE('import_data', { path, mode, dryRun }) }
+		} catch (e) {
+			if (e instanceof Error) throw e
+			else return { status: 'error', error: e as any }
+		}
+	},
+}
+
+/** user-defined events **/
+
+/** user-defined constants **/
+
+/** user-defined types **/
+
+/**
+ * Activity model
+ *
+ * Represents an activity that can be associated with mood check-ins or tracked
+ * independently via activity logging. This is the single source of truth for
+ * the Activity type across all features (mood, activities, visualization).
+ *
+ * The `HexColor` newtype ensures color values are always valid hex format.
+ */
+export type Activity = {
+	id: number
+	group_id: number
+	name: string
+	/**
+	 * Optional color in validated hex format (#RGB, #RRGGBB, or #RRGGBBAA)
+	 */
+	color: HexColor | null
+	icon: string | null
+	created_at: string
+	deleted_at: string | null
+}
+/**
+ * Activity correlation with mood
+ */
+export type ActivityCorrelation = {
+	activity: Activity
+	average_mood: number
+	checkin_count: number
+}
+/**
+ * Activity frequency report showing days per week
+ */
+export type ActivityFrequency = {
+	activity_id: number
+	/**
+	 * Number of unique days with activity logs
 	 */
 	unique_days: number
 	/**
@@ -634,9 +1325,48 @@ export type ActivityGroup = {
 	id: number
 	name: string
 	description: string | null
+	/**
+	 * Default color inherited by activities created without one
+	 */
+	color: HexColor | null
+	/**
+	 * Default icon inherited by activities created without one
+	 */
+	icon: string | null
+	/**
+	 * User-defined position (None = unpositioned, listed by name after positioned groups)
+	 */
+	sort_order: number | null
+	/**
+	 * Archived groups are hidden from pickers but keep their history and reports
+	 */
+	archived_at: string | null
 	created_at: string
 	deleted_at: string | null
 }
+/**
+ * Mood for one activity group, with its activities for drill-down
+ */
+export type ActivityGroupMoodData = {
+	group_id: number
+	group_name: string
+	comparison: MoodComparison
+	activities: ActivityMoodComparison[]
+}
+/**
+ * Activity logs per day for one activity group
+ */
+export type ActivityGroupTimelineSeries = { group_id: number; group_name: string; counts: number[] }
+/**
+ * An activity whose check-ins averaged above the week's mood
+ */
+export type ActivityInsight = {
+	activity_id: number
+	activity_name: string
+	average_mood: number
+	checkin_count: number
+	difference: number
+}
 /**
  * Activity Log model
  */
@@ -648,6 +1378,10 @@ export type ActivityLog = {
 	notes: string | null
 	deleted_at: string | null
 }
+/**
+ * Mood for one activity within its group
+ */
+export type ActivityMoodComparison = { activity: Activity; comparison: MoodComparison }
 /**
  * Activity-specific mood data for correlation analysis
  */
@@ -682,6 +1416,111 @@ export type ActivityTrend = {
 	 */
 	trend: Trend
 }
+/**
+ * A stored rule; lower `position` is checked first
+ */
+export type AdaptiveRule = {
+	id: number
+	schedule_id: number
+	position: number
+	comparison: SeverityComparison
+	severity_level: SeverityLevel
+	consecutive_count: number
+	frequency: ScheduleFrequency
+	day_of_week: number | null
+	day_of_month: number | null
+}
+/**
+ * A rule as submitted when replacing a schedule's rules
+ */
+export type AdaptiveRuleRequest = {
+	comparison: SeverityComparison
+	severity_level: SeverityLevel
+	consecutive_count: number
+	frequency: ScheduleFrequency
+	day_of_week: number | null
+	day_of_month: number | null
+}
+/**
+ * Plan-versus-actual adherence for an activity or a group
+ */
+export type AdherenceReport = {
+	/**
+	 * Set for activity reports
+	 */
+	activity_id: number | null
+	/**
+	 * Set for group reports
+	 */
+	group_id: number | null
+	/**
+	 * Start of analysis period (ISO 8601)
+	 */
+	period_start: string
+	/**
+	 * End of analysis period (ISO 8601)
+	 */
+	period_end: string
+	/**
+	 * Number of planned occurrences in the period
+	 */
+	planned: number
+	/**
+	 * Occurrences matched by a log
+	 */
+	done: number
+	/**
+	 * Occurrences whose window passed without a log
+	 */
+	missed: number
+	/**
+	 * Occurrences whose window is still open
+	 */
+	pending: number
+	/**
+	 * Logs in the period that did not match any planned occurrence
+	 */
+	unplanned: number
+	/**
+	 * done / (done + missed) * 100 (0 when nothing is due yet)
+	 */
+	adherence_percentage: number
+	/**
+	 * Every occurrence in chronological order
+	 */
+	occurrences: PlannedOccurrence[]
+}
+/**
+ * Result of applying a template
+ */
+export type AppliedGoalTemplate = {
+	group: ActivityGroup
+	/**
+	 * True when the group was created, false when an existing group was reused
+	 */
+	group_created: boolean
+	/**
+	 * Template activities, in template order (existing ones are reused)
+	 */
+	activities: Activity[]
+	/**
+	 * Template goals, in template order (identical active goals are reused)
+	 */
+	goals: ActivityGoal[]
+}
+/**
+ * Request to apply a goal template
+ *
+ * Exactly one of `template_key` (built-in) or `template_id` (saved) must be set.
+ */
+export type ApplyGoalTemplateRequest = {
+	template_key: string | null
+	template_id: number | null
+	/**
+	 * Overrides the template's group name
+	 */
+	group_name: string | null
+}
 /**
  * Assessment chart data with thresholds and statistics
  */
@@ -690,6 +1529,7 @@ export type AssessmentChartData = {
 	data_points: ChartDataPoint[]
 	thresholds: ThresholdLine[]
 	statistics: ChartStatistics
+	derived_series: DerivedSeries[]
 }
 /**
  * Assessment code identifying the assessment type
@@ -717,6 +1557,16 @@ export type AssessmentCode =
 	 * 5 questions, score 0-20
 	 */
 	| 'OASIS'
+/**
+ * Assessment statistics per period; None for a period without assessments
+ */
+export type AssessmentPeriodComparison = {
+	assessment_type_code: string
+	assessment_type_name: string
+	current: ChartStatistics | null
+	previous: ChartStatistics | null
+	average_change: number | null
+}
 /**
  * Assessment question
  */
@@ -735,18 +1585,34 @@ export type AssessmentResponse = {
 	status: AssessmentStatus
 }
 /**
- * Assessment schedule configuration
+ * Reminder schedule configuration
+ *
+ * The `assessment_type_*` fields are only set for assessment targets; `target_name`
+ * is the display label for every kind (assessment, activity or group name, or
+ * the habit text).
+ *
+ * Occurrences come from `rrule` (an RFC 5545 RRULE value) anchored at `dtstart`,
+ * minus `exdates`, all in local time of `timezone`. For daily/weekly/biweekly/
+ * monthly schedules the rule is derived from the simple fields; for custom
+ * schedules `time_of_day` is the rule's earliest time, for display.
  */
 export type AssessmentSchedule = {
 	id: number
-	assessment_type_id: number
-	assessment_type_code: string
-	assessment_type_name: string
+	target: ScheduleTarget
+	target_name: string
+	assessment_type_id: number | null
+	assessment_type_code: string | null
+	assessment_type_name: string | null
 	frequency: ScheduleFrequency
 	time_of_day: string
 	day_of_week: number | null
 	day_of_month: number | null
+	rrule: string
+	dtstart: string
+	exdates: string[]
+	timezone: string
 	enabled: boolean
+	missed_policy: MissedReminderPolicy
 	last_triggered_at: string | null
 	created_at: string
 	updated_at: string
@@ -757,6 +1623,19 @@ export type AssessmentSchedule = {
  * Draft assessments have incomplete responses; completed have full responses with scores.
  */
 export type AssessmentStatus = 'draft' | 'completed'
+/**
+ * Daily assessment scores normalised to the type's maximum (0-1)
+ */
+export type AssessmentTimelineSeries = {
+	assessment_type_code: string
+	assessment_type_name: string
+	max_score: number
+	values: (number | null)[]
+	/**
+	 * Against daily mood; None below MIN_CORRELATION_DAYS or if either side never varies
+	 */
+	mood_correlation: MoodCorrelation | null
+}
 /**
  * Assessment type (PHQ-9, GAD-7, CES-D, OASIS)
  */
@@ -772,10 +1651,30 @@ export type AssessmentType = {
 	min_score: number
 	max_score: number
 }
+/**
+ * Per-day aggregates for a range of local calendar days
+ */
+export type CalendarHeatmap = {
+	timezone: string
+	days: HeatmapDay[]
+	activity_groups: HeatmapActivityGroup[]
+}
+/**
+ * Outcome of importing an iCalendar file
+ */
+export type CalendarImportResult = {
+	imported: AssessmentSchedule[]
+	imported_plans: PlannedActivity[]
+	skipped: SkippedCalendarEvent[]
+}
 /**
  * Chart data point for time-series visualization
  */
 export type ChartDataPoint = { timestamp: string; value: number; label: string | null }
+/**
+ * Output format of a rendered chart
+ */
+export type ChartImageFormat = 'svg' | 'png'
 /**
  * Chart statistics (min, max, average, trend)
  */
@@ -784,8 +1683,18 @@ export type ChartStatistics = {
 	max: number
 	average: number
 	trend: TrendDirection
+	trend_analysis: TrendAnalysis
 	total_assessments: number
 }
+/**
+ * Aligned daily series for a date range
+ */
+export type CombinedTimeline = {
+	days: string[]
+	mood: (number | null)[]
+	assessments: AssessmentTimelineSeries[]
+	activity_groups: ActivityGroupTimelineSeries[]
+}
 /**
  * Structured error response for Tauri commands that provides:
  * - Human-readable error message
@@ -830,16 +1739,101 @@ export type CreateActivityRequest = {
 	icon: string | null
 	group_id: number
 }
+/**
+ * Request to plan an activity
+ */
+export type CreatePlannedActivityRequest = {
+	activity_id: number
+	/**
+	 * Weekdays (0 = Sunday, 6 = Saturday)
+	 */
+	days_of_week: number[]
+	/**
+	 * HH:MM in `timezone`
+	 */
+	time_of_day: string
+	/**
+	 * IANA name, defaults to the system timezone
+	 */
+	timezone: string | null
+	/**
+	 * Defaults to 1 (every week)
+	 */
+	interval_weeks: number | null
+	/**
+	 * Defaults to DEFAULT_PLAN_TOLERANCE_MINUTES
+	 */
+	tolerance_minutes: number | null
+	/**
+	 * YYYY-MM-DD
+	 */
+	start_date: string
+	/**
+	 * YYYY-MM-DD
+	 */
+	end_date: string | null
+	reminder_enabled: boolean
+	reminder_minutes_before: number | null
+}
 /**
  * Request to create a new schedule
  */
 export type CreateScheduleRequest = {
-	assessment_type_id: number
+	/**
+	 * Shorthand for an assessment target; use `target` for any other kind
+	 */
+	assessment_type_id: number | null
+	target: ScheduleTarget | null
 	frequency: ScheduleFrequency
 	time_of_day: string
 	day_of_week: number | null
 	day_of_month: number | null
+	timezone: string | null
+	/**
+	 * RRULE for custom schedules (required for, and only allowed with, `custom`)
+	 */
+	rrule: string | null
+	/**
+	 * Local start ("YYYY-MM-DD" or "YYYY-MM-DDTHH:MM:SS"), defaults to today
+	 */
+	dtstart: string | null
+	/**
+	 * Local dates ("YYYY-MM-DD", every occurrence that day) or date-times to skip
+	 */
+	exdates: string[] | null
+	/**
+	 * Defaults to coalescing missed occurrences into one notification
+	 */
+	missed_policy: MissedReminderPolicy | null
+}
+/**
+ * Resolved bounds of a period, as UTC timestamps in the stored format (both inclusive)
+ */
+export type DateBounds = { from_date: string; to_date: string }
+/**
+ * Average mood of one local day
+ */
+export type DayMood = { date: string; average_mood: number; checkin_count: number }
+/**
+ * A named series next to a chart's raw data points
+ */
+export type DerivedSeries = {
+	kind: DerivedSeriesKind
+	name: string
+	/**
+	 * One point per local day with data, timestamped at the start of the day
+	 */
+	data_points: ChartDataPoint[]
 }
+/**
+ * A series derived from the raw data points of a chart
+ */
+export type DerivedSeriesKind =
+	| 'daily_mean'
+	| 'rolling_average_7_day'
+	| 'rolling_average_30_day'
+	| 'exponential_moving_average_7_day'
+	| 'exponential_moving_average_30_day'
 /**
  * Error type enumeration for type-safe error classification
  *
@@ -862,6 +1856,40 @@ export type ErrorType =
 	| 'config'
 	| 'io_error'
 	| 'serialization'
+/**
+ * What an export wrote
+ */
+export type ExportSummary = {
+	schema_version: number
+	app_version: string
+	exported_at: string
+	tables: TableRowCount[]
+}
+/**
+ * A frequency change made by an adaptive rule
+ */
+export type FrequencyChange = {
+	id: number
+	schedule_id: number
+	rule_id: number | null
+	from_frequency: ScheduleFrequency
+	to_frequency: ScheduleFrequency
+	from_rrule: string
+	to_rrule: string
+	reason: string
+	assessment_response_id: number | null
+	changed_at: string
+}
+/**
+ * Progress of one goal at the end of the week
+ */
+export type GoalInsight = {
+	goal_id: number
+	target_name: string
+	current_value: number
+	target_value: number
+	is_achieved: boolean
+}
 /**
  * Goal progress report showing current vs target values
  */
@@ -892,6 +1920,35 @@ export type GoalProgress = {
 	 */
 	period_end: string
 }
+/**
+ * A goal template, either from the built-in catalogue or saved by the user
+ */
+export type GoalTemplate = {
+	/**
+	 * Saved template ID (None for built-in templates)
+	 */
+	id: number | null
+	/**
+	 * Stable key of a built-in template (None for saved templates)
+	 */
+	key: string | null
+	name: string
+	description: string | null
+	built_in: boolean
+	definition: GoalTemplateDefinition
+	created_at: string | null
+}
+/**
+ * Everything a template creates: one group, its activities and their goals
+ */
+export type GoalTemplateDefinition = {
+	group_name: string
+	group_description: string | null
+	group_color: HexColor | null
+	group_icon: string | null
+	activities: TemplateActivity[]
+	goals: TemplateGoal[]
+}
 /**
  * Goal type for activity tracking
  *
@@ -899,15 +1956,108 @@ export type GoalProgress = {
  */
 export type GoalType =
 	/**
-	 * Track frequency: achieve X days within a Y-day period
-	 * Example: "Exercise 3 days per 7-day period"
+	 * Track frequency: achieve X days within a Y-day period
+	 * Example: "Exercise 3 days per 7-day period"
+	 */
+	| 'days_per_period'
+	/**
+	 * Track improvement: increase activity by X% over baseline
+	 * Example: "Increase meditation by 20% over 30-day baseline"
+	 */
+	| 'percent_improvement'
+/**
+ * Group frequency report: days on which any activity in the group was logged
+ */
+export type GroupFrequency = {
+	group_id: number
+	/**
+	 * Number of unique days with a log for any non-deleted activity in the group
+	 */
+	unique_days: number
+	/**
+	 * Total number of activity logs across the group
+	 */
+	total_logs: number
+	/**
+	 * Average days per week: (unique_days / num_weeks)
+	 */
+	days_per_week: number
+	/**
+	 * Start of analysis period (ISO 8601)
+	 */
+	period_start: string
+	/**
+	 * End of analysis period (ISO 8601)
+	 */
+	period_end: string
+}
+/**
+ * Group trend report comparing current vs previous period
+ */
+export type GroupTrend = {
+	group_id: number
+	/**
+	 * Unique days in current period
+	 */
+	current_period_days: number
+	/**
+	 * Unique days in previous period (same duration)
+	 */
+	previous_period_days: number
+	/**
+	 * Difference: current - previous
+	 */
+	change_days: number
+	/**
+	 * Percentage change: ((current - previous) / previous) * 100
+	 */
+	change_percentage: number
+	/**
+	 * Trend classification based on change_percentage
+	 */
+	trend: Trend
+}
+/**
+ * One row of the all-groups weekly summary
+ */
+export type GroupWeeklySummary = {
+	group_id: number
+	group_name: string
+	/**
+	 * Unique days with any activity in the group during the last 7 days
+	 */
+	days_per_week: number
+	/**
+	 * Unique days during the 7 days before that
 	 */
-	| 'days_per_period'
+	previous_week_days: number
 	/**
-	 * Track improvement: increase activity by X% over baseline
-	 * Example: "Increase meditation by 20% over 30-day baseline"
+	 * Difference: days_per_week - previous_week_days
 	 */
-	| 'percent_improvement'
+	change_days: number
+	/**
+	 * Percentage change against the previous week
+	 */
+	change_percentage: number
+	/**
+	 * Trend classification based on change_percentage
+	 */
+	trend: Trend
+}
+/**
+ * Activity group referenced by heatmap days
+ */
+export type HeatmapActivityGroup = { id: number; name: string }
+/**
+ * Aggregates for one local day; days without data have no mood and zero counts
+ */
+export type HeatmapDay = {
+	date: string
+	average_mood: number | null
+	checkin_count: number
+	activity_group_ids: number[]
+	assessment_completed: boolean
+}
 /**
  * Validated hex color string
  *
@@ -917,6 +2067,20 @@ export type GoalType =
  * - #RRGGBBAA (9 chars, e.g., #FF000080 for semi-transparent red)
  */
 export type HexColor = string
+/**
+ * How an import treats data already in the database
+ */
+export type ImportMode = 'restore' | 'merge'
+/**
+ * What an import did, or would do in a dry run
+ */
+export type ImportReport = {
+	mode: ImportMode
+	dry_run: boolean
+	schema_version: number
+	app_version: string
+	tables: TableImportCount[]
+}
 /**
  * Request to log an activity
  */
@@ -929,13 +2093,50 @@ export type LogActivityRequest = {
  * Request to log a mood check-in
  */
 export type LogMoodRequest = { mood_rating: number; activity_ids: number[]; notes: string | null }
+/**
+ * An occurrence that fell due while the app was closed
+ */
+export type MissedReminder = {
+	id: number
+	schedule_id: number
+	target: ScheduleTarget
+	target_name: string
+	scheduled_for: string
+	local_time: string
+	policy: MissedReminderPolicy
+	reminder_event_id: number | null
+	detected_at: string
+	acknowledged_at: string | null
+}
+/**
+ * Reminders of one schedule missed during the week
+ */
+export type MissedReminderInsight = { schedule_id: number; target_name: string; count: number }
+/**
+ * What to do with occurrences that fell due while the app was closed
+ */
+export type MissedReminderPolicy =
+	/**
+	 * One catch-up notification covering all missed occurrences
+	 */
+	| 'coalesce'
+	/**
+	 * One catch-up notification per missed occurrence
+	 */
+	| 'fire_all'
+	/**
+	 * No notification; the occurrences are only recorded as missed
+	 */
+	| 'skip'
 /**
  * Mood chart data with activity breakdown
  */
 export type MoodChartData = {
 	data_points: ChartDataPoint[]
 	activity_breakdown: ActivityMoodData[]
+	group_breakdown: ActivityGroupMoodData[]
 	statistics: MoodStatistics
+	derived_series: DerivedSeries[]
 }
 /**
  * Mood check-in model
@@ -953,6 +2154,41 @@ export type MoodCheckin = {
 	activities: Activity[]
 	created_at: string
 }
+/**
+ * Mood with a group or activity against mood without it
+ */
+export type MoodComparison = {
+	checkin_count: number
+	average_mood: number
+	baseline_mood: number | null
+	difference: number | null
+	days_with: number
+	days_without: number
+	days_with_average: number | null
+	days_without_average: number | null
+	days_difference: number | null
+}
+/**
+ * Correlation between daily mood and a daily assessment score
+ */
+export type MoodCorrelation = { pearson: number; spearman: number; paired_days: number }
+/**
+ * Mood this week against the week before
+ */
+export type MoodInsight = {
+	average_mood: number | null
+	checkin_count: number
+	previous_average_mood: number | null
+	change: number | null
+}
+/**
+ * Mood statistics per period; None for a period without check-ins
+ */
+export type MoodPeriodComparison = {
+	current: MoodStatistics | null
+	previous: MoodStatistics | null
+	average_change: number | null
+}
 /**
  * Mood rating on a 7-point scale
  *
@@ -977,6 +2213,7 @@ export type MoodStatistics = {
 	mode: number
 	total_checkins: number
 	checkins_per_day: number
+	trend_analysis: TrendAnalysis
 }
 /**
  * Mood statistics
@@ -984,13 +2221,221 @@ export type MoodStatistics = {
 export type MoodStats = {
 	average_mood: number
 	total_checkins: number
-	mood_distribution: { [key in number]: number }
+	mood_distribution: Partial<{ [key in number]: number }>
 	activity_correlations: ActivityCorrelation[]
 }
+/**
+ * Notification preferences, as stored in `AppConfig`
+ */
+export type NotificationSettings = {
+	notification_enabled: boolean
+	quiet_hours: QuietHoursWindow[]
+	quiet_time_policy: QuietTimePolicy
+	paused_until: string | null
+}
+/**
+ * Mood and assessment statistics for two periods side by side
+ */
+export type PeriodComparison = {
+	current_period: DateBounds
+	previous_period: DateBounds
+	mood: MoodPeriodComparison
+	assessments: AssessmentPeriodComparison[]
+}
+/**
+ * Planned (intended) activity with weekly recurrence
+ *
+ * Describes when the user intends to do an activity, e.g. "Yoga on Tue/Thu at 07:00".
+ * Occurrences are expanded from `start_date` on the given weekdays every
+ * `interval_weeks` weeks, and matched against `activity_logs` within
+ * `tolerance_minutes` of the planned time.
+ *
+ * Days, dates and the time of day are wall-clock values in `timezone`, like
+ * assessment schedules; expanded occurrences are reported in UTC.
+ */
+export type PlannedActivity = {
+	id: number
+	activity_id: number
+	/**
+	 * Weekdays the activity is planned on (0 = Sunday, 6 = Saturday)
+	 */
+	days_of_week: number[]
+	/**
+	 * Planned time of day (HH:MM)
+	 */
+	time_of_day: string
+	/**
+	 * IANA timezone the days and time of day are local to
+	 */
+	timezone: string
+	/**
+	 * Repeat every N weeks (1 = weekly, 2 = every other week)
+	 */
+	interval_weeks: number
+	/**
+	 * How far (in minutes) a log may be from the planned time and still count
+	 */
+	tolerance_minutes: number
+	/**
+	 * First day of the plan (YYYY-MM-DD)
+	 */
+	start_date: string
+	/**
+	 * Last day of the plan (YYYY-MM-DD, None = open-ended)
+	 */
+	end_date: string | null
+	/**
+	 * Send a reminder through the scheduler before each occurrence
+	 */
+	reminder_enabled: boolean
+	/**
+	 * Minutes before the planned time to send the reminder
+	 */
+	reminder_minutes_before: number
+	last_reminded_at: string | null
+	created_at: string
+	deleted_at: string | null
+}
+/**
+ * A single expanded occurrence of a plan and how it was matched
+ */
+export type PlannedOccurrence = {
+	planned_activity_id: number
+	activity_id: number
+	/**
+	 * Planned time (ISO 8601, UTC)
+	 */
+	scheduled_at: string
+	status: PlannedOccurrenceStatus
+	/**
+	 * Activity log that fulfilled this occurrence
+	 */
+	matched_log_id: number | null
+	matched_logged_at: string | null
+}
+/**
+ * Outcome of a single planned occurrence
+ */
+export type PlannedOccurrenceStatus =
+	/**
+	 * A matching activity log was found within the tolerance window
+	 */
+	| 'done'
+	/**
+	 * The tolerance window has passed without a matching log
+	 */
+	| 'missed'
+	/**
+	 * The tolerance window has not closed yet
+	 */
+	| 'pending'
+/**
+ * Request to list upcoming occurrences of a (possibly unsaved) rule
+ */
+export type PreviewOccurrencesRequest = {
+	rrule: string
+	dtstart: string | null
+	timezone: string | null
+	exdates: string[] | null
+	count: number
+}
+/**
+ * A do-not-disturb window starting on one weekday
+ */
+export type QuietHoursWindow = { weekday: number; start: string; end: string }
+/**
+ * What happens to notifications that fall due during quiet time
+ */
+export type QuietTimePolicy =
+	/**
+	 * Hold them and send them when quiet time ends
+	 */
+	| 'defer'
+	/**
+	 * Don't send them; schedules move on to their next occurrence
+	 */
+	| 'drop'
+/**
+ * How well the reminders of one schedule were followed
+ */
+export type ReminderAdherence = {
+	schedule_id: number
+	target_name: string
+	reminders_fired: number
+	reminders_completed: number
+	reminders_snoozed: number
+	reminders_dismissed: number
+	completion_rate: number
+	median_minutes_to_completion: number | null
+}
+/**
+ * One entry of the reminder event log
+ *
+ * `related_event_id` is the original `fired` event the entry belongs to; it is
+ * `None` only for original reminders. A re-fire after a snooze is a `fired`
+ * event with `related_event_id` set.
+ */
+export type ReminderEvent = {
+	id: number
+	schedule_id: number
+	event_type: ReminderEventType
+	occurred_at: string
+	snooze_until: string | null
+	related_event_id: number | null
+	assessment_response_id: number | null
+}
+/**
+ * What happened to a reminder notification
+ */
+export type ReminderEventType = 'fired' | 'snoozed' | 'dismissed' | 'completed'
+/**
+ * Request to save an existing group's setup as a template
+ */
+export type SaveGoalTemplateRequest = { group_id: number; name: string; description: string | null }
 /**
  * Schedule frequency options
  */
-export type ScheduleFrequency = 'daily' | 'weekly' | 'biweekly' | 'monthly'
+export type ScheduleFrequency =
+	| 'daily'
+	| 'weekly'
+	| 'biweekly'
+	| 'monthly'
+	/**
+	 * Any other pattern, described by the schedule's `rrule`
+	 */
+	| 'custom'
+/**
+ * One upcoming occurrence of a schedule
+ */
+export type ScheduleOccurrence = { local_time: string; utc_time: string }
+/**
+ * What a reminder schedule asks the user to do
+ */
+export type ScheduleTarget =
+	/**
+	 * Complete an assessment (PHQ-9, GAD-7, ...)
+	 */
+	| { kind: 'assessment'; assessment_type_id: number }
+	/**
+	 * Record a mood check-in
+	 */
+	| { kind: 'mood_checkin' }
+	/**
+	 * Log a specific activity ("log your walk")
+	 */
+	| { kind: 'activity'; activity_id: number }
+	/**
+	 * Log any activity of a group
+	 */
+	| { kind: 'activity_group'; activity_group_id: number }
+	/**
+	 * Free-text habit reminder, nothing to log
+	 */
+	| { kind: 'habit'; text: string }
+/**
+ * Number of results with a score in [min_score, max_score]
+ */
+export type ScoreBin = { min_score: number; max_score: number; count: number }
 /**
  * Request to set an activity goal
  */
@@ -1004,6 +2449,69 @@ export type SetActivityGoalRequest = {
 	target_value: number
 	period_days: number
 }
+/**
+ * Request to set the default color and icon of an activity group.
+ * `None` clears the corresponding default.
+ */
+export type SetActivityGroupAppearanceRequest = {
+	/**
+	 * Color validated on deserialization via HexColor newtype
+	 */
+	color: HexColor | null
+	icon: string | null
+}
+/**
+ * An assessment whose severity level changed during the week
+ */
+export type SeverityChange = {
+	assessment_type_code: string
+	assessment_type_name: string
+	previous_severity: SeverityLevel
+	current_severity: SeverityLevel
+	previous_score: number
+	current_score: number
+	improved: boolean | null
+}
+/**
+ * How a result's severity is compared with a rule's threshold
+ */
+export type SeverityComparison =
+	/**
+	 * The threshold or worse
+	 */
+	| 'at_least'
+	/**
+	 * The threshold or better
+	 */
+	| 'at_most'
+/**
+ * Severity time shares, score histogram and episodes for one assessment type
+ */
+export type SeverityDistribution = {
+	assessment_type: AssessmentType
+	from_date: string
+	to_date: string
+	tracked_days: number
+	untracked_days: number
+	severity_shares: SeverityShare[]
+	score_histogram: ScoreBin[]
+	episode_severity: SeverityLevel
+	episodes: SeverityEpisode[]
+	longest_episode_days: number | null
+	average_episode_days: number | null
+}
+/**
+ * A run of results at or above the episode severity
+ */
+export type SeverityEpisode = {
+	start: string
+	end: string
+	days: number
+	ongoing: boolean
+	peak_severity: SeverityLevel
+	peak_score: number
+	result_count: number
+}
 /**
  * Severity level for assessment scores
  *
@@ -1017,6 +2525,109 @@ export type SeverityLevel =
 	| 'moderately_severe'
 	| 'severe'
 	| 'unknown'
+/**
+ * Time spent at one severity level
+ */
+export type SeverityShare = { severity: SeverityLevel; days: number; share: number }
+/**
+ * A VEVENT that was not imported, and why
+ */
+export type SkippedCalendarEvent = { uid: string | null; summary: string | null; reason: string }
+/**
+ * Streak report for an activity, activity group, or mood check-ins
+ */
+export type Streak = {
+	kind: StreakKind
+	/**
+	 * Activity or group ID (None for mood check-ins)
+	 */
+	target_id: number | null
+	/**
+	 * Rest days allowed between active days without breaking the streak
+	 */
+	grace_days: number
+	/**
+	 * Day the streak was evaluated at (YYYY-MM-DD)
+	 */
+	as_of: string
+	/**
+	 * Run that is still alive as of `as_of` (None if the streak is broken)
+	 */
+	current: StreakRun | null
+	/**
+	 * Longest run ever recorded (most recent wins on ties)
+	 */
+	longest: StreakRun | null
+	/**
+	 * Most recent active day (YYYY-MM-DD)
+	 */
+	last_active_date: string | null
+}
+/**
+ * What a streak is measured over
+ */
+export type StreakKind =
+	/**
+	 * Days with at least one log of a single activity
+	 */
+	| 'activity'
+	/**
+	 * Days with at least one log of any (non-deleted) activity in a group
+	 */
+	| 'activity_group'
+	/**
+	 * Days with at least one mood check-in
+	 */
+	| 'mood_checkin'
+/**
+ * A milestone reached by a current streak
+ */
+export type StreakMilestone = {
+	id: number
+	kind: StreakKind
+	target_id: number | null
+	/**
+	 * Display name of the activity/group ("Mood check-in" for mood)
+	 */
+	target_name: string
+	/**
+	 * Milestone length in active days (one of STREAK_MILESTONES)
+	 */
+	milestone: number
+	/**
+	 * Start date of the run that reached the milestone (YYYY-MM-DD)
+	 */
+	streak_start_date: string
+	achieved_at: string
+	/**
+	 * When a notification was sent (None if not yet notified)
+	 */
+	notified_at: string | null
+}
+/**
+ * A single run of active days
+ *
+ * Grace days inside the run are not counted in `length` and do not appear
+ * in `dates`; they only keep the run from breaking.
+ */
+export type StreakRun = {
+	/**
+	 * First active day of the run (YYYY-MM-DD)
+	 */
+	start_date: string
+	/**
+	 * Last active day of the run (YYYY-MM-DD)
+	 */
+	end_date: string
+	/**
+	 * Number of active days in the run
+	 */
+	length: number
+	/**
+	 * Every active day in the run, oldest first (YYYY-MM-DD)
+	 */
+	dates: string[]
+}
 /**
  * Request to submit assessment
  */
@@ -1026,14 +2637,50 @@ export type SubmitAssessmentRequest = {
 	notes: string | null
 	status?: AssessmentStatus
 }
+/**
+ * Rows of one table added, and rows skipped as already present
+ */
+export type TableImportCount = { table: string; imported: number; duplicates: number }
+export type TableRowCount = { table: string; rows: number }
+/**
+ * An activity created by a template
+ */
+export type TemplateActivity = { name: string; color: HexColor | null; icon: string | null }
+/**
+ * A goal created by a template
+ */
+export type TemplateGoal = {
+	/**
+	 * Name of the template activity this goal targets (None = goal on the whole group)
+	 */
+	activity_name: string | null
+	goal_type: GoalType
+	target_value: number
+	period_days: number
+}
 /**
  * Threshold line for severity level visualization
  */
 export type ThresholdLine = { label: string; value: number; color: string }
 /**
  * Time range for chart data queries
+ *
+ * Rolling ranges end now. Calendar ranges follow local calendar boundaries
+ * (weeks start on Monday) in the user's timezone.
  */
-export type TimeRange = 'week' | 'month' | 'quarter' | 'year' | 'alltime' | 'custom'
+export type TimeRange =
+	| 'week'
+	| 'month'
+	| 'quarter'
+	| 'year'
+	| 'alltime'
+	| 'custom'
+	| 'thisweek'
+	| 'lastweek'
+	| 'thismonth'
+	| 'lastmonth'
+	| 'yeartodate'
+	| 'samemonthlastyear'
 /**
  * Trend classification for activity analysis
  */
@@ -1051,7 +2698,26 @@ export type Trend =
 	 */
 	| 'Stable'
 /**
- * Trend direction for assessment scores
+ * Trend of a series over its whole range
+ */
+export type TrendAnalysis = {
+	direction: TrendDirection
+	/**
+	 * Theil–Sen slope in score points per week; None without enough data
+	 */
+	slope_per_week: number | null
+	/**
+	 * Confidence (0-1) that the series moves at all, from the Mann–Kendall test
+	 */
+	confidence: number
+	/**
+	 * Whether there were enough points over enough time to judge a trend
+	 */
+	sufficient_data: boolean
+	points_used: number
+}
+/**
+ * Trend direction for assessment scores and mood
  */
 export type TrendDirection = 'improving' | 'worsening' | 'stable'
 /**
@@ -1069,6 +2735,24 @@ export type UpdateActivityRequest = {
 	color: HexColor | null
 	icon: string | null
 }
+/**
+ * Request to update a planned activity
+ */
+export type UpdatePlannedActivityRequest = {
+	days_of_week: number[] | null
+	time_of_day: string | null
+	timezone: string | null
+	interval_weeks: number | null
+	tolerance_minutes: number | null
+	start_date: string | null
+	end_date: string | null
+	/**
+	 * Remove the end date, making the plan open-ended (can't be combined with `end_date`)
+	 */
+	clear_end_date?: boolean
+	reminder_enabled: boolean | null
+	reminder_minutes_before: number | null
+}
 /**
  * Request to update an existing schedule
  */
@@ -1077,7 +2761,35 @@ export type UpdateScheduleRequest = {
 	time_of_day: string | null
 	day_of_week: number | null
 	day_of_month: number | null
+	timezone: string | null
+	target: ScheduleTarget | null
 	enabled: boolean | null
+	/**
+	 * Setting an rrule without a frequency switches the schedule to `custom`
+	 */
+	rrule: string | null
+	dtstart: string | null
+	exdates: string[] | null
+	missed_policy: MissedReminderPolicy | null
+}
+/**
+ * Insights for one completed week, Monday to Sunday in the local timezone
+ */
+export type WeeklyDigest = {
+	id: number
+	week_start: string
+	week_end: string
+	timezone: string
+	mood: MoodInsight
+	best_day: DayMood | null
+	worst_day: DayMood | null
+	top_activities: ActivityInsight[]
+	goals: GoalInsight[]
+	missed_reminders: MissedReminderInsight[]
+	severity_changes: SeverityChange[]
+	sentences: string[]
+	generated_at: string
+	notified_at: string | null
 }
 
 /** tauri-specta globals **/
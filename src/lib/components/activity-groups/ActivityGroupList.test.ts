@@ -45,6 +45,10 @@ describe('ActivityGroupList', () => {
 			id: 1,
 			name: 'Exercise',
 			description: 'Physical activities',
+			color: null,
+			icon: null,
+			sort_order: null,
+			archived_at: null,
 			created_at: '2024-01-01T00:00:00Z',
 			deleted_at: null,
 		},
@@ -52,6 +56,10 @@ describe('ActivityGroupList', () => {
 			id: 2,
 			name: 'Social',
 			description: 'Social interactions',
+			color: null,
+			icon: null,
+			sort_order: null,
+			archived_at: null,
 			created_at: '2024-01-02T00:00:00Z',
 			deleted_at: null,
 		},
@@ -209,6 +217,25 @@ describe('ActivityGroupList', () => {
 			})
 		})
 
+		it('should expand the focused group', async () => {
+			const onEdit = vi.fn()
+			const onDelete = vi.fn()
+
+			render(ActivityGroupList, {
+				props: {
+					groups: mockGroups,
+					onEdit,
+					onDelete,
+					focusGroupId: 2,
+				},
+			})
+
+			await waitFor(() => {
+				expect(screen.getByLabelText('Collapse group')).toBeInTheDocument()
+			})
+			expect(screen.getAllByLabelText('Expand group')).toHaveLength(1)
+		})
+
 		it('should allow multiple groups to be expanded simultaneously', async () => {
 			const onEdit = vi.fn()
 			const onDelete = vi.fn()
@@ -1,7 +1,7 @@
 import { describe, it, expect, beforeEach, vi } from 'vitest'
 import { render } from '@testing-library/svelte'
 import AssessmentChart from './AssessmentChart.svelte'
-import type { AssessmentChartData, AssessmentType } from '$lib/bindings'
+import type { AssessmentChartData, AssessmentType, TrendAnalysis } from '$lib/bindings'
 
 /**
  * Integration test for AssessmentChart component
@@ -93,6 +93,14 @@ describe('AssessmentChart Integration Test', () => {
 		max_score: 27,
 	}
 
+	const mockTrendAnalysis: TrendAnalysis = {
+		direction: 'stable',
+		slope_per_week: 0,
+		confidence: 0.9,
+		sufficient_data: true,
+		points_used: 3,
+	}
+
 	const createMockChartData = (dataPoints: number): AssessmentChartData => ({
 		assessment_type: mockAssessmentType,
 		data_points: Array.from({ length: dataPoints }, (_, i) => ({
@@ -113,7 +121,9 @@ describe('AssessmentChart Integration Test', () => {
 			average: 13.5,
 			total_assessments: dataPoints,
 			trend: 'stable',
+			trend_analysis: mockTrendAnalysis,
 		},
+		derived_series: [],
 	})
 
 	describe('End-to-End Chart Rendering', () => {
@@ -195,7 +205,15 @@ describe('AssessmentChart Integration Test', () => {
 						{ timestamp: '2025-01-08T10:00:00Z', value: 12, label: null },
 					],
 					thresholds: [],
-					statistics: { min: 10, max: 12, average: 11, total_assessments: 2, trend: 'stable' },
+					statistics: {
+						min: 10,
+						max: 12,
+						average: 11,
+						total_assessments: 2,
+						trend: 'stable',
+						trend_analysis: mockTrendAnalysis,
+					},
+					derived_series: [],
 				}
 
 				expect(() => {
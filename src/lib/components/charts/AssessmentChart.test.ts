@@ -1,7 +1,7 @@
 import { describe, it, expect, beforeEach, vi } from 'vitest'
 import { render, screen } from '@testing-library/svelte'
 import AssessmentChart from './AssessmentChart.svelte'
-import type { AssessmentChartData } from '$lib/bindings'
+import type { AssessmentChartData, TrendAnalysis } from '$lib/bindings'
 
 /**
  * Week 0 Chart.js + Svelte 5 + Tauri Validation Suite (21 tests)
@@ -67,6 +67,14 @@ describe('AssessmentChart', () => {
 		max_score: 27,
 	}
 
+	const mockTrendAnalysis: TrendAnalysis = {
+		direction: 'improving',
+		slope_per_week: -2,
+		confidence: 0.9,
+		sufficient_data: true,
+		points_used: 3,
+	}
+
 	const mockChartData: AssessmentChartData = {
 		assessment_type: mockAssessmentType,
 		data_points: [
@@ -86,7 +94,9 @@ describe('AssessmentChart', () => {
 			average: 8,
 			total_assessments: 3,
 			trend: 'improving',
+			trend_analysis: mockTrendAnalysis,
 		},
+		derived_series: [],
 	}
 
 	describe('Loading State', () => {
@@ -1,15 +1,24 @@
 import { describe, it, expect } from 'vitest'
 import { render, screen } from '@testing-library/svelte'
 import ChartStatistics from './ChartStatistics.svelte'
-import type { ChartStatistics as ChartStatisticsType } from '$lib/bindings'
+import type { ChartStatistics as ChartStatisticsType, TrendAnalysis } from '$lib/bindings'
 
 describe('ChartStatistics', () => {
+	const mockTrendAnalysis: TrendAnalysis = {
+		direction: 'improving',
+		slope_per_week: -1.5,
+		confidence: 0.9,
+		sufficient_data: true,
+		points_used: 3,
+	}
+
 	const mockStatistics: ChartStatisticsType = {
 		min: 5,
 		max: 20,
 		average: 12.5,
 		total_assessments: 10,
 		trend: 'improving',
+		trend_analysis: mockTrendAnalysis,
 	}
 
 	describe('Rendering', () => {
@@ -51,6 +60,7 @@ describe('ChartStatistics', () => {
 				average: 12.567,
 				total_assessments: 15,
 				trend: 'stable',
+				trend_analysis: mockTrendAnalysis,
 			}
 
 			render(ChartStatistics, { props: { statistics: stats } })
@@ -167,6 +177,7 @@ describe('ChartStatistics', () => {
 				average: 0,
 				total_assessments: 0,
 				trend: 'stable',
+				trend_analysis: mockTrendAnalysis,
 			}
 
 			render(ChartStatistics, { props: { statistics: stats } })
@@ -186,6 +197,7 @@ describe('ChartStatistics', () => {
 				average: 5555.5,
 				total_assessments: 10000,
 				trend: 'improving',
+				trend_analysis: mockTrendAnalysis,
 			}
 
 			render(ChartStatistics, { props: { statistics: stats } })
@@ -203,6 +215,7 @@ describe('ChartStatistics', () => {
 				average: -7.5,
 				total_assessments: 5,
 				trend: 'worsening',
+				trend_analysis: mockTrendAnalysis,
 			}
 
 			render(ChartStatistics, { props: { statistics: stats } })
@@ -1,7 +1,7 @@
 import { describe, it, expect, beforeEach, vi } from 'vitest'
 import { render, screen } from '@testing-library/svelte'
 import MoodChart from './MoodChart.svelte'
-import type { MoodChartData } from '$lib/bindings'
+import type { MoodChartData, TrendAnalysis } from '$lib/bindings'
 
 // NOTE: We no longer mock Chart.js to catch real integration bugs!
 // We only mock the Canvas API which is unavailable in Node.js test environment
@@ -50,6 +50,14 @@ describe('MoodChart', () => {
 		})) as unknown as typeof HTMLCanvasElement.prototype.getContext
 	})
 
+	const mockTrendAnalysis: TrendAnalysis = {
+		direction: 'stable',
+		slope_per_week: 0,
+		confidence: 0.9,
+		sufficient_data: true,
+		points_used: 3,
+	}
+
 	const mockMoodData: MoodChartData = {
 		data_points: [
 			{ timestamp: '2025-01-01T10:00:00Z', value: 3, label: 'Exercise' },
@@ -87,6 +95,7 @@ describe('MoodChart', () => {
 				data_points: [{ timestamp: '2025-01-02T14:30:00Z', value: 4, label: 'Reading' }],
 			},
 		],
+		group_breakdown: [],
 		statistics: {
 			min: 2,
 			max: 4,
@@ -95,7 +104,9 @@ describe('MoodChart', () => {
 			mode: 3,
 			total_checkins: 3,
 			checkins_per_day: 1.0,
+			trend_analysis: mockTrendAnalysis,
 		},
+		derived_series: [],
 	}
 
 	describe('Loading State', () => {
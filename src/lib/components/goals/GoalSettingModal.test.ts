@@ -52,6 +52,10 @@ describe('GoalSettingModal', () => {
 		id: 1,
 		name: 'Exercise',
 		description: 'Physical activities',
+		color: null,
+		icon: null,
+		sort_order: null,
+		archived_at: null,
 		created_at: '2024-01-01T00:00:00Z',
 		deleted_at: null,
 	}
@@ -9,6 +9,10 @@ describe('ActivityForm', () => {
 			id: 1,
 			name: 'Physical',
 			description: 'Physical activities',
+			color: null,
+			icon: null,
+			sort_order: null,
+			archived_at: null,
 			created_at: '2024-01-01T00:00:00Z',
 			deleted_at: null,
 		},
@@ -16,6 +20,10 @@ describe('ActivityForm', () => {
 			id: 2,
 			name: 'Mental',
 			description: 'Mental activities',
+			color: null,
+			icon: null,
+			sort_order: null,
+			archived_at: null,
 			created_at: '2024-01-01T00:00:00Z',
 			deleted_at: null,
 		},
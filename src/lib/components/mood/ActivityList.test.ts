@@ -39,6 +39,10 @@ describe('ActivityList', () => {
 			id: 1,
 			name: 'Wellness',
 			description: 'Wellness activities',
+			color: null,
+			icon: null,
+			sort_order: null,
+			archived_at: null,
 			created_at: '2024-01-01T00:00:00Z',
 			deleted_at: null,
 		},
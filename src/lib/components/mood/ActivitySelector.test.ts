@@ -45,6 +45,10 @@ describe('ActivitySelector', () => {
 			id: 1,
 			name: 'Physical Activity',
 			description: 'Exercise and movement',
+			color: null,
+			icon: null,
+			sort_order: null,
+			archived_at: null,
 			created_at: '2024-01-01',
 			deleted_at: null,
 		},
@@ -36,6 +36,8 @@ import { displayError, displaySuccess } from '$lib/utils/errors'
 const mockSchedules: AssessmentSchedule[] = [
 	{
 		id: 1,
+		target: { kind: 'assessment', assessment_type_id: 1 },
+		target_name: 'PHQ-9 Depression Scale',
 		assessment_type_id: 1,
 		assessment_type_code: 'PHQ9',
 		assessment_type_name: 'PHQ-9 Depression Scale',
@@ -43,13 +45,20 @@ const mockSchedules: AssessmentSchedule[] = [
 		time_of_day: '09:00',
 		day_of_week: null,
 		day_of_month: null,
+		rrule: 'FREQ=DAILY;BYHOUR=9;BYMINUTE=0',
+		dtstart: '2025-10-28T00:00:00',
+		exdates: [],
+		timezone: 'UTC',
 		enabled: true,
+		missed_policy: 'coalesce',
 		last_triggered_at: null,
 		created_at: '2025-10-28T10:00:00Z',
 		updated_at: '2025-10-28T10:00:00Z',
 	},
 	{
 		id: 2,
+		target: { kind: 'assessment', assessment_type_id: 2 },
+		target_name: 'GAD-7 Anxiety Scale',
 		assessment_type_id: 2,
 		assessment_type_code: 'GAD7',
 		assessment_type_name: 'GAD-7 Anxiety Scale',
@@ -57,13 +66,20 @@ const mockSchedules: AssessmentSchedule[] = [
 		time_of_day: '14:30',
 		day_of_week: 3,
 		day_of_month: null,
+		rrule: 'FREQ=WEEKLY;BYDAY=WE;BYHOUR=14;BYMINUTE=30',
+		dtstart: '2025-10-28T00:00:00',
+		exdates: [],
+		timezone: 'UTC',
 		enabled: false,
+		missed_policy: 'coalesce',
 		last_triggered_at: '2025-10-27T14:30:00Z',
 		created_at: '2025-10-20T10:00:00Z',
 		updated_at: '2025-10-27T10:00:00Z',
 	},
 	{
 		id: 3,
+		target: { kind: 'assessment', assessment_type_id: 3 },
+		target_name: 'CES-D Depression Scale',
 		assessment_type_id: 3,
 		assessment_type_code: 'CESD',
 		assessment_type_name: 'CES-D Depression Scale',
@@ -71,7 +87,12 @@ const mockSchedules: AssessmentSchedule[] = [
 		time_of_day: '10:00',
 		day_of_week: null,
 		day_of_month: 15,
+		rrule: 'FREQ=MONTHLY;BYMONTHDAY=15;BYHOUR=10;BYMINUTE=0',
+		dtstart: '2025-10-28T00:00:00',
+		exdates: [],
+		timezone: 'UTC',
 		enabled: true,
+		missed_policy: 'coalesce',
 		last_triggered_at: null,
 		created_at: '2025-10-28T10:00:00Z',
 		updated_at: '2025-10-15T10:00:00Z',
@@ -113,6 +134,29 @@ describe('ScheduleList', () => {
 		})
 	})
 
+	it('should display target names for non-assessment schedules', async () => {
+		vi.mocked(commands.getSchedules).mockResolvedValue({
+			status: 'ok',
+			data: [
+				{
+					...mockSchedules[0],
+					id: 4,
+					target: { kind: 'activity', activity_id: 7 },
+					target_name: 'Evening walk',
+					assessment_type_id: null,
+					assessment_type_code: null,
+					assessment_type_name: null,
+				},
+			],
+		})
+
+		render(ScheduleList)
+
+		await waitFor(() => {
+			expect(screen.getByText('Evening walk')).toBeInTheDocument()
+		})
+	})
+
 	it('should display empty state when no schedules', async () => {
 		vi.mocked(commands.getSchedules).mockResolvedValue({ status: 'ok', data: [] })
 